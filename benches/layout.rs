@@ -0,0 +1,20 @@
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use nablo_ui::bench::{counters, reset_counters, LayoutScene};
+
+fn relayout(c: &mut Criterion) {
+	let mut scene = LayoutScene::widget_tree(100, 99);
+	assert_eq!(scene.widgets(), 10001);
+
+	reset_counters();
+	scene.relayout();
+	let counters = counters();
+	assert_eq!(counters.layout_passes, 1);
+	assert!(counters.widgets_painted > 0 && counters.widgets_painted <= scene.widgets());
+
+	c.bench_function("relayout 10k widgets", |b| b.iter(|| black_box(scene.relayout())));
+}
+
+criterion_group!(benches, relayout);
+criterion_main!(benches);
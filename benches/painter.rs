@@ -0,0 +1,41 @@
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use nablo_ui::bench::{counters, reset_counters, PaintScene};
+
+fn parse_shapes(c: &mut Criterion) {
+	let scene = PaintScene::shapes(1000);
+
+	reset_counters();
+	let commands = scene.parse(scene.painter());
+	let counters = counters();
+	assert_eq!(counters.shapes_parsed, 1000);
+	assert_eq!(counters.commands, commands);
+	assert!(commands >= 1000);
+
+	c.bench_function("parse 1k shapes", |b| b.iter_batched(
+		|| scene.painter(),
+		|painter| black_box(scene.parse(painter)),
+		BatchSize::SmallInput,
+	));
+}
+
+fn parse_text_frame(c: &mut Criterion) {
+	let scene = PaintScene::text_frame(50);
+
+	reset_counters();
+	let commands = scene.parse(scene.painter());
+	let counters = counters();
+	// the background, a panel every 10 lines and 80 glyphs per line.
+	assert_eq!(counters.shapes_parsed, 1 + 5 + 50 * 80);
+	assert!(commands >= 50 * 80);
+
+	c.bench_function("parse text-heavy frame", |b| b.iter_batched(
+		|| scene.painter(),
+		|painter| black_box(scene.parse(painter)),
+		BatchSize::SmallInput,
+	));
+}
+
+criterion_group!(benches, parse_shapes, parse_text_frame);
+criterion_main!(benches);
@@ -0,0 +1,223 @@
+//! Derive macros for `nablo_ui`, use them through `nablo_ui` instead of depending on this crate directly.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, Data, DeriveInput, Fields};
+
+/// Implement `Signal` for a type and generate helpers for matching enum variants.
+/// 
+/// See `nablo_ui::widgets::Signal` for the details.
+#[proc_macro_derive(Signal)]
+pub fn derive_signal(input: TokenStream) -> TokenStream {
+	let input = parse_macro_input!(input as DeriveInput);
+	match expand(input) {
+		Ok(out) => out.into(),
+		Err(err) => err.to_compile_error().into(),
+	}
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+	let name = &input.ident;
+	let vis = &input.vis;
+	let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+	let signal_impl = quote! {
+		impl #impl_generics ::nablo_ui::widgets::Signal for #name #ty_generics #where_clause {}
+	};
+
+	let data = match &input.data {
+		Data::Enum(data) => data,
+		_ => return Ok(signal_impl),
+	};
+
+	let mut methods = Vec::new();
+	let mut routing_decls = Vec::new();
+	let mut routing_impls = Vec::new();
+
+	for variant in &data.variants {
+		let ident = &variant.ident;
+		let snake = snake_case(&ident.to_string());
+		let is_fn = format_ident!("is_{}", snake);
+		let is_of_fn = format_ident!("is_{}_of", snake);
+		let pattern = match &variant.fields {
+			Fields::Unit => quote! { Self::#ident },
+			Fields::Unnamed(_) => quote! { Self::#ident(..) },
+			Fields::Named(_) => quote! { Self::#ident { .. } },
+		};
+		let is_doc = format!("Returns `true` if the signal is `{}::{}`.", name, ident);
+		methods.push(quote! {
+			#[doc = #is_doc]
+			#vis fn #is_fn(&self) -> bool {
+				matches!(self, #pattern)
+			}
+		});
+		let is_of_doc = format!("Returns `true` if the signal is `{}::{}` and sent by the widget with given alias.", name, ident);
+		routing_decls.push(quote! {
+			#[doc = #is_of_doc]
+			fn #is_of_fn(&self, alias: &str) -> bool;
+		});
+		routing_impls.push(quote! {
+			fn #is_of_fn(&self, alias: &str) -> bool {
+				self.is_from(alias) && self.signal.#is_fn()
+			}
+		});
+
+		let (bindings, types, destructure) = match &variant.fields {
+			Fields::Unit => continue,
+			Fields::Unnamed(fields) => {
+				let bindings = (0..fields.unnamed.len()).map(|i| format_ident!("field_{}", i)).collect::<Vec<_>>();
+				let types = fields.unnamed.iter().map(|field| field.ty.clone()).collect::<Vec<_>>();
+				let destructure = quote! { Self::#ident(#(#bindings),*) };
+				(bindings, types, destructure)
+			},
+			Fields::Named(fields) => {
+				let bindings = fields.named.iter().map(|field| field.ident.clone().unwrap()).collect::<Vec<_>>();
+				let types = fields.named.iter().map(|field| field.ty.clone()).collect::<Vec<_>>();
+				let destructure = quote! { Self::#ident { #(#bindings),* } };
+				(bindings, types, destructure)
+			},
+		};
+		let (ref_ty, owned_ty, value) = if bindings.len() == 1 {
+			let ty = &types[0];
+			let binding = &bindings[0];
+			(quote! { &#ty }, quote! { #ty }, quote! { #binding })
+		}else {
+			(quote! { (#(&#types),*) }, quote! { (#(#types),*) }, quote! { (#(#bindings),*) })
+		};
+		let as_fn = format_ident!("as_{}", snake);
+		let into_fn = format_ident!("into_{}", snake);
+		let as_of_fn = format_ident!("as_{}_of", snake);
+		let as_doc = format!("Get the payload of `{}::{}` by reference, returns `None` for other variants.", name, ident);
+		let into_doc = format!("Take the payload of `{}::{}`, returns `None` for other variants.", name, ident);
+		let as_of_doc = format!("Get the payload of `{}::{}` if the signal is sent by the widget with given alias.", name, ident);
+		methods.push(quote! {
+			#[doc = #as_doc]
+			#vis fn #as_fn(&self) -> Option<#ref_ty> {
+				match self {
+					#destructure => Some(#value),
+					#[allow(unreachable_patterns)]
+					_ => None,
+				}
+			}
+
+			#[doc = #into_doc]
+			#vis fn #into_fn(self) -> Option<#owned_ty> {
+				match self {
+					#destructure => Some(#value),
+					#[allow(unreachable_patterns)]
+					_ => None,
+				}
+			}
+		});
+		routing_decls.push(quote! {
+			#[doc = #as_of_doc]
+			fn #as_of_fn(&self, alias: &str) -> Option<#ref_ty>;
+		});
+		routing_impls.push(quote! {
+			fn #as_of_fn(&self, alias: &str) -> Option<#ref_ty> {
+				if self.is_from(alias) {
+					self.signal.#as_fn()
+				}else {
+					None
+				}
+			}
+		});
+	}
+
+	let routing = format_ident!("{}Routing", name);
+	let routing_doc = format!("Helpers for matching [`SignalWrapper<{}>`](::nablo_ui::widgets::SignalWrapper) by the alias of its sender, generated by `#[derive(Signal)]`.", name);
+
+	Ok(quote! {
+		#signal_impl
+
+		impl #impl_generics #name #ty_generics #where_clause {
+			#(#methods)*
+		}
+
+		#[doc = #routing_doc]
+		#vis trait #routing #impl_generics #where_clause {
+			#(#routing_decls)*
+		}
+
+		impl #impl_generics #routing #ty_generics for ::nablo_ui::widgets::SignalWrapper<#name #ty_generics> #where_clause {
+			#(#routing_impls)*
+		}
+	})
+}
+
+/// `OpenFloatContainer` -> `open_float_container`, `HTTPRequest` -> `http_request`.
+/// 
+/// A run of capitals is split before its last one only if at least two capitals are left before it,
+/// so the typo `OpenFLoatContainer` still becomes `open_float_container`.
+fn snake_case(ident: &str) -> String {
+	let chars = ident.chars().collect::<Vec<_>>();
+	let mut out = String::new();
+	// how many capitals are right before the current char.
+	let mut upper_run = 0;
+	for (i, c) in chars.iter().enumerate() {
+		if c.is_uppercase() {
+			let prev_lower = i > 0 && (chars[i - 1].is_lowercase() || chars[i - 1].is_ascii_digit());
+			let next_lower = chars.get(i + 1).is_some_and(|c| c.is_lowercase());
+			if prev_lower || (upper_run >= 2 && next_lower) {
+				out.push('_');
+			}
+			upper_run += 1;
+			out.extend(c.to_lowercase());
+		}else {
+			upper_run = 0;
+			out.push(*c);
+		}
+	}
+	out
+}
+
+mod test {
+	#[test]
+	fn test_snake_case() {
+		use super::snake_case;
+
+		assert_eq!(snake_case("Clicked"), "clicked");
+		assert_eq!(snake_case("OpenFloatContainer"), "open_float_container");
+		assert_eq!(snake_case("OpenFLoatContainer"), "open_float_container");
+		assert_eq!(snake_case("HTTPRequest"), "http_request");
+		assert_eq!(snake_case("IOError"), "io_error");
+		assert_eq!(snake_case("LoadURL"), "load_url");
+		assert_eq!(snake_case("Page2Loaded"), "page2_loaded");
+	}
+
+	#[test]
+	fn test_expand_enum() {
+		use super::expand;
+
+		let input = syn::parse_str("pub enum Message { Clicked, Open(u32), Moved { x: f32, y: f32 } }").unwrap();
+		// the tokens are compared without spaces, as their spacing is not stable.
+		let out = expand(input).unwrap().to_string().replace(' ', "");
+		let expected = [
+			"impl::nablo_ui::widgets::SignalforMessage{}",
+			"pubfnis_clicked(&self)->bool{matches!(self,Self::Clicked)}",
+			"pubfnis_open(&self)->bool{matches!(self,Self::Open(..))}",
+			"pubfnas_open(&self)->Option<&u32>{matchself{Self::Open(field_0)=>Some(field_0)",
+			"pubfninto_open(self)->Option<u32>",
+			"pubfnas_moved(&self)->Option<(&f32,&f32)>{matchself{Self::Moved{x,y}=>Some((x,y))",
+			"pubtraitMessageRouting{",
+			"implMessageRoutingfor::nablo_ui::widgets::SignalWrapper<Message>",
+			"fnis_clicked_of(&self,alias:&str)->bool{self.is_from(alias)&&self.signal.is_clicked()}",
+			"fnas_open_of(&self,alias:&str)->Option<&u32>{ifself.is_from(alias){self.signal.as_open()}else{None}}",
+		];
+		for code in expected {
+			assert!(out.contains(code), "`{}` is not generated in `{}`", code, out);
+		}
+		// unit variants have no payload to get.
+		assert!(!out.contains("fnas_clicked"));
+	}
+
+	#[test]
+	fn test_expand_struct() {
+		use super::expand;
+
+		let input = syn::parse_str("struct Message<T: Clone>(T);").unwrap();
+		let out = expand(input).unwrap().to_string().replace(' ', "");
+		assert_eq!(out, "impl<T:Clone>::nablo_ui::widgets::SignalforMessage<T>{}");
+	}
+}
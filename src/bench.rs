@@ -0,0 +1,211 @@
+//! Scenarios and counters for benchmarking the layout and the painter, enabled by the `bench` feature.
+//!
+//! The scenarios run without a window or GPU, see the criterion benches in `benches/`.
+//! The counters record the work done by [`crate::layout::Layout`] and [`crate::render::painter::Painter`],
+//! so the benches can check that a scenario still does the expected amount of work before timing it:
+//!
+//! ```no_run
+//! use nablo_ui::bench::*;
+//!
+//! let mut scene = LayoutScene::widget_tree(100, 99);
+//! reset_counters();
+//! scene.relayout();
+//! assert_eq!(counters().layout_passes, 1);
+//! assert!(counters().widgets_painted > 0);
+//! ```
+
+use std::{collections::HashMap, sync::{atomic::{AtomicUsize, Ordering}, Arc, Mutex}};
+
+use crate::{layout::{Layout, ROOT_LAYOUT_ID}, math::{color::Vec4, rect::Rect, vec2::Vec2}, render::{command_cache::CommandCache, damage::Damage, font::{FontId, FontPool}, font_render::GlyphMap, painter::Painter, shape::{BasicShapeData, FillMode}}, widgets::{card::{Card, Scroll}, progress_bar::ProgressBar, styles::{BACKGROUND_COLOR, CARD_COLOR, PRIMARY_COLOR, PRIMARY_TEXT_COLOR}, SignalWrapper}, App, Context};
+
+static LAYOUT_PASSES: AtomicUsize = AtomicUsize::new(0);
+static WIDGETS_PAINTED: AtomicUsize = AtomicUsize::new(0);
+static SHAPES_PARSED: AtomicUsize = AtomicUsize::new(0);
+static COMMANDS: AtomicUsize = AtomicUsize::new(0);
+
+/// The work recorded since the last [`reset_counters`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Counters {
+	/// The number of layout passes, one per drawn frame.
+	pub layout_passes: usize,
+	/// The number of widgets drawn by the layout passes.
+	pub widgets_painted: usize,
+	/// The number of shapes parsed by the painter.
+	pub shapes_parsed: usize,
+	/// The number of gpu commands produced by parsing.
+	pub commands: usize,
+}
+
+/// Get the work recorded since the last [`reset_counters`].
+pub fn counters() -> Counters {
+	Counters {
+		layout_passes: LAYOUT_PASSES.load(Ordering::Relaxed),
+		widgets_painted: WIDGETS_PAINTED.load(Ordering::Relaxed),
+		shapes_parsed: SHAPES_PARSED.load(Ordering::Relaxed),
+		commands: COMMANDS.load(Ordering::Relaxed),
+	}
+}
+
+/// Reset all the counters to zero.
+pub fn reset_counters() {
+	for counter in [&LAYOUT_PASSES, &WIDGETS_PAINTED, &SHAPES_PARSED, &COMMANDS] {
+		counter.store(0, Ordering::Relaxed);
+	}
+}
+
+pub(crate) fn count_layout_pass() {
+	LAYOUT_PASSES.fetch_add(1, Ordering::Relaxed);
+}
+
+pub(crate) fn count_widget_painted() {
+	WIDGETS_PAINTED.fetch_add(1, Ordering::Relaxed);
+}
+
+pub(crate) fn count_parse(shapes: usize, commands: usize) {
+	SHAPES_PARSED.fetch_add(shapes, Ordering::Relaxed);
+	COMMANDS.fetch_add(commands, Ordering::Relaxed);
+}
+
+/// The app used by the scenarios, it does nothing.
+pub struct BenchApp;
+
+impl App for BenchApp {
+	type Signal = ();
+
+	fn on_start(&mut self, _: &mut Context<(), Self>) {}
+
+	fn on_signal(&mut self, _: &mut Context<(), Self>, _: SignalWrapper<()>) {}
+}
+
+/// The window size used by the scenarios.
+pub const WINDOW_SIZE: Vec2 = Vec2::new(1920.0, 1080.0);
+
+/// A layout to measure relayouts with.
+pub struct LayoutScene {
+	layout: Layout<(), BenchApp>,
+	font_pool: Arc<Mutex<FontPool>>,
+}
+
+impl LayoutScene {
+	/// A scrollable card holding `rows` horizontal cards, each holding `columns` progress bars.
+	///
+	/// `LayoutScene::widget_tree(100, 99)` builds a tree of 10001 widgets.
+	pub fn widget_tree(rows: usize, columns: usize) -> Self {
+		let mut layout = Layout::new();
+		layout.insert_root_widget(Card::new_vertical().scroll(Scroll::vertical()));
+		for _ in 0..rows {
+			let Some(row) = layout.add_widget(ROOT_LAYOUT_ID, Card::new_horizontal().scroll(Scroll::horizontal())) else { continue };
+			for column in 0..columns {
+				layout.add_widget(row, ProgressBar::new().set_progress_without_animation(column as f32 / columns as f32));
+			}
+		}
+		Self {
+			layout,
+			font_pool: Arc::new(Mutex::new(FontPool::new())),
+		}
+	}
+
+	/// Get the number of widgets in the scene.
+	pub fn widgets(&self) -> usize {
+		self.layout.widgets()
+	}
+
+	/// Mark every widget dirty and run a layout pass, returns the painter the widgets are drawn into.
+	pub fn relayout(&mut self) -> Painter {
+		let mut painter = Painter::headless(self.font_pool.clone(), WINDOW_SIZE);
+		self.layout.make_all_dirty();
+		self.layout.handle_draw(&mut painter, WINDOW_SIZE);
+		painter
+	}
+}
+
+/// Shapes to measure parsing with.
+pub struct PaintScene {
+	draw: fn(&mut Painter, usize),
+	count: usize,
+	glyphs: GlyphMap,
+	font_pool: Arc<Mutex<FontPool>>,
+}
+
+const SAMPLE_TEXT: &str = "The quick brown fox jumps over the lazy dog, 0123456789. ";
+
+impl PaintScene {
+	/// `count` rects, circles and stroked shapes spread over the window, half of them filled with gradients.
+	pub fn shapes(count: usize) -> Self {
+		Self {
+			draw: draw_shapes,
+			count,
+			glyphs: GlyphMap::new(),
+			font_pool: Arc::new(Mutex::new(FontPool::new())),
+		}
+	}
+
+	/// A frame of `lines` lines of text over panels, like an editor or a document.
+	///
+	/// Every line is 80 glyphs, glyphs are not loaded from a font but mapped to fake texture ids.
+	pub fn text_frame(lines: usize) -> Self {
+		let glyphs = SAMPLE_TEXT.chars()
+			.enumerate()
+			.map(|(index, chr)| ((chr, FontId::default()), index as u32))
+			.collect::<HashMap<_, _>>();
+		Self {
+			draw: draw_text_frame,
+			count: lines,
+			glyphs,
+			font_pool: Arc::new(Mutex::new(FontPool::new())),
+		}
+	}
+
+	/// Draw the scene into a new painter.
+	pub fn painter(&self) -> Painter {
+		let mut painter = Painter::headless(self.font_pool.clone(), WINDOW_SIZE);
+		(self.draw)(&mut painter, self.count);
+		painter
+	}
+
+	/// Parse the painter into gpu commands, returns the number of commands.
+	pub fn parse(&self, painter: Painter) -> usize {
+		painter.parse(&self.glyphs, &Damage::from(Rect::from_size(WINDOW_SIZE)), &mut CommandCache::default()).0.len()
+	}
+}
+
+fn draw_shapes(painter: &mut Painter, count: usize) {
+	let columns = (count as f32).sqrt().ceil().max(1.0) as usize;
+	let cell = WINDOW_SIZE / columns as f32;
+	for index in 0..count {
+		let lt = Vec2::new((index % columns) as f32, (index / columns) as f32) * cell;
+		let rect = Rect::from_lt_size(lt, cell * 0.8);
+		if index % 2 == 0 {
+			painter.set_fill_mode(FillMode::LinearGradient(PRIMARY_COLOR, BACKGROUND_COLOR, rect.lt(), rect.rb()));
+		}else {
+			painter.set_fill_mode(PRIMARY_COLOR);
+		}
+		match index % 4 {
+			0 => painter.draw_rect(rect, Vec4::same(4.0)),
+			1 => painter.draw_circle(rect.center(), rect.w.min(rect.h) / 2.0),
+			2 => painter.draw_stroked_rect(rect, Vec4::same(4.0), 2.0),
+			_ => painter.draw_stroked_circle(rect.center(), rect.w.min(rect.h) / 2.0, 2.0),
+		}
+	}
+}
+
+fn draw_text_frame(painter: &mut Painter, lines: usize) {
+	const LINE_HEIGHT: f32 = 20.0;
+	const GLYPH_WIDTH: f32 = 9.0;
+	painter.set_fill_mode(BACKGROUND_COLOR);
+	painter.draw_rect(Rect::from_size(WINDOW_SIZE), Vec4::same(0.0));
+	let sample = SAMPLE_TEXT.chars().collect::<Vec<_>>();
+	for line in 0..lines {
+		let y = line as f32 * LINE_HEIGHT % WINDOW_SIZE.y;
+		if line % 10 == 0 {
+			painter.set_fill_mode(CARD_COLOR);
+			painter.draw_rect(Rect::from_lt_size(Vec2::new(0.0, y), Vec2::new(WINDOW_SIZE.x, LINE_HEIGHT * 10.0)), Vec4::same(8.0));
+			painter.set_fill_mode(PRIMARY_TEXT_COLOR);
+		}
+		for column in 0..80 {
+			let chr = sample[(line + column) % sample.len()];
+			let pos = Vec2::new(column as f32 * GLYPH_WIDTH, y);
+			painter.draw_shape(BasicShapeData::Text(pos, FontId::default(), 16.0, chr));
+		}
+	}
+}
@@ -0,0 +1,74 @@
+//! The region of a widget receiving touches, see [`HitRegion`].
+
+use std::sync::Arc;
+
+use crate::math::{rect::Rect, vec2::Vec2};
+
+/// The region of a widget receiving touches, which may differ from the area it is drawn in.
+///
+/// Useful for small touch targets like the handle of a slider.
+/// Declared by [`crate::widgets::Widget::hit_region`], and can be overridden by [`super::Layout::set_hit_region`].
+///
+/// Positions of the shapes are relative to the left top corner of the widget.
+#[derive(Clone, Default)]
+pub enum HitRegion {
+	/// The area the widget is drawn in.
+	#[default]
+	Area,
+	/// The area grown by the given amount on each side, negative values shrink it.
+	Expand(Vec2),
+	/// A rect relative to the widget.
+	Rect(Rect),
+	/// A circle relative to the widget.
+	Circle {
+		/// The center of the circle.
+		center: Vec2,
+		/// The radius of the circle.
+		radius: f32,
+	},
+	/// A custom shape, the function is called with positions relative to the widget.
+	Custom {
+		/// The rect containing the whole shape, relative to the widget.
+		bounds: Rect,
+		/// Check whether the position is inside the shape.
+		contains: Arc<dyn Fn(Vec2) -> bool>,
+	},
+}
+
+impl std::fmt::Debug for HitRegion {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::Area => write!(f, "Area"),
+			Self::Expand(amount) => f.debug_tuple("Expand").field(amount).finish(),
+			Self::Rect(rect) => f.debug_tuple("Rect").field(rect).finish(),
+			Self::Circle { center, radius } => f.debug_struct("Circle").field("center", center).field("radius", radius).finish(),
+			Self::Custom { bounds, .. } => f.debug_struct("Custom").field("bounds", bounds).finish_non_exhaustive(),
+		}
+	}
+}
+
+impl HitRegion {
+	/// Get the absolute rect containing the region.
+	///
+	/// `area` is the absolute area of the widget and `pos` is its absolute left top position.
+	pub fn bounds(&self, area: Rect, pos: Vec2) -> Rect {
+		match self {
+			Self::Area => area,
+			Self::Expand(amount) => area.inflate(amount.x, amount.y, amount.x, amount.y),
+			Self::Rect(rect) => rect.move_by(pos),
+			Self::Circle { center, radius } => Rect::from_center_size(*center + pos, Vec2::same(radius * 2.0)),
+			Self::Custom { bounds, .. } => bounds.move_by(pos),
+		}
+	}
+
+	/// Check whether the absolute point is inside the region.
+	///
+	/// `area` is the absolute area of the widget and `pos` is its absolute left top position.
+	pub fn contains(&self, area: Rect, pos: Vec2, point: Vec2) -> bool {
+		match self {
+			Self::Area | Self::Expand(_) | Self::Rect(_) => self.bounds(area, pos).contains(point),
+			Self::Circle { center, radius } => (point - pos - *center).length() <= *radius,
+			Self::Custom { bounds, contains } => bounds.move_by(pos).contains(point) && contains(point - pos),
+		}
+	}
+}
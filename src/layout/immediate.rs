@@ -0,0 +1,511 @@
+//! An immediate mode facade on top of the retained [`Layout`].
+//!
+//! Describe the ui again in every event frame with a [`Ui`], as in immediate mode libraries,
+//! and [`ImmediateUi`] keeps the retained widgets in sync: widgets are added the first frame they are built,
+//! replaced when their properties change and removed the first frame they are not built anymore.
+//! The widgets are still laid out, drawn and cached by the retained layout, so frames where nothing changed
+//! do not redraw anything.
+//!
+//! A widget is identified by where it is built in the source code, the enclosing container and how many times
+//! it was built at the same place before in the frame. Widgets built in loops whose items may be reordered
+//! should be built inside of [`Ui::push_id`] with a key of the item, so they keep their state when moved.
+//!
+//! ```no_run
+//! # use nablo_ui::prelude::*;
+//! enum Sig {}
+//! # impl Signal for Sig {}
+//! struct Counter {
+//!     ui: ImmediateUi<Sig, Counter>,
+//!     count: i32,
+//!     enabled: bool,
+//! }
+//!
+//! impl App for Counter {
+//!     type Signal = Sig;
+//!
+//!     fn on_start(&mut self, _: &mut Context<Sig, Self>) {}
+//!
+//!     fn on_signal(&mut self, _: &mut Context<Sig, Self>, _: SignalWrapper<Sig>) {}
+//!
+//!     fn on_event_frame(&mut self, ctx: &mut Context<Sig, Self>) {
+//!         self.ui.frame(&mut ctx.layout, |ui| {
+//!             ui.label(format!("Clicked {} times", self.count));
+//!             ui.horizontal(|ui| {
+//!                 if ui.button("+1").clicked() && self.enabled {
+//!                     self.count += 1;
+//!                 }
+//!                 if ui.button("Reset").clicked() {
+//!                     self.count = 0;
+//!                 }
+//!             });
+//!             ui.checkbox(&mut self.enabled, "Enabled");
+//!         });
+//!     }
+//! }
+//! ```
+
+use std::{any::Any, cell::Cell, collections::{hash_map::DefaultHasher, HashMap}, hash::{Hash, Hasher}, marker::PhantomData, ops::RangeInclusive, panic::Location, rc::Rc};
+
+use indexmap::IndexMap;
+use time::Duration;
+
+use crate::{prelude::{InputState, Painter, Rect, Vec2}, widgets::{button::Button, card::Card, collapse::Collapse, divider::Divider, label::Label, radio::Radio, slider::Slider, EventHandleStrategy, ScrollAction, ScrollSource, Signal, Widget}, App};
+
+use super::{HitRegion, Layout, LayoutId};
+
+/// What happened to a widget built by a [`Ui`], as of the last events it handled.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Response {
+	id: LayoutId,
+	clicked: bool,
+	hovered: bool,
+	pressed: bool,
+	changed: bool,
+}
+
+impl Response {
+	/// Get the id of the retained widget.
+	pub fn id(&self) -> LayoutId {
+		self.id
+	}
+
+	/// Check whether the widget was clicked since it was built last time.
+	pub fn clicked(&self) -> bool {
+		self.clicked
+	}
+
+	/// Check whether the pointer is on the widget.
+	pub fn hovered(&self) -> bool {
+		self.hovered
+	}
+
+	/// Check whether the widget is being pressed.
+	pub fn is_pressed(&self) -> bool {
+		self.pressed
+	}
+
+	/// Check whether the user changed the value bound to the widget, such as the value of [`Ui::slider`].
+	pub fn changed(&self) -> bool {
+		self.changed
+	}
+}
+
+/// A retained widget built by a [`Ui`], recording the [`Response`] of the widget.
+struct Tracked<W> {
+	widget: W,
+	response: Rc<Cell<Response>>,
+}
+
+impl<W: Widget> Widget for Tracked<W> {
+	type Signal = W::Signal;
+	type Application = W::Application;
+
+	fn handle_event(&mut self, app: &mut Self::Application, input_state: &mut InputState<Self::Signal>, id: LayoutId, area: Rect, pos: Vec2) -> bool {
+		// the widget may consume its own click, which is seen before it does.
+		let claimed = input_state.peek_clicked(id, area);
+		let redraw = self.widget.handle_event(app, input_state, id, area, pos);
+		let clicked = input_state.is_clicked(id, area) || claimed;
+
+		let response = self.response.get();
+		self.response.set(Response {
+			id,
+			clicked: response.clicked || clicked,
+			hovered: input_state.hover_pos().is_some_and(|hover| input_state.hit_test(area, hover)),
+			pressed: input_state.is_touch_in(area),
+			..response
+		});
+		redraw
+	}
+
+	fn draw(&mut self, painter: &mut Painter, size: Vec2) {
+		self.widget.draw(painter, size)
+	}
+
+	fn size(&self, id: LayoutId, painter: &Painter, layout: &Layout<Self::Signal, Self::Application>) -> Vec2 {
+		self.widget.size(id, painter, layout)
+	}
+
+	fn event_handle_strategy(&self) -> EventHandleStrategy {
+		self.widget.event_handle_strategy()
+	}
+
+	fn handle_child_layout(&mut self, childs: IndexMap<LayoutId, Vec2>, area: Rect, id: LayoutId) -> HashMap<LayoutId, Option<Rect>> {
+		self.widget.handle_child_layout(childs, area, id)
+	}
+
+	fn inner_padding(&self) -> Vec2 {
+		self.widget.inner_padding()
+	}
+
+	fn scroll_by(&mut self, delta: Vec2, source: ScrollSource) -> Vec2 {
+		self.widget.scroll_by(delta, source)
+	}
+
+	fn scroll_action(&mut self, action: ScrollAction) -> bool {
+		self.widget.scroll_action(action)
+	}
+
+	fn scroll_to_reveal(&mut self, area: Rect) -> Vec2 {
+		self.widget.scroll_to_reveal(area)
+	}
+
+	fn focusable(&self) -> bool {
+		self.widget.focusable()
+	}
+
+	fn tracks_interaction(&self) -> bool {
+		self.widget.tracks_interaction()
+	}
+
+	fn modal(&self) -> bool {
+		self.widget.modal()
+	}
+
+	fn type_name(&self) -> &'static str {
+		self.widget.type_name()
+	}
+
+	fn hit_region(&self) -> HitRegion {
+		self.widget.hit_region()
+	}
+
+	fn z_index(&self) -> i32 {
+		self.widget.z_index()
+	}
+
+	fn save_state(&self) -> Option<Box<dyn Any>> {
+		self.widget.save_state()
+	}
+
+	fn restore_state(&mut self, state: Box<dyn Any>) {
+		self.widget.restore_state(state)
+	}
+
+	fn update(&mut self, dt: Duration) -> bool {
+		self.widget.update(dt)
+	}
+
+	fn update_interval(&self) -> Option<Duration> {
+		self.widget.update_interval()
+	}
+}
+
+/// A widget built by a [`Ui`] in an earlier frame.
+struct Entry {
+	id: LayoutId,
+	response: Rc<Cell<Response>>,
+	/// the hash of the properties the widget was built with.
+	props: u64,
+	/// the value bound to the widget as of the last frame, see [`Ui::sync`].
+	synced: Option<Box<dyn Any>>,
+	/// the last frame the widget was built in.
+	frame: u64,
+}
+
+/// The retained state of an immediate mode ui, see [the module docs](self).
+///
+/// Keep it in the app and call [`Self::frame`] in every event frame, usually in [`App::on_event_frame`].
+pub struct ImmediateUi<S: Signal, A: App<Signal = S>> {
+	parent: LayoutId,
+	entries: HashMap<u64, Entry>,
+	frame: u64,
+	_marker: PhantomData<(S, A)>,
+}
+
+impl<S: Signal, A: App<Signal = S>> ImmediateUi<S, A> {
+	/// Create an immediate mode ui building the widgets as the children of `parent`.
+	///
+	/// The children of `parent` are reordered to match the order the widgets are built in,
+	/// so it should not have other children, e.g. add a [`Card`] for the immediate mode ui.
+	pub fn new(parent: LayoutId) -> Self {
+		Self {
+			parent,
+			entries: HashMap::new(),
+			frame: 0,
+			_marker: PhantomData,
+		}
+	}
+
+	/// Get the parent of the widgets.
+	pub fn parent(&self) -> LayoutId {
+		self.parent
+	}
+
+	/// Build the ui of this frame, then remove the widgets built in the last frame but not in this one.
+	///
+	/// Does nothing if the parent is not in the layout.
+	pub fn frame(&mut self, layout: &mut Layout<S, A>, build: impl FnOnce(&mut Ui<S, A>)) {
+		if let Err(e) = layout.validate_id(self.parent) {
+			log::warn!("Immediate mode ui is not built: {}", e);
+			return;
+		}
+		self.frame += 1;
+		let mut ui = Ui {
+			layout: &mut *layout,
+			entries: &mut self.entries,
+			frame: self.frame,
+			parent: self.parent,
+			scope: 0,
+			index: 0,
+			occurrences: HashMap::new(),
+		};
+		build(&mut ui);
+
+		let frame = self.frame;
+		self.entries.retain(|_, entry| {
+			if entry.frame != frame {
+				layout.remove_widget(entry.id);
+			}
+			entry.frame == frame
+		});
+	}
+
+	/// Remove all the widgets built.
+	pub fn clear(&mut self, layout: &mut Layout<S, A>) {
+		for (_, entry) in self.entries.drain() {
+			layout.remove_widget(entry.id);
+		}
+	}
+}
+
+/// The builder of an immediate mode ui for a frame, see [the module docs](self).
+pub struct Ui<'a, S: Signal, A: App<Signal = S>> {
+	layout: &'a mut Layout<S, A>,
+	entries: &'a mut HashMap<u64, Entry>,
+	frame: u64,
+	/// the container the widgets are built in.
+	parent: LayoutId,
+	/// the hash identifying the container and the ids pushed.
+	scope: u64,
+	/// the index of the next widget among the children of the container.
+	index: usize,
+	/// how many times widgets were built at a call site in the scope.
+	occurrences: HashMap<u64, usize>,
+}
+
+fn hash_of(value: impl Hash) -> u64 {
+	let mut hasher = DefaultHasher::new();
+	value.hash(&mut hasher);
+	hasher.finish()
+}
+
+impl<S: Signal, A: App<Signal = S>> Ui<'_, S, A> {
+	/// Get the retained layout, e.g. to set a tooltip on the widget of a [`Response`].
+	pub fn layout(&mut self) -> &mut Layout<S, A> {
+		self.layout
+	}
+
+	/// Get the container the widgets are built in.
+	pub fn parent(&self) -> LayoutId {
+		self.parent
+	}
+
+	/// Build the widgets of `build` with the ids made unique by `id`, e.g. the key of an item built in a loop.
+	#[track_caller]
+	pub fn push_id<R>(&mut self, id: impl Hash, build: impl FnOnce(&mut Self) -> R) -> R {
+		let scope = self.key();
+		let scope = std::mem::replace(&mut self.scope, hash_of((scope, id)));
+		let occurrences = std::mem::take(&mut self.occurrences);
+		let out = build(self);
+		self.scope = scope;
+		self.occurrences = occurrences;
+		out
+	}
+
+	/// Get the key of the widget built at the caller.
+	#[track_caller]
+	fn key(&mut self) -> u64 {
+		let location = Location::caller();
+		let site = hash_of((self.scope, location.file(), location.line(), location.column()));
+		let occurrence = self.occurrences.entry(site).or_default();
+		*occurrence += 1;
+		hash_of((site, *occurrence))
+	}
+
+	/// Make sure the widget is in the layout and at the current index, returns the key of the widget.
+	///
+	/// The widget is built again and replaces the retained one when `props` differ from the last frame,
+	/// its state is kept as long as [`Widget::save_state`] supports.
+	#[track_caller]
+	fn place<W: Widget<Signal = S, Application = A>>(&mut self, props: u64, build: impl FnOnce() -> W) -> u64 {
+		let key = self.key();
+		let layout = &mut *self.layout;
+		let retained = self.entries.get_mut(&key).filter(|entry| layout.get_widget::<Tracked<W>>(entry.id).is_some());
+		let id = match retained {
+			Some(entry) => {
+				if entry.props != props {
+					entry.props = props;
+					entry.synced = None;
+					layout.replace_widget(entry.id, Tracked { widget: build(), response: entry.response.clone() });
+				}
+				entry.frame = self.frame;
+				entry.id
+			},
+			None => {
+				// built at the same place with another widget type last time.
+				if let Some(entry) = self.entries.remove(&key) {
+					layout.remove_widget(entry.id);
+				}
+				let response = Rc::new(Cell::new(Response::default()));
+				let id = layout.add_widget(self.parent, Tracked { widget: build(), response: response.clone() })
+					.expect("the container of the widget is built in this frame");
+				response.set(Response { id, ..Response::default() });
+				self.entries.insert(key, Entry {
+					id,
+					response,
+					props,
+					synced: None,
+					frame: self.frame,
+				});
+				id
+			},
+		};
+
+		if layout.get_children_ids(self.parent).and_then(|children| children.get(self.index)) != Some(&id) {
+			let _ = layout.set_child_index(id, self.index);
+		}
+		self.index += 1;
+		key
+	}
+
+	/// Take the response of the widget, a click is only reported once.
+	fn response(&mut self, key: u64) -> Response {
+		let cell = &self.entries[&key].response;
+		let response = cell.get();
+		cell.set(Response { clicked: false, changed: false, ..response });
+		response
+	}
+
+	/// Keep the value bound to the widget in sync with the widget, returns whether the user changed it.
+	///
+	/// The value of the widget wins if it changed since the last frame, otherwise the value of the app is written to the widget.
+	fn sync<W, T>(&mut self, key: u64, value: &mut T, read: impl Fn(&W) -> T, write: impl FnOnce(W, T) -> W) -> bool
+	where
+		W: Widget<Signal = S, Application = A>,
+		T: PartialEq + Clone + 'static,
+	{
+		let Some(entry) = self.entries.get_mut(&key) else { return false };
+		let Some(current) = self.layout.get_widget::<Tracked<W>>(entry.id).map(|tracked| read(&tracked.widget)) else { return false };
+		let last = entry.synced.as_ref().and_then(|synced| synced.downcast_ref::<T>());
+		if last.is_some_and(|last| *last != current) {
+			*value = current.clone();
+			entry.synced = Some(Box::new(current));
+			let response = entry.response.get();
+			entry.response.set(Response { changed: true, ..response });
+			return true;
+		}
+		if current != *value {
+			let new_value = value.clone();
+			self.layout.widget_mut(entry.id, |tracked: Tracked<W>| Tracked { widget: write(tracked.widget, new_value), ..tracked });
+		}
+		entry.synced = Some(Box::new(value.clone()));
+		false
+	}
+
+	/// Build any widget, built again when `props` differ from the last frame.
+	///
+	/// `props` should cover everything `build` depends on, e.g. the text of a label.
+	#[track_caller]
+	pub fn add<W: Widget<Signal = S, Application = A>>(&mut self, props: impl Hash, build: impl FnOnce() -> W) -> Response {
+		let key = self.place(hash_of(props), build);
+		self.response(key)
+	}
+
+	/// Build any widget containing the widgets built by `children`, built again when `props` differ from the last frame.
+	///
+	/// Building the container again removes its children, which are built again by `children` right after.
+	#[track_caller]
+	pub fn add_container<W, R>(&mut self, props: impl Hash, build: impl FnOnce() -> W, children: impl FnOnce(&mut Ui<S, A>) -> R) -> (Response, R)
+	where
+		W: Widget<Signal = S, Application = A>,
+	{
+		let key = self.place(hash_of(props), build);
+		let parent = self.entries[&key].id;
+		let mut ui = Ui {
+			layout: &mut *self.layout,
+			entries: &mut *self.entries,
+			frame: self.frame,
+			parent,
+			scope: key,
+			index: 0,
+			occurrences: HashMap::new(),
+		};
+		let out = children(&mut ui);
+		(self.response(key), out)
+	}
+
+	/// Build a label.
+	#[track_caller]
+	pub fn label(&mut self, text: impl Into<String>) -> Response {
+		let text = text.into();
+		self.add(&text, || Label::new(text.clone()))
+	}
+
+	/// Build a title label.
+	#[track_caller]
+	pub fn heading(&mut self, text: impl Into<String>) -> Response {
+		let text = text.into();
+		self.add(&text, || Label::title(text.clone()))
+	}
+
+	/// Build a button, see [`Response::clicked`].
+	#[track_caller]
+	pub fn button(&mut self, text: impl Into<String>) -> Response {
+		let text = text.into();
+		self.add(&text, || Button::new(text.clone()))
+	}
+
+	/// Build a check box bound to `checked`.
+	#[track_caller]
+	pub fn checkbox(&mut self, checked: &mut bool, text: impl Into<String>) -> Response {
+		let text = text.into();
+		let initial = *checked;
+		let key = self.place(hash_of(&text), || Radio::new_check_box(text.clone()).selected(initial));
+		self.sync(key, checked, |radio: &Radio<S, A>| radio.inner.selected, |radio, selected| radio.selected(selected));
+		self.response(key)
+	}
+
+	/// Build a slider bound to `value`, the value is clamped to the range.
+	#[track_caller]
+	pub fn slider(&mut self, value: &mut f32, range: RangeInclusive<f32>) -> Response {
+		let (min, max) = range.into_inner();
+		*value = value.clamp(min, max);
+		let initial = *value;
+		let key = self.place(hash_of((min.to_bits(), max.to_bits())), || Slider::new(initial, min, max));
+		self.sync(key, value, |slider: &Slider<S, A>| slider.inner.value, |slider, value| slider.value(value));
+		self.response(key)
+	}
+
+	/// Build a horizontal divider.
+	#[track_caller]
+	pub fn separator(&mut self) -> Response {
+		self.add((), || Divider::new(false))
+	}
+
+	/// Build the widgets of `children` from left to right.
+	#[track_caller]
+	pub fn horizontal<R>(&mut self, children: impl FnOnce(&mut Ui<S, A>) -> R) -> R {
+		self.add_container((), || Card::new_horizontal().dont_draw(true), children).1
+	}
+
+	/// Build the widgets of `children` from top to bottom.
+	#[track_caller]
+	pub fn vertical<R>(&mut self, children: impl FnOnce(&mut Ui<S, A>) -> R) -> R {
+		self.add_container((), || Card::new_vertical().dont_draw(true), children).1
+	}
+
+	/// Build the widgets of `children` from top to bottom on a card.
+	#[track_caller]
+	pub fn group<R>(&mut self, children: impl FnOnce(&mut Ui<S, A>) -> R) -> R {
+		self.add_container((), Card::new_vertical, children).1
+	}
+
+	/// Build the widgets of `children` in a collapsible section with the title.
+	///
+	/// `children` is run even while collapsed, so the widgets keep their states.
+	#[track_caller]
+	pub fn collapsing<R>(&mut self, title: impl Into<String>, children: impl FnOnce(&mut Ui<S, A>) -> R) -> (Response, R) {
+		let title = title.into();
+		self.add_container(&title, || Collapse::new(title.clone()), children)
+	}
+}
@@ -1,22 +1,49 @@
 //! A tree-based layout for the Nablo UI.
 
 mod macros;
+mod hit_region;
+pub mod immediate;
 
-use std::{any::Any, collections::{HashMap, HashSet, VecDeque}, fmt::Display, hash::Hash};
+pub use hit_region::HitRegion;
+
+use std::{any::{Any, TypeId}, collections::{HashMap, HashSet, VecDeque}, fmt::Display, hash::Hash};
 
 use indexmap::{IndexMap, IndexSet};
 use rstar::{RTree, RTreeObject};
-// use quad_tree::QuadTree;
+use time::Duration;
 
-use crate::{math::rect::Rect, prelude::Vec2, render::painter::Painter, widgets::{EventHandleStrategy, Signal, Widget}, window::input_state::InputState, App};
+use crate::{math::{animation::Animatedf32, rect::Rect}, prelude::{Vec2, Vec4}, render::{command_cache::{CommandCache, DrawKey}, commands::Interaction, damage::Damage, painter::Painter, shape::FillMode}, widgets::{composite::CompositeWidget, context_menu::ContextMenu, tooltip::{Tooltip, TOOLTIP_OFFSET}, styles::{DEFAULT_ROUNDING, FOCUS_RING_COLOR, FOCUS_RING_WIDTH, PRIMARY_COLOR}, EventHandleStrategy, ScrollAction, ScrollSource, Signal, SignalWrapper, Widget}, window::{event::Key, input_state::{ChildRequest, InputState, OverlayRequest, MOUSE_RIGHT_ID}}, App};
 
 /// A unique identifier for a layout element.
+/// 
+/// The id is a generational index: the index of a removed widget may be reused by a later widget,
+/// but the generation will be different, so an id kept after its widget was removed will never refer to another widget.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
-pub struct LayoutId(pub usize);
+pub struct LayoutId {
+	index: usize,
+	generation: u32,
+}
+
+impl LayoutId {
+	/// Create a new id from its index and generation.
+	pub const fn new(index: usize, generation: u32) -> Self {
+		Self { index, generation }
+	}
+
+	/// Get the index of the id.
+	pub const fn index(&self) -> usize {
+		self.index
+	}
+
+	/// Get the generation of the id.
+	pub const fn generation(&self) -> u32 {
+		self.generation
+	}
+}
 
 impl Display for LayoutId {
 	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-		write!(f, "LayoutId({})", self.0)
+		write!(f, "LayoutId({}v{})", self.index, self.generation)
 	}
 }
 
@@ -36,8 +63,76 @@ impl RTreeObject for RstarBinding {
 
 const REMOVE_CONTINOUS_HANLDING_THRESHOLD: usize = 5;
 
+/// The most [`Widget::update`] steps of a widget run in an event frame to catch up,
+/// the steps left are dropped so that a slow frame does not make the next one even slower.
+pub const MAX_UPDATE_STEPS: usize = 8;
+
+/// The radius of the ring drawn around a finger while it long presses, see [`InputState::long_press_time`].
+pub static LONG_PRESS_INDICATOR_RADIUS: f32 = 28.0;
+/// The width of the ring drawn around a finger while it long presses.
+pub static LONG_PRESS_INDICATOR_WIDTH: f32 = 4.0;
+/// The part of the long press time passed before the ring is shown, so a tap does not flash it.
+const LONG_PRESS_INDICATOR_DELAY: f32 = 0.2;
+
+/// The seconds the hover and press factors of [`Painter::interaction`] take to fade in or out.
+pub static INTERACTION_FADE: f32 = 0.12;
+
+/// The space kept visible around a widget scrolled into view, see [`Layout::scroll_to_widget`].
+pub static SCROLL_REVEAL_MARGIN: f32 = 8.0;
+/// The layout passes a revealed widget is scrolled into view in, as the containers around it take a pass to fit its size.
+const REVEAL_PASSES: usize = 2;
+
 /// The root element's id.
-pub const ROOT_LAYOUT_ID: LayoutId = LayoutId(0);
+pub const ROOT_LAYOUT_ID: LayoutId = LayoutId::new(0, 0);
+
+/// An error that occurs when accessing widgets in the layout.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum LayoutError {
+	/// The given id does not refer to a widget in the layout.
+	#[error("the widget {0} is not in the layout")]
+	WidgetNotFound(LayoutId),
+	/// The given id refers to a widget that has been removed, its index may now belong to another widget.
+	/// The debug name of the removed widget is kept until its index is reused, see [`Layout::debug_name`].
+	#[error("the widget {0}{name} has been removed, the id is stale", name = name_suffix(.1))]
+	StaleId(LayoutId, Option<String>),
+	/// The given alias is not registered in the layout.
+	#[error("the alias `{0}` is not registered in the layout")]
+	AliasNotFound(String),
+	/// The widget exists but is not of the requested type.
+	#[error("the widget {id} `{found}` is not of type `{expected}`")]
+	TypeMismatch {
+		/// The id of the widget.
+		id: LayoutId,
+		/// The debug name of the widget, see [`Layout::debug_name`].
+		found: String,
+		/// The name of the requested type.
+		expected: &'static str,
+	},
+}
+
+fn name_suffix(name: &Option<String>) -> String {
+	name.as_ref().map(|name| format!(" `{name}`")).unwrap_or_default()
+}
+
+/// Strip the module paths from a type name, e.g. `nablo_ui::widgets::button::Button<app::Sig, app::MyApp>` becomes `Button<Sig, MyApp>`.
+fn short_type_name(name: &str) -> String {
+	let mut out = String::with_capacity(name.len());
+	// where the current path starts in the output.
+	let mut start = 0;
+	let mut chars = name.chars().peekable();
+	while let Some(chr) = chars.next() {
+		if chr == ':' && chars.peek() == Some(&':') {
+			chars.next();
+			out.truncate(start);
+			continue;
+		}
+		out.push(chr);
+		if !(chr.is_alphanumeric() || chr == '_') {
+			start = out.len();
+		}
+	}
+	out
+}
 
 /// A tree-based layout for the Nablo UI.
 pub struct Layout<S: Signal, A: App<Signal = S>> {
@@ -49,8 +144,14 @@ pub struct Layout<S: Signal, A: App<Signal = S>> {
 	/// This is used to find the parent of a widget.
 	/// root has [`ROOT_LAYOUT_ID`] as its parent.
 	inverse_tree: HashMap<LayoutId, LayoutId>,
-	/// the avaiable id.
+	/// the next never used index.
 	next_id: usize,
+	/// the current generation of every index ever used.
+	generations: Vec<u32>,
+	/// the indices of removed widgets, which can be reused.
+	free_indices: Vec<usize>,
+	/// the debug names of the removed widgets by their index, with the generation they had.
+	released_names: HashMap<usize, (u32, String)>,
 	/// the alias map for the layout.
 	alias_map: HashMap<String, LayoutId>,
 	/// the inversed alias map for the layout.
@@ -59,6 +160,180 @@ pub struct Layout<S: Signal, A: App<Signal = S>> {
 	rtree: RTree<RstarBinding>,
 	primary_widgets: HashMap<LayoutId, usize>,
 	secondary_widgets: HashMap<LayoutId, usize>,
+	/// the widgets rendered at a different internal resolution.
+	quality_factors: HashMap<LayoutId, f32>,
+	/// the custom draw hooks attached to widgets.
+	decorators: HashMap<LayoutId, Vec<Decorator>>,
+	/// the composite widgets, keyed by their outermost widget.
+	composites: HashMap<LayoutId, Box<dyn CompositeWidget<Signal = S, Application = A>>>,
+	/// the roots of the overlay trees, in the order they are drawn.
+	overlays: Vec<Overlay>,
+	/// the area to redraw without a dirty widget, left by overlays moved or removed since the last draw,
+	/// or requested by [`InputState::request_redraw_area`].
+	damage: Damage,
+	/// the hit regions overriding the ones declared by widgets.
+	hit_regions: HashMap<LayoutId, HitRegion>,
+	/// the rects of the widgets in the rtree.
+	hit_bounds: HashMap<LayoutId, Rect>,
+	/// the states saved from replaced widgets, taken over by the widgets added in their place before the next layout pass.
+	saved_states: HashMap<StateKey, (TypeId, Box<dyn Any>)>,
+	/// the widgets replaced since the last layout pass.
+	replaced_roots: HashSet<LayoutId>,
+	/// the widgets added since the last layout pass while there are saved states.
+	fresh_widgets: Vec<LayoutId>,
+	/// the widgets redrawn in the last frame with their areas, see [`Layout::take_dirty_report`].
+	dirty_report: Vec<(LayoutId, Rect)>,
+	/// the dirty widgets already passed to the dirty listener.
+	reported_dirty: HashSet<LayoutId>,
+	/// called when a widget becomes dirty, see [`Layout::on_dirty`].
+	dirty_listener: Option<Box<dyn FnMut(LayoutId)>>,
+	/// the innermost widget last pressed on, keyboard scrolling starts from it.
+	scroll_focus: LayoutId,
+	/// the context menus attached to widgets.
+	context_menus: HashMap<LayoutId, ContextMenu<S, A>>,
+	/// whether the right mouse button was pressed in the last event frame, so a press opens a menu only once.
+	context_menu_pressed: bool,
+	/// the finger holding still on a widget with a context menu.
+	long_press: Option<LongPress>,
+	/// the tooltips attached to widgets.
+	tooltips: HashMap<LayoutId, Tooltip<S, A>>,
+	/// the widget with a tooltip the pointer rests on.
+	tooltip_hover: Option<TooltipHover>,
+	/// the widgets tracking their interaction which are not idle, see [`Widget::tracks_interaction`].
+	interactions: HashMap<LayoutId, Interaction>,
+	/// the widgets fading in over the widgets they replaced, see [`Layout::crossfade_widget`].
+	crossfades: HashMap<LayoutId, Crossfade<S, A>>,
+	/// the gpu commands of the widgets drawn in the last frames, reused while they are not dirty.
+	pub(crate) command_cache: CommandCache,
+	/// the widgets with an update interval and the running time their next update is due, `None` before the first one.
+	updating: HashMap<LayoutId, Option<Duration>>,
+	/// the widget with the keyboard focus.
+	focused: Option<LayoutId>,
+	/// whether the focus was moved by the keyboard, so the focus ring is drawn.
+	focus_visible: bool,
+	/// the modal overlay the focus is kept in and the widget focused before it was opened.
+	modal_focus: Option<(LayoutId, Option<LayoutId>)>,
+	/// the focused widget and whether it was laid out in the last layout pass, to notice when it is revealed.
+	focus_shown: Option<(LayoutId, bool)>,
+	/// the widget to scroll into view after the next layout passes and how many passes are left.
+	pending_reveal: Option<(LayoutId, usize)>,
+	/// the sizes of the widgets before clipped by their parents in the last layout pass.
+	full_sizes: HashMap<LayoutId, Vec2>,
+	/// how the children of each widget were arranged, see [`Layout::reanrrage_widgets`].
+	arrangements: HashMap<LayoutId, Arrangement>,
+	/// the widgets which arranged their children again, so their own sizes are measured again in the next layout pass.
+	relayout_requests: HashSet<LayoutId>,
+	/// the widgets to arrange in the current layout pass, the dirty ones and their ancestors.
+	relayout_path: HashSet<LayoutId>,
+}
+
+/// The identity of a replaced widget, used to match it with the widget added in its place.
+#[derive(PartialEq, Eq, Hash)]
+enum StateKey {
+	/// The widget had an alias.
+	Alias(String),
+	/// The widget was a descendant of a replaced widget, located by the child indices from it.
+	Path(LayoutId, Vec<usize>),
+}
+
+/// Where an overlay is placed, see [`Layout::push_overlay`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OverlayAnchor {
+	/// Place the left top corner of the overlay at the given position in window coordinates.
+	Screen(Vec2),
+	/// Place the overlay at the given position in window coordinates like a popup menu,
+	/// flipped to the other side of the position where it would go out of the window.
+	Popup(Vec2),
+	/// Place the left top corner of the overlay relative to the area of another widget.
+	/// 
+	/// The overlay is hidden while the widget is not visible, and removed together with the widget.
+	Widget {
+		/// The widget to anchor to.
+		id: LayoutId,
+		/// The point in the widget's area, (0.0, 0.0) for its left top corner and (1.0, 1.0) for its right bottom corner.
+		align: Vec2,
+		/// The offset added after aligning.
+		offset: Vec2,
+	},
+}
+
+impl OverlayAnchor {
+	/// Place the overlay right below the given widget, useful for dropdowns and tooltips.
+	pub fn below(id: LayoutId) -> Self {
+		Self::Widget { id, align: Vec2::new(0.0, 1.0), offset: Vec2::ZERO }
+	}
+
+	/// Place the overlay right to the given widget, useful for sub menus.
+	pub fn right_of(id: LayoutId) -> Self {
+		Self::Widget { id, align: Vec2::new(1.0, 0.0), offset: Vec2::ZERO }
+	}
+}
+
+/// A finger holding still to open a context menu, see [`InputState::long_press_time`].
+struct LongPress {
+	/// the id of the touch.
+	touch: u64,
+	/// where the finger pressed.
+	pos: Vec2,
+	/// the part of the long press time passed, in the range [0, 1].
+	progress: f32,
+	/// whether the context menu is opened, nothing more is done until the finger lifts.
+	opened: bool,
+}
+
+impl LongPress {
+	/// The area of the ring drawn around the finger.
+	fn indicator_area(&self) -> Rect {
+		let half = Vec2::same(LONG_PRESS_INDICATOR_RADIUS + LONG_PRESS_INDICATOR_WIDTH + 1.0);
+		Rect::from_lt_size(self.pos - half, half * 2.0)
+	}
+
+	fn shows_indicator(&self) -> bool {
+		!self.opened && self.progress >= LONG_PRESS_INDICATOR_DELAY
+	}
+}
+
+/// A widget fading out under the widget replacing it, see [`Layout::crossfade_widget`].
+struct Crossfade<S: Signal, A: App<Signal = S>> {
+	/// the replaced widget, drawn in the area of the new one until the fade ends.
+	old: Box<dyn Widget<Signal = S, Application = A>>,
+	/// the opacity of the new widget, animated from `0.0` to `1.0`.
+	progress: Animatedf32,
+}
+
+/// How the children of a widget were arranged in the last layout pass, reused while nothing inside changes.
+struct Arrangement {
+	/// the area and the position of the widget the children were arranged in.
+	window: Rect,
+	pos: Vec2,
+	/// the sizes the children reported, a change means the widget has to arrange them again.
+	sizes: IndexMap<LayoutId, Vec2>,
+}
+
+/// The widget with a tooltip the pointer rests on, see [`Layout::set_tooltip`].
+struct TooltipHover {
+	/// the widget the tooltip is attached to.
+	id: LayoutId,
+	/// the running time the pointer came onto the widget.
+	since: Duration,
+	/// the overlay showing the tooltip.
+	shown: Option<LayoutId>,
+	/// whether a press hid the tooltip, so it is not shown again until the pointer leaves.
+	dismissed: bool,
+}
+
+/// The root of an overlay tree.
+struct Overlay {
+	id: LayoutId,
+	anchor: OverlayAnchor,
+}
+
+/// A custom draw hook attached to a widget, see [`Layout::decorate`].
+struct Decorator {
+	/// Whether to draw before the widget, so it appears behind the widget.
+	behind: bool,
+	#[allow(clippy::type_complexity)]
+	draw: Box<dyn Fn(&mut Painter, Rect)>,
 }
 
 /// A layout element that holds a widget and its properties.
@@ -80,6 +355,10 @@ pub struct LayoutElement<S: Signal, A: App<Signal = S>> {
 	/// 
 	/// We will also call the widget is dirty if it needs to be redrawn.
 	pub redraw_request: bool,
+	/// The name of the widget shown in errors and logs, see [`Layout::set_debug_name`].
+	pub debug_name: Option<String>,
+	/// The z-index of the widget, `None` to use [`Widget::z_index`], see [`Layout::set_z_index`].
+	pub z_index: Option<i32>,
 }
 
 impl<S: Signal, A: App<Signal = S>> Default for Layout<S, A> {
@@ -96,12 +375,46 @@ impl<S: Signal, A: App<Signal = S>> Layout<S, A> {
 			tree: HashMap::new(),
 			inverse_tree: HashMap::new(),
 			next_id: 1,
+			generations: vec![0],
+			free_indices: Vec::new(),
+			released_names: HashMap::new(),
 			alias_map: HashMap::new(),
 			inversed_alias_map: HashMap::new(),
-			// quad_tree: QuadTree::new(Rect::ZERO),
 			rtree: RTree::new(),
 			primary_widgets: HashMap::new(),
 			secondary_widgets: HashMap::new(),
+			quality_factors: HashMap::new(),
+			decorators: HashMap::new(),
+			composites: HashMap::new(),
+			overlays: Vec::new(),
+			damage: Damage::default(),
+			hit_regions: HashMap::new(),
+			hit_bounds: HashMap::new(),
+			saved_states: HashMap::new(),
+			replaced_roots: HashSet::new(),
+			fresh_widgets: Vec::new(),
+			dirty_report: Vec::new(),
+			reported_dirty: HashSet::new(),
+			dirty_listener: None,
+			scroll_focus: ROOT_LAYOUT_ID,
+			context_menus: HashMap::new(),
+			context_menu_pressed: false,
+			long_press: None,
+			tooltips: HashMap::new(),
+			tooltip_hover: None,
+			interactions: HashMap::new(),
+			crossfades: HashMap::new(),
+			command_cache: CommandCache::default(),
+			updating: HashMap::new(),
+			focused: None,
+			focus_visible: false,
+			modal_focus: None,
+			focus_shown: None,
+			pending_reveal: None,
+			full_sizes: HashMap::new(),
+			arrangements: HashMap::new(),
+			relayout_requests: HashSet::new(),
+			relayout_path: HashSet::new(),
 		}
 	}
 
@@ -120,6 +433,7 @@ impl<S: Signal, A: App<Signal = S>> Layout<S, A> {
 			},
 			_ => {},
 		}
+		self.track_update(ROOT_LAYOUT_ID, widget.update_interval());
 
 		if let Some(root) = self.widgets.get_mut(&ROOT_LAYOUT_ID) {
 			root.widget = Box::new(widget);
@@ -133,6 +447,8 @@ impl<S: Signal, A: App<Signal = S>> Layout<S, A> {
 					area_and_pos: Some((Rect::WINDOW, Vec2::ZERO)),
 					widget: Box::new(widget),
 					redraw_request: true,
+					debug_name: None,
+					z_index: None,
 				},
 			);
 			self.tree.insert(ROOT_LAYOUT_ID, Vec::new());
@@ -147,8 +463,13 @@ impl<S: Signal, A: App<Signal = S>> Layout<S, A> {
 	/// 
 	/// If the parent_id is not in the layout, the widget will not be added and None will be returned.
 	pub fn add_widget(&mut self, parent_id: LayoutId, widget: impl Widget<Signal = S, Application = A>) -> Option<LayoutId> {
+		self.add_boxed_widget(parent_id, Box::new(widget))
+	}
+
+	fn add_boxed_widget(&mut self, parent_id: LayoutId, widget: Box<dyn Widget<Signal = S, Application = A>>) -> Option<LayoutId> {
 		if self.widgets.contains_key(&parent_id) {
-			let id = LayoutId(self.next_id);
+			let id = self.allocate_id();
+			log::trace!("Adding widget {} `{}` to {}", id, short_type_name(widget.type_name()), parent_id);
 			match widget.event_handle_strategy() {
 				EventHandleStrategy::AlwaysPrimary => {
 					self.primary_widgets.insert(id, 0);
@@ -158,25 +479,234 @@ impl<S: Signal, A: App<Signal = S>> Layout<S, A> {
 				},
 				_ => {},
 			}
-			self.next_id += 1;
+			self.track_update(id, widget.update_interval());
 			self.widgets.insert(
 				id,
 				LayoutElement {
 					id,
 					area_and_pos: None,
-					widget: Box::new(widget),
+					widget,
 					redraw_request: true,
+					debug_name: None,
+					z_index: None,
 				},
 			);
 			self.widgets.get_mut(&parent_id).unwrap().redraw_request = true;
 			self.tree.entry(parent_id).or_default().push(id);
 			self.inverse_tree.insert(id, parent_id);
+			if !self.saved_states.is_empty() {
+				self.fresh_widgets.push(id);
+			}
 			Some(id)
 		}else {
+			if let Err(e) = self.validate_id(parent_id) {
+				log::debug!("Widget `{}` is not added: {}", short_type_name(widget.type_name()), e);
+			}
 			None
 		}
 	}
 
+	/// Push a widget as the root of a new overlay, which is drawn above the main tree and previous overlays,
+	/// and receives events before them.
+	/// 
+	/// Overlays are not clipped by any parent, only by the window, so they are suitable for tooltips, popovers,
+	/// toasts and drag ghosts. The size of the overlay is given by [`Widget::size`], children can be added by [`Self::add_widget`].
+	/// 
+	/// Returns the id of the overlay root.
+	pub fn push_overlay(&mut self, anchor: OverlayAnchor, widget: impl Widget<Signal = S, Application = A>) -> LayoutId {
+		self.push_boxed_overlay(anchor, Box::new(widget))
+	}
+
+	fn push_boxed_overlay(&mut self, anchor: OverlayAnchor, widget: Box<dyn Widget<Signal = S, Application = A>>) -> LayoutId {
+		let id = self.allocate_id();
+		match widget.event_handle_strategy() {
+			EventHandleStrategy::AlwaysPrimary => {
+				self.primary_widgets.insert(id, 0);
+			},
+			EventHandleStrategy::AlwaysSecondary => {
+				self.secondary_widgets.insert(id, 0);
+			},
+			_ => {},
+		}
+		self.track_update(id, widget.update_interval());
+		self.widgets.insert(
+			id,
+			LayoutElement {
+				id,
+				area_and_pos: None,
+				widget,
+				redraw_request: true,
+				debug_name: None,
+				z_index: None,
+			},
+		);
+		self.tree.insert(id, Vec::new());
+		self.overlays.push(Overlay { id, anchor });
+		id
+	}
+
+	/// Remove the topmost overlay and its children, returns empty vector if there is no overlay.
+	pub fn pop_overlay(&mut self) -> Vec<Box<dyn Widget<Signal = S, Application = A>>> {
+		match self.overlays.last() {
+			Some(overlay) => self.remove_widget(overlay.id),
+			None => vec!(),
+		}
+	}
+
+	/// Move an overlay to a new anchor.
+	/// 
+	/// Returns an error if the id is not the root of an overlay.
+	pub fn set_overlay_anchor(&mut self, id: LayoutId, anchor: OverlayAnchor) -> Result<(), LayoutError> {
+		self.validate_id(id)?;
+		let overlay = self.overlays.iter_mut().find(|overlay| overlay.id == id).ok_or(LayoutError::WidgetNotFound(id))?;
+		overlay.anchor = anchor;
+		Ok(())
+	}
+
+	/// Get the roots of the overlays, from the bottommost to the topmost.
+	pub fn overlays(&self) -> impl Iterator<Item = LayoutId> + '_ {
+		self.overlays.iter().map(|overlay| overlay.id)
+	}
+
+	/// Returns true if the id is the root of an overlay.
+	pub fn is_overlay(&self, id: LayoutId) -> bool {
+		self.overlays.iter().any(|overlay| overlay.id == id)
+	}
+
+	/// Get the root of the topmost modal overlay, see [`Widget::modal`].
+	pub fn modal(&self) -> Option<LayoutId> {
+		self.modal_index().map(|index| self.overlays[index].id)
+	}
+
+	/// Returns true if the widget is below the topmost modal overlay, so it receives no events.
+	pub fn is_blocked(&self, id: LayoutId) -> bool {
+		self.modal_index().is_some_and(|modal| self.is_below(id, modal))
+	}
+
+	/// the index of the topmost modal overlay.
+	fn modal_index(&self) -> Option<usize> {
+		self.overlays.iter().rposition(|overlay| self.widgets.get(&overlay.id).is_some_and(|element| element.widget.modal()))
+	}
+
+	/// whether the widget is in the main tree or an overlay pushed before the overlay at the index.
+	fn is_below(&self, id: LayoutId, modal: usize) -> bool {
+		match self.overlay_of(id) {
+			Some(root) => self.overlays.iter().position(|overlay| overlay.id == root).is_some_and(|index| index < modal),
+			None => true,
+		}
+	}
+
+	/// Get the root of the overlay containing the widget, `None` if the widget is in the main tree.
+	pub fn overlay_of(&self, id: LayoutId) -> Option<LayoutId> {
+		let mut current = id;
+		while let Some(parent) = self.inverse_tree.get(&current) {
+			if *parent == current {
+				return None;
+			}
+			current = *parent;
+		}
+		self.is_overlay(current).then_some(current)
+	}
+
+	fn allocate_id(&mut self) -> LayoutId {
+		if let Some(index) = self.free_indices.pop() {
+			LayoutId::new(index, self.generations[index])
+		}else {
+			let index = self.next_id;
+			self.next_id += 1;
+			self.generations.push(0);
+			LayoutId::new(index, 0)
+		}
+	}
+
+	/// Mark the index of a removed widget as reusable, all ids pointing to it will be stale from now on.
+	/// 
+	/// The debug name is kept to describe the stale ids, see [`LayoutError::StaleId`].
+	fn release_id(&mut self, id: LayoutId, name: Option<String>) {
+		if id == ROOT_LAYOUT_ID {
+			return;
+		}
+		if let Some(generation) = self.generations.get_mut(id.index) {
+			if *generation == id.generation {
+				*generation = generation.wrapping_add(1);
+				self.free_indices.push(id.index);
+				match name {
+					Some(name) => self.released_names.insert(id.index, (id.generation, name)),
+					None => self.released_names.remove(&id.index),
+				};
+			}
+		}
+	}
+
+	/// Check whether the id refers to a widget in the layout.
+	/// 
+	/// Returns [`LayoutError::StaleId`] if the widget of the id has been removed,
+	/// or [`LayoutError::WidgetNotFound`] if the id has never been given out by this layout.
+	pub fn validate_id(&self, id: LayoutId) -> Result<(), LayoutError> {
+		if self.widgets.contains_key(&id) {
+			Ok(())
+		}else if self.generations.get(id.index).is_some_and(|generation| *generation != id.generation) {
+			let name = self.released_names.get(&id.index).filter(|(generation, _)| *generation == id.generation).map(|(_, name)| name.clone());
+			Err(LayoutError::StaleId(id, name))
+		}else {
+			Err(LayoutError::WidgetNotFound(id))
+		}
+	}
+
+	/// Returns true if the id refers to a widget in the layout.
+	pub fn contains(&self, id: LayoutId) -> bool {
+		self.widgets.contains_key(&id)
+	}
+
+	/// Add a new widget to the layout.
+	/// 
+	/// Same as [`Self::add_widget`], but returns an error if the parent is not in the layout.
+	pub fn try_add_widget(&mut self, parent_id: LayoutId, widget: impl Widget<Signal = S, Application = A>) -> Result<LayoutId, LayoutError> {
+		self.validate_id(parent_id)?;
+		self.add_widget(parent_id, widget).ok_or(LayoutError::WidgetNotFound(parent_id))
+	}
+
+	/// Add a composite widget to the layout, see [`CompositeWidget`].
+	/// 
+	/// Returns the id of the outermost widget of the composite.
+	pub fn add_composite(&mut self, parent_id: LayoutId, mut composite: impl CompositeWidget<Signal = S, Application = A>) -> Result<LayoutId, LayoutError> {
+		self.validate_id(parent_id)?;
+		let id = composite.build(self, parent_id)?;
+		self.composites.insert(id, Box::new(composite));
+		Ok(id)
+	}
+
+	/// Pass the signal through the composites containing its sender, from the innermost one.
+	/// 
+	/// Returns `None` if a composite consumed the signal.
+	pub(crate) fn route_signal(&mut self, app: &mut A, mut signal: SignalWrapper<S>) -> Option<SignalWrapper<S>> {
+		if self.composites.is_empty() {
+			return Some(signal);
+		}
+		let mut current = signal.from;
+		loop {
+			if let Some(mut composite) = self.composites.remove(&current) {
+				let out = composite.on_signal(app, self, current, signal);
+				if self.contains(current) {
+					self.composites.insert(current, composite);
+				}
+				signal = out?;
+			}
+			match self.inverse_tree.get(&current) {
+				Some(parent) if *parent != current => current = *parent,
+				_ => return Some(signal),
+			}
+		}
+	}
+
+	/// Add a new widget to the layout by alias.
+	/// 
+	/// Same as [`Self::add_widget_by_alias`], but returns an error if the alias or the parent is missing.
+	pub fn try_add_widget_by_alias(&mut self, parent_alias: impl Into<String>, widget: impl Widget<Signal = S, Application = A>) -> Result<LayoutId, LayoutError> {
+		let id = self.try_alias_to_id(parent_alias)?;
+		self.try_add_widget(id, widget)
+	}
+
 	/// Add a new widget to the layout by alias.
 	/// 
 	/// Returns the id of the new widget.
@@ -191,10 +721,65 @@ impl<S: Signal, A: App<Signal = S>> Layout<S, A> {
 	/// Alias a widget by its id.
 	/// 
 	/// This will allow you to refer to the widget by its alias name instead of its id.
+	/// A widget has at most one alias, aliasing it again will replace the old alias.
+	/// If the alias is already used by another widget, that widget will lose its alias.
+	/// 
+	/// The alias will be removed automatically when the widget is removed.
+	/// Nothing will happen if the widget is not in the layout.
 	pub fn alias_widget(&mut self, id: LayoutId, alias: impl Into<String>) {
+		if !self.widgets.contains_key(&id) {
+			log::warn!("trying to alias {id}, which is not in the layout");
+			return;
+		}
+		let alias = alias.into();
+		if let Some(old_id) = self.alias_map.insert(alias.clone(), id) {
+			self.inversed_alias_map.remove(&old_id);
+		}
+		if let Some(old_alias) = self.inversed_alias_map.insert(id, alias.clone()) {
+			if old_alias != alias {
+				self.alias_map.remove(&old_alias);
+			}
+		}
+	}
+
+	/// Remove an alias, the widget itself will be kept.
+	/// 
+	/// Returns the id the alias referred to, or None if the alias is not registered.
+	pub fn remove_alias(&mut self, alias: impl Into<String>) -> Option<LayoutId> {
+		let id = self.alias_map.remove(&alias.into())?;
+		self.inversed_alias_map.remove(&id);
+		Some(id)
+	}
+
+	/// Rename an alias, the widget will be referred by the new alias afterwards.
+	/// 
+	/// Returns an error if the old alias is not registered.
+	/// If the new alias is already used by another widget, that widget will lose its alias.
+	pub fn rename_alias(&mut self, alias: impl Into<String>, new_alias: impl Into<String>) -> Result<LayoutId, LayoutError> {
 		let alias = alias.into();
-		self.alias_map.insert(alias.clone(), id);
-		self.inversed_alias_map.insert(id, alias);
+		let id = self.alias_map.remove(&alias).ok_or(LayoutError::AliasNotFound(alias))?;
+		self.inversed_alias_map.remove(&id);
+		self.alias_widget(id, new_alias);
+		Ok(id)
+	}
+
+	/// Iterate over all the aliases and the ids they refer to.
+	pub fn aliases(&self) -> impl Iterator<Item = (&str, LayoutId)> {
+		self.alias_map.iter().map(|(alias, id)| (alias.as_str(), *id))
+	}
+
+	/// Get the aliases referring to widgets that are no longer in the layout.
+	/// 
+	/// Aliases are cleaned up when widgets are removed, so this should always be empty.
+	pub fn dangling_aliases(&self) -> Vec<&str> {
+		self.alias_map.iter().filter(|(_, id)| !self.widgets.contains_key(id)).map(|(alias, _)| alias.as_str()).collect()
+	}
+
+	#[cfg(debug_assertions)]
+	fn check_aliases(&self) {
+		let dangling = self.dangling_aliases();
+		debug_assert!(dangling.is_empty(), "dangling aliases in layout: {:?}", dangling);
+		debug_assert_eq!(self.alias_map.len(), self.inversed_alias_map.len(), "alias maps are out of sync");
 	}
 
 	/// Remove a widget from the layout.
@@ -203,7 +788,9 @@ impl<S: Signal, A: App<Signal = S>> Layout<S, A> {
 	/// 
 	/// Will also remove all the children of the widget.
 	pub fn remove_widget(&mut self, id: LayoutId) -> Vec<Box<dyn Widget<Signal = S, Application = A>>> {
+		let name = self.debug_name(id);
 		if let Some(element) = self.widgets.remove(&id) {
+			log::trace!("Removing widget {}{}", id, name_suffix(&name));
 			let mut out = vec!();
 			if let Some(children) = self.tree.remove(&id) {
 				for child_id in children {
@@ -214,6 +801,34 @@ impl<S: Signal, A: App<Signal = S>> Layout<S, A> {
 				self.tree.entry(parent_id).or_default().retain(|&x| x != id);
 				if let Some(inner) = self.widgets.get_mut(&parent_id) { inner.redraw_request = true };
 			}
+			self.quality_factors.remove(&id);
+			self.hit_regions.remove(&id);
+			self.bind_hit_bounds(id, None);
+			self.decorators.remove(&id);
+			self.composites.remove(&id);
+			self.context_menus.remove(&id);
+			self.tooltips.remove(&id);
+			self.updating.remove(&id);
+			self.crossfades.remove(&id);
+			self.command_cache.remove(id);
+			self.full_sizes.remove(&id);
+			self.arrangements.remove(&id);
+			self.relayout_requests.remove(&id);
+			if self.focused == Some(id) {
+				self.focused = None;
+			}
+			if self.is_overlay(id) {
+				self.overlays.retain(|overlay| overlay.id != id);
+				if let Some((area, _)) = element.area_and_pos {
+					self.add_damage(area);
+				}
+			}
+			self.primary_widgets.remove(&id);
+			self.secondary_widgets.remove(&id);
+			if let Some(alias) = self.inversed_alias_map.remove(&id) {
+				self.alias_map.remove(&alias);
+			}
+			self.release_id(id, name);
 			out.push(element.widget);
 			out
 		}else {
@@ -256,55 +871,815 @@ impl<S: Signal, A: App<Signal = S>> Layout<S, A> {
 
 	/// Replace the given widget, will return the old widget and its children if any.
 	/// 
+	/// The new widget takes over the runtime state of the old one if they are of the same type, see [`Widget::save_state`].
+	/// The children added in place of the old children before the next layout pass take over their states as well,
+	/// matched by alias, or by the position in the tree otherwise.
+	/// 
 	/// # Panics
 	/// 
 	/// Panics if missing root widget in the layout or the widget is not in the layout.
+	/// See [`Self::try_replace_widget`] for a non-panicking version.
 	pub fn replace_widget(&mut self, id: LayoutId, widget: impl Widget<Signal = S, Application = A>) -> Vec<Box<dyn Widget<Signal = S, Application = A>>> {
-		let parent_id = if let Some(parent_id) = self.inverse_tree.get(&id) {
-			*parent_id
-		}else {
-			panic!("The given widget {id} is not in the layout.")
-		};
+		match self.try_replace_widget(id, widget) {
+			Ok(out) => out,
+			Err(e) => panic!("{e}"),
+		}
+	}
 
-		let out = self.remove_widget_children(id);
+	/// Replace the given widget, will return the old widget and its children if any.
+	/// 
+	/// Returns an error if the widget or its parent is not in the layout.
+	pub fn try_replace_widget<W: Widget<Signal = S, Application = A>>(&mut self, id: LayoutId, widget: W) -> Result<Vec<Box<dyn Widget<Signal = S, Application = A>>>, LayoutError> {
+		self.replace_boxed_widget(id, Box::new(widget), false)
+	}
 
-		if self.widgets.contains_key(&parent_id) {
-			match widget.event_handle_strategy() {
-				EventHandleStrategy::AlwaysPrimary => {
-					self.primary_widgets.insert(id, 0);
-				},
-				EventHandleStrategy::AlwaysSecondary => {
-					self.secondary_widgets.insert(id, 0);
-				},
-				_ => {},
+	/// Replace the given widget like [`Self::replace_widget`], fading the new widget in over the old one,
+	/// e.g. to swap a [`crate::widgets::skeleton::Skeleton`] for the content once the data arrives.
+	/// 
+	/// The old widget is kept and drawn in the area of the new one until the fade ends, so only its children are returned.
+	/// The fade follows the default animation of [`Animatedf32`].
+	/// 
+	/// # Panics
+	/// 
+	/// Panics if missing root widget in the layout or the widget is not in the layout.
+	/// See [`Self::try_crossfade_widget`] for a non-panicking version.
+	pub fn crossfade_widget(&mut self, id: LayoutId, widget: impl Widget<Signal = S, Application = A>) -> Vec<Box<dyn Widget<Signal = S, Application = A>>> {
+		match self.try_crossfade_widget(id, widget) {
+			Ok(out) => out,
+			Err(e) => panic!("{e}"),
+		}
+	}
+
+	/// Replace the given widget with a crossfade, see [`Self::crossfade_widget`].
+	/// 
+	/// Returns an error if the widget or its parent is not in the layout.
+	pub fn try_crossfade_widget<W: Widget<Signal = S, Application = A>>(&mut self, id: LayoutId, widget: W) -> Result<Vec<Box<dyn Widget<Signal = S, Application = A>>>, LayoutError> {
+		self.replace_boxed_widget(id, Box::new(widget), true)
+	}
+
+	fn replace_boxed_widget(&mut self, id: LayoutId, mut widget: Box<dyn Widget<Signal = S, Application = A>>, crossfade: bool) -> Result<Vec<Box<dyn Widget<Signal = S, Application = A>>>, LayoutError> {
+		self.validate_id(id)?;
+		let parent_id = self.inverse_tree.get(&id).copied();
+		match parent_id {
+			Some(parent_id) => self.validate_id(parent_id)?,
+			None if self.is_overlay(id) => {},
+			None => return Err(LayoutError::WidgetNotFound(id)),
+		}
+
+		self.save_children_states(id, id, &mut vec!());
+		self.replaced_roots.insert(id);
+		if let Some(old) = self.widgets.get(&id) {
+			if (*old.widget).type_id() == (*widget).type_id() {
+				if let Some(state) = old.widget.save_state() {
+					widget.restore_state(state);
+				}
 			}
-			self.widgets.insert(
+		}
+
+		let out = self.remove_widget_children(id);
+
+		match widget.event_handle_strategy() {
+			EventHandleStrategy::AlwaysPrimary => {
+				self.primary_widgets.insert(id, 0);
+			},
+			EventHandleStrategy::AlwaysSecondary => {
+				self.secondary_widgets.insert(id, 0);
+			},
+			_ => {},
+		}
+		self.track_update(id, widget.update_interval());
+		let debug_name = self.widgets.get(&id).and_then(|old| old.debug_name.clone());
+		let z_index = self.widgets.get(&id).and_then(|old| old.z_index);
+		let old = self.widgets.insert(
+			id,
+			LayoutElement {
 				id,
-				LayoutElement {
-					id,
-					area_and_pos: None,
-					widget: Box::new(widget),
-					redraw_request: true,
-				},
-			);
-			self.widgets.get_mut(&parent_id).unwrap().redraw_request = true;
-			// self.tree.entry(parent_id).or_default().push(id);
-			// self.inverse_tree.insert(id, parent_id);
-		}else {
-			panic!("The given widget {id} is not in the layout.")
+				area_and_pos: None,
+				widget,
+				redraw_request: true,
+				debug_name,
+				z_index,
+			},
+		);
+		match old {
+			Some(old) if crossfade => {
+				let mut progress = Animatedf32::default_with_value(0.0);
+				progress.set(1.0);
+				self.crossfades.insert(id, Crossfade { old: old.widget, progress });
+			},
+			_ => {
+				self.crossfades.remove(&id);
+			},
+		}
+		if let Some(parent) = parent_id.and_then(|parent_id| self.widgets.get_mut(&parent_id)) {
+			parent.redraw_request = true;
+		}
+		// self.tree.entry(parent_id).or_default().push(id);
+		// self.inverse_tree.insert(id, parent_id);
+
+		Ok(out)
+	}
+
+	/// Save the states of the descendants of a replaced widget, see [`Self::try_replace_widget`].
+	fn save_children_states(&mut self, root: LayoutId, id: LayoutId, path: &mut Vec<usize>) {
+		let children = self.tree.get(&id).cloned().unwrap_or_default();
+		for (index, child) in children.into_iter().enumerate() {
+			path.push(index);
+			if let Some(element) = self.widgets.get(&child) {
+				if let Some(state) = element.widget.save_state() {
+					let key = match self.inversed_alias_map.get(&child) {
+						Some(alias) => StateKey::Alias(alias.clone()),
+						None => StateKey::Path(root, path.clone()),
+					};
+					self.saved_states.insert(key, ((*element.widget).type_id(), state));
+				}
+			}
+			self.save_children_states(root, child, path);
+			path.pop();
+		}
+	}
+
+	/// Let the widgets added since the last replacement take over the saved states.
+	fn restore_saved_states(&mut self) {
+		let fresh_widgets = std::mem::take(&mut self.fresh_widgets);
+		if !self.saved_states.is_empty() {
+			for id in fresh_widgets {
+				let by_alias = self.inversed_alias_map.get(&id).and_then(|alias| self.saved_states.remove(&StateKey::Alias(alias.clone())));
+				let Some((type_id, state)) = by_alias.or_else(|| {
+					let key = self.replaced_path(id)?;
+					self.saved_states.remove(&key)
+				}) else { continue };
+				if let Some(element) = self.widgets.get_mut(&id) {
+					if (*element.widget).type_id() == type_id {
+						element.widget.restore_state(state);
+					}
+				}
+			}
 		}
+		self.saved_states.clear();
+		self.replaced_roots.clear();
+	}
 
+	/// Get the child indices from the closest replaced ancestor to the widget.
+	fn replaced_path(&self, id: LayoutId) -> Option<StateKey> {
+		let mut path = vec!();
+		let mut current = id;
+		loop {
+			let parent = *self.inverse_tree.get(&current)?;
+			if parent == current {
+				return None;
+			}
+			path.push(self.tree.get(&parent)?.iter().position(|child| *child == current)?);
+			if self.replaced_roots.contains(&parent) {
+				path.reverse();
+				return Some(StateKey::Path(parent, path));
+			}
+			current = parent;
+		}
+	}
+
+	/// Override the region receiving touches of the given widget, see [`HitRegion`].
+	/// 
+	/// Takes effect from the next layout pass.
+	pub fn set_hit_region(&mut self, id: LayoutId, region: HitRegion) {
+		if let Some(element) = self.widgets.get_mut(&id) {
+			element.redraw_request = true;
+			self.hit_regions.insert(id, region);
+		}
+	}
+
+	/// Set the z-index of the given widget, `None` to use [`Widget::z_index`] again.
+	/// 
+	/// Widgets with a larger z-index are drawn above the others of the same tree and receive the touches first.
+	pub fn set_z_index(&mut self, id: LayoutId, z_index: Option<i32>) {
+		if let Some(element) = self.widgets.get_mut(&id) {
+			element.redraw_request = true;
+			element.z_index = z_index;
+		}
+	}
+
+	/// Get the z-index the given widget is drawn with.
+	/// 
+	/// A widget with a z-index of `0` inherits the one of its nearest parent with a non-zero z-index.
+	pub fn z_index(&self, id: LayoutId) -> i32 {
+		let mut current = id;
+		loop {
+			let z_index = self.widgets.get(&current).map_or(0, Self::own_z_index);
+			if z_index != 0 {
+				return z_index;
+			}
+			match self.inverse_tree.get(&current) {
+				// the root is its own parent.
+				Some(parent) if *parent != current => current = *parent,
+				_ => return 0,
+			}
+		}
+	}
+
+	fn own_z_index(element: &LayoutElement<S, A>) -> i32 {
+		element.z_index.unwrap_or_else(|| element.widget.z_index())
+	}
+
+	/// Remove the hit region override of the given widget, returns the removed region.
+	pub fn remove_hit_region(&mut self, id: LayoutId) -> Option<HitRegion> {
+		let out = self.hit_regions.remove(&id);
+		if let Some(element) = self.widgets.get_mut(&id) {
+			element.redraw_request = true;
+		}
+		out
+	}
+
+	/// Get the region receiving touches of the given widget,
+	/// which is the override set by [`Self::set_hit_region`] or the one declared by the widget.
+	pub fn hit_region(&self, id: LayoutId) -> Option<HitRegion> {
+		self.hit_regions.get(&id).cloned().or_else(|| self.widgets.get(&id).map(|element| element.widget.hit_region()))
+	}
+
+	/// Check whether the absolute point hits the given widget, returns false if the widget is not laid out.
+	pub fn hit_test(&self, id: LayoutId, point: Vec2) -> bool {
+		let Some((area, pos)) = self.widgets.get(&id).and_then(|element| element.area_and_pos) else { return false };
+		self.hit_bounds.get(&id).is_some_and(|bounds| bounds.contains(point))
+			&& self.hit_region(id).is_some_and(|region| region.contains(area, pos, point))
+	}
+
+	/// Update the rect of the widget in the rtree, `None` to remove it.
+	fn bind_hit_bounds(&mut self, id: LayoutId, bounds: Option<Rect>) {
+		if let Some(rect) = self.hit_bounds.remove(&id) {
+			self.rtree.remove(&RstarBinding { id, rect });
+		}
+		if let Some(rect) = bounds {
+			self.rtree.insert(RstarBinding { id, rect });
+			self.hit_bounds.insert(id, rect);
+		}
+	}
+
+	/// Render the given widget and its children at a higher internal resolution.
+	/// 
+	/// Pixels inside the widget's area will be sampled `factor.ceil()` times in each direction and averaged,
+	/// which is useful for text-heavy panels while keeping the rest of the window at the window's quality factor.
+	/// Factors less than or equal to 1.0 have no effect and the factor is clamped to [`crate::render::painter::MAX_SUPERSAMPLE_FACTOR`].
+	/// 
+	/// Only the first [`crate::render::painter::MAX_SUPERSAMPLE_REGIONS`] visible regions will be used.
+	pub fn set_quality_factor(&mut self, id: LayoutId, factor: f32) {
+		if let Some(element) = self.widgets.get_mut(&id) {
+			self.quality_factors.insert(id, factor);
+			element.redraw_request = true;
+		}
+	}
+
+	/// Set the quality factor of a widget by its alias, see [`Self::set_quality_factor`].
+	pub fn set_quality_factor_by_alias(&mut self, alias: impl Into<String>, factor: f32) {
+		if let Some(id) = self.alias_to_id(alias) {
+			self.set_quality_factor(id, factor);
+		}
+	}
+
+	/// Remove the quality factor of a widget, returns the removed factor if any.
+	pub fn remove_quality_factor(&mut self, id: LayoutId) -> Option<f32> {
+		let out = self.quality_factors.remove(&id);
+		if out.is_some() {
+			if let Some(element) = self.widgets.get_mut(&id) {
+				element.redraw_request = true;
+			}
+		}
 		out
 	}
 
+	/// Get the quality factor of a widget set by [`Self::set_quality_factor`].
+	pub fn quality_factor(&self, id: LayoutId) -> Option<f32> {
+		self.quality_factors.get(&id).copied()
+	}
+
+	/// Attach a custom draw hook to a widget, drawn on top of the widget after its own draw.
+	/// 
+	/// The hook gets the painter positioned at the left top of the widget and the area of the widget in the painter's coordinates,
+	/// useful for drawing a debug border, a badge or a highlight around an existing widget without writing a wrapper widget.
+	/// Painter states such as the fill mode are reset before each hook.
+	/// Multiple hooks on the same widget are drawn in the order they are attached.
+	pub fn decorate(&mut self, id: LayoutId, draw: impl Fn(&mut Painter, Rect) + 'static) {
+		self.insert_decorator(id, false, Box::new(draw));
+	}
+
+	/// Attach a custom draw hook to a widget, drawn before the widget so it appears behind the widget.
+	/// 
+	/// See [`Self::decorate`] for details.
+	pub fn decorate_behind(&mut self, id: LayoutId, draw: impl Fn(&mut Painter, Rect) + 'static) {
+		self.insert_decorator(id, true, Box::new(draw));
+	}
+
+	/// Attach a custom draw hook to a widget by its alias, see [`Self::decorate`].
+	pub fn decorate_by_alias(&mut self, alias: impl Into<String>, draw: impl Fn(&mut Painter, Rect) + 'static) {
+		if let Some(id) = self.alias_to_id(alias) {
+			self.decorate(id, draw);
+		}
+	}
+
+	/// Remove all the draw hooks attached to a widget, returns true if there were any.
+	pub fn remove_decorators(&mut self, id: LayoutId) -> bool {
+		let out = self.decorators.remove(&id).is_some();
+		if out {
+			if let Some(element) = self.widgets.get_mut(&id) {
+				element.redraw_request = true;
+			}
+		}
+		out
+	}
+
+	#[allow(clippy::type_complexity)]
+	fn insert_decorator(&mut self, id: LayoutId, behind: bool, draw: Box<dyn Fn(&mut Painter, Rect)>) {
+		if let Some(element) = self.widgets.get_mut(&id) {
+			self.decorators.entry(id).or_default().push(Decorator { behind, draw });
+			element.redraw_request = true;
+		}
+	}
+
+	/// Attach a context menu to a widget, opened at the pointer when the widget or its children are right clicked.
+	/// 
+	/// The menu of the innermost widget is opened, the signals of its items are sent from the widget it is attached to.
+	/// Widgets opening a menu of their own, such as [`crate::widgets::inputbox::InputBox`], take precedence.
+	/// Attaching another menu replaces the previous one.
+	pub fn set_context_menu(&mut self, id: LayoutId, menu: ContextMenu<S, A>) {
+		if self.contains(id) {
+			self.context_menus.insert(id, menu);
+		}
+	}
+
+	/// Attach a context menu to a widget by its alias, see [`Self::set_context_menu`].
+	pub fn set_context_menu_by_alias(&mut self, alias: impl Into<String>, menu: ContextMenu<S, A>) {
+		if let Some(id) = self.alias_to_id(alias) {
+			self.set_context_menu(id, menu);
+		}
+	}
+
+	/// Remove the context menu attached to a widget, opened menus are not closed.
+	pub fn remove_context_menu(&mut self, id: LayoutId) -> Option<ContextMenu<S, A>> {
+		self.context_menus.remove(&id)
+	}
+
+	/// Find the innermost widget at the position with a context menu attached.
+	fn context_menu_target(&self, pos: Vec2) -> Option<LayoutId> {
+		let target = self.widget_at(pos);
+		let mut candidates = self.get_parents(target);
+		candidates.push(target);
+		candidates.into_iter().rev().find(|id| self.context_menus.contains_key(id))
+	}
+
+	/// Open the context menu attached to the innermost widget at the position.
+	fn open_context_menu(&mut self, pos: Vec2) {
+		let Some(menu) = self.context_menu_target(pos).and_then(|id| self.context_menus.get(&id).map(|menu| menu.opened_for(id))) else {
+			return;
+		};
+		self.push_overlay(OverlayAnchor::Popup(pos), menu);
+	}
+
+	/// Open the context menu under a finger held still for [`InputState::long_press_time`],
+	/// and redraw the ring showing how long it has been held.
+	fn update_long_press(&mut self, state: &mut InputState<S>) {
+		let last = self.long_press.take();
+		if let Some(last) = &last {
+			if last.shows_indicator() {
+				self.add_damage(last.indicator_area());
+				state.request_redraw();
+			}
+		}
+		let (Some(hold), Some((touch, pos, elapsed))) = (state.long_press_time, state.long_press_candidate()) else {
+			return;
+		};
+		if last.as_ref().is_some_and(|last| last.touch == touch && last.opened) {
+			self.long_press = last;
+			return;
+		}
+		if self.context_menu_target(pos).is_none() {
+			return;
+		}
+		let progress = (elapsed.as_seconds_f32() / hold.as_seconds_f32().max(f32::EPSILON)).min(1.0);
+		let opened = progress >= 1.0;
+		if opened {
+			self.open_context_menu(pos);
+			state.cancel_touch_click(touch);
+		}else {
+			state.request_frame_after(Duration::milliseconds(16));
+		}
+		let long_press = LongPress { touch, pos, progress, opened };
+		if long_press.shows_indicator() {
+			self.add_damage(long_press.indicator_area());
+			state.request_redraw();
+		}
+		self.long_press = Some(long_press);
+	}
+
+	/// Draw the ring around the finger long pressing, over everything else.
+	fn paint_long_press(&self, painter: &mut Painter) {
+		let Some(long_press) = &self.long_press else { return };
+		if !long_press.shows_indicator() {
+			return;
+		}
+		painter.set_interaction(Interaction::default());
+		painter.set_opacity(1.0);
+		painter.set_clip_rect(Rect::from_size(painter.window_size));
+		painter.set_relative_to(Vec2::ZERO);
+		painter.reset_blend_mode();
+		painter.reset_transform();
+		let mut track = FillMode::Color(PRIMARY_COLOR);
+		track.mul_alpha(0.3);
+		painter.set_fill_mode(track);
+		painter.draw_stroked_circle(long_press.pos, LONG_PRESS_INDICATOR_RADIUS, LONG_PRESS_INDICATOR_WIDTH);
+		painter.set_fill_mode(PRIMARY_COLOR);
+		// starting from the top.
+		painter.draw_arc(long_press.pos, LONG_PRESS_INDICATOR_RADIUS, LONG_PRESS_INDICATOR_WIDTH, -std::f32::consts::FRAC_PI_2, long_press.progress * std::f32::consts::TAU);
+		painter.reset_fill_mode();
+	}
+
+	/// Attach a tooltip to a widget, shown when the pointer rests on the widget or its children without a tooltip of their own.
+	/// Attaching another tooltip replaces the previous one.
+	pub fn set_tooltip(&mut self, id: LayoutId, tooltip: Tooltip<S, A>) {
+		if self.contains(id) {
+			self.tooltips.insert(id, tooltip);
+		}
+	}
+
+	/// Attach a tooltip to a widget by its alias, see [`Self::set_tooltip`].
+	pub fn set_tooltip_by_alias(&mut self, alias: impl Into<String>, tooltip: Tooltip<S, A>) {
+		if let Some(id) = self.alias_to_id(alias) {
+			self.set_tooltip(id, tooltip);
+		}
+	}
+
+	/// Remove the tooltip attached to a widget, hiding it if shown.
+	pub fn remove_tooltip(&mut self, id: LayoutId) -> Option<Tooltip<S, A>> {
+		if self.tooltip_hover.as_ref().is_some_and(|hover| hover.id == id) {
+			self.hide_tooltip();
+		}
+		self.tooltips.remove(&id)
+	}
+
+	/// Fade the hover and press factors of the widgets tracking them, and redraw the widgets whose factors changed.
+	fn update_interactions(&mut self, state: &mut InputState<S>) {
+		let mut hovered = HashSet::new();
+		let mut pressed = HashSet::new();
+		let pointers = state.hover_pos().map(|pos| (pos, false)).into_iter()
+			.chain(state.pressing_positions().into_iter().map(|pos| (pos, true)));
+		for (pos, is_pressing) in pointers {
+			let target = self.widget_at(pos);
+			let mut chain = self.get_parents(target);
+			chain.push(target);
+			for id in chain {
+				hovered.insert(id);
+				if is_pressing {
+					pressed.insert(id);
+				}
+			}
+		}
+
+		// a long idle time would skip the fade otherwise.
+		let step = state.delta_time().as_seconds_f32().min(1.0 / 30.0) / INTERACTION_FADE;
+		let approach = |value: f32, target: bool| if target { (value + step).min(1.0) } else { (value - step).max(0.0) };
+		let candidates = hovered.iter().copied().chain(self.interactions.keys().copied()).collect::<HashSet<_>>();
+		let mut fading = false;
+		for id in candidates {
+			let Some(element) = self.widgets.get_mut(&id) else {
+				self.interactions.remove(&id);
+				continue;
+			};
+			if !element.widget.tracks_interaction() {
+				continue;
+			}
+			let current = self.interactions.get(&id).copied().unwrap_or_default();
+			let target = (hovered.contains(&id), pressed.contains(&id));
+			let next = Interaction {
+				hover: approach(current.hover, target.0),
+				press: approach(current.press, target.1),
+			};
+			if next != current {
+				element.redraw_request = true;
+				fading = true;
+			}
+			if next.is_idle() {
+				self.interactions.remove(&id);
+			}else {
+				self.interactions.insert(id, next);
+			}
+		}
+		if fading {
+			state.request_frame_after(Duration::milliseconds(16));
+		}
+	}
+
+	/// Redraw the widgets fading in, and drop the replaced widgets once their fade ended.
+	fn update_crossfades(&mut self, state: &mut InputState<S>) {
+		if self.crossfades.is_empty() {
+			return;
+		}
+		let mut finished = vec!();
+		for (id, crossfade) in &self.crossfades {
+			if let Some(element) = self.widgets.get_mut(id) {
+				// redraw once more after the fade ended, at full opacity.
+				element.redraw_request = true;
+			}
+			if !crossfade.progress.is_animating() {
+				finished.push(*id);
+			}
+		}
+		for id in finished {
+			self.crossfades.remove(&id);
+		}
+		state.request_frame_after(Duration::milliseconds(16));
+	}
+
+	/// Get how much the widget is hovered and pressed, see [`Widget::tracks_interaction`].
+	pub fn interaction(&self, id: LayoutId) -> Interaction {
+		self.interactions.get(&id).copied().unwrap_or_default()
+	}
+
+	/// Show the tooltip of the widget the pointer rests on once its delay passed, hide it when the pointer leaves or presses.
+	fn update_tooltip(&mut self, state: &mut InputState<S>) {
+		if self.tooltips.is_empty() && self.tooltip_hover.is_none() {
+			return;
+		}
+		let pointer = state.hover_pos();
+		let hovered = self.tooltip_hover.as_ref().map(|hover| (hover.id, hover.shown));
+		let target = pointer.and_then(|pos| {
+			let target = self.widget_at(pos);
+			if let Some((id, Some(shown))) = hovered {
+				if self.overlay_of(target) == Some(shown) {
+					// the pointer moved onto the tooltip itself.
+					return Some(id);
+				}
+			}
+			let mut candidates = self.get_parents(target);
+			candidates.push(target);
+			candidates.into_iter().rev().find(|id| self.tooltips.contains_key(id))
+		});
+		if hovered.map(|(id, _)| id) != target {
+			self.hide_tooltip();
+			self.tooltip_hover = target.map(|id| TooltipHover { id, since: state.program_running_time(), shown: None, dismissed: false });
+		}
+
+		let Some(hover) = &self.tooltip_hover else { return };
+		if !state.pressed_positions().is_empty() {
+			self.hide_tooltip();
+			if let Some(hover) = &mut self.tooltip_hover {
+				hover.dismissed = true;
+			}
+			return;
+		}
+		if hover.dismissed || hover.shown.is_some() {
+			return;
+		}
+		let (Some(pointer), Some(tooltip)) = (pointer, self.tooltips.get(&hover.id)) else { return };
+		let elapsed = state.program_running_time() - hover.since;
+		if elapsed < tooltip.inner.delay {
+			state.request_frame_after(tooltip.inner.delay - elapsed);
+			return;
+		}
+		let tooltip = tooltip.clone();
+		let shown = self.push_overlay(OverlayAnchor::Popup(pointer + TOOLTIP_OFFSET), tooltip);
+		if let Some(hover) = &mut self.tooltip_hover {
+			hover.shown = Some(shown);
+		}
+	}
+
+	/// Remove the overlay showing the tooltip, if any.
+	fn hide_tooltip(&mut self) {
+		if let Some(shown) = self.tooltip_hover.as_mut().and_then(|hover| hover.shown.take()) {
+			if self.is_overlay(shown) {
+				self.remove_widget(shown);
+			}
+		}
+	}
+
+	/// Get the widget with the keyboard focus, see [`Widget::focusable`].
+	pub fn focused(&self) -> Option<LayoutId> {
+		self.focused
+	}
+
+	/// Move the keyboard focus to the widget, the focus ring is shown if the focus was last moved by the keyboard.
+	/// 
+	/// The widget does not have to be [`Widget::focusable`], e.g. to focus a container so the keyboard scrolls it.
+	pub fn set_focus(&mut self, id: LayoutId) {
+		if self.contains(id) {
+			self.change_focus(Some(id), self.focus_visible);
+		}
+	}
+
+	/// Remove the keyboard focus.
+	pub fn clear_focus(&mut self) {
+		self.change_focus(None, false);
+	}
+
+	/// Move the keyboard focus to the next focusable widget in the order of the layout tree, or the previous one if `backward`,
+	/// as Tab and Shift+Tab do. The focus wraps around, and the widgets not laid out are skipped.
+	/// 
+	/// Returns the newly focused widget.
+	pub fn focus_next(&mut self, backward: bool) -> Option<LayoutId> {
+		let mut order = vec!();
+		let roots = std::iter::once(ROOT_LAYOUT_ID).chain(self.overlays()).filter(|root| !self.is_blocked(*root)).collect::<Vec<_>>();
+		for root in roots {
+			self.collect_focusable(root, &mut order);
+		}
+		if order.is_empty() {
+			return None;
+		}
+		let current = self.focused.and_then(|focused| order.iter().position(|id| *id == focused));
+		let next = match (current, backward) {
+			(Some(index), false) => (index + 1) % order.len(),
+			(Some(index), true) => (index + order.len() - 1) % order.len(),
+			(None, false) => 0,
+			(None, true) => order.len() - 1,
+		};
+		self.change_focus(Some(order[next]), true);
+		Some(order[next])
+	}
+
+	/// Collect the focusable widgets laid out in the subtree in depth-first order.
+	fn collect_focusable(&self, id: LayoutId, out: &mut Vec<LayoutId>) {
+		let Some(element) = self.widgets.get(&id) else { return };
+		if element.area_and_pos.is_none() {
+			return;
+		}
+		if element.widget.focusable() {
+			out.push(id);
+		}
+		for child in self.tree.get(&id).map(|children| children.as_slice()).unwrap_or_default() {
+			self.collect_focusable(*child, out);
+		}
+	}
+
+	/// Move the focus, redrawing the focus rings and letting both widgets notice the change in the next event frame.
+	fn change_focus(&mut self, focused: Option<LayoutId>, visible: bool) {
+		if self.focused == focused && self.focus_visible == visible {
+			return;
+		}
+		for id in [self.focused, focused].into_iter().flatten() {
+			if let Some(element) = self.widgets.get_mut(&id) {
+				element.redraw_request = true;
+				self.secondary_widgets.insert(id, 0);
+			}
+		}
+		self.focused = focused;
+		self.focus_visible = visible;
+		if let Some(id) = focused.filter(|_| visible) {
+			self.pending_reveal = Some((id, REVEAL_PASSES));
+		}
+	}
+
+	/// Scroll the widgets around the focused one after a layout pass if the focus was moved by the keyboard,
+	/// or the focused widget was just laid out, e.g. in a [`crate::widgets::collapse::Collapse`] opened.
+	fn reveal_focused(&mut self) {
+		let shown = self.focused.map(|id| (id, self.widgets.get(&id).is_some_and(|element| element.area_and_pos.is_some())));
+		if let (Some((id, true)), Some((last, false))) = (shown, std::mem::replace(&mut self.focus_shown, shown)) {
+			if id == last {
+				self.pending_reveal = Some((id, REVEAL_PASSES));
+			}
+		}
+		if let Some((id, passes)) = self.pending_reveal.take() {
+			self.scroll_to_widget(id);
+			if passes > 1 {
+				self.pending_reveal = Some((id, passes - 1));
+			}
+		}
+	}
+
+	/// Scroll the containers around the widget with animation so that it becomes visible,
+	/// with a margin of [`SCROLL_REVEAL_MARGIN`] around it. Returns whether any container scrolled.
+	/// 
+	/// The containers are scrolled from the innermost one outwards, see [`Widget::scroll_to_reveal`].
+	/// Nothing is scrolled if the widget is not laid out, such as the children of a collapsed [`crate::widgets::collapse::Collapse`].
+	/// 
+	/// The keyboard focus is scrolled to automatically when moved by the keyboard, or when the focused widget is revealed.
+	pub fn scroll_to_widget(&mut self, id: LayoutId) -> bool {
+		let Some((area, pos)) = self.widgets.get(&id).and_then(|element| element.area_and_pos) else { return false };
+		let size = self.full_sizes.get(&id).copied().unwrap_or(area.size());
+		let mut area = Rect::from_lt_size(pos, size).shrink(- Vec2::same(SCROLL_REVEAL_MARGIN));
+		let mut scrolled = false;
+		for parent in self.get_parents(id).into_iter().rev() {
+			let size = self.full_sizes.get(&parent).copied();
+			let Some(element) = self.widgets.get_mut(&parent) else { break };
+			let Some((parent_area, parent_pos)) = element.area_and_pos else { break };
+			let shift = element.widget.scroll_to_reveal(area.move_by(- parent_pos));
+			if shift != Vec2::ZERO {
+				scrolled = true;
+				element.redraw_request = true;
+				self.secondary_widgets.insert(parent, 0);
+			}
+			// only the part shown by the container is left for the containers around it to reveal.
+			area = area.move_by(- shift) & Rect::from_lt_size(parent_pos, size.unwrap_or(parent_area.size()));
+		}
+		scrolled
+	}
+
+	/// Keep the focus inside of the topmost modal overlay once it is laid out, and give it back when the overlay is gone.
+	fn trap_focus(&mut self) {
+		let modal = self.modal().filter(|id| self.widgets.get(id).is_some_and(|element| element.area_and_pos.is_some()));
+		match (self.modal_focus, modal) {
+			(Some((trapped, _)), Some(modal)) if trapped == modal => {},
+			(trapped, Some(modal)) => {
+				// a modal opened over another one gives the focus back to where it was before the first.
+				let previous = trapped.map_or(self.focused, |(_, previous)| previous);
+				self.modal_focus = Some((modal, previous));
+				let mut order = vec!();
+				self.collect_focusable(modal, &mut order);
+				self.change_focus(order.first().copied(), self.focus_visible && !order.is_empty());
+			},
+			(Some((_, previous)), None) => {
+				self.modal_focus = None;
+				let previous = previous.filter(|id| self.contains(*id) && !self.is_blocked(*id));
+				self.change_focus(previous, self.focus_visible && previous.is_some());
+			},
+			(None, None) => {},
+		}
+		if self.modal_focus.is_some() && self.focused.is_some_and(|id| self.is_blocked(id)) {
+			self.change_focus(None, false);
+		}
+	}
+
+	/// Focus the innermost focusable widget under the press, presses in overlays without one keep the focus.
+	fn focus_pressed(&mut self, pos: Vec2) {
+		let target = self.widget_at(pos);
+		let mut candidates = self.get_parents(target);
+		candidates.push(target);
+		let focusable = candidates.into_iter().rev()
+			.find(|id| self.widgets.get(id).is_some_and(|element| element.widget.focusable()));
+		match focusable {
+			Some(id) => self.change_focus(Some(id), false),
+			None if self.overlay_of(target).is_some() => {},
+			None => self.change_focus(None, false),
+		}
+	}
+
+	/// Start running [`Widget::update`] of the widget if it has an update interval.
+	fn track_update(&mut self, id: LayoutId, interval: Option<Duration>) {
+		if interval.is_some() {
+			self.updating.entry(id).or_insert(None);
+		}
+	}
+
+	/// Run [`Widget::update`] of the widgets whose steps are due at the running time `now`.
+	/// 
+	/// Returns the earliest running time the next step is due, `None` if no widget is updated.
+	pub(crate) fn handle_updates(&mut self, now: Duration) -> Option<Duration> {
+		let mut next: Option<Duration> = None;
+		self.updating.retain(|id, due| {
+			let Some(element) = self.widgets.get_mut(id) else {
+				return false;
+			};
+			let Some(interval) = element.widget.update_interval().filter(|interval| interval.is_positive()) else {
+				return false;
+			};
+			let due = due.get_or_insert(now + interval);
+			let mut steps = 0;
+			while *due <= now {
+				if steps == MAX_UPDATE_STEPS {
+					*due = now + interval;
+					break;
+				}
+				element.redraw_request |= element.widget.update(interval);
+				*due += interval;
+				steps += 1;
+			}
+			next = Some(next.map_or(*due, |next| next.min(*due)));
+			true
+		});
+		next
+	}
+
 	/// Turn an alias to an id.
 	pub fn alias_to_id(&self, alias: impl Into<String>) -> Option<LayoutId> {
 		self.alias_map.get(&alias.into()).cloned()
 	}
 
-	/// Turn an id to an alias.
-	pub fn id_to_alias(&self, id: LayoutId) -> Option<&str> {
-		self.inversed_alias_map.get(&id).map(|x| x.as_str())
+	/// Turn an alias to an id.
+	/// 
+	/// Returns an error if the alias is not registered.
+	pub fn try_alias_to_id(&self, alias: impl Into<String>) -> Result<LayoutId, LayoutError> {
+		let alias = alias.into();
+		self.alias_map.get(&alias).cloned().ok_or(LayoutError::AliasNotFound(alias))
+	}
+
+	/// Turn an id to an alias.
+	pub fn id_to_alias(&self, id: LayoutId) -> Option<&str> {
+		self.inversed_alias_map.get(&id).map(|x| x.as_str())
+	}
+
+	/// Name the widget in errors and logs, which makes it easier to tell which widget went wrong.
+	/// 
+	/// The name is kept when the widget is replaced, and does nothing if the widget is not in the layout.
+	pub fn set_debug_name(&mut self, id: LayoutId, name: impl Into<String>) {
+		if let Some(element) = self.widgets.get_mut(&id) {
+			element.debug_name = Some(name.into());
+		}
+	}
+
+	/// Get the name describing the widget in errors and logs.
+	/// 
+	/// This is the name given by [`Self::set_debug_name`], or the alias of the widget,
+	/// or the type name of the widget without module paths, in that order.
+	/// Returns `None` if the widget is not in the layout.
+	pub fn debug_name(&self, id: LayoutId) -> Option<String> {
+		let element = self.widgets.get(&id)?;
+		Some(element.debug_name.clone()
+			.or_else(|| self.inversed_alias_map.get(&id).cloned())
+			.unwrap_or_else(|| short_type_name(element.widget.type_name())))
 	}
 
 	/// Replace the given widget by its alias, will return the old widget and its children if any.
@@ -321,6 +1696,18 @@ impl<S: Signal, A: App<Signal = S>> Layout<S, A> {
 		}
 	}
 
+	/// Replace the given widget by its alias, will return the old widget and its children if any.
+	/// 
+	/// Returns an error if the alias is not registered or the widget is not in the layout.
+	pub fn try_replace_widget_by_alias(
+		&mut self, 
+		alias: impl Into<String>,
+		widget: impl Widget<Signal = S, Application = A>
+	) -> Result<Vec<Box<dyn Widget<Signal = S, Application = A>>>, LayoutError> {
+		let id = self.try_alias_to_id(alias)?;
+		self.try_replace_widget(id, widget)
+	}
+
 	/// Get the widget by its id.
 	pub fn get_widget<T: Widget<Signal = S, Application = A> + Any>(&self, id: LayoutId) -> Option<&T> {
 		if let Some(inner) = self.widgets.get(&id) {
@@ -340,6 +1727,27 @@ impl<S: Signal, A: App<Signal = S>> Layout<S, A> {
 		}
 	}
 
+	/// Get the widget by its id.
+	/// 
+	/// Returns an error if the widget is not in the layout or is not of type `T`.
+	pub fn try_get_widget<T: Widget<Signal = S, Application = A> + Any>(&self, id: LayoutId) -> Result<&T, LayoutError> {
+		self.validate_id(id)?;
+		let element = self.widgets.get(&id).ok_or(LayoutError::WidgetNotFound(id))?;
+		element.widget.downcast_ref::<T>().ok_or_else(|| LayoutError::TypeMismatch {
+			id,
+			found: self.debug_name(id).unwrap_or_default(),
+			expected: std::any::type_name::<T>(),
+		})
+	}
+
+	/// Get the widget by its alias.
+	/// 
+	/// Returns an error if the alias is not registered, the widget is not in the layout or is not of type `T`.
+	pub fn try_get_widget_by_alias<T: Widget<Signal = S, Application = A> + Any>(&self, alias: impl Into<String>) -> Result<&T, LayoutError> {
+		let id = self.try_alias_to_id(alias)?;
+		self.try_get_widget(id)
+	}
+
 	/// Get the widget mutably by its id.
 	/// 
 	/// This function will automatically mark the widget as dirty.
@@ -347,26 +1755,59 @@ impl<S: Signal, A: App<Signal = S>> Layout<S, A> {
 	/// Due to the limitation of Rust's type system, we cannot return a mutable reference to the widget.
 	/// 
 	/// Instead, we will use a closure to modify the widget.
+	/// 
+	/// Will do nothing if the widget is not in the layout or is not of type `W`,
+	/// see [`Self::try_widget_mut`] to detect these cases.
 	pub fn widget_mut<W: Widget<Signal = S, Application = A> + Any>(&mut self, id: LayoutId, f: impl FnOnce(W) -> W) {
-		if let Some(element) = self.widgets.remove(&id) {
-			let area_and_pos = element.area_and_pos;
-			if element.widget.is::<W>() {
-				let widget = *unsafe { Box::from_raw(Box::into_raw(element.widget) as *mut W) };
-				let widget = f(widget);
-				self.widgets.insert(id, LayoutElement {
-					id,
-					area_and_pos,
-					widget: Box::new(widget),
-					redraw_request: true,
-				});
-			}else {
-				self.widgets.insert(id, LayoutElement {
-					id,
-					area_and_pos,
-					widget: element.widget,
-					redraw_request: true,
-				});
+		let _ = self.try_widget_mut(id, f);
+	}
+
+	/// Get the widget mutably by its id.
+	/// 
+	/// Same as [`Self::widget_mut`], but returns an error if the widget is not in the layout or is not of type `W`.
+	/// The widget will be marked as dirty as long as it is in the layout.
+	pub fn try_widget_mut<W: Widget<Signal = S, Application = A> + Any>(&mut self, id: LayoutId, f: impl FnOnce(W) -> W) -> Result<(), LayoutError> {
+		self.validate_id(id)?;
+		let element = self.widgets.remove(&id).ok_or(LayoutError::WidgetNotFound(id))?;
+		let area_and_pos = element.area_and_pos;
+		let debug_name = element.debug_name;
+		let z_index = element.z_index;
+		if element.widget.is::<W>() {
+			let widget = *unsafe { Box::from_raw(Box::into_raw(element.widget) as *mut W) };
+			let widget = f(widget);
+			match widget.event_handle_strategy() {
+				EventHandleStrategy::AlwaysPrimary => {
+					self.primary_widgets.insert(id, 0);
+				},
+				EventHandleStrategy::AlwaysSecondary => {
+					self.secondary_widgets.insert(id, 0);
+				},
+				_ => {},
 			}
+			self.track_update(id, widget.update_interval());
+			self.widgets.insert(id, LayoutElement {
+				id,
+				area_and_pos,
+				widget: Box::new(widget),
+				redraw_request: true,
+				debug_name,
+				z_index,
+			});
+			Ok(())
+		}else {
+			self.widgets.insert(id, LayoutElement {
+				id,
+				area_and_pos,
+				widget: element.widget,
+				redraw_request: true,
+				debug_name,
+				z_index,
+			});
+			Err(LayoutError::TypeMismatch {
+				id,
+				found: self.debug_name(id).unwrap_or_default(),
+				expected: std::any::type_name::<W>(),
+			})
 		}
 	}
 
@@ -384,6 +1825,15 @@ impl<S: Signal, A: App<Signal = S>> Layout<S, A> {
 		}
 	}
 
+	/// Get the widget mutably by its alias.
+	/// 
+	/// Same as [`Self::widget_mut_by_alias`], but returns an error if the alias is not registered, 
+	/// the widget is not in the layout or is not of type `W`.
+	pub fn try_widget_mut_by_alias<W: Widget<Signal = S, Application = A> + Any>(&mut self, alias: impl Into<String>, f: impl FnOnce(W) -> W) -> Result<(), LayoutError> {
+		let id = self.try_alias_to_id(alias)?;
+		self.try_widget_mut(id, f)
+	}
+
 	/// Get the area of a widget.
 	pub fn get_widget_area(&self, id: LayoutId) -> Option<Rect> {
 		if let Some(element) = self.widgets.get(&id) {
@@ -402,6 +1852,12 @@ impl<S: Signal, A: App<Signal = S>> Layout<S, A> {
 		}
 	}
 
+	/// Get the full size of a widget as of the last layout, the area may be smaller as it is clipped by the parents.
+	pub fn get_widget_size(&self, id: LayoutId) -> Option<Vec2> {
+		let (area, _) = self.widgets.get(&id)?.area_and_pos?;
+		Some(self.full_sizes.get(&id).copied().unwrap_or(area.size()))
+	}
+
 	/// Get the padding of a widget.
 	pub fn get_widget_padding(&self, id: LayoutId) -> Option<Vec2> {
 		self.widgets.get(&id).map(|inner| inner.widget.inner_padding())
@@ -428,9 +1884,9 @@ impl<S: Signal, A: App<Signal = S>> Layout<S, A> {
 		let mut current = Some(id);
 
 		while let Some(id) = current {
-			if let Some(id) = self.get_parent_id(id) {
-				out.push(id);
-				current = Some(id);
+			current = self.get_parent_id(id).filter(|parent| *parent != id);
+			if let Some(parent) = current {
+				out.push(parent);
 			}
 		}
 
@@ -456,6 +1912,28 @@ impl<S: Signal, A: App<Signal = S>> Layout<S, A> {
 		self.tree.get(&id).map(|x| x.as_slice())
 	}
 
+	/// Move the widget to the given position among the children of its parent, which decides the order they are laid out in.
+	/// 
+	/// The index is clamped to the number of children, does nothing for the root and overlay roots.
+	pub fn set_child_index(&mut self, id: LayoutId, index: usize) -> Result<(), LayoutError> {
+		self.validate_id(id)?;
+		let Some(parent_id) = self.inverse_tree.get(&id).copied().filter(|parent_id| *parent_id != id) else {
+			return Ok(());
+		};
+		let children = self.tree.entry(parent_id).or_default();
+		let Some(current) = children.iter().position(|child| *child == id) else {
+			return Ok(());
+		};
+		if current != index {
+			children.remove(current);
+			children.insert(index.min(children.len()), id);
+			if let Some(parent) = self.widgets.get_mut(&parent_id) {
+				parent.redraw_request = true;
+			}
+		}
+		Ok(())
+	}
+
 	/// Get the number of the widgets.
 	pub fn widgets(&self) -> usize {
 		self.widgets.len()
@@ -475,7 +1953,10 @@ impl<S: Signal, A: App<Signal = S>> Layout<S, A> {
 	}
 
 	fn widget_layer_inner(&self, id: LayoutId, layer_count: &mut usize) -> Option<()> {
-		let parent = *self.inverse_tree.get(&id)?;
+		let Some(parent) = self.inverse_tree.get(&id).copied() else {
+			// overlay roots are on the top layer of their own tree.
+			return self.is_overlay(id).then_some(());
+		};
 		*layer_count += 1;
 		if parent == ROOT_LAYOUT_ID {
 			return Some(());
@@ -526,6 +2007,29 @@ impl<S: Signal, A: App<Signal = S>> Layout<S, A> {
 		}
 	}
 
+	/// Collect the widgets to arrange in this layout pass, the dirty widgets, the ones requested and their ancestors.
+	/// 
+	/// The other widgets keep the areas of their children from the last pass unless their own area changes.
+	fn collect_relayout_path(&mut self) {
+		let mut path = HashSet::new();
+		let requests = std::mem::take(&mut self.relayout_requests);
+		let dirty = self.widgets.values().filter(|element| element.redraw_request).map(|element| element.id);
+		for id in dirty.chain(requests) {
+			let mut current = id;
+			while path.insert(current) {
+				match self.inverse_tree.get(&current) {
+					Some(parent) if *parent != current => current = *parent,
+					_ => break,
+				}
+			}
+		}
+		self.relayout_path = path;
+	}
+
+	/// Arrange the children of the widget in its area, then their children.
+	/// 
+	/// A subtree without dirty widgets keeps its last arrangement if the area of its root is the same,
+	/// and a widget is only asked to arrange its children again if it is dirty or the sizes of the children changed.
 	fn reanrrage_widgets(
 		&mut self, 
 		mut parent_window: Rect, 
@@ -540,6 +2044,11 @@ impl<S: Signal, A: App<Signal = S>> Layout<S, A> {
 			return;
 		};
 
+		let arranged = self.arrangements.get(&layout_id).filter(|arrangement| arrangement.window == parent_window && arrangement.pos == parent_pos);
+		if arranged.is_some() && !self.relayout_path.contains(&layout_id) {
+			return;
+		}
+
 		let mut children_set = children.iter().copied().collect::<IndexSet<_>>();
 
 		painter.set_relative_to(parent_pos);
@@ -547,6 +2056,20 @@ impl<S: Signal, A: App<Signal = S>> Layout<S, A> {
 			self.widgets.get(child_id).map(|child| (*child_id, child.widget.size(*child_id, painter, self)))
 		}).collect::<IndexMap<_, _>>();
 
+		let dirty = self.widgets.get(&layout_id).is_some_and(|element| element.redraw_request);
+		if !dirty && arranged.is_some_and(|arrangement| arrangement.sizes == children_size_map) {
+			// the children keep their areas, only the dirty widgets inside of them are arranged again.
+			for child_id in children {
+				if let Some((child_window, child_pos)) = self.widgets.get(&child_id).and_then(|child| child.area_and_pos) {
+					self.reanrrage_widgets(child_window, child_pos, child_id, painter, widget_to_remove);
+				}
+			}
+			return;
+		}
+		// the size of the widget may depend on how its children are arranged, so its parent measures it again next pass.
+		self.relayout_requests.insert(layout_id);
+		self.arrangements.insert(layout_id, Arrangement { window: parent_window, pos: parent_pos, sizes: children_size_map.clone() });
+
 		let mut children_size_map = if let Some(parent) = self.widgets.get_mut(&layout_id) {
 			if let Some((rect, _)) = parent.area_and_pos {
 				parent.widget.handle_child_layout(children_size_map, rect, layout_id)
@@ -565,12 +2088,18 @@ impl<S: Signal, A: App<Signal = S>> Layout<S, A> {
 			if let Some(child_window) = child_window {
 				if let Some(child) = self.widgets.get_mut(&child_id) {
 					let child_pos = parent_pos + child_window.lt();
+					self.full_sizes.insert(child_id, child_window.size());
 					let child_window = child_window.move_by(parent_pos) & parent_window;
-					if let Some((original_child_window, _)) = &child.area_and_pos {
-						self.rtree.remove(&RstarBinding { id: child_id, rect: *original_child_window });
+					let original = child.area_and_pos.replace((child_window, child_pos));
+					// a moved widget is drawn again, its children are moved and marked when arranged.
+					if original != child.area_and_pos {
+						child.redraw_request = true;
+						if let Some((original_area, _)) = original {
+							self.add_damage(original_area);
+						}
 					}
-					self.rtree.insert(RstarBinding { id: child_id, rect: child_window });
-					child.area_and_pos = Some((child_window, child_pos));
+					let hit_bounds = self.hit_region(child_id).unwrap_or_default().bounds(child_window, child_pos) & parent_window;
+					self.bind_hit_bounds(child_id, Some(hit_bounds));
 					self.reanrrage_widgets(child_window, child_pos, child_id, painter, widget_to_remove);
 					children_set.swap_remove(&child_id);
 				}
@@ -583,6 +2112,7 @@ impl<S: Signal, A: App<Signal = S>> Layout<S, A> {
 			if let Some(element) = self.widgets.get_mut(&id) {
 				element.area_and_pos = None;
 			}
+			self.arrangements.remove(&id);
 			if let Some(children) = self.tree.get(&id) {
 				for child_id in children {
 					children_set.insert(*child_id);
@@ -592,19 +2122,57 @@ impl<S: Signal, A: App<Signal = S>> Layout<S, A> {
 	}
 
 	/// Clear the layout.
+	/// 
+	/// Ids given out before will be stale afterwards.
 	pub fn clear(&mut self) {
+		let ids = self.widgets.keys().copied().collect::<Vec<_>>();
+		for id in ids {
+			let name = self.debug_name(id);
+			self.release_id(id, name);
+		}
 		self.widgets.clear();
 		self.tree.clear();
 		self.inverse_tree.clear();
-		self.next_id = 1;
+		self.primary_widgets.clear();
+		self.secondary_widgets.clear();
 		self.alias_map.clear();
+		self.inversed_alias_map.clear();
+		self.quality_factors.clear();
+		self.decorators.clear();
+		self.composites.clear();
+		self.overlays.clear();
+		self.damage = Damage::default();
+		self.crossfades.clear();
+		self.command_cache.clear();
+		self.long_press = None;
+		self.hit_regions.clear();
+		self.hit_bounds.clear();
+		self.rtree = RTree::new();
+		self.saved_states.clear();
+		self.replaced_roots.clear();
+		self.fresh_widgets.clear();
+		self.reported_dirty.clear();
+		self.scroll_focus = ROOT_LAYOUT_ID;
+		self.context_menus.clear();
+		self.tooltips.clear();
+		self.tooltip_hover = None;
+		self.updating.clear();
+		self.focused = None;
+		self.modal_focus = None;
+		self.focus_shown = None;
+		self.pending_reveal = None;
+		self.full_sizes.clear();
+		self.arrangements.clear();
+		self.relayout_requests.clear();
 	}
 
-	pub(crate) fn handle_draw(&mut self, painter: &mut Painter, window_size: Vec2) -> Option<Rect> {
+	pub(crate) fn handle_draw(&mut self, painter: &mut Painter, window_size: Vec2) -> Damage {
 		let mut widget_to_remove = vec!();
 
+		self.restore_saved_states();
 		self.sperate_dirty_widgets();
-		// self.quad_tree = QuadTree::new(Rect::from_size(window_size));
+		self.notify_dirty();
+		self.collect_relayout_path();
 		self.reanrrage_widgets(
 			Rect::from_size(window_size), 
 			Vec2::ZERO, 
@@ -615,11 +2183,106 @@ impl<S: Signal, A: App<Signal = S>> Layout<S, A> {
 		// #[cfg(debug_assertions)]
 		// self.check_overlap(vec![ROOT_LAYOUT_ID]);
 
+		self.arrange_overlays(Rect::from_size(window_size), painter, &mut widget_to_remove);
+
 		for id in widget_to_remove {
 			self.remove_widget(id);
 		}
+		#[cfg(debug_assertions)]
+		self.check_aliases();
+		self.reveal_focused();
+
+		let refresh_area = self.handle_paint(painter);
+		#[cfg(feature = "bench")]
+		crate::bench::count_layout_pass();
+		log::trace!("Layout pass over {} widgets, refresh area: {:?}", self.widgets.len(), refresh_area.rects());
+
+		let window = Rect::from_size(window_size);
+		for (id, factor) in &self.quality_factors {
+			if let Some((area, _)) = self.widgets.get(id).and_then(|element| element.area_and_pos) {
+				let area = area & window;
+				if !area.is_empty() {
+					painter.push_supersample_region(area, *factor);
+				}
+			}
+		}
+
+		refresh_area
+	}
+
+	/// Place the overlays by their anchors, then arrange their children.
+	fn arrange_overlays(&mut self, window: Rect, painter: &mut Painter, widget_to_remove: &mut Vec<LayoutId>) {
+		let overlays = self.overlays.iter().map(|overlay| (overlay.id, overlay.anchor)).collect::<Vec<_>>();
+		for (id, anchor) in overlays {
+			painter.set_relative_to(Vec2::ZERO);
+			let lt = match anchor {
+				OverlayAnchor::Screen(pos) => Some(pos),
+				OverlayAnchor::Popup(pos) => self.widgets.get(&id).map(|element| {
+					let size = element.widget.size(id, painter, self);
+					let flip = |pos: f32, size: f32, max: f32| if pos + size > max { (pos - size).max(0.0) } else { pos };
+					Vec2::new(flip(pos.x, size.x, window.x + window.w), flip(pos.y, size.y, window.y + window.h))
+				}),
+				OverlayAnchor::Widget { id: anchor_id, align, offset } => {
+					if !self.contains(anchor_id) {
+						widget_to_remove.push(id);
+						continue;
+					}
+					self.widgets.get(&anchor_id)
+						.and_then(|element| element.area_and_pos)
+						.filter(|(area, _)| !area.is_empty())
+						.map(|(area, _)| area.lt() + area.size() * align + offset)
+				},
+			};
+			let area_and_pos = match (lt, self.widgets.get(&id)) {
+				(Some(lt), Some(element)) => {
+					let size = element.widget.size(id, painter, self);
+					self.full_sizes.insert(id, size);
+					Some((Rect::from_lt_size(lt, size) & window, lt))
+				},
+				_ => None,
+			};
+			let Some(element) = self.widgets.get_mut(&id) else { continue };
+			let original = std::mem::replace(&mut element.area_and_pos, area_and_pos);
+			if original != area_and_pos {
+				element.redraw_request = true;
+				if let Some((original_area, _)) = original {
+					self.add_damage(original_area);
+				}
+			}
+			let hit_bounds = area_and_pos.map(|(area, pos)| self.hit_region(id).unwrap_or_default().bounds(area, pos) & window);
+			self.bind_hit_bounds(id, hit_bounds);
+			match area_and_pos {
+				Some((area, pos)) => self.reanrrage_widgets(area, pos, id, painter, widget_to_remove),
+				None => self.hide_children(id),
+			}
+		}
+	}
+
+	/// Mark the children of the widget as not laid out.
+	fn hide_children(&mut self, id: LayoutId) {
+		let mut children = self.tree.get(&id).cloned().unwrap_or_default();
+		while let Some(id) = children.pop() {
+			if let Some(element) = self.widgets.get_mut(&id) {
+				element.area_and_pos = None;
+			}
+			self.arrangements.remove(&id);
+			if let Some(grand_children) = self.tree.get(&id) {
+				children.extend(grand_children.iter().copied());
+			}
+		}
+	}
+
+	/// Record the area to redraw in the next draw, e.g. uncovered by an overlay.
+	fn add_damage(&mut self, area: Rect) {
+		self.damage.add(area);
+	}
 
-		self.handle_paint(painter)
+	/// The order to receive events, widgets in later pushed overlays come first, then the deeper widgets.
+	fn event_priority(&self, id: LayoutId) -> (usize, i32, usize) {
+		let overlay = self.overlay_of(id)
+			.and_then(|root| self.overlays.iter().position(|overlay| overlay.id == root))
+			.map_or(0, |index| index + 1);
+		(overlay, self.z_index(id), self.widget_layer(id).unwrap_or(0))
 	}
 
 	pub(crate) fn make_all_dirty(&mut self) {
@@ -631,62 +2294,280 @@ impl<S: Signal, A: App<Signal = S>> Layout<S, A> {
 	fn handle_paint(
 		&mut self,
 		painter: &mut Painter,
-	) -> Option<Rect> {
-		let mut refresh_area = None; 
-
-		let mut child_ids = VecDeque::new();
+	) -> Damage {
+		let mut refresh_area = std::mem::take(&mut self.damage);
+		// the widgets in the area requested to redraw are drawn again even if not dirty, e.g. for a blinking caret.
+		let requested_area = refresh_area.clone();
+		self.dirty_report.clear();
+		self.reported_dirty.clear();
+
+		let roots = std::iter::once(ROOT_LAYOUT_ID).chain(self.overlays()).collect::<Vec<_>>();
+		for root in roots {
+			for (id, inherited_opacity) in self.paint_order(root) {
+				let crossfade = self.crossfades.get(&id).map(|crossfade| crossfade.progress.value());
+				let opacity = inherited_opacity * crossfade.unwrap_or(1.0);
+				if let Some(element) = self.widgets.get_mut(&id) {
+					if let Some((area, pos)) = element.area_and_pos {
+						if element.redraw_request {
+							self.dirty_report.push((id, area));
+							refresh_area.add(area);
+						}
 
-		child_ids.push_back(ROOT_LAYOUT_ID);
+						if area.is_empty() {
+							continue;
+						}
 
-		while let Some(id) = child_ids.pop_front() {
-			if let Some(element) = self.widgets.get_mut(&id) {
-				if let Some((area, pos)) = element.area_and_pos {
-					if element.redraw_request {
-						if let Some(refresh) = &mut refresh_area {
-							*refresh |= area;
+						let interaction = self.interactions.get(&id).copied().unwrap_or_default();
+						let reset_painter = |painter: &mut Painter| {
+							painter.set_interaction(interaction);
+							painter.set_opacity(opacity);
+							painter.set_clip_rect(area);
+							painter.set_relative_to(pos);
+							painter.reset_blend_mode();
+							painter.reset_fill_mode();
+							painter.reset_transform();
+						};
+
+						// painter.push_drawing(id);
+						reset_painter(painter);
+						let size = if area.size().has_inf() {
+							painter.window_size
+						}else {
+							area.rb() - pos
+						};
+						let key = DrawKey {
+							area,
+							pos,
+							opacity,
+							interaction,
+							focus_ring: self.focus_visible && self.focused == Some(id) && element.widget.focusable(),
+						};
+						let reusable = !element.redraw_request 
+							&& crossfade.is_none() 
+							&& !requested_area.intersects(area) 
+							&& self.command_cache.is_valid(id, &key);
+						if reusable {
+							painter.reuse_segment(id);
 						}else {
-							refresh_area = Some(area);
+							painter.begin_segment(id, key);
+							if let (Some(crossfade), Some(progress)) = (self.crossfades.get_mut(&id), crossfade) {
+								painter.set_opacity(inherited_opacity * (1.0 - progress));
+								crossfade.old.draw(painter, size);
+								reset_painter(painter);
+							}
+							let decorators = self.decorators.get(&id).map(|inner| inner.as_slice()).unwrap_or_default();
+							for decorator in decorators.iter().filter(|decorator| decorator.behind) {
+								(decorator.draw)(painter, Rect::from_size(size));
+								reset_painter(painter);
+							}
+							element.widget.draw(painter, size);
+							#[cfg(feature = "bench")]
+							crate::bench::count_widget_painted();
+							for decorator in decorators.iter().filter(|decorator| !decorator.behind) {
+								reset_painter(painter);
+								(decorator.draw)(painter, Rect::from_size(size));
+							}
+							if key.focus_ring {
+								reset_painter(painter);
+								painter.set_fill_mode(FOCUS_RING_COLOR);
+								painter.draw_stroked_rect(Rect::from_size(size).shrink(Vec2::same(FOCUS_RING_WIDTH / 2.0)), Vec4::same(DEFAULT_ROUNDING), FOCUS_RING_WIDTH);
+							}
+							painter.end_segment();
 						}
 					}
+					element.redraw_request = false;
+				}
+			}
+		}
 
-					if area.is_empty() {
-						continue;
-					}
+		self.paint_long_press(painter);
 
-					// painter.push_drawing(id);
-					painter.set_clip_rect(area);
-					painter.set_relative_to(pos);
-					painter.reset_blend_mode();
-					painter.reset_fill_mode();
-					painter.reset_transform();
-					let size = if area.size().has_inf() {
-						painter.window_size
-					}else {
-						area.rb() - pos
-					};
-					element.widget.draw(painter, size);
+		refresh_area
+	}
+
+	/// Get the widgets of the tree in the order to draw them and the opacity inherited from their parents.
+	/// 
+	/// The widgets are drawn layer by layer, the widgets with a larger [`Layout::z_index`] after the others.
+	fn paint_order(&self, root: LayoutId) -> Vec<(LayoutId, f32)> {
+		let mut order = vec!();
+		// the opacity is inherited from the parents, so the children of a widget fading in fade as well.
+		let mut child_ids = VecDeque::from([(root, 1.0, 0)]);
+		while let Some((id, inherited_opacity, inherited_z)) = child_ids.pop_front() {
+			let element = self.widgets.get(&id);
+			let z_index = match element.map_or(0, Self::own_z_index) {
+				0 => inherited_z,
+				z_index => z_index,
+			};
+			order.push((id, inherited_opacity, z_index));
+			// the children of a widget clipped out are not drawn either.
+			if element.and_then(|element| element.area_and_pos).is_some_and(|(area, _)| area.is_empty()) {
+				continue;
+			}
+			let opacity = inherited_opacity * self.crossfades.get(&id).map_or(1.0, |crossfade| crossfade.progress.value());
+			for child_id in self.tree.get(&id).map(|children| children.as_slice()).unwrap_or_default() {
+				child_ids.push_back((*child_id, opacity, z_index));
+			}
+		}
+		order.sort_by_key(|(_, _, z_index)| *z_index);
+		order.into_iter().map(|(id, opacity, _)| (id, opacity)).collect()
+	}
+
+	/// Dispatch wheel and drag deltas to the innermost widget under them, and chain the remaining delta to its ancestors.
+	fn handle_scroll(&mut self, state: &mut InputState<S>) {
+		let mut wheel = state.wheel_delta();
+		if wheel != Vec2::ZERO {
+			// mice usually only have a vertical wheel, holding shift scrolls horizontally instead.
+			if state.modifiers().shift && wheel.x == 0.0 {
+				wheel = Vec2::new(wheel.y, 0.0);
+			}
+			if let Some(mouse_pos) = state.mouse_pos() {
+				if self.dispatch_scroll(mouse_pos, wheel, ScrollSource::Wheel) {
+					state.wheel_delta_consume();
 				}
-				element.redraw_request = false;
 			}
-			if let Some(children) = self.tree.get(&id) {
-				for child_id in children {
-					child_ids.push_back(*child_id);
+		}
+
+		for (origin, delta) in state.scroll_drags() {
+			self.dispatch_scroll(origin, delta, ScrollSource::Drag);
+		}
+
+		for pos in state.pressed_positions() {
+			self.scroll_focus = self.widget_at(pos);
+		}
+	}
+
+	/// Returns true if any widget consumed some of the delta.
+	fn dispatch_scroll(&mut self, origin: Vec2, delta: Vec2, source: ScrollSource) -> bool {
+		let target = self.widget_at(origin);
+		self.scroll_chain(target, delta, source)
+	}
+
+	/// Get the innermost widget receiving touches at the given position, overlays first.
+	/// 
+	/// Widgets blocked by a modal overlay are skipped, the modal overlay itself is returned if nothing else is hit.
+	fn widget_at(&self, pos: Vec2) -> LayoutId {
+		let modal = self.modal_index();
+		let mut target = modal.map_or(ROOT_LAYOUT_ID, |index| self.overlays[index].id);
+		let mut target_priority = (0, i32::MIN, 0);
+		for binding in self.rtree.locate_in_envelope_intersecting(&Rect::from_center_size(pos, Vec2::same(1.0))) {
+			if !self.hit_test(binding.id, pos) || modal.is_some_and(|modal| self.is_below(binding.id, modal)) {
+				continue;
+			}
+			let priority = self.event_priority(binding.id);
+			if priority > target_priority {
+				target = binding.id;
+				target_priority = priority;
+			}
+		}
+		target
+	}
+
+	/// Dispatch the keyboard scroll actions whose keys are not used by any widget, starting from the widget last pressed on.
+	fn handle_key_scroll(&mut self, state: &mut InputState<S>) {
+		for key in ScrollAction::KEYS {
+			let Some(action) = ScrollAction::from_key(key) else { continue };
+			if !state.is_key_pressed(key) {
+				continue;
+			}
+			let mut current = if self.widgets.contains_key(&self.scroll_focus) && !self.is_blocked(self.scroll_focus) {
+				self.scroll_focus
+			}else {
+				self.modal().unwrap_or(ROOT_LAYOUT_ID)
+			};
+			while let Some(element) = self.widgets.get_mut(&current) {
+				if element.widget.scroll_action(action) {
+					element.redraw_request = true;
+					self.secondary_widgets.insert(current, 0);
+					break;
 				}
+				if current == ROOT_LAYOUT_ID {
+					break;
+				}
+				current = match self.inverse_tree.get(&current) {
+					Some(parent) => *parent,
+					None => break,
+				};
 			}
 		}
+	}
 
-		refresh_area
+	/// Pass the scroll delta from `start` up to its ancestors until consumed, return whether any widget scrolled.
+	fn scroll_chain(&mut self, start: LayoutId, delta: Vec2, source: ScrollSource) -> bool {
+		let mut remaining = delta;
+		let mut consumed = false;
+		let mut current = start;
+		while let Some(element) = self.widgets.get_mut(&current) {
+			let left = element.widget.scroll_by(remaining, source);
+			if left != remaining {
+				consumed = true;
+				element.redraw_request = true;
+				self.secondary_widgets.insert(current, 0);
+			}
+			remaining = left;
+			if remaining == Vec2::ZERO || current == ROOT_LAYOUT_ID {
+				break;
+			}
+			current = match self.inverse_tree.get(&current) {
+				Some(parent) => *parent,
+				None => break,
+			};
+		}
+		consumed
+	}
+
+	/// Scroll the ancestors of the given widget so that `area` lies inside `visible`, return whether any widget scrolled.
+	/// 
+	/// Both rects are in window coordinates, usually `area` is the area of the widget and `visible` is
+	/// [`InputState::visible_area`]. If `area` is taller than `visible`, its top edge is kept visible.
+	pub fn scroll_into_view(&mut self, id: LayoutId, area: Rect, visible: Rect) -> bool {
+		let mut delta = Vec2::ZERO;
+		if area.y < visible.y || area.h > visible.h {
+			delta.y = visible.y - area.y;
+		}else if area.y + area.h > visible.y + visible.h {
+			delta.y = visible.y + visible.h - area.y - area.h;
+		}
+		if area.x < visible.x || area.w > visible.w {
+			delta.x = visible.x - area.x;
+		}else if area.x + area.w > visible.x + visible.w {
+			delta.x = visible.x + visible.w - area.x - area.w;
+		}
+		if delta == Vec2::ZERO {
+			return false;
+		}
+		let Some(parent) = self.inverse_tree.get(&id).copied() else { return false };
+		self.scroll_chain(parent, delta, ScrollSource::Drag)
 	}
 
 	pub(crate) fn handle_events(&mut self, state: &mut InputState<S>, app: &mut A) {
+		self.handle_scroll(state);
+		self.update_interactions(state);
+		self.update_crossfades(state);
+
+		for touch in state.get_touch_pressed_on(Rect::WINDOW) {
+			if let Some(pos) = state.get_touch_pos(touch) {
+				self.focus_pressed(pos);
+			}
+		}
+		state.focused = self.focused;
+		state.focus_visible = self.focus_visible;
+
 		let primary_widgets = std::mem::take(&mut self.primary_widgets);
 		let secondary_widgets = std::mem::take(&mut self.secondary_widgets);
+		// the widgets below a modal overlay keep their places until it is gone.
+		let modal = self.modal_index();
+		let blocked = |layout: &Self, id: LayoutId| modal.is_some_and(|modal| layout.is_below(id, modal));
 
 		for (id, times) in &primary_widgets {
+			if blocked(self, *id) {
+				self.primary_widgets.insert(*id, *times);
+				continue;
+			}
 			if let Some(element) = self.widgets.get_mut(id) {
 				if let Some((area, pos)) = element.area_and_pos {
 					if area.is_positive() {
+						state.hit_region = Some((area, pos, self.hit_regions.get(id).cloned().unwrap_or_else(|| element.widget.hit_region())));
 						element.redraw_request |= element.widget.handle_event(app, state, *id, area, pos);
+						state.hit_region = None;
 						match element.widget.event_handle_strategy() {
 							EventHandleStrategy::AlwaysPrimary => {
 								self.primary_widgets.insert(element.id, 0);
@@ -713,18 +2594,21 @@ impl<S: Signal, A: App<Signal = S>> Layout<S, A> {
 			).collect::<Vec<_>>();
 
 			childs.sort_by_key(|a| {
-				self.widget_layer(a.id).unwrap_or(0)
+				let (overlay, z_index, layer) = self.event_priority(a.id);
+				(std::cmp::Reverse(overlay), std::cmp::Reverse(z_index), layer)
 			});
 
 			for child in childs {
-				if secondary_widgets.contains_key(&child.id) || primary_widgets.contains_key(&child.id) {
+				if secondary_widgets.contains_key(&child.id) || primary_widgets.contains_key(&child.id) || blocked(self, child.id) {
 					continue;
 				}
 				state.handling_id = child.id;
 				if let Some(element) = self.widgets.get_mut(&child.id) {
 					if let Some((area, pos)) = element.area_and_pos {
 						if area.is_positive() {
+							state.hit_region = Some((area, pos, self.hit_regions.get(&child.id).cloned().unwrap_or_else(|| element.widget.hit_region())));
 							element.redraw_request |= element.widget.handle_event(app, state, child.id, area, pos);
+							state.hit_region = None;
 							match element.widget.event_handle_strategy() {
 								EventHandleStrategy::AlwaysPrimary => {
 									self.primary_widgets.insert(element.id, 0);
@@ -743,11 +2627,34 @@ impl<S: Signal, A: App<Signal = S>> Layout<S, A> {
 			}
 		}
 
+		// widgets not laid out are skipped, those handled every frame stay so until they are laid out, e.g. after being added.
+		for id in primary_widgets.keys().chain(secondary_widgets.keys()) {
+			let Some(element) = self.widgets.get(id) else { continue };
+			if element.area_and_pos.is_some_and(|(area, _)| area.is_positive()) {
+				continue;
+			}
+			match element.widget.event_handle_strategy() {
+				EventHandleStrategy::AlwaysPrimary => {
+					self.primary_widgets.insert(*id, 0);
+				},
+				EventHandleStrategy::AlwaysSecondary => {
+					self.secondary_widgets.insert(*id, 0);
+				},
+				_ => {},
+			}
+		}
+
 		for (id, times) in secondary_widgets {
+			if blocked(self, id) {
+				self.secondary_widgets.insert(id, times);
+				continue;
+			}
 			if let Some(element) = self.widgets.get_mut(&id) {
 				if let Some((area, pos)) = element.area_and_pos {
 					if area.is_positive() {
+						state.hit_region = Some((area, pos, self.hit_regions.get(&id).cloned().unwrap_or_else(|| element.widget.hit_region())));
 						element.redraw_request |= element.widget.handle_event(app, state, id, area, pos);
+						state.hit_region = None;
 						match element.widget.event_handle_strategy() {
 							EventHandleStrategy::AlwaysPrimary => {
 								self.primary_widgets.insert(element.id, 0);
@@ -768,7 +2675,93 @@ impl<S: Signal, A: App<Signal = S>> Layout<S, A> {
 			}
 		}
 		
+		self.damage.extend(std::mem::take(&mut state.damage));
+
+		match state.focus_request.take() {
+			Some(Some(id)) => self.set_focus(id),
+			Some(None) => self.clear_focus(),
+			None => {},
+		}
+		self.trap_focus();
+		if state.is_key_pressed(Key::Tab) {
+			// the newly focused widget is scrolled into view after the next layout pass.
+			self.focus_next(state.modifiers().shift);
+		}
+		state.focused = self.focused;
+		state.focus_visible = self.focus_visible;
+
+		self.handle_key_scroll(state);
+
+		// the keyboard of a removed widget would never be hidden otherwise.
+		if state.keyboard_owner.is_some_and(|id| !self.widgets.contains_key(&id)) {
+			state.keyboard_owner = None;
+		}
+		if let Some((id, area)) = state.visible_request.take() {
+			self.scroll_into_view(id, area, state.visible_area());
+		}
+		for (id, area) in std::mem::take(&mut state.reveal_requests) {
+			let Some(element) = self.widgets.get_mut(&id) else { continue };
+			if element.widget.scroll_to_reveal(area) != Vec2::ZERO {
+				element.redraw_request = true;
+				self.secondary_widgets.insert(id, 0);
+			}
+		}
+
+		let right_pressed = state.is_touch_pressed(MOUSE_RIGHT_ID);
+		let opened_by_widget = std::mem::take(&mut state.context_menu_opened);
+		if right_pressed && !self.context_menu_pressed && !opened_by_widget && !self.context_menus.is_empty() {
+			if let Some(pos) = state.get_touch_pos(MOUSE_RIGHT_ID) {
+				self.open_context_menu(pos);
+			}
+		}
+		self.context_menu_pressed = right_pressed;
+		self.update_long_press(state);
+		self.update_tooltip(state);
+
+		for request in std::mem::take(&mut state.overlay_requests) {
+			match request {
+				OverlayRequest::Push(anchor, widget) => match widget.downcast::<Box<dyn Widget<Signal = S, Application = A>>>() {
+					Ok(widget) => {
+						self.push_boxed_overlay(anchor, *widget);
+					},
+					Err(_) => log::error!("overlay requested by a widget of another application type is ignored"),
+				},
+				OverlayRequest::Remove(id) => {
+					if self.is_overlay(id) {
+						self.remove_widget(id);
+					}
+				},
+			}
+		}
+
+		for request in std::mem::take(&mut state.child_requests) {
+			match request {
+				ChildRequest::Add(parent, widget) => match widget.downcast::<Box<dyn Widget<Signal = S, Application = A>>>() {
+					Ok(widget) => {
+						self.add_boxed_widget(parent, *widget);
+					},
+					Err(_) => log::error!("child requested by a widget of another application type is ignored"),
+				},
+				ChildRequest::Replace(parent, id, widget) => match widget.downcast::<Box<dyn Widget<Signal = S, Application = A>>>() {
+					Ok(widget) if self.get_parent_id(id) == Some(parent) => {
+						let _ = self.replace_boxed_widget(id, *widget, false);
+					},
+					Ok(_) => {},
+					Err(_) => log::error!("child requested by a widget of another application type is ignored"),
+				},
+				ChildRequest::Remove(parent, id) => {
+					if self.get_parent_id(id) == Some(parent) {
+						self.remove_widget(id);
+					}
+				},
+			}
+		}
+
 		self.secondary_widgets.insert(ROOT_LAYOUT_ID, 0);
+		// the focused widget listens to the keys.
+		if let Some(focused) = self.focused {
+			self.secondary_widgets.insert(focused, 0);
+		}
 	}
 
 	// fn __handle_events(&mut self, parent_id: LayoutId, state: &mut InputState<S>, app: &mut A) {
@@ -793,7 +2786,177 @@ impl<S: Signal, A: App<Signal = S>> Layout<S, A> {
 	// 	}
 	// }
 
+	/// Take the widgets redrawn in the last frame with their absolute areas.
+	/// 
+	/// Returns empty vector if nothing was redrawn or the report is already taken.
+	pub fn take_dirty_report(&mut self) -> Vec<(LayoutId, Rect)> {
+		std::mem::take(&mut self.dirty_report)
+	}
+
+	/// Set the listener called when a widget becomes dirty, i.e. needs to be redrawn.
+	/// 
+	/// The listener is called once for each widget until it is redrawn,
+	/// checked after each event frame and before each layout pass.
+	/// Useful for integrating with external render schedulers.
+	pub fn on_dirty(&mut self, listener: impl FnMut(LayoutId) + 'static) {
+		self.dirty_listener = Some(Box::new(listener));
+	}
+
+	/// Remove the listener set by [`Self::on_dirty`].
+	pub fn remove_dirty_listener(&mut self) {
+		self.dirty_listener = None;
+	}
+
+	/// Pass the widgets became dirty since the last check to the dirty listener.
+	pub(crate) fn notify_dirty(&mut self) {
+		let Some(listener) = &mut self.dirty_listener else { return };
+		for element in self.widgets.values() {
+			if element.redraw_request && self.reported_dirty.insert(element.id) {
+				listener(element.id);
+			}
+		}
+	}
+
 	pub(crate) fn any_widget_dirty(&self) -> bool {
 		self.widgets.values().any(|x| x.redraw_request)
 	}
-}
\ No newline at end of file
+
+	/// Check whether the widgets arranged in the last pass still need their parents to measure them again.
+	pub(crate) fn needs_relayout(&self) -> bool {
+		!self.relayout_requests.is_empty()
+	}
+}
+mod test {
+	#[test]
+	fn test_relayout_keeps_clean_subtree() {
+		use std::sync::{Arc, Mutex};
+		use crate::{layout::{Layout, ROOT_LAYOUT_ID}, prelude::{Card, FontPool, Painter, Vec2}, widgets::SignalWrapper, App, Context};
+
+		struct TestApp;
+		impl App for TestApp {
+			type Signal = ();
+			fn on_start(&mut self, _: &mut Context<(), Self>) {}
+			fn on_signal(&mut self, _: &mut Context<(), Self>, _: SignalWrapper<()>) {}
+		}
+
+		let window = Vec2::new(400.0, 600.0);
+		let mut painter = Painter::headless(Arc::new(Mutex::new(FontPool::new())), window);
+		let mut layout = Layout::<(), TestApp>::new();
+		layout.insert_root_widget(Card::new_vertical());
+		let clean = layout.add_widget(ROOT_LAYOUT_ID, Card::new_vertical()).unwrap();
+		let clean_child = layout.add_widget(clean, Card::new_vertical().set_size(Vec2::new(100.0, 50.0))).unwrap();
+		let dirty = layout.add_widget(ROOT_LAYOUT_ID, Card::new_vertical()).unwrap();
+		let dirty_child = layout.add_widget(dirty, Card::new_vertical().set_size(Vec2::new(100.0, 50.0))).unwrap();
+		let mut settle = |layout: &mut Layout<(), TestApp>| {
+			layout.handle_draw(&mut painter, window);
+			let mut passes = 1;
+			while layout.needs_relayout() {
+				assert!(passes < 10, "the layout does not settle");
+				layout.handle_draw(&mut painter, window);
+				passes += 1;
+			}
+		};
+		settle(&mut layout);
+
+		let before = (layout.get_widget_area(clean_child), layout.get_widget_pos(clean_child));
+		layout.widget_mut::<Card<(), TestApp>>(dirty_child, |card| card.set_size(Vec2::new(100.0, 150.0)));
+		settle(&mut layout);
+		assert_eq!((layout.get_widget_area(clean_child), layout.get_widget_pos(clean_child)), before);
+		assert_eq!(layout.get_widget_size(dirty_child), Some(Vec2::new(100.0, 150.0)));
+	}
+
+	#[test]
+	fn test_relayout_moves_siblings() {
+		use std::sync::{Arc, Mutex};
+		use crate::{layout::{Layout, ROOT_LAYOUT_ID}, prelude::{Card, FontPool, Painter, Vec2}, widgets::SignalWrapper, App, Context};
+
+		struct TestApp;
+		impl App for TestApp {
+			type Signal = ();
+			fn on_start(&mut self, _: &mut Context<(), Self>) {}
+			fn on_signal(&mut self, _: &mut Context<(), Self>, _: SignalWrapper<()>) {}
+		}
+
+		let window = Vec2::new(400.0, 600.0);
+		let mut painter = Painter::headless(Arc::new(Mutex::new(FontPool::new())), window);
+		let mut layout = Layout::<(), TestApp>::new();
+		layout.insert_root_widget(Card::new_vertical());
+		let grown = layout.add_widget(ROOT_LAYOUT_ID, Card::new_vertical().set_size(Vec2::new(100.0, 50.0))).unwrap();
+		let sibling = layout.add_widget(ROOT_LAYOUT_ID, Card::new_vertical().set_size(Vec2::new(100.0, 50.0))).unwrap();
+		layout.handle_draw(&mut painter, window);
+		let before = layout.get_widget_pos(sibling).unwrap();
+
+		layout.widget_mut::<Card<(), TestApp>>(grown, |card| card.set_size(Vec2::new(100.0, 150.0)));
+		layout.handle_draw(&mut painter, window);
+		assert_eq!(layout.get_widget_pos(sibling).unwrap(), before + Vec2::new(0.0, 100.0));
+	}
+
+	#[test]
+	fn test_relayout_resizes_ancestors() {
+		use std::sync::{Arc, Mutex};
+		use crate::{layout::{Layout, ROOT_LAYOUT_ID}, prelude::{Card, FontPool, Painter, Vec2}, widgets::SignalWrapper, App, Context};
+
+		struct TestApp;
+		impl App for TestApp {
+			type Signal = ();
+			fn on_start(&mut self, _: &mut Context<(), Self>) {}
+			fn on_signal(&mut self, _: &mut Context<(), Self>, _: SignalWrapper<()>) {}
+		}
+
+		let window = Vec2::new(400.0, 600.0);
+		let mut painter = Painter::headless(Arc::new(Mutex::new(FontPool::new())), window);
+		let mut layout = Layout::<(), TestApp>::new();
+		layout.insert_root_widget(Card::new_vertical());
+		let outer = layout.add_widget(ROOT_LAYOUT_ID, Card::new_vertical()).unwrap();
+		let middle = layout.add_widget(outer, Card::new_vertical()).unwrap();
+		let grown = layout.add_widget(middle, Card::new_vertical().set_size(Vec2::new(100.0, 50.0))).unwrap();
+		let mut settle = |layout: &mut Layout<(), TestApp>| {
+			layout.handle_draw(&mut painter, window);
+			let mut passes = 1;
+			while layout.needs_relayout() {
+				assert!(passes < 10, "the layout does not settle");
+				layout.handle_draw(&mut painter, window);
+				passes += 1;
+			}
+		};
+		settle(&mut layout);
+		let before = (layout.get_widget_size(middle).unwrap(), layout.get_widget_size(outer).unwrap());
+
+		layout.widget_mut::<Card<(), TestApp>>(grown, |card| card.set_size(Vec2::new(100.0, 150.0)));
+		settle(&mut layout);
+		assert_eq!(layout.get_widget_size(middle).unwrap(), before.0 + Vec2::new(0.0, 100.0));
+		assert_eq!(layout.get_widget_size(outer).unwrap(), before.1 + Vec2::new(0.0, 100.0));
+	}
+
+	#[test]
+	fn test_relayout_on_window_resize() {
+		use std::sync::{Arc, Mutex};
+		use crate::{layout::{Layout, ROOT_LAYOUT_ID}, prelude::{Card, Divider, FontPool, Painter, Vec2}, widgets::SignalWrapper, App, Context};
+
+		struct TestApp;
+		impl App for TestApp {
+			type Signal = ();
+			fn on_start(&mut self, _: &mut Context<(), Self>) {}
+			fn on_signal(&mut self, _: &mut Context<(), Self>, _: SignalWrapper<()>) {}
+		}
+
+		let fonts = Arc::new(Mutex::new(FontPool::new()));
+		let mut layout = Layout::<(), TestApp>::new();
+		layout.insert_root_widget(Card::new_vertical());
+		let divider = layout.add_widget(ROOT_LAYOUT_ID, Divider::new(false)).unwrap();
+		let width_at = |layout: &mut Layout<(), TestApp>, window: Vec2| {
+			let mut painter = Painter::headless(fonts.clone(), window);
+			layout.handle_draw(&mut painter, window);
+			let mut passes = 1;
+			while layout.needs_relayout() {
+				assert!(passes < 10, "the layout does not settle");
+				layout.handle_draw(&mut painter, window);
+				passes += 1;
+			}
+			layout.get_widget_size(divider).unwrap().x
+		};
+		let narrow = width_at(&mut layout, Vec2::new(400.0, 600.0));
+		let wide = width_at(&mut layout, Vec2::new(600.0, 600.0));
+		assert_eq!(wide, narrow + 200.0);
+	}
+}
@@ -4,11 +4,13 @@ use std::{collections::HashMap, sync::{Arc, Mutex}};
 
 use indexmap::IndexSet;
 use layout::Layout;
-use math::vec2::Vec2;
+use math::{animation::AnimatedColor, color::Color, vec2::Vec2};
 use prelude::FontId;
-use render::{font::FontPool, texture::{Texture, TextureId}};
-use widgets::{Signal, SignalWrapper};
-use window::{event::OutputEvent, input_state::InputState};
+use math::rect::Rect;
+use render::{backend::Uniform, commands::DrawCommandGpu, damage::Damage, debug::RedrawDebugger, font::FontPool, font_render::GlyphMap, painter::Painter, texture::{AnimatedTexture, Texture, TextureId}};
+use time::OffsetDateTime;
+use widgets::{styles::BACKGROUND_COLOR, Signal, SignalWrapper};
+use window::{event::{OutputEvent, Theme}, input_state::InputState};
 
 pub mod layout;
 pub mod render;
@@ -16,6 +18,9 @@ pub mod window;
 pub mod widgets;
 pub mod math;
 pub mod prelude;
+pub mod pack;
+#[cfg(feature = "bench")]
+pub mod bench;
 
 // TODO: Implement Context struct.
 /// The context for Nablo UI.
@@ -28,13 +33,41 @@ pub struct Context<S: Signal, A: App<Signal = S>> {
 	/// If true, the app will be redrawn every frame, even if there are no changes,
 	/// and will redraw the entire screen instead of just the changed parts.
 	pub force_redraw_per_frame: bool,
+	/// Whether to keep a copy of the registered textures, so they can be uploaded again when the app resumes.
+	/// 
+	/// Mobile platforms drop the gpu resources when the app is suspended,
+	/// textures registered while this is `false` are lost after resuming and need to be registered again.
+	/// 
+	/// By default, this is `true` on Android and iOS.
+	pub retain_textures: bool,
+	/// Visualizes the redrawn areas and a redraw heatmap when set.
+	/// 
+	/// See [`render::debug::RedrawDebugger`] for details.
+	pub redraw_debugger: Option<RedrawDebugger>,
+	/// Records the ui into an animated image when set.
+	/// 
+	/// See [`render::capture::DemoCapture`] for details.
+	#[cfg(feature = "capture")]
+	pub capture: Option<render::capture::DemoCapture>,
+	/// The custom gpu passes rendered with the UI.
+	/// 
+	/// See [`render::graph`] for details.
+	#[cfg(feature = "render-graph")]
+	pub render_graph: render::graph::RenderGraph,
 	/// The font pool for the app.
 	/// 
 	/// used to save and load fonts.
 	pub fonts: Arc<Mutex<FontPool>>,
+	pub(crate) extensions: pack::Extensions,
+	pub(crate) viewports: render::viewport::Viewports,
+	#[cfg(feature = "image-loader")]
+	images: render::image_loader::ImageLoader,
 	textures: HashMap<TextureId, Texture>,
+	texture_data: HashMap<TextureId, Vec<u8>>,
 	available_texture_ids: IndexSet<TextureId>,
 	input_state: InputState<S>,
+	clear_color: AnimatedColor,
+	theme_clear_colors: HashMap<Theme, Color>,
 	exit: bool,
 	// pub(crate) painter_context: PainterCtx,
 	// padding: Vec2,
@@ -43,25 +76,134 @@ pub struct Context<S: Signal, A: App<Signal = S>> {
 impl<S: Signal, A: App<Signal = S>> Context<S, A> {
 	/// Creates a new context with default values.
 	pub fn new(font_data: Vec<u8>, index: u32) -> Self {
-		let mut font_pool = FontPool::new();
-		font_pool.insert_font(font_data, index);
+		let mut ctx = Self::without_fonts();
+		ctx.insert_font(font_data, index);
+		ctx
+	}
+
+	/// Creates a new context using the embedded default font, see [`render::font::DEFAULT_FONT`].
+	#[cfg(feature = "default-font")]
+	pub fn with_default_font() -> Self {
+		Self::new(render::font::DEFAULT_FONT.to_vec(), 0)
+	}
 
+	/// Creates a new context without any font.
+	/// 
+	/// Texts will not be drawn until a font is loaded by [`Self::insert_font`],
+	/// the first font loaded gets the id `0`, which is used by the built-in widgets by default.
+	pub fn without_fonts() -> Self {
 		Self {
 			input_state: InputState::new(),
+			clear_color: AnimatedColor::default_with_value(BACKGROUND_COLOR),
+			theme_clear_colors: HashMap::new(),
 			force_redraw_per_frame: false,
+			retain_textures: cfg!(any(target_os = "android", target_os = "ios")),
+			redraw_debugger: None,
+			#[cfg(feature = "capture")]
+			capture: None,
+			#[cfg(feature = "render-graph")]
+			render_graph: render::graph::RenderGraph::default(),
+			extensions: pack::Extensions::default(),
+			viewports: render::viewport::Viewports::default(),
+			#[cfg(feature = "image-loader")]
+			images: render::image_loader::ImageLoader::default(),
 			textures: HashMap::new(),
+			texture_data: HashMap::new(),
 			available_texture_ids: IndexSet::new(),
 			layout: Layout::new(),
 			exit: false,
 			// padding: Vec2::same(EM),
-			fonts: Arc::new(Mutex::new(font_pool)),
+			fonts: Arc::new(Mutex::new(FontPool::new())),
 			// painter_context: PainterCtx::default(),
 		}
 	}
 
 	/// Insert a font into the font pool.
+	/// 
+	/// All widgets will be redrawn, so texts waiting for the font show up.
 	pub fn insert_font(&mut self, font_data: Vec<u8>, index: u32) -> FontId {
-		self.fonts.lock().unwrap().insert_font(font_data, index)
+		let id = self.fonts.lock().unwrap().insert_font(font_data, index);
+		self.layout.make_all_dirty();
+		id
+	}
+
+	/// Set the font used for the given role, `None` to unregister the role.
+	/// 
+	/// Widgets using the role will be redrawn with the new font, see [`render::font::FontRole`].
+	pub fn set_font_role(&mut self, role: render::font::FontRole, font_id: Option<FontId>) {
+		self.fonts.lock().unwrap().set_role(role, font_id);
+		self.layout.make_all_dirty();
+	}
+
+	/// Get the font registered for the given role.
+	pub fn font_role(&self, role: render::font::FontRole) -> Option<FontId> {
+		self.fonts.lock().unwrap().role(role)
+	}
+
+	/// Install a widget pack, registering its fonts, theme entries, icon sets and output event handlers.
+	/// 
+	/// All widgets will be redrawn, see [`pack`] for details.
+	pub fn install_pack(&mut self, pack: impl pack::WidgetPack) -> Result<(), pack::PackError> {
+		self.extensions.install(&pack, &self.fonts)?;
+		self.layout.make_all_dirty();
+		Ok(())
+	}
+
+	/// Check whether the widget pack with the given name is installed.
+	pub fn is_pack_installed(&self, name: &str) -> bool {
+		self.extensions.is_installed(name)
+	}
+
+	/// Get the names of the installed widget packs, in the order they are installed.
+	pub fn installed_packs(&self) -> impl Iterator<Item = &str> {
+		self.extensions.packs()
+	}
+
+	/// Get a theme entry registered by a widget pack or set by the app.
+	pub fn theme_entry(&self, key: &str) -> Option<pack::ThemeValue> {
+		self.extensions.theme_entry(key)
+	}
+
+	/// Get a theme entry if it is a color.
+	pub fn theme_color(&self, key: &str) -> Option<Color> {
+		match self.theme_entry(key)? {
+			pack::ThemeValue::Color(color) => Some(color),
+			_ => None,
+		}
+	}
+
+	/// Get a theme entry if it is a number.
+	pub fn theme_number(&self, key: &str) -> Option<f32> {
+		match self.theme_entry(key)? {
+			pack::ThemeValue::Number(number) => Some(number),
+			_ => None,
+		}
+	}
+
+	/// Set a theme entry, overriding the value registered by widget packs.
+	/// 
+	/// All widgets will be redrawn.
+	pub fn set_theme_entry(&mut self, key: impl Into<String>, value: impl Into<pack::ThemeValue>) {
+		self.extensions.set_theme_entry(key.into(), value.into());
+		self.layout.make_all_dirty();
+	}
+
+	/// Get the font and the glyph of an icon in an icon set registered by a widget pack.
+	pub fn icon(&self, icon_set: &str, name: &str) -> Option<(FontId, char)> {
+		self.extensions.icon(icon_set, name)
+	}
+
+	/// Set the localizer used by the built-in widgets to translate messages and format numbers.
+	/// 
+	/// All widgets will be redrawn with the new localizer.
+	pub fn set_localizer(&mut self, localizer: impl widgets::localizer::Localizer + 'static) {
+		self.input_state.localizer = std::sync::Arc::new(localizer);
+		self.layout.make_all_dirty();
+	}
+
+	/// Get the localizer used by the built-in widgets.
+	pub fn localizer(&self) -> &dyn widgets::localizer::Localizer {
+		self.input_state.localizer()
 	}
 
 	/// Set the advance factor of the font pool.
@@ -70,6 +212,34 @@ impl<S: Signal, A: App<Signal = S>> Context<S, A> {
 		self.layout.make_all_dirty();
 	}
 
+	/// Announce the text to the assistive technologies, such as "upload complete", for the changes not conveyed otherwise.
+	/// 
+	/// The announcement is posted to VoiceOver on macOS. On the other platforms it is spoken by the text to speech engine
+	/// of the system if [`window::manager::WindowSettings::speech_fallback`] is set, and dropped otherwise.
+	/// When embedded, the announcement is returned to the host as [`window::event::OutputEvent::Announce`].
+	pub fn announce(&mut self, text: impl Into<String>, priority: window::event::AnnouncePriority) {
+		self.input_state.announce(text, priority);
+	}
+
+	/// Get the zoom level of the ui, see [`Self::set_zoom`].
+	pub fn zoom(&self) -> f32 {
+		self.input_state.zoom()
+	}
+
+	/// Set the zoom level of the ui, independent of the scale factor of the system.
+	/// 
+	/// All the texts, paddings and sizes are scaled by it and the layout reflows to fit the window,
+	/// see [`InputState::set_zoom`]. Users can also zoom with Ctrl+= and Ctrl+- (Cmd on macOS), see [`Self::set_zoom_shortcuts`].
+	pub fn set_zoom(&mut self, zoom: f32) {
+		self.input_state.set_zoom(zoom);
+		self.layout.make_all_dirty();
+	}
+
+	/// Set whether Ctrl+= and Ctrl+- zoom in and out, and Ctrl+0 resets the zoom level. Enabled by default.
+	pub fn set_zoom_shortcuts(&mut self, enabled: bool) {
+		self.input_state.set_zoom_shortcuts(enabled);
+	}
+
 	/// Get a reference to the input state.
 	pub fn input_state(&self) -> &InputState<S> {
 		&self.input_state
@@ -79,8 +249,11 @@ impl<S: Signal, A: App<Signal = S>> Context<S, A> {
 	/// 
 	/// Note: Do NOT call this method every frame, as it will cause a lot of unnecessary texture uploads.
 	pub fn register_texture(&mut self, rgba: Vec<u8>, size: Vec2) -> TextureId {
-		self.input_state.output_events.push(OutputEvent::RegisterTexture(size, rgba));
 		let id =self.available_texture_ids.pop().unwrap_or(self.textures.len() as u32);
+		if self.retain_textures {
+			self.texture_data.insert(id, rgba.clone());
+		}
+		self.input_state.output_events.push(OutputEvent::RegisterTexture(id, size, rgba));
 		self.textures.insert(id, Texture {
 			texture_id: id,
 			width: size.x as u32,
@@ -91,6 +264,28 @@ impl<S: Signal, A: App<Signal = S>> Context<S, A> {
 		id
 	}
 
+	/// Register the frames of an animation as textures, such as a decoded GIF, and play it with [`widgets::animated_image::AnimatedImage`].
+	/// 
+	/// All frames have the size given, and each is shown for its delay.
+	/// Frames without a delay are shown for the last delay given, or [`render::texture::DEFAULT_FRAME_DELAY`] if none.
+	pub fn register_texture_animated(&mut self, frames: Vec<Vec<u8>>, size: Vec2, delays: Vec<time::Duration>) -> AnimatedTexture {
+		let last_delay = delays.last().copied().unwrap_or(render::texture::DEFAULT_FRAME_DELAY);
+		let mut delays = delays;
+		delays.resize(frames.len(), last_delay);
+		AnimatedTexture {
+			frames: frames.into_iter().map(|rgba| self.register_texture(rgba, size)).collect(),
+			delays,
+			size,
+		}
+	}
+
+	/// Remove all frames of an animated texture from the context.
+	pub fn remove_texture_animated(&mut self, texture: &AnimatedTexture) {
+		for texture_id in &texture.frames {
+			self.remove_texture(*texture_id);
+		}
+	}
+
 	/// Update a texture in the context.
 	/// 
 	/// Note: Do NOT call this method every frame, as it will cause a lot of unnecessary texture uploads.
@@ -98,6 +293,9 @@ impl<S: Signal, A: App<Signal = S>> Context<S, A> {
 	/// Returns true if the texture was updated, false otherwise.
 	pub fn update_texture(&mut self, texture_id: TextureId, rgba: Vec<u8>, new_size: Vec2) -> bool {
 		if let Some(texture) = self.textures.get_mut(&texture_id) {
+			if self.retain_textures {
+				self.texture_data.insert(texture_id, rgba.clone());
+			}
 			self.input_state.output_events.push(OutputEvent::UpdateTexture(texture_id, new_size, rgba));
 			texture.width = new_size.x as u32;
 			texture.height = new_size.y as u32;
@@ -112,6 +310,7 @@ impl<S: Signal, A: App<Signal = S>> Context<S, A> {
 	pub fn remove_texture(&mut self, texture_id: TextureId) -> Option<Texture> {
 		self.input_state.output_events.push(OutputEvent::RemoveTexture(texture_id));
 		self.available_texture_ids.insert(texture_id);
+		self.texture_data.remove(&texture_id);
 		self.textures.remove(&texture_id)
 	}
 
@@ -119,15 +318,229 @@ impl<S: Signal, A: App<Signal = S>> Context<S, A> {
 	pub fn clear_textures(&mut self) {
 		self.input_state.output_events.push(OutputEvent::ClearTexture);
 		self.textures.clear();
+		self.texture_data.clear();
 		self.available_texture_ids.clear();
 	}
 
+	/// Decode an encoded image, such as PNG or JPEG, off the main thread and register it as a texture when done.
+	/// 
+	/// The returned handle turns ready on a later event frame, see [`render::image_loader`].
+	#[cfg(feature = "image-loader")]
+	pub fn load_image(&mut self, bytes: Vec<u8>) -> render::image_loader::ImageHandle {
+		self.images.load(bytes)
+	}
+
+	/// Register the images decoded since the last event frame, and keep polling while any is loading.
+	#[cfg(feature = "image-loader")]
+	fn poll_images(&mut self) {
+		use render::image_loader::ImageState;
+
+		for (handle, decoded) in self.images.poll() {
+			if handle.is_orphan() {
+				continue;
+			}
+			match decoded {
+				Ok((rgba, size)) => {
+					let texture_id = self.register_texture(rgba, size);
+					handle.set_state(ImageState::Ready(texture_id, size));
+				},
+				Err(error) => {
+					log::warn!("Failed to decode image: {}", error);
+					handle.set_state(ImageState::Error(error));
+				},
+			}
+		}
+		if self.images.is_loading() {
+			self.input_state.request_frame_after(time::Duration::milliseconds(16));
+		}
+	}
+
+	/// Add a viewport hosting content rendered with wgpu, shown by [`widgets::viewport::WgpuViewport`].
+	/// 
+	/// The viewport takes a texture id, see [`render::viewport`] for details.
+	pub fn add_viewport(&mut self, renderer: impl render::viewport::ViewportRenderer) -> render::viewport::ViewportId {
+		let id = self.register_texture(vec![0; 4], Vec2::same(1.0));
+		self.viewports.insert(id, Box::new(renderer));
+		id
+	}
+
+	/// Remove a viewport and its texture, returns the renderer if found.
+	pub fn remove_viewport(&mut self, id: render::viewport::ViewportId) -> Option<Box<dyn render::viewport::ViewportRenderer>> {
+		let renderer = self.viewports.remove(id)?;
+		self.remove_texture(id);
+		Some(renderer)
+	}
+
+	/// Get the color the window is cleared with.
+	/// 
+	/// The clear color shows behind the widgets and while the window is resizing.
+	pub fn clear_color(&self) -> Color {
+		self.clear_color.value()
+	}
+
+	/// Set the color the window is cleared with, animated.
+	pub fn set_clear_color(&mut self, color: Color) {
+		self.clear_color.set(color);
+		self.layout.make_all_dirty();
+	}
+
+	/// Set the color the window is cleared with, without animation.
+	pub fn set_clear_color_without_animation(&mut self, color: Color) {
+		self.clear_color.set_without_animation(color);
+		self.layout.make_all_dirty();
+	}
+
+	/// Set the clear color used when the window switches to the given theme, `None` to keep the clear color on switching.
+	/// 
+	/// Takes effect immediately if the window is using the theme.
+	pub fn set_theme_clear_color(&mut self, theme: Theme, color: Option<Color>) {
+		if let Some(color) = color {
+			self.theme_clear_colors.insert(theme, color);
+			if self.input_state.theme == theme {
+				self.set_clear_color(color);
+			}
+		}else {
+			self.theme_clear_colors.remove(&theme);
+		}
+	}
+
+	/// Get the memory used by the textures in bytes, estimated as 4 bytes per pixel.
+	pub fn texture_memory(&self) -> usize {
+		self.textures.values().map(|texture| texture.width as usize * texture.height as usize * 4).sum()
+	}
+
 	/// Get a reference to the texture with the given id.
 	pub fn get_texture(&self, texture_id: TextureId) -> Option<&Texture> {
 		self.textures.get(&texture_id)
 	}
+
+	/// Handle the events of an event frame, routes the signals to the app and generates the glyph textures.
+	pub(crate) fn handle_event_frame(&mut self, app: &mut A) {
+		self.input_state.frame_stats.widgets = self.layout.widgets();
+		self.input_state.frame_stats.texture_memory = self.texture_memory();
+		// self.layout.handle_continous_events(&mut self.input_state);
+		let now = self.input_state.program_running_time();
+		if let Some(next) = self.layout.handle_updates(now) {
+			self.input_state.request_frame_after(next - now);
+		}
+		#[cfg(feature = "image-loader")]
+		self.poll_images();
+		self.layout.handle_events(&mut self.input_state, app);
+		self.input_state.handle_zoom_shortcuts();
+		for (id, input) in std::mem::take(&mut self.input_state.viewport_inputs) {
+			self.viewports.handle_input(id, &input);
+		}
+		let signals = self.input_state.signals_to_send.drain(..).collect::<Vec<_>>();
+		for mut signal in signals {
+			signal.alias = self.layout.id_to_alias(signal.from).map(String::from);
+			if let Some(signal) = self.layout.route_signal(app, signal) {
+				app.on_signal(self, signal);
+			}
+		}
+
+		let events = if let Ok(mut events) = self.fonts.lock() {
+			events.generate_textures()
+		}else {
+			panic!("Failed to lock font pool")
+		};
+		if !events.is_empty() {
+			// the glyph atlas changes, so the cached text commands are outdated.
+			self.layout.command_cache.clear();
+		}
+
+		self.input_state.output_events.extend(events);
+
+		self.input_state.prepare_for_next_frame();
+
+		if self.input_state.all_dirty {
+			self.input_state.all_dirty = false;
+			self.layout.make_all_dirty();
+		}
+	}
+
+	/// Check whether the next draw frame has anything to draw.
+	pub(crate) fn needs_redraw(&self) -> bool {
+		self.input_state.redraw_requested || self.layout.any_widget_dirty() || self.force_redraw_per_frame || self.clear_color.is_animating()
+	}
+
+	/// Draw the dirty widgets, returns the painter and the areas to refresh, `None` if nothing is drawn.
+	/// 
+	/// The whole window is refreshed if `redraw_whole` is set.
+	pub(crate) fn draw_frame(&mut self, app: &mut A, delta: time::Duration, redraw_whole: bool) -> Option<(Painter, Damage)> {
+		self.input_state.redraw_requested = false;
+		let mut painter = Painter::new(self.fonts.clone(), self.input_state.window_size);
+		painter.set_scale_factor(self.input_state.ui_scale());
+		painter.set_localizer(self.input_state.localizer.clone());
+		
+		if self.force_redraw_per_frame || self.clear_color.is_animating() {
+			self.layout.make_all_dirty();
+		}
+		
+		self.input_state.update_draw_delta_time(delta);
+		app.on_draw_frame(self, delta);
+		let refresh_area = self.layout.handle_draw(&mut painter, self.input_state.window_size);
+		// nothing else may schedule the pass measuring the widgets arranged in this one.
+		self.input_state.redraw_requested |= self.layout.needs_relayout();
+		let refresh_area = if let Some(debugger) = &mut self.redraw_debugger {
+			debugger.record(&refresh_area, self.input_state.window_size);
+			debugger.paint(&mut painter);
+			self.input_state.redraw_requested |= debugger.is_fading();
+			Damage::from(Rect::WINDOW)
+		}else if self.force_redraw_per_frame || redraw_whole {
+			Damage::from(Rect::WINDOW)
+		}else if refresh_area.is_empty() {
+			return None;
+		}else {
+			refresh_area
+		};
+		Some((painter, refresh_area))
+	}
+
+	/// Parse the painter into gpu commands, and build the uniform of the frame.
+	/// 
+	/// The viewports drawn are rendered by [`render::backend::WgpuState::render_viewports`] afterwards.
+	pub(crate) fn build_frame(&mut self, mut painter: Painter, glyphs: &GlyphMap, refresh_area: &Damage) -> (Vec<DrawCommandGpu>, Uniform) {
+		// painter.shapes.reverse();
+		let supersample_regions = painter.take_supersample_regions();
+		self.viewports.set_drawn(painter.take_viewports());
+		let (commands, stack_len) = painter.parse(glyphs, refresh_area, &mut self.layout.command_cache);
+
+		if stack_len >= STACK_SIZE {
+			panic!("Gpu Stack overflows, max size is {} but current size is {}", STACK_SIZE, stack_len);
+		}
+		// println!("commands: {:#?}", commands);
+		// panic!();
+		let mut uniform = self.frame_uniform();
+		uniform.command_len = commands.len() as u32;
+		uniform.stack_len = stack_len;
+		uniform.set_supersample_regions(&supersample_regions);
+		(commands, uniform)
+	}
+
+	/// Build the uniform of the frame without any command.
+	pub(crate) fn frame_uniform(&self) -> Uniform {
+		let window_size = self.input_state.window_size();
+		let mouse_pos = self.input_state.mouse_pos().unwrap_or(Vec2::INF);
+		let time = (OffsetDateTime::now_utc() - self.input_state.program_start_time).as_seconds_f32();
+
+		Uniform {
+			window_size: [
+				window_size.x, 
+				window_size.y
+			],
+			mouse: [
+				mouse_pos.x, 
+				mouse_pos.y
+			],
+			time,
+			scale_factor: self.input_state.ui_scale(),
+			..bytemuck::Zeroable::zeroed()
+		}
+	}
 }
 
+const STACK_SIZE: u32 = 64;
+
 /// The main trait for Nablo UI.
 pub trait App: 'static + Sized {
 	type Signal: Signal;
@@ -141,14 +554,28 @@ pub trait App: 'static + Sized {
 		let _ = ctx;
 	}
 	/// Here you can hanlde your app every draw loop frame.
-	fn on_draw_frame(&mut self, ctx: &mut Context<Self::Signal, Self>) {
-		let _ = ctx;
+	/// 
+	/// `delta` is the time elapsed since the last draw frame, same as [`InputState::draw_delta_time`].
+	fn on_draw_frame(&mut self, ctx: &mut Context<Self::Signal, Self>, delta: time::Duration) {
+		let _ = (ctx, delta);
 	}
 	/// Will be called when the os requests the app to exit. If you want to exit the app, return true.
 	fn on_request_exit(&mut self, ctx: &mut Context<Self::Signal, Self>) -> bool { 
 		let _ = ctx;
 		true 
 	}
+	/// Will be called when the app is suspended, e.g. sent to background on mobile platforms.
+	/// 
+	/// The window and the gpu resources are dropped after this, and the program running time stops until resumed.
+	fn on_suspend(&mut self, ctx: &mut Context<Self::Signal, Self>) {
+		let _ = ctx;
+	}
+	/// Will be called when the app is resumed after [`Self::on_suspend`], the gpu resources are already recreated.
+	/// 
+	/// [`Self::on_start`] is not called again.
+	fn on_resume(&mut self, ctx: &mut Context<Self::Signal, Self>) {
+		let _ = ctx;
+	}
 	/// Here you can do some cleanup when the app exits.
 	fn on_exit(&mut self, ctx: &mut Context<Self::Signal, Self>) {
 		let _ = ctx;
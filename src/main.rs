@@ -6,7 +6,6 @@ use time::Duration;
 #[derive(Debug, Clone, Default)]
 struct TestApp {
 	value: u32,
-	last_frame: Duration,
 }
 
 #[derive(Debug, Clone)]
@@ -32,7 +31,7 @@ impl App for TestApp {
 			.on_click(|_, _| Sig::BgClicked)
 			.padding(Vec2::same(16.0))
 			.scroll(Scroll::both())
-			.pin_child(LayoutId(1), Vec2::ZERO) => 
+			.pin_child(LayoutId::new(1, 0), Vec2::ZERO) => 
 		{
 			["Float", FloatingContainer::new().draggable(true) => { Card::new(LayoutStrategy::default())
 				.rounding(Vec4::same(16.0))
@@ -52,7 +51,7 @@ impl App for TestApp {
 			},
 			Reactive::new(Label::new("你点了 0 次按钮"), |app: &mut Self, inner| {
 				inner.text(format!("你点了 {} 次按钮", app.value))
-			}),
+			}).key(|label| label.inner.text.clone()),
 			Divider::new(false),
 			Label::new("内置的单选"),
 			Card::new_horizontal().dont_draw(true).padding(Vec2::same(16.0)).alignments([Alignment::Center, Alignment::Center]) => {
@@ -106,13 +105,14 @@ impl App for TestApp {
 				banned: true,
 				..Default::default()
 			}),
+			InputBox::new(0, EM).placeholder("0 到 100 的整数").validator(RangeValidator::new(Some(0.0), Some(100.0)).integer().clamp(true)).error_label(true),
 			Radio::new_radio("显示密码").on_click(|_, _| Sig::SwitchPassword),
 			Divider::new(false),
 			Radio::new_radio("打开浮动容器").on_click(|_, _| Sig::OpenFLoatContainer),
 			["painter", Canvas::new(Vec2::same(256.0), |_| {}, true)],
 			["painter_projective", Canvas::new(Vec2::same(256.0), |_| {}, true)],
 			["progress_bar", ProgressBar::new().set_length(256.0)],
-			["fps", Label::new("fps: 0.00")],
+			PerfOverlay::new(),
 		});
 
 		println!("Starting...");
@@ -122,17 +122,11 @@ impl App for TestApp {
 		println!("Exiting...");
 	}
 
-	fn on_draw_frame(&mut self, ctx: &mut Context<Sig, Self>) {
+	fn on_draw_frame(&mut self, ctx: &mut Context<Sig, Self>, _: Duration) {
 		let current = ctx.input_state().program_running_time();
 		let t= current.as_seconds_f32().sin() * 0.5 + 0.5; 
 		let t2= (current.as_seconds_f32() / 2.0).sin() * 0.5 + 0.5; 
 		let t4= (current.as_seconds_f32() / 4.0).sin() * 0.5 + 0.5; 
-		let delta = current - self.last_frame;
-		self.last_frame = current;
-		ctx.layout.widget_mut_by_alias::<Label<_, _>>("fps", |inner| {
-			inner.text(format!("fps: {:.2}", 1.0 / delta.as_seconds_f32()))
-		});
-
 		ctx.layout.widget_mut_by_alias::<ProgressBar<_, _>>("progress_bar", |inner| {
 			inner
 				.set_progress_without_animation(t)
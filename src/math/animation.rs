@@ -1,18 +1,19 @@
 //! This file contains the implementation of the animation related structs.
 
-use std::{fmt::Debug, ops::{Add, Index, IndexMut, Mul}};
+use std::{fmt::Debug, ops::{Add, Index, IndexMut}};
 
 use lyon_geom::{point, CubicBezierSegment};
 use time::{Duration, OffsetDateTime};
 
-use super::{color::Color, vec2::Vec2};
+use super::{color::Color, rect::Rect, transform2d::Transform2D, vec2::Vec2};
 
 /// The default duration of an animated f32.
 pub static DEFAULT_ANIMATION_DURATION: Duration = Duration::milliseconds(150);
 
 /// Represents a one dimensional animation.
 #[derive(Default, Clone)]
-#[derive(serde::Serialize, serde::Deserialize, Debug)]
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Animation {
 	/// The start value of the animation.
 	pub start_value: f32,
@@ -22,7 +23,8 @@ pub struct Animation {
 
 /// Represents a node of an animation.
 #[derive(Default, Clone)]
-#[derive(serde::Serialize, serde::Deserialize, Debug)]
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AnimationNode {
 	/// The time relative to the last node.
 	pub time: Duration,
@@ -34,7 +36,8 @@ pub struct AnimationNode {
 
 /// Represents a interpolation function of an animation node.
 #[derive(Default, Clone)]
-#[derive(serde::Serialize, serde::Deserialize, Debug)]
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Linker {
 	/// Become the value of the next node instantly when reach the end of the current node.
 	#[default] Mutation,
@@ -63,6 +66,31 @@ pub enum Linker {
 // 	) -> f32;
 // }
 
+impl Linker {
+	/// Maps the progress between two nodes to the interpolation factor of the node.
+	/// 
+	/// `progress` is expected to be in the range [0, 1].
+	/// The returned factor is 0.0 for the previous value and 1.0 for the next value.
+	pub fn ease(&self, progress: f32) -> f32 {
+		match self {
+			Linker::Mutation => 0.0,
+			Linker::Linear => progress,
+			Linker::Bezier(p1, p2) => {
+				let p1 = p1.clamp_both(Vec2::ZERO, Vec2::ONE);
+				let p2 = p2.clamp_both(Vec2::ZERO, Vec2::ONE);
+				let bezier = CubicBezierSegment {
+					from: point(0.0, 0.0),
+					ctrl1: point(p1.x, p1.y),
+					ctrl2: point(p2.x, p2.y),
+					to: point(1.0, 1.0),
+				};
+				let t = bezier.solve_t_for_x(progress).first().cloned().unwrap_or_default();
+				bezier.y(t)
+			},
+		}
+	}
+}
+
 impl Animation {
 	/// Creates a new animation with the given start value and nodes.
 	pub fn new(start_value: f32, nodes: Vec<AnimationNode>) -> Self {
@@ -313,38 +341,10 @@ impl Animation {
 				continue;
 			}
 
-			out_value = match &node.interpolation {
-				Linker::Mutation => {
-					previous_value
-				},
-				Linker::Linear => {
-					let progress = ((time - current_time) / node.time) as f32;
-					(1.0 - progress) * previous_value + progress * node.value
-				},
-				Linker::Bezier(p1, p2) => {
-					let p1 = p1.clamp_both(Vec2::ZERO, Vec2::ONE);
-					let p2 = p2.clamp_both(Vec2::ZERO, Vec2::ONE);
-					let bezier = CubicBezierSegment {
-						from: point(0.0, 0.0),
-						ctrl1: point(p1.x, p1.y),
-						ctrl2: point(p2.x, p2.y),
-						to: point(1.0, 1.0),
-					};
-					let x = ((time - current_time) / node.time) as f32;
-					let t = bezier.solve_t_for_x(x).first().cloned().unwrap_or_default();
-					let y = bezier.y(t);
-					(1.0 - y) * previous_value + y * node.value
-				},
-				// Linker::Custom(interpolation) => {
-				// 	interpolation.interpolate(
-				// 		current_time, 
-				// 		node.time, 
-				// 		time, 
-				// 		previous_value, 
-				// 		node.value,
-				// 	)
-				// }
-			};
+			let progress = ((time - current_time) / node.time) as f32;
+			let t = node.interpolation.ease(progress);
+			out_value = (1.0 - t) * previous_value + t * node.value;
+			break;
 		}
 
 		out_value
@@ -412,12 +412,62 @@ pub type Animatedf32 = AnimatedValue<f32>;
 /// An animated 2D vector that can be used in a UI.
 pub type AnimatedVec2 = AnimatedValue<Vec2>;
 /// An animated Color(4D vector) that can be used in a UI.
+/// 
+/// Also used for animating roundings, since [`super::color::Vec4`] is the same type.
 pub type AnimatedColor = AnimatedValue<Color>;
+/// An animated rectangle that can be used in a UI.
+pub type AnimatedRect = AnimatedValue<Rect>;
+/// An animated transform that can be used in a UI.
+pub type AnimatedTransform2D = AnimatedValue<Transform2D>;
+
+/// Types that can be linearly interpolated.
+/// 
+/// Any type implementing this trait can be used in an [`AnimatedValue`].
+pub trait Lerp: Sized {
+	/// Linearly interpolate between `self` and `other`.
+	/// 
+	/// `t` is 0.0 for `self` and 1.0 for `other`, values outside of [0, 1] will extrapolate.
+	fn lerp(self, other: Self, t: f32) -> Self;
+}
+
+impl Lerp for f32 {
+	fn lerp(self, other: Self, t: f32) -> Self {
+		self * (1.0 - t) + other * t
+	}
+}
+
+impl Lerp for Vec2 {
+	fn lerp(self, other: Self, t: f32) -> Self {
+		self * (1.0 - t) + other * t
+	}
+}
+
+impl Lerp for Color {
+	fn lerp(self, other: Self, t: f32) -> Self {
+		Color::lerp(self, other, t)
+	}
+}
+
+impl Lerp for Rect {
+	fn lerp(self, other: Self, t: f32) -> Self {
+		Rect::lerp(self, other, t)
+	}
+}
+
+impl Lerp for Transform2D {
+	/// Interpolates each component of the matrix.
+	/// 
+	/// Works well for translations and scales, but rotations will not keep the length of the axes.
+	fn lerp(self, other: Self, t: f32) -> Self {
+		self * (1.0 - t) + other * t
+	}
+}
 
 /// An animated value that can be used in a UI.
 /// 
 /// By default, the animation will be a beizer interpolation with control points (0.5, 0.0) and (0.5, 1.0) between 0.0 and 1.0.
-pub struct AnimatedValue<T: Add + Mul<f32> + PartialEq + Clone> {
+#[derive(Clone)]
+pub struct AnimatedValue<T: AnimatedValueExt> {
 	animation: Animation,
 	last_changes: OffsetDateTime,
 	from: T,
@@ -425,9 +475,9 @@ pub struct AnimatedValue<T: Add + Mul<f32> + PartialEq + Clone> {
 }
 
 /// Extension trait for AnimatedValue. Used for shorthand syntax.
-pub trait AnimatedValueExt: Add<Output = Self> + Mul<f32, Output = Self> + PartialEq + Clone {}
+pub trait AnimatedValueExt: Lerp + PartialEq + Clone {}
 
-impl<T: Add<Output = Self> + Mul<f32, Output = Self> + PartialEq + Clone> AnimatedValueExt for T {}
+impl<T: Lerp + PartialEq + Clone> AnimatedValueExt for T {}
 
 
 impl<T: AnimatedValueExt + Default> Default for AnimatedValue<T> {
@@ -466,6 +516,22 @@ impl<T: AnimatedValueExt + PartialOrd> PartialOrd for AnimatedValue<T> {
 	}
 }
 
+/// Serialized as the value the animation is heading to, the animation itself is not kept.
+#[cfg(feature = "serde")]
+impl<T: AnimatedValueExt + serde::Serialize> serde::Serialize for AnimatedValue<T> {
+	fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		self.to.serialize(serializer)
+	}
+}
+
+/// Deserialized with the default animation, see [`AnimatedValue::default_with_value`].
+#[cfg(feature = "serde")]
+impl<'de, T: AnimatedValueExt + serde::Deserialize<'de>> serde::Deserialize<'de> for AnimatedValue<T> {
+	fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		T::deserialize(deserializer).map(Self::default_with_value)
+	}
+}
+
 impl<T: AnimatedValueExt> AnimatedValue<T> {
 	/// Creates a new animated value with the given animation.
 	/// 
@@ -504,7 +570,12 @@ impl<T: AnimatedValueExt> AnimatedValue<T> {
 		let now = OffsetDateTime::now_utc();
 		let t = self.animation.value_at(now - self.last_changes);
 		// println!("{}, {}", self.animation.start_value, self.animation.last_value());
-		self.from.clone() * (1.0 - t) + self.to.clone() * t
+		self.from.clone().lerp(self.to.clone(), t)
+	}
+
+	/// Returns the value the animation is heading to.
+	pub fn target(&self) -> T {
+		self.to.clone()
 	}
 
 	/// Sets the new value of the animation.
@@ -517,9 +588,18 @@ impl<T: AnimatedValueExt> AnimatedValue<T> {
 		}
 	}
 
-	/// Sets the new value of the animation by a delta.
-	pub fn set_by(&mut self, delta: T) {
-		self.set(self.to.clone() + delta)
+	/// Continue from the value this one replaces, keeping the own animation and target.
+	/// 
+	/// If both are heading to the same target, the running animation of `old` goes on,
+	/// otherwise a new animation starts from the current value of `old` to the own target.
+	pub fn continue_from(&mut self, old: &Self) {
+		if self.to == old.to {
+			self.from = old.from.clone();
+			self.last_changes = old.last_changes;
+		}else {
+			self.from = old.value();
+			self.last_changes = OffsetDateTime::now_utc();
+		}
 	}
 
 	/// Sets the new value of the animation without animating.
@@ -542,6 +622,13 @@ impl<T: AnimatedValueExt> AnimatedValue<T> {
 	}
 }
 
+impl<T: AnimatedValueExt + Add<Output = T>> AnimatedValue<T> {
+	/// Sets the new value of the animation by a delta.
+	pub fn set_by(&mut self, delta: T) {
+		self.set(self.to.clone() + delta)
+	}
+}
+
 impl <T: AnimatedValueExt + PartialOrd> AnimatedValue<T> {
 	/// Clamps the value of the animation between the given min and max values.
 	pub fn clamp(&mut self, min: T, max: T) {
@@ -13,7 +13,7 @@ pub type Vec4 = Color;
 /// You can also use the `Color` as a `Vec4` Type if you want by using `x`, `y`, `z`, and `w` methods.
 /// The color is unpremultiplied. You need use `premultiply` method to get the premultiplied color manually.
 #[derive(Debug, Clone, Copy, PartialEq)]
-#[derive(serde::Deserialize, serde::Serialize)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Color {
 	pub r: f32,
 	pub g: f32,
@@ -127,7 +127,7 @@ impl Color {
 			240.0..=300.0 => (x, 0.0, c),
 			_ => (c, 0.0, x),
 		};
-		Self::new((r + m) * 255.0, (g + m) * 255.0, (b + m) * 255.0, 1.0)
+		Self::new(r + m, g + m, b + m, 1.0)
 	}
 
 	/// Create a new color with the given HSLA values.
@@ -145,7 +145,7 @@ impl Color {
 			240.0..=300.0 => (x, 0.0, c),
 			_ => (c, 0.0, x),
 		};
-		Self::new((r + m) * 255.0, (g + m) * 255.0, (b + m) * 255.0, a)
+		Self::new(r + m, g + m, b + m, a)
 	}
 
 	/// Create a new color with the given HSV values.
@@ -163,7 +163,7 @@ impl Color {
 			240.0..=300.0 => (x, 0.0, c),
 			_ => (c, 0.0, x),
 		};
-		Self::new((r + m) * 255.0, (g + m) * 255.0, (b + m) * 255.0, 1.0)
+		Self::new(r + m, g + m, b + m, 1.0)
 	}
 
 	/// Create a new color with the given HSVA values.
@@ -181,7 +181,7 @@ impl Color {
 			240.0..=300.0 => (x, 0.0, c),
 			_ => (c, 0.0, x),
 		};
-		Self::new((r + m) * 255.0, (g + m) * 255.0, (b + m) * 255.0, a)
+		Self::new(r + m, g + m, b + m, a)
 	}
 
 	/// Create a new color with the given CMYK values.
@@ -627,4 +627,21 @@ impl std::fmt::Display for Color {
 	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
 		write!(f, "({}, {}, {}, {})", self.r, self.g, self.b, self.a)
 	}
-}
\ No newline at end of file
+}
+mod test {
+	#[test]
+	fn test_hsv_round_trip() {
+		use crate::prelude::Color;
+
+		fn close(a: Color, b: Color) -> bool {
+			(a - b).length() < 1e-4
+		}
+
+		assert!(close(Color::from_hsv(0.0, 1.0, 1.0), Color::RED));
+		assert!(close(Color::from_hsva(240.0, 1.0, 1.0, 0.5), Color::new(0.0, 0.0, 1.0, 0.5)));
+		assert!(close(Color::from_hsl(120.0, 1.0, 0.5), Color::GREEN));
+		let color = Color::new(0.2, 0.6, 0.4, 0.8);
+		let hsva = color.to_hsva();
+		assert!(close(Color::from_hsva(hsva.r, hsva.g, hsva.b, hsva.a), color));
+	}
+}
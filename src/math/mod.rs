@@ -8,3 +8,4 @@ pub mod color;
 pub mod transform2d;
 pub mod prelude;
 pub mod animation;
+pub mod timeline;
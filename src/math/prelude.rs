@@ -4,4 +4,5 @@ pub use super::color::*;
 pub use super::vec2::*;
 pub use super::rect::*;
 pub use super::transform2d::*;
-pub use super::animation::*;
\ No newline at end of file
+pub use super::animation::*;
+pub use super::timeline::*;
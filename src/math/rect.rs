@@ -6,9 +6,21 @@ use rstar::{Envelope, Point};
 
 use super::{prelude::Transform2D, vec2::Vec2};
 
+/// The alignment of the contents along one axis, see [`Rect::align_inside`].
+#[derive(Clone, Debug, PartialEq, Eq, Default, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Alignment {
+	/// Align the contents to the left or top.
+	#[default] Positive,
+	/// Align the contents to the center.
+	Center,
+	/// Align the contents to the right or bottom.
+	Negative,
+}
+
 /// A simple rectangle class with logical operators and methods.
 #[derive(Debug, Clone, Copy, PartialEq)]
-#[derive(serde::Deserialize, serde::Serialize)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Rect {
 	pub x: f32,
 	pub y: f32,
@@ -213,6 +225,113 @@ impl Rect {
 		Self::new(self.x, self.y, w, h)
 	}
 
+	/// Shrink each side of the rectangle by the given amount.
+	/// 
+	/// Negative values will grow the side instead.
+	pub fn inset(self, left: f32, top: f32, right: f32, bottom: f32) -> Self {
+		Self::new(self.x + left, self.y + top, self.w - left - right, self.h - top - bottom)
+	}
+
+	/// Grow each side of the rectangle by the given amount.
+	/// 
+	/// Negative values will shrink the side instead.
+	pub fn inflate(self, left: f32, top: f32, right: f32, bottom: f32) -> Self {
+		self.inset(-left, -top, -right, -bottom)
+	}
+
+	/// Split the rectangle into a left part with the given width and the remainder.
+	/// 
+	/// The width will be clamped to the width of the rectangle.
+	pub fn split_left(self, width: f32) -> (Self, Self) {
+		let width = width.clamp(0.0, self.w.max(0.0));
+		(
+			Self::new(self.x, self.y, width, self.h),
+			Self::new(self.x + width, self.y, self.w - width, self.h),
+		)
+	}
+
+	/// Split the rectangle into a right part with the given width and the remainder.
+	/// 
+	/// The width will be clamped to the width of the rectangle.
+	pub fn split_right(self, width: f32) -> (Self, Self) {
+		let width = width.clamp(0.0, self.w.max(0.0));
+		(
+			Self::new(self.x + self.w - width, self.y, width, self.h),
+			Self::new(self.x, self.y, self.w - width, self.h),
+		)
+	}
+
+	/// Split the rectangle into a top part with the given height and the remainder.
+	/// 
+	/// The height will be clamped to the height of the rectangle.
+	pub fn split_top(self, height: f32) -> (Self, Self) {
+		let height = height.clamp(0.0, self.h.max(0.0));
+		(
+			Self::new(self.x, self.y, self.w, height),
+			Self::new(self.x, self.y + height, self.w, self.h - height),
+		)
+	}
+
+	/// Split the rectangle into a bottom part with the given height and the remainder.
+	/// 
+	/// The height will be clamped to the height of the rectangle.
+	pub fn split_bottom(self, height: f32) -> (Self, Self) {
+		let height = height.clamp(0.0, self.h.max(0.0));
+		(
+			Self::new(self.x, self.y + self.h - height, self.w, height),
+			Self::new(self.x, self.y, self.w, self.h - height),
+		)
+	}
+
+	/// Place a rectangle of the given size inside this rectangle.
+	/// 
+	/// The first alignment is for the horizontal axis, the second one is for the vertical axis.
+	pub fn align_inside(self, size: impl Into<Vec2>, alignment: [Alignment; 2]) -> Self {
+		let size = size.into();
+		fn offset(alignment: Alignment, free: f32) -> f32 {
+			match alignment {
+				Alignment::Positive => 0.0,
+				Alignment::Center => free / 2.0,
+				Alignment::Negative => free,
+			}
+		}
+		Self::new(
+			self.x + offset(alignment[0], self.w - size.x),
+			self.y + offset(alignment[1], self.h - size.y),
+			size.x,
+			size.y,
+		)
+	}
+
+	/// Split the rectangle into a grid of cells with the given gap between them.
+	/// 
+	/// The cells are returned row by row.
+	/// Will return an empty vector if `rows` or `columns` is zero.
+	pub fn grid_cells(self, rows: usize, columns: usize, gap: impl Into<Vec2>) -> Vec<Self> {
+		if rows == 0 || columns == 0 {
+			return vec!();
+		}
+		let gap = gap.into();
+		let cell_size = Vec2::new(
+			((self.w - gap.x * (columns - 1) as f32) / columns as f32).max(0.0),
+			((self.h - gap.y * (rows - 1) as f32) / rows as f32).max(0.0),
+		);
+		let mut cells = Vec::with_capacity(rows * columns);
+		for row in 0..rows {
+			for column in 0..columns {
+				let lt = self.lt() + Vec2::new(column as f32, row as f32) * (cell_size + gap);
+				cells.push(Self::from_lt_size(lt, cell_size));
+			}
+		}
+		cells
+	}
+
+	/// Get the smallest rectangle containing both this rectangle and the given point.
+	pub fn expand_to_include(self, point: impl Into<Vec2>) -> Self {
+		let point = point.into();
+		Self::from_ltrb(self.lt().min(point), self.rb().max(point))
+	}
+
 	/// Move the rectangle to the given position.
 	pub fn move_to(self, pos: impl Into<Vec2>) -> Self {
 		let pos = pos.into();
@@ -0,0 +1,463 @@
+//! This file contains the implementation of keyframe timelines.
+//!
+//! A [`Timeline`] drives several properties (position, opacity, color, rotation, ...) of one or more widgets in sync.
+//! Each property is a [`Keyframes`] track, and all tracks share the same playback state.
+
+use std::hash::Hash;
+
+use indexmap::IndexMap;
+use time::{Duration, OffsetDateTime};
+
+use super::{animation::{AnimatedValueExt, Linker}, color::Color, vec2::Vec2};
+
+/// Represents a keyframe of a [`Keyframes`] track.
+#[derive(Default, Clone)]
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Keyframe<T> {
+	/// The time relative to the last keyframe.
+	pub time: Duration,
+	/// The value of the keyframe.
+	pub value: T,
+	/// The interpolation function used to reach this keyframe from the previous one.
+	pub interpolation: Linker,
+}
+
+/// Represents a track of keyframes for a single property.
+///
+/// Works like [`super::animation::Animation`], but the values can be any type that can be interpolated.
+#[derive(Default, Clone)]
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Keyframes<T> {
+	/// The value of the track at time 0.
+	pub start_value: T,
+	/// The keyframes of the track.
+	pub frames: Vec<Keyframe<T>>,
+}
+
+impl<T: AnimatedValueExt> Keyframes<T> {
+	/// Creates a new track with the given start value and no keyframes.
+	pub fn new(start_value: T) -> Self {
+		Self {
+			start_value,
+			frames: Vec::new(),
+		}
+	}
+
+	/// Adds a keyframe reached `time` after the previous one.
+	pub fn then(mut self, time: Duration, value: T, interpolation: Linker) -> Self {
+		self.push(Keyframe { time, value, interpolation });
+		self
+	}
+
+	/// Adds a new keyframe to the track.
+	pub fn push(&mut self, frame: Keyframe<T>) {
+		self.frames.push(frame);
+	}
+
+	/// Get the total duration of the track.
+	pub fn duration(&self) -> Duration {
+		self.frames.iter().map(|frame| frame.time).sum()
+	}
+
+	/// Get the last value of the track.
+	pub fn last_value(&self) -> T {
+		self.frames.last().map(|frame| frame.value.clone()).unwrap_or(self.start_value.clone())
+	}
+
+	/// Calculates the interpolated value of the track at the given time.
+	///
+	/// If the time is greater than the duration of the track, the last value will be returned.
+	///
+	/// If the track has no keyframes or the time is less than or equal to 0, the start value will be returned.
+	pub fn value_at(&self, time: Duration) -> T {
+		if self.frames.is_empty() || time <= Duration::ZERO {
+			return self.start_value.clone();
+		}
+
+		let mut current_time = Duration::ZERO;
+		let mut previous_value = &self.start_value;
+		for frame in &self.frames {
+			if current_time + frame.time < time {
+				current_time += frame.time;
+				previous_value = &frame.value;
+				continue;
+			}
+
+			let progress = if frame.time <= Duration::ZERO {
+				1.0
+			}else {
+				((time - current_time) / frame.time) as f32
+			};
+			let t = frame.interpolation.ease(progress);
+			return previous_value.clone().lerp(frame.value.clone(), t);
+		}
+
+		self.last_value()
+	}
+}
+
+/// Represents a track of a [`Timeline`].
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TimelineTrack {
+	/// A scalar property, such as opacity or rotation.
+	Float(Keyframes<f32>),
+	/// A 2D property, such as position or scale.
+	Vec2(Keyframes<Vec2>),
+	/// A color property.
+	Color(Keyframes<Color>),
+}
+
+/// Represents the value of a [`TimelineTrack`] at some time.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TimelineValue {
+	/// The value of a [`TimelineTrack::Float`].
+	Float(f32),
+	/// The value of a [`TimelineTrack::Vec2`].
+	Vec2(Vec2),
+	/// The value of a [`TimelineTrack::Color`].
+	Color(Color),
+}
+
+impl TimelineTrack {
+	/// Get the total duration of the track.
+	pub fn duration(&self) -> Duration {
+		match self {
+			Self::Float(track) => track.duration(),
+			Self::Vec2(track) => track.duration(),
+			Self::Color(track) => track.duration(),
+		}
+	}
+
+	/// Calculates the value of the track at the given time.
+	pub fn value_at(&self, time: Duration) -> TimelineValue {
+		match self {
+			Self::Float(track) => TimelineValue::Float(track.value_at(time)),
+			Self::Vec2(track) => TimelineValue::Vec2(track.value_at(time)),
+			Self::Color(track) => TimelineValue::Color(track.value_at(time)),
+		}
+	}
+}
+
+/// How a [`Timeline`] behaves when it reaches its end.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PlaybackMode {
+	/// Play the timeline once and stay at the last keyframe.
+	#[default] Once,
+	/// Restart from the beginning when the end is reached.
+	Loop,
+	/// Play forward, then backward, and repeat.
+	PingPong,
+}
+
+/// A set of keyframe tracks played in sync.
+///
+/// Tracks are identified by a key of type `K`, for example `(LayoutId, &'static str)` to animate several properties of several widgets.
+///
+/// The timeline is time based, so you should read the values in [`crate::App::on_draw_frame`] or [`crate::App::on_event_frame`]
+/// and request a redraw while [`Timeline::is_animating`] returns true.
+/// Use [`Timeline::take_finished`] to get notified when a [`PlaybackMode::Once`] timeline completes.
+#[derive(Clone, Debug)]
+pub struct Timeline<K: Hash + Eq> {
+	tracks: IndexMap<K, TimelineTrack>,
+	mode: PlaybackMode,
+	/// The elapsed time when the timeline was paused or seeked for the last time.
+	elapsed: Duration,
+	/// The moment the timeline was resumed, `None` if paused.
+	resumed_at: Option<OffsetDateTime>,
+	finish_reported: bool,
+}
+
+impl<K: Hash + Eq> Default for Timeline<K> {
+	fn default() -> Self {
+		Self {
+			tracks: IndexMap::new(),
+			mode: PlaybackMode::default(),
+			elapsed: Duration::ZERO,
+			resumed_at: None,
+			finish_reported: false,
+		}
+	}
+}
+
+impl<K: Hash + Eq> Timeline<K> {
+	/// Creates a new paused timeline with no tracks.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Sets the playback mode of the timeline.
+	pub fn mode(self, mode: PlaybackMode) -> Self {
+		Self {
+			mode,
+			..self
+		}
+	}
+
+	/// Adds a track to the timeline, replacing the track with the same key.
+	pub fn track(mut self, key: K, track: TimelineTrack) -> Self {
+		self.insert_track(key, track);
+		self
+	}
+
+	/// Adds a scalar track to the timeline.
+	pub fn float_track(self, key: K, track: Keyframes<f32>) -> Self {
+		self.track(key, TimelineTrack::Float(track))
+	}
+
+	/// Adds a 2D track to the timeline.
+	pub fn vec2_track(self, key: K, track: Keyframes<Vec2>) -> Self {
+		self.track(key, TimelineTrack::Vec2(track))
+	}
+
+	/// Adds a color track to the timeline.
+	pub fn color_track(self, key: K, track: Keyframes<Color>) -> Self {
+		self.track(key, TimelineTrack::Color(track))
+	}
+
+	/// Inserts a track into the timeline, returns the replaced track if any.
+	pub fn insert_track(&mut self, key: K, track: TimelineTrack) -> Option<TimelineTrack> {
+		self.tracks.insert(key, track)
+	}
+
+	/// Removes a track from the timeline.
+	pub fn remove_track(&mut self, key: &K) -> Option<TimelineTrack> {
+		self.tracks.shift_remove(key)
+	}
+
+	/// Sets the playback mode of the timeline.
+	pub fn set_mode(&mut self, mode: PlaybackMode) {
+		self.mode = mode;
+	}
+
+	/// Get the playback mode of the timeline.
+	pub fn playback_mode(&self) -> PlaybackMode {
+		self.mode
+	}
+
+	/// Get the duration of one cycle of the timeline, which is the duration of the longest track.
+	pub fn duration(&self) -> Duration {
+		self.tracks.values().map(|track| track.duration()).max().unwrap_or(Duration::ZERO)
+	}
+
+	/// Starts or resumes the timeline.
+	///
+	/// Will restart from the beginning if a [`PlaybackMode::Once`] timeline has already finished.
+	pub fn play(&mut self) {
+		if self.is_playing() {
+			return;
+		}
+		if self.is_finished() {
+			self.elapsed = Duration::ZERO;
+			self.finish_reported = false;
+		}
+		self.resumed_at = Some(OffsetDateTime::now_utc());
+	}
+
+	/// Pauses the timeline at the current time.
+	pub fn pause(&mut self) {
+		if self.is_playing() {
+			self.elapsed = self.total_elapsed();
+			self.resumed_at = None;
+		}
+	}
+
+	/// Toggles between playing and paused.
+	pub fn toggle(&mut self) {
+		if self.is_playing() {
+			self.pause();
+		}else {
+			self.play();
+		}
+	}
+
+	/// Pauses the timeline and rewinds it to the beginning.
+	pub fn stop(&mut self) {
+		self.resumed_at = None;
+		self.elapsed = Duration::ZERO;
+		self.finish_reported = false;
+	}
+
+	/// Moves the timeline to the given time without changing whether it is playing.
+	pub fn seek(&mut self, time: Duration) {
+		self.elapsed = time.max(Duration::ZERO);
+		if self.resumed_at.is_some() {
+			self.resumed_at = Some(OffsetDateTime::now_utc());
+		}
+		self.finish_reported = false;
+	}
+
+	/// Moves the timeline to the given progress of one cycle, in the range [0, 1].
+	pub fn seek_progress(&mut self, progress: f32) {
+		self.seek(self.duration() * progress.clamp(0.0, 1.0));
+	}
+
+	/// Returns true if the timeline is playing.
+	pub fn is_playing(&self) -> bool {
+		self.resumed_at.is_some()
+	}
+
+	/// Returns true if a [`PlaybackMode::Once`] timeline reached its end.
+	///
+	/// Looping timelines never finish.
+	pub fn is_finished(&self) -> bool {
+		self.mode == PlaybackMode::Once && self.total_elapsed() >= self.duration()
+	}
+
+	/// Returns true if the values of the timeline are changing, which means the ui should be redrawn.
+	pub fn is_animating(&self) -> bool {
+		self.is_playing() && !self.is_finished() && self.duration() > Duration::ZERO
+	}
+
+	/// Returns true once after a [`PlaybackMode::Once`] timeline finished.
+	///
+	/// Intended to be polled every frame to emit a completion signal.
+	pub fn take_finished(&mut self) -> bool {
+		if !self.finish_reported && self.is_finished() {
+			self.finish_reported = true;
+			true
+		}else {
+			false
+		}
+	}
+
+	/// Get the number of full cycles played since the timeline started.
+	pub fn cycles(&self) -> u32 {
+		let duration = self.duration();
+		if duration <= Duration::ZERO {
+			return 0;
+		}
+		(self.total_elapsed() / duration) as u32
+	}
+
+	/// Get the time elapsed since the start of the timeline, ignoring the playback mode.
+	pub fn total_elapsed(&self) -> Duration {
+		match self.resumed_at {
+			Some(resumed_at) => self.elapsed + (OffsetDateTime::now_utc() - resumed_at),
+			None => self.elapsed,
+		}
+	}
+
+	/// Get the current position in the timeline, taking the playback mode into account.
+	pub fn current_time(&self) -> Duration {
+		let duration = self.duration();
+		let elapsed = self.total_elapsed();
+		if duration <= Duration::ZERO {
+			return Duration::ZERO;
+		}
+
+		match self.mode {
+			PlaybackMode::Once => elapsed.min(duration),
+			PlaybackMode::Loop => {
+				let cycles = (elapsed / duration).floor();
+				elapsed - duration * cycles
+			},
+			PlaybackMode::PingPong => {
+				let cycles = (elapsed / duration).floor();
+				let time = elapsed - duration * cycles;
+				if cycles as i64 % 2 == 0 {
+					time
+				}else {
+					duration - time
+				}
+			},
+		}
+	}
+
+	/// Get the current progress of the cycle, in the range [0, 1].
+	pub fn progress(&self) -> f32 {
+		let duration = self.duration();
+		if duration <= Duration::ZERO {
+			return 0.0;
+		}
+		(self.current_time() / duration) as f32
+	}
+
+	/// Get the current value of the given track.
+	pub fn value(&self, key: &K) -> Option<TimelineValue> {
+		self.tracks.get(key).map(|track| track.value_at(self.current_time()))
+	}
+
+	/// Get the current value of the given scalar track.
+	///
+	/// Returns `None` if the track does not exist or is not a scalar track.
+	pub fn float(&self, key: &K) -> Option<f32> {
+		match self.value(key)? {
+			TimelineValue::Float(value) => Some(value),
+			_ => None,
+		}
+	}
+
+	/// Get the current value of the given 2D track.
+	///
+	/// Returns `None` if the track does not exist or is not a 2D track.
+	pub fn vec2(&self, key: &K) -> Option<Vec2> {
+		match self.value(key)? {
+			TimelineValue::Vec2(value) => Some(value),
+			_ => None,
+		}
+	}
+
+	/// Get the current value of the given color track.
+	///
+	/// Returns `None` if the track does not exist or is not a color track.
+	pub fn color(&self, key: &K) -> Option<Color> {
+		match self.value(key)? {
+			TimelineValue::Color(value) => Some(value),
+			_ => None,
+		}
+	}
+
+	/// Iterates over the current values of all tracks.
+	pub fn values(&self) -> impl Iterator<Item = (&K, TimelineValue)> {
+		let time = self.current_time();
+		self.tracks.iter().map(move |(key, track)| (key, track.value_at(time)))
+	}
+}
+
+mod test {
+	#[test]
+	fn test_timeline_playback() {
+		use time::Duration;
+		use super::{Keyframes, PlaybackMode, Timeline};
+		use crate::prelude::Linker;
+
+		let close = |a: Option<f32>, b: f32| a.is_some_and(|a| (a - b).abs() < 1e-4);
+		// a paused timeline only moves by seeking, so the values do not depend on the clock.
+		let track = Keyframes::new(0.0).then(Duration::seconds(1), 10.0, Linker::Linear);
+		let mut timeline = Timeline::new().float_track("x", track);
+		assert_eq!(timeline.duration(), Duration::seconds(1));
+		assert!(close(timeline.float(&"x"), 0.0));
+		assert_eq!(timeline.vec2(&"x"), None);
+
+		timeline.seek(Duration::milliseconds(250));
+		assert!(close(timeline.float(&"x"), 2.5));
+		timeline.seek_progress(0.5);
+		assert!(close(timeline.float(&"x"), 5.0));
+		assert!(!timeline.is_finished());
+		timeline.seek(Duration::milliseconds(1500));
+		assert!(close(timeline.float(&"x"), 10.0));
+		assert!(timeline.is_finished());
+		assert!(timeline.take_finished());
+		assert!(!timeline.take_finished());
+
+		timeline.set_mode(PlaybackMode::Loop);
+		assert!(!timeline.is_finished());
+		assert!(close(timeline.float(&"x"), 5.0));
+		assert_eq!(timeline.cycles(), 1);
+
+		timeline.set_mode(PlaybackMode::PingPong);
+		timeline.seek(Duration::milliseconds(1250));
+		assert!(close(timeline.float(&"x"), 7.5));
+		timeline.seek(Duration::milliseconds(2250));
+		assert!(close(timeline.float(&"x"), 2.5));
+		assert!(close(Some(timeline.progress()), 0.25));
+
+		timeline.stop();
+		assert_eq!(timeline.total_elapsed(), Duration::ZERO);
+		assert!(!timeline.is_playing());
+	}
+}
@@ -2,7 +2,7 @@
 
 use std::ops::{Add, AddAssign, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Shl, ShlAssign, Shr, ShrAssign, Sub, SubAssign};
 
-use super::vec2::Vec2;
+use super::{rect::Rect, vec2::Vec2};
 
 /// A 2D transformation matrix.
 /// 
@@ -19,7 +19,7 @@ use super::vec2::Vec2;
 /// You can use indexing to access the components of the matrix, 
 /// and the `Default` trait to create an identity matrix.
 #[derive(Debug, Clone, Copy, PartialEq)]
-#[derive(serde::Deserialize, serde::Serialize)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Transform2D([[f32; 3]; 3]);
 
 impl Default for Transform2D {
@@ -102,9 +102,124 @@ impl Transform2D {
 		)
 	}
 
+	/// Creates a new 2D transformation matrix that skews by the given angles in radians.
+	/// 
+	/// `x` skews along the x axis (shearing horizontal lines), `y` skews along the y axis.
+	pub fn skew(x: f32, y: f32) -> Self {
+		Self::column_major(
+			1.0, x.tan(), 0.0, 
+			y.tan(), 1.0, 0.0
+		)
+	}
+
+	/// Creates a new 2D transformation matrix that skews by the given angles in degrees.
+	pub fn skew_degrees(x: f32, y: f32) -> Self {
+		Self::skew(x.to_radians(), y.to_radians())
+	}
+
+	/// Creates a new 2D transformation matrix that maps the rectangle `from` onto the rectangle `to`.
+	/// 
+	/// Useful for mapping between coordinate spaces, e.g. from canvas space to screen space.
+	/// Will produce non-finite values if `from` has zero width or height.
+	pub fn from_rect_to_rect(from: Rect, to: Rect) -> Self {
+		Self::translate(to.lt()) >> Self::scale(to.size() / from.size()) >> Self::translate(- from.lt())
+	}
+
 	/// Calculates the inverse of the transformation matrix.
+	/// 
+	/// Will produce non-finite values if the matrix is not invertible, see [`Self::invert`] for a checked version.
 	pub fn inverse(self) -> Self {
-		self.cofactor_matrix() / self.det()
+		self.adjugate() / self.det()
+	}
+
+	/// Calculates the inverse of the transformation matrix.
+	/// 
+	/// Returns `None` if the matrix is not invertible.
+	pub fn invert(self) -> Option<Self> {
+		let det = self.det();
+		if det.abs() <= f32::EPSILON || !det.is_finite() {
+			None
+		}else {
+			Some(self.adjugate() / det)
+		}
+	}
+
+	/// Calculates the transpose of the transformation matrix.
+	pub fn transpose(self) -> Self {
+		let mut result = Transform2D([[0.0; 3]; 3]);
+		for (col, column) in self.0.iter().enumerate() {
+			for (row, value) in column.iter().enumerate() {
+				result.0[row][col] = *value;
+			}
+		}
+		result
+	}
+
+	/// Calculates the adjugate matrix of the transformation matrix, which is the transpose of the cofactor matrix.
+	pub fn adjugate(self) -> Self {
+		self.cofactor_matrix().transpose()
+	}
+
+	/// Decomposes an affine transformation matrix into `(translation, rotation, scale, skew)`.
+	/// 
+	/// The rotation and skew are in radians, and the skew is along the x axis.
+	/// The original matrix can be rebuilt by
+	/// `Transform2D::translate(translation) >> Transform2D::rotate(rotation) >> Transform2D::skew(skew, 0.0) >> Transform2D::scale(scale)`.
+	/// 
+	/// A reflection will be represented as a negative y scale.
+	/// The projective part of the matrix is ignored.
+	pub fn decompose(&self) -> (Vec2, f32, Vec2, f32) {
+		let translation = self.translation();
+		let x_axis = Vec2::new(self.0[0][0], self.0[0][1]);
+		let y_axis = Vec2::new(self.0[1][0], self.0[1][1]);
+
+		let scale_x = x_axis.length();
+		if scale_x == 0.0 {
+			return (translation, 0.0, Vec2::new(0.0, y_axis.length()), 0.0);
+		}
+		let rotation = x_axis.y.atan2(x_axis.x);
+		let (sin, cos) = rotation.sin_cos();
+		let scale_y = cos * y_axis.y - sin * y_axis.x;
+		let shear = if scale_y == 0.0 {
+			0.0
+		}else {
+			(cos * y_axis.x + sin * y_axis.y) / scale_y
+		};
+
+		(translation, rotation, Vec2::new(scale_x, scale_y), shear.atan())
+	}
+
+	/// Get the translation part of the transformation matrix.
+	pub fn translation(&self) -> Vec2 {
+		Vec2::new(self.0[2][0], self.0[2][1])
+	}
+
+	/// Applies the transformation to a point, same as `self >> point`.
+	pub fn transform_point(&self, point: impl Into<Vec2>) -> Vec2 {
+		self.apply(point)
+	}
+
+	/// Applies the linear part of the transformation to a vector, ignoring the translation.
+	/// 
+	/// Useful for transforming directions and offsets such as drag deltas.
+	pub fn transform_vector(&self, vector: impl Into<Vec2>) -> Vec2 {
+		let vector = vector.into();
+		Vec2::new(
+			self.0[0][0] * vector.x + self.0[1][0] * vector.y,
+			self.0[0][1] * vector.x + self.0[1][1] * vector.y,
+		)
+	}
+
+	/// Applies the inverse of the transformation to a point.
+	/// 
+	/// Returns `None` if the matrix is not invertible.
+	pub fn inverse_transform_point(&self, point: impl Into<Vec2>) -> Option<Vec2> {
+		self.invert().map(|inverse| inverse.apply(point))
+	}
+
+	/// Applies the transformation to a rectangle, see [`Rect::transformed`].
+	pub fn transform_rect(&self, rect: Rect) -> Rect {
+		rect.transformed(*self)
 	}
 
 	/// Calculates the determinant of the transformation matrix.
@@ -402,4 +517,34 @@ mod test {
 		assert_eq!(lhs << rhs, expected_r);
 		assert_eq!(lhs >> vec, Vec2::new(26.0, 74.0));
 	}
-}
\ No newline at end of file
+
+	#[test]
+	fn test_inverse_and_decompose() {
+		use crate::prelude::Vec2;
+		use crate::prelude::Rect;
+		use crate::prelude::Transform2D;
+
+		fn close(a: Vec2, b: Vec2) -> bool {
+			(a - b).length() < 1e-4
+		}
+
+		let mat = Transform2D::translate(Vec2::new(3.0, -2.0)) >> 
+			Transform2D::rotate(0.5) >> 
+			Transform2D::skew(0.25, 0.0) >> 
+			Transform2D::scale(Vec2::new(2.0, 3.0));
+		let point = Vec2::new(5.0, 7.0);
+		let inverse = mat.invert().unwrap();
+		assert!(close(inverse >> (mat >> point), point));
+		assert!(Transform2D::scale(Vec2::new(0.0, 1.0)).invert().is_none());
+
+		let (translation, rotation, scale, skew) = mat.decompose();
+		assert!(close(translation, Vec2::new(3.0, -2.0)));
+		assert!((rotation - 0.5).abs() < 1e-4);
+		assert!(close(scale, Vec2::new(2.0, 3.0)));
+		assert!((skew - 0.25).abs() < 1e-4);
+
+		let mapping = Transform2D::from_rect_to_rect(Rect::new(0.0, 0.0, 10.0, 10.0), Rect::new(10.0, 20.0, 20.0, 5.0));
+		assert!(close(mapping >> Vec2::new(10.0, 10.0), Vec2::new(30.0, 25.0)));
+		assert!(close(mapping.transform_vector(Vec2::new(1.0, 1.0)), Vec2::new(2.0, 0.5)));
+	}
+}
@@ -6,7 +6,7 @@ use rstar::Point;
 
 /// A simple 2D vector implementation
 #[derive(Debug, Copy, Clone, PartialEq, Default)]
-#[derive(serde::Deserialize, serde::Serialize)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Vec2 {
 	pub x: f32,
 	pub y: f32,
@@ -82,6 +82,30 @@ impl Vec2 {
 		other * (self.dot(other) / other.dot(other))
 	}
 
+	/// Get the projection of one vector onto another
+	/// 
+	/// Unlike [`Self::project`], returns zero if `other` is zero instead of nan values.
+	pub fn project_onto(self, other: Self) -> Self {
+		let length_squared = other.length_squared();
+		if length_squared == 0.0 {
+			Self::ZERO
+		} else {
+			other * (self.dot(other) / length_squared)
+		}
+	}
+
+	/// Get the signed angle in radians needed to rotate this vector onto another
+	/// 
+	/// The result is in the range (-PI, PI], positive values are in the same direction as [`Self::rotated`].
+	pub fn angle_to(self, other: Self) -> f32 {
+		self.cross(other).atan2(self.dot(other))
+	}
+
+	/// Get the signed angle in degrees needed to rotate this vector onto another
+	pub fn angle_to_degrees(self, other: Self) -> f32 {
+		self.angle_to(other).to_degrees()
+	}
+
 	/// Get the reflection of one vector off of another
 	pub fn reflect(self, other: Self) -> Self {
 		self - 2.0 * self.project(other)
@@ -136,6 +160,75 @@ impl Vec2 {
 		self.rotated(angle * std::f32::consts::PI / 180.0)
 	}
 
+	/// Get the point rotated around the given pivot by the given angle in radians
+	pub fn rotate_around(self, pivot: Self, angle: f32) -> Self {
+		(self - pivot).rotated(angle) + pivot
+	}
+
+	/// Get the vector rotated by 90 degrees, in the same direction as [`Self::rotated`]
+	pub fn perp(self) -> Self {
+		Self {
+			x: -self.y,
+			y: self.x,
+		}
+	}
+
+	/// Get the distance between two points
+	pub fn distance(self, other: Self) -> f32 {
+		(self - other).length()
+	}
+
+	/// Get the squared distance between two points
+	pub fn distance_squared(self, other: Self) -> f32 {
+		(self - other).length_squared()
+	}
+
+	/// Get the closest point to this point on the segment from `start` to `end`
+	pub fn closest_point_on_segment(self, start: Self, end: Self) -> Self {
+		let segment = end - start;
+		let length_squared = segment.length_squared();
+		if length_squared == 0.0 {
+			return start;
+		}
+		let t = ((self - start).dot(segment) / length_squared).clamp(0.0, 1.0);
+		start + segment * t
+	}
+
+	/// Get the distance between this point and the segment from `start` to `end`
+	pub fn distance_to_segment(self, start: Self, end: Self) -> f32 {
+		self.distance(self.closest_point_on_segment(start, end))
+	}
+
+	/// Linearly interpolate between two vectors
+	pub fn lerp(self, other: Self, t: f32) -> Self {
+		self + (other - self) * t
+	}
+
+	/// Get the point at `t` on the quadratic bezier curve defined by `from`, `ctrl` and `to`
+	pub fn quadratic_bezier(from: Self, ctrl: Self, to: Self, t: f32) -> Self {
+		let inv = 1.0 - t;
+		from * (inv * inv) + ctrl * (2.0 * inv * t) + to * (t * t)
+	}
+
+	/// Get the point at `t` on the cubic bezier curve defined by `from`, `ctrl1`, `ctrl2` and `to`
+	pub fn cubic_bezier(from: Self, ctrl1: Self, ctrl2: Self, to: Self, t: f32) -> Self {
+		let inv = 1.0 - t;
+		from * (inv * inv * inv) + 
+			ctrl1 * (3.0 * inv * inv * t) + 
+			ctrl2 * (3.0 * inv * t * t) + 
+			to * (t * t * t)
+	}
+
+	/// Get the tangent at `t` on the cubic bezier curve defined by `from`, `ctrl1`, `ctrl2` and `to`
+	/// 
+	/// The result is not normalized.
+	pub fn cubic_bezier_tangent(from: Self, ctrl1: Self, ctrl2: Self, to: Self, t: f32) -> Self {
+		let inv = 1.0 - t;
+		(ctrl1 - from) * (3.0 * inv * inv) + 
+			(ctrl2 - ctrl1) * (6.0 * inv * t) + 
+			(to - ctrl2) * (3.0 * t * t)
+	}
+
 	/// Get the vector's angle in radians with respect to the x-axis
 	pub fn angle_x(self) -> f32 {
 		self.y.atan2(self.x)
@@ -174,6 +267,20 @@ impl Vec2 {
 		}
 	}
 
+	/// Clamp the vector's length to the given range, keeping its direction
+	/// 
+	/// A zero vector stays zero since it has no direction.
+	pub fn clamp_length_range(self, min_length: f32, max_length: f32) -> Self {
+		let length = self.length();
+		if length > max_length {
+			self.normalize() * max_length
+		} else if length < min_length && length > 0.0 {
+			self.normalize() * min_length
+		} else {
+			self
+		}
+	}
+
 	/// Clamp the vector's x and y components to the given range
 	pub fn clamp(self, min: f32, max: f32) -> Self {
 		Self {
@@ -523,4 +630,24 @@ impl Point for Vec2 {
 			_ => panic!("Index out of range"),
 		}
 	}
-}
\ No newline at end of file
+}
+
+mod test {
+	#[test]
+	fn test_geometry_helpers() {
+		use crate::prelude::Vec2;
+
+		fn close(a: Vec2, b: Vec2) -> bool {
+			(a - b).length() < 1e-5
+		}
+
+		assert!((Vec2::X.angle_to(Vec2::Y) - std::f32::consts::FRAC_PI_2).abs() < 1e-5);
+		assert!((Vec2::Y.angle_to(Vec2::X) + std::f32::consts::FRAC_PI_2).abs() < 1e-5);
+		assert!(close(Vec2::new(2.0, 1.0).rotate_around(Vec2::new(1.0, 1.0), std::f32::consts::PI), Vec2::new(0.0, 1.0)));
+		assert!(close(Vec2::new(3.0, 4.0).project_onto(Vec2::X), Vec2::new(3.0, 0.0)));
+		assert_eq!(Vec2::new(3.0, 4.0).project_onto(Vec2::ZERO), Vec2::ZERO);
+		assert_eq!(Vec2::new(5.0, 3.0).distance_to_segment(Vec2::ZERO, Vec2::new(10.0, 0.0)), 3.0);
+		assert_eq!(Vec2::new(-4.0, 3.0).distance_to_segment(Vec2::ZERO, Vec2::new(10.0, 0.0)), 5.0);
+		assert!(close(Vec2::cubic_bezier(Vec2::ZERO, Vec2::Y, Vec2::ONE, Vec2::X, 0.5), Vec2::new(0.5, 0.75)));
+	}
+}
@@ -0,0 +1,241 @@
+//! The extension API for third-party widget packs.
+//!
+//! A widget pack, such as a charts or a material design crate, implements [`WidgetPack`]
+//! and is installed once at startup with [`crate::Context::install_pack`].
+//! While installed, the pack registers everything its widgets need through [`PackRegistry`]:
+//!
+//! - fonts and the font roles they fill, see [`crate::render::font::FontRole`].
+//! - theme entries, named values such as colors the widgets read from the context, the app can override them afterwards.
+//! - icon sets, icons drawn as glyphs of an icon font.
+//! - handlers of custom output events, sent by the widgets with [`crate::window::input_state::InputState::send_custom_event`]
+//!   and handled with access to the window.
+//!
+//! Names of theme entries, icon sets and output events are shared by all the packs, prefix them with the pack name,
+//! e.g. `charts.axis_color`.
+//!
+//! ```no_run
+//! use nablo_ui::pack::*;
+//! use nablo_ui::prelude::*;
+//!
+//! struct ChartsPack;
+//!
+//! impl WidgetPack for ChartsPack {
+//!     fn name(&self) -> &str {
+//!         "charts"
+//!     }
+//!
+//!     fn register(&self, registry: &mut PackRegistry) -> Result<(), PackError> {
+//!         registry.set_theme_entry("charts.axis_color", Color::WHITE);
+//!         registry.set_theme_entry("charts.line_width", 2.0);
+//!         registry.add_output_handler("charts.export", |_window, payload| {
+//!             if let Some(path) = payload.downcast_ref::<String>() {
+//!                 println!("exporting chart to {}", path);
+//!             }
+//!         })
+//!     }
+//! }
+//! ```
+
+use std::{any::Any, collections::HashMap, sync::{Arc, Mutex}};
+
+use indexmap::IndexSet;
+use winit::window::Window;
+
+use crate::{math::{color::Color, vec2::Vec2}, render::font::{FontId, FontPool, FontRole}};
+
+/// A pack of widgets and the resources they need, see [`crate::pack`].
+pub trait WidgetPack {
+	/// The unique name of the pack, a pack can only be installed once.
+	fn name(&self) -> &str;
+
+	/// Register the resources of the pack, called once by [`crate::Context::install_pack`].
+	///
+	/// If an error is returned, the theme entries, icon sets and handlers registered are discarded,
+	/// but the fonts stay loaded.
+	fn register(&self, registry: &mut PackRegistry) -> Result<(), PackError>;
+}
+
+/// An error that occurs when installing a widget pack.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum PackError {
+	/// A pack with the same name is already installed.
+	#[error("pack `{0}` is already installed")]
+	AlreadyInstalled(String),
+	/// An icon set with the same name is already registered.
+	#[error("icon set `{0}` is already registered")]
+	DuplicateIconSet(String),
+	/// A handler of the output event is already registered.
+	#[error("output event `{0}` already has a handler")]
+	DuplicateHandler(String),
+	/// A custom error of the pack, e.g. invalid font data.
+	#[error("{0}")]
+	Other(String),
+}
+
+/// A value of a theme entry.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ThemeValue {
+	/// A color, e.g. of a background.
+	Color(Color),
+	/// A number, e.g. a stroke width.
+	Number(f32),
+	/// A vector, e.g. a padding.
+	Vec2(Vec2),
+}
+
+impl From<Color> for ThemeValue {
+	fn from(value: Color) -> Self {
+		Self::Color(value)
+	}
+}
+
+impl From<f32> for ThemeValue {
+	fn from(value: f32) -> Self {
+		Self::Number(value)
+	}
+}
+
+impl From<Vec2> for ThemeValue {
+	fn from(value: Vec2) -> Self {
+		Self::Vec2(value)
+	}
+}
+
+/// A set of icons drawn as glyphs of an icon font.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct IconSet {
+	/// The font containing the icons.
+	pub font: FontId,
+	/// The glyph of each icon by its name.
+	pub glyphs: HashMap<String, char>,
+}
+
+impl IconSet {
+	/// Create an empty icon set drawn with the given font.
+	pub fn new(font: FontId) -> Self {
+		Self {
+			font,
+			glyphs: HashMap::new(),
+		}
+	}
+
+	/// Add an icon drawn as the given glyph.
+	pub fn icon(mut self, name: impl Into<String>, glyph: char) -> Self {
+		self.glyphs.insert(name.into(), glyph);
+		self
+	}
+}
+
+/// A handler of a custom output event, receiving the window and the payload of the event.
+pub type OutputHandler = Box<dyn FnMut(&Window, &(dyn Any + Send + Sync))>;
+
+/// The resources registered by the installed packs, owned by [`crate::Context`].
+#[derive(Default)]
+pub(crate) struct Extensions {
+	packs: IndexSet<String>,
+	theme: HashMap<String, ThemeValue>,
+	icon_sets: HashMap<String, IconSet>,
+	handlers: HashMap<String, OutputHandler>,
+}
+
+impl Extensions {
+	pub(crate) fn install(&mut self, pack: &dyn WidgetPack, fonts: &Mutex<FontPool>) -> Result<(), PackError> {
+		let name = pack.name().to_string();
+		if self.packs.contains(&name) {
+			return Err(PackError::AlreadyInstalled(name));
+		}
+		let mut registry = PackRegistry {
+			installed: self,
+			fonts,
+			staged: Extensions::default(),
+		};
+		pack.register(&mut registry)?;
+		let staged = registry.staged;
+		self.theme.extend(staged.theme);
+		self.icon_sets.extend(staged.icon_sets);
+		self.handlers.extend(staged.handlers);
+		self.packs.insert(name);
+		Ok(())
+	}
+
+	pub(crate) fn is_installed(&self, name: &str) -> bool {
+		self.packs.contains(name)
+	}
+
+	pub(crate) fn packs(&self) -> impl Iterator<Item = &str> {
+		self.packs.iter().map(String::as_str)
+	}
+
+	pub(crate) fn theme_entry(&self, key: &str) -> Option<ThemeValue> {
+		self.theme.get(key).copied()
+	}
+
+	pub(crate) fn set_theme_entry(&mut self, key: String, value: ThemeValue) {
+		self.theme.insert(key, value);
+	}
+
+	pub(crate) fn icon(&self, set: &str, name: &str) -> Option<(FontId, char)> {
+		let set = self.icon_sets.get(set)?;
+		set.glyphs.get(name).map(|glyph| (set.font, *glyph))
+	}
+
+	/// Run the handler of a custom output event, returns `false` if there is no handler.
+	pub(crate) fn handle_output(&mut self, window: &Window, name: &str, payload: &(dyn Any + Send + Sync)) -> bool {
+		match self.handlers.get_mut(name) {
+			Some(handler) => {
+				handler(window, payload);
+				true
+			},
+			None => false,
+		}
+	}
+}
+
+/// Registers the resources of a pack, see [`WidgetPack::register`].
+pub struct PackRegistry<'a> {
+	installed: &'a Extensions,
+	fonts: &'a Mutex<FontPool>,
+	staged: Extensions,
+}
+
+impl PackRegistry<'_> {
+	/// Load a font, returns its id.
+	pub fn add_font(&mut self, font_data: Vec<u8>, index: u32) -> FontId {
+		self.fonts.lock().unwrap().insert_font(font_data, index)
+	}
+
+	/// Set the font used for the given role, replacing the font set by the app or by other packs.
+	pub fn set_font_role(&mut self, role: FontRole, font_id: FontId) {
+		self.fonts.lock().unwrap().set_role(role, Some(font_id));
+	}
+
+	/// Set a theme entry, replacing the value set by the packs installed before.
+	pub fn set_theme_entry(&mut self, key: impl Into<String>, value: impl Into<ThemeValue>) {
+		self.staged.theme.insert(key.into(), value.into());
+	}
+
+	/// Register an icon set, the names of the icon sets must be unique.
+	pub fn add_icon_set(&mut self, name: impl Into<String>, icon_set: IconSet) -> Result<(), PackError> {
+		let name = name.into();
+		if self.installed.icon_sets.contains_key(&name) || self.staged.icon_sets.contains_key(&name) {
+			return Err(PackError::DuplicateIconSet(name));
+		}
+		self.staged.icon_sets.insert(name, icon_set);
+		Ok(())
+	}
+
+	/// Register the handler of the custom output event with the given name, an event can only have one handler.
+	///
+	/// The handler runs on the event loop when the event is sent by [`crate::window::input_state::InputState::send_custom_event`].
+	pub fn add_output_handler(&mut self, name: impl Into<String>, handler: impl FnMut(&Window, &(dyn Any + Send + Sync)) + 'static) -> Result<(), PackError> {
+		let name = name.into();
+		if self.installed.handlers.contains_key(&name) || self.staged.handlers.contains_key(&name) {
+			return Err(PackError::DuplicateHandler(name));
+		}
+		self.staged.handlers.insert(name, Box::new(handler));
+		Ok(())
+	}
+}
+
+/// The payload of a custom output event.
+pub type OutputPayload = Arc<dyn Any + Send + Sync>;
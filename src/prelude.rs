@@ -5,6 +5,8 @@ pub use crate::widgets::prelude::*;
 pub use crate::window::prelude::*;
 pub use crate::render::prelude::*;
 pub use crate::layout::*;
+pub use crate::layout::immediate::{ImmediateUi, Ui, Response};
+pub use crate::pack::{WidgetPack, PackRegistry, PackError, ThemeValue, IconSet};
 pub use crate::Context;
 pub use crate::App;
 pub use crate::layout_gen;
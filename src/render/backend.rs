@@ -15,11 +15,14 @@ use wgpu::{util::DeviceExt, InstanceDescriptor};
 use winit::window::Window;
 use pollster::FutureExt as _;
 
-use crate::math::{rect::Rect, vec2::Vec2};
+use crate::math::{color::Color, rect::Rect, vec2::Vec2};
 
 use crate::prelude::BACKGROUND_COLOR;
+use crate::window::event::OutputEvent;
 
-use super::{commands::DrawCommandGpu, font::FontId, font_render::FontRender, texture::{create_new_texture_array, CreateTextureError, TextureId, TexturePool, DEFAULT_TEXTURE_LAYER, MAX_TEXTURE_SIZE}};
+#[cfg(feature = "render-graph")]
+use super::graph::{GraphNode, PassContext, RenderGraph};
+use super::{commands::DrawCommandGpu, damage::Damage, font::FontId, viewport::Viewports, painter::MAX_SUPERSAMPLE_REGIONS, font_render::FontRender, texture::{create_new_texture_array, CreateTextureError, TextureId, TexturePool, DEFAULT_TEXTURE_LAYER, MAX_TEXTURE_SIZE}};
 
 // const EMPTY_STACK_DATA: [u8; 16 * 64] = [0; 16 * 64];
 const COMMAND_BUFFER_MUL_THERSHOLD: u64 = 2048;
@@ -48,10 +51,28 @@ pub(crate) struct Uniform {
 	pub scale_factor: f32,
 	pub stack_len: u32,
 	pub command_len: u32,
+	pub supersample_len: u32,
+	pub _padding: [u32; 3],
+	/// left, top, right, bottom of each region in logical pixels.
+	pub supersample_regions: [[f32; 4]; MAX_SUPERSAMPLE_REGIONS],
+	/// factors of the regions packed by four.
+	pub supersample_factors: [[f32; 4]; MAX_SUPERSAMPLE_REGIONS / 4],
+}
+
+impl Uniform {
+	pub fn set_supersample_regions(&mut self, regions: &[(Rect, f32)]) {
+		let len = regions.len().min(MAX_SUPERSAMPLE_REGIONS);
+		self.supersample_len = len as u32;
+		for (i, (area, factor)) in regions.iter().take(len).enumerate() {
+			self.supersample_regions[i] = [area.x, area.y, area.x + area.w, area.y + area.h];
+			self.supersample_factors[i / 4][i % 4] = *factor;
+		}
+	}
 }
 
 pub(crate) struct WgpuState<'a> {
-	pub surface: wgpu::Surface<'a>,
+	/// the window surface, `None` if the ui is embedded and drawn into the views given by the host.
+	pub surface: Option<wgpu::Surface<'a>>,
 	pub device: wgpu::Device,
 	pub queue: wgpu::Queue,
 
@@ -76,6 +97,7 @@ pub(crate) struct WgpuState<'a> {
 	
 	pub is_first_frame: bool,
 	pub quality_factor: f32,
+	pub clear_color: Color,
 }
 
 pub(crate) fn create_bind_group_with_buffer(
@@ -155,6 +177,26 @@ pub(crate) fn crate_wgpu_state<'a>(window: Arc<Window>, size: Vec2) -> WgpuState
 
 	surface.configure(&device, &config);
 
+	create_state(Some(surface), device, queue, config, size)
+}
+
+/// Create the gpu state drawing into the views given by the host application, see [`crate::window::embed`].
+pub(crate) fn crate_embedded_state(device: wgpu::Device, queue: wgpu::Queue, format: wgpu::TextureFormat, size: Vec2) -> WgpuState<'static> {
+	let config = wgpu::SurfaceConfiguration {
+		usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+		format,
+		width: size.x as u32,
+		height: size.y as u32,
+		present_mode: wgpu::PresentMode::Fifo,
+		alpha_mode: wgpu::CompositeAlphaMode::Auto,
+		view_formats: vec![],
+		desired_maximum_frame_latency: 2,
+	};
+
+	create_state(None, device, queue, config, size)
+}
+
+fn create_state<'a>(surface: Option<wgpu::Surface<'a>>, device: wgpu::Device, queue: wgpu::Queue, config: wgpu::SurfaceConfiguration, size: Vec2) -> WgpuState<'a> {
 	let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
 		label: None,
 		source: wgpu::ShaderSource::Wgsl(include_str!("./shader.wgsl").into()),
@@ -169,6 +211,10 @@ pub(crate) fn crate_wgpu_state<'a>(window: Arc<Window>, size: Vec2) -> WgpuState
 			scale_factor: 1.0,
 			stack_len: 0,
 			command_len: 0,
+			supersample_len: 0,
+			_padding: [0; 3],
+			supersample_regions: [[0.0; 4]; MAX_SUPERSAMPLE_REGIONS],
+			supersample_factors: [[0.0; 4]; MAX_SUPERSAMPLE_REGIONS / 4],
 		}),
 		usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
 	});
@@ -390,6 +436,7 @@ pub(crate) fn crate_wgpu_state<'a>(window: Arc<Window>, size: Vec2) -> WgpuState
 		scale_pipeline,
 		is_first_frame: true,
 		quality_factor: 1.0,
+		clear_color: BACKGROUND_COLOR,
 	}
 }
 
@@ -445,14 +492,34 @@ fn create_render_pipeline(
 }
 
 impl WgpuState<'_> {
-	pub fn insert_texture(&mut self, rgba: &[u8], width: u32, height: u32) -> Result<TextureId, CreateTextureError> {
-		let (id, changed) = self.texture_pool.insert_texture(&self.device, &self.queue, rgba, width, height)?;
+	/// Upload a texture with the id given out by the context, see [`crate::Context::register_texture`].
+	pub fn insert_texture(&mut self, texture_id: TextureId, rgba: &[u8], width: u32, height: u32) -> Result<(), CreateTextureError> {
+		let (_, changed) = self.texture_pool.insert_texture_with_id(&self.device, &self.queue, texture_id, rgba, width, height)?;
+
+		if changed {
+			self.update_render_pipeline();
+		}
+
+		Ok(())
+	}
+
+	/// Restore the textures with their original ids, used after the gpu resources are recreated.
+	pub fn restore_textures<'a>(
+		&mut self, 
+		textures: impl IntoIterator<Item = (TextureId, &'a [u8], u32, u32)>, 
+		available_texture_ids: IndexSet<TextureId>
+	) -> Result<(), CreateTextureError> {
+		let mut changed = false;
+		for (id, rgba, width, height) in textures {
+			changed |= self.texture_pool.insert_texture_with_id(&self.device, &self.queue, id, rgba, width, height)?.1;
+		}
+		self.texture_pool.available_texture_ids = available_texture_ids;
 
 		if changed {
 			self.update_render_pipeline();
 		}
 
-		Ok(id)
+		Ok(())
 	}
 
 	pub fn remove_texture(&mut self, texture_id: TextureId) {
@@ -467,8 +534,16 @@ impl WgpuState<'_> {
 		self.texture_pool.clear()
 	}
 
+	/// Set the color the window is cleared with, the whole window is redrawn if it changes.
+	pub fn set_clear_color(&mut self, color: Color) {
+		if self.clear_color != color {
+			self.clear_color = color;
+			self.is_first_frame = true;
+		}
+	}
+
 	pub fn resized(&mut self, new_size: Vec2, quality_factor: f32) {
-		if self.size != new_size {
+		if self.size != new_size || self.quality_factor != quality_factor {
 			self.size = new_size;
 			self.size_changed = true;
 			self.quality_factor = quality_factor;
@@ -522,7 +597,9 @@ impl WgpuState<'_> {
 		if self.size_changed {
 			self.surface_config.width = self.size.x as u32;
 			self.surface_config.height = self.size.y as u32;
-			self.surface.configure(&self.device, &self.surface_config);
+			if let Some(surface) = &self.surface {
+				surface.configure(&self.device, &self.surface_config);
+			}
 			self.recreate_render_texture();
 			self.size_changed = false;
 		}
@@ -627,16 +704,56 @@ impl WgpuState<'_> {
 	}
 
 	pub fn draw(&mut self, 
-		mut render_area: Rect,
+		damage: Damage,
 		commands: Vec<DrawCommandGpu>,
 		// expected_stack_size: u64,
-		mut uniform: Uniform,
+		uniform: Uniform,
+		#[cfg(feature = "render-graph")]
+		graph: &mut RenderGraph,
 	) {
+		let Some(frame) = self.prepare(damage, commands, uniform) else {
+			return;
+		};
+			
+		let Some(surface) = &self.surface else {
+			log::error!("No surface to draw onto, use `EmbeddedUi::render` to draw an embedded ui");
+			return;
+		};
+		let output = surface.get_current_texture().expect("Failed to acquire next texture view");
+		let output_view = output.texture.create_view(&wgpu::TextureViewDescriptor {
+			label: Some("Output View"),
+			..Default::default()
+		});
+
+		let mut encoder = self.device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+			label: Some("Main Render Encoder"),
+		});
+		self.encode(
+			&mut encoder, 
+			&output_view, 
+			Some(frame), 
+			&uniform, 
+			#[cfg(feature = "render-graph")]
+			graph,
+		);
+		self.queue.submit(std::iter::once(encoder.finish()));
+		
+		output.present();
+	} 
+
+	/// Upload the commands and the uniform of a frame.
+	/// 
+	/// Returns the rects of the render texture to redraw and whether to clear the render texture, `None` if there is nothing to draw.
+	pub fn prepare(&mut self, 
+		damage: Damage,
+		commands: Vec<DrawCommandGpu>,
+		mut uniform: Uniform,
+	) -> Option<(Damage, bool)> {
 		uniform.scale_factor *= self.quality_factor;
 		// use rayon::prelude::*;
 
 		if !self.resize() {
-			return;
+			return None;
 		}
 
 		while (commands.len() * std::mem::size_of::<DrawCommandGpu>()) as u64 > self.commands.size {
@@ -656,31 +773,87 @@ impl WgpuState<'_> {
 		self.queue.write_buffer(&self.uniform.uniform, 0, bytemuck::bytes_of(&uniform));
 		self.queue.submit([]);
 			
-		render_area = Rect::from_lt_size(render_area.lt() * uniform.scale_factor, render_area.size() * uniform.scale_factor);
-		render_area &= Rect::new(0.0, 0.0, self.size.x, self.size.y);
-		if render_area.is_empty() {
-			return;
+		let window = Rect::new(0.0, 0.0, self.size.x, self.size.y);
+		let damage = damage.map(|render_area| {
+			Rect::from_lt_size(render_area.lt() * uniform.scale_factor, render_area.size() * uniform.scale_factor) & window
+		});
+		if damage.is_empty() {
+			return None;
 		}
-			
-		let output = self.surface.get_current_texture().expect("Failed to acquire next texture view");
 
-		let mut encoder = self.device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
-			label: Some("Main Render Encoder"),
+		let clear = self.is_first_frame;
+		let damage = if self.is_first_frame {
+			self.is_first_frame = false;
+			Damage::from(window)
+		}else {
+			damage
+		};
+
+		let quality_factor = self.quality_factor;
+		let damage = damage.map(|mut render_area| {
+			if quality_factor < 1.0 {
+				render_area &= Rect::new(0.0, 0.0, window.w * quality_factor, window.h * quality_factor);
+			}else if quality_factor > 1.0 {
+				render_area.x *= quality_factor;
+				render_area.y *= quality_factor;
+				render_area.w *= quality_factor;
+				render_area.h *= quality_factor;
+			}
+			// round outwards to whole pixels, so the scissors of the rects neither overlap nor leave a gap.
+			let lt = render_area.lt().floor();
+			Rect::from_lt_size(lt, render_area.rb().ceil() - lt)
 		});
 
+		self.queue.write_buffer(&self.render_uniform, 0, bytemuck::bytes_of(&[
+			self.size.x,
+			self.size.y,
+		]));
+
+		Some((damage, clear))
+	}
+
+	/// Record the passes of a frame into the encoder, drawing onto `output_view`.
+	/// 
+	/// `frame` is returned by [`Self::prepare`], the ui pass is skipped if it is `None` and the render texture is drawn as it is.
+	pub fn encode(&self, 
+		encoder: &mut wgpu::CommandEncoder, 
+		output_view: &wgpu::TextureView, 
+		frame: Option<(Damage, bool)>, 
+		uniform: &Uniform,
+		#[cfg(feature = "render-graph")]
+		graph: &mut RenderGraph,
+	) {
+		#[cfg(not(feature = "render-graph"))]
+		{
+			let _ = uniform;
+			if let Some((damage, clear)) = frame {
+				self.ui_pass(encoder, &damage, clear);
+			}
+			self.scale_pass(encoder, output_view);
+		}
+		#[cfg(feature = "render-graph")]
+		self.run_graph(encoder, graph, output_view, frame, uniform);
+	}
+
+	fn clear_color(&self) -> wgpu::Color {
+		wgpu::Color { 
+			r: self.clear_color.r.powf(2.2) as f64, 
+			g: self.clear_color.g.powf(2.2) as f64, 
+			b: self.clear_color.b.powf(2.2) as f64, 
+			a: self.clear_color.a as f64
+		}
+	}
+
+	/// Draw the ui into the damaged rects of the render texture, clearing it first if `clear` is set.
+	fn ui_pass(&self, encoder: &mut wgpu::CommandEncoder, damage: &Damage, clear: bool) {
 		let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
 			label: Some("Main Render Pass"),
 			color_attachments: &[Some(wgpu::RenderPassColorAttachment {
 				view: &self.render_view,
 				resolve_target: None,
 				ops: wgpu::Operations {
-					load: if self.is_first_frame {
-						wgpu::LoadOp::Clear(wgpu::Color { 
-							r: BACKGROUND_COLOR.r.powf(2.2) as f64, 
-							g: BACKGROUND_COLOR.g.powf(2.2) as f64, 
-							b: BACKGROUND_COLOR.b.powf(2.2) as f64, 
-							a: BACKGROUND_COLOR.a as f64
-						})
+					load: if clear {
+						wgpu::LoadOp::Clear(self.clear_color())
 					}else {
 						wgpu::LoadOp::Load
 					},
@@ -691,56 +864,29 @@ impl WgpuState<'_> {
 			..Default::default()
 		});
 
-		render_area = if self.is_first_frame {
-			self.is_first_frame = false;
-			Rect::new(0.0, 0.0, self.size.x, self.size.y)
-		}else {
-			render_area
-		};
-
-		if self.quality_factor < 1.0 {
-			render_area &= Rect::new(0.0, 0.0, self.size.x * self.quality_factor, self.size.y * self.quality_factor);
-		}else if self.quality_factor > 1.0 {
-			render_area.x *= self.quality_factor;
-			render_area.y *= self.quality_factor;
-			render_area.w *= self.quality_factor;
-			render_area.h *= self.quality_factor;
-		}
-
-		render_pass.set_scissor_rect(
-			render_area.x as u32, 
-			render_area.y as u32, 
-			render_area.w as u32, 
-			render_area.h as u32
-		);
 		render_pass.set_pipeline(&self.render_pipeline);
 		render_pass.set_bind_group(0, &self.uniform.bind_group, &[]);
 		render_pass.set_bind_group(1, &self.commands.bind_group, &[]);
 		render_pass.set_bind_group(2, &self.texture_pool.texture_array[0].bind_group, &[]);
 		render_pass.set_bind_group(3, &self.font_render.bind_group, &[]);
 		// render_pass.set_viewport(0.0, 0.0, self.size.x, self.size.y, 0.0, 1.0);
-		render_pass.draw(0..6, 0..1);
-
-		drop(render_pass);
-
-		self.queue.submit(std::iter::once(encoder.finish()));
-		
-		self.queue.write_buffer(&self.render_uniform, 0, bytemuck::bytes_of(&[
-			self.size.x,
-			self.size.y,
-		]));
-
-		let mut encoder = self.device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
-			label: Some("Copy Encoder"),
-		});
+		for render_area in damage.rects() {
+			render_pass.set_scissor_rect(
+				render_area.x as u32, 
+				render_area.y as u32, 
+				render_area.w as u32, 
+				render_area.h as u32
+			);
+			render_pass.draw(0..6, 0..1);
+		}
+	}
 
+	/// Draw the render texture onto the window surface.
+	fn scale_pass(&self, encoder: &mut wgpu::CommandEncoder, output_view: &wgpu::TextureView) {
 		let mut copy_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
 			label: Some("Copy Pass"),
 			color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-				view: &output.texture.create_view(&wgpu::TextureViewDescriptor {
-					label: Some("Output View"),
-					..Default::default()
-				}),
+				view: output_view,
 				resolve_target: None,
 				ops: wgpu::Operations {
 					load: wgpu::LoadOp::Load,
@@ -754,13 +900,179 @@ impl WgpuState<'_> {
 		copy_pass.set_pipeline(&self.scale_pipeline);
 		copy_pass.set_bind_group(0, &self.render_bind_group, &[]);
 		copy_pass.draw(0..6, 0..1);
+	}
+
+	/// Run the built-in passes and the custom passes in the order of the graph.
+	/// 
+	/// If `frame` is `None`, the render texture is kept as it is, only the scale pass and the passes after it run.
+	#[cfg(feature = "render-graph")]
+	fn run_graph(&self, encoder: &mut wgpu::CommandEncoder, graph: &mut RenderGraph, output_view: &wgpu::TextureView, frame: Option<(Damage, bool)>, uniform: &Uniform) {
+		let nodes = graph.nodes();
+		let drawn = frame.is_some();
+		let (damage, clear) = frame.unwrap_or_default();
+		let render_area = damage.bounds().unwrap_or(Rect::ZERO);
+		let window = [Rect::new(0.0, 0.0, self.size.x, self.size.y)];
+
+		// the passes before the ui pass draw onto the cleared render texture.
+		let clear_in_ui_pass = matches!(nodes.first(), Some(GraphNode::Ui));
+		if clear && !clear_in_ui_pass {
+			encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+				label: Some("Clear Pass"),
+				color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+					view: &self.render_view,
+					resolve_target: None,
+					ops: wgpu::Operations {
+						load: wgpu::LoadOp::Clear(self.clear_color()),
+						store: wgpu::StoreOp::Store,
+					},
+				})],
+				depth_stencil_attachment: None,
+				..Default::default()
+			});
+		}
+
+		let mut after_scale = false;
+		for node in nodes {
+			match node {
+				GraphNode::Ui => if drawn {
+					self.ui_pass(encoder, &damage, clear && clear_in_ui_pass);
+				},
+				GraphNode::Scale => {
+					self.scale_pass(encoder, output_view);
+					after_scale = true;
+				},
+				GraphNode::Pass(_) if !drawn && !after_scale => {},
+				GraphNode::Pass(name) => {
+					let mut ctx = PassContext {
+						device: &self.device,
+						queue: &self.queue,
+						encoder,
+						render_texture: &self.render_texture,
+						render_view: &self.render_view,
+						target: if after_scale { output_view } else { &self.render_view },
+						format: self.surface_config.format,
+						render_area: if after_scale {
+							window[0]
+						}else {
+							render_area
+						},
+						damage: if after_scale {
+							&window
+						}else {
+							damage.rects()
+						},
+						window_size: Vec2::from(uniform.window_size),
+						scale_factor: uniform.scale_factor * self.quality_factor,
+						time: uniform.time,
+						after_scale,
+					};
+					graph.run(&name, &mut ctx);
+				},
+			}
+		}
+	}
 
-		drop(copy_pass);
+	/// Read back the last rendered frame in rgba8, returns the width, height and the pixels.
+	/// 
+	/// Returns `None` if the surface format is not a 8 bit rgba or bgra format or the readback failed.
+	#[cfg(feature = "capture")]
+	pub fn read_frame(&self) -> Option<(u32, u32, Vec<u8>)> {
+		let is_bgra = match self.surface_config.format {
+			wgpu::TextureFormat::Bgra8Unorm | wgpu::TextureFormat::Bgra8UnormSrgb => true,
+			wgpu::TextureFormat::Rgba8Unorm | wgpu::TextureFormat::Rgba8UnormSrgb => false,
+			_ => return None,
+		};
+		let width = self.render_texture.width();
+		let height = self.render_texture.height();
+		let unpadded_bytes_per_row = width * 4;
+		let padded_bytes_per_row = unpadded_bytes_per_row.div_ceil(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT) * wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+
+		let buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
+			label: Some("Capture Buffer"),
+			size: (padded_bytes_per_row * height) as u64,
+			usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+			mapped_at_creation: false,
+		});
 
+		let mut encoder = self.device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+			label: Some("Capture Encoder"),
+		});
+		encoder.copy_texture_to_buffer(
+			wgpu::TexelCopyTextureInfo {
+				texture: &self.render_texture,
+				mip_level: 0,
+				origin: wgpu::Origin3d::ZERO,
+				aspect: wgpu::TextureAspect::All,
+			},
+			wgpu::TexelCopyBufferInfo {
+				buffer: &buffer,
+				layout: wgpu::TexelCopyBufferLayout {
+					offset: 0,
+					bytes_per_row: Some(padded_bytes_per_row),
+					rows_per_image: Some(height),
+				}
+			},
+			self.render_texture.size(),
+		);
 		self.queue.submit(std::iter::once(encoder.finish()));
-		
-		output.present();
-	} 
+
+		let slice = buffer.slice(..);
+		let (sender, receiver) = std::sync::mpsc::channel();
+		slice.map_async(wgpu::MapMode::Read, move |result| {
+			let _ = sender.send(result);
+		});
+		let _ = self.device.poll(wgpu::Maintain::Wait);
+		receiver.recv().ok()?.ok()?;
+
+		let data = slice.get_mapped_range();
+		let mut rgba = Vec::with_capacity((unpadded_bytes_per_row * height) as usize);
+		for row in data.chunks(padded_bytes_per_row as usize) {
+			rgba.extend_from_slice(&row[..unpadded_bytes_per_row as usize]);
+		}
+		drop(data);
+		buffer.unmap();
+
+		if is_bgra {
+			for pixel in rgba.chunks_exact_mut(4) {
+				pixel.swap(0, 2);
+			}
+		}
+
+		Some((width, height, rgba))
+	}
+
+	/// Apply the output event if it changes the gpu resources, such as textures and glyphs, otherwise return it.
+	pub fn handle_output_event(&mut self, event: OutputEvent) -> Option<OutputEvent> {
+		match event {
+			OutputEvent::RegisterTexture(texture_id, size, data) => {
+				log::debug!("Uploading texture {} of size {}x{}", texture_id, size.x, size.y);
+				self.insert_texture(texture_id, &data, size.x as u32, size.y as u32).expect("Failed to create texture");
+			},
+			OutputEvent::UpdateTexture(texture_id, size, data) => {
+				log::debug!("Updating texture {} with size {}x{}", texture_id, size.x, size.y);
+				self.update_texture(texture_id, &data,size.x as u32, size.y as u32).expect("Failed to update texture");
+			},
+			OutputEvent::RemoveTexture(texture_id) => {
+				self.remove_texture(texture_id);
+			},
+			OutputEvent::ClearTexture => {
+				self.clear_texture();
+			},
+			OutputEvent::AddChar(data, chr, font_id) => {
+				self.add_char(font_id, chr, data);
+			},
+			OutputEvent::RemoveFont(font_id) => {
+				self.remove_font(font_id);
+			},
+			event => return Some(event),
+		}
+		None
+	}
+
+	/// Render the viewports drawn in the frame into their textures, call before drawing the frame.
+	pub fn render_viewports(&self, viewports: &mut Viewports, uniform: &Uniform) {
+		viewports.render(&self.device, &self.queue, &self.texture_pool, uniform.scale_factor, uniform.time);
+	}
 
 	pub fn cleanup(&mut self) {
 		self.texture_pool.cleanup();
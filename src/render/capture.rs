@@ -0,0 +1,175 @@
+//! Recording the ui into animated images, useful for documentation and demos.
+//!
+//! Only available with the `capture` feature.
+
+use std::{fs::File, io::BufWriter, path::PathBuf};
+
+use time::Duration;
+
+/// The format of the recorded animation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CaptureFormat {
+	/// Animated GIF, widely supported but limited to 256 colors per frame.
+	#[default] Gif,
+	/// Animated PNG, lossless and supports full colors.
+	Apng,
+}
+
+/// An error that occurs when writing the recorded animation.
+#[derive(Debug, thiserror::Error)]
+pub enum CaptureError {
+	/// Failed to create or write the file.
+	#[error("failed to write the capture: {0}")]
+	Io(#[from] std::io::Error),
+	/// Failed to encode the GIF.
+	#[error("failed to encode the gif: {0}")]
+	Gif(#[from] image::ImageError),
+	/// Failed to encode the APNG.
+	#[error("failed to encode the apng: {0}")]
+	Apng(#[from] png::EncodingError),
+	/// No frame was recorded.
+	#[error("no frame was recorded")]
+	Empty,
+}
+
+/// A recorded frame in rgba8.
+struct CapturedFrame {
+	width: u32,
+	height: u32,
+	rgba: Vec<u8>,
+	delay: Duration,
+}
+
+/// Records the frames drawn in the next few seconds and writes them into an animated image.
+///
+/// Set [`crate::Context::capture`] to start recording.
+/// While recording, the whole window will be redrawn at [`Self::frame_rate`],
+/// the file is encoded on another thread after the recording finished and [`crate::Context::capture`] is cleared.
+pub struct DemoCapture {
+	/// The file to write the animation to.
+	pub path: PathBuf,
+	/// The format of the animation.
+	pub format: CaptureFormat,
+	/// How long to record.
+	pub duration: Duration,
+	/// How many frames to record per second.
+	pub frame_rate: f32,
+	frames: Vec<CapturedFrame>,
+	elapsed: Duration,
+	last_frame_at: Option<Duration>,
+}
+
+impl DemoCapture {
+	/// Creates a new capture recording for the given seconds into a GIF file at 30 frames per second.
+	pub fn new(path: impl Into<PathBuf>, seconds: f32) -> Self {
+		Self {
+			path: path.into(),
+			format: CaptureFormat::default(),
+			duration: Duration::seconds_f32(seconds.max(0.0)),
+			frame_rate: 30.0,
+			frames: Vec::new(),
+			elapsed: Duration::ZERO,
+			last_frame_at: None,
+		}
+	}
+
+	/// Sets the format of the animation.
+	pub fn format(self, format: CaptureFormat) -> Self {
+		Self {
+			format,
+			..self
+		}
+	}
+
+	/// Sets how many frames to record per second.
+	pub fn frame_rate(self, frame_rate: f32) -> Self {
+		Self {
+			frame_rate: frame_rate.clamp(1.0, 100.0),
+			..self
+		}
+	}
+
+	/// Get how many frames are recorded.
+	pub fn frame_count(&self) -> usize {
+		self.frames.len()
+	}
+
+	/// Get the progress of the recording, in the range [0, 1].
+	pub fn progress(&self) -> f32 {
+		if self.duration.is_zero() {
+			1.0
+		}else {
+			(self.elapsed / self.duration) as f32
+		}.min(1.0)
+	}
+
+	/// Returns true if the recording is finished.
+	pub fn is_finished(&self) -> bool {
+		self.elapsed >= self.duration
+	}
+
+	/// Returns true if a frame should be recorded at the given time since the program started.
+	pub(crate) fn wants_frame(&self, now: Duration) -> bool {
+		match self.last_frame_at {
+			Some(last) => now - last >= Duration::seconds_f32(1.0 / self.frame_rate),
+			None => true,
+		}
+	}
+
+	/// Records a frame drawn at the given time since the program started.
+	pub(crate) fn push_frame(&mut self, now: Duration, width: u32, height: u32, rgba: Vec<u8>) {
+		if let Some(last) = self.last_frame_at {
+			let delay = now - last;
+			self.elapsed += delay;
+			if let Some(frame) = self.frames.last_mut() {
+				frame.delay = delay;
+			}
+		}
+		self.last_frame_at = Some(now);
+		self.frames.push(CapturedFrame {
+			width,
+			height,
+			rgba,
+			delay: Duration::seconds_f32(1.0 / self.frame_rate),
+		});
+	}
+
+	/// Writes the recorded frames into the file.
+	///
+	/// Frames with a size different from the first one, which may be caused by resizing, are skipped.
+	pub fn save(self) -> Result<PathBuf, CaptureError> {
+		let first = self.frames.first().ok_or(CaptureError::Empty)?;
+		let (width, height) = (first.width, first.height);
+		let frames = self.frames.iter().filter(|frame| frame.width == width && frame.height == height).collect::<Vec<_>>();
+		let writer = BufWriter::new(File::create(&self.path)?);
+
+		match self.format {
+			CaptureFormat::Gif => {
+				let mut encoder = image::codecs::gif::GifEncoder::new(writer);
+				encoder.set_repeat(image::codecs::gif::Repeat::Infinite)?;
+				for frame in frames {
+					let Some(buffer) = image::RgbaImage::from_raw(width, height, frame.rgba.clone()) else {
+						continue;
+					};
+					let delay = image::Delay::from_saturating_duration(frame.delay.unsigned_abs());
+					encoder.encode_frame(image::Frame::from_parts(buffer, 0, 0, delay))?;
+				}
+			},
+			CaptureFormat::Apng => {
+				let mut encoder = png::Encoder::new(writer, width, height);
+				encoder.set_color(png::ColorType::Rgba);
+				encoder.set_depth(png::BitDepth::Eight);
+				encoder.set_animated(frames.len() as u32, 0)?;
+				let mut writer = encoder.write_header()?;
+				for frame in frames {
+					let delay = frame.delay.whole_milliseconds().clamp(1, u16::MAX as i128) as u16;
+					writer.set_frame_delay(delay, 1000)?;
+					writer.write_image_data(&frame.rgba)?;
+				}
+				writer.finish()?;
+			},
+		}
+
+		Ok(self.path)
+	}
+}
@@ -0,0 +1,106 @@
+//! Reusing the gpu commands of the widgets not changed since the last frame.
+//!
+//! The layout draws every widget into the painter each frame, and the shapes are compiled into [`DrawCommandGpu`]s.
+//! A widget neither dirty nor moved is skipped instead, and the commands compiled for it in an earlier frame are reused,
+//! see [`crate::render::painter::Painter::reuse_segment`].
+
+use std::{collections::{HashMap, HashSet}, sync::Arc};
+
+use crate::{layout::LayoutId, math::{rect::Rect, vec2::Vec2}};
+
+use super::{commands::{DrawCommandGpu, Interaction}, font_render::GlyphMap, painter::ShapeToDraw};
+
+/// Everything the shapes of a widget depend on besides the widget itself.
+///
+/// The cached commands of a widget are only reused if the key is the same.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct DrawKey {
+	pub area: Rect,
+	pub pos: Vec2,
+	pub opacity: f32,
+	pub interaction: Interaction,
+	pub focus_ring: bool,
+}
+
+/// A shape compiled into gpu commands, kept with the rects to cull it by.
+pub(crate) struct CompiledShape {
+	clip_rect: Rect,
+	bounds: Rect,
+	commands: Vec<DrawCommandGpu>,
+	stack_len: u32,
+}
+
+impl CompiledShape {
+	/// Compile the shape, `None` if it is never visible.
+	pub(crate) fn new(shape: ShapeToDraw, glyphs: &GlyphMap) -> Option<Self> {
+		if shape.shape.0.is_empty() || shape.fill_mode.is_invisible() {
+			return None;
+		}
+		let clip_rect = shape.clip_rect;
+		let bounds = shape.shape.bounded_rect();
+		let (commands, stack_len) = shape.parse(glyphs);
+		Some(Self { clip_rect, bounds, commands, stack_len })
+	}
+
+	/// Check if the shape is visible in the rect, same as the check before compiling a shape.
+	pub(crate) fn is_visible_in_rect(&self, rect: Rect) -> bool {
+		!(self.clip_rect & rect).is_empty() && !(self.bounds & rect).is_empty()
+	}
+
+	pub(crate) fn commands(&self) -> &[DrawCommandGpu] {
+		&self.commands
+	}
+
+	pub(crate) fn stack_len(&self) -> u32 {
+		self.stack_len
+	}
+}
+
+/// The compiled shapes of each widget drawn in the last frames, owned by the layout.
+#[derive(Default)]
+pub(crate) struct CommandCache {
+	entries: HashMap<LayoutId, (DrawKey, Arc<Vec<CompiledShape>>)>,
+	/// the widgets drawn or reused in the current frame, the others are dropped after it.
+	used: HashSet<LayoutId>,
+}
+
+impl CommandCache {
+	/// Check if the commands of the widget can be reused with the key.
+	pub(crate) fn is_valid(&self, id: LayoutId, key: &DrawKey) -> bool {
+		self.entries.get(&id).is_some_and(|(cached, _)| cached == key)
+	}
+
+	/// Start compiling a frame.
+	pub(crate) fn begin_frame(&mut self) {
+		self.used.clear();
+	}
+
+	/// Get the compiled shapes of the widget, `None` if it was dropped since.
+	pub(crate) fn reuse(&mut self, id: LayoutId) -> Option<Arc<Vec<CompiledShape>>> {
+		self.used.insert(id);
+		self.entries.get(&id).map(|(_, shapes)| shapes.clone())
+	}
+
+	pub(crate) fn insert(&mut self, id: LayoutId, key: DrawKey, shapes: Arc<Vec<CompiledShape>>) {
+		self.used.insert(id);
+		self.entries.insert(id, (key, shapes));
+	}
+
+	/// Drop the widgets not drawn in the frame.
+	pub(crate) fn end_frame(&mut self) {
+		let used = &self.used;
+		self.entries.retain(|id, _| used.contains(id));
+	}
+
+	/// Drop the commands of the widget, so it is drawn again.
+	pub(crate) fn remove(&mut self, id: LayoutId) {
+		self.entries.remove(&id);
+	}
+
+	/// Drop everything, e.g. when the glyph atlas changes, as the text commands refer into it.
+	/// 
+	/// A glyph missing from the atlas is skipped when compiled, so the text is only complete once the atlas grew.
+	pub(crate) fn clear(&mut self) {
+		self.entries.clear();
+	}
+}
@@ -9,7 +9,7 @@ use std::hash::Hash;
 /// 
 /// Due to the memory alignment strategy of the wgpu, the struct actually contains a field which is used for padding.
 #[derive(bytemuck::Pod, bytemuck::Zeroable, Debug, Clone, Copy, Default)]
-#[derive(serde::Deserialize, serde::Serialize)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[repr(C, align(16))]
 pub struct DrawCommandGpu {
 	/// See [`CommandGpu`] for possible values.
@@ -43,7 +43,35 @@ pub struct DrawCommandGpu {
 	pub smooth_parameter: f32,
 	/// The index of the shape to combine with the previous content.
 	pub lhs: u32,
-	pub(crate) __padding: [u8; 4],
+	/// The [`Interaction`] of the shape packed as two 16 bits unorms, 0 for none.
+	/// 
+	/// Only used by the fill commands, which brighten the color by the hover factor and darken it by the press factor.
+	pub interaction: u32,
+}
+
+/// How much a widget is hovered and pressed, see [`crate::render::painter::Painter::set_interaction_effect`].
+/// 
+/// The factors fade between 0.0 and 1.0 on the cpu, so the effect animates smoothly.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Interaction {
+	/// How much the widget is hovered.
+	pub hover: f32,
+	/// How much the widget is pressed.
+	pub press: f32,
+}
+
+impl Interaction {
+	/// Check if the widget is neither hovered nor pressed.
+	pub fn is_idle(&self) -> bool {
+		self.hover <= 0.0 && self.press <= 0.0
+	}
+
+	/// Pack the factors into [`DrawCommandGpu::interaction`].
+	pub(crate) fn pack(self) -> u32 {
+		let hover = (self.hover.clamp(0.0, 1.0) * 65535.0).round() as u32;
+		let press = (self.press.clamp(0.0, 1.0) * 65535.0).round() as u32;
+		hover | (press << 16)
+	}
 }
 
 impl Hash for DrawCommandGpu {
@@ -55,6 +83,7 @@ impl Hash for DrawCommandGpu {
 		self.operation.hash(state);
 		self.smooth_parameter.to_bits().hash(state);
 		self.lhs.hash(state);
+		self.interaction.hash(state);
 		for slot in self.slots.iter() {
 			for value in slot.iter() {
 				value.to_bits().hash(state);
@@ -76,7 +105,7 @@ impl Hash for DrawCommandGpu {
 // }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
-#[derive(serde::Deserialize, serde::Serialize)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[repr(u32)]
 /// The possible commands that can be sent to the gpu.
 /// 
@@ -252,10 +281,45 @@ pub enum CommandGpu {
 	/// Will expect 1 value in `slot`:
 	/// 1. index of the shape in the stack as u32
 	Load = 14,
+	/// Fill the current path with a texture sliced into nine parts.
+	/// 
+	/// Will expect 13 values in `slot`:
+	/// 1. top_left.x
+	/// 2. top_left.y
+	/// 3. right_bottom.x
+	/// 4. right_bottom.y
+	/// 5. texture_left_top.x
+	/// 6. texture_left_top.y
+	/// 7. texture_right_bottom.x
+	/// 8. texture_right_bottom.y
+	/// 9. texture id as u32
+	/// 10. left inset
+	/// 11. top inset
+	/// 12. right inset
+	/// 13. bottom inset
+	FillNinePatch = 15,
+	/// Fill the current path with repeating stripes of two colors.
+	/// 
+	/// Will expect 12 values in `slot`:
+	/// 1. a.r
+	/// 2. a.g
+	/// 3. a.b
+	/// 4. a.a
+	/// 5. b.r
+	/// 6. b.g
+	/// 7. b.b
+	/// 8. b.a
+	/// 9. start.x
+	/// 10. start.y
+	/// 11. end.x
+	/// 12. end.y
+	/// 
+	/// The start and end span one period, the first half is filled with `a`.
+	FillStripes = 16,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
-#[derive(serde::Deserialize, serde::Serialize)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[repr(u16)]
 /// The possible operations that can be done between two distance fields.
 /// 
@@ -314,7 +378,7 @@ pub enum OperationGpu {
 
 /// The possible blend modes for the current shape.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
-#[derive(serde::Deserialize, serde::Serialize)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[repr(u32)]
 pub enum BlendMode {
 	/// Simply replace the color of the shape.
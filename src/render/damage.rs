@@ -0,0 +1,142 @@
+//! Tracking the areas to redraw in a frame.
+//!
+//! Instead of a single union, a few separate rects are kept, so two small changes in the opposite corners of the window
+//! do not redraw everything between them. Each rect is drawn with its own scissor by the UI pass.
+
+use crate::math::rect::Rect;
+
+/// The most rects kept in a [`Damage`], the two closest rects are merged beyond it.
+pub const MAX_DAMAGE_RECTS: usize = 8;
+/// How much larger than the two rects their union may be to still merge them, as a ratio of their areas.
+///
+/// Every rect costs a draw call, so nearly adjacent rects are cheaper drawn at once.
+pub const DAMAGE_MERGE_RATIO: f32 = 1.25;
+
+/// The areas to redraw in a frame, as a few rects that never overlap.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Damage {
+	rects: Vec<Rect>,
+}
+
+impl From<Rect> for Damage {
+	fn from(area: Rect) -> Self {
+		let mut damage = Self::default();
+		damage.add(area);
+		damage
+	}
+}
+
+impl Damage {
+	/// Add an area to redraw, merging it with the rects it overlaps or is close to.
+	pub fn add(&mut self, area: Rect) {
+		if area.is_empty() {
+			return;
+		}
+		let mut area = area;
+		// merging may make the area overlap a rect checked before, so check again until nothing merges.
+		while let Some(index) = self.rects.iter().position(|rect| should_merge(*rect, area)) {
+			area |= self.rects.swap_remove(index);
+		}
+		self.rects.push(area);
+
+		while self.rects.len() > MAX_DAMAGE_RECTS {
+			let (a, b) = self.cheapest_merge();
+			let merged = self.rects.swap_remove(b) | self.rects.swap_remove(a);
+			self.add(merged);
+		}
+	}
+
+	/// Add all rects of another damage.
+	pub fn extend(&mut self, other: Damage) {
+		for rect in other.rects {
+			self.add(rect);
+		}
+	}
+
+	/// Get the rects to redraw.
+	pub fn rects(&self) -> &[Rect] {
+		&self.rects
+	}
+
+	/// Check if there is nothing to redraw.
+	pub fn is_empty(&self) -> bool {
+		self.rects.is_empty()
+	}
+
+	/// Get the union of all rects, `None` if there is nothing to redraw.
+	pub fn bounds(&self) -> Option<Rect> {
+		self.rects.iter().copied().reduce(|a, b| a | b)
+	}
+
+	/// Check if the area overlaps any rect to redraw.
+	pub fn intersects(&self, area: Rect) -> bool {
+		self.rects.iter().any(|rect| rect.intersects(area))
+	}
+
+	/// Map every rect, e.g. to scale it into pixels, dropping the empty results.
+	pub fn map(self, mut f: impl FnMut(Rect) -> Rect) -> Self {
+		let mut out = Self::default();
+		for rect in self.rects {
+			out.add(f(rect));
+		}
+		out
+	}
+
+	/// Find the indices of the two rects whose union adds the least area, the first index is smaller.
+	fn cheapest_merge(&self) -> (usize, usize) {
+		let mut best = (0, 1, f32::INFINITY);
+		for a in 0..self.rects.len() {
+			for b in a + 1..self.rects.len() {
+				let waste = merge_waste(self.rects[a], self.rects[b]);
+				if waste < best.2 {
+					best = (a, b, waste);
+				}
+			}
+		}
+		(best.0, best.1)
+	}
+}
+
+/// The area of the union of two rects not covered by either of them, ignoring their overlap.
+fn merge_waste(a: Rect, b: Rect) -> f32 {
+	(a | b).area() - a.area() - b.area()
+}
+
+/// The rects must not overlap, as the ui pass blends onto the previous frame and would draw the overlap twice.
+fn should_merge(a: Rect, b: Rect) -> bool {
+	a.intersects(b) || (a | b).area() <= (a.area() + b.area()) * DAMAGE_MERGE_RATIO
+}
+
+mod test {
+	#[test]
+	fn test_damage_merge() {
+		use super::{Damage, MAX_DAMAGE_RECTS};
+		use crate::prelude::Rect;
+
+		let mut damage = Damage::default();
+		damage.add(Rect::new(0.0, 0.0, 10.0, 10.0));
+		damage.add(Rect::new(500.0, 500.0, 10.0, 10.0));
+		assert_eq!(damage.rects().len(), 2);
+
+		// overlapping the first rect.
+		damage.add(Rect::new(5.0, 5.0, 10.0, 10.0));
+		assert_eq!(damage.rects().len(), 2);
+		assert!(damage.rects().contains(&Rect::new(0.0, 0.0, 15.0, 15.0)));
+
+		// adjacent to the second rect.
+		damage.add(Rect::new(510.0, 500.0, 10.0, 10.0));
+		assert_eq!(damage.rects().len(), 2);
+		assert!(damage.rects().contains(&Rect::new(500.0, 500.0, 20.0, 10.0)));
+
+		for i in 0..MAX_DAMAGE_RECTS * 2 {
+			damage.add(Rect::new(i as f32 * 100.0, 1000.0, 1.0, 1.0));
+		}
+		assert!(damage.rects().len() <= MAX_DAMAGE_RECTS);
+		let rects = damage.rects();
+		for (i, a) in rects.iter().enumerate() {
+			for b in &rects[i + 1..] {
+				assert!(!a.intersects(*b));
+			}
+		}
+	}
+}
@@ -0,0 +1,189 @@
+//! Debug helpers for visualizing how the ui is redrawn.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::math::{color::{Color, Vec4}, rect::Rect, vec2::Vec2};
+
+use super::{damage::Damage, painter::Painter};
+
+/// Visualizes the damaged (refreshed) area of each frame and accumulates a heatmap of how often each region redraws.
+///
+/// Set [`crate::Context::redraw_debugger`] to enable it.
+/// While enabled, the whole window will be redrawn every frame so that the overlay stays accurate,
+/// which means the performance is not representative.
+///
+/// Regions redrawn every frame will turn red, which usually means a widget marks itself dirty more often than needed.
+#[derive(Debug, Clone)]
+pub struct RedrawDebugger {
+	/// The size of a heatmap cell in logical pixels.
+	pub cell_size: f32,
+	/// How much heat is kept after each drawn frame, in the range [0, 1).
+	pub decay: f32,
+	/// Whether to tint the damaged area of the last frame.
+	pub show_damage: bool,
+	/// Whether to draw the heatmap.
+	pub show_heatmap: bool,
+	/// The color used to tint the damaged area of the last frame.
+	pub damage_color: Color,
+	heat: HashMap<(i32, i32), f32>,
+	last_damage: Damage,
+	frames: u64,
+	redrawn_frames: u64,
+}
+
+impl Default for RedrawDebugger {
+	fn default() -> Self {
+		Self {
+			cell_size: 64.0,
+			decay: 0.95,
+			show_damage: true,
+			show_heatmap: true,
+			damage_color: Color::new(1.0, 0.0, 1.0, 0.2),
+			heat: HashMap::new(),
+			last_damage: Damage::default(),
+			frames: 0,
+			redrawn_frames: 0,
+		}
+	}
+}
+
+impl RedrawDebugger {
+	/// The minimum heat of a cell to be drawn or kept.
+	const MIN_HEAT: f32 = 0.05;
+
+	/// Creates a new debugger with default settings.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Sets the size of a heatmap cell.
+	pub fn cell_size(self, cell_size: f32) -> Self {
+		Self {
+			cell_size: cell_size.max(1.0),
+			..self
+		}
+	}
+
+	/// Sets how much heat is kept after each drawn frame.
+	pub fn decay(self, decay: f32) -> Self {
+		Self {
+			decay: decay.clamp(0.0, 0.999),
+			..self
+		}
+	}
+
+	/// Sets whether to tint the damaged area of the last frame.
+	pub fn show_damage(self, show_damage: bool) -> Self {
+		Self {
+			show_damage,
+			..self
+		}
+	}
+
+	/// Sets whether to draw the heatmap.
+	pub fn show_heatmap(self, show_heatmap: bool) -> Self {
+		Self {
+			show_heatmap,
+			..self
+		}
+	}
+
+	/// Clears the accumulated heatmap and statistics.
+	pub fn reset(&mut self) {
+		self.heat.clear();
+		self.last_damage = Damage::default();
+		self.frames = 0;
+		self.redrawn_frames = 0;
+	}
+
+	/// Get the damaged rects of the last frame, empty if nothing was redrawn.
+	pub fn last_damage(&self) -> &Damage {
+		&self.last_damage
+	}
+
+	/// Get the number of frames recorded and how many of them redrew something.
+	pub fn frames(&self) -> (u64, u64) {
+		(self.frames, self.redrawn_frames)
+	}
+
+	/// Get the heat at the given position.
+	///
+	/// A heat of 1.0 means the region is redrawn every frame.
+	pub fn heat_at(&self, pos: impl Into<Vec2>) -> f32 {
+		let pos = pos.into() / self.cell_size;
+		self.heat.get(&(pos.x.floor() as i32, pos.y.floor() as i32)).map(|heat| heat * (1.0 - self.decay)).unwrap_or(0.0)
+	}
+
+	/// Get the hottest region of the heatmap and its heat.
+	pub fn hottest(&self) -> Option<(Rect, f32)> {
+		self.heat.iter()
+			.max_by(|a, b| a.1.partial_cmp(b.1).unwrap_or(std::cmp::Ordering::Equal))
+			.map(|(cell, heat)| (self.cell_rect(*cell), heat * (1.0 - self.decay)))
+	}
+
+	/// Returns true if the heatmap is still fading out, which means the overlay needs to be redrawn.
+	pub fn is_fading(&self) -> bool {
+		!self.heat.is_empty() || !self.last_damage.is_empty()
+	}
+
+	fn cell_rect(&self, (x, y): (i32, i32)) -> Rect {
+		Rect::new(x as f32 * self.cell_size, y as f32 * self.cell_size, self.cell_size, self.cell_size)
+	}
+
+	/// Records the damaged area of a frame.
+	pub(crate) fn record(&mut self, damage: &Damage, window_size: Vec2) {
+		self.frames += 1;
+		self.heat.retain(|_, heat| {
+			*heat *= self.decay;
+			*heat >= Self::MIN_HEAT
+		});
+
+		self.last_damage = damage.clone().map(|damage| damage & Rect::from_size(window_size));
+		if !self.last_damage.is_empty() {
+			self.redrawn_frames += 1;
+		}
+		// a cell covered by several rects is still redrawn once.
+		let mut cells = HashSet::new();
+		for damage in self.last_damage.rects() {
+			let lt = (damage.lt() / self.cell_size).floor();
+			let rb = (damage.rb() / self.cell_size).ceil();
+			for x in lt.x as i32..rb.x as i32 {
+				for y in lt.y as i32..rb.y as i32 {
+					cells.insert((x, y));
+				}
+			}
+		}
+		for cell in cells {
+			*self.heat.entry(cell).or_default() += 1.0;
+		}
+	}
+
+	/// Paints the overlay on top of everything painted before.
+	pub(crate) fn paint(&self, painter: &mut Painter) {
+		painter.set_clip_rect(Rect::from_size(painter.window_size));
+		painter.set_relative_to(Vec2::ZERO);
+		painter.reset_transform();
+		painter.reset_blend_mode();
+
+		if self.show_heatmap {
+			for (cell, heat) in &self.heat {
+				let heat = (heat * (1.0 - self.decay)).min(1.0);
+				if heat < Self::MIN_HEAT {
+					continue;
+				}
+				let color = Color::new(0.0, 0.3, 1.0, 0.0).lerp(Color::new(1.0, 0.0, 0.0, 0.0), heat);
+				painter.set_fill_mode(Color { a: 0.1 + 0.3 * heat, ..color });
+				painter.draw_rect(self.cell_rect(*cell), Vec4::ZERO);
+			}
+		}
+
+		if self.show_damage {
+			painter.set_fill_mode(self.damage_color);
+			for damage in self.last_damage.rects() {
+				painter.draw_rect(*damage, Vec4::ZERO);
+			}
+		}
+
+		painter.reset_fill_mode();
+	}
+}
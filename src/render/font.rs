@@ -31,11 +31,37 @@ pub const MAX_FONTS: usize = 16;
 /// The font id type.
 pub type FontId = u32;
 
+/// The semantic role of a font, so widgets can follow the typography of the app.
+/// 
+/// Register the font of each role by [`crate::Context::set_font_role`].
+/// A widget with a role draws in the font registered for it, and in its own font id when the role is `None` or not registered.
+/// Setting the font id of a widget by its `font` builder clears the role, so the given font is always used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FontRole {
+	/// The font for titles and headings.
+	Heading,
+	/// The font for common texts.
+	Body,
+	/// The monospaced font for codes and numbers.
+	Monospace,
+}
+
+/// The embedded default font, DejaVu Sans.
+/// 
+/// It covers Latin, Greek and Cyrillic, but not CJK, load your own font if you need more characters.
+/// The license can be found at `src/render/fonts/LICENSE`.
+/// 
+/// Only available with the `default-font` feature.
+#[cfg(feature = "default-font")]
+pub const DEFAULT_FONT: &[u8] = include_bytes!("fonts/DejaVuSans.ttf");
+
 /// The font pool, used to store and manage font textures and character textures.
 pub struct FontPool {
 	fonts: HashMap<FontId, Font>,
 	removed_fonts: HashSet<FontId>,
 	new_id: FontId,
+	roles: HashMap<FontRole, FontId>,
 }
 
 impl Default for FontPool {
@@ -51,6 +77,7 @@ impl FontPool{
 			fonts: HashMap::new(),
 			removed_fonts: HashSet::new(),
 			new_id: 0,
+			roles: HashMap::new(),
 		}
 	}
 
@@ -76,6 +103,7 @@ impl FontPool{
 	pub fn remove_font(&mut self, font_id: FontId) -> bool {
 		if self.fonts.remove(&font_id).is_some() {
 			self.removed_fonts.insert(font_id);
+			self.roles.retain(|_, id| *id != font_id);
 			true
 		}else {
 			false
@@ -85,9 +113,28 @@ impl FontPool{
 	/// Clear the font pool.
 	pub fn clear(&mut self) {
 		self.fonts.clear();
+		self.roles.clear();
 		self.new_id = 0;
 	}
 
+	/// Sets the font used for the given role, `None` to unregister the role.
+	pub fn set_role(&mut self, role: FontRole, font_id: Option<FontId>) {
+		match font_id {
+			Some(font_id) => self.roles.insert(role, font_id),
+			None => self.roles.remove(&role),
+		};
+	}
+
+	/// Gets the font registered for the given role.
+	pub fn role(&self, role: FontRole) -> Option<FontId> {
+		self.roles.get(&role).copied()
+	}
+
+	/// Gets the font of the role if it is registered, otherwise the fallback font.
+	pub fn resolve(&self, role: Option<FontRole>, fallback: FontId) -> FontId {
+		role.and_then(|role| self.role(role)).unwrap_or(fallback)
+	}
+
 	/// Returns the line height of the font with the given id.
 	/// 
 	/// Will use [`EM`] as font size. To use a different size, use [`Self::line_height_with_size`].
@@ -143,6 +190,14 @@ impl FontPool{
 		}
 	}
 
+	/// Queue all the loaded glyphs to be uploaded again, used after the gpu resources are recreated.
+	pub(crate) fn reupload_glyphs(&mut self) {
+		for font in self.fonts.values_mut() {
+			let chars = font.char_map.keys().copied().collect::<Vec<_>>();
+			font.to_add_to_texture.extend(chars);
+		}
+	}
+
 	pub(crate) fn generate_textures(&mut self) -> Vec<OutputEvent> {
 		let mut out = vec!();
 		for (id, font) in self.fonts.iter_mut() {
@@ -281,6 +336,9 @@ impl Font {
 		let face = self.face.as_face_ref();
 		let len = self.to_add_to_texture.len();
 		let chars = self.to_add_to_texture.drain(0..len.min(MAXIUM_CHAR_UPLOAD_PER_FRAME)).collect::<Vec<_>>();
+		if !chars.is_empty() {
+			log::debug!("Generating {} glyph textures for font {}, {} left", chars.len(), font_id, self.to_add_to_texture.len());
+		}
 		let factor = face.height() as f32 / self.base_units_per_em;
 		let descender = face.descender() as f32;
 		let proj = Projection {
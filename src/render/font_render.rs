@@ -7,11 +7,14 @@ use super::{font::{FontId, CHAR_TEXTURE_SIZE, FONT_TEXTURE_SIZE}, texture::{crea
 
 const DEFAULT_FONT_LAYERS: u32 = 4;
 
+/// The layer in the font texture of each uploaded glyph.
+pub(crate) type GlyphMap = HashMap<(char, FontId), u32>;
+
 pub(crate) struct FontRender {
 	pub texture: wgpu::Texture,
 	pub bind_group: wgpu::BindGroup,
 	pub bind_group_layout: wgpu::BindGroupLayout,
-	pub char_texture_map: HashMap<(char, FontId), u32>,
+	pub char_texture_map: GlyphMap,
 	pub empty_positions: IndexSet<u32>,
 	pub layers: u32
 }
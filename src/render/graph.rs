@@ -0,0 +1,285 @@
+//! A minimal render graph for injecting custom gpu passes, enabled by the `render-graph` feature.
+//!
+//! Each frame is rendered by two built-in passes:
+//! the UI pass draws the shapes into an intermediate render texture sized by the quality factor,
+//! then the scale pass draws the render texture onto the window surface.
+//!
+//! Register your own passes with [`RenderGraph::add_pass`] on [`crate::Context::render_graph`],
+//! ordered relative to the built-in passes and to each other by [`PassDesc`]:
+//!
+//! - passes before the scale pass draw into the render texture, e.g. a background shader before the UI pass,
+//!   or a post-processing effect after it.
+//! - passes after the scale pass draw onto the window surface, e.g. an overlay in physical pixels.
+//!
+//! The UI is rendered incrementally, only the damaged rects given by [`PassContext::damage`] are redrawn by the UI pass
+//! and the passes only run when the UI is redrawn.
+//! Set [`crate::Context::force_redraw_per_frame`] if your passes animate.
+
+use indexmap::IndexMap;
+
+use crate::math::{rect::Rect, vec2::Vec2};
+
+/// A node of the render graph, used to order the passes.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PassAnchor {
+	/// The built-in pass drawing the UI into the render texture.
+	Ui,
+	/// The built-in pass drawing the render texture onto the window surface.
+	Scale,
+	/// A pass registered with the given name.
+	Pass(String),
+}
+
+impl From<&str> for PassAnchor {
+	fn from(name: &str) -> Self {
+		Self::Pass(name.to_string())
+	}
+}
+
+impl From<String> for PassAnchor {
+	fn from(name: String) -> Self {
+		Self::Pass(name)
+	}
+}
+
+/// Describes where a pass runs in the render graph.
+///
+/// A pass without any dependency runs between the UI pass and the scale pass.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PassDesc {
+	/// The unique name of the pass.
+	pub name: String,
+	/// The passes this pass runs after.
+	pub after: Vec<PassAnchor>,
+	/// The passes this pass runs before.
+	pub before: Vec<PassAnchor>,
+}
+
+impl PassDesc {
+	/// Create a new description with the given name and no dependency.
+	pub fn new(name: impl Into<String>) -> Self {
+		Self {
+			name: name.into(),
+			after: vec!(),
+			before: vec!(),
+		}
+	}
+
+	/// Run the pass after the given pass.
+	pub fn after(mut self, anchor: impl Into<PassAnchor>) -> Self {
+		self.after.push(anchor.into());
+		self
+	}
+
+	/// Run the pass before the given pass.
+	pub fn before(mut self, anchor: impl Into<PassAnchor>) -> Self {
+		self.before.push(anchor.into());
+		self
+	}
+}
+
+/// An error that occurs when building the render graph.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum RenderGraphError {
+	/// A pass with the same name is already registered.
+	#[error("pass `{0}` is already registered")]
+	DuplicatePass(String),
+	/// A pass depends on a pass not registered.
+	#[error("pass `{0}` depends on unregistered pass `{1}`")]
+	UnknownPass(String, String),
+	/// The dependencies form a cycle, contains the passes in the cycle and the passes depending on them.
+	#[error("passes {0:?} depend on each other")]
+	Cycle(Vec<String>),
+}
+
+/// The gpu resources available to a pass.
+pub struct PassContext<'a> {
+	/// The gpu device.
+	pub device: &'a wgpu::Device,
+	/// The gpu queue.
+	pub queue: &'a wgpu::Queue,
+	/// The encoder to record the pass into, submitted after the pass.
+	pub encoder: &'a mut wgpu::CommandEncoder,
+	/// The intermediate texture the UI is rendered into.
+	pub render_texture: &'a wgpu::Texture,
+	/// The view of [`Self::render_texture`].
+	pub render_view: &'a wgpu::TextureView,
+	/// The view to draw into, [`Self::render_view`] before the scale pass and the window surface after it.
+	pub target: &'a wgpu::TextureView,
+	/// The format of the render texture and the window surface.
+	pub format: wgpu::TextureFormat,
+	/// The bounds of the damaged rects redrawn this frame, in pixels of the target.
+	pub render_area: Rect,
+	/// The damaged rects redrawn this frame, in pixels of the target, never overlapping.
+	pub damage: &'a [Rect],
+	/// The size of the window in logical pixels.
+	pub window_size: Vec2,
+	/// The scale factor of the window multiplied by the quality factor.
+	pub scale_factor: f32,
+	/// The seconds since the program started, same as the `time` uniform of the UI shader.
+	pub time: f32,
+	/// Whether the pass runs after the scale pass, drawing onto the window surface.
+	pub after_scale: bool,
+}
+
+/// A custom gpu pass, see [`crate::render::graph`].
+pub trait RenderPass: 'static {
+	/// Create the gpu resources of the pass, such as pipelines.
+	///
+	/// Called before the pass first runs, and again when the gpu resources are recreated,
+	/// e.g. when the app is resumed on mobile platforms. `format` is the format of the render texture and the window surface.
+	fn setup(&mut self, device: &wgpu::Device, queue: &wgpu::Queue, format: wgpu::TextureFormat) {
+		let _ = (device, queue, format);
+	}
+
+	/// Record the pass into [`PassContext::encoder`].
+	fn run(&mut self, ctx: &mut PassContext);
+}
+
+struct PassEntry {
+	desc: PassDesc,
+	pass: Box<dyn RenderPass>,
+	is_set_up: bool,
+}
+
+/// A node in the resolved order of the render graph.
+pub(crate) enum GraphNode {
+	Ui,
+	Scale,
+	Pass(String),
+}
+
+/// The custom passes and their order, see [`crate::render::graph`].
+#[derive(Default)]
+pub struct RenderGraph {
+	passes: IndexMap<String, PassEntry>,
+	order: Option<Vec<PassAnchor>>,
+}
+
+impl RenderGraph {
+	/// Register a pass.
+	///
+	/// The dependencies are checked when the graph is resolved, so passes can depend on passes registered later.
+	/// Call [`Self::order`] to check the graph after registering all the passes.
+	pub fn add_pass(&mut self, desc: PassDesc, pass: impl RenderPass) -> Result<(), RenderGraphError> {
+		if self.passes.contains_key(&desc.name) {
+			return Err(RenderGraphError::DuplicatePass(desc.name));
+		}
+		self.order = None;
+		self.passes.insert(desc.name.clone(), PassEntry {
+			desc,
+			pass: Box::new(pass),
+			is_set_up: false,
+		});
+		Ok(())
+	}
+
+	/// Remove a pass, returns it if found.
+	pub fn remove_pass(&mut self, name: &str) -> Option<Box<dyn RenderPass>> {
+		self.order = None;
+		self.passes.shift_remove(name).map(|entry| entry.pass)
+	}
+
+	/// Check whether a pass with the given name is registered.
+	pub fn contains(&self, name: &str) -> bool {
+		self.passes.contains_key(name)
+	}
+
+	/// Check whether no pass is registered.
+	pub fn is_empty(&self) -> bool {
+		self.passes.is_empty()
+	}
+
+	/// Resolve the order the passes run in, including the built-in passes.
+	///
+	/// Passes not ordered relative to each other run in the order they are registered.
+	pub fn order(&self) -> Result<Vec<PassAnchor>, RenderGraphError> {
+		let mut nodes = vec!(PassAnchor::Ui, PassAnchor::Scale);
+		nodes.extend(self.passes.keys().map(|name| PassAnchor::Pass(name.clone())));
+		let index_of = |anchor: &PassAnchor| nodes.iter().position(|node| node == anchor);
+
+		// edges from a node to the nodes running after it.
+		let mut edges = vec![vec!(); nodes.len()];
+		let mut incoming = vec![0; nodes.len()];
+		let mut add_edge = |from: usize, to: usize| {
+			edges[from].push(to);
+			incoming[to] += 1;
+		};
+		add_edge(0, 1);
+		for (index, entry) in self.passes.values().enumerate() {
+			let index = index + 2;
+			let find = |anchor: &PassAnchor| index_of(anchor).ok_or_else(|| match anchor {
+				PassAnchor::Pass(name) => RenderGraphError::UnknownPass(entry.desc.name.clone(), name.clone()),
+				_ => unreachable!(),
+			});
+			if entry.desc.after.is_empty() && entry.desc.before.is_empty() {
+				add_edge(0, index);
+				add_edge(index, 1);
+			}
+			for anchor in &entry.desc.after {
+				add_edge(find(anchor)?, index);
+			}
+			for anchor in &entry.desc.before {
+				add_edge(index, find(anchor)?);
+			}
+		}
+
+		let mut order = vec!();
+		let mut ready = (0..nodes.len()).filter(|index| incoming[*index] == 0).collect::<Vec<_>>();
+		while !ready.is_empty() {
+			// take the earliest registered node to keep the order stable.
+			let (position, _) = ready.iter().enumerate().min_by_key(|(_, index)| **index).unwrap();
+			let index = ready.remove(position);
+			order.push(index);
+			for next in &edges[index] {
+				incoming[*next] -= 1;
+				if incoming[*next] == 0 {
+					ready.push(*next);
+				}
+			}
+		}
+
+		if order.len() < nodes.len() {
+			let cycle = (0..nodes.len()).filter(|index| incoming[*index] > 0).map(|index| match &nodes[index] {
+				PassAnchor::Ui => String::from("ui"),
+				PassAnchor::Scale => String::from("scale"),
+				PassAnchor::Pass(name) => name.clone(),
+			}).collect();
+			return Err(RenderGraphError::Cycle(cycle));
+		}
+
+		Ok(order.into_iter().map(|index| nodes[index].clone()).collect())
+	}
+
+	/// Mark all the passes to be set up again, called when the gpu resources are recreated.
+	pub(crate) fn invalidate(&mut self) {
+		for entry in self.passes.values_mut() {
+			entry.is_set_up = false;
+		}
+	}
+
+	/// Get the resolved order, falling back to the built-in passes only if the graph is invalid.
+	pub(crate) fn nodes(&mut self) -> Vec<GraphNode> {
+		if self.order.is_none() {
+			self.order = Some(self.order().unwrap_or_else(|e| {
+				log::error!("Invalid render graph, custom passes are skipped: {}", e);
+				vec!(PassAnchor::Ui, PassAnchor::Scale)
+			}));
+		}
+		self.order.iter().flatten().map(|anchor| match anchor {
+			PassAnchor::Ui => GraphNode::Ui,
+			PassAnchor::Scale => GraphNode::Scale,
+			PassAnchor::Pass(name) => GraphNode::Pass(name.clone()),
+		}).collect()
+	}
+
+	/// Run a registered pass, setting it up first if needed.
+	pub(crate) fn run(&mut self, name: &str, ctx: &mut PassContext) {
+		let Some(entry) = self.passes.get_mut(name) else { return };
+		if !entry.is_set_up {
+			entry.pass.setup(ctx.device, ctx.queue, ctx.format);
+			entry.is_set_up = true;
+		}
+		entry.pass.run(ctx);
+	}
+}
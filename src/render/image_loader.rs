@@ -0,0 +1,117 @@
+//! Decoding images off the main thread, enabled by the `image-loader` feature.
+//!
+//! Start loading with [`crate::Context::load_image`], the bytes are decoded on the rayon thread pool
+//! and registered as a texture on a later event frame.
+//! Poll the returned [`ImageHandle`] to know when the image is ready, or show it with [`crate::widgets::image::Image`].
+
+use std::{cell::RefCell, rc::Rc, sync::mpsc::{channel, Receiver, TryRecvError}};
+
+use crate::prelude::Vec2;
+
+use super::texture::TextureId;
+
+/// The state of an image loaded by [`crate::Context::load_image`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum ImageState {
+	/// The image is being decoded.
+	Loading,
+	/// The image is registered as the texture, given the size of the image in pixels.
+	Ready(TextureId, Vec2),
+	/// The image failed to decode, given the reason.
+	Error(String),
+}
+
+/// A handle to an image loaded by [`crate::Context::load_image`], cheap to clone and share between widgets.
+///
+/// The texture is owned by the context, remove it with [`crate::Context::remove_texture`] when no longer needed.
+/// If every handle is dropped before the image is decoded, the texture is never registered.
+#[derive(Clone, Debug)]
+pub struct ImageHandle {
+	state: Rc<RefCell<ImageState>>,
+}
+
+impl PartialEq for ImageHandle {
+	fn eq(&self, other: &Self) -> bool {
+		Rc::ptr_eq(&self.state, &other.state)
+	}
+}
+
+impl ImageHandle {
+	/// Get the current state of the image.
+	pub fn state(&self) -> ImageState {
+		self.state.borrow().clone()
+	}
+
+	/// Check if the image is still being decoded.
+	pub fn is_loading(&self) -> bool {
+		*self.state.borrow() == ImageState::Loading
+	}
+
+	/// Get the texture and the size in pixels of the image, if ready.
+	pub fn texture(&self) -> Option<(TextureId, Vec2)> {
+		match &*self.state.borrow() {
+			ImageState::Ready(texture_id, size) => Some((*texture_id, *size)),
+			_ => None,
+		}
+	}
+
+	pub(crate) fn set_state(&self, state: ImageState) {
+		*self.state.borrow_mut() = state;
+	}
+
+	/// Whether only the loader holds the handle.
+	pub(crate) fn is_orphan(&self) -> bool {
+		Rc::strong_count(&self.state) == 1
+	}
+}
+
+/// The rgba data and the size of a decoded image, or the reason it failed.
+pub(crate) type DecodedImage = Result<(Vec<u8>, Vec2), String>;
+
+/// The images being decoded, polled every event frame by the context.
+#[derive(Default)]
+pub(crate) struct ImageLoader {
+	pending: Vec<(Receiver<DecodedImage>, ImageHandle)>,
+}
+
+impl ImageLoader {
+	/// Start decoding the encoded image on the thread pool.
+	pub(crate) fn load(&mut self, bytes: Vec<u8>) -> ImageHandle {
+		let (sender, receiver) = channel();
+		rayon::spawn(move || {
+			// the receiver is gone if the context was dropped, nothing to do then.
+			let _ = sender.send(decode(&bytes));
+		});
+		let handle = ImageHandle { state: Rc::new(RefCell::new(ImageState::Loading)) };
+		self.pending.push((receiver, handle.clone()));
+		handle
+	}
+
+	/// Take the images decoded since the last poll.
+	pub(crate) fn poll(&mut self) -> Vec<(ImageHandle, DecodedImage)> {
+		let mut out = vec!();
+		self.pending.retain(|(receiver, handle)| match receiver.try_recv() {
+			Ok(decoded) => {
+				out.push((handle.clone(), decoded));
+				false
+			},
+			Err(TryRecvError::Empty) => true,
+			Err(TryRecvError::Disconnected) => {
+				out.push((handle.clone(), Err("the decoding thread panicked".to_string())));
+				false
+			},
+		});
+		out
+	}
+
+	/// Check if any image is still being decoded.
+	pub(crate) fn is_loading(&self) -> bool {
+		!self.pending.is_empty()
+	}
+}
+
+fn decode(bytes: &[u8]) -> DecodedImage {
+	let image = image::load_from_memory(bytes).map_err(|error| error.to_string())?.to_rgba8();
+	let size = Vec2::new(image.width() as f32, image.height() as f32);
+	Ok((image.into_raw(), size))
+}
@@ -5,8 +5,20 @@ pub mod font;
 pub mod shape;
 pub mod painter;
 pub mod texture;
+pub mod debug;
+pub mod damage;
+pub mod snapshot;
+pub mod text_layout;
+pub mod viewport;
+#[cfg(feature = "capture")]
+pub mod capture;
+#[cfg(feature = "render-graph")]
+pub mod graph;
+#[cfg(feature = "image-loader")]
+pub mod image_loader;
 pub mod prelude;
 pub(crate) mod backend;
+pub(crate) mod command_cache;
 pub(crate) mod font_render;
 // pub(crate) mod painter_ctx;
 // pub(crate) mod shape_compile;
\ No newline at end of file
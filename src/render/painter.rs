@@ -4,9 +4,14 @@ use std::sync::{Arc, Mutex};
 
 use lyon_geom::{point, CubicBezierSegment};
 
-use crate::{math::{color::Vec4, prelude::Transform2D, rect::Rect, vec2::Vec2}, render::{commands::{CommandGpu, OperationGpu}, font::EM, font_render::FontRender}};
+use crate::{layout::LayoutId, math::{color::Vec4, prelude::Transform2D, rect::Rect, vec2::Vec2}, render::{commands::{CommandGpu, OperationGpu}, font::EM, font_render::GlyphMap}, widgets::localizer::{DefaultLocalizer, Localizer}};
 
-use super::{commands::{BlendMode, DrawCommandGpu}, font::{FontId, FontPool}, shape::{BasicShape, BasicShapeData, FillMode, Operator, Shape, ShapeOrOp}};
+use super::{command_cache::{CommandCache, CompiledShape, DrawKey}, commands::{BlendMode, DrawCommandGpu, Interaction}, damage::Damage, font::{FontId, FontPool, FontRole}, shape::{BasicShape, BasicShapeData, FillMode, Operator, Shape, ShapeOrOp}, text_layout::{PositionedGlyph, TextLayout, TextLine}, viewport::{viewport_texture_size, ViewportId}};
+
+/// The maximum number of supersampled regions in a single frame.
+pub const MAX_SUPERSAMPLE_REGIONS: usize = 16;
+/// The maximum quality factor of a supersampled region.
+pub const MAX_SUPERSAMPLE_FACTOR: f32 = 4.0;
 
 /// A shape to draw.
 pub struct ShapeToDraw {
@@ -20,6 +25,8 @@ pub struct ShapeToDraw {
 	// pub transform: Transform2D,
 	/// The clip rect to use.
 	pub clip_rect: Rect,
+	/// The hover and press state the fill reacts to, see [`Painter::set_interaction_effect`].
+	pub interaction: Interaction,
 }
 
 impl ShapeToDraw {
@@ -66,6 +73,26 @@ pub struct Painter {
 	releative_to: Vec2,
 	clip_rect: Rect,
 	scale_factor: f32,
+	supersample_regions: Vec<(Rect, f32)>,
+	/// the viewports drawn and the sizes of their textures.
+	viewports: Vec<(ViewportId, [u32; 2])>,
+	localizer: Option<Arc<dyn Localizer>>,
+	interaction: Interaction,
+	interaction_effect: bool,
+	/// how much the shapes drawn next are faded out, `0.0` is fully opaque.
+	transparency: f32,
+	/// the index of the first shape of each run of shapes and who drew it, see [`Painter::begin_segment`].
+	segments: Vec<(usize, Segment)>,
+}
+
+/// Who drew a run of shapes, so the commands of the widgets can be cached, see [`super::command_cache`].
+enum Segment {
+	/// Not cached, such as the overlays drawn by the layout.
+	Uncached,
+	/// Drawn by the widget, given the key to cache the commands with and the number of viewports drawn before.
+	Widget(LayoutId, DrawKey, usize),
+	/// The widget is not drawn, the commands cached for it are used instead.
+	Reused(LayoutId),
 }
 
 impl Painter {
@@ -78,6 +105,15 @@ impl Painter {
 		}
 	}
 
+	/// Get the localizer used to format the texts of the built-in widgets.
+	pub fn localizer(&self) -> &dyn Localizer {
+		self.localizer.as_deref().unwrap_or(&DefaultLocalizer)
+	}
+
+	pub(crate) fn set_localizer(&mut self, localizer: Arc<dyn Localizer>) {
+		self.localizer = Some(localizer);
+	}
+
 	/// Get relatvie to position.
 	pub fn releative_to(&self) -> Vec2 {
 		self.releative_to
@@ -98,6 +134,41 @@ impl Painter {
 		self.blend_mode = BlendMode::default();
 	}
 
+	/// Get how much the widget being drawn is hovered and pressed.
+	/// 
+	/// Only tracked for the widgets returning `true` from [`crate::widgets::Widget::tracks_interaction`],
+	/// idle for the others.
+	pub fn interaction(&self) -> Interaction {
+		self.interaction
+	}
+
+	/// Set whether the shapes drawn next react to [`Self::interaction`] on the gpu,
+	/// brightening on hover and darkening on press.
+	/// 
+	/// The factors are packed into the draw commands, so a hover glow fades in without redrawing the shapes differently.
+	/// Reset to `false` before each widget is drawn.
+	pub fn set_interaction_effect(&mut self, enabled: bool) {
+		self.interaction_effect = enabled;
+	}
+
+	pub(crate) fn set_interaction(&mut self, interaction: Interaction) {
+		self.interaction = interaction;
+		self.interaction_effect = false;
+	}
+
+	/// Get the opacity applied to the shapes drawn next, see [`Self::set_opacity`].
+	pub fn opacity(&self) -> f32 {
+		1.0 - self.transparency
+	}
+
+	/// Set the opacity applied to the shapes drawn next, multiplied into the alpha of their fill mode.
+	/// 
+	/// The layout sets it before each widget is drawn, e.g. while crossfading, see [`crate::layout::Layout::crossfade_widget`].
+	/// Texture fills are not faded.
+	pub fn set_opacity(&mut self, opacity: f32) {
+		self.transparency = 1.0 - opacity.clamp(0.0, 1.0);
+	}
+
 	/// Reset the fill mode to the default mode.
 	pub fn reset_fill_mode(&mut self) {
 		self.fill_mode = FillMode::default();
@@ -169,11 +240,15 @@ impl Painter {
 		let shape = shape.into().move_by(self.releative_to);
 		let mut fill = self.fill_mode.clone();
 		fill.move_by(self.releative_to);
+		if self.transparency > 0.0 {
+			fill.mul_alpha(self.opacity());
+		}
 		self.shapes.push(ShapeToDraw {
 			shape: shape.transform(self.transform),
 			fill_mode: fill,
 			blend_mode: self.blend_mode,
 			clip_rect: self.clip_rect,
+			interaction: if self.interaction_effect { self.interaction } else { Interaction::default() },
 		});
 	}
 
@@ -181,6 +256,9 @@ impl Painter {
 	pub fn draw_shape_detailed(&mut self, shape: ShapeToDraw) {
 		let mut fill_mode = shape.fill_mode;
 		fill_mode.move_by(self.releative_to);
+		if self.transparency > 0.0 {
+			fill_mode.mul_alpha(self.opacity());
+		}
 
 		let shape = ShapeToDraw {
 			shape: shape.shape.move_by(self.releative_to).transform(self.transform),
@@ -223,6 +301,39 @@ impl Painter {
 		self.draw_shape(shape);
 	}
 
+	/// Draw an arc of a stroked circle, starting at `start` and sweeping clockwise by `sweep`, both in radians.
+	/// 
+	/// The angle `0.0` points to the right.
+	pub fn draw_arc(&mut self, center: impl Into<Vec2>, radius: f32, width: f32, start: f32, sweep: f32) {
+		use std::f32::consts::TAU;
+
+		let center = center.into();
+		let sweep = sweep.min(TAU);
+		if sweep <= 0.0 {
+			return;
+		}
+		if sweep >= TAU {
+			self.draw_stroked_circle(center, radius, width);
+			return;
+		}
+		// a fan of triangles reaching past the ring cuts the arc out of it.
+		let segments = (sweep / TAU * 32.0).ceil().max(1.0) as usize;
+		let reach = (radius + width) * 1.2;
+		let point = |index: usize| {
+			let angle = start + sweep * index as f32 / segments as f32;
+			center + Vec2::new(angle.cos(), angle.sin()) * reach
+		};
+		let mut fan = Shape::from(BasicShapeData::Triangle(center, point(0), point(1)));
+		for index in 1..segments {
+			fan |= BasicShapeData::Triangle(center, point(index), point(index + 1));
+		}
+		let ring = BasicShape {
+			stroke: Some(width),
+			..BasicShape::from(BasicShapeData::Circle(center, radius))
+		};
+		self.draw_shape(fan & ring);
+	}
+
 	/// Draw a triangle.
 	pub fn draw_triangle(&mut self, a: impl Into<Vec2>, b: impl Into<Vec2>, c: impl Into<Vec2>) {
 		self.draw_shape(BasicShapeData::Triangle(a.into(), b.into(), c.into()));
@@ -276,6 +387,32 @@ impl Painter {
 		self.draw_shape(BasicShapeData::SDFTexture(rect.lt(), rect.rb(), texture_id));
 	}
 
+	/// Draw the content of a viewport, see [`crate::render::viewport`].
+	/// 
+	/// The viewport is rendered into a texture of the size of the rect in physical pixels before the frame is drawn.
+	pub fn draw_viewport(&mut self, rect: impl Into<Rect>, viewport: ViewportId, roundings: impl Into<Vec4>) {
+		let rect = rect.into();
+		let [width, height] = viewport_texture_size(rect.size(), self.scale_factor);
+		self.viewports.push((viewport, [width, height]));
+		let fill_mode = std::mem::replace(&mut self.fill_mode, FillMode::Texture(viewport, rect.lt(), rect.rb(), Vec2::ZERO, Vec2::new(width as f32, height as f32)));
+		self.draw_rect(rect, roundings);
+		self.fill_mode = fill_mode;
+	}
+
+	/// Draw a texture sliced into nine parts, used for skinnable panels and buttons.
+	/// 
+	/// `source` is the part of the texture to draw in texture pixels,
+	/// `insets` are the left, top, right and bottom borders of the slices in texture pixels.
+	/// The corners are drawn at their size, the edges stretch along the rect and the center fills the rest.
+	/// If the rect is smaller than the corners, they shrink to fit.
+	pub fn draw_nine_patch(&mut self, rect: impl Into<Rect>, texture_id: u32, source: impl Into<Rect>, insets: impl Into<Vec4>, roundings: impl Into<Vec4>) {
+		let rect = rect.into();
+		let source = source.into();
+		let fill_mode = std::mem::replace(&mut self.fill_mode, FillMode::NinePatch(texture_id, rect.lt(), rect.rb(), source.lt(), source.rb(), insets.into()));
+		self.draw_rect(rect, roundings);
+		self.fill_mode = fill_mode;
+	}
+
 	/// Draw a cubic bezier curve.
 	/// 
 	/// Note: We're using quadratic bezier curve to approximate the cubic bezier curve.
@@ -360,54 +497,81 @@ impl Painter {
 		font_size: f32, 
 		text: impl Into<String>,
 	) -> bool {
-		let font_pool = if let Ok(inner) = self.font_pool.lock() {
-			inner
+		if let Some(layout) = self.layout_text(font_id, font_size, text) {
+			self.draw_text_layout(pos, &layout);
+			true
 		}else {
-			return false;
-		};
+			false
+		}
+	}
+
+	/// Lay out a text, giving the position of every glyph and the box of every line.
+	/// 
+	/// Draw the result with [`Self::draw_text_layout`], see [`TextLayout`] for details.
+	/// 
+	/// Returns None if the font is not found or a character has no glyph.
+	pub fn layout_text(
+		&self, 
+		font_id: FontId, 
+		font_size: f32, 
+		text: impl Into<String>,
+	) -> Option<TextLayout> {
+		let mut font_pool = self.font_pool.lock().ok()?;
 		let text = text.into();
-		let mut pos = pos.into();
-		let mut x = 0.0;
-		let factor = font_size / EM * if let Some(factor) = font_pool.advance_factor(font_id) {
-			factor
-		}else {
-			return false;
-		};
-		let line_height = if let Some(inner) = font_pool.line_height(font_id) {
-			inner
-		}else {
-			return false;
+		let factor = font_size / EM * font_pool.advance_factor(font_id)?;
+		let line_height = font_pool.line_height(font_id)? * factor;
+		let size = font_pool.caculate_text_size(font_id, text.as_str(), font_size, false)?;
+		let mut glyphs = vec!();
+		let mut lines = vec!();
+		let mut pen = Vec2::ZERO;
+		let mut line = 0;
+		let mut line_start = (0, 0);
+		let mut push_line = |glyphs: &Vec<PositionedGlyph>, line_start: &mut (usize, usize), end: usize, pen: Vec2| {
+			lines.push(TextLine {
+				glyphs: line_start.0..glyphs.len(),
+				bytes: line_start.1..end,
+				rect: Rect::from_lt_size(Vec2::y(pen.y), Vec2::new(pen.x, line_height)),
+			});
+			*line_start = (glyphs.len(), end + 1);
 		};
-		// let ancestor = if let Some(inner) = font_pool.anscender(font_id) {
-		// 	0.0
-		// }else {
-		// 	return false;
-		// };
-		drop(font_pool);
-		for chr in text.chars() {
-			let mut font_pool = if let Ok(inner) = self.font_pool.lock() {
-				inner
-			}else {
-				return false;
-			};
+		for (byte, chr) in text.char_indices() {
 			if chr == '\n' {
-				x = 0.0;
-				pos.y += line_height * factor;
+				push_line(&glyphs, &mut line_start, byte, pen);
+				line += 1;
+				pen = Vec2::y(pen.y + line_height);
 				continue;
 			}
-			
-			let glyph =  if let Some(inner) = font_pool.get_glyph(font_id, chr) {
-				inner
-			}else {
-				return false;
-			};
-			let chr_pos = pos + Vec2::new(x, 0.0) + Vec2::x(glyph.bearing.x * factor);
-			drop(font_pool);
-			self.draw_shape(BasicShapeData::Text(chr_pos, font_id, font_size, chr));
-			x += glyph.advance.x * factor; 
+			let glyph = font_pool.get_glyph(font_id, chr)?;
+			glyphs.push(PositionedGlyph {
+				chr,
+				cluster: byte..byte + chr.len_utf8(),
+				line,
+				pos: pen,
+				bearing: glyph.bearing.x * factor,
+				advance: glyph.advance.x * factor,
+			});
+			pen.x += glyph.advance.x * factor;
 		}
+		push_line(&glyphs, &mut line_start, text.len(), pen);
+
+		Some(TextLayout {
+			text,
+			font_id,
+			font_size,
+			glyphs,
+			lines,
+			size,
+		})
+	}
 
-		true
+	/// Draw a text laid out by [`Self::layout_text`], without looking up the glyphs again.
+	/// 
+	/// Make sure to set the font before calling this function.
+	pub fn draw_text_layout(&mut self, pos: impl Into<Vec2>, layout: &TextLayout) {
+		let pos = pos.into();
+		for glyph in &layout.glyphs {
+			self.draw_shape(BasicShapeData::Text(pos + glyph.draw_pos(), layout.font_id, layout.font_size, glyph.chr));
+		}
 	}
 
 	/// Get size of a text.
@@ -455,10 +619,35 @@ impl Painter {
 		}
 	}
 
+	/// Get the font of the role if it is registered, otherwise the fallback font.
+	/// 
+	/// See [`FontRole`] for details.
+	pub fn resolve_font(&self, role: Option<FontRole>, fallback: FontId) -> FontId {
+		if let Ok(inner) = self.font_pool.lock() {
+			inner.resolve(role, fallback)
+		}else {
+			fallback
+		}
+	}
+
 	pub(crate) fn set_scale_factor(&mut self, factor: f32) {
 		self.scale_factor = factor;
 	}
 
+	pub(crate) fn push_supersample_region(&mut self, area: Rect, factor: f32) {
+		if factor > 1.0 && self.supersample_regions.len() < MAX_SUPERSAMPLE_REGIONS {
+			self.supersample_regions.push((area, factor.min(MAX_SUPERSAMPLE_FACTOR)));
+		}
+	}
+
+	pub(crate) fn take_supersample_regions(&mut self) -> Vec<(Rect, f32)> {
+		std::mem::take(&mut self.supersample_regions)
+	}
+
+	pub(crate) fn take_viewports(&mut self) -> Vec<(ViewportId, [u32; 2])> {
+		std::mem::take(&mut self.viewports)
+	}
+
 	pub(crate) fn set_relative_to(&mut self, pos: Vec2) {
 		self.releative_to = pos;
 	}
@@ -468,32 +657,93 @@ impl Painter {
 		self.clip_rect = rect;
 	}
 
-	pub(crate) fn parse(mut self, font_render: &FontRender, dirty_rect: Rect) -> (Vec<DrawCommandGpu>, u32) {
-		use rayon::prelude::*;
+	/// Start the shapes drawn by the widget, the commands compiled from them are cached with the key.
+	pub(crate) fn begin_segment(&mut self, id: LayoutId, key: DrawKey) {
+		self.close_segment();
+		self.segments.push((self.shapes.len(), Segment::Widget(id, key, self.viewports.len())));
+	}
 
-		self.shapes.reverse();
+	/// Use the commands cached for the widget instead of drawing it.
+	pub(crate) fn reuse_segment(&mut self, id: LayoutId) {
+		self.close_segment();
+		self.segments.push((self.shapes.len(), Segment::Reused(id)));
+		self.segments.push((self.shapes.len(), Segment::Uncached));
+	}
+
+	/// End the shapes drawn by the widget, the shapes drawn next are not cached.
+	pub(crate) fn end_segment(&mut self) {
+		self.close_segment();
+		self.segments.push((self.shapes.len(), Segment::Uncached));
+	}
+
+	/// Stop caching the last widget if it drew a viewport, as a viewport is only rendered if drawn in the frame.
+	fn close_segment(&mut self) {
+		let viewports = self.viewports.len();
+		if let Some((_, segment)) = self.segments.last_mut() {
+			if matches!(segment, Segment::Widget(_, _, before) if *before != viewports) {
+				*segment = Segment::Uncached;
+			}
+		}
+	}
 
-		// let mut out = vec!();
-		// let mut expect_stack_size = 0;
-		// let mut current_transform = Transform2D::IDENTITY;
-		// let mut current_blend_mode = BlendMode::default();
+	pub(crate) fn parse(mut self, glyphs: &GlyphMap, damage: &Damage, cache: &mut CommandCache) -> (Vec<DrawCommandGpu>, u32) {
+		use rayon::prelude::*;
 
+		self.close_segment();
+		cache.begin_frame();
 		let shapes = std::mem::take(&mut self.shapes);
+		#[cfg(feature = "bench")]
+		let shape_count = shapes.len();
+
+		let mut segments = std::mem::take(&mut self.segments);
+		if segments.first().is_none_or(|(start, _)| *start > 0) {
+			segments.insert(0, (0, Segment::Uncached));
+		}
+		let mut owners = Vec::with_capacity(shapes.len());
+		for (index, (start, _)) in segments.iter().enumerate() {
+			let end = segments.get(index + 1).map_or(shapes.len(), |(next, _)| *next);
+			owners.extend(std::iter::repeat_n(index, end - start));
+		}
 
-		let out = shapes.into_par_iter().filter_map(|shape| {
-			if !shape.is_visible_in_rect(dirty_rect) {
+		// the shapes of the widgets are all compiled to be cached, the others only if they are in the damage.
+		let compiled = shapes.into_par_iter().zip(owners.par_iter()).map(|(shape, owner)| {
+			let cached = matches!(segments[*owner].1, Segment::Widget(..));
+			if !cached && !damage.rects().iter().any(|rect| shape.is_visible_in_rect(*rect)) {
 				return None;
 			}
-			Some(shape.parse(font_render))
+			CompiledShape::new(shape, glyphs)
 		}).collect::<Vec<_>>();
 
-		
+		let mut runs = segments.iter().map(|_| vec!()).collect::<Vec<_>>();
+		for (shape, owner) in compiled.into_iter().zip(owners) {
+			if let Some(shape) = shape {
+				runs[owner].push(shape);
+			}
+		}
+		let runs = segments.iter().zip(runs).map(|((_, segment), run)| match segment {
+			Segment::Uncached => Arc::new(run),
+			Segment::Widget(id, key, _) => {
+				let run = Arc::new(run);
+				cache.insert(*id, *key, run.clone());
+				run
+			},
+			Segment::Reused(id) => cache.reuse(*id).unwrap_or_default(),
+		}).collect::<Vec<_>>();
+		cache.end_frame();
+
+		// the shapes drawn last are at the front of the commands.
 		let mut expect_stack_size = 0;
-		for (_, size) in out.iter() {
-			expect_stack_size = (*size).max(expect_stack_size);
+		let mut commands = vec!();
+		for shape in runs.iter().rev().flat_map(|run| run.iter().rev()) {
+			if damage.rects().iter().any(|rect| shape.is_visible_in_rect(*rect)) {
+				expect_stack_size = shape.stack_len().max(expect_stack_size);
+				commands.extend_from_slice(shape.commands());
+			}
 		}
+		#[cfg(feature = "bench")]
+		crate::bench::count_parse(shape_count, commands.len());
 
-		(out.into_iter().flat_map(|(inner, _)| inner).collect(), expect_stack_size)
+		(commands, expect_stack_size)
 	}
 }
 
@@ -551,7 +801,7 @@ fn hanle_binary_op(
 	lhs: ShapeOrStack, 
 	rhs: ShapeOrStack,
 	current_transform: &mut Transform2D,
-	font_render: &FontRender,
+	glyphs: &GlyphMap,
 	stack_index: &mut u32,
 	// clip_rect: Rect
 ) -> Option<(Vec<DrawCommandGpu>, ShapeOrStack)> {
@@ -575,7 +825,7 @@ fn hanle_binary_op(
 				*current_transform = shape.transform;
 				out.push(get_transform(shape.transform));
 			}
-			let (command, slots) = shape.data.compile(font_render)?;
+			let (command, slots) = shape.data.compile(glyphs)?;
 			let stroke_width = shape.stroke.unwrap_or(-1.0);
 			out.push(DrawCommandGpu {
 				command: command as u32,
@@ -594,7 +844,7 @@ fn hanle_binary_op(
 				*current_transform = shape2.transform;
 				out.push(get_transform(shape2.transform));
 			}
-			let (command, slots) = shape2.data.compile(font_render)?;
+			let (command, slots) = shape2.data.compile(glyphs)?;
 			let stroke_width = shape2.stroke.unwrap_or(-1.0);
 			out.push(DrawCommandGpu {
 				command: command as u32,
@@ -613,7 +863,7 @@ fn hanle_binary_op(
 		},
 		(ShapeOrStack::Stack(index), ShapeOrStack::Shape(shape)) | 
 		(ShapeOrStack::Shape(shape), ShapeOrStack::Stack(index)) => {
-			let (command, slots) = shape.data.compile(font_render)?;
+			let (command, slots) = shape.data.compile(glyphs)?;
 			let stroke_width = shape.stroke.unwrap_or(-1.0);
 			out.push(DrawCommandGpu {
 				command: command as u32,
@@ -662,7 +912,7 @@ fn hanle_binary_op(
 }
 
 impl ShapeToDraw {
-	pub(crate) fn parse(self, font_render: &FontRender) -> (Vec<DrawCommandGpu>, u32) {
+	pub(crate) fn parse(self, glyphs: &GlyphMap) -> (Vec<DrawCommandGpu>, u32) {
 		// let clip_rect = self.clip_rect;
 		
 		let mut current_transform = Transform2D::IDENTITY; 
@@ -693,7 +943,7 @@ impl ShapeToDraw {
 									current_transform = shape.transform;
 									out.push(get_transform(shape.transform));
 								}
-								let (command, slots) = shape.data.compile(font_render).unwrap();
+								let (command, slots) = shape.data.compile(glyphs).unwrap();
 								let stroke_width = shape.stroke.unwrap_or(-1.0);
 								out.push(DrawCommandGpu {
 									command: command as u32,
@@ -725,7 +975,7 @@ impl ShapeToDraw {
 						lhs, 
 						rhs, 
 						&mut current_transform, 
-						font_render, 
+						glyphs, 
 						&mut used_stack_amount, 
 						// clip_rect
 					) {
@@ -746,7 +996,7 @@ impl ShapeToDraw {
 						current_transform = shape.transform;
 						out.push(get_transform(shape.transform));
 					}
-					let (command, slots) = if let Some(inner) = shape.data.compile(font_render) {
+					let (command, slots) = if let Some(inner) = shape.data.compile(glyphs) {
 						inner
 					}else {
 						return (vec!(), 0);
@@ -791,7 +1041,7 @@ impl ShapeToDraw {
 			smooth_parameter: 0.0,
 			lhs: 1,
 			parameter: 0.0,
-			__padding: Default::default(),
+			interaction: 0,
 			// ..Default::default()
 		});
 
@@ -849,6 +1099,7 @@ impl ShapeToDraw {
 		out.push(DrawCommandGpu {
 			command: fill as u32,
 			slots,
+			interaction: self.interaction.pack(),
 			stroke_width: -1.0,
 			operation: OperationGpu::None as u32,
 			// smooth_function: 0,
@@ -899,6 +1150,16 @@ impl FillMode {
 					[0.0, 0.0, 0.0, 0.0],
 				])
 			},
+			Self::Stripes(a, b, start, end) => {
+				let a = a.premultiply();
+				let b = b.premultiply();
+				(CommandGpu::FillStripes, [
+					[a.r, a.g, a.b, a.a],
+					[b.r, b.g, b.b, b.a],
+					[start.x, start.y, end.x, end.y],
+					[0.0, 0.0, 0.0, 0.0],
+				])
+			},
 			Self::Texture(texture_id, lt, rb, tlt, trb)=> {
 				(CommandGpu::FillTexture, [
 					[lt.x, lt.y, rb.x, rb.y],
@@ -907,12 +1168,20 @@ impl FillMode {
 					[0.0, 0.0, 0.0, 0.0]
 				])
 			},
+			Self::NinePatch(texture_id, lt, rb, tlt, trb, insets) => {
+				(CommandGpu::FillNinePatch, [
+					[lt.x, lt.y, rb.x, rb.y],
+					[tlt.x, tlt.y, trb.x, trb.y],
+					[texture_id as f32, 0.0, 0.0, 0.0],
+					[insets.r, insets.g, insets.b, insets.a],
+				])
+			},
 		}
 	}
 }
 
 impl BasicShapeData {
-	fn compile(self, font_render: &FontRender) -> Option<(CommandGpu, [[f32; 4]; 4])> {
+	fn compile(self, glyphs: &GlyphMap) -> Option<(CommandGpu, [[f32; 4]; 4])> {
 		Some(match self {
 			Self::Circle(center, radius) => {
 				(CommandGpu::DrawCircle, [
@@ -963,7 +1232,7 @@ impl BasicShapeData {
 				])
 			},
 			Self::Text(pos, font_id, font_size, chr) => {
-				let char_id = *font_render.char_texture_map.get(&(chr, font_id))?;
+				let char_id = *glyphs.get(&(chr, font_id))?;
 				(CommandGpu::DrawChar, [
 					[pos.x, pos.y, font_size, char_id as f32],
 					[0.0, 0.0, 0.0, 0.0],
@@ -4,4 +4,15 @@ pub use crate::render::commands::*;
 pub use crate::render::font::*;
 pub use crate::render::shape::*;
 pub use crate::render::painter::*;
-pub use crate::render::texture::*;
\ No newline at end of file
+pub use crate::render::texture::*;
+pub use crate::render::debug::*;
+pub use crate::render::damage::*;
+pub use crate::render::snapshot::*;
+pub use crate::render::text_layout::*;
+pub use crate::render::viewport::{ViewportId, ViewportRenderer, ViewportInput};
+#[cfg(feature = "capture")]
+pub use crate::render::capture::*;
+#[cfg(feature = "render-graph")]
+pub use crate::render::graph::*;
+#[cfg(feature = "image-loader")]
+pub use crate::render::image_loader::{ImageHandle, ImageState};
\ No newline at end of file
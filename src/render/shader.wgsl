@@ -7,6 +7,8 @@ struct DrawCommand {
 	operation: u32,
 	smooth_parameter: f32,
 	lhs: u32,
+	// `Interaction` in Rust, hover and press factors packed as two unorm16.
+	interaction: u32,
 }
 
 // struct DrawCommand {
@@ -44,6 +46,11 @@ struct Uniforms {
 	scale_factor: f32,
 	stack_len: u32,
 	command_len: u32,
+	supersample_len: u32,
+	// left, top, right, bottom in logical pixels.
+	supersample_regions: array<vec4<f32>, 16>,
+	// factors packed by four.
+	supersample_factors: array<vec4<f32>, 4>,
 }
 
 const EDGE_WIDTH: f32 = 1.0;
@@ -306,6 +313,21 @@ fn linear_gradient(
 	return mix(inner_color, outer_color, clamp(abs(t), 0.0, 1.0));
 }
 
+fn stripes(
+	pos: vec2<f32>,
+	start: vec2<f32>,
+	end: vec2<f32>,
+	a: vec4<f32>,
+	b: vec4<f32>,
+) -> vec4f {
+	let period = length(end - start);
+	let t = fract(dot(pos - start, end - start) / (period * period));
+	// distance to the nearest edge between the stripes in pixels, for anti-aliasing.
+	let edge = min(min(t, abs(t - 0.5)), 1.0 - t) * period;
+	let factor = clamp(edge / EDGE_WIDTH, 0.0, 1.0) * 0.5;
+	return select(mix(a, b, 0.5 - factor), mix(a, b, 0.5 + factor), t >= 0.5);
+}
+
 fn texture_fill(
 	pos: vec2<f32>,
 	texture_id: u32,
@@ -321,6 +343,45 @@ fn texture_fill(
 	return textureSample(texture_array, sampler_texture, uv, texture_id);
 }
 
+const HOVER_GLOW: f32 = 0.075;
+const PRESS_DIM: f32 = 0.15;
+
+// brighten the premultiplied color by the hover factor and darken it by the press factor.
+fn apply_interaction(color: vec4f, interaction: u32) -> vec4f {
+	if interaction == 0u {
+		return color;
+	}
+	let factors = unpack2x16unorm(interaction);
+	let rgb = (color.xyz + factors.x * HOVER_GLOW * color.w) * (1.0 - factors.y * PRESS_DIM);
+	return vec4f(rgb, color.w);
+}
+
+fn nine_patch_fill(
+	pos: vec2<f32>,
+	texture_id: u32,
+	lt: vec2<f32>,
+	rb: vec2<f32>,
+	uv_lt: vec2<f32>,
+	uv_rb: vec2<f32>,
+	// left, top, right, bottom in texture pixels.
+	insets: vec4<f32>,
+) -> vec4f {
+	let local = pos - lt;
+	let size = rb - lt;
+	let source_size = uv_rb - uv_lt;
+	// shrink the corners when the shape is smaller than them.
+	let scale = min(vec2f(1.0), size / max(insets.xy + insets.zw, vec2f(EPSILON)));
+	let start = insets.xy * scale;
+	let end = size - insets.zw * scale;
+	let center_source = max(source_size - insets.xy - insets.zw, vec2f(0.0));
+	let in_start = local / max(scale, vec2f(EPSILON));
+	let in_center = (local - start) / max(end - start, vec2f(EPSILON)) * center_source + insets.xy;
+	let in_end = (local - end) / max(scale, vec2f(EPSILON)) + source_size - insets.zw;
+	let texture_pos = select(select(in_center, in_end, local > end), in_start, local < start);
+	let uv = (uv_lt + texture_pos) / TEXTURE_SIZE;
+	return textureSample(texture_array, sampler_texture, uv, texture_id);
+}
+
 // Simulating enum, therefore we use UpperCamelCase rather than SCREAMING_SNAKE_CASE.
 // Here is `CommandGpu` in Rust, see more details in `src/render/command.rs`.
 const CommandNone: u32 = 0u;
@@ -338,6 +399,8 @@ const TextureFill: u32 = 11u;
 const SetTransform: u32 = 12u;
 const SetBlendMode: u32 = 13u;
 const Load: u32 = 14u;
+const NinePatchFill: u32 = 15u;
+const StripesFill: u32 = 16u;
 
 // here is `BlendMode` in Rust, see more details in `src/render/command.rs`.
 const MixReplace: u32 = 0u;
@@ -455,10 +518,36 @@ fn mix_color(ori_color: vec4<f32>, new_color: vec4<f32>, blend_mode: u32) -> vec
 // 	return !is_isotropic;
 // }
 
+fn supersample_factor(pos: vec2<f32>) -> f32 {
+	var factor = 1.0;
+	for (var i = 0u; i < uniforms.supersample_len; i += 1u) {
+		let region = uniforms.supersample_regions[i];
+		if pos.x >= region.x && pos.y >= region.y && pos.x < region.z && pos.y < region.w {
+			factor = max(factor, uniforms.supersample_factors[i / 4u][i % 4u]);
+		}
+	}
+	return factor;
+}
+
 @fragment
 fn fs_main(@builtin(position) clip_pos: vec4<f32>) -> @location(0) vec4f {
-	let pos = clip_pos.xy / uniforms.scale_factor;
-	
+	let factor = supersample_factor(clip_pos.xy / uniforms.scale_factor);
+	if factor <= 1.0 {
+		return shade(clip_pos.xy / uniforms.scale_factor);
+	}
+
+	let samples = u32(ceil(factor));
+	var color = vec4f(0.0, 0.0, 0.0, 0.0);
+	for (var x = 0u; x < samples; x += 1u) {
+		for (var y = 0u; y < samples; y += 1u) {
+			let offset = (vec2f(f32(x), f32(y)) + 0.5) / f32(samples) - 0.5;
+			color += shade((clip_pos.xy + offset) / uniforms.scale_factor);
+		}
+	}
+	return color / f32(samples * samples);
+}
+
+fn shade(pos: vec2<f32>) -> vec4f {
 	var current_command_index = 0u;
 	var current_color = vec4f(0.0, 0.0, 0.0, 0.0);
 	var current_blend_mode = AlphaAdd;
@@ -641,7 +730,7 @@ fn fs_main(@builtin(position) clip_pos: vec4<f32>) -> @location(0) vec4f {
 						slots[3][0],
 					);
 					let anti_aliasing = clamp(- stack[1] / EDGE_WIDTH, 0.0, 1.0);
-					let new_color = vec4f(color.xyz, color.w * anti_aliasing);
+					let new_color = apply_interaction(vec4f(color.xyz, color.w * anti_aliasing), draw_commands[current_command_index].interaction);
 					current_color = mix_color(current_color, new_color, current_blend_mode);
 				}
 			}
@@ -669,7 +758,7 @@ fn fs_main(@builtin(position) clip_pos: vec4<f32>) -> @location(0) vec4f {
 					);
 					let color = linear_gradient(p, start_pos, end_pos, start_color, end_color);
 					let anti_aliasing = clamp(- stack[1] / EDGE_WIDTH, 0.0, 1.0);
-					let new_color = vec4f(color.xyz, color.w * anti_aliasing);
+					let new_color = apply_interaction(vec4f(color.xyz, color.w * anti_aliasing), draw_commands[current_command_index].interaction);
 					current_color = mix_color(current_color, new_color, current_blend_mode);
 				}
 			}
@@ -694,7 +783,7 @@ fn fs_main(@builtin(position) clip_pos: vec4<f32>) -> @location(0) vec4f {
 					let radius = slots[2][2];
 					let color = radial_gradient(p, center, radius, start_color, end_color);
 					let anti_aliasing = clamp(- stack[1] / EDGE_WIDTH, 0.0, 1.0);
-					let new_color = vec4f(color.xyz, color.w * anti_aliasing);
+					let new_color = apply_interaction(vec4f(color.xyz, color.w * anti_aliasing), draw_commands[current_command_index].interaction);
 					current_color = mix_color(current_color, new_color, current_blend_mode);
 				}
 			}
@@ -719,7 +808,66 @@ fn fs_main(@builtin(position) clip_pos: vec4<f32>) -> @location(0) vec4f {
 					let texture_id = u32(slots[0][2]);
 					let color = texture_fill(p, texture_id, lt, rb, tlt, trb);
 					let anti_aliasing = clamp(- stack[1] / EDGE_WIDTH, 0.0, 1.0);
-					let new_color = vec4f(color.xyz, color.w * anti_aliasing);
+					let new_color = apply_interaction(vec4f(color.xyz, color.w * anti_aliasing), draw_commands[current_command_index].interaction);
+					current_color = mix_color(current_color, new_color, current_blend_mode);
+				}
+			}
+			case StripesFill: {
+				if stack[1] < 0.0 {
+					let a = vec4f(
+						slots[0][0],
+						slots[1][0],
+						slots[2][0],
+						slots[3][0],
+					);
+					let b = vec4f(
+						slots[0][1],
+						slots[1][1],
+						slots[2][1],
+						slots[3][1],
+					);
+					let start_pos = vec2f(
+						slots[0][2],
+						slots[1][2],
+					);
+					let end_pos = vec2f(
+						slots[2][2],
+						slots[3][2],
+					);
+					let color = stripes(p, start_pos, end_pos, a, b);
+					let anti_aliasing = clamp(- stack[1] / EDGE_WIDTH, 0.0, 1.0);
+					let new_color = apply_interaction(vec4f(color.xyz, color.w * anti_aliasing), draw_commands[current_command_index].interaction);
+					current_color = mix_color(current_color, new_color, current_blend_mode);
+				}
+			}
+			case NinePatchFill: {
+				if stack[1] < 0.0 {
+					let lt = vec2f(
+						slots[0][0], 
+						slots[1][0],
+					);
+					let rb = vec2f(
+						slots[2][0], 
+						slots[3][0],
+					);
+					let tlt = vec2f(
+						slots[0][1], 
+						slots[1][1],
+					);
+					let trb = vec2f(
+						slots[2][1], 
+						slots[3][1],
+					);
+					let texture_id = u32(slots[0][2]);
+					let insets = vec4f(
+						slots[0][3],
+						slots[1][3],
+						slots[2][3],
+						slots[3][3],
+					);
+					let color = nine_patch_fill(p, texture_id, lt, rb, tlt, trb, insets);
+					let anti_aliasing = clamp(- stack[1] / EDGE_WIDTH, 0.0, 1.0);
+					let new_color = apply_interaction(vec4f(color.xyz, color.w * anti_aliasing), draw_commands[current_command_index].interaction);
 					current_color = mix_color(current_color, new_color, current_blend_mode);
 				}
 			}
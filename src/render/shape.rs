@@ -9,7 +9,7 @@ use crate::{math::{color::{Color, Vec4}, transform2d::Transform2D, vec2::Vec2},
 /// Note: there's no precedence defined for the operators,
 /// in other words, the order of the caculation will alway be from left to right.
 #[derive(Debug, Clone, Copy, PartialEq)]
-#[derive(serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Operator {
 	/// Get the intersection of two shapes.
 	And,
@@ -31,7 +31,7 @@ pub enum Operator {
 
 /// A basic shape defined by its data, fill mode, and blend mode.
 #[derive(Debug, PartialEq, Clone)]
-#[derive(serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BasicShape {
 	/// The data of the basic shape.
 	pub data: BasicShapeData,
@@ -150,7 +150,7 @@ impl BasicShape {
 
 /// The fill mode of the basic shape.
 #[derive(Debug, Clone, PartialEq)]
-#[derive(serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FillMode {
 	/// Fill the shape with the given color.
 	Color(Color),
@@ -158,6 +158,12 @@ pub enum FillMode {
 	/// 
 	/// Given texture id, top-left corner, right-bottom corner, and the texture left-top corner and right-bottom corner.
 	Texture(u32, Vec2, Vec2, Vec2, Vec2),
+	/// Fill the shape with a texture sliced into nine parts, see [`crate::render::painter::Painter::draw_nine_patch`].
+	/// 
+	/// Given texture id, top-left corner, right-bottom corner, the texture left-top corner and right-bottom corner,
+	/// and the left, top, right and bottom insets in texture pixels.
+	/// The corners keep their size, the edges stretch along them and the center stretches both ways.
+	NinePatch(u32, Vec2, Vec2, Vec2, Vec2, Vec4),
 	/// Fill the shape with linear gradient.
 	/// 
 	/// Given start and end color, and the start and end position of the gradient.
@@ -166,16 +172,43 @@ pub enum FillMode {
 	/// 
 	/// Given start and end color, center position, and the radiusof the gradient.
 	RadialGradient(Color, Color, Vec2, f32),
+	/// Fill the shape with repeating stripes of two colors.
+	/// 
+	/// Given the two colors, and the start and end position of one period, each color taking half of it.
+	Stripes(Color, Color, Vec2, Vec2),
+}
+
+/// A ready-made fill for common UI effects, see [`FillMode::preset`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FillPreset {
+	/// A translucent light panel, lighter at the top.
+	/// 
+	/// The renderer has no blur layer, so the content behind is not blurred, only veiled.
+	FrostedGlass,
+	/// The given color lit subtly from the top.
+	TopLight(Color),
+	/// A highlight band sweeping over the base color, for loading placeholders.
+	/// 
+	/// Given the base color, the highlight color and the progress of the sweep, wrapping every 1.0.
+	Shimmer(Color, Color, f32),
+	/// Diagonal stripes of the two colors, such as yellow and black for warnings.
+	Stripes(Color, Color),
 }
 
+/// The width of a stripe of [`FillPreset::Stripes`].
+pub static PRESET_STRIPE_WIDTH: f32 = 8.0;
+/// How much lighter [`FillPreset::TopLight`] is at the top.
+pub static PRESET_TOP_LIGHT: f32 = 0.06;
+
 impl FillMode {
 	/// Check if the fill mode is invisible.
 	pub fn is_invisible(&self) -> bool {
 		match self {
 			FillMode::Color(color) => color.a <= 0.0,
-			FillMode::Texture(_, _, _, _, _) => false,
+			FillMode::Texture(_, _, _, _, _) | FillMode::NinePatch(_, _, _, _, _, _) => false,
 			FillMode::LinearGradient(from, to, _, _) => from.a <= 0.0 && to.a <= 0.0,
 			FillMode::RadialGradient(from, to, _, _) => from.a <= 0.0 && to.a <= 0.0,
+			FillMode::Stripes(a, b, _, _) => a.a <= 0.0 && b.a <= 0.0,
 		}
 	}
 
@@ -191,12 +224,12 @@ impl FillMode {
 			FillMode::Color(color) => {
 				*color += bright_factor * Color::WHITE;
 			},
-			FillMode::Texture(_, _, _, _, _) => {},
+			FillMode::Texture(_, _, _, _, _) | FillMode::NinePatch(_, _, _, _, _, _) => {},
 			FillMode::LinearGradient(from, to, _, _) => {
 				*from += bright_factor * Color::WHITE;
 				*to += bright_factor * Color::WHITE;
 			},
-			FillMode::RadialGradient(from, to, _, _) => {
+			FillMode::RadialGradient(from, to, _, _) | FillMode::Stripes(from, to, _, _) => {
 				*from += bright_factor * Color::WHITE;
 				*to += bright_factor * Color::WHITE;
 			},
@@ -215,12 +248,12 @@ impl FillMode {
 			FillMode::Color(color) => {
 				color.a *= alpha;
 			},
-			FillMode::Texture(_, _, _, _, _) => {},
+			FillMode::Texture(_, _, _, _, _) | FillMode::NinePatch(_, _, _, _, _, _) => {},
 			FillMode::LinearGradient(from, to, _, _) => {
 				from.a *= alpha;
 				to.a *= alpha;
 			},
-			FillMode::RadialGradient(from, to, _, _) => {
+			FillMode::RadialGradient(from, to, _, _) | FillMode::Stripes(from, to, _, _) => {
 				from.a *= alpha;
 				to.a *= alpha;
 			},
@@ -230,11 +263,11 @@ impl FillMode {
 	pub(crate) fn move_by(&mut self, offset: impl Into<Vec2>) {
 		let offset = offset.into();
 		match self {
-			FillMode::Texture(_, top_left, right_bottom, _, _) => {
+			FillMode::Texture(_, top_left, right_bottom, _, _) | FillMode::NinePatch(_, top_left, right_bottom, _, _, _) => {
 				*top_left += offset;
 				*right_bottom += offset;
 			},
-			FillMode::LinearGradient(_, _, start, end) => {
+			FillMode::LinearGradient(_, _, start, end) | FillMode::Stripes(_, _, start, end) => {
 				*start += offset;
 				*end += offset;
 			},
@@ -246,6 +279,36 @@ impl FillMode {
 	}
 }
 
+impl FillMode {
+	/// Create the fill of a preset for the rect, see [`FillPreset`].
+	/// 
+	/// The rect is relative to the painter like the shapes, so the fill can be set right before drawing them.
+	pub fn preset(preset: FillPreset, rect: Rect) -> Self {
+		match preset {
+			FillPreset::FrostedGlass => FillMode::LinearGradient(
+				Color::new(1.0, 1.0, 1.0, 0.18), 
+				Color::new(1.0, 1.0, 1.0, 0.08), 
+				rect.lt(), 
+				rect.lb(),
+			),
+			FillPreset::TopLight(color) => FillMode::LinearGradient(
+				color + Color::new(PRESET_TOP_LIGHT, PRESET_TOP_LIGHT, PRESET_TOP_LIGHT, 0.0), 
+				color, 
+				rect.lt(), 
+				rect.lb(),
+			),
+			FillPreset::Shimmer(base, highlight, progress) => {
+				// the gradient mirrors around its start, so the highlight fades out on both sides.
+				let band = (rect.w * 0.4).max(rect.h);
+				let center = rect.x - band + (rect.w + band * 2.0) * progress.rem_euclid(1.0);
+				let start = Vec2::new(center, rect.y);
+				FillMode::LinearGradient(highlight, base, start, start + Vec2::new(band, 0.0))
+			},
+			FillPreset::Stripes(a, b) => FillMode::Stripes(a, b, rect.lt(), rect.lt() + Vec2::same(PRESET_STRIPE_WIDTH * std::f32::consts::SQRT_2)),
+		}
+	}
+}
+
 impl<T> From<T> for FillMode
 where T: Into<Color>,
 {
@@ -268,7 +331,7 @@ impl Default for FillMode {
 /// If you need to draw a general cubic bezier curve, you can use combination of `QuadHalfPlane` shape,
 /// which is simple due to sdf based rendering approach.
 #[derive(Debug, PartialEq, Clone)]
-#[derive(serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BasicShapeData {
 	/// A circle defined by center and radius.
 	Circle(Vec2, f32),
@@ -308,7 +371,7 @@ pub enum BasicShapeData {
 /// - `!Shape` to get the complement of a shape.
 /// 
 /// Note: be careful when using `-`, since they have higher precedence than other bitwise operator in rust.
-#[derive(serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone)]
 pub struct Shape(pub Vec<ShapeOrOp>);
 
@@ -536,7 +599,7 @@ impl Not for Shape {
 }
 
 /// A shape or operator used in the complex shape.
-#[derive(serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone)]
 pub enum ShapeOrOp {
 	/// A basic shape.
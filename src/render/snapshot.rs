@@ -0,0 +1,303 @@
+//! Snapshot testing for widget rendering without a GPU.
+//!
+//! Draw a widget into a [`Painter::headless`] painter, dump the recorded shapes with [`Painter::debug_dump`]
+//! and compare the dump with a stored one using [`compare_dumps`].
+//!
+//! The dump lists one shape per line followed by its parts indented, numbers are rounded to 3 decimals.
+//! Rects of shapes are given by their corners, while clip rects are given by their left top corner and size:
+//!
+//! ```text
+//! shape blend=AlphaAdd clip=rect(0, 0, 800, 600) fill=rgba(0.165, 0.165, 0.165, 1)
+//!   rect(0, 0, 100, 40) rounding(8, 8, 8, 8)
+//! ```
+
+use std::sync::{Arc, Mutex};
+
+use crate::math::{color::Color, prelude::Transform2D, rect::Rect, vec2::Vec2};
+
+use super::{font::FontPool, painter::{Painter, ShapeToDraw}, shape::{BasicShape, BasicShapeData, FillMode, Operator, ShapeOrOp}};
+
+/// The tolerances used by [`compare_dumps`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DumpTolerance {
+	/// The maximum difference of positions, sizes and other numbers.
+	pub position: f32,
+	/// The maximum difference of color channels, which range from 0.0 to 1.0.
+	pub color: f32,
+}
+
+impl Default for DumpTolerance {
+	fn default() -> Self {
+		Self {
+			position: 0.01,
+			color: 1.0 / 255.0,
+		}
+	}
+}
+
+impl DumpTolerance {
+	/// Require the dumps to be exactly the same.
+	pub const EXACT: Self = Self { position: 0.0, color: 0.0 };
+}
+
+/// The difference found by [`compare_dumps`].
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum DumpMismatch {
+	/// The dumps have a different number of lines, usually a different number of shapes.
+	#[error("expected {expected} lines, found {actual}")]
+	LineCount {
+		/// The number of lines in the expected dump.
+		expected: usize,
+		/// The number of lines in the actual dump.
+		actual: usize,
+	},
+	/// A line differs beyond the tolerance.
+	#[error("line {line} differs:\n  expected: {expected}\n  actual:   {actual}")]
+	Line {
+		/// The line number, starting from 1.
+		line: usize,
+		/// The line in the expected dump.
+		expected: String,
+		/// The line in the actual dump.
+		actual: String,
+	},
+}
+
+/// Compare two dumps made by [`Painter::debug_dump`], numbers may differ within the tolerance.
+///
+/// Returns the first difference found. Blank lines and trailing whitespace are ignored,
+/// so the expected dump can be kept in a file edited by hand.
+pub fn compare_dumps(expected: &str, actual: &str, tolerance: DumpTolerance) -> Result<(), DumpMismatch> {
+	let lines = |dump: &str| dump.lines().map(str::trim_end).filter(|line| !line.is_empty()).map(String::from).collect::<Vec<_>>();
+	let expected = lines(expected);
+	let actual = lines(actual);
+
+	for (index, (expected, actual)) in expected.iter().zip(actual.iter()).enumerate() {
+		if !lines_match(expected, actual, tolerance) {
+			return Err(DumpMismatch::Line {
+				line: index + 1,
+				expected: expected.clone(),
+				actual: actual.clone(),
+			});
+		}
+	}
+
+	if expected.len() != actual.len() {
+		return Err(DumpMismatch::LineCount {
+			expected: expected.len(),
+			actual: actual.len(),
+		});
+	}
+
+	Ok(())
+}
+
+/// Split a line into words, numbers and delimiters, remembering the group each token is in.
+fn tokenize(line: &str) -> Vec<(&str, &str)> {
+	let mut tokens = vec!();
+	let mut groups = vec!();
+	let mut start = 0;
+	for (index, chr) in line.char_indices() {
+		if matches!(chr, '(' | ')' | ',' | '=' | ' ') {
+			let word = &line[start..index];
+			let group = groups.last().copied().unwrap_or("");
+			if !word.is_empty() {
+				tokens.push((word, group));
+			}
+			match chr {
+				'(' => groups.push(word),
+				')' => { groups.pop(); },
+				_ => {},
+			}
+			tokens.push((&line[index..index + 1], group));
+			start = index + 1;
+		}
+	}
+	if start < line.len() {
+		tokens.push((&line[start..], groups.last().copied().unwrap_or("")));
+	}
+	tokens
+}
+
+fn lines_match(expected: &str, actual: &str, tolerance: DumpTolerance) -> bool {
+	let expected = tokenize(expected);
+	let actual = tokenize(actual);
+	expected.len() == actual.len() && expected.iter().zip(actual.iter()).all(|((expected, group), (actual, _))| {
+		match (expected.parse::<f32>(), actual.parse::<f32>()) {
+			(Ok(expected), Ok(actual)) if expected.is_finite() && actual.is_finite() => {
+				let tolerance = if *group == "rgba" { tolerance.color } else { tolerance.position };
+				(expected - actual).abs() <= tolerance
+			},
+			_ => expected == actual,
+		}
+	})
+}
+
+/// Format a number rounded to 3 decimals, without trailing zeros.
+fn num(value: f32) -> String {
+	if !value.is_finite() {
+		return format!("{}", value);
+	}
+	let out = format!("{:.3}", value);
+	let out = out.trim_end_matches('0').trim_end_matches('.');
+	if out == "-0" {
+		String::from("0")
+	}else {
+		String::from(out)
+	}
+}
+
+fn vec2(value: Vec2) -> String {
+	format!("{}, {}", num(value.x), num(value.y))
+}
+
+fn color(value: Color) -> String {
+	format!("rgba({}, {}, {}, {})", num(value.r), num(value.g), num(value.b), num(value.a))
+}
+
+fn rect(value: Rect) -> String {
+	format!("rect({}, {}, {}, {})", num(value.x), num(value.y), num(value.w), num(value.h))
+}
+
+fn fill_mode(value: &FillMode) -> String {
+	match value {
+		FillMode::Color(value) => color(*value),
+		FillMode::Texture(id, lt, rb, texture_lt, texture_rb) => format!(
+			"texture({}, {}, {}, {}, {})", id, vec2(*lt), vec2(*rb), vec2(*texture_lt), vec2(*texture_rb)
+		),
+		FillMode::NinePatch(id, lt, rb, texture_lt, texture_rb, insets) => format!(
+			"nine_patch({}, {}, {}, {}, {}, {})", id, vec2(*lt), vec2(*rb), vec2(*texture_lt), vec2(*texture_rb), color(*insets)
+		),
+		FillMode::LinearGradient(from, to, start, end) => format!(
+			"linear({}, {}, {}, {})", color(*from), color(*to), vec2(*start), vec2(*end)
+		),
+		FillMode::RadialGradient(from, to, center, radius) => format!(
+			"radial({}, {}, {}, {})", color(*from), color(*to), vec2(*center), num(*radius)
+		),
+		FillMode::Stripes(a, b, start, end) => format!(
+			"stripes({}, {}, {}, {})", color(*a), color(*b), vec2(*start), vec2(*end)
+		),
+	}
+}
+
+fn basic_shape(shape: &BasicShape) -> String {
+	let mut out = match &shape.data {
+		BasicShapeData::Circle(center, radius) => format!("circle({}, {})", vec2(*center), num(*radius)),
+		BasicShapeData::Triangle(a, b, c) => format!("triangle({}, {}, {})", vec2(*a), vec2(*b), vec2(*c)),
+		BasicShapeData::Rectangle(lt, rb, rounding) => format!(
+			"rect({}, {}) rounding({}, {}, {}, {})", vec2(*lt), vec2(*rb), num(rounding.r), num(rounding.g), num(rounding.b), num(rounding.a)
+		),
+		BasicShapeData::HalfPlane(a, b) => format!("half_plane({}, {})", vec2(*a), vec2(*b)),
+		BasicShapeData::QuadBezierPlane(a, b, c) => format!("quad_bezier_plane({}, {}, {})", vec2(*a), vec2(*b), vec2(*c)),
+		BasicShapeData::SDFTexture(lt, rb, id) => format!("sdf_texture({}, {}, {})", vec2(*lt), vec2(*rb), id),
+		BasicShapeData::Text(pos, font, size, chr) => format!("glyph({}, {}, {}, U+{:04X})", vec2(*pos), font, num(*size), *chr as u32),
+	};
+	if let Some(width) = shape.stroke {
+		out.push_str(&format!(" stroke={}", num(width)));
+	}
+	if shape.transform != Transform2D::IDENTITY {
+		let t = shape.transform;
+		out.push_str(&format!(
+			" transform({}, {}, {}, {}, {}, {})",
+			num(t[0][0]), num(t[0][1]), num(t[0][2]), num(t[1][0]), num(t[1][1]), num(t[1][2])
+		));
+	}
+	out
+}
+
+fn operator(operator: &Operator) -> String {
+	match operator {
+		Operator::And => String::from("and"),
+		Operator::Or => String::from("or"),
+		Operator::Minus => String::from("minus"),
+		Operator::Xor => String::from("xor"),
+		Operator::Not => String::from("not"),
+		Operator::Lerp(factor) => format!("lerp({})", num(*factor)),
+		Operator::SmoothStep(factor) => format!("smooth_step({})", num(*factor)),
+		Operator::Sigmoid(factor) => format!("sigmoid({})", num(*factor)),
+	}
+}
+
+fn dump_shape(out: &mut String, shape: &ShapeToDraw) {
+	out.push_str(&format!(
+		"shape blend={:?} clip={} fill={}\n",
+		shape.blend_mode, rect(shape.clip_rect), fill_mode(&shape.fill_mode)
+	));
+	for part in &shape.shape.0 {
+		let part = match part {
+			ShapeOrOp::Shape(shape) => basic_shape(shape),
+			ShapeOrOp::Op(op) => format!("op {}", operator(op)),
+		};
+		out.push_str("  ");
+		out.push_str(&part);
+		out.push('\n');
+	}
+}
+
+impl Painter {
+	/// Create a painter not bound to any window or GPU, for drawing widgets in tests.
+	///
+	/// The clip rect covers the whole window, register the fonts used by the widgets in `font_pool` to draw texts.
+	pub fn headless(font_pool: Arc<Mutex<FontPool>>, window_size: impl Into<Vec2>) -> Self {
+		let window_size = window_size.into();
+		let mut painter = Self::new(font_pool, window_size);
+		painter.set_clip_rect(Rect::from_size(window_size));
+		painter
+	}
+
+	/// Dump the shapes drawn so far to a stable textual form, see [`crate::render::snapshot`].
+	pub fn debug_dump(&self) -> String {
+		self.debug_dump_from(0)
+	}
+
+	/// Dump the shapes drawn since the given number of shapes, useful for dumping a single widget drawn into a shared painter.
+	pub fn debug_dump_from(&self, start: usize) -> String {
+		let mut out = String::new();
+		for shape in self.shapes.iter().skip(start) {
+			dump_shape(&mut out, shape);
+		}
+		out
+	}
+}
+
+mod test {
+	#[test]
+	fn test_compare_dumps() {
+		use super::{compare_dumps, DumpMismatch, DumpTolerance};
+
+		let expected = "shape blend=AlphaAdd clip=rect(0, 0, 800, 600) fill=rgba(0.165, 0.165, 0.165, 1)\n  rect(0, 0, 100, 40) rounding(8, 8, 8, 8)\n";
+		let dump = |rect: &str, channel: &str| format!(
+			"shape blend=AlphaAdd clip=rect(0, 0, 800, 600) fill=rgba({channel}, 0.165, 0.165, 1)\n  rect({rect}, 0, 100, 40) rounding(8, 8, 8, 8)\n"
+		);
+
+		// blank lines and trailing whitespace are ignored.
+		assert_eq!(compare_dumps(expected, &format!("\n{}  \n\n", expected.trim_end()), DumpTolerance::EXACT), Ok(()));
+		assert_eq!(compare_dumps(expected, &dump("0.005", "0.167"), DumpTolerance::default()), Ok(()));
+		assert!(matches!(
+			compare_dumps(expected, &dump("0.02", "0.165"), DumpTolerance::default()),
+			Err(DumpMismatch::Line { line: 2, .. })
+		));
+		assert!(matches!(
+			compare_dumps(expected, &dump("0", "0.17"), DumpTolerance::default()),
+			Err(DumpMismatch::Line { line: 1, .. })
+		));
+
+		// the numbers in `rgba` use the color tolerance, the others the position tolerance.
+		let tolerance = DumpTolerance { position: 0.1, color: 0.001 };
+		assert_eq!(compare_dumps(expected, &dump("0.05", "0.1655"), tolerance), Ok(()));
+		assert!(compare_dumps(expected, &dump("0", "0.167"), tolerance).is_err());
+
+		assert_eq!(
+			compare_dumps(expected, &format!("{}  rect(0, 0, 1, 1)\n", expected), DumpTolerance::default()),
+			Err(DumpMismatch::LineCount { expected: 2, actual: 3 })
+		);
+		assert!(matches!(
+			compare_dumps(expected, &expected.replace("AlphaAdd", "Alpha"), DumpTolerance::default()),
+			Err(DumpMismatch::Line { line: 1, .. })
+		));
+		assert!(matches!(
+			compare_dumps(expected, &expected.replace("rounding", "stroke"), DumpTolerance::default()),
+			Err(DumpMismatch::Line { line: 2, .. })
+		));
+	}
+}
@@ -0,0 +1,127 @@
+//! Positioned glyphs of a laid out text, see [`Painter::layout_text`](super::painter::Painter::layout_text).
+//!
+//! A [`TextLayout`] keeps where every glyph of a text goes, which part of the text it comes from and the box of every line,
+//! so widgets can hit test characters, paint their own selections or leave room for inline content
+//! without measuring the text piece by piece.
+//! Drawing a layout with [`Painter::draw_text_layout`](super::painter::Painter::draw_text_layout) does not touch the font pool again.
+//!
+//! Every character is its own cluster for now, as the text is not shaped.
+
+use std::ops::Range;
+
+use crate::math::{rect::Rect, vec2::Vec2};
+
+use super::font::FontId;
+
+/// A glyph of a [`TextLayout`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct PositionedGlyph {
+	/// The character represented by the glyph.
+	pub chr: char,
+	/// The byte range of the text the glyph comes from.
+	pub cluster: Range<usize>,
+	/// The index of the line the glyph is on.
+	pub line: usize,
+	/// The pen position of the glyph, relative to the left top of the layout.
+	pub pos: Vec2,
+	/// The horizontal distance from the pen position to where the glyph is drawn.
+	pub bearing: f32,
+	/// How far the pen moves after the glyph.
+	pub advance: f32,
+}
+
+impl PositionedGlyph {
+	/// Get the position the glyph is drawn at, relative to the left top of the layout.
+	pub fn draw_pos(&self) -> Vec2 {
+		self.pos + Vec2::x(self.bearing)
+	}
+}
+
+/// A line of a [`TextLayout`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextLine {
+	/// The indices of the glyphs on the line.
+	pub glyphs: Range<usize>,
+	/// The byte range of the text on the line, without the line break.
+	pub bytes: Range<usize>,
+	/// The box of the line, relative to the left top of the layout.
+	pub rect: Rect,
+}
+
+/// A text laid out by [`Painter::layout_text`](super::painter::Painter::layout_text).
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextLayout {
+	/// The laid out text.
+	pub text: String,
+	/// The font the text is laid out with.
+	pub font_id: FontId,
+	/// The font size the text is laid out with.
+	pub font_size: f32,
+	/// The glyphs in the order of the text, line breaks have no glyph.
+	pub glyphs: Vec<PositionedGlyph>,
+	/// The lines of the text, there is always at least one.
+	pub lines: Vec<TextLine>,
+	/// The size of the text, the same as [`Painter::text_size`](super::painter::Painter::text_size) gives.
+	pub size: Vec2,
+}
+
+impl TextLayout {
+	/// Get the index of the glyph drawn for the byte of the text.
+	pub fn glyph_of_byte(&self, byte: usize) -> Option<usize> {
+		self.glyphs.binary_search_by(|glyph| {
+			if glyph.cluster.end <= byte {
+				std::cmp::Ordering::Less
+			}else if glyph.cluster.start > byte {
+				std::cmp::Ordering::Greater
+			}else {
+				std::cmp::Ordering::Equal
+			}
+		}).ok()
+	}
+
+	/// Get the index of the line containing the byte of the text, a byte at the end of a line belongs to the line.
+	pub fn line_of_byte(&self, byte: usize) -> usize {
+		self.lines.iter().position(|line| byte <= line.bytes.end).unwrap_or(self.lines.len() - 1)
+	}
+
+	/// Get the index of the line at the given height, clamped to the first and last line.
+	pub fn line_at(&self, y: f32) -> usize {
+		self.lines.iter().position(|line| y < line.rect.rb().y).unwrap_or(self.lines.len() - 1)
+	}
+
+	/// Get the left top of the caret placed before the byte of the text, the byte is clamped to the text.
+	pub fn caret_pos(&self, byte: usize) -> Vec2 {
+		let line = &self.lines[self.line_of_byte(byte.min(self.text.len()))];
+		self.glyphs[line.glyphs.clone()].iter()
+			.find(|glyph| glyph.cluster.end > byte)
+			.map(|glyph| glyph.pos)
+			.unwrap_or(Vec2::new(line.rect.rb().x, line.rect.lt().y))
+	}
+
+	/// Get the byte of the text to place the caret at for a position relative to the left top of the layout.
+	///
+	/// The position is clamped to the lines, and the caret goes before or after a glyph depending on which half is hit.
+	pub fn hit_test(&self, pos: impl Into<Vec2>) -> usize {
+		let pos = pos.into();
+		let line = &self.lines[self.line_at(pos.y)];
+		self.glyphs[line.glyphs.clone()].iter()
+			.find(|glyph| pos.x < glyph.pos.x + glyph.advance / 2.0)
+			.map(|glyph| glyph.cluster.start)
+			.unwrap_or(line.bytes.end)
+	}
+
+	/// Get the boxes covering the byte range of the text, one for every line it touches, e.g. to paint a selection.
+	pub fn selection_rects(&self, range: Range<usize>) -> Vec<Rect> {
+		if range.is_empty() {
+			return vec!();
+		}
+		self.lines.iter().filter(|line| line.bytes.start <= range.end && line.bytes.end >= range.start).filter_map(|line| {
+			let start = self.caret_pos(range.start.max(line.bytes.start)).x;
+			let end = self.caret_pos(range.end.min(line.bytes.end)).x;
+			if start >= end {
+				return None;
+			}
+			Some(Rect::from_ltrb(Vec2::new(start, line.rect.lt().y), Vec2::new(end, line.rect.rb().y)))
+		}).collect()
+	}
+}
@@ -3,8 +3,11 @@
 use std::collections::HashMap;
 
 use indexmap::IndexSet;
+use time::Duration;
 use wgpu::util::DeviceExt;
 
+use crate::math::vec2::Vec2;
+
 /// A texture ID
 pub type TextureId = u32; 
 
@@ -45,6 +48,53 @@ pub struct Texture {
 	pub(crate) used_in_last_frame: bool,
 }
 
+/// The delay of the frames of an animated texture registered without one.
+pub const DEFAULT_FRAME_DELAY: Duration = Duration::milliseconds(100);
+
+/// An animated texture, such as a decoded GIF, registered by [`crate::Context::register_texture_animated`].
+/// 
+/// Every frame is a texture of its own, remove them with [`crate::Context::remove_texture_animated`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct AnimatedTexture {
+	/// The texture of each frame.
+	pub frames: Vec<TextureId>,
+	/// How long each frame is shown, as many as the frames.
+	pub delays: Vec<Duration>,
+	/// The size of the frames in pixels.
+	pub size: Vec2,
+}
+
+impl AnimatedTexture {
+	/// The time to play all frames once.
+	pub fn duration(&self) -> Duration {
+		self.delays.iter().copied().sum()
+	}
+
+	/// Get the index of the frame shown at the time since the animation started, looping forever.
+	/// 
+	/// Also returns how long until the next frame.
+	pub fn frame_at(&self, time: Duration) -> (usize, Duration) {
+		let duration = self.duration();
+		if self.frames.len() <= 1 || duration <= Duration::ZERO {
+			return (0, Duration::MAX);
+		}
+		let mut time = Duration::seconds_f64(time.as_seconds_f64().rem_euclid(duration.as_seconds_f64()));
+		for (index, delay) in self.delays.iter().enumerate() {
+			if time < *delay {
+				return (index, *delay - time);
+			}
+			time -= *delay;
+		}
+		// only reached through rounding at the very end of the loop.
+		(0, self.delays[0])
+	}
+
+	/// Get the texture shown at the time since the animation started, see [`Self::frame_at`].
+	pub fn texture_at(&self, time: Duration) -> Option<TextureId> {
+		self.frames.get(self.frame_at(time).0).copied()
+	}
+}
+
 #[derive(Default)]
 pub(crate) struct TexturePool {
 	pub textures: HashMap<TextureId, Texture>,
@@ -282,10 +332,12 @@ impl TexturePool {
 		Ok(())
 	}
 
-	pub(crate) fn insert_texture(
+	/// Insert a texture with the id given out by the context, or the original id when the gpu resources are recreated.
+	pub(crate) fn insert_texture_with_id(
 		&mut self, 
 		device: &wgpu::Device, 
 		queue: &wgpu::Queue,
+		texture_id: TextureId,
 		rgba: &[u8], 
 		width: u32, 
 		height: u32
@@ -293,7 +345,7 @@ impl TexturePool {
 		if width > MAX_TEXTURE_SIZE[0] || height > MAX_TEXTURE_SIZE[1] {
 			return Err(CreateTextureError::TooLarge(width, height, MAX_TEXTURE_SIZE[0], MAX_TEXTURE_SIZE[1]));
 		}
-		let texture_id = self.available_texture_ids.pop().unwrap_or(self.textures.len() as u32);
+		self.available_texture_ids.shift_remove(&texture_id);
 		let array_index = texture_id / MAX_TEXTURE_LAYERS_PER_BUFFER;
 		let layer_index = texture_id % MAX_TEXTURE_LAYERS_PER_BUFFER;
 		let mut changed = false;
@@ -378,6 +430,16 @@ impl TexturePool {
 		Ok((texture_id, changed))
 	}
 
+	/// Get the texture array and the layer of an existing texture.
+	pub(crate) fn layer_of(&self, texture_id: TextureId) -> Option<(&wgpu::Texture, u32)> {
+		if !self.textures.contains_key(&texture_id) {
+			return None;
+		}
+		let array = self.texture_array.get((texture_id / MAX_TEXTURE_LAYERS_PER_BUFFER) as usize)?;
+		let layer = texture_id % MAX_TEXTURE_LAYERS_PER_BUFFER;
+		(layer < array.len).then_some((&array.texture, layer))
+	}
+
 	pub(crate) fn cleanup(&mut self) {
 		let mut avaiable_texture_ids = IndexSet::new();
 		self.textures.retain(|id, texture| {
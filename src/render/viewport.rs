@@ -0,0 +1,263 @@
+//! Hosting content rendered with wgpu inside the ui, such as the scene view of a game editor or a 3D model preview.
+//!
+//! Register a [`ViewportRenderer`] with [`crate::Context::add_viewport`] and show it with [`crate::widgets::viewport::WgpuViewport`].
+//! Every frame the widget is drawn, the renderer renders into a texture sized to the area of the widget in physical pixels,
+//! the texture is then drawn by the widget like an image, so it is clipped and covered by the other widgets as usual.
+//!
+//! The input on the widget is forwarded to [`ViewportRenderer::handle_input`], with positions relative to the widget.
+//!
+//! ```no_run
+//! use nablo_ui::prelude::*;
+//! use nablo_ui::render::viewport::ViewportContext;
+//!
+//! fn add_scene<A: App<Signal = ()>>(ctx: &mut Context<(), A>) {
+//!     let viewport = ctx.add_viewport(|ctx: &mut ViewportContext| {
+//!         // draw your scene here, the view is cleared to transparent before.
+//!         ctx.encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+//!             label: Some("Scene"),
+//!             color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+//!                 view: ctx.view,
+//!                 resolve_target: None,
+//!                 ops: wgpu::Operations {
+//!                     load: wgpu::LoadOp::Clear(wgpu::Color::BLUE),
+//!                     store: wgpu::StoreOp::Store,
+//!                 },
+//!             })],
+//!             ..Default::default()
+//!         });
+//!     });
+//!     ctx.layout.add_widget(ROOT_LAYOUT_ID, WgpuViewport::new(viewport, Vec2::new(640.0, 360.0))).unwrap();
+//! }
+//! ```
+
+use std::collections::HashMap;
+
+use crate::{math::vec2::Vec2, window::event::Key};
+
+use super::texture::{TextureId, TexturePool, MAX_TEXTURE_SIZE};
+
+/// The id of a viewport, the viewport is drawn as the texture with the same id.
+pub type ViewportId = TextureId;
+
+/// The format of the textures the viewports render into.
+pub const VIEWPORT_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8UnormSrgb;
+
+/// The gpu resources available to a [`ViewportRenderer`].
+pub struct ViewportContext<'a> {
+	/// The gpu device.
+	pub device: &'a wgpu::Device,
+	/// The gpu queue.
+	pub queue: &'a wgpu::Queue,
+	/// The encoder to record into, submitted before the ui is drawn.
+	pub encoder: &'a mut wgpu::CommandEncoder,
+	/// The texture to render into, cleared to transparent before.
+	pub texture: &'a wgpu::Texture,
+	/// The view of [`Self::texture`].
+	pub view: &'a wgpu::TextureView,
+	/// The format of [`Self::texture`], always [`VIEWPORT_FORMAT`].
+	pub format: wgpu::TextureFormat,
+	/// The size of [`Self::texture`] in physical pixels.
+	///
+	/// The size of the widget multiplied by the scale factor, at most [`MAX_TEXTURE_SIZE`].
+	pub size: Vec2,
+	/// The scale factor of the window.
+	pub scale_factor: f32,
+	/// The seconds since the program started, same as the `time` uniform of the UI shader.
+	pub time: f32,
+}
+
+/// An input on a [`crate::widgets::viewport::WgpuViewport`], positions are in logical pixels relative to the left top corner of the widget.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ViewportInput {
+	/// The mouse moved over the viewport.
+	PointerMoved(Vec2),
+	/// The mouse left the viewport.
+	PointerLeft,
+	/// A touch or a mouse button is pressed on the viewport, given the touch id and the position.
+	///
+	/// See [`crate::window::input_state::MOUSE_ID`] for the ids of the mouse buttons.
+	Pressed(u64, Vec2),
+	/// A touch pressed on the viewport moved, given the touch id, the position and the delta since the last frame.
+	///
+	/// Touches pressed on the viewport keep being forwarded after leaving it until they are released.
+	Dragged(u64, Vec2, Vec2),
+	/// A touch pressed on the viewport is released, given the touch id and the position.
+	Released(u64, Vec2),
+	/// The mouse wheel or the trackpad scrolled over the viewport.
+	Wheel(Vec2),
+	/// A key is pressed while the viewport is focused, the viewport is focused once pressed on until a press outside of it.
+	KeyPressed(Key),
+	/// A key is released while the viewport is focused.
+	KeyReleased(Key),
+}
+
+/// Renders the content of a viewport, see [`crate::render::viewport`].
+pub trait ViewportRenderer: 'static {
+	/// Create the gpu resources of the renderer, such as pipelines.
+	///
+	/// Called before the renderer first renders, and again when the gpu resources are recreated,
+	/// e.g. when the app is resumed on mobile platforms.
+	fn setup(&mut self, device: &wgpu::Device, queue: &wgpu::Queue, format: wgpu::TextureFormat) {
+		let _ = (device, queue, format);
+	}
+
+	/// Record the rendering into [`ViewportContext::encoder`], called every frame the viewport is drawn.
+	fn render(&mut self, ctx: &mut ViewportContext);
+
+	/// Handle an input on the viewport, e.g. to move the camera.
+	///
+	/// Called after the event frame, the viewport widget is redrawn if it is not [`crate::widgets::viewport::WgpuViewportInner::continuous`].
+	fn handle_input(&mut self, input: &ViewportInput) {
+		let _ = input;
+	}
+}
+
+impl<F: FnMut(&mut ViewportContext) + 'static> ViewportRenderer for F {
+	fn render(&mut self, ctx: &mut ViewportContext) {
+		self(ctx)
+	}
+}
+
+struct ViewportEntry {
+	renderer: Box<dyn ViewportRenderer>,
+	is_set_up: bool,
+	/// the texture rendered into, recreated when the size changes.
+	target: Option<(wgpu::Texture, wgpu::TextureView)>,
+}
+
+/// The viewports registered in [`crate::Context`].
+#[derive(Default)]
+pub(crate) struct Viewports {
+	entries: HashMap<ViewportId, ViewportEntry>,
+	/// the viewports drawn in the current frame and their sizes in physical pixels.
+	drawn: Vec<(ViewportId, [u32; 2])>,
+}
+
+impl Viewports {
+	pub(crate) fn insert(&mut self, id: ViewportId, renderer: Box<dyn ViewportRenderer>) {
+		self.entries.insert(id, ViewportEntry {
+			renderer,
+			is_set_up: false,
+			target: None,
+		});
+	}
+
+	pub(crate) fn remove(&mut self, id: ViewportId) -> Option<Box<dyn ViewportRenderer>> {
+		self.entries.remove(&id).map(|entry| entry.renderer)
+	}
+
+	/// Mark all the renderers to be set up again and drop the textures, called when the gpu resources are recreated.
+	pub(crate) fn invalidate(&mut self) {
+		for entry in self.entries.values_mut() {
+			entry.is_set_up = false;
+			entry.target = None;
+		}
+	}
+
+	pub(crate) fn handle_input(&mut self, id: ViewportId, input: &ViewportInput) {
+		if let Some(entry) = self.entries.get_mut(&id) {
+			entry.renderer.handle_input(input);
+		}
+	}
+
+	/// Set the viewports drawn in the frame, the last size is kept if a viewport is drawn more than once.
+	pub(crate) fn set_drawn(&mut self, mut drawn: Vec<(ViewportId, [u32; 2])>) {
+		drawn.reverse();
+		let mut seen = vec!();
+		drawn.retain(|(id, _)| if seen.contains(id) {
+			false
+		}else {
+			seen.push(*id);
+			true
+		});
+		self.drawn = drawn;
+	}
+
+	/// Render the viewports drawn in the frame, then copy them into their layers of the texture array sampled by the ui.
+	pub(crate) fn render(&mut self, device: &wgpu::Device, queue: &wgpu::Queue, textures: &TexturePool, scale_factor: f32, time: f32) {
+		if self.drawn.is_empty() {
+			return;
+		}
+		let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+			label: Some("Viewport Encoder"),
+		});
+		for (id, [width, height]) in std::mem::take(&mut self.drawn) {
+			let Some(entry) = self.entries.get_mut(&id) else { continue };
+			let Some((array, layer)) = textures.layer_of(id) else { continue };
+			if !entry.is_set_up {
+				entry.renderer.setup(device, queue, VIEWPORT_FORMAT);
+				entry.is_set_up = true;
+			}
+			let size = wgpu::Extent3d {
+				width,
+				height,
+				depth_or_array_layers: 1,
+			};
+			if entry.target.as_ref().is_none_or(|(texture, _)| texture.size() != size) {
+				let texture = device.create_texture(&wgpu::TextureDescriptor {
+					label: Some(&format!("Viewport {}", id)),
+					size,
+					mip_level_count: 1,
+					sample_count: 1,
+					dimension: wgpu::TextureDimension::D2,
+					format: VIEWPORT_FORMAT,
+					usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC | wgpu::TextureUsages::TEXTURE_BINDING,
+					view_formats: &[],
+				});
+				let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+				entry.target = Some((texture, view));
+			}
+			let Some((texture, view)) = &entry.target else { continue };
+
+			encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+				label: Some("Viewport Clear Pass"),
+				color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+					view,
+					resolve_target: None,
+					ops: wgpu::Operations {
+						load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+						store: wgpu::StoreOp::Store,
+					},
+				})],
+				depth_stencil_attachment: None,
+				..Default::default()
+			});
+			entry.renderer.render(&mut ViewportContext {
+				device,
+				queue,
+				encoder: &mut encoder,
+				texture,
+				view,
+				format: VIEWPORT_FORMAT,
+				size: Vec2::new(width as f32, height as f32),
+				scale_factor,
+				time,
+			});
+			encoder.copy_texture_to_texture(
+				wgpu::TexelCopyTextureInfo {
+					texture,
+					mip_level: 0,
+					origin: wgpu::Origin3d::ZERO,
+					aspect: wgpu::TextureAspect::All,
+				},
+				wgpu::TexelCopyTextureInfo {
+					texture: array,
+					mip_level: 0,
+					origin: wgpu::Origin3d { x: 0, y: 0, z: layer },
+					aspect: wgpu::TextureAspect::All,
+				},
+				size,
+			);
+		}
+		queue.submit(std::iter::once(encoder.finish()));
+	}
+}
+
+/// Get the size of the texture of a viewport drawn in the given logical size, in physical pixels.
+pub(crate) fn viewport_texture_size(size: Vec2, scale_factor: f32) -> [u32; 2] {
+	let size = size * scale_factor;
+	[
+		(size.x.round() as u32).clamp(1, MAX_TEXTURE_SIZE[0]),
+		(size.y.round() as u32).clamp(1, MAX_TEXTURE_SIZE[1]),
+	]
+}
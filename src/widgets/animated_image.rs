@@ -0,0 +1,114 @@
+//! A widget playing an animated texture registered by [`crate::Context::register_texture_animated`].
+
+use time::Duration;
+
+use crate::{layout::{Layout, LayoutId}, prelude::{AnimatedTexture, FillMode, InputState, Painter, Rect, Vec2, Vec4}, App};
+
+use super::{EventHandleStrategy, Signal, SignalGenerator, Widget};
+
+/// A widget playing an animated texture, such as a decoded GIF, looping forever.
+///
+/// The widget only redraws itself when the shown frame changes.
+pub struct AnimatedImage<S: Signal, A: App<Signal = S>> {
+	/// The inner properties of the animated image.
+	pub inner: AnimatedImageInner,
+	/// The signals generated by the animated image.
+	pub signals: SignalGenerator<S, AnimatedImageInner, A>,
+	started_at: Option<Duration>,
+	frame: usize,
+}
+
+/// The inner properties of the animated image.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AnimatedImageInner {
+	/// The animation to play.
+	pub texture: AnimatedTexture,
+	/// The size of the image, the size of the frames in pixels if not set.
+	pub size: Option<Vec2>,
+	/// The roundings of the image.
+	pub roundings: Vec4,
+	/// If `true`, the animation stops at the current frame.
+	pub paused: bool,
+}
+
+impl<S: Signal, A: App<Signal = S>> AnimatedImage<S, A> {
+	/// Create an animated image playing the texture.
+	pub fn new(texture: AnimatedTexture) -> Self {
+		Self {
+			inner: AnimatedImageInner {
+				texture,
+				size: None,
+				roundings: Vec4::ZERO,
+				paused: false,
+			},
+			signals: SignalGenerator::default(),
+			started_at: None,
+			frame: 0,
+		}
+	}
+
+	/// Set the size of the image.
+	pub fn set_size(self, size: Vec2) -> Self {
+		Self { inner: AnimatedImageInner { size: Some(size), ..self.inner }, ..self }
+	}
+
+	/// Set the roundings of the image.
+	pub fn roundings(self, roundings: impl Into<Vec4>) -> Self {
+		Self { inner: AnimatedImageInner { roundings: roundings.into(), ..self.inner }, ..self }
+	}
+
+	/// Set whether the animation is paused.
+	pub fn paused(self, paused: bool) -> Self {
+		Self { inner: AnimatedImageInner { paused, ..self.inner }, ..self }
+	}
+}
+
+impl<S: Signal, A: App<Signal = S>> Widget for AnimatedImage<S, A> {
+	type Signal = S;
+	type Application = A;
+
+	fn size(&self, _: LayoutId, _: &Painter, _: &Layout<Self::Signal, Self::Application>) -> Vec2 {
+		self.inner.size.unwrap_or(self.inner.texture.size)
+	}
+
+	fn draw(&mut self, painter: &mut Painter, size: Vec2) {
+		let Some(texture_id) = self.inner.texture.frames.get(self.frame) else {
+			return;
+		};
+		let rect = Rect::from_lt_size(Vec2::ZERO, size);
+		painter.set_fill_mode(FillMode::Texture(*texture_id, rect.lt(), rect.rb(), Vec2::ZERO, self.inner.texture.size));
+		painter.draw_rect(rect, self.inner.roundings);
+	}
+
+	fn handle_event(&mut self, app: &mut A, input_state: &mut InputState<Self::Signal>, id: LayoutId, area: Rect, _: Vec2) -> bool {
+		self.signals.generate_signals(app, &mut self.inner, input_state, id, area, false, false);
+		let now = input_state.program_running_time();
+		if self.inner.paused {
+			// resume from the paused frame rather than jumping ahead.
+			self.started_at = None;
+			return false;
+		}
+		let started_at = *self.started_at.get_or_insert_with(|| {
+			let offset: Duration = self.inner.texture.delays.iter().take(self.frame).copied().sum();
+			now - offset
+		});
+		let (frame, next_in) = self.inner.texture.frame_at(now - started_at);
+		if next_in < Duration::MAX {
+			input_state.request_frame_after(next_in);
+		}
+		if frame != self.frame {
+			self.frame = frame;
+			true
+		}else {
+			false
+		}
+	}
+
+	fn event_handle_strategy(&self) -> EventHandleStrategy {
+		if self.inner.paused {
+			EventHandleStrategy::OnHover
+		}else {
+			EventHandleStrategy::AlwaysSecondary
+		}
+	}
+}
@@ -1,8 +1,10 @@
 //! Button widget implementation.
 
-use crate::{layout::{Layout, LayoutId}, prelude::{Animatedf32, InputState, Rect, Vec2, Vec4}, render::{font::FontId, painter::Painter, shape::FillMode}, App};
+use std::any::Any;
 
-use super::{styles::{BRIGHT_FACTOR, CONTENT_TEXT_SIZE, DEFAULT_PADDING, DEFAULT_ROUNDING, DISABLE_COLOR, DISABLE_TEXT_COLOR, PRIMARY_COLOR, PRIMARY_TEXT_COLOR, TITLE_TEXT_SIZE}, Signal, SignalGenerator, Widget};
+use crate::{layout::{Layout, LayoutId}, math::animation::AnimatedValue, prelude::{Animatedf32, InputState, Rect, Vec2, Vec4}, render::{font::{FontId, FontRole}, painter::Painter, shape::FillMode}, window::event::Key, App};
+
+use super::{styles::{StateColors, StateStyle, WidgetState, BRIGHT_FACTOR, CONTENT_TEXT_SIZE, DEFAULT_PADDING, DEFAULT_ROUNDING, TITLE_TEXT_SIZE}, Signal, SignalGenerator, Widget};
 
 /// Button widget.
 pub struct Button<S: Signal, A: App<Signal = S>> {
@@ -13,10 +15,14 @@ pub struct Button<S: Signal, A: App<Signal = S>> {
 	hover_factor: Animatedf32,
 	pressed_factor: Animatedf32,
 	clicked_factor: Animatedf32,
+	/// the colors of the current state, `None` before the first event frame.
+	colors: Option<AnimatedValue<StateColors>>,
 }
 
 /// Button's inner properties.
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct ButtonInner {
 	/// Button label.
 	pub label: String,
@@ -26,10 +32,16 @@ pub struct ButtonInner {
 	pub size: ButtonSize,
 	/// Button's font.
 	pub font: FontId,
+	/// The font role of the button, see [`FontRole`].
+	pub font_role: Option<FontRole>,
 	/// Button's padding.
 	pub padding: Vec2,
 	/// Button's rounding.
 	pub rounding: Vec4,
+	/// The colors of the button in each state, `None` to use the preset of [`Self::style`], see [`ButtonStyle::state_style`].
+	/// 
+	/// Ignored by [`ButtonStyle::Custom`].
+	pub state_style: Option<StateStyle>,
 }
 
 impl Default for ButtonInner {
@@ -41,6 +53,8 @@ impl Default for ButtonInner {
 			padding: Vec2::same(DEFAULT_PADDING),
 			rounding: Vec4::same(DEFAULT_ROUNDING),
 			font: 0,
+			font_role: Some(FontRole::Body),
+			state_style: None,
 		}
 	}
 }
@@ -53,12 +67,14 @@ impl<S: Signal, A: App<Signal = S>> Default for Button<S, A> {
 			hover_factor: Animatedf32::default(),
 			pressed_factor: Animatedf32::default(),
 			clicked_factor: Animatedf32::default(),
+			colors: None,
 		}
 	}
 }
 
 /// Button's style.
 #[derive(Debug, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ButtonStyle {
 	#[default] Primary,
 	Secondary,
@@ -75,8 +91,21 @@ pub enum ButtonStyle {
 	},
 }
 
+impl ButtonStyle {
+	/// Get the preset colors of the style in each state, `None` for [`ButtonStyle::Custom`].
+	pub fn state_style(&self) -> Option<StateStyle> {
+		match self {
+			ButtonStyle::Primary | ButtonStyle::Disabled => Some(StateStyle::primary()),
+			ButtonStyle::Secondary => Some(StateStyle::secondary()),
+			ButtonStyle::Text => Some(StateStyle::text()),
+			ButtonStyle::Custom { .. } => None,
+		}
+	}
+}
+
 /// Button's font size.
 #[derive(Debug, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ButtonSize {
 	Tiny,
 	#[default] Small,
@@ -131,10 +160,24 @@ impl<S: Signal, A: App<Signal = S>> Button<S, A> {
 	}
 
 	/// Sets the button's font.
+	/// 
+	/// Clears the font role, so the font is used even if the theme registers one, see [`Self::font_role`].
 	pub fn font(self, font: FontId) -> Self {
 		Self {
 			inner: ButtonInner {
 				font,
+				font_role: None,
+				..self.inner
+			},
+			..self
+		}
+	}
+
+	/// Sets the font role of the button, `None` to always use the font id.
+	pub fn font_role(self, font_role: Option<FontRole>) -> Self {
+		Self {
+			inner: ButtonInner {
+				font_role,
 				..self.inner
 			},
 			..self
@@ -163,6 +206,38 @@ impl<S: Signal, A: App<Signal = S>> Button<S, A> {
 		}
 	}
 
+	/// Sets the colors of the button in each state, replacing the preset of its style.
+	pub fn state_style(self, state_style: StateStyle) -> Self {
+		Self {
+			inner: ButtonInner {
+				state_style: Some(state_style),
+				..self.inner
+			},
+			..self
+		}
+	}
+
+	/// Get the colors of the button in the state, `None` for [`ButtonStyle::Custom`].
+	fn state_colors(&self, state: WidgetState) -> Option<StateColors> {
+		if matches!(&self.inner.style, ButtonStyle::Custom { .. }) {
+			return None;
+		}
+		self.inner.state_style.or_else(|| self.inner.style.state_style()).map(|style| style.get(state))
+	}
+
+	/// Animate the colors towards the state, returns whether they are still animating.
+	fn update_colors(&mut self, state: WidgetState) -> bool {
+		let Some(target) = self.state_colors(state) else {
+			self.colors = None;
+			return false;
+		};
+		match &mut self.colors {
+			Some(colors) => colors.set(target),
+			None => self.colors = Some(AnimatedValue::default_with_value(target)),
+		}
+		self.colors.as_ref().is_some_and(|colors| colors.is_animating())
+	}
+
 	pub fn calc_size(&self, painter: &Painter) -> Vec2 {
 		let font_size = match self.inner.size {
 			ButtonSize::Tiny => CONTENT_TEXT_SIZE * 0.75,
@@ -172,7 +247,7 @@ impl<S: Signal, A: App<Signal = S>> Button<S, A> {
 			ButtonSize::Custom(size) => size,
 		};
 
-		let text_size = painter.text_size(self.inner.font, font_size, &self.inner.label).unwrap_or_default();
+		let text_size = painter.text_size(painter.resolve_font(self.inner.font_role, self.inner.font), font_size, &self.inner.label).unwrap_or_default();
 		text_size + self.inner.padding * 2.0
 	}
 }
@@ -191,39 +266,29 @@ impl<S: Signal, A: App<Signal = S>> Widget for Button<S, A> {
 			ButtonSize::Custom(size) => size,
 		};
 
-		let text_size = painter.text_size(self.inner.font, font_size, &self.inner.label).unwrap_or_default();
+		let text_size = painter.text_size(painter.resolve_font(self.inner.font_role, self.inner.font), font_size, &self.inner.label).unwrap_or_default();
 		// println!("size: {}, text_size: {}", size, text_size);
-		let bright_factor = self.hover_factor.value() * BRIGHT_FACTOR - self.pressed_factor.value() * BRIGHT_FACTOR;
 		let text_pos = (size - text_size) / 2.0;
+		let state = if matches!(&self.inner.style, ButtonStyle::Disabled) { WidgetState::Disabled } else { WidgetState::Normal };
+		let colors = self.colors.as_ref().map(|colors| colors.value()).or_else(|| self.state_colors(state));
 
-		let (mut text_color, mut background_color) = match &self.inner.style {
-			ButtonStyle::Disabled => {
-				let mut fill = FillMode::from(DISABLE_COLOR);
-				fill.brighter(bright_factor);
-				painter.set_fill_mode(fill.clone());
+		let (text_color, mut background_color) = match (&self.inner.style, colors) {
+			(ButtonStyle::Primary | ButtonStyle::Disabled, Some(colors)) => {
+				painter.set_fill_mode(colors.background);
 				painter.draw_rect(Rect::from_size(size), self.inner.rounding);
-				(FillMode::from(DISABLE_TEXT_COLOR), fill)
+				(FillMode::from(colors.foreground), FillMode::from(colors.background))
 			},
-			ButtonStyle::Primary => {
-				let mut fill = FillMode::from(PRIMARY_COLOR);
-				fill.brighter(bright_factor);
-				painter.set_fill_mode(fill.clone());
-				painter.draw_rect(Rect::from_size(size), self.inner.rounding);
-				(FillMode::from(PRIMARY_TEXT_COLOR), fill)
-			},
-			ButtonStyle::Secondary => {
-				let mut fill = FillMode::from(PRIMARY_COLOR);
-				fill.brighter(bright_factor);
-				painter.set_fill_mode(fill.clone());
+			(ButtonStyle::Secondary, Some(colors)) => {
+				painter.set_fill_mode(colors.border);
 				painter.draw_stroked_rect(Rect::from_size(size).shrink(Vec2::same(0.75)), self.inner.rounding, 1.5);
-				(FillMode::from(PRIMARY_COLOR), fill)
+				(FillMode::from(colors.foreground), FillMode::from(colors.border))
 			},
-			ButtonStyle::Text => {
-				let t = self.hover_factor.value();
-				let fill = FillMode::from(t * PRIMARY_COLOR + (1.0 - t) * PRIMARY_TEXT_COLOR);
-				(fill, PRIMARY_COLOR.into())
+			(ButtonStyle::Text, Some(colors)) => {
+				(FillMode::from(colors.foreground), FillMode::from(colors.border))
 			},
-			ButtonStyle::Custom{ background, text, width } => {
+			(ButtonStyle::Custom{ background, text, width }, _) => {
+				// the custom fills may be gradients, so they are brightened rather than taken from a state style.
+				let bright_factor = self.hover_factor.value() * BRIGHT_FACTOR - self.pressed_factor.value() * BRIGHT_FACTOR;
 				let mut fill = background.clone();
 				fill.brighter(bright_factor);
 				painter.set_fill_mode(fill.clone());
@@ -232,11 +297,13 @@ impl<S: Signal, A: App<Signal = S>> Widget for Button<S, A> {
 				}else {
 					painter.draw_rect(Rect::from_size(size), self.inner.rounding);
 				}
-				(text.clone(), fill)
-			}
+				let mut text = text.clone();
+				text.brighter(bright_factor);
+				(text, fill)
+			},
+			(_, None) => return,
 		};
 
-		text_color.brighter(bright_factor);
 		if self.clicked_factor.is_animating() {
 			let click_factor = self.clicked_factor.value();
 			background_color.mul_alpha(1.0 - click_factor);
@@ -245,7 +312,7 @@ impl<S: Signal, A: App<Signal = S>> Widget for Button<S, A> {
 		}
 
 		painter.set_fill_mode(text_color);
-		painter.draw_text(text_pos, self.inner.font, font_size, &self.inner.label);
+		painter.draw_text(text_pos, painter.resolve_font(self.inner.font_role, self.inner.font), font_size, &self.inner.label);
 	}
 
 	fn size(&self, _: LayoutId, painter: &Painter, _: &Layout<Self::Signal, A>) -> Vec2 {
@@ -269,7 +336,7 @@ impl<S: Signal, A: App<Signal = S>> Widget for Button<S, A> {
 			}else {
 				// input_state.set_cursor_icon(CursorIcon::Default);
 			}
-			return false;
+			return self.update_colors(WidgetState::Disabled);
 		}
 
 		if mouse_over {
@@ -301,7 +368,39 @@ impl<S: Signal, A: App<Signal = S>> Widget for Button<S, A> {
 			self.clicked_factor.set(1.0);
 		}
 
+		if input_state.is_focused(id) && [Key::Enter, Key::KeypadEnter, Key::Space].into_iter().any(|key| input_state.is_key_pressed(key)) {
+			self.signals.click(app, &mut self.inner, input_state, id);
+			self.clicked_factor.set_start(0.0);
+			self.clicked_factor.set(1.0);
+		}
 
-		self.hover_factor.is_animating() || self.pressed_factor.is_animating() || self.clicked_factor.is_animating()
+		let state = if mouse_over && self.pressed_factor.target() > 0.0 {
+			WidgetState::Active
+		}else if mouse_over {
+			WidgetState::Hover
+		}else {
+			WidgetState::Normal
+		};
+		let colors_animating = self.update_colors(state);
+
+		self.hover_factor.is_animating() || self.pressed_factor.is_animating() || self.clicked_factor.is_animating() || colors_animating
+	}
+
+	fn focusable(&self) -> bool {
+		!matches!(&self.inner.style, ButtonStyle::Disabled)
+	}
+
+	fn save_state(&self) -> Option<Box<dyn Any>> {
+		Some(Box::new(([self.hover_factor.clone(), self.pressed_factor.clone(), self.clicked_factor.clone()], self.colors.clone())))
+	}
+
+	fn restore_state(&mut self, state: Box<dyn Any>) {
+		if let Ok(state) = state.downcast::<([Animatedf32; 3], Option<AnimatedValue<StateColors>>)>() {
+			let ([hover_factor, pressed_factor, clicked_factor], colors) = *state;
+			self.hover_factor = hover_factor;
+			self.pressed_factor = pressed_factor;
+			self.clicked_factor = clicked_factor;
+			self.colors = colors;
+		}
 	}
 }
\ No newline at end of file
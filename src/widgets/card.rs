@@ -1,32 +1,59 @@
 //! A simple card container supporting scrolling and different layout for displaying other widgets.
 
-use std::collections::HashMap;
+use std::{any::Any, collections::HashMap};
 
-use crate::{layout::{Layout, LayoutId}, math::{color::Vec4, prelude::Animatedf32, rect::Rect, vec2::Vec2}, prelude::{Animation, AnimationNode, Linker, BACKGROUND_COLOR, DEFAULT_ANIMATION_DURATION, PRIMARY_COLOR}, render::{painter::Painter, shape::FillMode}, window::input_state::InputState, App};
+use time::Duration;
 
-use super::{styles::{CARD_BORDER_COLOR, CARD_COLOR, DEFAULT_ROUNDING}, Signal, SignalGenerator, Widget};
+use crate::{layout::{Layout, LayoutId}, math::{color::Vec4, prelude::{AnimatedColor, Animatedf32}, rect::Rect, vec2::Vec2}, prelude::{Animation, AnimationNode, Linker, BACKGROUND_COLOR, DEFAULT_ANIMATION_DURATION, EM, PRIMARY_COLOR}, render::{painter::Painter, shape::FillMode}, window::input_state::InputState, App};
+
+// the alignment lives in `math` for `Rect::align_inside`, re-exported here as it used to be defined here.
+pub use crate::math::rect::Alignment;
+
+use super::{styles::{CARD_BORDER_COLOR, CARD_COLOR, DEFAULT_ROUNDING}, ScrollAction, ScrollSource, Signal, SignalGenerator, Widget};
 
 /// A simple card container for displaying other widgets.
 /// 
-/// Note: You need set drag signal by [`Self::on_drag`] to use scrolling correctly.
+/// Scrolling cards can be nested, see [`OverscrollPolicy`] for how the scroll delta is passed between them.
 pub struct Card<S: Signal, A: App<Signal = S>> {
 	/// The inner properties of the card.
 	pub inner: CardInner,
 	/// The signals generated by the card.
 	pub signals: SignalGenerator<S, CardInner, A>,
+	/// The signal to be generated when the scroll position changes, see [`Scroll::offset`] for the new position.
+	#[allow(clippy::type_complexity)]
+	pub on_scroll: Option<Box<dyn Fn(&mut A, &mut CardInner) -> S>>,
 	actual_size: Vec2,
 	inner_size: Vec2,
+	rounding: AnimatedColor,
+	scrollbar_opacity: Animatedf32,
+	scrollbar_expand: Animatedf32,
+	scrollbar_active_at: Duration,
+	scrolled: bool,
+	last_offset: Vec2,
+	last_maximum: Vec2,
 }
 
 /// The inner properties of the card.
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct CardInner {
 	/// The layout strategy to use for the card.
 	pub layout_strategy: LayoutStrategy,
 	/// Set position of a child widget maually instead of using the layout strategy.
 	/// 
 	/// Will only affect the child with the given `LayoutId`.
+	/// 
+	/// Not serialized, as the ids are only valid in the layout they were given by.
+	#[cfg_attr(feature = "serde", serde(skip))]
 	pub fixed_children: HashMap<LayoutId, Vec2>,
+	/// How the children share the space left along the stacking direction, see [`Flex`].
+	/// 
+	/// Only affects horizontal and vertical typesetting, the children not listed keep their own sizes.
+	/// 
+	/// Not serialized, as the ids are only valid in the layout they were given by.
+	#[cfg_attr(feature = "serde", serde(skip))]
+	pub flex: HashMap<LayoutId, Flex>,
 	/// Set the background color of the card.
 	pub background_color: FillMode,
 	/// Set the rounding of the card.
@@ -35,6 +62,16 @@ pub struct CardInner {
 	pub size: (Option<f32>, Option<f32>),
 	/// Set the scroll state of the card.
 	pub scroll: Scroll,
+	/// What to do with the scroll delta left when the card reached its scroll limit.
+	pub overscroll: OverscrollPolicy,
+	/// The multiplier applied to the mouse wheel and trackpad delta.
+	pub wheel_sensitivity: f32,
+	/// Whether to invert the direction of the mouse wheel and trackpad.
+	pub natural_scrolling: bool,
+	/// The distance scrolled by the arrow keys.
+	pub line_step: f32,
+	/// The appearance of the scroll bars.
+	pub scrollbar: ScrollbarStyle,
 	/// The border color and width of the card.
 	pub border: Option<(FillMode, f32)>,
 	/// Whether to draw the stroke of the card.
@@ -48,10 +85,16 @@ impl Default for CardInner {
 		Self {
 			layout_strategy: LayoutStrategy::default(),
 			fixed_children: HashMap::new(),
+			flex: HashMap::new(),
 			background_color: FillMode::default(),
 			rounding: Vec4::same(DEFAULT_ROUNDING),
 			size: (None, None),
 			scroll: Scroll::default(),
+			overscroll: OverscrollPolicy::default(),
+			wheel_sensitivity: 1.0,
+			natural_scrolling: false,
+			line_step: EM * 2.5,
+			scrollbar: ScrollbarStyle::default(),
 			border: None,
 			draw_stroke: true,
 			dont_draw: false,
@@ -66,17 +109,31 @@ impl<S: Signal, A: App<Signal = S>> Card<S, A> {
 			inner: CardInner {
 				layout_strategy,
 				fixed_children: HashMap::new(),
+				flex: HashMap::new(),
 				background_color: FillMode::from(CARD_COLOR),
 				rounding: Vec4::same(DEFAULT_ROUNDING),
 				size: (None, None),
 				scroll: Scroll::default(),
+				overscroll: OverscrollPolicy::default(),
+				wheel_sensitivity: 1.0,
+				natural_scrolling: false,
+				line_step: EM * 2.5,
+				scrollbar: ScrollbarStyle::default(),
 				border: None,
 				draw_stroke: true,
 				dont_draw: false,
 			},
 			signals: Default::default(),
+			on_scroll: None,
 			actual_size: Vec2::ZERO,
 			inner_size: Vec2::ZERO,
+			rounding: AnimatedColor::default_with_value(Vec4::same(DEFAULT_ROUNDING)),
+			scrollbar_opacity: Animatedf32::default_with_value(1.0),
+			scrollbar_expand: Animatedf32::default_with_value(0.0),
+			scrollbar_active_at: Duration::ZERO,
+			scrolled: false,
+			last_offset: Vec2::ZERO,
+			last_maximum: Vec2::ZERO,
 		}
 	}
 
@@ -107,9 +164,13 @@ impl<S: Signal, A: App<Signal = S>> Card<S, A> {
 	}
 
 	/// Sets the rounding of the card.
-	pub fn rounding(self, rounding: impl Into<Vec4>) -> Self {
+	/// 
+	/// Changing [`CardInner::rounding`] later will animate the rounding to the new value.
+	pub fn rounding(mut self, rounding: impl Into<Vec4>) -> Self {
+		let rounding = rounding.into();
+		self.rounding.set_without_animation(rounding);
 		Self {
-			inner: CardInner { rounding: rounding.into(), ..self.inner },
+			inner: CardInner { rounding, ..self.inner },
 			..self
 		}
 	}
@@ -155,12 +216,33 @@ impl<S: Signal, A: App<Signal = S>> Card<S, A> {
 		}
 	}
 
+	/// Sets the signal to be generated when the scroll position changes.
+	pub fn on_scroll(self, on_scroll: impl Fn(&mut A, &mut CardInner) -> S + 'static) -> Self {
+		Self {
+			on_scroll: Some(Box::new(on_scroll)),
+			..self
+		}
+	}
+
 	/// Sets the child widget with the given `LayoutId` to a fixed position.
 	pub fn pin_child(mut self, id: LayoutId, pos: impl Into<Vec2>) -> Self {
 		self.inner.fixed_children.insert(id, pos.into());
 		self
 	}
 
+	/// Sets how the child widget with the given `LayoutId` grows and shrinks, see [`Flex`].
+	pub fn flex(mut self, id: LayoutId, flex: Flex) -> Self {
+		self.inner.flex.insert(id, flex);
+		self
+	}
+
+	/// Sets the child widget with the given `LayoutId` to take the share of the space left by the given weight.
+	/// 
+	/// Same as [`Self::flex`] with [`Flex::grow`].
+	pub fn child_weight(self, id: LayoutId, weight: f32) -> Self {
+		self.flex(id, Flex::grow(weight))
+	}
+
 	/// Sets the direction of the card contents.
 	pub fn direction(self, direction: Direction) -> Self {
 		Self {
@@ -183,6 +265,21 @@ impl<S: Signal, A: App<Signal = S>> Card<S, A> {
 		}
 	}
 
+	/// Arranges the contents in a grid with the given number of columns, see [`Typesetting::AutoGrid`].
+	pub fn grid(self, columns: usize) -> Self {
+		self.typesetting(Typesetting::AutoGrid(GridLayout::new(columns)))
+	}
+
+	/// Sets how the child with the given `LayoutId` is placed in the grid.
+	/// 
+	/// Only takes effect with [`Typesetting::AutoGrid`], set the typesetting before.
+	pub fn grid_cell(mut self, id: LayoutId, cell: GridCell) -> Self {
+		if let Typesetting::AutoGrid(grid) = &mut self.inner.layout_strategy.typesetting {
+			grid.cells.insert(id, cell);
+		}
+		self
+	}
+
 	/// Sets the alignments of the contents.
 	pub fn alignments(self, alignment: [Alignment; 2]) -> Self {
 		Self {
@@ -205,6 +302,49 @@ impl<S: Signal, A: App<Signal = S>> Card<S, A> {
 		}
 	}
 
+	/// Sets what to do with the scroll delta left when the card reached its scroll limit.
+	pub fn overscroll(self, overscroll: OverscrollPolicy) -> Self {
+		Self {
+			inner: CardInner { overscroll, ..self.inner },
+			..self
+		}
+	}
+
+	/// Sets the multiplier applied to the mouse wheel and trackpad delta.
+	pub fn wheel_sensitivity(self, wheel_sensitivity: f32) -> Self {
+		Self {
+			inner: CardInner { wheel_sensitivity, ..self.inner },
+			..self
+		}
+	}
+
+	/// Sets whether to invert the direction of the mouse wheel and trackpad.
+	pub fn natural_scrolling(self, natural_scrolling: bool) -> Self {
+		Self {
+			inner: CardInner { natural_scrolling, ..self.inner },
+			..self
+		}
+	}
+
+	/// Sets the distance scrolled by the arrow keys.
+	pub fn line_step(self, line_step: f32) -> Self {
+		Self {
+			inner: CardInner { line_step, ..self.inner },
+			..self
+		}
+	}
+
+	/// Sets the appearance of the scroll bars.
+	pub fn scrollbar(mut self, scrollbar: ScrollbarStyle) -> Self {
+		if scrollbar.visibility == ScrollbarVisibility::Never {
+			self.scrollbar_opacity.set_without_animation(0.0);
+		}
+		Self {
+			inner: CardInner { scrollbar, ..self.inner },
+			..self
+		}
+	}
+
 	/// Sets whether to draw the any thing related to the card.
 	pub fn dont_draw(self, dont_draw: bool) -> Self {
 		Self {
@@ -213,13 +353,124 @@ impl<S: Signal, A: App<Signal = S>> Card<S, A> {
 		}
 	}
 
-	fn scroll_pos(&self) -> Vec2 {
-		match &self.inner.scroll {
-			Scroll::Off => Vec2::ZERO,
-			Scroll::Vertical{current, ..} => Vec2::new(0.0, current.value()),
-			Scroll::Horizontal{current, ..} => Vec2::new(current.value(), 0.0),
-			Scroll::Both{current_vertical, current_horizontal, ..} => Vec2::new(current_horizontal.value(), current_vertical.value()),
+	/// Get the scroll position the card is heading to.
+	pub fn offset(&self) -> Vec2 {
+		self.inner.scroll.offset()
+	}
+
+	/// Get the maximum scroll position, as of the last layout.
+	pub fn max_offset(&self) -> Vec2 {
+		self.inner.scroll.maximum(self.actual_size - self.inner_size)
+	}
+
+	/// Get the size of the area the contents are shown in, as of the last layout.
+	pub fn viewport_size(&self) -> Vec2 {
+		self.inner_size
+	}
+
+	/// Get the size of the contents, as of the last layout.
+	pub fn content_size(&self) -> Vec2 {
+		self.actual_size
+	}
+
+	/// Scroll to the given position with animation, clamped by the size of the contents as of the last layout.
+	/// 
+	/// Pass [`f32::INFINITY`] to scroll to the end, see [`Scroll::follow_end`] to stay at the end while the contents grow.
+	pub fn set_offset(&mut self, offset: impl Into<Vec2>) {
+		let offset = offset.into().max(Vec2::ZERO);
+		let offset = match self.inner.scroll.clamp {
+			ScrollClamp::Off => offset.min(Vec2::same(f32::MAX)),
+			_ => offset.min(self.max_offset()),
+		};
+		self.inner.scroll.set_offset(offset);
+	}
+
+	/// Grow or shrink the children along the stacking direction to fill the card, see [`Flex`].
+	fn apply_flex(&self, mut childs: indexmap::IndexMap<LayoutId, Vec2>, size: Vec2) -> indexmap::IndexMap<LayoutId, Vec2> {
+		let padding = self.inner.layout_strategy.padding;
+		// the space taken by the paddings around and between the children, as they are placed in `handle_child_layout`.
+		let (is_horizontal, space, leading_padding) = match self.inner.layout_strategy.typesetting {
+			Typesetting::Horizontal => (true, size.x, padding.x * 2.0),
+			Typesetting::Vertical => (false, size.y, padding.y),
+			_ => return childs,
+		};
+		if self.inner.flex.is_empty() || !space.is_finite() {
+			return childs;
+		}
+		let main = |size: &Vec2| if is_horizontal { size.x } else { size.y };
+		let flowing = childs.iter()
+			.filter(|(id, size)| size.x >= 0.0 && size.y >= 0.0 && !self.inner.fixed_children.contains_key(id))
+			.map(|(id, size)| (*id, main(size)))
+			.collect::<Vec<_>>();
+		let gap = if is_horizontal { padding.x } else { padding.y };
+		let used = leading_padding + flowing.iter().map(|(_, length)| length + gap).sum::<f32>();
+		let free = space - used;
+
+		let weights = flowing.iter().map(|(id, length)| {
+			let flex = self.inner.flex.get(id).copied().unwrap_or_default();
+			// like css, the larger children shrink more for the same factor.
+			if free >= 0.0 { flex.grow.max(0.0) } else { flex.shrink.max(0.0) * length }
+		}).collect::<Vec<_>>();
+		let total_weight = weights.iter().sum::<f32>();
+		if total_weight <= 0.0 || free == 0.0 {
+			return childs;
 		}
+
+		for ((id, length), weight) in flowing.into_iter().zip(weights) {
+			let new_length = (length + free * weight / total_weight).max(0.0);
+			if let Some(size) = childs.get_mut(&id) {
+				if is_horizontal {
+					size.x = new_length;
+				}else {
+					size.y = new_length;
+				}
+			}
+		}
+		childs
+	}
+
+	/// Scroll the card by the given delta of scroll position, returns the delta can not be applied.
+	fn apply_scroll(&mut self, delta: Vec2, source: ScrollSource) -> Vec2 {
+		self.inner.scroll.apply(delta, source, self.actual_size - self.inner_size)
+	}
+
+	/// Keep the scroll position inside of the contents and at the end if following it, called every event frame.
+	fn update_scroll(&mut self, state: &InputState<S>, area: Rect) {
+		// the size of the contents is unknown before the first layout.
+		if self.actual_size == Vec2::ZERO {
+			return;
+		}
+
+		let maximum = self.max_offset();
+		let last_maximum = std::mem::replace(&mut self.last_maximum, maximum);
+		let dragging = state.is_drag_scrolling_on(area);
+		self.inner.scroll.keep_inside(maximum, last_maximum, dragging);
+	}
+
+	fn is_scroll_animating(&self) -> bool {
+		self.inner.scroll.is_animating()
+	}
+
+	/// Get the current position, the maximum position and whether it is vertical for each scroll bar.
+	fn scroll_bars(&self) -> Vec<(f32, f32, bool)> {
+		let overflow = self.actual_size - self.inner_size;
+		let bar = |axis: &ScrollAxis, overflow: f32, is_vertical: bool| {
+			let maximum = axis.maximum(overflow);
+			(axis.current.value().clamp(0.0, maximum), maximum, is_vertical)
+		};
+
+		let mut bars = vec!();
+		if let Some(axis) = &self.inner.scroll.vertical {
+			bars.push(bar(axis, overflow.y, true));
+		}
+		if let Some(axis) = &self.inner.scroll.horizontal {
+			bars.push(bar(axis, overflow.x, false));
+		}
+		bars
+	}
+
+	fn scroll_pos(&self) -> Vec2 {
+		self.inner.scroll.current_offset()
 	}
 }
 
@@ -228,54 +479,106 @@ impl<S: Signal, A: App<Signal = S>> Default for Card<S, A> {
 		Self {
 			inner: Default::default(),
 			signals: Default::default(),
+			on_scroll: None,
 			actual_size: Vec2::ZERO,
 			inner_size: Vec2::ZERO,
+			rounding: AnimatedColor::default_with_value(Vec4::same(DEFAULT_ROUNDING)),
+			scrollbar_opacity: Animatedf32::default_with_value(1.0),
+			scrollbar_expand: Animatedf32::default_with_value(0.0),
+			scrollbar_active_at: Duration::ZERO,
+			scrolled: false,
+			last_offset: Vec2::ZERO,
+			last_maximum: Vec2::ZERO,
 		}
 	}
 }
 
 /// The scroll state of the card.
-#[derive(Debug, PartialEq, Default)]
-pub enum Scroll {
-	/// Scroll is disabled.
-	#[default] Off,
-	/// Vertical scroll is enabled.
-	Vertical {
-		/// The current scroll position.
-		current: Animatedf32,
-		/// The maximum scroll position.
-		///
-		/// Set to None to automatically calculate the maximum scroll position based on the size of the contents.
-		maximum: Option<f32>,
-	},
-	/// Horizontal scroll is enabled.
-	Horizontal {
-		/// The current scroll position.
-		current: Animatedf32,
-		/// The maximum scroll position.
-		/// 
-		/// Set to None to automatically calculate the maximum scroll position based on the size of the contents.
-		maximum: Option<f32>,
-	},
-	/// Both vertical and horizontal scroll are enabled.
-	Both {
-		/// The current scroll position vertically.
-		current_vertical: Animatedf32,
-		/// The current scroll position horizontally.
-		current_horizontal: Animatedf32,
-		/// The maximum scroll position vertically.
-		/// 
-		/// Set to None to automatically calculate the maximum scroll position based on the size of the contents.
-		maximum_vertical: Option<f32>,
-		/// The maximum scroll position horizontally.
-		/// 
-		/// Set to None to automatically calculate the maximum scroll position based on the size of the contents.
-		maximum_horizontal: Option<f32>,
+/// 
+/// Each axis can be enabled separately, see [`Self::vertical`], [`Self::horizontal`] and [`Self::both`].
+#[derive(Debug, PartialEq, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Scroll {
+	/// The vertical scroll state, `None` if vertical scroll is disabled.
+	pub vertical: Option<ScrollAxis>,
+	/// The horizontal scroll state, `None` if horizontal scroll is disabled.
+	pub horizontal: Option<ScrollAxis>,
+	/// How the scroll position is kept inside of the contents.
+	pub clamp: ScrollClamp,
+	/// Keep the scroll position at the end while the contents grow, as long as it was at the end before.
+	/// 
+	/// Useful for chats and logs, where new messages should be scrolled into view unless the user scrolled away.
+	pub follow_end: bool,
+}
+
+/// The scroll state of a single axis.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScrollAxis {
+	/// The current scroll position.
+	pub current: Animatedf32,
+	/// The maximum scroll position.
+	///
+	/// Set to None to automatically calculate the maximum scroll position based on the size of the contents.
+	pub maximum: Option<f32>,
+}
+
+impl Default for ScrollAxis {
+	fn default() -> Self {
+		Self {
+			current: get_default_scroll_animaion(),
+			maximum: None,
+		}
+	}
+}
+
+impl ScrollAxis {
+	/// Get the maximum scroll position, `overflow` is how far the contents exceed the card.
+	fn maximum(&self, overflow: f32) -> f32 {
+		self.maximum.unwrap_or(overflow).max(0.0)
+	}
+}
+
+/// How a scroll container keeps its scroll position inside of the contents.
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ScrollClamp {
+	/// Stop at the edges of the contents.
+	#[default] Hard,
+	/// Let drags pull the contents past the edges, springing back once released.
+	/// 
+	/// The mouse wheel and programmatic scrolling still stop at the edges.
+	Elastic {
+		/// How much the pull is resisted, from 0.0 for moving as freely as inside the contents to 1.0 for not moving at all.
+		resistance: f32,
+		/// The farthest distance the contents can be pulled past the edges.
+		limit: f32,
 	},
+	/// Never clamp the scroll position, only the minimum of 0.0 is kept.
+	/// 
+	/// Useful for contents whose size is unknown to the card, e.g. infinite lists.
+	Off,
+}
+
+impl ScrollClamp {
+	/// The default elastic clamping, similar to mobile platforms.
+	pub const ELASTIC: Self = Self::Elastic { resistance: 0.5, limit: 120.0 };
+}
+
+/// Get the scroll position along an axis showing the span from `start` of `length` in a viewport of `viewport`,
+/// staying at `offset` if it is already shown. The start is kept visible if the span is longer than the viewport.
+pub(crate) fn reveal_offset(start: f32, length: f32, offset: f32, viewport: f32) -> f32 {
+	if start < offset || length > viewport {
+		start
+	}else if start + length > offset + viewport {
+		start + length - viewport
+	}else {
+		offset
+	}
 }
 
 #[inline(always)]
-fn get_default_scroll_animaion() -> Animatedf32 {
+pub(crate) fn get_default_scroll_animaion() -> Animatedf32 {
 	let mut current_animation = Animation::default();
 	current_animation.push(AnimationNode {
 		time: DEFAULT_ANIMATION_DURATION / 2.0,
@@ -288,42 +591,340 @@ fn get_default_scroll_animaion() -> Animatedf32 {
 impl Scroll {
 	/// create an empty Scroll.
 	pub fn empty() -> Self {
-		Self::Off
+		Self::default()
+	}
+
+	/// create a Scroll with the given axes enabled.
+	pub fn new(horizontal: bool, vertical: bool) -> Self {
+		Self {
+			vertical: vertical.then(ScrollAxis::default),
+			horizontal: horizontal.then(ScrollAxis::default),
+			..Default::default()
+		}
 	}
 
 	/// create a vertical Scroll.
 	pub fn vertical() -> Self {
-		Self::Vertical {
-			current: get_default_scroll_animaion(),
-			maximum: None,
-		}
+		Self::new(false, true)
 	}
 
 	/// create a horizontal Scroll.
 	pub fn horizontal() -> Self {
-		Self::Horizontal {
-			current: get_default_scroll_animaion(),
-			maximum: None,
-		}
+		Self::new(true, false)
 	}
 
 	/// create a both Scroll.
 	pub fn both() -> Self {
-		Self::Both {
-			current_vertical: get_default_scroll_animaion(),
-			current_horizontal:get_default_scroll_animaion(),
-			maximum_vertical: None,
-			maximum_horizontal: None,
+		Self::new(true, true)
+	}
+
+	/// Check whether scrolling is enabled on any axis.
+	pub fn is_enabled(&self) -> bool {
+		self.vertical.is_some() || self.horizontal.is_some()
+	}
+
+	/// Enable or disable vertical scroll, keeping the scroll position if it was enabled already.
+	pub fn enable_vertical(self, enable: bool) -> Self {
+		Self {
+			vertical: if enable { Some(self.vertical.unwrap_or_default()) } else { None },
+			..self
+		}
+	}
+
+	/// Enable or disable horizontal scroll, keeping the scroll position if it was enabled already.
+	pub fn enable_horizontal(self, enable: bool) -> Self {
+		Self {
+			horizontal: if enable { Some(self.horizontal.unwrap_or_default()) } else { None },
+			..self
+		}
+	}
+
+	/// Keep the scroll position of the scroll it replaces, for the axes enabled in both.
+	pub fn continue_from(&mut self, old: &Self) {
+		if let (Some(axis), Some(old)) = (&mut self.vertical, &old.vertical) {
+			axis.current = old.current.clone();
+		}
+		if let (Some(axis), Some(old)) = (&mut self.horizontal, &old.horizontal) {
+			axis.current = old.current.clone();
 		}
 	}
 
 	/// set the maximum scroll position.
-	pub fn max(self, maximum: f32) -> Self {
-		match self {
-			Self::Off => self,
-			Self::Vertical { current, .. } => Self::Vertical { current, maximum: Some(maximum) },
-			Self::Horizontal { current, .. } => Self::Horizontal { current, maximum: Some(maximum) },
-			Self::Both { current_vertical, current_horizontal, .. } => Self::Both { current_vertical, current_horizontal, maximum_vertical: Some(maximum), maximum_horizontal: Some(maximum) },
+	pub fn max(mut self, maximum: f32) -> Self {
+		for axis in [&mut self.vertical, &mut self.horizontal].into_iter().flatten() {
+			axis.maximum = Some(maximum);
+		}
+		self
+	}
+
+	/// set the scroll position to start with, clamped once the size of the contents is known.
+	pub fn initial_offset(mut self, offset: impl Into<Vec2>) -> Self {
+		let offset = offset.into();
+		if let Some(axis) = &mut self.vertical {
+			axis.current.set_without_animation(offset.y.max(0.0));
+		}
+		if let Some(axis) = &mut self.horizontal {
+			axis.current.set_without_animation(offset.x.max(0.0));
+		}
+		self
+	}
+
+	/// set how the scroll position is kept inside of the contents.
+	pub fn clamp(self, clamp: ScrollClamp) -> Self {
+		Self { clamp, ..self }
+	}
+
+	/// set whether to keep the scroll position at the end while the contents grow.
+	pub fn follow_end(self, follow_end: bool) -> Self {
+		Self { follow_end, ..self }
+	}
+
+	/// Get the scroll position the card is heading to, zero for disabled axes.
+	pub fn offset(&self) -> Vec2 {
+		Vec2::new(
+			self.horizontal.as_ref().map_or(0.0, |axis| axis.current.target()),
+			self.vertical.as_ref().map_or(0.0, |axis| axis.current.target()),
+		)
+	}
+
+	/// Get the scroll position currently displayed, which differs from [`Self::offset`] while animating.
+	pub fn current_offset(&self) -> Vec2 {
+		Vec2::new(
+			self.horizontal.as_ref().map_or(0.0, |axis| axis.current.value()),
+			self.vertical.as_ref().map_or(0.0, |axis| axis.current.value()),
+		)
+	}
+
+	/// Scroll to the given position with animation, disabled axes are ignored.
+	/// 
+	/// The position is clamped by the card on its next event frame, see [`Card::set_offset`] to clamp immediately.
+	pub fn set_offset(&mut self, offset: impl Into<Vec2>) {
+		let offset = offset.into();
+		if let Some(axis) = &mut self.vertical {
+			axis.current.set(offset.y.max(0.0));
+		}
+		if let Some(axis) = &mut self.horizontal {
+			axis.current.set(offset.x.max(0.0));
+		}
+	}
+
+	/// Get the maximum scroll position, `overflow` is how far the contents exceed the container, zero for disabled axes.
+	pub(crate) fn maximum(&self, overflow: Vec2) -> Vec2 {
+		Vec2::new(
+			self.horizontal.as_ref().map_or(0.0, |axis| axis.maximum(overflow.x)),
+			self.vertical.as_ref().map_or(0.0, |axis| axis.maximum(overflow.y)),
+		)
+	}
+
+	/// Scroll by the given delta of scroll position following [`Self::clamp`], returns the delta can not be applied.
+	pub(crate) fn apply(&mut self, delta: Vec2, source: ScrollSource, overflow: Vec2) -> Vec2 {
+		let clamp = self.clamp;
+		let apply = |axis: &mut Option<ScrollAxis>, delta: f32, overflow: f32| -> f32 {
+			let Some(axis) = axis else { return delta };
+			let maximum = axis.maximum(overflow);
+			let before = axis.current.target();
+			match clamp {
+				ScrollClamp::Elastic { resistance, limit } if source == ScrollSource::Drag && maximum > 0.0 => {
+					let target = before + delta;
+					let edge = target.clamp(0.0, maximum);
+					let past = target - edge;
+					if past == 0.0 || delta * past < 0.0 {
+						axis.current.set(target);
+					}else {
+						// the farther the contents are pulled past the edge, the harder it gets.
+						let before_past = before - before.clamp(0.0, maximum);
+						let factor = (1.0 - resistance).clamp(0.0, 1.0) * (1.0 - before_past.abs() / limit).max(0.0);
+						let past = (before_past + (past - before_past) * factor).clamp(- limit, limit);
+						axis.current.set(edge + past);
+					}
+					0.0
+				},
+				ScrollClamp::Off => {
+					axis.current.set_by(delta);
+					axis.current.min(0.0);
+					delta - (axis.current.target() - before)
+				},
+				_ => {
+					axis.current.set_by(delta);
+					axis.current.clamp(0.0, maximum);
+					delta - (axis.current.target() - before)
+				},
+			}
+		};
+
+		Vec2::new(
+			apply(&mut self.horizontal, delta.x, overflow.x),
+			apply(&mut self.vertical, delta.y, overflow.y),
+		)
+	}
+
+	/// Keep the scroll position inside of the contents and at the end if following it.
+	/// 
+	/// `maximum` and `last_maximum` are the maximum scroll positions now and in the last call,
+	/// `dragging` is whether a drag is scrolling the container, which may pull it past the edges.
+	pub(crate) fn keep_inside(&mut self, maximum: Vec2, last_maximum: Vec2, dragging: bool) {
+		let spring_back = match self.clamp {
+			ScrollClamp::Hard => true,
+			ScrollClamp::Elastic { .. } => !dragging,
+			ScrollClamp::Off => false,
+		};
+		let follow_end = self.follow_end;
+		let update = |axis: &mut Option<ScrollAxis>, maximum: f32, last_maximum: f32| {
+			let Some(axis) = axis else { return };
+			if follow_end && maximum > last_maximum && axis.current.target() >= last_maximum - 0.5 {
+				axis.current.set(maximum);
+			}else if spring_back {
+				axis.current.clamp(0.0, maximum);
+			}
+		};
+		update(&mut self.horizontal, maximum.x, last_maximum.x);
+		update(&mut self.vertical, maximum.y, last_maximum.y);
+	}
+
+	pub(crate) fn is_animating(&self) -> bool {
+		[&self.vertical, &self.horizontal].into_iter().flatten().any(|axis| axis.current.is_animating())
+	}
+}
+
+/// What a scroll container does with the scroll delta left when it reached its scroll limit.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum OverscrollPolicy {
+	/// Pass the remaining delta to the parent, so the parent scrolls once the card reached its limit.
+	#[default] Chain,
+	/// Keep the remaining delta, the parent will never be scrolled by scrolling inside of the card.
+	Contain,
+}
+
+/// When to show the scroll bars of a card.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ScrollbarVisibility {
+	/// Always show the scroll bars while the contents overflow.
+	#[default] Always,
+	/// Fade the scroll bars out once the card is neither scrolled nor the scroll bars hovered for the given duration.
+	AutoHide(Duration),
+	/// Never show the scroll bars, the card is still scrollable.
+	Never,
+}
+
+/// The appearance of the scroll bars of a card.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct ScrollbarStyle {
+	/// When to show the scroll bars.
+	pub visibility: ScrollbarVisibility,
+	/// The width of the scroll bars.
+	pub width: f32,
+	/// The width of the scroll bars while hovered, making them easier to hit.
+	/// 
+	/// The hovered area is always as wide as this.
+	pub hover_width: f32,
+	/// The distance between the scroll bars and the edges of the card.
+	pub margin: f32,
+	/// The minimum length of the thumb, so it stays grabbable for very long contents.
+	pub min_thumb_size: f32,
+	/// The rounding of the track and the thumb, limited to half of the width.
+	pub rounding: f32,
+	/// The color of the track, `None` to draw the thumb only.
+	pub track_color: Option<FillMode>,
+	/// The color of the thumb.
+	pub thumb_color: FillMode,
+}
+
+impl Default for ScrollbarStyle {
+	fn default() -> Self {
+		Self {
+			visibility: ScrollbarVisibility::default(),
+			width: 4.0,
+			hover_width: 8.0,
+			margin: 4.0,
+			min_thumb_size: DEFAULT_ROUNDING * 2.0,
+			rounding: f32::INFINITY,
+			track_color: Some(FillMode::from(BACKGROUND_COLOR)),
+			thumb_color: FillMode::from(PRIMARY_COLOR),
+		}
+	}
+}
+
+impl ScrollbarStyle {
+	/// Sets when to show the scroll bars.
+	pub fn visibility(self, visibility: ScrollbarVisibility) -> Self {
+		Self { visibility, ..self }
+	}
+
+	/// Sets the width of the scroll bars, and the width while hovered.
+	pub fn width(self, width: f32, hover_width: f32) -> Self {
+		Self { width, hover_width, ..self }
+	}
+
+	/// Sets the distance between the scroll bars and the edges of the card.
+	pub fn margin(self, margin: f32) -> Self {
+		Self { margin, ..self }
+	}
+
+	/// Sets the minimum length of the thumb.
+	pub fn min_thumb_size(self, min_thumb_size: f32) -> Self {
+		Self { min_thumb_size, ..self }
+	}
+
+	/// Sets the rounding of the track and the thumb.
+	pub fn rounding(self, rounding: f32) -> Self {
+		Self { rounding, ..self }
+	}
+
+	/// Sets the color of the track, `None` to draw the thumb only.
+	pub fn track_color(self, track_color: Option<FillMode>) -> Self {
+		Self { track_color, ..self }
+	}
+
+	/// Sets the color of the thumb.
+	pub fn thumb_color(self, thumb_color: impl Into<FillMode>) -> Self {
+		Self { thumb_color: thumb_color.into(), ..self }
+	}
+
+	/// Get the rect of the track relative to the card, `expand` goes from 0.0 to 1.0 while the scroll bar is hovered.
+	fn track_rect(&self, size: Vec2, is_vertical: bool, expand: f32) -> Rect {
+		let width = self.width + (self.hover_width - self.width).max(0.0) * expand;
+		if is_vertical {
+			Rect::from_lt_size(Vec2::new(size.x - self.margin - width, self.margin), Vec2::new(width, size.y - self.margin * 2.0))
+		}else {
+			Rect::from_lt_size(Vec2::new(self.margin, size.y - self.margin - width), Vec2::new(size.x - self.margin * 2.0, width))
+		}
+	}
+}
+
+/// How a child of a card takes the space along the stacking direction, see [`CardInner::flex`].
+/// 
+/// When the children are smaller than the card, the space left is shared by the children proportionally to [`Self::grow`].
+/// When they are larger, the overflow is taken from the children proportionally to [`Self::shrink`] multiplied by their sizes.
+/// The children are then given the new sizes, widgets drawing to the size they are given, such as cards, fill them.
+/// 
+/// Only takes effect if the size of the card is known, i.e. set by [`Card::set_size`] or given by its parent.
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Flex {
+	/// The weight of the space left taken by the child.
+	pub grow: f32,
+	/// The weight of the overflow taken from the child.
+	pub shrink: f32,
+}
+
+impl Flex {
+	/// Creates a flex growing by the given weight and never shrinking.
+	pub fn grow(grow: f32) -> Self {
+		Self {
+			grow,
+			shrink: 0.0,
+		}
+	}
+
+	/// Sets the weight of the overflow taken from the child.
+	pub fn shrink(self, shrink: f32) -> Self {
+		Self {
+			shrink,
+			..self
 		}
 	}
 }
@@ -332,6 +933,7 @@ impl Scroll {
 /// 
 /// Describes how to arrange the contents of the card.
 #[derive(Clone, Debug, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LayoutStrategy {
 	/// The direction of the card contents.
 	pub direction: Direction,
@@ -339,7 +941,7 @@ pub struct LayoutStrategy {
 	pub typesetting: Typesetting,
 	/// The alignment of the contents.
 	/// 
-	/// In grid typesetting, the first element is for the horizontal alignment, 
+	/// In grid typesetting (both [`Typesetting::Grid`] and [`Typesetting::AutoGrid`]), the first element is for the horizontal alignment, 
 	/// the second element is for the vertical alignment.
 	/// In horizontal typesetting, the first element is for the vertical alignment globally, 
 	/// the second element is for the horizontal alignment for each row.
@@ -350,19 +952,9 @@ pub struct LayoutStrategy {
 	pub padding: Vec2,
 }
 
-/// The alignment of the contents.
-#[derive(Clone, Debug, PartialEq, Eq, Default, Copy)]
-pub enum Alignment {
-	/// Align the contents to the left or top.
-	#[default] Positive,
-	/// Align the contents to the center.
-	Center,
-	/// Align the contents to the right or bottom.
-	Negative,
-}
-
 /// The direction of the card contents.
 #[derive(Clone, Debug, PartialEq, Eq, Default, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Direction {
 	/// From left to right or top to bottom.
 	#[default] Positive, 
@@ -376,6 +968,7 @@ pub enum Direction {
 
 /// How to put the contents of the card.
 #[derive(Clone, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Typesetting {
 	/// The contents are arranged in a single row.
 	Horizontal,
@@ -390,6 +983,85 @@ pub enum Typesetting {
 		/// Whether the content of the grid is horizontal or vertical placced.
 		is_vertical: bool,
 	},
+	/// The contents are arranged in a grid with a fixed number of columns, rows are added as needed.
+	/// 
+	/// Unlike [`Self::Grid`], the columns and rows are sized by their contents, and the contents can span several cells.
+	/// [`LayoutStrategy::padding`] is used as the gaps between the columns and the rows, [`LayoutStrategy::direction`] is ignored.
+	AutoGrid(GridLayout),
+}
+
+/// The properties of [`Typesetting::AutoGrid`].
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct GridLayout {
+	/// The number of columns in the grid, at least one.
+	pub columns: usize,
+	/// The cells of the children placed differently than a single cell, see [`GridCell`].
+	/// 
+	/// Not serialized, as the ids are only valid in the layout they were given by.
+	#[cfg_attr(feature = "serde", serde(skip))]
+	pub cells: HashMap<LayoutId, GridCell>,
+}
+
+impl GridLayout {
+	/// Creates a new grid with the given number of columns.
+	pub fn new(columns: usize) -> Self {
+		Self {
+			columns,
+			cells: HashMap::new(),
+		}
+	}
+
+	/// Sets how the child with the given `LayoutId` is placed.
+	pub fn cell(mut self, id: LayoutId, cell: GridCell) -> Self {
+		self.cells.insert(id, cell);
+		self
+	}
+}
+
+/// How a child is placed in [`Typesetting::AutoGrid`].
+/// 
+/// The children are placed in the order they are added, row by row, skipping the cells taken by the children spanning several rows.
+#[derive(Clone, Debug, PartialEq, Eq, Copy)]
+pub struct GridCell {
+	/// The number of columns the child spans, clamped to the columns of the grid.
+	pub column_span: usize,
+	/// The number of rows the child spans.
+	pub row_span: usize,
+	/// The alignment of the child in its cell, overriding [`LayoutStrategy::alignment`].
+	/// 
+	/// The first element is for the horizontal alignment, the second element is for the vertical alignment.
+	pub alignment: Option<[Alignment; 2]>,
+}
+
+impl Default for GridCell {
+	fn default() -> Self {
+		Self {
+			column_span: 1,
+			row_span: 1,
+			alignment: None,
+		}
+	}
+}
+
+impl GridCell {
+	/// Creates a cell spanning the given number of columns and rows.
+	pub fn span(column_span: usize, row_span: usize) -> Self {
+		Self {
+			column_span,
+			row_span,
+			..Default::default()
+		}
+	}
+
+	/// Sets the alignment of the child in its cell.
+	pub fn alignment(self, alignment: [Alignment; 2]) -> Self {
+		Self {
+			alignment: Some(alignment),
+			..self
+		}
+	}
 }
 
 impl<S: Signal, A: App<Signal = S>> Widget for Card<S, A> {
@@ -405,8 +1077,49 @@ impl<S: Signal, A: App<Signal = S>> Widget for Card<S, A> {
 			redraw = true;
 		}
 
-		let force_draggable = !matches!(self.inner.scroll, Scroll::Off);
-		let res = self.signals.generate_signals(
+		self.rounding.set(self.inner.rounding);
+		redraw |= self.rounding.is_animating();
+
+		self.update_scroll(state, area);
+		let offset = self.inner.scroll.offset();
+		if offset != self.last_offset {
+			self.last_offset = offset;
+			if let Some(on_scroll) = &self.on_scroll {
+				let signal = on_scroll(app, &mut self.inner);
+				state.send_signal_from(id, signal);
+			}
+		}
+
+		let style = &self.inner.scrollbar;
+		let scroll_bars = self.scroll_bars();
+		let hovering_bar = style.visibility != ScrollbarVisibility::Never && state.hover_pos().is_some_and(|pos| {
+			scroll_bars.iter().any(|(_, maximum, is_vertical)| {
+				*maximum > 0.0 && style.track_rect(area.size(), *is_vertical, 1.0).move_by(area.lt()).contains(pos)
+			})
+		});
+		let now = state.program_running_time();
+		if std::mem::take(&mut self.scrolled) || hovering_bar || self.is_scroll_animating() {
+			self.scrollbar_active_at = now;
+		}
+		let shown = match style.visibility {
+			ScrollbarVisibility::Always => true,
+			ScrollbarVisibility::Never => false,
+			ScrollbarVisibility::AutoHide(idle) => {
+				let shown = now.saturating_sub(self.scrollbar_active_at) < idle;
+				if shown {
+					// keep the event frames running to hide the scroll bars once idle.
+					state.request_redraw();
+				}
+				shown
+			},
+		};
+		self.scrollbar_opacity.set(if shown { 1.0 } else { 0.0 });
+		self.scrollbar_expand.set(if hovering_bar { 1.0 } else { 0.0 });
+		redraw |= self.scrollbar_opacity.is_animating() || self.scrollbar_expand.is_animating();
+
+		// scrolling itself is dispatched by the layout through `scroll_by`, so nested cards can chain.
+		let force_draggable = self.inner.scroll.is_enabled();
+		self.signals.generate_signals(
 			app, 
 			&mut self.inner, 
 			state, 
@@ -416,40 +1129,65 @@ impl<S: Signal, A: App<Signal = S>> Widget for Card<S, A> {
 			force_draggable
 		);
 
-		redraw | if let Some(delta) = res.drag_delta {
-			let delta = - delta;
-			match &mut self.inner.scroll {
-				Scroll::Off => false,
-				Scroll::Vertical{current, maximum} => {
-					let maxium = maximum.unwrap_or(self.actual_size.y - self.inner_size.y).max(0.0);
-					current.set_by(delta.y);
-					current.clamp(0.0, maxium);
-					current.is_animating()
-				},
-				Scroll::Horizontal{current, maximum} => {
-					let maxium = maximum.unwrap_or(self.actual_size.x - self.inner_size.x).max(0.0);
-					current.set_by(delta.x);
-					current.clamp(0.0, maxium);
-					current.is_animating()
-				},
-				Scroll::Both{
-					current_vertical, 
-					current_horizontal, 
-					maximum_vertical, 
-					maximum_horizontal
-				} => {
-					let maxium_vertical = maximum_vertical.unwrap_or(self.actual_size.y - self.inner_size.y).max(0.0);
-					let maxium_horizontal = maximum_horizontal.unwrap_or(self.actual_size.x - self.inner_size.x).max(0.0);
-					current_vertical.set_by(delta.y);
-					current_horizontal.set_by(delta.x);
-					current_vertical.clamp(0.0, maxium_vertical);
-					current_horizontal.clamp(0.0, maxium_horizontal);
-					current_horizontal.is_animating() || current_vertical.is_animating()
-				},
-			}
-		}else {
-			false
+		redraw | self.is_scroll_animating()
+	}
+
+	fn scroll_by(&mut self, delta: Vec2, source: ScrollSource) -> Vec2 {
+		let factor = match source {
+			ScrollSource::Wheel if self.inner.natural_scrolling => - self.inner.wheel_sensitivity,
+			ScrollSource::Wheel => self.inner.wheel_sensitivity,
+			ScrollSource::Drag => 1.0,
+		};
+		if factor == 0.0 {
+			return delta;
 		}
+		// the remaining delta is given back to the parent in the unit it was received.
+		let remaining = - self.apply_scroll(- delta * factor, source) / factor;
+		self.scrolled |= remaining != delta;
+		match self.inner.overscroll {
+			OverscrollPolicy::Chain => remaining,
+			OverscrollPolicy::Contain if !self.inner.scroll.is_enabled() => remaining,
+			OverscrollPolicy::Contain => Vec2::ZERO,
+		}
+	}
+
+	fn scroll_action(&mut self, action: ScrollAction) -> bool {
+		// page and jump actions go along the vertical axis if it is enabled.
+		let is_vertical = self.inner.scroll.vertical.is_some();
+		let along = |value: f32| if is_vertical { Vec2::y(value) } else { Vec2::x(value) };
+		let line = self.inner.line_step;
+		let page = if is_vertical { self.inner_size.y } else { self.inner_size.x };
+		let before = self.offset();
+		let maximum = self.max_offset();
+		let target = match action {
+			ScrollAction::LineUp => before - Vec2::y(line),
+			ScrollAction::LineDown => before + Vec2::y(line),
+			ScrollAction::LineLeft => before - Vec2::x(line),
+			ScrollAction::LineRight => before + Vec2::x(line),
+			ScrollAction::PageUp => before - along(page),
+			ScrollAction::PageDown => before + along(page),
+			ScrollAction::Home if is_vertical => Vec2::new(before.x, 0.0),
+			ScrollAction::Home => Vec2::new(0.0, before.y),
+			ScrollAction::End if is_vertical => Vec2::new(before.x, maximum.y),
+			ScrollAction::End => Vec2::new(maximum.x, before.y),
+		};
+		self.set_offset(target);
+		let scrolled = self.offset() != before;
+		self.scrolled |= scrolled;
+		scrolled
+	}
+
+	fn scroll_to_reveal(&mut self, area: Rect) -> Vec2 {
+		let current = self.scroll_pos();
+		let before = self.offset();
+		// the area in the coordinates of the contents.
+		let area = area.move_by(current);
+		self.set_offset(Vec2::new(
+			reveal_offset(area.x, area.w, before.x, self.inner_size.x),
+			reveal_offset(area.y, area.h, before.y, self.inner_size.y),
+		));
+		self.scrolled |= self.offset() != before;
+		self.offset() - current
 	}
 
 	fn draw(&mut self, painter: &mut Painter, size: Vec2) {
@@ -464,6 +1202,7 @@ impl<S: Signal, A: App<Signal = S>> Widget for Card<S, A> {
 		let desired_rect = Rect::from_size(desired_size);
 		let clip_rect = Rect::from_size(size);
 		let rect_to_draw = desired_rect & clip_rect;
+		let rounding = self.rounding.value();
 		let width = 1.5;
 
 		// println!("{}, {}", self.actual_size, self.inner_size);
@@ -472,75 +1211,57 @@ impl<S: Signal, A: App<Signal = S>> Widget for Card<S, A> {
 			let lt = rect_to_draw.lt() + Vec2::x(*width);
 			let card_size = rect_to_draw.size() - Vec2::x(*width);
 			painter.set_fill_mode(color.clone());
-			painter.draw_rect(rect_to_draw, self.inner.rounding);
+			painter.draw_rect(rect_to_draw, rounding);
 			painter.set_fill_mode(self.inner.background_color.clone());
-			painter.draw_rect(Rect::from_lt_size(lt, card_size), self.inner.rounding);
+			painter.draw_rect(Rect::from_lt_size(lt, card_size), rounding);
 		}else {
 			painter.set_fill_mode(self.inner.background_color.clone());
-			painter.draw_rect(rect_to_draw, self.inner.rounding);
+			painter.draw_rect(rect_to_draw, rounding);
 		}
 		
-		fn draw_scroll_bar(painter: &mut Painter, current: f32, maximum: f32, size: Vec2, is_vertical: bool) {
-			// println!("{maximum}, {size}");
+		fn draw_scroll_bar(painter: &mut Painter, style: &ScrollbarStyle, (current, maximum, is_vertical): (f32, f32, bool), size: Vec2, expand: f32, opacity: f32) {
 			if maximum <= 0.0 {
 				return;
 			}
 
-			let scroll_bar_size = if is_vertical {
-				Vec2::new(4.0, size.y - 8.0)
+			let track = style.track_rect(size, is_vertical, expand);
+			let (track_length, view_length) = if is_vertical {
+				(track.height(), size.y)
 			}else {
-				Vec2::new(size.x - 8.0, 4.0)
+				(track.width(), size.x)
 			};
-
-			let scroll_bar_pos = if is_vertical {
-				Vec2::new(size.x - 8.0, 4.0)
+			let thumb_length = (track_length * view_length / (maximum + view_length)).max(style.min_thumb_size).min(track_length);
+			let thumb_offset = current / maximum * (track_length - thumb_length);
+			let thumb = if is_vertical {
+				Rect::from_lt_size(track.lt() + Vec2::y(thumb_offset), Vec2::new(track.width(), thumb_length))
 			}else {
-				Vec2::new(4.0, size.y - 8.0)
+				Rect::from_lt_size(track.lt() + Vec2::x(thumb_offset), Vec2::new(thumb_length, track.height()))
 			};
+			let rounding = Vec4::same(style.rounding.min(track.width().min(track.height()) / 2.0));
 
-			let scroll_size = if is_vertical {
-				Vec2::new(4.0, scroll_bar_size.y * size.y / (maximum + size.y))
-			}else {
-				Vec2::new(scroll_bar_size.x * size.x / (maximum + size.x), 4.0)
-			};
-
-			let scroll_pos = if is_vertical {
-				Vec2::new(size.x - 8.0, current / maximum * (scroll_bar_size.y - scroll_size.y) + 4.0)
-			}else {
-				Vec2::new(current / maximum * (scroll_bar_size.x - scroll_size.x) + 4.0, size.y - 8.0)
-			};
-
-			painter.set_fill_mode(BACKGROUND_COLOR);
-			painter.draw_rect(Rect::from_lt_size(scroll_bar_pos, scroll_bar_size), Vec4::same(2.0));
-			painter.set_fill_mode(PRIMARY_COLOR);
-			painter.draw_rect(Rect::from_lt_size(scroll_pos, scroll_size), Vec4::same(2.0));
+			if let Some(track_color) = &style.track_color {
+				let mut track_color = track_color.clone();
+				track_color.mul_alpha(opacity);
+				painter.set_fill_mode(track_color);
+				painter.draw_rect(track, rounding);
+			}
+			let mut thumb_color = style.thumb_color.clone();
+			thumb_color.mul_alpha(opacity);
+			painter.set_fill_mode(thumb_color);
+			painter.draw_rect(thumb, rounding);
 		}
 
-		match &self.inner.scroll {
-			Scroll::Off => {},
-			Scroll::Vertical{current, maximum} => {
-				let maxium = maximum.unwrap_or(self.actual_size.y - self.inner_size.y).max(0.0);
-				let current = current.value().clamp(0.0, maxium);
-				draw_scroll_bar(painter, current, maxium, size, true);
-			},
-			Scroll::Horizontal{current, maximum} => {
-				let maxium = maximum.unwrap_or(self.actual_size.x - self.inner_size.x).max(0.0);
-				let current = current.value().clamp(0.0, maxium);
-				draw_scroll_bar(painter, current, maxium, size, false);
-			},
-			Scroll::Both{current_vertical, current_horizontal, maximum_vertical, maximum_horizontal} => {
-				let maxium_vertical = maximum_vertical.unwrap_or(self.actual_size.y - self.inner_size.y).max(0.0);
-				let maxium_horizontal = maximum_horizontal.unwrap_or(self.actual_size.x - self.inner_size.x).max(0.0);
-				let current_vertical = current_vertical.value().clamp(0.0, maxium_vertical);
-				let current_horizontal = current_horizontal.value().clamp(0.0, maxium_horizontal);
-				draw_scroll_bar(painter, current_vertical, maxium_vertical, size, true);
-				draw_scroll_bar(painter, current_horizontal, maxium_horizontal, size, false);
+		let opacity = self.scrollbar_opacity.value();
+		if opacity > 0.0 {
+			let expand = self.scrollbar_expand.value();
+			for bar in self.scroll_bars() {
+				draw_scroll_bar(painter, &self.inner.scrollbar, bar, size, expand, opacity);
 			}
 		}
 
 		if self.inner.draw_stroke {
 			painter.set_fill_mode(CARD_BORDER_COLOR);
-			painter.draw_stroked_rect(rect_to_draw.shrink(Vec2::same(width)), self.inner.rounding, width);
+			painter.draw_stroked_rect(rect_to_draw.shrink(Vec2::same(width)), rounding, width);
 		}
 
 	}
@@ -562,6 +1283,7 @@ impl<S: Signal, A: App<Signal = S>> Widget for Card<S, A> {
 			return HashMap::new();
 		}
 		
+		let childs = self.apply_flex(childs, size);
 		let mut child_positions = HashMap::new();
 		match &self.inner.layout_strategy.typesetting {
 			Typesetting::Horizontal => {
@@ -737,6 +1459,112 @@ impl<S: Signal, A: App<Signal = S>> Widget for Card<S, A> {
 
 				self.actual_size = size;
 			},
+			Typesetting::AutoGrid(grid) => {
+				let padding = self.inner.layout_strategy.padding;
+				let columns = grid.columns.max(1);
+
+				// place the children row by row, the cells taken by the spanning children are skipped.
+				let mut taken: Vec<Vec<bool>> = vec!();
+				let mut cursor = 0;
+				let mut cells = vec!();
+				for (id, child_size) in childs {
+					if child_size.x < 0.0 || child_size.y < 0.0 {
+						continue;
+					}
+
+					if let Some(fixed_position) = self.inner.fixed_children.get(&id) {
+						child_positions.insert(id, Rect::from_lt_size(*fixed_position, child_size));
+						continue;
+					}
+
+					let cell = grid.cells.get(&id).copied().unwrap_or_default();
+					let column_span = cell.column_span.clamp(1, columns);
+					let row_span = cell.row_span.max(1);
+					let fits = |taken: &Vec<Vec<bool>>, index: usize| {
+						let (row, column) = (index / columns, index % columns);
+						column + column_span <= columns && (row..row + row_span).all(|row| {
+							(column..column + column_span).all(|column| !taken.get(row).is_some_and(|taken| taken[column]))
+						})
+					};
+					while !fits(&taken, cursor) {
+						cursor += 1;
+					}
+
+					let (row, column) = (cursor / columns, cursor % columns);
+					if taken.len() < row + row_span {
+						taken.resize(row + row_span, vec![false; columns]);
+					}
+					for taken_row in &mut taken[row..row + row_span] {
+						taken_row[column..column + column_span].fill(true);
+					}
+					cells.push((id, child_size, [column, row], [column_span, row_span], cell.alignment));
+				}
+
+				// the columns and rows fit their single cell children first, 
+				// then the last column or row covered by a spanning child grows if the child does not fit.
+				let mut lengths = [vec![0.0_f32; columns], vec![0.0_f32; taken.len()]];
+				for single in [true, false] {
+					for (_, child_size, start, span, _) in &cells {
+						for axis in 0..2 {
+							if (span[axis] == 1) != single {
+								continue;
+							}
+							let lengths = &mut lengths[axis][start[axis]..start[axis] + span[axis]];
+							let gap = if axis == 0 { padding.x } else { padding.y };
+							let covered = lengths.iter().sum::<f32>() + gap * (span[axis] - 1) as f32;
+							let wanted = if axis == 0 { child_size.x } else { child_size.y };
+							lengths[span[axis] - 1] += (wanted - covered).max(0.0);
+						}
+					}
+				}
+				let offsets = [(&lengths[0], padding.x), (&lengths[1], padding.y)].map(|(lengths, gap)| {
+					lengths.iter().scan(gap, |next, length| {
+						let offset = *next;
+						*next += length + gap;
+						Some(offset)
+					}).collect::<Vec<_>>()
+				});
+
+				for (id, child_size, start, span, alignment) in cells {
+					let cell_lt = Vec2::new(offsets[0][start[0]], offsets[1][start[1]]);
+					let end = [start[0] + span[0] - 1, start[1] + span[1] - 1];
+					let cell_size = Vec2::new(offsets[0][end[0]] + lengths[0][end[0]], offsets[1][end[1]] + lengths[1][end[1]]) - cell_lt;
+
+					let alignment = alignment.unwrap_or(self.inner.layout_strategy.alignment);
+					let mut child_position = cell_lt;
+					match alignment[0] {
+						Alignment::Positive => {}
+						Alignment::Center => {
+							child_position.x += (cell_size.x - child_size.x) / 2.0;
+						}
+						Alignment::Negative => {
+							child_position.x += cell_size.x - child_size.x;
+						}
+					}
+
+					match alignment[1] {
+						Alignment::Positive => {}
+						Alignment::Center => {
+							child_position.y += (cell_size.y - child_size.y) / 2.0;
+						}
+						Alignment::Negative => {
+							child_position.y += cell_size.y - child_size.y;
+						}
+					}
+
+					let rect = Rect::from_lt_size(child_position - self.scroll_pos(), child_size);
+					if (rect.move_by(area.lt()) & area).is_empty() {
+						continue;
+					}
+
+					child_positions.insert(id, rect);
+				}
+
+				let total = [(&lengths[0], padding.x), (&lengths[1], padding.y)].map(|(lengths, gap)| {
+					lengths.iter().sum::<f32>() + gap * lengths.len().saturating_sub(1) as f32
+				});
+				self.actual_size = padding + Vec2::new(total[0], total[1]);
+			},
 		}
 
 		child_positions.into_iter().map(|(id, rect)| (id, Some(rect))).collect()
@@ -745,4 +1573,16 @@ impl<S: Signal, A: App<Signal = S>> Widget for Card<S, A> {
 	fn inner_padding(&self) -> Vec2 {
 		self.inner.layout_strategy.padding
 	}
+
+	fn save_state(&self) -> Option<Box<dyn Any>> {
+		Some(Box::new((self.rounding.clone(), self.inner.scroll.clone())))
+	}
+
+	fn restore_state(&mut self, state: Box<dyn Any>) {
+		if let Ok(state) = state.downcast::<(AnimatedColor, Scroll)>() {
+			let (rounding, scroll) = *state;
+			self.rounding.continue_from(&rounding);
+			self.inner.scroll.continue_from(&scroll);
+		}
+	}
 }
\ No newline at end of file
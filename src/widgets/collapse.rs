@@ -1,10 +1,10 @@
 //! A widget that can be collapsed or expanded.
 
-use std::{collections::HashMap, f32::consts::PI};
+use std::{any::Any, collections::HashMap, f32::consts::PI};
 
 use indexmap::IndexMap;
 
-use crate::{layout::{Layout, LayoutId}, prelude::{Animatedf32, FillMode, FontId, InputState, Painter, Rect, Transform2D, Vec2, Vec4}, App};
+use crate::{layout::{Layout, LayoutId}, prelude::{Animatedf32, FillMode, FontId, FontRole, InputState, Painter, Rect, Transform2D, Vec2, Vec4}, App};
 
 use super::{styles::{CARD_BORDER_COLOR, CONTENT_TEXT_SIZE, SECONDARY_TEXT_COLOR}, Signal, SignalGenerator, Widget};
 
@@ -26,6 +26,8 @@ pub struct Collapse<S: Signal, A: App<Signal = S>> {
 pub struct CollapseInner {
 	/// The font id of the title.
 	pub font: FontId,
+	/// The font role of the title, see [`FontRole`].
+	pub font_role: Option<FontRole>,
 	/// The font size of the title.
 	pub font_size: f32,
 	/// The padding of each element.
@@ -42,6 +44,7 @@ impl Default for CollapseInner {
 	fn default() -> Self {
 		Self {
 			font: 0,
+			font_role: Some(FontRole::Body),
 			font_size: CONTENT_TEXT_SIZE,
 			padding: CONTENT_TEXT_SIZE,
 			font_color: FillMode::Color(SECONDARY_TEXT_COLOR),
@@ -90,9 +93,19 @@ impl<S: Signal, A: App<Signal = S>> Collapse<S, A> {
 	}
 
 	/// sets the font id of the title.
+	/// 
+	/// Clears the font role, so the font is used even if the theme registers one, see [`Self::font_role`].
 	pub fn font(self, font: FontId) -> Self {
 		Self {
-			inner: CollapseInner { font, ..self.inner },
+			inner: CollapseInner { font, font_role: None, ..self.inner },
+			..self
+		}
+	}
+
+	/// Sets the font role of the title, `None` to always use the font id.
+	pub fn font_role(self, font_role: Option<FontRole>) -> Self {
+		Self {
+			inner: CollapseInner { font_role, ..self.inner },
 			..self
 		}
 	}
@@ -148,7 +161,7 @@ impl<S: Signal, A: App<Signal = S>> Widget for Collapse<S, A> {
 	}
 
 	fn size(&self, _: LayoutId, painter: &Painter, _: &Layout<Self::Signal, A>) -> Vec2 {
-		let title_size = Vec2::x(self.inner.font_size) + painter.text_size(self.inner.font, self.inner.font_size, &self.inner.title).unwrap_or(Vec2::ZERO);
+		let title_size = Vec2::x(self.inner.font_size) + painter.text_size(painter.resolve_font(self.inner.font_role, self.inner.font), self.inner.font_size, &self.inner.title).unwrap_or(Vec2::ZERO);
 		if self.inner.collapsed || self.inner_size == Vec2::ZERO {
 			title_size
 		}else {
@@ -158,7 +171,7 @@ impl<S: Signal, A: App<Signal = S>> Widget for Collapse<S, A> {
 
 	fn draw(&mut self, painter: &mut Painter, _: Vec2) {
 		let size = painter.clip_rect().rb() - painter.releative_to();
-		let title_size = painter.text_size(self.inner.font, self.inner.font_size, &self.inner.title).unwrap_or(Vec2::ZERO);
+		let title_size = painter.text_size(painter.resolve_font(self.inner.font_role, self.inner.font), self.inner.font_size, &self.inner.title).unwrap_or(Vec2::ZERO);
 		self.title_size = title_size + Vec2::same(self.inner.font_size);
 		if !self.inner.collapsed {
 			painter.set_fill_mode(CARD_BORDER_COLOR);
@@ -171,7 +184,7 @@ impl<S: Signal, A: App<Signal = S>> Widget for Collapse<S, A> {
 			);
 		}
 		painter.set_fill_mode(self.inner.font_color.clone());
-		painter.draw_text(Vec2::x(self.inner.font_size), self.inner.font, self.inner.font_size, &self.inner.title);
+		painter.draw_text(Vec2::x(self.inner.font_size), painter.resolve_font(self.inner.font_role, self.inner.font), self.inner.font_size, &self.inner.title);
 		let transform = Transform2D::translate(painter.releative_to())
 			>> Transform2D::translate(Vec2::same(self.inner.font_size / 2.0))
 			>> Transform2D::scale(Vec2::same(0.5))
@@ -206,4 +219,14 @@ impl<S: Signal, A: App<Signal = S>> Widget for Collapse<S, A> {
 	fn inner_padding(&self) -> Vec2 {
 		Vec2::same(self.inner.padding)
 	}
+
+	fn save_state(&self) -> Option<Box<dyn Any>> {
+		Some(Box::new(self.rotate_factor.clone()))
+	}
+
+	fn restore_state(&mut self, state: Box<dyn Any>) {
+		if let Ok(rotate_factor) = state.downcast::<Animatedf32>() {
+			self.rotate_factor.continue_from(&rotate_factor);
+		}
+	}
 }
\ No newline at end of file
@@ -1,21 +1,549 @@
-// //! A color picker widget for the Nablo UI library.
-
-// use crate::prelude::Vec2;
-
-// use super::SignalGenerator;
-
-// /// A color picker widget for the Nablo UI library.
-// pub struct ColorPicker<S: Signal> {
-// 	/// The inner properties of color picker
-// 	pub inner: ColorPickerInner,
-// 	/// The signals emitted by the color picker
-// 	pub signals: SignalGenerator<S, ColorPickerInner>,
-// }
-
-// /// The inner properties of color picker
-// pub struct ColorPickerInner {
-// 	/// The current color of the color picker
-// 	pub color: Color,
-// 	/// The size of the color picker
-// 	pub size: Vec2,
-// }
\ No newline at end of file
+//! A color picker widget for the Nablo UI library.
+
+use std::{any::Any, cell::RefCell, collections::HashMap, rc::Rc};
+
+use indexmap::IndexMap;
+
+use crate::{layout::{Layout, LayoutId}, prelude::{Color, FillMode, FontId, InputState, Painter, Rect, Vec2, Vec4}, App};
+
+use super::{inputbox::InputBox, styles::{CARD_BORDER_COLOR, CARD_COLOR, CONTENT_TEXT_SIZE, DEFAULT_PADDING, DEFAULT_ROUNDING}, EventHandleStrategy, Signal, SignalGenerator, Widget};
+
+/// A color picker widget for the Nablo UI library.
+///
+/// The saturation and value are picked on a square tinted by the hue, the hue and alpha on the bars below it.
+/// Under the bars, a swatch shows the picked color next to an [`InputBox`] taking hex colors,
+/// such as `#ff8800` or `#ff880080`, the input box is added as the only child of the picker when it first handles events.
+///
+/// The hue and saturation are kept while the color is black or gray, so picking them does not jump back.
+pub struct ColorPicker<S: Signal, A: App<Signal = S>> {
+	/// The inner properties of the color picker.
+	pub inner: ColorPickerInner,
+	/// The signals emitted by the color picker.
+	pub signals: SignalGenerator<S, ColorPickerInner, A>,
+	/// The signal to send when the color is changed by the user, given the new color.
+	#[allow(clippy::type_complexity)]
+	pub on_change: Option<Box<dyn Fn(&mut A, Color) -> S>>,
+	/// the touch dragging a part of the picker.
+	dragging: Option<(u64, Part)>,
+	/// the state shared with the hex input, `None` before it is added.
+	hex: Option<Rc<RefCell<HexState>>>,
+	hex_id: Option<LayoutId>,
+	hex_focused: bool,
+}
+
+/// The inner properties of the color picker.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ColorPickerInner {
+	/// The hue of the color in degrees, from 0 to 360.
+	pub hue: f32,
+	/// The saturation of the color, from 0 to 1.
+	pub saturation: f32,
+	/// The value of the color, from 0 to 1.
+	pub value: f32,
+	/// The alpha of the color, from 0 to 1.
+	pub alpha: f32,
+	/// Whether to show the alpha bar, the alpha is kept as is when hidden.
+	pub show_alpha: bool,
+	/// Whether to show the swatch and the hex input.
+	pub show_hex: bool,
+	/// The side length of the saturation and value square.
+	pub square_size: f32,
+	/// The height of the hue and alpha bars.
+	pub bar_height: f32,
+	/// The font of the hex input.
+	pub font: FontId,
+	/// The font size of the hex input.
+	pub font_size: f32,
+	/// The padding around the picker.
+	pub padding: f32,
+	/// The space between the parts of the picker.
+	pub spacing: f32,
+	/// The background color of the picker.
+	pub background_color: FillMode,
+	/// The border color of the picker.
+	pub border_color: FillMode,
+	/// The roundings of the picker.
+	pub roundings: Vec4,
+}
+
+impl Default for ColorPickerInner {
+	fn default() -> Self {
+		Self {
+			hue: 0.0,
+			saturation: 1.0,
+			value: 1.0,
+			alpha: 1.0,
+			show_alpha: true,
+			show_hex: true,
+			square_size: 160.0,
+			bar_height: CONTENT_TEXT_SIZE * 0.75,
+			font: 0,
+			font_size: CONTENT_TEXT_SIZE,
+			padding: DEFAULT_PADDING,
+			spacing: DEFAULT_PADDING,
+			background_color: FillMode::Color(CARD_COLOR),
+			border_color: FillMode::Color(CARD_BORDER_COLOR),
+			roundings: Vec4::same(DEFAULT_ROUNDING),
+		}
+	}
+}
+
+impl ColorPickerInner {
+	/// Get the picked color.
+	pub fn color(&self) -> Color {
+		Color::from_hsva(self.hue, self.saturation, self.value, self.alpha)
+	}
+
+	/// Set the picked color.
+	///
+	/// The hue is kept if the color is gray, and the saturation is kept if it is black.
+	pub fn set_color(&mut self, color: Color) {
+		let hsva = color.clamp(0.0, 1.0).to_hsva();
+		if hsva.b > 0.0 {
+			if hsva.g > 0.0 {
+				self.hue = hsva.r;
+			}
+			self.saturation = hsva.g;
+		}
+		self.value = hsva.b;
+		self.alpha = hsva.a;
+	}
+
+	/// Get the picked color as a hex string, such as `#ff8800`, the alpha is appended when the alpha bar is shown.
+	pub fn hex(&self) -> String {
+		let color = self.color().clamp(0.0, 1.0);
+		let byte = |value: f32| (value * 255.0).round() as u8;
+		if self.show_alpha {
+			format!("#{:02x}{:02x}{:02x}{:02x}", byte(color.r), byte(color.g), byte(color.b), byte(color.a))
+		}else {
+			format!("#{:02x}{:02x}{:02x}", byte(color.r), byte(color.g), byte(color.b))
+		}
+	}
+
+	fn bar_rect(&self, index: usize) -> Rect {
+		let top = self.padding + self.square_size + self.spacing + (self.bar_height + self.spacing) * index as f32;
+		Rect::new(self.padding, top, self.square_size, self.bar_height)
+	}
+
+	fn square_rect(&self) -> Rect {
+		Rect::new(self.padding, self.padding, self.square_size, self.square_size)
+	}
+
+	fn hue_rect(&self) -> Rect {
+		self.bar_rect(0)
+	}
+
+	fn alpha_rect(&self) -> Option<Rect> {
+		self.show_alpha.then(|| self.bar_rect(1))
+	}
+
+	fn hex_height(&self) -> f32 {
+		self.font_size + DEFAULT_PADDING
+	}
+
+	/// the swatch and the hex input.
+	fn hex_rects(&self) -> Option<(Rect, Rect)> {
+		if !self.show_hex {
+			return None;
+		}
+		let top = self.bar_rect(if self.show_alpha { 2 } else { 1 }).y;
+		let height = self.hex_height();
+		let swatch = Rect::new(self.padding, top, height, height);
+		let input = Rect::new(self.padding + height + self.spacing, top, (self.square_size - height - self.spacing).max(0.0), height);
+		Some((swatch, input))
+	}
+
+	fn bottom(&self) -> f32 {
+		match self.hex_rects() {
+			Some((swatch, _)) => swatch.rb().y,
+			None => self.alpha_rect().unwrap_or_else(|| self.hue_rect()).rb().y,
+		}
+	}
+}
+
+/// Parse a hex color such as `#ff8800` or `ff880080`, returns `None` if the text is not a color.
+pub fn parse_hex_color(text: &str) -> Option<Color> {
+	let text = text.trim();
+	let digits = text.strip_prefix('#').unwrap_or(text);
+	if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+		return None;
+	}
+	let value = u32::from_str_radix(digits, 16).ok()?;
+	match digits.len() {
+		6 => Some(Color::from_hex((value << 8) | 0xff)),
+		8 => Some(Color::from_hex(value)),
+		_ => None,
+	}
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Part {
+	Square,
+	Hue,
+	Alpha,
+}
+
+/// the state shared between the picker and its hex input.
+#[derive(Default)]
+struct HexState {
+	/// the hex of the picked color, shown while the input is not focused.
+	text: String,
+	/// a color typed into the input, taken by the picker.
+	typed: Option<Color>,
+}
+
+impl<S: Signal, A: App<Signal = S>> Default for ColorPicker<S, A> {
+	fn default() -> Self {
+		Self {
+			inner: ColorPickerInner::default(),
+			signals: SignalGenerator::default(),
+			on_change: None,
+			dragging: None,
+			hex: None,
+			hex_id: None,
+			hex_focused: false,
+		}
+	}
+}
+
+impl<S: Signal, A: App<Signal = S>> ColorPicker<S, A> {
+	/// Create a new color picker with the given color.
+	pub fn new(color: impl Into<Color>) -> Self {
+		let mut out = Self::default();
+		out.inner.set_color(color.into());
+		out
+	}
+
+	/// Set the picked color.
+	pub fn color(mut self, color: impl Into<Color>) -> Self {
+		self.inner.set_color(color.into());
+		self
+	}
+
+	/// Set whether to show the alpha bar.
+	pub fn show_alpha(self, show_alpha: bool) -> Self {
+		Self { inner: ColorPickerInner { show_alpha, ..self.inner }, ..self }
+	}
+
+	/// Set whether to show the swatch and the hex input.
+	pub fn show_hex(self, show_hex: bool) -> Self {
+		Self { inner: ColorPickerInner { show_hex, ..self.inner }, ..self }
+	}
+
+	/// Set the side length of the saturation and value square.
+	pub fn square_size(self, square_size: f32) -> Self {
+		Self { inner: ColorPickerInner { square_size, ..self.inner }, ..self }
+	}
+
+	/// Set the height of the hue and alpha bars.
+	pub fn bar_height(self, bar_height: f32) -> Self {
+		Self { inner: ColorPickerInner { bar_height, ..self.inner }, ..self }
+	}
+
+	/// Set the font of the hex input.
+	pub fn font(self, font: FontId) -> Self {
+		Self { inner: ColorPickerInner { font, ..self.inner }, ..self }
+	}
+
+	/// Set the font size of the hex input.
+	pub fn font_size(self, font_size: f32) -> Self {
+		Self { inner: ColorPickerInner { font_size, ..self.inner }, ..self }
+	}
+
+	/// Set the padding around the picker.
+	pub fn padding(self, padding: f32) -> Self {
+		Self { inner: ColorPickerInner { padding, ..self.inner }, ..self }
+	}
+
+	/// Set the space between the parts of the picker.
+	pub fn spacing(self, spacing: f32) -> Self {
+		Self { inner: ColorPickerInner { spacing, ..self.inner }, ..self }
+	}
+
+	/// Set the background color of the picker.
+	pub fn background_color(self, background_color: impl Into<FillMode>) -> Self {
+		Self { inner: ColorPickerInner { background_color: background_color.into(), ..self.inner }, ..self }
+	}
+
+	/// Set the border color of the picker.
+	pub fn border_color(self, border_color: impl Into<FillMode>) -> Self {
+		Self { inner: ColorPickerInner { border_color: border_color.into(), ..self.inner }, ..self }
+	}
+
+	/// Set the roundings of the picker.
+	pub fn roundings(self, roundings: impl Into<Vec4>) -> Self {
+		Self { inner: ColorPickerInner { roundings: roundings.into(), ..self.inner }, ..self }
+	}
+
+	/// Set the signal to send when the color is changed by the user.
+	pub fn on_change(self, on_change: impl Fn(&mut A, Color) -> S + 'static) -> Self {
+		Self {
+			on_change: Some(Box::new(on_change)),
+			..self
+		}
+	}
+
+	fn part_at(&self, pos: Vec2) -> Option<Part> {
+		if self.inner.square_rect().contains(pos) {
+			Some(Part::Square)
+		}else if self.inner.hue_rect().contains(pos) {
+			Some(Part::Hue)
+		}else if self.inner.alpha_rect().is_some_and(|rect| rect.contains(pos)) {
+			Some(Part::Alpha)
+		}else {
+			None
+		}
+	}
+
+	/// Pick the value of the part under the position relative to the picker, returns whether the color changed.
+	fn pick(&mut self, part: Part, pos: Vec2) -> bool {
+		let before = self.inner.clone();
+		let fraction = |rect: Rect| ((pos - rect.lt()) / rect.size()).clamp(0.0, 1.0);
+		match part {
+			Part::Square => {
+				let fraction = fraction(self.inner.square_rect());
+				self.inner.saturation = fraction.x;
+				self.inner.value = 1.0 - fraction.y;
+			},
+			Part::Hue => self.inner.hue = fraction(self.inner.hue_rect()).x * 360.0,
+			Part::Alpha => if let Some(rect) = self.inner.alpha_rect() {
+				self.inner.alpha = fraction(rect).x;
+			},
+		}
+		before != self.inner
+	}
+}
+
+/// Draw a checkerboard showing through transparent colors.
+fn draw_checker(painter: &mut Painter, rect: Rect, cell: f32) {
+	let clip = painter.clip_rect();
+	painter.set_clip_rect(clip & Rect::from_lt_size(painter.releative_to() + rect.lt(), rect.size()));
+	painter.set_fill_mode(Color::from_gray_f32(0.8));
+	painter.draw_rect(rect, Vec4::ZERO);
+	painter.set_fill_mode(Color::from_gray_f32(0.55));
+	let columns = (rect.width() / cell).ceil() as usize;
+	let rows = (rect.height() / cell).ceil() as usize;
+	for row in 0..rows {
+		for column in (row % 2..columns).step_by(2) {
+			painter.draw_rect(Rect::new(rect.x + column as f32 * cell, rect.y + row as f32 * cell, cell, cell), Vec4::ZERO);
+		}
+	}
+	painter.set_clip_rect(clip);
+}
+
+/// Draw the marker of a position on a bar.
+fn draw_bar_marker(painter: &mut Painter, rect: Rect, fraction: f32) {
+	let x = rect.x + rect.width() * fraction;
+	let marker = Rect::new(x - 2.0, rect.y - 2.0, 4.0, rect.height() + 4.0);
+	painter.set_fill_mode(Color::BLACK);
+	painter.draw_stroked_rect(marker.inflate(1.0, 1.0, 1.0, 1.0), Vec4::same(3.0), 1.0);
+	painter.set_fill_mode(Color::WHITE);
+	painter.draw_stroked_rect(marker, Vec4::same(2.0), 2.0);
+}
+
+impl<S: Signal, A: App<Signal = S>> Widget for ColorPicker<S, A> {
+	type Signal = S;
+	type Application = A;
+
+	fn size(&self, _: LayoutId, _: &Painter, _: &Layout<Self::Signal, Self::Application>) -> Vec2 {
+		Vec2::new(self.inner.square_size + self.inner.padding * 2.0, self.inner.bottom() + self.inner.padding)
+	}
+
+	fn draw(&mut self, painter: &mut Painter, size: Vec2) {
+		painter.set_fill_mode(self.inner.background_color.clone());
+		painter.draw_rect(Rect::from_size(size), self.inner.roundings);
+		painter.set_fill_mode(self.inner.border_color.clone());
+		painter.draw_stroked_rect(Rect::from_size(size), self.inner.roundings, 1.0);
+
+		let radius = self.inner.bar_height / 4.0;
+		let rounding = Vec4::same(radius);
+		let square = self.inner.square_rect();
+		painter.set_fill_mode(Color::from_hsv(self.inner.hue, 1.0, 1.0));
+		painter.draw_rect(square, rounding);
+		painter.set_fill_mode(FillMode::LinearGradient(Color::WHITE, Color::new(1.0, 1.0, 1.0, 0.0), square.lt(), square.rt()));
+		painter.draw_rect(square, rounding);
+		painter.set_fill_mode(FillMode::LinearGradient(Color::new(0.0, 0.0, 0.0, 0.0), Color::BLACK, square.lt(), square.lb()));
+		painter.draw_rect(square, rounding);
+		let marker = square.lt() + Vec2::new(self.inner.saturation, 1.0 - self.inner.value) * square.size();
+		let marker_radius = self.inner.bar_height / 3.0;
+		painter.set_fill_mode(Color::BLACK);
+		painter.draw_stroked_circle(marker, marker_radius + 1.0, 1.0);
+		painter.set_fill_mode(Color::WHITE);
+		painter.draw_stroked_circle(marker, marker_radius, 2.0);
+
+		let hue = self.inner.hue_rect();
+		let segment = hue.width() / 6.0;
+		for index in 0..6 {
+			let left = hue.x + segment * index as f32;
+			let from = Color::from_hsv(index as f32 * 60.0, 1.0, 1.0);
+			let to = Color::from_hsv((index + 1) as f32 * 60.0, 1.0, 1.0);
+			let roundings = match index {
+				0 => Vec4::new(radius, 0.0, 0.0, radius),
+				5 => Vec4::new(0.0, radius, radius, 0.0),
+				_ => Vec4::ZERO,
+			};
+			painter.set_fill_mode(FillMode::LinearGradient(from, to, Vec2::new(left, hue.y), Vec2::new(left + segment, hue.y)));
+			// overlapped by a pixel so no seam shows between the segments.
+			painter.draw_rect(Rect::new(left, hue.y, segment + if index == 5 { 0.0 } else { 1.0 }, hue.height()), roundings);
+		}
+		draw_bar_marker(painter, hue, self.inner.hue / 360.0);
+
+		let color = self.inner.color();
+		if let Some(alpha) = self.inner.alpha_rect() {
+			draw_checker(painter, alpha, alpha.height() / 2.0);
+			painter.set_fill_mode(FillMode::LinearGradient(Color { a: 0.0, ..color }, Color { a: 1.0, ..color }, alpha.lt(), alpha.rt()));
+			painter.draw_rect(alpha, rounding);
+			draw_bar_marker(painter, alpha, self.inner.alpha);
+		}
+
+		if let Some((swatch, _)) = self.inner.hex_rects() {
+			draw_checker(painter, swatch, swatch.height() / 4.0);
+			painter.set_fill_mode(color);
+			painter.draw_rect(swatch, Vec4::ZERO);
+			painter.set_fill_mode(self.inner.border_color.clone());
+			painter.draw_stroked_rect(swatch, Vec4::ZERO, 1.0);
+		}
+	}
+
+	fn handle_event(&mut self, app: &mut A, input_state: &mut InputState<Self::Signal>, id: LayoutId, area: Rect, pos: Vec2) -> bool {
+		if self.inner.show_hex && self.hex.is_none() {
+			let state = Rc::new(RefCell::new(HexState { text: self.inner.hex(), typed: None }));
+			let (_, rect) = self.inner.hex_rects().unwrap_or_default();
+			let input = InputBox::<S, A>::new(self.inner.font, self.inner.font_size)
+				.padding(Vec2::same(DEFAULT_PADDING / 2.0))
+				.size((rect.size() - Vec2::same(DEFAULT_PADDING)).max(Vec2::ZERO))
+				.text(self.inner.hex());
+			input_state.add_child(id, HexInput { input, state: state.clone(), focused: false });
+			self.hex = Some(state);
+		}
+
+		// taken before the signals, which mark the touches as used.
+		let pressed = input_state.get_touch_pressed_on(area);
+		let res = self.signals.generate_signals(app, &mut self.inner, input_state, id, area, false, false);
+
+		let mut changed = false;
+		for touch in pressed {
+			if self.dragging.is_some_and(|(dragging, _)| dragging == touch) || input_state.is_touch_released(touch) {
+				continue;
+			}
+			let Some(part) = input_state.get_touch_pos(touch).and_then(|touch_pos| self.part_at(touch_pos - pos)) else { continue };
+			self.dragging = Some((touch, part));
+		}
+		if let Some((touch, part)) = self.dragging {
+			if let Some(touch_pos) = input_state.get_touch_pos(touch) {
+				changed |= self.pick(part, touch_pos - pos);
+			}
+			if input_state.is_touch_released(touch) {
+				self.dragging = None;
+			}
+		}
+
+		if let Some(typed) = self.hex.as_ref().and_then(|state| state.borrow_mut().typed.take()) {
+			let before = self.inner.clone();
+			self.inner.set_color(typed);
+			changed |= before != self.inner;
+		}
+
+		if changed {
+			if let Some(state) = &self.hex {
+				state.borrow_mut().text = self.inner.hex();
+			}
+			if let Some(on_change) = &self.on_change {
+				let signal = on_change(app, self.inner.color());
+				input_state.send_signal_from(id, signal);
+			}
+		}
+
+		self.hex_focused = self.hex_id.is_some_and(|hex_id| input_state.is_focused(hex_id));
+
+		changed || res.is_clicked || res.drag_delta.is_some()
+	}
+
+	fn event_handle_strategy(&self) -> EventHandleStrategy {
+		if self.dragging.is_some() || self.hex_focused {
+			// takes the colors typed into the hex input while it is focused.
+			EventHandleStrategy::AlwaysSecondary
+		}else {
+			EventHandleStrategy::OnHover
+		}
+	}
+
+	fn handle_child_layout(&mut self, childs: IndexMap<LayoutId, Vec2>, _: Rect, _: LayoutId) -> HashMap<LayoutId, Option<Rect>> {
+		self.hex_id = childs.keys().next().copied();
+		match (self.hex_id, self.inner.hex_rects()) {
+			(Some(hex_id), Some((_, input))) => HashMap::from([(hex_id, Some(input))]),
+			_ => HashMap::new(),
+		}
+	}
+
+	fn save_state(&self) -> Option<Box<dyn Any>> {
+		Some(Box::new(self.hex.clone()))
+	}
+
+	fn restore_state(&mut self, state: Box<dyn Any>) {
+		if let Ok(hex) = state.downcast::<Option<Rc<RefCell<HexState>>>>() {
+			self.hex = *hex;
+			if let Some(state) = &self.hex {
+				state.borrow_mut().text = self.inner.hex();
+			}
+		}
+	}
+}
+
+/// the hex input of a [`ColorPicker`], showing the picked color while it is not focused.
+struct HexInput<S: Signal, A: App<Signal = S>> {
+	input: InputBox<S, A>,
+	state: Rc<RefCell<HexState>>,
+	focused: bool,
+}
+
+impl<S: Signal, A: App<Signal = S>> Widget for HexInput<S, A> {
+	type Signal = S;
+	type Application = A;
+
+	fn size(&self, id: LayoutId, painter: &Painter, layout: &Layout<Self::Signal, Self::Application>) -> Vec2 {
+		Widget::size(&self.input, id, painter, layout)
+	}
+
+	fn draw(&mut self, painter: &mut Painter, size: Vec2) {
+		if !self.focused {
+			let state = self.state.borrow();
+			if self.input.inner.text != state.text {
+				self.input.inner.text = state.text.clone();
+				self.input.inner.pointer = super::inputbox::Pointer::new(0);
+			}
+		}
+		self.input.draw(painter, size);
+	}
+
+	fn handle_event(&mut self, app: &mut A, input_state: &mut InputState<Self::Signal>, id: LayoutId, area: Rect, pos: Vec2) -> bool {
+		let before = self.input.inner.text.clone();
+		let redraw = self.input.handle_event(app, input_state, id, area, pos);
+		let focused = input_state.is_focused(id);
+		if self.input.inner.text != before {
+			let mut state = self.state.borrow_mut();
+			if let Some(color) = parse_hex_color(&self.input.inner.text).filter(|_| self.input.inner.text != state.text) {
+				state.typed = Some(color);
+			}
+		}
+		let focus_changed = focused != self.focused;
+		self.focused = focused;
+		redraw || focus_changed
+	}
+
+	fn event_handle_strategy(&self) -> EventHandleStrategy {
+		self.input.event_handle_strategy()
+	}
+
+	fn focusable(&self) -> bool {
+		self.input.focusable()
+	}
+
+	fn save_state(&self) -> Option<Box<dyn Any>> {
+		self.input.save_state()
+	}
+
+	fn restore_state(&mut self, state: Box<dyn Any>) {
+		self.input.restore_state(state)
+	}
+}
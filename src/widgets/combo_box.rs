@@ -0,0 +1,614 @@
+//! A dropdown picking one of a list of items.
+
+use std::{cell::RefCell, marker::PhantomData, ops::Range, rc::{Rc, Weak}};
+
+use time::Duration;
+
+use crate::{layout::{Layout, LayoutId, OverlayAnchor}, prelude::{FillMode, FontId, FontRole, ImeString, InputState, Painter, Rect, Vec2, Vec4}, window::event::Key, App};
+
+use super::{styles::{CONTENT_TEXT_SIZE, DEFAULT_PADDING, DEFAULT_ROUNDING, INPUT_BACKGROUND_COLOR, INPUT_BORDER_COLOR, PRIMARY_TEXT_COLOR, SECONDARY_TEXT_COLOR, SELECTED_TEXT_COLOR}, EventHandleStrategy, ScrollSource, Signal, SignalGenerator, Widget};
+
+/// The maximum number of items shown in the popup of a [`ComboBox`] at once, the rest are scrolled to.
+pub const MAX_SHOWN_ITEMS: usize = 8;
+
+/// The typed text of a [`ComboBox`] is cleared when no key is typed for this long.
+pub const TYPEAHEAD_TIMEOUT: Duration = Duration::seconds(1);
+
+/// A dropdown showing the selected item, and a popup listing all the items when pressed.
+///
+/// When focused, the arrow keys move the selection, the enter and space keys open the popup
+/// and choose the highlighted item, and the escape key closes it.
+/// Typing jumps to the first item starting with the typed text, the text is cleared after [`TYPEAHEAD_TIMEOUT`].
+///
+/// ```no_run
+/// # use nablo_ui::prelude::*;
+/// enum Sig {
+///     Pick(usize),
+/// }
+/// # impl Signal for Sig {}
+/// # struct MyApp;
+/// # impl App for MyApp {
+/// #     type Signal = Sig;
+/// #     fn on_start(&mut self, _: &mut Context<Sig, Self>) {}
+/// #     fn on_signal(&mut self, _: &mut Context<Sig, Self>, _: SignalWrapper<Sig>) {}
+/// # }
+/// fn add_combo_box(layout: &mut Layout<Sig, MyApp>) {
+///     let combo_box = ComboBox::new(["Apple", "Banana", "Cherry"])
+///         .selected(Some(0))
+///         .on_select(|_, _, index| Sig::Pick(index));
+///     layout.add_widget(ROOT_LAYOUT_ID, combo_box);
+/// }
+/// ```
+pub struct ComboBox<S: Signal, A: App<Signal = S>> {
+	/// The inner properties of the combo box.
+	pub inner: ComboBoxInner,
+	/// The signals generated by the combo box.
+	pub signals: SignalGenerator<S, ComboBoxInner, A>,
+	/// The signal to send when an item is selected by the user, given the index of the item.
+	#[allow(clippy::type_complexity)]
+	pub on_select: Option<Box<dyn Fn(&mut A, &mut ComboBoxInner, usize) -> S>>,
+	popup: Rc<RefCell<PopupState>>,
+	popup_session: u64,
+	/// the touch pressing the box.
+	pressing: Option<u64>,
+	/// the typed text, and when it was last typed.
+	typeahead: (String, Duration),
+	focused: bool,
+}
+
+/// The inner properties of a combo box.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ComboBoxInner {
+	/// The items to choose from.
+	pub items: Vec<String>,
+	/// The selected item, `None` shows the placeholder.
+	pub selected: Option<usize>,
+	/// The text shown when no item is selected.
+	pub placeholder: String,
+	/// The width of the combo box, the popup has the same width.
+	pub width: f32,
+	/// The font of the items.
+	pub font: FontId,
+	/// The font role of the items, see [`FontRole`].
+	pub font_role: Option<FontRole>,
+	/// The font size of the items.
+	pub font_size: f32,
+	/// The padding around the text.
+	pub padding: Vec2,
+	/// The background color of the combo box.
+	pub background_color: FillMode,
+	/// The border color of the combo box.
+	pub border_color: FillMode,
+	/// The color of the selected item.
+	pub text_color: FillMode,
+	/// The color of the placeholder and the arrow.
+	pub placeholder_color: FillMode,
+	/// The roundings of the combo box.
+	pub roundings: Vec4,
+}
+
+impl Default for ComboBoxInner {
+	fn default() -> Self {
+		Self {
+			items: vec!(),
+			selected: None,
+			placeholder: String::new(),
+			width: CONTENT_TEXT_SIZE * 10.0,
+			font: 0,
+			font_role: Some(FontRole::Body),
+			font_size: CONTENT_TEXT_SIZE,
+			padding: Vec2::same(DEFAULT_PADDING),
+			background_color: FillMode::Color(INPUT_BACKGROUND_COLOR),
+			border_color: FillMode::Color(INPUT_BORDER_COLOR),
+			text_color: FillMode::Color(PRIMARY_TEXT_COLOR),
+			placeholder_color: FillMode::Color(SECONDARY_TEXT_COLOR),
+			roundings: Vec4::same(DEFAULT_ROUNDING / 2.0),
+		}
+	}
+}
+
+impl ComboBoxInner {
+	/// Get the text of the selected item.
+	pub fn selected_item(&self) -> Option<&str> {
+		self.selected.and_then(|index| self.items.get(index)).map(String::as_str)
+	}
+}
+
+impl<S: Signal, A: App<Signal = S>> Default for ComboBox<S, A> {
+	fn default() -> Self {
+		Self {
+			inner: ComboBoxInner::default(),
+			signals: SignalGenerator::default(),
+			on_select: None,
+			popup: Rc::new(RefCell::new(PopupState::default())),
+			popup_session: 0,
+			pressing: None,
+			typeahead: (String::new(), Duration::ZERO),
+			focused: false,
+		}
+	}
+}
+
+impl<S: Signal, A: App<Signal = S>> ComboBox<S, A> {
+	/// Create a combo box with the given items, nothing is selected.
+	pub fn new(items: impl IntoIterator<Item = impl Into<String>>) -> Self {
+		Self {
+			inner: ComboBoxInner { items: items.into_iter().map(Into::into).collect(), ..Default::default() },
+			..Default::default()
+		}
+	}
+
+	/// Set the selected item, `None` shows the placeholder.
+	pub fn selected(self, selected: Option<usize>) -> Self {
+		Self { inner: ComboBoxInner { selected, ..self.inner }, ..self }
+	}
+
+	/// Set the text shown when no item is selected.
+	pub fn placeholder(self, placeholder: impl Into<String>) -> Self {
+		Self { inner: ComboBoxInner { placeholder: placeholder.into(), ..self.inner }, ..self }
+	}
+
+	/// Set the width of the combo box.
+	pub fn width(self, width: f32) -> Self {
+		Self { inner: ComboBoxInner { width, ..self.inner }, ..self }
+	}
+
+	/// Set the font of the items.
+	/// 
+	/// Clears the font role, so the font is used even if the theme registers one, see [`Self::font_role`].
+	pub fn font(self, font: FontId) -> Self {
+		Self { inner: ComboBoxInner { font, font_role: None, ..self.inner }, ..self }
+	}
+
+	/// Set the font role of the items, see [`FontRole`].
+	pub fn font_role(self, font_role: Option<FontRole>) -> Self {
+		Self { inner: ComboBoxInner { font_role, ..self.inner }, ..self }
+	}
+
+	/// Set the font size of the items.
+	pub fn font_size(self, font_size: f32) -> Self {
+		Self { inner: ComboBoxInner { font_size, ..self.inner }, ..self }
+	}
+
+	/// Set the padding around the text.
+	pub fn padding(self, padding: impl Into<Vec2>) -> Self {
+		Self { inner: ComboBoxInner { padding: padding.into(), ..self.inner }, ..self }
+	}
+
+	/// Set the background color of the combo box.
+	pub fn background_color(self, background_color: impl Into<FillMode>) -> Self {
+		Self { inner: ComboBoxInner { background_color: background_color.into(), ..self.inner }, ..self }
+	}
+
+	/// Set the border color of the combo box.
+	pub fn border_color(self, border_color: impl Into<FillMode>) -> Self {
+		Self { inner: ComboBoxInner { border_color: border_color.into(), ..self.inner }, ..self }
+	}
+
+	/// Set the color of the selected item.
+	pub fn text_color(self, text_color: impl Into<FillMode>) -> Self {
+		Self { inner: ComboBoxInner { text_color: text_color.into(), ..self.inner }, ..self }
+	}
+
+	/// Set the color of the placeholder and the arrow.
+	pub fn placeholder_color(self, placeholder_color: impl Into<FillMode>) -> Self {
+		Self { inner: ComboBoxInner { placeholder_color: placeholder_color.into(), ..self.inner }, ..self }
+	}
+
+	/// Set the roundings of the combo box.
+	pub fn roundings(self, roundings: impl Into<Vec4>) -> Self {
+		Self { inner: ComboBoxInner { roundings: roundings.into(), ..self.inner }, ..self }
+	}
+
+	/// Set the signal to send when an item is selected by the user.
+	pub fn on_select(self, on_select: impl Fn(&mut A, &mut ComboBoxInner, usize) -> S + 'static) -> Self {
+		Self {
+			on_select: Some(Box::new(on_select)),
+			..self
+		}
+	}
+
+	/// Check whether the popup is open.
+	pub fn is_open(&self) -> bool {
+		self.popup.borrow().session.is_some()
+	}
+
+	fn height(&self) -> f32 {
+		self.inner.font_size + self.inner.padding.y * 2.0
+	}
+
+	fn open(&mut self, input_state: &mut InputState<S>, id: LayoutId) {
+		if self.inner.items.is_empty() {
+			return;
+		}
+		let mut popup = self.popup.borrow_mut();
+		popup.items = self.inner.items.clone();
+		popup.first_shown = 0;
+		popup.highlight(self.inner.selected.unwrap_or(0));
+		popup.pressed = None;
+		popup.chosen = None;
+		popup.area = Rect::ZERO;
+		popup.width = self.inner.width;
+		popup.font = self.inner.font;
+		popup.font_role = self.inner.font_role;
+		popup.font_size = self.inner.font_size;
+		popup.padding = self.inner.padding;
+		self.popup_session += 1;
+		popup.session = Some(self.popup_session);
+		input_state.push_overlay(OverlayAnchor::below(id), ComboBoxPopup::<S, A> {
+			state: Rc::downgrade(&self.popup),
+			session: self.popup_session,
+			seen_version: 0,
+			drawn_size: Vec2::ZERO,
+			last_hover: None,
+			scrolled_rows: 0.0,
+			_marker: PhantomData,
+		});
+	}
+
+	/// Close the popup, it removes itself in the next event frame.
+	fn close(&mut self) {
+		let mut popup = self.popup.borrow_mut();
+		popup.session = None;
+		popup.version += 1;
+	}
+
+	/// Select the item and send the signal, returns whether the selection changed.
+	fn select(&mut self, app: &mut A, input_state: &mut InputState<S>, id: LayoutId, index: usize) -> bool {
+		if index >= self.inner.items.len() || self.inner.selected == Some(index) {
+			return false;
+		}
+		self.inner.selected = Some(index);
+		if let Some(on_select) = &self.on_select {
+			let signal = on_select(app, &mut self.inner, index);
+			input_state.send_signal_from(id, signal);
+		}
+		true
+	}
+
+	/// Find the first item starting with the typed text, case insensitively.
+	fn typeahead_match(&self) -> Option<usize> {
+		let typed = self.typeahead.0.to_lowercase();
+		self.inner.items.iter().position(|item| item.to_lowercase().starts_with(&typed))
+	}
+
+	fn handle_keys(&mut self, app: &mut A, input_state: &mut InputState<S>, id: LayoutId) -> bool {
+		let open = self.is_open();
+		let mut changed = false;
+		let step = if input_state.is_key_pressed(Key::ArrawDown) {
+			1
+		}else if input_state.is_key_pressed(Key::ArrawUp) {
+			-1
+		}else {
+			0
+		};
+		if step != 0 {
+			if open {
+				self.popup.borrow_mut().highlight_by(step);
+				changed = true;
+			}else if !self.inner.items.is_empty() {
+				let index = self.inner.selected.map_or(0, |index| (index as isize + step).clamp(0, self.inner.items.len() as isize - 1) as usize);
+				changed |= self.select(app, input_state, id, index);
+			}
+		}
+
+		// the space key chooses the item, but it is part of the search in the middle of the typed text.
+		let typing = !self.typeahead.0.is_empty() && input_state.program_running_time() - self.typeahead.1 <= TYPEAHEAD_TIMEOUT;
+		let space = input_state.is_key_pressed(Key::Space) && !typing;
+		if input_state.is_key_pressed(Key::Enter) || input_state.is_key_pressed(Key::KeypadEnter) || space {
+			if open {
+				let highlighted = self.popup.borrow().highlighted;
+				self.select(app, input_state, id, highlighted);
+				self.close();
+			}else {
+				self.open(input_state, id);
+			}
+			changed = true;
+		}
+		if open && input_state.is_key_pressed(Key::Escape) {
+			self.close();
+			changed = true;
+		}
+
+		if let ImeString::ImeOff(text) = input_state.get_input_string() {
+			let now = input_state.program_running_time();
+			if now - self.typeahead.1 > TYPEAHEAD_TIMEOUT {
+				self.typeahead.0.clear();
+			}
+			self.typeahead.1 = now;
+			// a space typed before any search chose the item above, so it does not start a search.
+			self.typeahead.0.extend(text.chars().filter(|c| !c.is_control()));
+			if self.typeahead.0.trim().is_empty() {
+				self.typeahead.0.clear();
+			}else if let Some(index) = self.typeahead_match() {
+				if self.is_open() {
+					self.popup.borrow_mut().highlight(index);
+				}else {
+					self.select(app, input_state, id, index);
+				}
+				changed = true;
+			}
+		}
+		changed
+	}
+}
+
+impl<S: Signal, A: App<Signal = S>> Widget for ComboBox<S, A> {
+	type Signal = S;
+	type Application = A;
+
+	fn size(&self, _: LayoutId, _: &Painter, _: &Layout<Self::Signal, Self::Application>) -> Vec2 {
+		Vec2::new(self.inner.width, self.height())
+	}
+
+	fn draw(&mut self, painter: &mut Painter, size: Vec2) {
+		let stroke = 1.0;
+		painter.set_fill_mode(self.inner.background_color.clone());
+		painter.draw_rect(Rect::from_size(size), self.inner.roundings);
+		painter.set_fill_mode(self.inner.border_color.clone());
+		painter.draw_stroked_rect(Rect::from_size(size).shrink(Vec2::same(stroke / 2.0)), self.inner.roundings, stroke);
+
+		let padding = self.inner.padding;
+		let half = self.inner.font_size / 4.0;
+		let arrow_space = half * 2.0 + padding.x;
+		let font = painter.resolve_font(self.inner.font_role, self.inner.font);
+		let clip = painter.clip_rect();
+		painter.set_clip_rect(clip & Rect::from_lt_size(painter.releative_to(), Vec2::new((size.x - arrow_space).max(0.0), size.y)));
+		match self.inner.selected_item() {
+			Some(item) => {
+				painter.set_fill_mode(self.inner.text_color.clone());
+				painter.draw_text(padding, font, self.inner.font_size, item);
+			},
+			None => {
+				painter.set_fill_mode(self.inner.placeholder_color.clone());
+				painter.draw_text(padding, font, self.inner.font_size, &self.inner.placeholder);
+			}
+		}
+		painter.set_clip_rect(clip);
+
+		let center = Vec2::new(size.x - padding.x - half, size.y / 2.0);
+		painter.set_fill_mode(self.inner.placeholder_color.clone());
+		if self.is_open() {
+			painter.draw_triangle(center + Vec2::new(- half, half / 2.0), center + Vec2::new(half, half / 2.0), center + Vec2::new(0.0, - half / 2.0));
+		}else {
+			painter.draw_triangle(center + Vec2::new(- half, - half / 2.0), center + Vec2::new(half, - half / 2.0), center + Vec2::new(0.0, half / 2.0));
+		}
+	}
+
+	fn handle_event(&mut self, app: &mut A, input_state: &mut InputState<Self::Signal>, id: LayoutId, area: Rect, _: Vec2) -> bool {
+		// taken before the signals, which mark the touches as used.
+		let pressed = input_state.get_touch_pressed_on(area);
+		let res = self.signals.generate_signals(app, &mut self.inner, input_state, id, area, true, false);
+		let was_open = self.is_open();
+		let mut changed = false;
+
+		let (chosen, popup_area) = {
+			let mut popup = self.popup.borrow_mut();
+			(popup.chosen.take(), popup.area)
+		};
+		if let Some(index) = chosen {
+			self.select(app, input_state, id, index);
+			self.close();
+		}
+
+		if was_open && input_state.is_any_touch_pressed() && !input_state.any_touch_pressed_on(area) && !input_state.any_touch_pressed_on(popup_area) {
+			self.close();
+		}
+
+		for touch in pressed {
+			if self.pressing == Some(touch) || input_state.is_touch_released(touch) {
+				continue;
+			}
+			self.pressing = Some(touch);
+			input_state.request_focus(id);
+			if self.is_open() {
+				self.close();
+			}else {
+				self.open(input_state, id);
+			}
+		}
+		if self.pressing.is_some_and(|touch| input_state.is_touch_released(touch)) {
+			self.pressing = None;
+		}
+
+		let focused = input_state.is_focused(id);
+		if focused {
+			changed |= self.handle_keys(app, input_state, id);
+		}else if self.focused && self.is_open() {
+			self.close();
+		}
+		self.focused = focused;
+
+		changed || res.is_clicked || was_open != self.is_open() || chosen.is_some()
+	}
+
+	fn event_handle_strategy(&self) -> EventHandleStrategy {
+		if self.is_open() || self.pressing.is_some() {
+			// sees the presses outside of the box and the popup.
+			EventHandleStrategy::AlwaysSecondary
+		}else {
+			EventHandleStrategy::OnHover
+		}
+	}
+
+	fn focusable(&self) -> bool {
+		true
+	}
+}
+
+/// The items of a [`ComboBox`], shared by the combo box and its popup.
+#[derive(Default)]
+struct PopupState {
+	/// the session of the open popup, `None` if closed. Popups of other sessions remove themselves.
+	session: Option<u64>,
+	items: Vec<String>,
+	highlighted: usize,
+	first_shown: usize,
+	/// the item pressed in the popup.
+	pressed: Option<usize>,
+	/// the item clicked in the popup, taken by the combo box.
+	chosen: Option<usize>,
+	/// the area of the popup, presses inside of it do not close the popup.
+	area: Rect,
+	/// increased every time the popup has to be redrawn.
+	version: u64,
+	width: f32,
+	font: FontId,
+	font_role: Option<FontRole>,
+	font_size: f32,
+	padding: Vec2,
+}
+
+impl PopupState {
+	/// Highlight the item, scrolling the popup to show it.
+	fn highlight(&mut self, index: usize) {
+		self.highlighted = index.min(self.items.len().saturating_sub(1));
+		self.first_shown = self.first_shown.min(self.highlighted).max((self.highlighted + 1).saturating_sub(MAX_SHOWN_ITEMS));
+		self.version += 1;
+	}
+
+	/// Highlight the next or previous item, stopping at the ends.
+	fn highlight_by(&mut self, amount: isize) {
+		let len = self.items.len() as isize;
+		if len > 0 {
+			self.highlight((self.highlighted as isize + amount).clamp(0, len - 1) as usize);
+		}
+	}
+
+	fn row_height(&self) -> f32 {
+		self.font_size + self.padding.y
+	}
+
+	fn shown(&self) -> Range<usize> {
+		self.first_shown..(self.first_shown + MAX_SHOWN_ITEMS).min(self.items.len())
+	}
+
+	/// Get the index of the item at the position relative to the popup.
+	fn item_at(&self, pos: Vec2) -> Option<usize> {
+		let row = (pos.y - self.padding.y / 2.0) / self.row_height();
+		if row < 0.0 {
+			return None;
+		}
+		let index = self.first_shown + row as usize;
+		self.shown().contains(&index).then_some(index)
+	}
+}
+
+/// The popup listing the items of a [`ComboBox`], pushed as an overlay below it.
+struct ComboBoxPopup<S: Signal, A: App<Signal = S>> {
+	state: Weak<RefCell<PopupState>>,
+	session: u64,
+	seen_version: u64,
+	/// keeps the popup hit-testable after the combo box is dropped, so it can still remove itself.
+	drawn_size: Vec2,
+	last_hover: Option<Vec2>,
+	/// the scrolled rows not applied yet, so small deltas of touchpads add up.
+	scrolled_rows: f32,
+	_marker: PhantomData<(S, A)>,
+}
+
+impl<S: Signal, A: App<Signal = S>> Widget for ComboBoxPopup<S, A> {
+	type Signal = S;
+	type Application = A;
+
+	fn size(&self, _: LayoutId, _: &Painter, _: &Layout<Self::Signal, A>) -> Vec2 {
+		match self.state.upgrade() {
+			Some(state) => {
+				let state = state.borrow();
+				Vec2::new(state.width, state.shown().len() as f32 * state.row_height() + state.padding.y)
+			},
+			None => self.drawn_size,
+		}
+	}
+
+	fn draw(&mut self, painter: &mut Painter, size: Vec2) {
+		self.drawn_size = size;
+		let Some(state) = self.state.upgrade() else {
+			return;
+		};
+		let state = state.borrow();
+		if state.session != Some(self.session) {
+			return;
+		}
+		let stroke = 1.0;
+		let roundings = Vec4::same(DEFAULT_ROUNDING / 2.0);
+		painter.set_fill_mode(INPUT_BACKGROUND_COLOR);
+		painter.draw_rect(Rect::from_size(size), roundings);
+		painter.set_fill_mode(INPUT_BORDER_COLOR);
+		painter.draw_stroked_rect(Rect::from_size(size).shrink(Vec2::same(stroke / 2.0)), roundings, stroke);
+
+		let font = painter.resolve_font(state.font_role, state.font);
+		let row_height = state.row_height();
+		for (row, index) in state.shown().enumerate() {
+			let top = state.padding.y / 2.0 + row as f32 * row_height;
+			if index == state.highlighted {
+				painter.set_fill_mode(SELECTED_TEXT_COLOR);
+				painter.draw_rect(Rect::new(stroke, top, size.x - stroke * 2.0, row_height), Vec4::same(state.font_size / 8.0));
+			}
+			painter.set_fill_mode(PRIMARY_TEXT_COLOR);
+			painter.draw_text(Vec2::new(state.padding.x, top + state.padding.y / 2.0), font, state.font_size, &state.items[index]);
+		}
+	}
+
+	fn handle_event(&mut self, _: &mut A, input_state: &mut InputState<Self::Signal>, id: LayoutId, area: Rect, pos: Vec2) -> bool {
+		let Some(state) = self.state.upgrade() else {
+			input_state.remove_overlay(id);
+			return false;
+		};
+		let mut state = state.borrow_mut();
+		if state.session != Some(self.session) {
+			input_state.remove_overlay(id);
+			return false;
+		}
+		state.area = area;
+
+		// only follow the pointer when it moves, so it does not fight with the arrow keys.
+		let hover = input_state.hover_pos().filter(|hover| area.contains(*hover));
+		if hover.is_some() && hover != self.last_hover {
+			if let Some(index) = hover.and_then(|hover| state.item_at(hover - pos)) {
+				if index != state.highlighted {
+					state.highlight(index);
+				}
+			}
+		}
+		self.last_hover = hover;
+
+		if let Some(touch) = input_state.get_touch_pressed_on(area).first() {
+			state.pressed = input_state.get_touch_pos(*touch).and_then(|touch| state.item_at(touch - pos));
+		}
+		if input_state.is_clicked(id, area) {
+			state.chosen = state.pressed.take();
+		}
+
+		let changed = state.version != self.seen_version;
+		self.seen_version = state.version;
+		changed
+	}
+
+	fn event_handle_strategy(&self) -> EventHandleStrategy {
+		// handled before the combo box, which takes the chosen item in the same frame.
+		EventHandleStrategy::AlwaysPrimary
+	}
+
+	fn scroll_by(&mut self, delta: Vec2, _: ScrollSource) -> Vec2 {
+		let Some(state) = self.state.upgrade() else {
+			return delta;
+		};
+		let mut state = state.borrow_mut();
+		let max_first = state.items.len().saturating_sub(MAX_SHOWN_ITEMS) as isize;
+		self.scrolled_rows -= delta.y / state.row_height();
+		let rows = self.scrolled_rows.trunc();
+		self.scrolled_rows -= rows;
+		let first_shown = (state.first_shown as isize + rows as isize).clamp(0, max_first) as usize;
+		if max_first == 0 {
+			return delta;
+		}
+		if first_shown == state.first_shown {
+			return Vec2::ZERO;
+		}
+		state.first_shown = first_shown;
+		state.highlighted = state.highlighted.clamp(first_shown, (first_shown + MAX_SHOWN_ITEMS).min(state.items.len()) - 1);
+		state.version += 1;
+		Vec2::ZERO
+	}
+}
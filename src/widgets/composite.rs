@@ -0,0 +1,82 @@
+//! Building widgets out of other widgets.
+
+use crate::{layout::{Layout, LayoutError, LayoutId}, App};
+
+use super::{Signal, SignalWrapper};
+
+/// A widget built out of other widgets, such as a slider with a label or a row of settings.
+/// 
+/// Declare the inner widgets in [`Self::build`] and wire their signals in [`Self::on_signal`],
+/// the inner widgets are ordinary widgets in the layout, so layout, drawing and events are handled as usual.
+/// Add it to the layout by [`Layout::add_composite`].
+/// 
+/// ```
+/// # use nablo_ui::prelude::*;
+/// enum Sig {
+///     Volume(f32),
+/// }
+/// # impl Signal for Sig {}
+/// # struct MyApp;
+/// # impl App for MyApp {
+/// #     type Signal = Sig;
+/// #     fn on_start(&mut self, _: &mut Context<Sig, Self>) {}
+/// #     fn on_signal(&mut self, _: &mut Context<Sig, Self>, _: SignalWrapper<Sig>) {}
+/// # }
+/// 
+/// /// A slider with a label showing its value.
+/// struct LabeledSlider {
+///     label: Option<LayoutId>,
+/// }
+/// 
+/// impl CompositeWidget for LabeledSlider {
+///     type Signal = Sig;
+///     type Application = MyApp;
+/// 
+///     fn build(&mut self, layout: &mut Layout<Sig, MyApp>, parent: LayoutId) -> Result<LayoutId, LayoutError> {
+///         let row = layout.try_add_widget(parent, Card::new_horizontal())?;
+///         self.label = Some(layout.try_add_widget(row, Label::new("0.00"))?);
+///         layout.try_add_widget(row, Slider::new(0.0, 0.0, 1.0).on_drag(|_, inner, _| Sig::Volume(inner.value)))?;
+///         Ok(row)
+///     }
+/// 
+///     fn on_signal(&mut self, _: &mut MyApp, layout: &mut Layout<Sig, MyApp>, _: LayoutId, signal: SignalWrapper<Sig>) -> Option<SignalWrapper<Sig>> {
+///         let Sig::Volume(value) = signal.signal;
+///         if let Some(label) = self.label {
+///             layout.widget_mut(label, |label: Label<Sig, MyApp>| label.text(format!("{:.2}", value)));
+///         }
+///         Some(signal)
+///     }
+/// }
+/// 
+/// let mut layout = Layout::new();
+/// layout.insert_root_widget(Card::new_vertical());
+/// layout.add_composite(ROOT_LAYOUT_ID, LabeledSlider { label: None }).unwrap();
+/// assert_eq!(layout.widgets(), 4);
+/// ```
+pub trait CompositeWidget: 'static {
+	/// The signal type of the app.
+	type Signal: Signal;
+	/// The app type.
+	type Application: App<Signal = Self::Signal>;
+
+	/// Add the inner widgets under `parent`, returns the id of the outermost widget.
+	/// 
+	/// The outermost widget represents the composite, signals sent by it or its children are passed to [`Self::on_signal`].
+	fn build(&mut self, layout: &mut Layout<Self::Signal, Self::Application>, parent: LayoutId) -> Result<LayoutId, LayoutError>;
+
+	/// Handle a signal sent by the widgets inside the composite, `id` is the id of the outermost widget.
+	/// 
+	/// Return the signal to pass to the app, or `None` to consume it.
+	/// Signals returned are also passed to the composites containing this one.
+	/// By default, all signals are passed unchanged.
+	fn on_signal(
+		&mut self, 
+		app: &mut Self::Application, 
+		layout: &mut Layout<Self::Signal, Self::Application>, 
+		id: LayoutId, 
+		signal: SignalWrapper<Self::Signal>
+	) -> Option<SignalWrapper<Self::Signal>> {
+		let _ = (app, layout, id);
+		Some(signal)
+	}
+}
@@ -0,0 +1,530 @@
+//! A menu listing actions, opened at the pointer by a right click.
+//!
+//! Attach a menu to any widget with [`crate::layout::Layout::set_context_menu`],
+//! or open one from your own widget with [`ContextMenu::open`].
+
+use std::{cell::{Cell, RefCell}, rc::Rc};
+
+use crate::{layout::{Layout, LayoutId, OverlayAnchor}, prelude::{FontId, FontRole, InputState, Key, Modifiers, Painter, Rect, Vec2, Vec4}, App};
+
+use super::{styles::{CARD_BORDER_COLOR, CARD_COLOR, CONTENT_TEXT_SIZE, DEFAULT_PADDING, DEFAULT_ROUNDING, DISABLE_TEXT_COLOR, PRIMARY_TEXT_COLOR, SECONDARY_TEXT_COLOR, SELECTED_TEXT_COLOR}, EventHandleStrategy, Signal, Widget};
+
+/// An item of a [`ContextMenu`].
+pub struct ContextMenuItem<S: Signal, A: App<Signal = S>> {
+	/// The text of the item.
+	pub label: String,
+	/// The shortcut shown at the right of the label, such as `Ctrl+C`, only for display.
+	pub shortcut: Option<String>,
+	/// Whether the item can be chosen, disabled items are greyed out.
+	pub enabled: bool,
+	/// the action run when chosen, `None` for separators and submenus.
+	#[allow(clippy::type_complexity)]
+	action: Option<Rc<dyn Fn(&mut A, &mut InputState<S>, LayoutId)>>,
+	/// the items of the menu opened next to the item, empty if the item is not a submenu.
+	submenu: Vec<ContextMenuItem<S, A>>,
+}
+
+impl<S: Signal, A: App<Signal = S>> Clone for ContextMenuItem<S, A> {
+	fn clone(&self) -> Self {
+		Self {
+			label: self.label.clone(),
+			shortcut: self.shortcut.clone(),
+			enabled: self.enabled,
+			action: self.action.clone(),
+			submenu: self.submenu.clone(),
+		}
+	}
+}
+
+impl<S: Signal, A: App<Signal = S>> ContextMenuItem<S, A> {
+	/// Create an item sending the signal when chosen, the signal is sent from the widget the menu is opened for.
+	pub fn new(label: impl Into<String>, signal: impl Fn(&mut A) -> S + 'static) -> Self {
+		Self::action(label, move |app, input_state, source| {
+			let signal = signal(app);
+			input_state.send_signal_from(source, signal);
+		})
+	}
+
+	/// Create an item running the action when chosen, given the id of the widget the menu is opened for.
+	pub fn action(label: impl Into<String>, action: impl Fn(&mut A, &mut InputState<S>, LayoutId) + 'static) -> Self {
+		Self {
+			label: label.into(),
+			shortcut: None,
+			enabled: true,
+			action: Some(Rc::new(action)),
+			submenu: vec!(),
+		}
+	}
+
+	/// Create an item opening a menu with the given items next to it, when hovered or chosen.
+	pub fn submenu(label: impl Into<String>, items: impl IntoIterator<Item = ContextMenuItem<S, A>>) -> Self {
+		Self {
+			label: label.into(),
+			shortcut: None,
+			enabled: true,
+			action: None,
+			submenu: items.into_iter().collect(),
+		}
+	}
+
+	/// Create a line separating the groups of items.
+	pub fn separator() -> Self {
+		Self {
+			label: String::new(),
+			shortcut: None,
+			enabled: false,
+			action: None,
+			submenu: vec!(),
+		}
+	}
+
+	/// Set the shortcut shown at the right of the label.
+	pub fn shortcut(self, shortcut: impl Into<String>) -> Self {
+		Self { shortcut: Some(shortcut.into()), ..self }
+	}
+
+	/// Set the shortcut shown at the right of the label as the key with the primary modifier,
+	/// `Cmd+C` on macOS and `Ctrl+C` elsewhere, see [`Modifiers::primary`].
+	pub fn primary_shortcut(self, key: &str) -> Self {
+		self.shortcut(Modifiers::primary_shortcut(key))
+	}
+
+	/// Set whether the item can be chosen.
+	pub fn enabled(self, enabled: bool) -> Self {
+		Self { enabled, ..self }
+	}
+
+	/// Check whether the item is a separator.
+	pub fn is_separator(&self) -> bool {
+		self.action.is_none() && self.submenu.is_empty()
+	}
+
+	/// Check whether the item opens a submenu.
+	pub fn is_submenu(&self) -> bool {
+		!self.submenu.is_empty()
+	}
+
+	fn is_choosable(&self) -> bool {
+		self.enabled && !self.is_separator()
+	}
+}
+
+/// A handle to an opened [`ContextMenu`], see [`ContextMenu::open`].
+#[derive(Clone, Debug)]
+pub struct ContextMenuHandle {
+	open: Rc<Cell<bool>>,
+}
+
+impl ContextMenuHandle {
+	/// Check whether the menu is still open.
+	pub fn is_open(&self) -> bool {
+		self.open.get()
+	}
+
+	/// Close the menu, it is removed in the next event frame.
+	pub fn close(&self) {
+		self.open.set(false);
+	}
+}
+
+/// A menu listing actions, shown as an overlay at the pointer.
+///
+/// The menu closes when an item is chosen, on a press outside of it, or on the escape key.
+/// The items can also be chosen by the arrow keys and the enter key.
+///
+/// Submenus, see [`ContextMenuItem::submenu`], open next to their items when hovered or chosen, 
+/// the right arrow key opens and the left arrow key closes them. The menu and its submenus close together.
+pub struct ContextMenu<S: Signal, A: App<Signal = S>> {
+	/// The inner properties of the menu.
+	pub inner: ContextMenuInner<S, A>,
+	/// the widget the menu is opened for.
+	source: LayoutId,
+	/// whether the menu is open, shared with its submenus.
+	open: Rc<Cell<bool>>,
+	/// whether this submenu is open, cleared by the parent menu to close only this one.
+	level_open: Rc<Cell<bool>>,
+	/// the areas of the menu and its open submenus by depth, presses inside of them do not close the menus.
+	areas: Rc<RefCell<Vec<Rect>>>,
+	/// how many menus this submenu is opened from.
+	depth: usize,
+	/// the item whose submenu is open, and the flag to close the submenu.
+	submenu: Option<(usize, Rc<Cell<bool>>)>,
+	hovered: Option<usize>,
+	pressed: Option<usize>,
+	/// whether the press opening the menu is still going on, which should not close it.
+	opening: bool,
+	last_hover: Option<Vec2>,
+}
+
+/// The inner properties of a context menu.
+pub struct ContextMenuInner<S: Signal, A: App<Signal = S>> {
+	/// The items of the menu.
+	pub items: Vec<ContextMenuItem<S, A>>,
+	/// The font of the items.
+	pub font: FontId,
+	/// The font role of the items, see [`FontRole`].
+	pub font_role: Option<FontRole>,
+	/// The font size of the items.
+	pub font_size: f32,
+	/// The padding around the text of the items.
+	pub padding: Vec2,
+	/// The minimum width of the menu.
+	pub min_width: f32,
+	/// The roundings of the menu.
+	pub roundings: Vec4,
+}
+
+impl<S: Signal, A: App<Signal = S>> Clone for ContextMenuInner<S, A> {
+	fn clone(&self) -> Self {
+		Self {
+			items: self.items.clone(),
+			font: self.font,
+			font_role: self.font_role,
+			font_size: self.font_size,
+			padding: self.padding,
+			min_width: self.min_width,
+			roundings: self.roundings,
+		}
+	}
+}
+
+impl<S: Signal, A: App<Signal = S>> Default for ContextMenu<S, A> {
+	fn default() -> Self {
+		Self {
+			inner: ContextMenuInner {
+				items: vec!(),
+				font: 0,
+				font_role: Some(FontRole::Body),
+				font_size: CONTENT_TEXT_SIZE,
+				padding: Vec2::same(DEFAULT_PADDING),
+				min_width: CONTENT_TEXT_SIZE * 8.0,
+				roundings: Vec4::same(DEFAULT_ROUNDING / 2.0),
+			},
+			source: crate::layout::ROOT_LAYOUT_ID,
+			open: Rc::new(Cell::new(true)),
+			level_open: Rc::new(Cell::new(true)),
+			areas: Rc::new(RefCell::new(vec!())),
+			depth: 0,
+			submenu: None,
+			hovered: None,
+			pressed: None,
+			opening: true,
+			last_hover: None,
+		}
+	}
+}
+
+impl<S: Signal, A: App<Signal = S>> ContextMenu<S, A> {
+	/// Create an empty menu.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Add an item to the menu.
+	pub fn item(mut self, item: ContextMenuItem<S, A>) -> Self {
+		self.inner.items.push(item);
+		self
+	}
+
+	/// Add a separator to the menu.
+	pub fn separator(self) -> Self {
+		self.item(ContextMenuItem::separator())
+	}
+
+	/// Set the items of the menu.
+	pub fn items(self, items: impl IntoIterator<Item = ContextMenuItem<S, A>>) -> Self {
+		Self { inner: ContextMenuInner { items: items.into_iter().collect(), ..self.inner }, ..self }
+	}
+
+	/// Set the font of the items.
+	/// 
+	/// Clears the font role, so the font is used even if the theme registers one, see [`Self::font_role`].
+	pub fn font(self, font: FontId) -> Self {
+		Self { inner: ContextMenuInner { font, font_role: None, ..self.inner }, ..self }
+	}
+
+	/// Set the font role of the items, see [`FontRole`].
+	pub fn font_role(self, font_role: Option<FontRole>) -> Self {
+		Self { inner: ContextMenuInner { font_role, ..self.inner }, ..self }
+	}
+
+	/// Set the font size of the items.
+	pub fn font_size(self, font_size: f32) -> Self {
+		Self { inner: ContextMenuInner { font_size, ..self.inner }, ..self }
+	}
+
+	/// Set the padding around the text of the items.
+	pub fn padding(self, padding: impl Into<Vec2>) -> Self {
+		Self { inner: ContextMenuInner { padding: padding.into(), ..self.inner }, ..self }
+	}
+
+	/// Set the minimum width of the menu.
+	pub fn min_width(self, min_width: f32) -> Self {
+		Self { inner: ContextMenuInner { min_width, ..self.inner }, ..self }
+	}
+
+	/// Set the roundings of the menu.
+	pub fn roundings(self, roundings: impl Into<Vec4>) -> Self {
+		Self { inner: ContextMenuInner { roundings: roundings.into(), ..self.inner }, ..self }
+	}
+
+	/// Open the menu at the absolute position, usually the pointer, for the given widget.
+	///
+	/// Use this in [`Widget::handle_event`] to open a menu of your own widget, the signals of the items are sent from `source`.
+	/// The menu is flipped to stay in the window, and menus attached by [`Layout::set_context_menu`] are not opened for the same click.
+	pub fn open(self, input_state: &mut InputState<S>, source: LayoutId, pos: Vec2) -> ContextMenuHandle {
+		let menu = self.opened_for(source);
+		let handle = ContextMenuHandle { open: menu.open.clone() };
+		input_state.context_menu_opened = true;
+		input_state.push_overlay(OverlayAnchor::Popup(pos), menu);
+		handle
+	}
+
+	/// Get a fresh copy of the menu to open for the widget.
+	pub(crate) fn opened_for(&self, source: LayoutId) -> Self {
+		Self {
+			inner: self.inner.clone(),
+			source,
+			..Self::default()
+		}
+	}
+
+	fn row_height(&self, item: &ContextMenuItem<S, A>) -> f32 {
+		if item.is_separator() {
+			self.inner.padding.y
+		}else {
+			self.inner.font_size + self.inner.padding.y
+		}
+	}
+
+	/// Get the top of the item relative to the menu.
+	fn item_top(&self, index: usize) -> f32 {
+		self.inner.padding.y / 2.0 + self.inner.items.iter().take(index).map(|item| self.row_height(item)).sum::<f32>()
+	}
+
+	/// Get the item at the height relative to the menu.
+	fn item_at(&self, y: f32) -> Option<usize> {
+		let mut top = self.inner.padding.y / 2.0;
+		for (index, item) in self.inner.items.iter().enumerate() {
+			let height = self.row_height(item);
+			if y >= top && y < top + height {
+				return Some(index);
+			}
+			top += height;
+		}
+		None
+	}
+
+	/// Move the hovered item to the next choosable one in the direction, wrapping around.
+	fn hover_by(&mut self, amount: isize) {
+		let len = self.inner.items.len() as isize;
+		let mut index = self.hovered.map_or(if amount > 0 { -1 } else { len }, |index| index as isize);
+		for _ in 0..len {
+			index = (index + amount).rem_euclid(len);
+			if self.inner.items[index as usize].is_choosable() {
+				self.hovered = Some(index as usize);
+				return;
+			}
+		}
+	}
+
+	fn close(&mut self, input_state: &mut InputState<S>, id: LayoutId) {
+		self.open.set(false);
+		input_state.remove_overlay(id);
+	}
+
+	fn choose(&mut self, app: &mut A, input_state: &mut InputState<S>, id: LayoutId, index: usize, area: Rect, pos: Vec2) {
+		if let Some(item) = self.inner.items.get(index).filter(|item| item.is_choosable()) {
+			if item.is_submenu() {
+				self.open_submenu(input_state, index, area, pos);
+				return;
+			}
+			if let Some(action) = item.action.clone() {
+				action(app, input_state, self.source);
+			}
+			self.close(input_state, id);
+		}
+	}
+
+	/// Open the submenu of the item next to the menu, closing the one already open.
+	fn open_submenu(&mut self, input_state: &mut InputState<S>, index: usize, area: Rect, pos: Vec2) {
+		if self.submenu.as_ref().is_some_and(|(open, _)| *open == index) {
+			return;
+		}
+		self.close_submenu();
+		let Some(item) = self.inner.items.get(index).filter(|item| item.is_submenu() && item.enabled) else { return };
+		let level_open = Rc::new(Cell::new(true));
+		let menu = Self {
+			inner: ContextMenuInner { items: item.submenu.clone(), ..self.inner.clone() },
+			source: self.source,
+			open: self.open.clone(),
+			level_open: level_open.clone(),
+			areas: self.areas.clone(),
+			depth: self.depth + 1,
+			opening: false,
+			..Self::default()
+		};
+		// the first item of the submenu lines up with the item.
+		let top = pos.y + self.item_top(index) - self.inner.padding.y / 2.0;
+		input_state.push_overlay(OverlayAnchor::Popup(Vec2::new(area.x + area.w, top)), menu);
+		self.submenu = Some((index, level_open));
+	}
+
+	fn close_submenu(&mut self) {
+		if let Some((_, level_open)) = self.submenu.take() {
+			level_open.set(false);
+			self.areas.borrow_mut().truncate(self.depth + 1);
+		}
+	}
+}
+
+impl<S: Signal, A: App<Signal = S>> Widget for ContextMenu<S, A> {
+	type Signal = S;
+	type Application = A;
+
+	fn size(&self, _: LayoutId, painter: &Painter, _: &Layout<Self::Signal, A>) -> Vec2 {
+		let font = painter.resolve_font(self.inner.font_role, self.inner.font);
+		let text_width = |text: &str| painter.text_size(font, self.inner.font_size, text).map_or(0.0, |size| size.x);
+		let width = self.inner.items.iter().map(|item| {
+			let right = if item.is_submenu() { self.inner.font_size / 2.0 } else { item.shortcut.as_ref().map_or(0.0, |shortcut| text_width(shortcut)) };
+			text_width(&item.label) + if right > 0.0 { right + self.inner.padding.x * 2.0 } else { 0.0 }
+		}).fold(0.0, f32::max) + self.inner.padding.x * 2.0;
+		let height = self.inner.items.iter().map(|item| self.row_height(item)).sum::<f32>() + self.inner.padding.y;
+		Vec2::new(width.max(self.inner.min_width), height)
+	}
+
+	fn draw(&mut self, painter: &mut Painter, size: Vec2) {
+		let stroke = 1.0;
+		painter.set_fill_mode(CARD_COLOR);
+		painter.draw_rect(Rect::from_size(size), self.inner.roundings);
+		painter.set_fill_mode(CARD_BORDER_COLOR);
+		painter.draw_stroked_rect(Rect::from_size(size).shrink(Vec2::same(stroke / 2.0)), self.inner.roundings, stroke);
+
+		let font = painter.resolve_font(self.inner.font_role, self.inner.font);
+		let padding = self.inner.padding;
+		let mut top = padding.y / 2.0;
+		for (index, item) in self.inner.items.iter().enumerate() {
+			let height = self.row_height(item);
+			if item.is_separator() {
+				painter.set_fill_mode(CARD_BORDER_COLOR);
+				painter.draw_line(Vec2::new(padding.x / 2.0, top + height / 2.0), Vec2::new(size.x - padding.x / 2.0, top + height / 2.0), stroke);
+				top += height;
+				continue;
+			}
+			if self.hovered == Some(index) {
+				painter.set_fill_mode(SELECTED_TEXT_COLOR);
+				painter.draw_rect(Rect::new(stroke * 2.0, top, size.x - stroke * 4.0, height), Vec4::same(self.inner.font_size / 8.0));
+			}
+			let text_top = top + padding.y / 2.0;
+			painter.set_fill_mode(if item.enabled { PRIMARY_TEXT_COLOR } else { DISABLE_TEXT_COLOR });
+			painter.draw_text(Vec2::new(padding.x, text_top), font, self.inner.font_size, &item.label);
+			if item.is_submenu() {
+				let half = self.inner.font_size / 4.0;
+				let center = Vec2::new(size.x - padding.x - half, top + height / 2.0);
+				painter.set_fill_mode(if item.enabled { SECONDARY_TEXT_COLOR } else { DISABLE_TEXT_COLOR });
+				painter.draw_triangle(center + Vec2::new(- half, - half), center + Vec2::new(half, 0.0), center + Vec2::new(- half, half));
+			}else if let Some(shortcut) = &item.shortcut {
+				let width = painter.text_size(font, self.inner.font_size, shortcut).map_or(0.0, |size| size.x);
+				painter.set_fill_mode(if item.enabled { SECONDARY_TEXT_COLOR } else { DISABLE_TEXT_COLOR });
+				painter.draw_text(Vec2::new(size.x - padding.x - width, text_top), font, self.inner.font_size, shortcut);
+			}
+			top += height;
+		}
+	}
+
+	fn handle_event(&mut self, app: &mut A, input_state: &mut InputState<Self::Signal>, id: LayoutId, area: Rect, pos: Vec2) -> bool {
+		if !self.open.get() || !self.level_open.get() {
+			self.close_submenu();
+			input_state.remove_overlay(id);
+			return false;
+		}
+		let (hovered, pressed) = (self.hovered, self.pressed);
+		{
+			let mut areas = self.areas.borrow_mut();
+			if areas.len() <= self.depth {
+				areas.resize(self.depth + 1, Rect::ZERO);
+			}
+			areas[self.depth] = area;
+		}
+		// the submenu may close itself by the left arrow key.
+		if self.submenu.as_ref().is_some_and(|(_, level_open)| !level_open.get()) {
+			self.close_submenu();
+		}
+
+		if self.opening {
+			self.opening = input_state.is_any_touch_pressed();
+		}else if input_state.is_any_touch_pressed() && !self.areas.borrow().iter().any(|area| input_state.any_touch_pressed_on(*area)) {
+			self.close(input_state, id);
+			return false;
+		}
+
+		// only follow the pointer when it moves, so it does not fight with the arrow keys.
+		let hover = input_state.hover_pos().filter(|hover| area.contains(*hover));
+		if hover != self.last_hover {
+			let hovering = hover.and_then(|hover| self.item_at(hover.y - pos.y))
+				.filter(|index| self.inner.items[*index].is_choosable());
+			// keep the item of the open submenu highlighted while the pointer moves into the submenu.
+			if hover.is_some() || self.submenu.is_none() {
+				self.hovered = hovering;
+			}
+			if let Some(index) = hovering {
+				if self.inner.items[index].is_submenu() {
+					self.open_submenu(input_state, index, area, pos);
+				}else {
+					self.close_submenu();
+				}
+			}
+			self.last_hover = hover;
+		}
+
+		if !self.opening {
+			if let Some(touch) = input_state.get_touch_pressed_on(area).first() {
+				self.pressed = input_state.get_touch_pos(*touch)
+					.and_then(|touch| self.item_at(touch.y - pos.y));
+			}
+		}
+		if input_state.is_clicked(id, area) {
+			if let Some(index) = self.pressed.take() {
+				self.choose(app, input_state, id, index, area, pos);
+				return true;
+			}
+		}
+
+		// the keys go to the open submenu.
+		if self.submenu.is_some() {
+			return self.hovered != hovered || self.pressed != pressed;
+		}
+		if input_state.is_key_pressed(Key::ArrawDown) {
+			self.hover_by(1);
+		}
+		if input_state.is_key_pressed(Key::ArrawUp) {
+			self.hover_by(-1);
+		}
+		if input_state.is_key_pressed(Key::Escape) {
+			self.close(input_state, id);
+			return false;
+		}
+		if input_state.is_key_pressed(Key::Enter) || input_state.is_key_pressed(Key::KeypadEnter) || input_state.is_key_pressed(Key::Space) {
+			if let Some(index) = self.hovered {
+				self.choose(app, input_state, id, index, area, pos);
+			}
+		}
+		if input_state.is_key_pressed(Key::ArrawRight) {
+			if let Some(index) = self.hovered.filter(|index| self.inner.items[*index].is_submenu()) {
+				self.open_submenu(input_state, index, area, pos);
+			}
+		}
+		if self.depth > 0 && input_state.is_key_pressed(Key::ArrawLeft) {
+			self.level_open.set(false);
+			input_state.remove_overlay(id);
+			return false;
+		}
+
+		self.hovered != hovered || self.pressed != pressed
+	}
+
+	fn event_handle_strategy(&self) -> EventHandleStrategy {
+		// sees the presses outside of the menu and the keys.
+		EventHandleStrategy::AlwaysPrimary
+	}
+}
@@ -1,6 +1,8 @@
 //! A widget that can be dragged to change its value.
 
-use crate::{layout::{Layout, LayoutId}, prelude::{Animatedf32, FillMode, FontId, InputState, Painter, Rect, Vec2, Vec4}, App};
+use std::any::Any;
+
+use crate::{layout::{Layout, LayoutId}, prelude::{Animatedf32, FillMode, FontId, FontRole, InputState, Painter, Rect, Vec2, Vec4}, App};
 
 use super::{styles::{BRIGHT_FACTOR, CONTENT_TEXT_SIZE, DEFAULT_PADDING, DEFAULT_ROUNDING, INPUT_BACKGROUND_COLOR, INPUT_BORDER_COLOR, SECONDARY_TEXT_COLOR}, Signal, SignalGenerator, Widget};
 
@@ -16,6 +18,8 @@ pub struct DraggableValue<S: Signal, A: App<Signal = S>> {
 
 /// The inner properties of the draggable value widget.
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct DraggableValueInner {
 	/// The current value of the draggable value widget.
 	pub value: f32,
@@ -39,6 +43,8 @@ pub struct DraggableValueInner {
 	pub font_color: FillMode,
 	/// The font of the draggable value widget.
 	pub font: FontId,
+	/// The font role of the draggable value widget, see [`FontRole`].
+	pub font_role: Option<FontRole>,
 	/// The padding of the draggable value widget.
 	pub padding: Vec2,
 	/// The number of decimal places to display.
@@ -63,6 +69,7 @@ impl Default for DraggableValueInner {
 			font_size: CONTENT_TEXT_SIZE,
 			font_color: FillMode::Color(SECONDARY_TEXT_COLOR),
 			font: 0,
+			font_role: Some(FontRole::Body),
 			padding: Vec2::same(DEFAULT_PADDING),
 			decimal_places: 2,
 			speed: 0.01,
@@ -180,9 +187,19 @@ impl<S: Signal, A: App<Signal = S>> DraggableValue<S, A> {
 	}
 
 	/// Sets the font of the draggable value widget.
+	/// 
+	/// Clears the font role, so the font is used even if the theme registers one, see [`Self::font_role`].
 	pub fn font(self, font: FontId) -> Self {
 		Self {
-			inner: DraggableValueInner { font, ..self.inner },
+			inner: DraggableValueInner { font, font_role: None, ..self.inner },
+			..self
+		}
+	}
+
+	/// Sets the font role of the draggable value widget, `None` to always use the font id.
+	pub fn font_role(self, font_role: Option<FontRole>) -> Self {
+		Self {
+			inner: DraggableValueInner { font_role, ..self.inner },
 			..self
 		}
 	}
@@ -225,14 +242,13 @@ impl<S: Signal, A: App<Signal = S>> Widget for DraggableValue<S, A> {
 	type Application = A;
 
 	fn size(&self, _: LayoutId, painter: &Painter, _: &Layout<Self::Signal, A>) -> Vec2 {
-		let text_to_draw = format!("{}{:.3$}{}", 
+		let text_to_draw = format!("{}{}{}", 
 			self.inner.prefix, 
-			self.inner.value, 
+			painter.localizer().format_number(self.inner.value, self.inner.decimal_places), 
 			self.inner.suffix, 
-			self.inner.decimal_places
 		);
 
-		let text_size = painter.text_size(self.inner.font, self.inner.font_size, text_to_draw).unwrap_or_default();
+		let text_size = painter.text_size(painter.resolve_font(self.inner.font_role, self.inner.font), self.inner.font_size, text_to_draw).unwrap_or_default();
 
 		text_size + 2.0 * self.inner.padding
 	}
@@ -240,11 +256,10 @@ impl<S: Signal, A: App<Signal = S>> Widget for DraggableValue<S, A> {
 	fn draw(&mut self, painter: &mut Painter, size: Vec2) {
 		let bright_factor = BRIGHT_FACTOR * (self.hover_factor.value() - self.pressed_factor.value()).max(0.0); 
 
-		let text_to_draw = format!("{}{:.3$}{}", 
+		let text_to_draw = format!("{}{}{}", 
 			self.inner.prefix, 
-			self.inner.value, 
+			painter.localizer().format_number(self.inner.value, self.inner.decimal_places), 
 			self.inner.suffix, 
-			self.inner.decimal_places
 		);
 
 		let mut backgound_color = self.inner.background_color.clone();
@@ -262,7 +277,7 @@ impl<S: Signal, A: App<Signal = S>> Widget for DraggableValue<S, A> {
 		painter.draw_stroked_rect(Rect::from_size(size).shrink(Vec2::same(stroke_width / 2.0)), self.inner.rounding, stroke_width);
 
 		painter.set_fill_mode(font_color);
-		painter.draw_text(self.inner.padding, self.inner.font, self.inner.font_size, text_to_draw);
+		painter.draw_text(self.inner.padding, painter.resolve_font(self.inner.font_role, self.inner.font), self.inner.font_size, text_to_draw);
 	}
 
 	fn handle_event(&mut self, app: &mut A, input_state: &mut InputState<Self::Signal>, from: LayoutId, area: Rect, _: Vec2) -> bool {
@@ -303,6 +318,15 @@ impl<S: Signal, A: App<Signal = S>> Widget for DraggableValue<S, A> {
 		self.hover_factor.is_animating() || self.pressed_factor.is_animating() || changed
 	}
 
+	fn scroll_by(&mut self, delta: Vec2, _: super::ScrollSource) -> Vec2 {
+		// the drag is moving this widget, dont scroll the parents.
+		if self.signals.is_dragging() {
+			Vec2::ZERO
+		}else {
+			delta
+		}
+	}
+
 	fn event_handle_strategy(&self) -> super::EventHandleStrategy {
 		if self.signals.is_dragging() {
 			super::EventHandleStrategy::AlwaysSecondary
@@ -310,4 +334,16 @@ impl<S: Signal, A: App<Signal = S>> Widget for DraggableValue<S, A> {
 			super::EventHandleStrategy::OnHover
 		}
 	}
+
+	fn save_state(&self) -> Option<Box<dyn Any>> {
+		Some(Box::new([self.hover_factor.clone(), self.pressed_factor.clone()]))
+	}
+
+	fn restore_state(&mut self, state: Box<dyn Any>) {
+		if let Ok(state) = state.downcast::<[Animatedf32; 2]>() {
+			let [hover_factor, pressed_factor] = *state;
+			self.hover_factor = hover_factor;
+			self.pressed_factor = pressed_factor;
+		}
+	}
 }
\ No newline at end of file
@@ -1,6 +1,6 @@
 //! A floating container widget that can be used as modal, message, tooltip, window, dropdown, etc.
 
-use std::{cell::RefCell, collections::HashMap};
+use std::{any::Any, cell::RefCell, collections::HashMap};
 
 use indexmap::IndexMap;
 use time::Duration;
@@ -9,6 +9,9 @@ use crate::{layout::{Layout, LayoutId}, prelude::{Animatedf32, Animation, Animat
 
 use super::{Signal, SignalGenerator, Widget};
 
+/// The default z-index of the floating containers.
+pub static FLOATING_Z_INDEX: i32 = 1;
+
 /// A floating container widget that can be used as modal, message, tooltip, window, dropdown, etc.
 /// 
 /// 
@@ -47,6 +50,8 @@ pub struct FloatingContainerInner {
 	pub resizeable: Option<(Vec2, Vec2)>,
 	/// The padding of the floating container.
 	pub padding: Vec2,
+	/// The z-index of the floating container, so it is drawn above its siblings, see [`Widget::z_index`].
+	pub z_index: i32,
 }
 
 /// The position of the floating container.
@@ -88,6 +93,7 @@ impl Default for FloatingContainerInner {
 			size: None,
 			resizeable: None,
 			padding: Vec2::ZERO,
+			z_index: FLOATING_Z_INDEX,
 		}
 	}
 }
@@ -175,6 +181,14 @@ impl<S: Signal, A: App<Signal = S>> FloatingContainer<S, A> {
 		}
 	}
 
+	/// Set the z-index of the floating container.
+	pub fn z_index(self, z_index: i32) -> Self {
+		Self {
+			inner: FloatingContainerInner { z_index, ..self.inner },
+			..self
+		}
+	}
+
 	/// Reset the context of the floating container.
 	pub fn reset_context(&mut self) {
 		self.current_pos = None;
@@ -269,6 +283,10 @@ impl<S: Signal, A: App<Signal = S>> Widget for FloatingContainer<S, A> {
 
 	fn draw(&mut self, _: &mut Painter, _: Vec2) {}
 
+	fn z_index(&self) -> i32 {
+		self.inner.z_index
+	}
+
 	fn handle_event(&mut self, app: &mut A, input_state: &mut InputState<Self::Signal>, id: LayoutId, _: Rect, _: Vec2) -> bool {
 		if self.inner.show {
 			self.show_factor.set(1.0);
@@ -387,6 +405,15 @@ impl<S: Signal, A: App<Signal = S>> Widget for FloatingContainer<S, A> {
 		}
 	}
 
+	fn scroll_by(&mut self, delta: Vec2, _: super::ScrollSource) -> Vec2 {
+		// the drag is moving this widget, dont scroll the parents.
+		if self.signals.is_dragging() {
+			Vec2::ZERO
+		}else {
+			delta
+		}
+	}
+
 	fn event_handle_strategy(&self) -> super::EventHandleStrategy {
 		if self.inner.show {
 			super::EventHandleStrategy::AlwaysPrimary
@@ -394,4 +421,18 @@ impl<S: Signal, A: App<Signal = S>> Widget for FloatingContainer<S, A> {
 			super::EventHandleStrategy::OnHover
 		}
 	}
+
+	fn save_state(&self) -> Option<Box<dyn Any>> {
+		Some(Box::new((self.current_pos, self.current_size, self.show_factor.clone())))
+	}
+
+	fn restore_state(&mut self, state: Box<dyn Any>) {
+		if let Ok(state) = state.downcast::<(Option<Vec2>, Option<Vec2>, Animatedf32)>() {
+			let (current_pos, current_size, show_factor) = *state;
+			// keep the place the container was dragged or resized to.
+			self.current_pos = current_pos;
+			self.current_size = current_size;
+			self.show_factor.continue_from(&show_factor);
+		}
+	}
 }
\ No newline at end of file
@@ -0,0 +1,112 @@
+//! A widget showing an image loaded by [`crate::Context::load_image`], enabled by the `image-loader` feature.
+
+use crate::{layout::{Layout, LayoutId}, prelude::{FillMode, ImageHandle, ImageState, InputState, Painter, Rect, Vec2, Vec4}, App};
+
+use super::{styles::{ERROR_COLOR, INPUT_BACKGROUND_COLOR}, EventHandleStrategy, Signal, SignalGenerator, Widget};
+
+/// The size of an image without a size set, while it is loading or failed.
+pub const PLACEHOLDER_SIZE: Vec2 = Vec2::new(64.0, 64.0);
+
+/// A widget showing an image loaded by [`crate::Context::load_image`].
+///
+/// A placeholder is drawn while the image is decoding, the widget redraws itself once the image is ready.
+pub struct Image<S: Signal, A: App<Signal = S>> {
+	/// The inner properties of the image.
+	pub inner: ImageInner,
+	/// The signals generated by the image.
+	pub signals: SignalGenerator<S, ImageInner, A>,
+	shown: ImageState,
+}
+
+/// The inner properties of the image.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ImageInner {
+	/// The image to show.
+	pub handle: ImageHandle,
+	/// The size of the image, the size of the image in pixels if not set.
+	pub size: Option<Vec2>,
+	/// The roundings of the image.
+	pub roundings: Vec4,
+	/// The fill of the placeholder drawn while the image is loading.
+	pub placeholder_color: FillMode,
+	/// The fill of the placeholder drawn if the image failed to load.
+	pub error_color: FillMode,
+}
+
+impl<S: Signal, A: App<Signal = S>> Image<S, A> {
+	/// Create an image widget showing the handle.
+	pub fn new(handle: ImageHandle) -> Self {
+		Self {
+			inner: ImageInner {
+				handle,
+				size: None,
+				roundings: Vec4::ZERO,
+				placeholder_color: FillMode::Color(INPUT_BACKGROUND_COLOR),
+				error_color: FillMode::Color(ERROR_COLOR),
+			},
+			signals: SignalGenerator::default(),
+			shown: ImageState::Loading,
+		}
+	}
+
+	/// Set the size of the image.
+	pub fn set_size(self, size: Vec2) -> Self {
+		Self { inner: ImageInner { size: Some(size), ..self.inner }, ..self }
+	}
+
+	/// Set the roundings of the image.
+	pub fn roundings(self, roundings: impl Into<Vec4>) -> Self {
+		Self { inner: ImageInner { roundings: roundings.into(), ..self.inner }, ..self }
+	}
+
+	/// Set the fill of the placeholder drawn while the image is loading.
+	pub fn placeholder_color(self, placeholder_color: impl Into<FillMode>) -> Self {
+		Self { inner: ImageInner { placeholder_color: placeholder_color.into(), ..self.inner }, ..self }
+	}
+
+	/// Set the fill of the placeholder drawn if the image failed to load.
+	pub fn error_color(self, error_color: impl Into<FillMode>) -> Self {
+		Self { inner: ImageInner { error_color: error_color.into(), ..self.inner }, ..self }
+	}
+}
+
+impl<S: Signal, A: App<Signal = S>> Widget for Image<S, A> {
+	type Signal = S;
+	type Application = A;
+
+	fn size(&self, _: LayoutId, _: &Painter, _: &Layout<Self::Signal, Self::Application>) -> Vec2 {
+		self.inner.size.or_else(|| self.inner.handle.texture().map(|(_, size)| size)).unwrap_or(PLACEHOLDER_SIZE)
+	}
+
+	fn draw(&mut self, painter: &mut Painter, size: Vec2) {
+		let rect = Rect::from_lt_size(Vec2::ZERO, size);
+		match &self.shown {
+			ImageState::Ready(texture_id, texture_size) => {
+				painter.set_fill_mode(FillMode::Texture(*texture_id, rect.lt(), rect.rb(), Vec2::ZERO, *texture_size));
+			},
+			ImageState::Loading => painter.set_fill_mode(self.inner.placeholder_color.clone()),
+			ImageState::Error(_) => painter.set_fill_mode(self.inner.error_color.clone()),
+		}
+		painter.draw_rect(rect, self.inner.roundings);
+	}
+
+	fn handle_event(&mut self, app: &mut A, input_state: &mut InputState<Self::Signal>, id: LayoutId, area: Rect, _: Vec2) -> bool {
+		self.signals.generate_signals(app, &mut self.inner, input_state, id, area, false, false);
+		let state = self.inner.handle.state();
+		if state != self.shown {
+			self.shown = state;
+			true
+		}else {
+			false
+		}
+	}
+
+	fn event_handle_strategy(&self) -> EventHandleStrategy {
+		// keep checking the handle until the image is decoded.
+		if self.shown == ImageState::Loading {
+			EventHandleStrategy::AlwaysSecondary
+		}else {
+			EventHandleStrategy::OnHover
+		}
+	}
+}
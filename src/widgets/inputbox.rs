@@ -1,10 +1,17 @@
 //! A simple input box widget.
 
-use crate::{layout::{Layout, LayoutId}, prelude::{AnimatedColor, Animatedf32, Color, FillMode, FontId, ImeString, InputState, Key, Painter, Rect, Vec2, Vec4}, App};
+use std::{any::Any, cell::{Cell, RefCell}, marker::PhantomData, ops::Range, rc::{Rc, Weak}};
 
-use super::{styles::{BRIGHT_FACTOR, CONTENT_TEXT_SIZE, DEFAULT_PADDING, DEFAULT_ROUNDING, DISABLE_TEXT_COLOR, INPUT_BACKGROUND_COLOR, INPUT_BORDER_COLOR, PRIMARY_COLOR, SECONDARY_TEXT_COLOR, SELECTED_TEXT_COLOR}, EventHandleStrategy, Signal, SignalGenerator, Widget};
+use time::Duration;
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::{layout::{Layout, LayoutId, OverlayAnchor}, prelude::{AnimatedColor, Animatedf32, Color, FillMode, FontId, FontRole, ImeString, InputState, Key, Painter, Rect, Vec2, Vec4}, App, window::input_state::MOUSE_RIGHT_ID};
+
+use super::{context_menu::{ContextMenu, ContextMenuHandle, ContextMenuItem}, localizer::{BuiltinMessage, DefaultLocalizer, Localizer}, ScrollSource, styles::{BRIGHT_FACTOR, CONTENT_TEXT_SIZE, DEFAULT_PADDING, DEFAULT_ROUNDING, DISABLE_TEXT_COLOR, INPUT_BACKGROUND_COLOR, INPUT_BORDER_COLOR, ERROR_COLOR, PRIMARY_COLOR, SECONDARY_TEXT_COLOR, SELECTED_TEXT_COLOR}, EventHandleStrategy, Signal, SignalGenerator, Widget, DOUBLE_CLICK_THRESHOLD};
 
 /// The word splitter for the input box.
+/// 
+/// Note: [`Pointer`] no longer uses it, word boundaries follow the Unicode word segmentation rules (UAX#29).
 pub static WORD_SPLITER: &[char] = &[' ', '\t', '\n', ';', ',', '.', ':', '!', '?', '(', ')', '[', ']', '{', '}', '<', '>', '/', '\\', '\'', '\"', '@', '#', '$', '%', '^', '&', '*', '-', '_', '+', '=', '|', '`', '~'];
 
 /// A simple input box widget.
@@ -21,13 +28,69 @@ pub struct InputBox<S: Signal, A: App<Signal = S>> {
 	/// The signal will be constructed with the current text in the input box.
 	#[allow(clippy::type_complexity)]
 	pub on_change: Option<Box<dyn Fn(&mut InputBoxInner) -> S>>,
+	/// How long the text has to stay unchanged before [`Self::on_change`] is sent, zero to send it on every change.
+	/// 
+	/// A pending change is sent immediately when the input box is submitted, before [`Self::on_submit`].
+	pub change_debounce: Duration,
+	/// The signal to send when the input is rejected or the text becomes invalid.
+	/// 
+	/// The signal will be constructed with the error message.
+	#[allow(clippy::type_complexity)]
+	pub on_invalid: Option<Box<dyn Fn(&mut InputBoxInner, &str) -> S>>,
 	/// The general signal to send when the input box is interacted with.
 	pub signals: SignalGenerator<S, InputBoxInner, A>,
 	is_typing: bool,
 	hover_factor: Animatedf32,
+	last_click_time: Option<Duration>,
+	click_count: usize,
+	selecting_by: Option<u64>,
+	pending_hit: Option<PendingHit>,
+	text_offset: Vec2,
+	/// the time of the last change not sent yet because of [`Self::change_debounce`].
+	pending_change: Option<Duration>,
+	/// the candidates of the completer, shared with the popup showing them.
+	completions: Rc<RefCell<CompletionState>>,
+	completion_session: u64,
+	/// the open context menu.
+	menu: Option<ContextMenuHandle>,
+	/// the item chosen in the context menu, run by the input box.
+	menu_command: Rc<Cell<Option<EditCommand>>>,
+	/// whether the right mouse button was pressed in the last event frame.
+	menu_pressed: bool,
+	/// whether the input box had the keyboard focus in the last event handling.
+	has_focus: bool,
+	/// whether the caret is shown in the blinking.
+	caret_shown: bool,
+	/// the time the caret last moved, the blinking starts from it.
+	caret_moved_at: Duration,
+	/// the area of the caret relative to the input box as last drawn, redrawn alone when blinking.
+	caret_area: Option<Rect>,
+}
+
+/// An item of the context menu of the input box.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum EditCommand {
+	Cut,
+	Copy,
+	Paste,
+	SelectAll,
+}
+
+/// A mouse action on the text, resolved in [`Widget::draw`] where the text metrics are available.
+enum PendingHit {
+	/// Place the pointer at the position, extending the selection if `true`.
+	Place(Vec2, bool),
+	/// Select the word at the position.
+	Word(Vec2),
+	/// Select the line at the position.
+	Line(Vec2),
 }
 
 /// The inner properties of the input box.
+/// 
+/// The validator, the highlighter and the completer are not serialized.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct InputBoxInner {
 	/// The placeholder text to display when the input box is empty.
 	pub placeholder: String,
@@ -39,17 +102,25 @@ pub struct InputBoxInner {
 	pub size: Vec2,
 	/// The font id of the input box.
 	pub font: FontId,
+	/// The font role of the input box, see [`FontRole`].
+	pub font_role: Option<FontRole>,
 	/// The font size of the input box.
 	pub font_size: f32,
 	/// The validator to use for the input box.
+	#[cfg_attr(feature = "serde", serde(skip))]
 	pub validator: Option<Box<dyn Validator>>,
-	// /// The highlighter to use for the input box.
-	// pub highligher: Option<Box<dyn Highlighter>>,
-	// /// The completer to use for the input box.
-	// pub completer: Option<Box<dyn Completer>>,
+	/// The highlighter to use for the input box, see [`Highlighter`].
+	#[cfg_attr(feature = "serde", serde(skip))]
+	pub highlighter: Option<Box<dyn Highlighter>>,
+	/// The completer to use for the input box, see [`Completer`].
+	#[cfg_attr(feature = "serde", serde(skip))]
+	pub completer: Option<Box<dyn Completer>>,
 	/// The current pointer position in the input box.
 	pub pointer: Pointer,
-	/// The current scroll position in the input box.
+	/// The current scroll position in the input box, the offset of the text from the top left of the content area.
+	/// 
+	/// While typing, it follows the pointer to keep it visible.
+	/// You can also set it manually, it will be clamped to the text size when drawing.
 	pub scroll_position: Vec2,
 	/// The background color of the input box.
 	pub background_color: FillMode,
@@ -65,8 +136,51 @@ pub struct InputBoxInner {
 	pub placeholder_color: FillMode,
 	/// The color of the selected text.
 	pub selected_color: FillMode,
+	/// The current error given by [`Validator::check_text`], `None` if the text is valid.
+	/// 
+	/// The message is shown at the right side of the input box, or under it if [`Self::error_label`] is set.
+	pub error: Option<String>,
+	/// Whether to reserve a line under the input box for the error message.
+	/// 
+	/// The line is always reserved even there is no error, so the layout will not jump.
+	pub error_label: bool,
+	/// Whether to open a menu with cut, copy, paste and select all on right click.
+	pub context_menu: bool,
+	/// The shape of the caret.
+	pub caret_style: CaretStyle,
+	/// The width of the bar caret, or the height of the underscore caret.
+	pub caret_width: f32,
+	/// The color of the caret, `None` to use [`Self::text_color`].
+	pub caret_color: Option<FillMode>,
+	/// How long the caret stays shown or hidden when blinking, `None` to not blink.
+	/// 
+	/// The caret stays shown while typing or moving it, and starts blinking after the interval.
+	pub caret_blink: Option<Duration>,
 }
 
+/// The shape of the caret of an input box.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CaretStyle {
+	/// A thin vertical bar before the current char.
+	#[default]
+	Bar,
+	/// A box covering the current char, as in terminals.
+	Block,
+	/// A line under the current char.
+	Underscore,
+}
+
+/// How long the caret stays shown or hidden when blinking by default, following the platform.
+#[cfg(target_os = "windows")]
+pub const DEFAULT_CARET_BLINK: Duration = Duration::milliseconds(530);
+/// How long the caret stays shown or hidden when blinking by default, following the platform.
+#[cfg(any(target_os = "macos", target_os = "ios", target_os = "android"))]
+pub const DEFAULT_CARET_BLINK: Duration = Duration::milliseconds(500);
+/// How long the caret stays shown or hidden when blinking by default, following the platform.
+#[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "ios", target_os = "android")))]
+pub const DEFAULT_CARET_BLINK: Duration = Duration::milliseconds(600);
+
 impl Default for InputBoxInner {
 	fn default() -> Self {
 		Self {
@@ -75,6 +189,7 @@ impl Default for InputBoxInner {
 			text: "".to_string(),
 			size: Vec2::new(200.0, CONTENT_TEXT_SIZE),
 			font: 0,
+			font_role: Some(FontRole::Body),
 			font_size: CONTENT_TEXT_SIZE,
 			validator: None,
 			pointer: Pointer::default(),
@@ -86,14 +201,25 @@ impl Default for InputBoxInner {
 			roundings: Vec4::same(DEFAULT_ROUNDING),
 			placeholder_color: FillMode::Color(DISABLE_TEXT_COLOR),
 			selected_color: FillMode::Color(SELECTED_TEXT_COLOR),
-			// highligher: None,
-			// completer: None,
+			error: None,
+			error_label: false,
+			highlighter: None,
+			completer: None,
+			context_menu: true,
+			caret_style: CaretStyle::Bar,
+			caret_width: 2.0,
+			caret_color: None,
+			caret_blink: Some(DEFAULT_CARET_BLINK),
 		}
 	}
 }
 
 /// The current pointer position in the input box.
-#[derive(Clone, Copy, Debug, Default)]
+/// 
+/// The indices are counted in chars, but the pointer always moves and deletes by grapheme clusters,
+/// so emoji and combining sequences are never split.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Pointer {
 	/// The start index of the selected text.
 	/// 
@@ -134,9 +260,11 @@ impl PointerPos {
 
 /// A enum to represent the amount of the pointer movement.
 pub enum PointerAmount {
-	/// Move the pointer by one character.
+	/// Move the pointer by one character, which is a grapheme cluster.
 	Char(isize),
-	/// Move the pointer by one word.
+	/// Move the pointer by one word, following the Unicode word boundaries.
+	/// 
+	/// Moving forward stops at the end of the next word, moving backward stops at the start of the previous word.
 	Word(isize),
 	/// Move the pointer by one line.
 	Line(isize),
@@ -178,37 +306,15 @@ impl Pointer {
 	pub fn move_by(&mut self, text: &str, amount: PointerAmount, with_selection: bool) {
 		match amount {
 			PointerAmount::Char(amount) => {
-				let new_index = self.current_index_utf8() as isize + amount;
-				if with_selection {
-					if new_index < 0 {
-						if self.is_start_current {
-							self.start = 0;
-						}else {
-							self.start = 0;
-							self.end = 0;
-							self.is_start_current = false;
-						}
-					}else if self.is_start_current {
-						self.start = new_index as usize;
-					}else {
-						self.end = new_index as usize;
-					}
-				}else if new_index < 0 {
-					self.start = 0;
-					self.end = 0;
-					self.is_start_current = false;
-				}else {
-					self.start = new_index as usize;
-					self.end = new_index as usize;
-					self.is_start_current = false;
-				}
+				let new_index = step_graphemes(text, self.current_index_utf8(), amount);
+				self.set_current(new_index, with_selection);
 			},
-			PointerAmount::Word(delta) | PointerAmount::Line(delta) => {
-				let spliter = if matches!(amount, PointerAmount::Word(_)) {
-					WORD_SPLITER
-				}else {
-					&['\n']
-				};
+			PointerAmount::Word(amount) => {
+				let new_index = step_words(text, self.current_index_utf8(), amount);
+				self.set_current(new_index, with_selection);
+			},
+			PointerAmount::Line(delta) => {
+				let spliter = &['\n'];
 
 				let words = text.split(spliter);
 				let mut current_word = 0;
@@ -258,6 +364,17 @@ impl Pointer {
 			}
 		}
 
+		self.normalize(text);
+	}
+
+	/// Move the pointer to the given char index, snapping to the grapheme cluster containing it.
+	pub fn move_to(&mut self, text: &str, index: usize, with_selection: bool) {
+		let index = step_graphemes(text, index, 0);
+		self.set_current(index, with_selection);
+		self.normalize(text);
+	}
+
+	fn normalize(&mut self, text: &str) {
 		if self.start > self.end {
 			std::mem::swap(&mut self.start, &mut self.end);
 			self.is_start_current = !self.is_start_current;
@@ -267,6 +384,56 @@ impl Pointer {
 		self.end = self.end.min(len);
 	}
 
+	fn set_current(&mut self, index: usize, with_selection: bool) {
+		if with_selection {
+			if self.is_start_current {
+				self.start = index;
+			}else {
+				self.end = index;
+			}
+		}else {
+			self.start = index;
+			self.end = index;
+			self.is_start_current = false;
+		}
+	}
+
+	/// Select the word, or the run of spaces or punctuations, around the pointer.
+	/// 
+	/// Refer to double click in most text editors.
+	pub fn select_word(&mut self, text: &str) {
+		let current = self.current_index_utf8();
+		let mut index = 0;
+		for segment in text.split_word_bounds() {
+			let len = segment.chars().count();
+			if current < index + len || index + len == text.chars().count() {
+				self.start = index;
+				self.end = index + len;
+				self.is_start_current = false;
+				return;
+			}
+			index += len;
+		}
+	}
+
+	/// Select the line around the pointer, without the line break.
+	/// 
+	/// Refer to triple click in most text editors.
+	pub fn select_line(&mut self, text: &str) {
+		let current = self.current_index_utf8();
+		let mut index = 0;
+		for line in text.split('\n') {
+			let len = line.chars().count();
+			if current <= index + len {
+				self.start = index;
+				self.end = index + len;
+				self.is_start_current = false;
+				return;
+			}
+			index += len + 1;
+		}
+	}
+
 	/// Delete the selected text only.
 	pub fn delete_selected_text(&mut self, text: &mut String) {
 		if self.has_selected_text() {
@@ -284,9 +451,53 @@ impl Pointer {
 			self.end = self.start;
 		}else if self.current_index_utf8() > 0 && self.current_index_utf8() <= text.chars().count() {
 			let current = self.current_index_utf8();
-			text.replace_range(convert_range(text, current - 1, current), "");
-			self.start -= 1;
-			self.end -= 1;
+			let previous = step_graphemes(text, current, -1);
+			text.replace_range(convert_range(text, previous, current), "");
+			self.start = previous;
+			self.end = previous;
+		}
+	}
+
+	/// Delete the selected text or the character after the pointer.
+	pub fn delete_forward(&mut self, text: &mut String) {
+		if self.has_selected_text() {
+			self.delete_selected_text(text);
+		}else if self.current_index_utf8() < text.chars().count() {
+			let current = self.current_index_utf8();
+			let next = step_graphemes(text, current, 1);
+			text.replace_range(convert_range(text, current, next), "");
+			self.start = current;
+			self.end = current;
+		}
+	}
+
+	/// Delete the selected text or the word before the pointer.
+	/// 
+	/// Refer to `ctrl + backspace` in most text editors.
+	pub fn delete_word(&mut self, text: &mut String) {
+		if self.has_selected_text() {
+			self.delete_selected_text(text);
+		}else {
+			let current = self.current_index_utf8().min(text.chars().count());
+			let previous = step_words(text, current, -1);
+			text.replace_range(convert_range(text, previous, current), "");
+			self.start = previous;
+			self.end = previous;
+		}
+	}
+
+	/// Delete the selected text or the word after the pointer.
+	/// 
+	/// Refer to `ctrl + delete` in most text editors.
+	pub fn delete_word_forward(&mut self, text: &mut String) {
+		if self.has_selected_text() {
+			self.delete_selected_text(text);
+		}else {
+			let current = self.current_index_utf8().min(text.chars().count());
+			let next = step_words(text, current, 1);
+			text.replace_range(convert_range(text, current, next), "");
+			self.start = current;
+			self.end = current;
 		}
 	}
 
@@ -315,6 +526,11 @@ impl Pointer {
 
 	/// Insert some text at the current position of the pointer.
 	pub fn insert_text(&mut self, text: &mut String, new_text: ImeString, validator: &Option<Box<dyn Validator>>) -> ValidatorResult {
+		self.insert_text_localized(text, new_text, validator, &DefaultLocalizer)
+	}
+
+	/// Insert some text at the current position of the pointer, validation messages are translated by the localizer.
+	pub fn insert_text_localized(&mut self, text: &mut String, new_text: ImeString, validator: &Option<Box<dyn Validator>>, localizer: &dyn Localizer) -> ValidatorResult {
 		if new_text.is_empty() {
 			return ValidatorResult::Valid;
 		}
@@ -325,7 +541,7 @@ impl Pointer {
 		}
 		let out = if let ImeString::ImeOff(inner) = &new_text {
 			if let Some(validator) = validator {
-				validator.validate(inner, text, *self)
+				validator.validate_localized(inner, text, *self, localizer)
 			}else {
 				ValidatorResult::Valid
 			}
@@ -352,6 +568,11 @@ impl Pointer {
 		out
 	}
 
+	/// Get the char indices of the start and the end of the selected text, the start is never after the end.
+	pub fn selection(&self) -> (usize, usize) {
+		(self.start, self.end)
+	}
+
 	/// Check if there is any selected text.
 	pub fn has_selected_text(&self) -> bool {
 		self.start != self.end
@@ -388,7 +609,9 @@ impl Pointer {
 				end_line += 1;
 			}
 		}
-		lines[start_line..end_line + 1].to_vec()
+		// a selection spanning the final line break ends on a line `lines` does not yield.
+		let end_line = end_line.min(lines.len().saturating_sub(1));
+		lines.get(start_line.min(end_line)..=end_line).map(<[_]>::to_vec).unwrap_or_default()
 	}
 
 	/// Caculate the position of the pointer.
@@ -413,12 +636,14 @@ impl Pointer {
 		};
 
 		if self.has_selected_text() {
-			let selected_lines = self.get_selected_lines(text);
 			let selected_text = self.get_selected_text_lines(text);
 			let mut selection_rect = Vec::new();
-			for (i, (total, selected)) in selected_lines.into_iter().zip(selected_text.into_iter()).enumerate() {
-				let start_index = text.find(selected).unwrap();
-				let start_size = painter.text_size_pointer(font_id, font_size, &total[0..start_index]).unwrap_or_default();
+			// only the first line of the selection starts inside the line.
+			let before = char_slice(text, 0, self.start);
+			let first_line_before = &before[before.rfind('\n').map_or(0, |index| index + 1)..];
+			for (i, selected) in selected_text.into_iter().enumerate() {
+				let before = if i == 0 { first_line_before } else { "" };
+				let start_size = painter.text_size_pointer(font_id, font_size, before).unwrap_or_default();
 				let selected_size = painter.text_size_pointer(font_id, font_size, selected).unwrap_or_default();
 				selection_rect.push(Rect::from_lt_size(
 					Vec2::new(start_size.x, i as f32 * line_height * if self.is_start_current { 1.0 } else { -1.0 } + pointer_pos.y),
@@ -430,6 +655,32 @@ impl Pointer {
 			PointerPos::Single(pointer_pos)
 		}
 	}
+
+	/// Get the char index closest to the given position, the inverse of [`Self::caculate_pointer_pos`].
+	/// 
+	/// The position is relative to the top left of the text.
+	pub fn index_at_pos(text: &str, pos: Vec2, font_size: f32, font_id: FontId, painter: &Painter) -> usize {
+		let line_height = painter.line_height(font_id, font_size).unwrap_or_default();
+		let lines = text.split('\n').collect::<Vec<_>>();
+		let line_index = if line_height > 0.0 {
+			((pos.y / line_height).max(0.0) as usize).min(lines.len() - 1)
+		}else {
+			0
+		};
+		let line_start = lines[0..line_index].iter().map(|line| line.chars().count() + 1).sum::<usize>();
+		let line = lines[line_index];
+
+		let mut previous = (0, 0.0);
+		for boundary in grapheme_boundaries(line).into_iter().skip(1) {
+			let width = painter.text_size_pointer(font_id, font_size, &line[convert_range(line, 0, boundary)]).unwrap_or_default().x;
+			if width >= pos.x {
+				// pick the nearer side of the grapheme.
+				return line_start + if pos.x - previous.1 < width - pos.x { previous.0 } else { boundary };
+			}
+			previous = (boundary, width);
+		}
+		line_start + previous.0
+	}
 }
 
 impl<S: Signal, A: App<Signal = S>> Default for InputBox<S, A> {
@@ -438,15 +689,34 @@ impl<S: Signal, A: App<Signal = S>> Default for InputBox<S, A> {
 			inner: InputBoxInner::default(),
 			on_submit: None,
 			on_change: None,
+			change_debounce: Duration::ZERO,
+			on_invalid: None,
 			signals: SignalGenerator::default(),
 			is_typing: false,
 			hover_factor: Animatedf32::default(),
+			last_click_time: None,
+			click_count: 0,
+			selecting_by: None,
+			pending_hit: None,
+			text_offset: Vec2::ZERO,
+			pending_change: None,
+			completions: Rc::new(RefCell::new(CompletionState::default())),
+			completion_session: 0,
+			menu: None,
+			menu_command: Rc::new(Cell::new(None)),
+			menu_pressed: false,
+			has_focus: false,
+			caret_shown: true,
+			caret_moved_at: Duration::ZERO,
+			caret_area: None,
 		}
 	}
 }
 
 impl<S: Signal, A: App<Signal = S>> InputBox<S, A> {
 	/// Create a new input box.
+	/// 
+	/// `font` is used when the [`FontRole::Body`] font is not registered, see [`Self::font_role`].
 	pub fn new(font: FontId, font_size: f32) -> Self {
 		Self {
 			inner: InputBoxInner {
@@ -513,21 +783,46 @@ impl<S: Signal, A: App<Signal = S>> InputBox<S, A> {
 		}
 	}
 
-	// /// Set the highlighter to use for the input box.
-	// pub fn highligher(self, highligher: impl Highlighter + 'static) -> Self {
-	// 	Self {
-	// 		highligher: Some(Box::new(highligher)),
-	// 		..self
-	// 	}
-	// }
+	/// Set the highlighter to use for the input box.
+	pub fn highlighter(self, highlighter: impl Highlighter + 'static) -> Self {
+		Self {
+			inner: InputBoxInner { highlighter: Some(Box::new(highlighter)), ..self.inner },
+			..self
+		}
+	}
+
+	/// Set the completer to use for the input box.
+	pub fn completer(self, completer: impl Completer + 'static) -> Self {
+		Self {
+			inner: InputBoxInner { completer: Some(Box::new(completer)), ..self.inner },
+			..self
+		}
+	}
 
-	// /// Set the completer to use for the input box.
-	// pub fn completer(self, completer: impl Completer + 'static) -> Self {
-	// 	Self {
-	// 		completer: Some(Box::new(completer)),
-	// 		..self
-	// 	}
-	// }
+	/// Set whether to open a menu with cut, copy, paste and select all on right click.
+	pub fn context_menu(self, context_menu: bool) -> Self {
+		Self { inner: InputBoxInner { context_menu, ..self.inner }, ..self }
+	}
+
+	/// Set the shape of the caret.
+	pub fn caret_style(self, caret_style: CaretStyle) -> Self {
+		Self { inner: InputBoxInner { caret_style, ..self.inner }, ..self }
+	}
+
+	/// Set the width of the bar caret, or the height of the underscore caret.
+	pub fn caret_width(self, caret_width: f32) -> Self {
+		Self { inner: InputBoxInner { caret_width, ..self.inner }, ..self }
+	}
+
+	/// Set the color of the caret.
+	pub fn caret_color(self, caret_color: impl Into<FillMode>) -> Self {
+		Self { inner: InputBoxInner { caret_color: Some(caret_color.into()), ..self.inner }, ..self }
+	}
+
+	/// Set how long the caret stays shown or hidden when blinking, `None` to not blink.
+	pub fn caret_blink(self, caret_blink: Option<Duration>) -> Self {
+		Self { inner: InputBoxInner { caret_blink, ..self.inner }, ..self }
+	}
 
 	/// Set the signal to send when the input box is submitted.
 	pub fn on_submit(self, on_submit: impl Fn(&mut InputBoxInner) -> S + 'static) -> Self {
@@ -545,19 +840,266 @@ impl<S: Signal, A: App<Signal = S>> InputBox<S, A> {
 		}
 	}
 
+	/// Set how long the text has to stay unchanged before the change signal is sent, zero to send it on every change.
+	/// 
+	/// Useful when the change signal triggers expensive work, e.g. searching.
+	pub fn change_debounce(self, change_debounce: Duration) -> Self {
+		Self {
+			change_debounce,
+			..self
+		}
+	}
+
+	/// Set the font of the input box.
+	/// 
+	/// Clears the font role, so the font is used even if the theme registers one, see [`Self::font_role`].
+	pub fn font(self, font: FontId) -> Self {
+		Self {
+			inner: InputBoxInner {
+				font,
+				font_role: None,
+				..self.inner
+			},
+			..self
+		}
+	}
+
+	/// Set the font role of the input box, `None` to always use the font id.
+	pub fn font_role(self, font_role: Option<FontRole>) -> Self {
+		Self {
+			inner: InputBoxInner {
+				font_role,
+				..self.inner
+			},
+			..self
+		}
+	}
+
+	/// Set the signal to send when the input is rejected or the text becomes invalid.
+	pub fn on_invalid(self, on_invalid: impl Fn(&mut InputBoxInner, &str) -> S + 'static) -> Self {
+		Self {
+			on_invalid: Some(Box::new(on_invalid)),
+			..self
+		}
+	}
+
+	/// Set whether to reserve a line under the input box for the error message.
+	pub fn error_label(self, error_label: bool) -> Self {
+		Self {
+			inner: InputBoxInner {
+				error_label,
+				..self.inner
+			},
+			..self
+		}
+	}
+
 	/// Set the current pointer position in the input box.
 	pub fn pointer(self, pointer: Pointer) -> Self {
 		Self { inner: InputBoxInner { pointer, ..self.inner }, ..self }
 	}
 
+	/// Scroll the text so the caret stays inside the view, keeping a margin of about one character horizontally.
+	fn follow_pointer(&mut self, caret: Rect, view: Vec2) {
+		let margin = Vec2::x(self.inner.font_size.min(view.x / 4.0));
+		let least = caret.rb() + margin - view;
+		let most = caret.lt() - margin;
+		self.inner.scroll_position = self.inner.scroll_position.max(least).min(most.max(least));
+	}
+
+	fn resolve_hit(&mut self, text: &str, painter: &Painter) {
+		let Some(hit) = self.pending_hit.take() else {
+			return;
+		};
+		let index_at = |pos: Vec2| if self.inner.text.is_empty() {
+			0
+		}else {
+			Pointer::index_at_pos(text, pos, self.inner.font_size, painter.resolve_font(self.inner.font_role, self.inner.font), painter)
+		};
+		match hit {
+			PendingHit::Place(pos, with_selection) => {
+				let index = index_at(pos);
+				self.inner.pointer.move_to(&self.inner.text, index, with_selection);
+			},
+			PendingHit::Word(pos) => {
+				let index = index_at(pos);
+				self.inner.pointer.move_to(&self.inner.text, index, false);
+				self.inner.pointer.select_word(&self.inner.text);
+			},
+			PendingHit::Line(pos) => {
+				let index = index_at(pos);
+				self.inner.pointer.move_to(&self.inner.text, index, false);
+				self.inner.pointer.select_line(&self.inner.text);
+			},
+		}
+	}
+
+	/// Correct and check the text by the validator.
+	fn check_text(&mut self, submitting: bool, input_state: &mut InputState<S>, id: LayoutId) {
+		if let Some(validator) = &self.inner.validator {
+			let localizer = input_state.localizer();
+			if validator.correct_text(&mut self.inner.text, submitting, localizer) {
+				self.inner.pointer.move_to_end(&self.inner.text);
+			}
+			let error = validator.check_text(&self.inner.text, localizer);
+			self.set_error(error, input_state, id);
+		}
+	}
+
+	/// Set the current error, sends [`Self::on_invalid`] if there is a new error.
+	fn set_error(&mut self, error: Option<String>, input_state: &mut InputState<S>, id: LayoutId) {
+		if let Some(message) = &error {
+			if self.inner.error.as_ref() != Some(message) {
+				if let Some(on_invalid) = &self.on_invalid {
+					let signal = on_invalid(&mut self.inner, message);
+					input_state.send_signal_from(id, signal);
+				}
+			}
+		}
+		self.inner.error = error;
+	}
+
+	fn error_label_height(&self) -> f32 {
+		if self.inner.error_label {
+			self.inner.font_size * 0.75 + self.inner.padding.y
+		}else {
+			0.0
+		}
+	}
+
+	fn update_border_color(&mut self) {
+		self.inner.border_color.set(if self.inner.error.is_some() {
+			ERROR_COLOR
+		}else if self.is_typing {
+			PRIMARY_COLOR + BRIGHT_FACTOR * Color::WHITE
+		}else {
+			INPUT_BORDER_COLOR
+		});
+	}
+
 	fn submit(&mut self, input_state: &mut InputState<S>, id: LayoutId) {
 		self.is_typing = false;
-		self.inner.border_color.set(INPUT_BORDER_COLOR);
+		self.check_text(true, input_state, id);
+		self.update_border_color();
+		if self.pending_change.is_some() {
+			self.send_change(input_state, id);
+		}
 		if let Some(on_submit) = &self.on_submit {
 			let signal = on_submit(&mut self.inner);
 			input_state.send_signal_from(id, signal);
 		}
 	}
+
+	/// Send the change signal, or wait until the text stays unchanged for [`Self::change_debounce`].
+	fn text_changed(&mut self, input_state: &mut InputState<S>, id: LayoutId) {
+		if self.change_debounce <= Duration::ZERO {
+			self.send_change(input_state, id);
+		}else {
+			self.pending_change = Some(input_state.program_running_time());
+			input_state.request_frame_after(self.change_debounce);
+		}
+	}
+
+	fn send_change(&mut self, input_state: &mut InputState<S>, id: LayoutId) {
+		self.pending_change = None;
+		if let Some(on_change) = &self.on_change {
+			let signal = on_change(&mut self.inner);
+			input_state.send_signal_from(id, signal);
+		}
+	}
+
+	/// Ask the completer for the candidates, opening the popup if there are any and closing it otherwise.
+	fn update_completions(&mut self, input_state: &mut InputState<S>, id: LayoutId, area: Rect) {
+		let Some(completer) = &self.inner.completer else {
+			return;
+		};
+		let (range, candidates) = completer.complete(&self.inner.text, self.inner.pointer);
+		if candidates.is_empty() {
+			self.close_completions();
+			return;
+		}
+		let mut state = self.completions.borrow_mut();
+		state.range = range;
+		state.candidates = candidates;
+		state.first_shown = 0;
+		state.select(0);
+		state.width = area.w;
+		state.font = self.inner.font;
+		state.font_role = self.inner.font_role;
+		state.font_size = self.inner.font_size;
+		state.padding = self.inner.padding;
+		if state.session.is_none() {
+			self.completion_session += 1;
+			state.session = Some(self.completion_session);
+			let anchor = OverlayAnchor::Widget { id, align: Vec2::new(0.0, 1.0), offset: Vec2::y(-self.error_label_height()) };
+			input_state.push_overlay(anchor, CompletionPopup::<S, A> {
+				state: Rc::downgrade(&self.completions),
+				session: self.completion_session,
+				seen_version: 0,
+				drawn_size: Vec2::ZERO,
+				last_hover: None,
+				_marker: PhantomData,
+			});
+		}
+	}
+
+	/// Close the popup, it removes itself in the next event frame.
+	fn close_completions(&mut self) {
+		let mut state = self.completions.borrow_mut();
+		state.session = None;
+		state.candidates.clear();
+		state.pressed = None;
+		state.version += 1;
+	}
+
+	/// Open the context menu at the position, focusing the input box.
+	fn open_context_menu(&mut self, input_state: &mut InputState<S>, id: LayoutId, pos: Vec2) {
+		self.is_typing = true;
+		self.update_border_color();
+		let can_copy = self.inner.pointer.has_selected_text() && !self.inner.password;
+		let localizer = input_state.localizer();
+		let item = |message: BuiltinMessage, command: EditCommand| {
+			let chosen = self.menu_command.clone();
+			ContextMenuItem::<S, A>::action(localizer.message(message), move |_, _, _| chosen.set(Some(command)))
+		};
+		let menu = ContextMenu::new()
+			.font(self.inner.font)
+			.font_role(self.inner.font_role)
+			.font_size(self.inner.font_size)
+			.item(item(BuiltinMessage::Cut, EditCommand::Cut).shortcut("Ctrl+X").enabled(can_copy))
+			.item(item(BuiltinMessage::Copy, EditCommand::Copy).shortcut("Ctrl+C").enabled(can_copy))
+			.item(item(BuiltinMessage::Paste, EditCommand::Paste).shortcut("Ctrl+V"))
+			.separator()
+			.item(item(BuiltinMessage::SelectAll, EditCommand::SelectAll).shortcut("Ctrl+A").enabled(!self.inner.text.is_empty()));
+		if let Some(menu) = self.menu.replace(menu.open(input_state, id, pos)) {
+			menu.close();
+		}
+	}
+
+	fn is_completing(&self) -> bool {
+		self.completions.borrow().session.is_some()
+	}
+
+	/// Replace the completed range of the text with the candidate and close the popup.
+	/// 
+	/// Returns false if there is no such candidate.
+	fn accept_completion(&mut self, index: usize) -> bool {
+		let (range, candidate) = {
+			let state = self.completions.borrow();
+			match state.candidates.get(index) {
+				Some(candidate) => (state.range.clone(), candidate.clone()),
+				None => return false,
+			}
+		};
+		let len = self.inner.text.chars().count();
+		let start = range.start.min(len);
+		let end = range.end.clamp(start, len);
+		let bytes = convert_index(&self.inner.text, start)..convert_index(&self.inner.text, end);
+		self.inner.text.replace_range(bytes, &candidate);
+		self.inner.pointer.move_to(&self.inner.text, start + candidate.chars().count(), false);
+		self.close_completions();
+		true
+	}
 }
 
 /// Possible results of input validation.
@@ -587,28 +1129,302 @@ pub trait Validator {
 	/// Returns an error message if the input is invalid, `None` for valid input.
 	fn validate(&self, newly_input: &str, current_text: &str, pointer: Pointer) -> ValidatorResult;
 
+	/// Same as [`Self::validate`], but messages should be translated by the given localizer.
+	/// 
+	/// The input box always calls this method, by default it simply calls [`Self::validate`].
+	fn validate_localized(&self, newly_input: &str, current_text: &str, pointer: Pointer, localizer: &dyn Localizer) -> ValidatorResult {
+		let _ = localizer;
+		self.validate(newly_input, current_text, pointer)
+	}
+
 	/// Whether to validate the input when the input box changes.
 	/// 
 	/// If true, the `validate` method will be called when the input box changes.
 	/// If false, the `validate` method will only be called when the input box is submitted.
 	fn validate_when_change(&self) -> bool;
+
+	/// Check the whole text in the input box, returns the error message if it is invalid.
+	/// 
+	/// Called after every change and when the input box is submitted,
+	/// the input box shows the message with [`ERROR_COLOR`] until the text is valid again.
+	/// By default, the text is always valid.
+	fn check_text(&self, text: &str, localizer: &dyn Localizer) -> Option<String> {
+		let _ = (text, localizer);
+		None
+	}
+
+	/// Correct the text in the input box, called after every change and when the input box is submitted.
+	/// 
+	/// Returns true if the text is changed, the pointer will be moved to the end of the text then.
+	/// By default, the text is never changed.
+	fn correct_text(&self, text: &mut String, submitting: bool, localizer: &dyn Localizer) -> bool {
+		let _ = (text, submitting, localizer);
+		false
+	}
 }
 
-// /// A trait for input highlighting.
-// pub trait Highlighter {
-// 	/// Highlight the input text and the current text in the input box.
-// 	/// 
-// 	/// Returns a list of tuples containing the highlighted text and the fill mode to use.
-// 	fn highlight(&self, text: &str, pointer: Pointer) -> Vec<(String, FillMode)>;
-// }
+/// A trait for input highlighting, e.g. syntax highlighting for a small code editor.
+pub trait Highlighter {
+	/// Highlight the current text in the input box.
+	/// 
+	/// Returns the ranges of the text, counted in chars, and the fill modes to draw them with.
+	/// The text outside the ranges is drawn with [`InputBoxInner::text_color`].
+	/// Ranges overlapping the previous ones are cut, ranges out of the text are ignored.
+	/// 
+	/// Not called for the placeholder and password inputs.
+	fn highlight(&self, text: &str, pointer: Pointer) -> Vec<(Range<usize>, FillMode)>;
+}
 
-// /// A trait for input completion.
-// pub trait Completer {
-// 	/// Give a list of completions for the input text and the current text in the input box.
-// 	/// 
-// 	/// Returns a list of completions.
-// 	fn complete(&self, text: &str, current_text: &str, pointer: Pointer) -> Vec<String>;
-// }
+/// Split the text into the ranges to draw, filling the gaps between the highlighted ranges with `base`.
+fn highlight_segments(len: usize, mut ranges: Vec<(Range<usize>, FillMode)>, base: &FillMode) -> Vec<(Range<usize>, FillMode)> {
+	ranges.sort_by_key(|(range, _)| range.start);
+	let mut out = vec!();
+	let mut index = 0;
+	for (range, fill_mode) in ranges {
+		let start = range.start.max(index);
+		let end = range.end.min(len);
+		if start >= end {
+			continue;
+		}
+		if start > index {
+			out.push((index..start, base.clone()));
+		}
+		out.push((start..end, fill_mode));
+		index = end;
+	}
+	if index < len {
+		out.push((index..len, base.clone()));
+	}
+	out
+}
+
+/// A trait for input completion, the candidates are shown in a popup below the input box.
+/// 
+/// While the popup is shown, the up and down arrow keys choose a candidate, tab and enter accept it,
+/// and escape closes the popup. Candidates can also be accepted by clicking them.
+pub trait Completer {
+	/// Give the completions for the current text in the input box, called after every change while typing.
+	/// 
+	/// Returns the range of the text to replace, counted in chars, and the candidates to replace it with,
+	/// e.g. the range of the word before the pointer and the words starting with it.
+	/// The popup is closed if there is no candidate.
+	fn complete(&self, current_text: &str, pointer: Pointer) -> (Range<usize>, Vec<String>);
+}
+
+/// A simple completer for daliy use, completes the word before the pointer from a list of words.
+#[derive(Clone, Debug, Default)]
+pub struct WordCompleter {
+	/// The words to complete.
+	pub words: Vec<String>,
+	/// Whether the typed word has to match the case of the words.
+	pub case_sensitive: bool,
+	/// How many chars of the word have to be typed before showing the candidates.
+	pub min_chars: usize,
+}
+
+impl WordCompleter {
+	/// Create a new [`WordCompleter`] with the given words, showing the candidates from the first char.
+	pub fn new(words: impl IntoIterator<Item = impl Into<String>>) -> Self {
+		Self {
+			words: words.into_iter().map(Into::into).collect(),
+			case_sensitive: false,
+			min_chars: 1,
+		}
+	}
+
+	/// Set whether the typed word has to match the case of the words.
+	pub fn case_sensitive(self, case_sensitive: bool) -> Self {
+		Self {
+			case_sensitive,
+			..self
+		}
+	}
+
+	/// Set how many chars of the word have to be typed before showing the candidates.
+	pub fn min_chars(self, min_chars: usize) -> Self {
+		Self {
+			min_chars,
+			..self
+		}
+	}
+}
+
+impl Completer for WordCompleter {
+	fn complete(&self, current_text: &str, pointer: Pointer) -> (Range<usize>, Vec<String>) {
+		let end = pointer.current_index(current_text);
+		let before = char_slice(current_text, 0, end);
+		let start = end - before.chars().rev().take_while(|c| c.is_alphanumeric() || *c == '_').count();
+		let word = char_slice(current_text, start, end);
+		if end - start < self.min_chars {
+			return (start..end, vec!());
+		}
+		let candidates = if self.case_sensitive {
+			self.words.iter().filter(|candidate| candidate.starts_with(word) && candidate.as_str() != word).cloned().collect()
+		}else {
+			let word = word.to_lowercase();
+			self.words.iter().filter(|candidate| {
+				let candidate = candidate.to_lowercase();
+				candidate.starts_with(&word) && candidate != word
+			}).cloned().collect()
+		};
+		(start..end, candidates)
+	}
+}
+
+/// How many candidates are shown in the popup of a [`Completer`] at most, the popup scrolls to show the chosen one.
+pub const MAX_SHOWN_COMPLETIONS: usize = 8;
+
+/// The candidates of a [`Completer`], shared by the input box and its popup.
+#[derive(Default)]
+struct CompletionState {
+	/// the session of the open popup, `None` if closed. Popups of other sessions remove themselves.
+	session: Option<u64>,
+	/// the range of the text to replace, counted in chars.
+	range: Range<usize>,
+	candidates: Vec<String>,
+	selected: usize,
+	first_shown: usize,
+	/// the candidate pressed in the popup, accepted by the input box.
+	pressed: Option<usize>,
+	/// whether a touch was pressed on the popup, so its release does not end typing.
+	grabbed: bool,
+	/// increased every time the popup has to be redrawn.
+	version: u64,
+	width: f32,
+	font: FontId,
+	font_role: Option<FontRole>,
+	font_size: f32,
+	padding: Vec2,
+}
+
+impl CompletionState {
+	/// Choose the candidate, scrolling the popup to show it.
+	fn select(&mut self, index: usize) {
+		self.selected = index.min(self.candidates.len().saturating_sub(1));
+		self.first_shown = self.first_shown.min(self.selected).max((self.selected + 1).saturating_sub(MAX_SHOWN_COMPLETIONS));
+		self.version += 1;
+	}
+
+	/// Choose the next or previous candidate, wrapping around.
+	fn select_by(&mut self, amount: isize) {
+		let len = self.candidates.len() as isize;
+		if len > 0 {
+			self.select((self.selected as isize + amount).rem_euclid(len) as usize);
+		}
+	}
+
+	fn row_height(&self) -> f32 {
+		self.font_size + self.padding.y
+	}
+
+	fn shown(&self) -> Range<usize> {
+		self.first_shown..(self.first_shown + MAX_SHOWN_COMPLETIONS).min(self.candidates.len())
+	}
+}
+
+/// The popup listing the candidates of a [`Completer`], pushed as an overlay below the input box.
+struct CompletionPopup<S: Signal, A: App<Signal = S>> {
+	state: Weak<RefCell<CompletionState>>,
+	session: u64,
+	seen_version: u64,
+	/// keeps the popup hit-testable after the input box is dropped, so it can still remove itself.
+	drawn_size: Vec2,
+	last_hover: Option<Vec2>,
+	_marker: PhantomData<(S, A)>,
+}
+
+impl<S: Signal, A: App<Signal = S>> CompletionPopup<S, A> {
+	/// Get the index of the candidate at the position relative to the popup.
+	fn candidate_at(state: &CompletionState, pos: Vec2) -> Option<usize> {
+		let row = (pos.y - state.padding.y / 2.0) / state.row_height();
+		if row < 0.0 {
+			return None;
+		}
+		let index = state.first_shown + row as usize;
+		state.shown().contains(&index).then_some(index)
+	}
+}
+
+impl<S: Signal, A: App<Signal = S>> Widget for CompletionPopup<S, A> {
+	type Signal = S;
+	type Application = A;
+
+	fn size(&self, _: LayoutId, _: &Painter, _: &Layout<Self::Signal, A>) -> Vec2 {
+		match self.state.upgrade() {
+			Some(state) => {
+				let state = state.borrow();
+				Vec2::new(state.width, state.shown().len() as f32 * state.row_height() + state.padding.y)
+			},
+			None => self.drawn_size,
+		}
+	}
+
+	fn draw(&mut self, painter: &mut Painter, size: Vec2) {
+		self.drawn_size = size;
+		let Some(state) = self.state.upgrade() else {
+			return;
+		};
+		let state = state.borrow();
+		if state.session != Some(self.session) {
+			return;
+		}
+		let stroke = 1.0;
+		let roundings = Vec4::same(DEFAULT_ROUNDING / 2.0);
+		painter.set_fill_mode(INPUT_BACKGROUND_COLOR);
+		painter.draw_rect(Rect::from_size(size), roundings);
+		painter.set_fill_mode(INPUT_BORDER_COLOR);
+		painter.draw_stroked_rect(Rect::from_size(size).shrink(Vec2::same(stroke / 2.0)), roundings, stroke);
+
+		let font = painter.resolve_font(state.font_role, state.font);
+		let row_height = state.row_height();
+		for (row, index) in state.shown().enumerate() {
+			let top = state.padding.y / 2.0 + row as f32 * row_height;
+			if index == state.selected {
+				painter.set_fill_mode(SELECTED_TEXT_COLOR);
+				painter.draw_rect(Rect::new(stroke, top, size.x - stroke * 2.0, row_height), Vec4::same(state.font_size / 8.0));
+			}
+			painter.set_fill_mode(SECONDARY_TEXT_COLOR);
+			painter.draw_text(Vec2::new(state.padding.x, top + state.padding.y / 2.0), font, state.font_size, &state.candidates[index]);
+		}
+	}
+
+	fn handle_event(&mut self, _: &mut A, input_state: &mut InputState<Self::Signal>, id: LayoutId, area: Rect, pos: Vec2) -> bool {
+		let Some(state) = self.state.upgrade() else {
+			input_state.remove_overlay(id);
+			return false;
+		};
+		let mut state = state.borrow_mut();
+		if state.session != Some(self.session) {
+			input_state.remove_overlay(id);
+			return false;
+		}
+
+		// only follow the pointer when it moves, so it does not fight with the arrow keys.
+		let hover = input_state.touch_positions().into_iter().find(|touch| area.contains(*touch));
+		if hover.is_some() && hover != self.last_hover {
+			if let Some(index) = hover.and_then(|touch| Self::candidate_at(&state, touch - pos)) {
+				if index != state.selected {
+					state.select(index);
+				}
+			}
+		}
+		self.last_hover = hover;
+
+		if let Some(touch) = input_state.get_touch_pressed_on(area).first() {
+			state.grabbed = true;
+			state.pressed = input_state.get_touch_pos(*touch).and_then(|touch| Self::candidate_at(&state, touch - pos));
+		}
+
+		let changed = state.version != self.seen_version;
+		self.seen_version = state.version;
+		changed
+	}
+
+	fn event_handle_strategy(&self) -> EventHandleStrategy {
+		// handled before the input box, which accepts the pressed candidate in the same frame.
+		EventHandleStrategy::AlwaysPrimary
+	}
+}
 
 /// A simple input validator for daliy use.
 #[derive(Clone, Debug, Default)]
@@ -638,6 +1454,10 @@ pub enum NumerValidation {
 
 impl Validator for SimpleValidator {
 	fn validate(&self, newly_input: &str, current_text: &str, poniter: Pointer) -> ValidatorResult {
+		self.validate_localized(newly_input, current_text, poniter, &DefaultLocalizer)
+	}
+
+	fn validate_localized(&self, newly_input: &str, current_text: &str, poniter: Pointer, localizer: &dyn Localizer) -> ValidatorResult {
 		if self.banned {
 			return ValidatorResult::Banned;
 		}
@@ -645,7 +1465,7 @@ impl Validator for SimpleValidator {
 		if let Some(limit) = self.limit {
 			if current_text.chars().count() + newly_input.chars().count() > limit {
 				return ValidatorResult::Invalid {
-					message: Some("Too long".to_string()),
+					message: Some(localizer.message(BuiltinMessage::TooLong { limit })),
 					allow_input: false,
 				};
 			}
@@ -658,16 +1478,16 @@ impl Validator for SimpleValidator {
 		if let NumerValidation::Integer = self.number_validation {
 			if newly_input.chars().any(|c| !c.is_numeric()) {
 				return ValidatorResult::Invalid {
-					message: Some("Only digits allowed".to_string()),
+					message: Some(localizer.message(BuiltinMessage::OnlyDigits)),
 					allow_input: true,
 				};
 			}
 		}else if let NumerValidation::Float = self.number_validation {
 			let mut final_str = current_text.to_string();
 			final_str.insert_str(poniter.current_index(current_text), newly_input);
-			if let Err(e) = final_str.parse::<f32>() {
+			if localizer.parse_number(&final_str).is_none() {
 				return ValidatorResult::Invalid {
-					message: Some(format!("Invalid number: {}", e)),
+					message: Some(localizer.message(BuiltinMessage::InvalidNumber { input: &final_str })),
 					allow_input: true,
 				};
 			}
@@ -681,15 +1501,147 @@ impl Validator for SimpleValidator {
 	}
 }
 
+/// A validator for numbers within a range.
+/// 
+/// Numbers out of the range can still be typed, so you can type `15` when the minimum is `10`,
+/// but the input box shows an error until the number is in the range.
+#[derive(Clone, Debug, Default)]
+pub struct RangeValidator {
+	/// The minimum allowed value.
+	pub min: Option<f32>,
+	/// The maximum allowed value.
+	pub max: Option<f32>,
+	/// Whether only integers are allowed.
+	pub integer: bool,
+	/// Whether to clamp the number into the range.
+	/// 
+	/// The number is clamped when the input box is submitted, 
+	/// or right away when it can not get back into the range by typing more digits, 
+	/// such as `150` with a maximum of `100`.
+	pub clamp: bool,
+	/// The decimal places used when writing a clamped number back.
+	pub decimal_places: usize,
+}
+
+impl RangeValidator {
+	/// Create a new [`RangeValidator`] with the given bounds.
+	pub fn new(min: Option<f32>, max: Option<f32>) -> Self {
+		Self {
+			min,
+			max,
+			decimal_places: 2,
+			..Default::default()
+		}
+	}
+
+	/// Only allow integers.
+	pub fn integer(self) -> Self {
+		Self {
+			integer: true,
+			..self
+		}
+	}
+
+	/// Set whether to clamp the number into the range.
+	pub fn clamp(self, clamp: bool) -> Self {
+		Self {
+			clamp,
+			..self
+		}
+	}
+
+	/// Set the decimal places used when writing a clamped number back.
+	pub fn decimal_places(self, decimal_places: usize) -> Self {
+		Self {
+			decimal_places,
+			..self
+		}
+	}
+
+	fn is_in_range(&self, value: f32) -> bool {
+		self.min.is_none_or(|min| value >= min) && self.max.is_none_or(|max| value <= max)
+	}
+}
+
+impl Validator for RangeValidator {
+	fn validate(&self, newly_input: &str, current_text: &str, poniter: Pointer) -> ValidatorResult {
+		self.validate_localized(newly_input, current_text, poniter, &DefaultLocalizer)
+	}
+
+	fn validate_localized(&self, newly_input: &str, current_text: &str, poniter: Pointer, localizer: &dyn Localizer) -> ValidatorResult {
+		if newly_input.contains('\n') {
+			return ValidatorResult::FinishType;
+		}
+
+		let mut final_str = current_text.to_string();
+		final_str.insert_str(poniter.current_index(current_text), newly_input);
+		if self.integer {
+			let digits = final_str.strip_prefix('-').unwrap_or(&final_str);
+			if digits.chars().any(|c| !c.is_ascii_digit()) {
+				return ValidatorResult::Invalid {
+					message: Some(localizer.message(BuiltinMessage::OnlyDigits)),
+					allow_input: false,
+				};
+			}
+		// allows unfinished numbers such as `-` or `1.`
+		}else if localizer.parse_number(&final_str).is_none() && localizer.parse_number(&format!("{}0", final_str)).is_none() {
+			return ValidatorResult::Invalid {
+				message: Some(localizer.message(BuiltinMessage::InvalidNumber { input: &final_str })),
+				allow_input: false,
+			};
+		}
+
+		ValidatorResult::Valid
+	}
+
+	fn validate_when_change(&self) -> bool {
+		true
+	}
+
+	fn check_text(&self, text: &str, localizer: &dyn Localizer) -> Option<String> {
+		if text.is_empty() {
+			return None;
+		}
+		match localizer.parse_number(text) {
+			Some(value) if self.is_in_range(value) => None,
+			Some(_) => Some(localizer.message(BuiltinMessage::OutOfRange { min: self.min, max: self.max })),
+			None => Some(localizer.message(BuiltinMessage::InvalidNumber { input: text })),
+		}
+	}
+
+	fn correct_text(&self, text: &mut String, submitting: bool, localizer: &dyn Localizer) -> bool {
+		if !self.clamp {
+			return false;
+		}
+		let Some(value) = localizer.parse_number(text) else {
+			return false;
+		};
+		if self.is_in_range(value) {
+			return false;
+		}
+		// more digits only take the number further from zero.
+		let unreachable = self.max.is_some_and(|max| value > max && max >= 0.0) || self.min.is_some_and(|min| value < min && min <= 0.0);
+		if !submitting && !unreachable {
+			return false;
+		}
+		let value = value.clamp(self.min.unwrap_or(f32::MIN), self.max.unwrap_or(f32::MAX));
+		*text = localizer.format_number(value, if self.integer { 0 } else { self.decimal_places });
+		true
+	}
+}
+
 impl<S: Signal, A: App<Signal = S>> Widget for InputBox<S, A> {
 	type Signal = S;
 	type Application = A;
 
 	fn size(&self, _: LayoutId, _: &Painter, _: &Layout<Self::Signal, A>) -> Vec2 {
-		self.inner.size + self.inner.padding * 2.0
+		self.inner.size + self.inner.padding * 2.0 + Vec2::y(self.error_label_height())
 	}
 
 	fn draw(&mut self, painter: &mut Painter, size: Vec2) {
+		let label_height = self.error_label_height();
+		let label_pos = Vec2::new(self.inner.padding.x, size.y - label_height + self.inner.padding.y / 2.0);
+		let size = size - Vec2::y(label_height);
 		let (text, mut text_color) = if self.inner.text.is_empty() {
 			(self.inner.placeholder.clone(), self.inner.placeholder_color.clone())
 		}else if self.inner.password {
@@ -705,32 +1657,97 @@ impl<S: Signal, A: App<Signal = S>> Widget for InputBox<S, A> {
 		painter.draw_rect(Rect::from_size(size), self.inner.roundings);
 		painter.set_fill_mode(self.inner.border_color.value() + self.hover_factor.value() * BRIGHT_FACTOR * Color::WHITE);
 		painter.draw_stroked_rect(Rect::from_size(size).shrink(Vec2::same(stroke / 2.0)), self.inner.roundings, stroke);
+
+		self.resolve_hit(&text, painter);
 		
-		let pointer_pos = self.inner.pointer.caculate_pointer_pos(&text, self.inner.font_size, self.inner.font, painter);
+		let pointer_pos = self.inner.pointer.caculate_pointer_pos(&text, self.inner.font_size, painter.resolve_font(self.inner.font_role, self.inner.font), painter);
 		
-		let text_pos = pointer_pos.pos() + self.inner.padding;
-		let text_pos = if Rect::from_size(size - Vec2::same(self.inner.font_size)).contains(text_pos) {
-			Vec2::ZERO
-		}else {
-			- (text_pos - size + Vec2::same(self.inner.font_size)).max(Vec2::ZERO)
-		} + self.inner.padding;
+		let mut view = (size - self.inner.padding * 2.0).max(Vec2::ZERO);
+		let error_font_size = self.inner.font_size * 0.75;
+		let error_size = self.inner.error.as_ref().and_then(|error| painter.text_size(painter.resolve_font(self.inner.font_role, self.inner.font), error_font_size, error));
+		if let (false, Some(error_size)) = (self.inner.error_label, error_size) {
+			view.x = (view.x - error_size.x - self.inner.padding.x).max(0.0);
+		}
+		let caret_size = Vec2::new(self.inner.caret_width, self.inner.font_size);
+		if self.is_typing {
+			self.follow_pointer(Rect::from_lt_size(pointer_pos.pos(), caret_size), view);
+		}
+		let content = painter.text_size_pointer(painter.resolve_font(self.inner.font_role, self.inner.font), self.inner.font_size, &text).unwrap_or_default() + Vec2::x(caret_size.x);
+		self.inner.scroll_position = self.inner.scroll_position.min((content - view).max(Vec2::ZERO)).max(Vec2::ZERO);
+
+		let text_pos = self.inner.padding - self.inner.scroll_position;
+		self.text_offset = text_pos;
+		let clip_rect = painter.clip_rect();
+		painter.set_clip_rect(clip_rect & Rect::from_lt_size(painter.releative_to() + self.inner.padding, view));
 		let text_color = if self.is_typing {
 			text_color
 		}else {
 			text_color.brighter(self.hover_factor.value() * BRIGHT_FACTOR);
 			text_color
 		};
-		painter.set_fill_mode(text_color);
-		painter.draw_text(text_pos, self.inner.font, self.inner.font_size, &text);
+		let caret_color = self.inner.caret_color.clone().unwrap_or_else(|| text_color.clone());
+		match self.inner.highlighter.as_ref().filter(|_| !self.inner.text.is_empty() && !self.inner.password) {
+			Some(highlighter) => {
+				let font = painter.resolve_font(self.inner.font_role, self.inner.font);
+				let font_size = self.inner.font_size;
+				let line_height = painter.line_height(font, font_size).unwrap_or_default();
+				let hover = if self.is_typing { 0.0 } else { self.hover_factor.value() * BRIGHT_FACTOR };
+				let ranges = highlighter.highlight(&text, self.inner.pointer).into_iter().map(|(range, mut fill_mode)| {
+					fill_mode.brighter(hover);
+					(range, fill_mode)
+				}).collect();
+				// the text color is already brightened.
+				let segments = highlight_segments(text.chars().count(), ranges, &text_color);
+				// the segments are drawn line by line, so the lines after a line break start at the left.
+				let mut line_start = 0;
+				for (line_index, line) in text.split('\n').enumerate() {
+					let line_end = line_start + line.chars().count();
+					for (range, fill_mode) in &segments {
+						let start = range.start.max(line_start) - line_start;
+						let end = range.end.min(line_end).saturating_sub(line_start);
+						if start >= end {
+							continue;
+						}
+						let x = painter.text_size_pointer(font, font_size, char_slice(line, 0, start)).map_or(0.0, |size| size.x);
+						painter.set_fill_mode(fill_mode.clone());
+						painter.draw_text(text_pos + Vec2::new(x, line_index as f32 * line_height), font, font_size, char_slice(line, start, end));
+					}
+					line_start = line_end + 1;
+				}
+			},
+			None => {
+				painter.set_fill_mode(text_color);
+				painter.draw_text(text_pos, painter.resolve_font(self.inner.font_role, self.inner.font), self.inner.font_size, &text);
+			},
+		}
 		if self.is_typing {
-			// let line_height = painter.line_height(self.font, self.font_size).unwrap_or_default();
-			painter.draw_rect(
-				Rect::from_lt_size(
-					pointer_pos.pos() + Vec2::new(text_pos.x, self.inner.padding.y), 
-					Vec2::new(2.0, self.inner.font_size)
-				), 
-				Vec4::ZERO
-			);
+			// the char under the caret, covered by the block and the underscore.
+			let current = if self.inner.pointer.is_start_current { self.inner.pointer.start } else { self.inner.pointer.end };
+			let current_char = text.chars().nth(current).filter(|c| *c != '\n').map(String::from);
+			let font = painter.resolve_font(self.inner.font_role, self.inner.font);
+			let char_width = current_char.as_ref()
+				.and_then(|c| painter.text_size_pointer(font, self.inner.font_size, c))
+				.map(|size| size.x)
+				.filter(|width| *width > 0.0)
+				.unwrap_or(self.inner.font_size / 2.0);
+			let caret_pos = pointer_pos.pos() + text_pos;
+			let caret = match self.inner.caret_style {
+				CaretStyle::Bar => Rect::from_lt_size(caret_pos, caret_size),
+				CaretStyle::Block => Rect::from_lt_size(caret_pos, Vec2::new(char_width, self.inner.font_size)),
+				CaretStyle::Underscore => Rect::from_lt_size(
+					caret_pos + Vec2::y(self.inner.font_size - self.inner.caret_width), 
+					Vec2::new(char_width, self.inner.caret_width)
+				),
+			};
+			self.caret_area = Some(caret);
+			if self.caret_shown {
+				painter.set_fill_mode(caret_color);
+				painter.draw_rect(caret, Vec4::ZERO);
+				if let (CaretStyle::Block, Some(current_char)) = (self.inner.caret_style, &current_char) {
+					painter.set_fill_mode(self.inner.background_color.clone());
+					painter.draw_text(caret_pos, font, self.inner.font_size, current_char);
+				}
+			}
 			if let PointerPos::Selected { selection_rect,.. } = pointer_pos {
 				painter.set_fill_mode(self.inner.selected_color.clone());
 				for rect in selection_rect {
@@ -738,10 +1755,28 @@ impl<S: Signal, A: App<Signal = S>> Widget for InputBox<S, A> {
 				}
 			}
 		}
+		painter.set_clip_rect(clip_rect);
+
+		if let (Some(error), Some(error_size)) = (&self.inner.error, error_size) {
+			painter.set_fill_mode(ERROR_COLOR);
+			let pos = if self.inner.error_label {
+				label_pos
+			}else {
+				Vec2::new(size.x - self.inner.padding.x - error_size.x, (size.y - error_size.y) / 2.0)
+			};
+			painter.draw_text(
+				pos,
+				painter.resolve_font(self.inner.font_role, self.inner.font),
+				error_font_size,
+				error,
+			);
+		}
 	}
 
-	fn handle_event(&mut self, app: &mut A, input_state: &mut InputState<Self::Signal>, id: LayoutId, area: Rect, _: Vec2) -> bool {
-		let res = self.signals.generate_signals(app, &mut self.inner, input_state, id, area, true, false);
+	fn handle_event(&mut self, app: &mut A, input_state: &mut InputState<Self::Signal>, id: LayoutId, area: Rect, widget_pos: Vec2) -> bool {
+		let was_typing = self.is_typing;
+		let before = (self.inner.text.clone(), self.inner.pointer, self.inner.error.clone(), self.inner.scroll_position);
+		self.signals.generate_signals(app, &mut self.inner, input_state, id, area, true, false);
 
 		if input_state.is_touch_in(area) {
 			self.hover_factor.set(1.0);
@@ -749,30 +1784,93 @@ impl<S: Signal, A: App<Signal = S>> Widget for InputBox<S, A> {
 			self.hover_factor.set(0.0);
 		}
 
-		if input_state.is_any_touch_released() && !input_state.is_touch_in(area) && self.is_typing {
+		// a drag selection released outside the box should not end typing.
+		let selection_released = self.selecting_by.is_some_and(|touch| input_state.is_touch_released(touch));
+		// neither should a click on the completion popup.
+		let popup_released = input_state.is_any_touch_released() && std::mem::take(&mut self.completions.borrow_mut().grabbed);
+		// nor a click on the context menu, which is closed by the time the input box sees the release.
+		let menu_released = self.menu.as_ref().is_some_and(|menu| menu.is_open()) || self.menu_command.get().is_some();
+		if input_state.is_any_touch_released() && !input_state.is_touch_in(area) && self.is_typing && !selection_released && !popup_released && !menu_released {
 			self.submit(input_state, id);
 		}
 
-		if res.is_clicked {
-			self.is_typing = true;
-			self.inner.border_color.set(PRIMARY_COLOR + BRIGHT_FACTOR * Color::WHITE);
+		let right_pressed = input_state.is_touch_pressed(MOUSE_RIGHT_ID);
+		if self.inner.context_menu && right_pressed && !self.menu_pressed {
+			if let Some(pos) = input_state.get_touch_pos(MOUSE_RIGHT_ID).filter(|pos| input_state.hit_test(area, *pos)) {
+				self.open_context_menu(input_state, id, pos);
+			}
+		}
+		self.menu_pressed = right_pressed;
+
+		match self.signals.dragging_by() {
+			// the right button opens the menu, it should not move the pointer and lose the selection.
+			Some(MOUSE_RIGHT_ID) => {},
+			Some(touch) if self.selecting_by != Some(touch) => {
+				self.selecting_by = Some(touch);
+				let current = input_state.program_running_time();
+				self.click_count = if self.last_click_time.is_some_and(|last| current - last < DOUBLE_CLICK_THRESHOLD) {
+					self.click_count % 3 + 1
+				}else {
+					1
+				};
+				self.last_click_time = Some(current);
+				if let Some(pos) = input_state.get_touch_pos(touch) {
+					let pos = pos - area.lt() - self.text_offset;
+					self.pending_hit = Some(match self.click_count {
+						1 => PendingHit::Place(pos, self.is_typing && input_state.modifiers().shift),
+						2 => PendingHit::Word(pos),
+						_ => PendingHit::Line(pos),
+					});
+				}
+				self.is_typing = true;
+				self.update_border_color();
+			},
+			Some(touch) => {
+				if self.click_count == 1 && input_state.drag_delta(touch) != Vec2::ZERO {
+					if let Some(pos) = input_state.get_touch_pos(touch) {
+						self.pending_hit = Some(PendingHit::Place(pos - area.lt() - self.text_offset, true));
+					}
+				}
+			},
+			None => self.selecting_by = None,
 		}
 
 		if self.is_typing {
 			let modifiers = input_state.modifiers();
-				
-			let input = input_state.get_input_string();
-			match self.inner.pointer.insert_text(&mut self.inner.text, input, &self.inner.validator) {
-				ValidatorResult::Valid => {
-					if let Some(on_change) = &self.on_change {
-						let signal = on_change(&mut self.inner);
-						input_state.send_signal_from(id, signal);
+			let previous_text = self.inner.text.clone();
+
+			let pressed = self.completions.borrow_mut().pressed.take();
+			let mut accepted = pressed.is_some_and(|index| self.accept_completion(index));
+			let mut input = input_state.get_input_string();
+			if self.is_completing() {
+				if input_state.is_key_pressed(Key::ArrawDown) {
+					self.completions.borrow_mut().select_by(1);
+				}
+				if input_state.is_key_pressed(Key::ArrawUp) {
+					self.completions.borrow_mut().select_by(-1);
+				}
+				if input_state.is_key_pressed(Key::Escape) {
+					self.close_completions();
+				}else if input_state.is_key_pressed(Key::Tab) || input_state.is_key_pressed(Key::Enter) || input_state.is_key_pressed(Key::KeypadEnter) {
+					let selected = self.completions.borrow().selected;
+					accepted |= self.accept_completion(selected);
+					// the enter accepts the candidate instead of breaking the line or finishing the input.
+					if let ImeString::ImeOff(text) = &mut input {
+						text.retain(|c| c != '\n');
+					}
+				}
+			}
+			match self.inner.pointer.insert_text_localized(&mut self.inner.text, input, &self.inner.validator, input_state.localizer()) {
+				ValidatorResult::Valid => {},
+				ValidatorResult::Invalid { message, .. } => {
+					if message.is_some() {
+						self.set_error(message, input_state, id);
+						self.update_border_color();
 					}
 				},
-				ValidatorResult::Invalid { .. } => {},
 				ValidatorResult::Banned => {
 					self.is_typing = false;
-					self.inner.border_color.set(INPUT_BORDER_COLOR);
+					self.update_border_color();
 				},
 				ValidatorResult::FinishType => {
 					self.submit(input_state, id);
@@ -811,37 +1909,151 @@ impl<S: Signal, A: App<Signal = S>> Widget for InputBox<S, A> {
 				self.inner.pointer.move_by(&self.inner.text, PointerAmount::Line(1), modifiers.shift)
 			}
 
-			if input_state.is_key_pressed(Key::KeyA) && modifiers.ctrl {
+			if input_state.is_key_pressed(Key::KeyA) && modifiers.primary() {
 				self.inner.pointer.select_all(&self.inner.text)
 			}
 			
-			if input_state.is_key_pressed(Key::Backspace) || input_state.is_key_pressed(Key::Delete) {
-				// println!("delete");
-				self.inner.pointer.delete(&mut self.inner.text);
+			let delete_word = modifiers.ctrl || modifiers.alt;
+			let mut cut = false;
+
+			if input_state.is_key_pressed(Key::Backspace) {
+				if delete_word {
+					self.inner.pointer.delete_word(&mut self.inner.text);
+				}else {
+					self.inner.pointer.delete(&mut self.inner.text);
+				}
 			}
 
-			if modifiers.ctrl && input_state.is_key_pressed(Key::KeyC) {
+			if input_state.is_key_pressed(Key::Delete) {
+				if modifiers.shift && self.inner.pointer.has_selected_text() {
+					cut = true;
+				}else if delete_word {
+					self.inner.pointer.delete_word_forward(&mut self.inner.text);
+				}else {
+					self.inner.pointer.delete_forward(&mut self.inner.text);
+				}
+			}
+
+			let insert_pressed = input_state.is_key_pressed(Key::Insert);
+			let command = self.menu_command.take();
+
+			if command == Some(EditCommand::SelectAll) {
+				self.inner.pointer.select_all(&self.inner.text)
+			}
+
+			if command == Some(EditCommand::Copy) || (modifiers.primary() && input_state.is_key_pressed(Key::KeyC)) || (modifiers.ctrl && insert_pressed) {
 				let text = self.inner.pointer.get_selected_text(&self.inner.text);
 				input_state.copy_text(text);
 			}
 
-			if modifiers.ctrl && input_state.is_key_pressed(Key::KeyX) {
+			if cut || command == Some(EditCommand::Cut) || (modifiers.primary() && input_state.is_key_pressed(Key::KeyX)) {
 				let text = self.inner.pointer.get_selected_text(&self.inner.text);
 				input_state.copy_text(text);
 				self.inner.pointer.delete_selected_text(&mut self.inner.text);
 			}
 
-			if modifiers.ctrl && input_state.is_key_pressed(Key::KeyV) {
+			if command == Some(EditCommand::Paste) || (modifiers.primary() && input_state.is_key_pressed(Key::KeyV)) || (modifiers.shift && insert_pressed) {
 				input_state.request_paste_text();
 			}
 
-			if input_state.is_key_pressed(Key::Escape) 
-			|| input_state.is_key_pressed(Key::Tab) {
+			if self.inner.text != previous_text {
+				if self.is_typing {
+					self.check_text(false, input_state, id);
+					self.update_border_color();
+				}
+				self.text_changed(input_state, id);
+				if self.is_typing && !accepted {
+					self.update_completions(input_state, id, area);
+				}
+			}
+
+			if input_state.is_key_pressed(Key::Escape) {
+				self.submit(input_state, id);
+			}
+		}
+
+		// the focus moved by the keyboard starts typing, moving it away finishes.
+		let focused = input_state.is_focused(id);
+		if focused != self.has_focus {
+			if focused && input_state.is_focus_visible() && !self.is_typing {
+				self.is_typing = true;
+				self.inner.pointer.select_all(&self.inner.text);
+				self.update_border_color();
+			}else if !focused && self.is_typing {
 				self.submit(input_state, id);
 			}
+		}else if focused && !was_typing && !self.is_typing && (input_state.is_key_pressed(Key::Enter) || input_state.is_key_pressed(Key::KeypadEnter)) {
+			self.is_typing = true;
+			self.update_border_color();
+		}else if self.is_typing && !focused {
+			input_state.request_focus(id);
 		}
+		self.has_focus = input_state.is_focused(id);
 
-		self.is_typing || self.inner.border_color.is_animating() || self.hover_factor.is_animating()
+		if let Some(changed_at) = self.pending_change {
+			if !self.is_typing || input_state.program_running_time() - changed_at >= self.change_debounce {
+				self.send_change(input_state, id);
+			}
+		}
+
+		if !self.is_typing && self.is_completing() {
+			self.close_completions();
+		}
+
+		if !self.is_typing {
+			if let Some(menu) = self.menu.take() {
+				menu.close();
+			}
+		}
+
+		if self.is_typing != was_typing {
+			if self.is_typing {
+				input_state.show_keyboard(id);
+				// only scroll away from a keyboard actually covering the window.
+				if input_state.keyboard_inset() > 0.0 {
+					input_state.request_visible(id, area);
+				}
+			}else {
+				input_state.hide_keyboard(id);
+			}
+		}
+
+		let changed = before != (self.inner.text.clone(), self.inner.pointer, self.inner.error.clone(), self.inner.scroll_position)
+			|| self.is_typing != was_typing
+			|| self.pending_hit.is_some()
+			|| self.selecting_by.is_some();
+
+		// the caret stays shown while it moves, and only its own area is redrawn when it blinks.
+		let now = input_state.program_running_time();
+		if changed {
+			self.caret_moved_at = now;
+		}
+		let caret_shown = match self.inner.caret_blink.filter(|interval| self.is_typing && interval.is_positive()) {
+			Some(interval) => {
+				let elapsed = now - self.caret_moved_at;
+				let phase = (elapsed / interval).floor();
+				input_state.request_frame_after(interval * (phase + 1.0) - elapsed);
+				phase as i64 % 2 == 0
+			},
+			None => true,
+		};
+		if caret_shown != self.caret_shown {
+			self.caret_shown = caret_shown;
+			if let (false, Some(caret)) = (changed, self.caret_area) {
+				input_state.request_redraw_area(caret.move_by(widget_pos) & area);
+			}
+		}
+
+		changed || self.inner.border_color.is_animating() || self.hover_factor.is_animating()
+	}
+
+	fn scroll_by(&mut self, delta: Vec2, _: ScrollSource) -> Vec2 {
+		// the drag is selecting text, dont scroll the parents.
+		if self.selecting_by.is_some() {
+			Vec2::ZERO
+		}else {
+			delta
+		}
 	}
 
 	fn event_handle_strategy(&self) -> super::EventHandleStrategy {
@@ -851,35 +2063,177 @@ impl<S: Signal, A: App<Signal = S>> Widget for InputBox<S, A> {
 			EventHandleStrategy::OnHover
 		}
 	}
+
+	fn focusable(&self) -> bool {
+		true
+	}
+
+	fn save_state(&self) -> Option<Box<dyn Any>> {
+		Some(Box::new((self.hover_factor.clone(), self.inner.border_color.clone())))
+	}
+
+	fn restore_state(&mut self, state: Box<dyn Any>) {
+		if let Ok(state) = state.downcast::<(Animatedf32, AnimatedColor)>() {
+			let (hover_factor, border_color) = *state;
+			self.hover_factor = hover_factor;
+			self.inner.border_color.continue_from(&border_color);
+		}
+	}
 }
 
-#[inline]
-fn convert_range(s: &str, from: usize, to: usize) -> std::ops::Range<usize>  {
-	if from == 0 && to == 0 {
-		return 0..0;
+/// Get the char index of the start of every grapheme cluster, and the length of the text in chars at last.
+pub(crate) fn grapheme_boundaries(text: &str) -> Vec<usize> {
+	let mut out = vec![0];
+	let mut index = 0;
+	for grapheme in text.graphemes(true) {
+		index += grapheme.chars().count();
+		out.push(index);
 	}
-	assert!(from <= to);
+	out
+}
+
+/// Move the char index by the given amount of grapheme clusters.
+pub(crate) fn step_graphemes(text: &str, index: usize, amount: isize) -> usize {
+	let boundaries = grapheme_boundaries(text);
+	// an index inside a grapheme cluster is treated as the start of the cluster.
+	let current = boundaries.partition_point(|boundary| *boundary <= index).saturating_sub(1) as isize;
+	let target = (current + amount).clamp(0, boundaries.len() as isize - 1);
+	boundaries[target as usize]
+}
 
-	let inner = s.char_indices().nth(from).map(|(start_pos, _)| {
-		let len = s.chars().count(); 
-		assert!(to <= len);
+/// Move the char index by the given amount of words.
+fn step_words(text: &str, mut index: usize, amount: isize) -> usize {
+	let mut words = vec!();
+	let mut start = 0;
+	for segment in text.split_word_bounds() {
+		let len = segment.chars().count();
+		if segment.chars().any(|c| c.is_alphanumeric()) {
+			words.push((start, start + len));
+		}
+		start += len;
+	}
+
+	for _ in 0..amount.unsigned_abs() {
+		index = if amount > 0 {
+			words.iter().find(|(_, end)| *end > index).map(|(_, end)| *end).unwrap_or(start)
+		}else {
+			words.iter().rev().find(|(start, _)| *start < index).map(|(start, _)| *start).unwrap_or(0)
+		};
+	}
+	index
+}
 
-		let end_pos = s[start_pos..]
-			.char_indices()
-			.nth(to - from)
-			.map(|(end_pos, _)| end_pos).unwrap_or(s.len() - start_pos);
+/// Get the text between the char indices.
+pub(crate) fn char_slice(text: &str, from: usize, to: usize) -> &str {
+	let byte_index = |index: usize| text.char_indices().nth(index).map_or(text.len(), |(byte, _)| byte);
+	&text[byte_index(from)..byte_index(to.max(from))]
+}
 
-		(start_pos, end_pos + start_pos)
-	}).unwrap();
+/// The errors of converting char indices into byte indices.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub(crate) enum TextIndexError {
+	/// The range starts after its end.
+	#[error("The range {from}..{to} starts after its end")]
+	Reversed {
+		from: usize,
+		to: usize,
+	},
+	/// The char index is after the end of the text.
+	#[error("The char index {index} is out of the text of {len} chars")]
+	OutOfBounds {
+		index: usize,
+		len: usize,
+	},
+}
 
-	inner.0..inner.1
+/// Convert the char range into a byte range, an index out of the text is clamped to its end.
+#[inline]
+pub(crate) fn convert_range(s: &str, from: usize, to: usize) -> std::ops::Range<usize>  {
+	try_convert_range(s, from, to).unwrap_or_else(|_| {
+		let end = convert_index(s, to);
+		convert_index(s, from).min(end)..end
+	})
 }
 
+/// Convert the char index into a byte index, an index out of the text is clamped to its end.
 #[inline]
 fn convert_index(s: &str, index: usize) -> usize {
-	if index >= s.chars().count() {
-		s.len()
-	}else {
-		s.char_indices().nth(index).map(|(start_pos, _)| start_pos).expect("Invalid index")
+	try_convert_index(s, index).unwrap_or(s.len())
+}
+
+/// Convert the char range into a byte range, the end of the text is a valid index.
+pub(crate) fn try_convert_range(s: &str, from: usize, to: usize) -> Result<std::ops::Range<usize>, TextIndexError> {
+	if from > to {
+		return Err(TextIndexError::Reversed { from, to });
+	}
+	let start = try_convert_index(s, from)?;
+	let end = start + try_convert_index(&s[start..], to - from).map_err(|_| TextIndexError::OutOfBounds { index: to, len: s.chars().count() })?;
+	Ok(start..end)
+}
+
+/// Convert the char index into a byte index, the end of the text is a valid index.
+pub(crate) fn try_convert_index(s: &str, index: usize) -> Result<usize, TextIndexError> {
+	s.char_indices()
+		.map(|(byte, _)| byte)
+		.chain(std::iter::once(s.len()))
+		.nth(index)
+		.ok_or_else(|| TextIndexError::OutOfBounds { index, len: s.chars().count() })
+}
+mod test {
+	#[test]
+	fn test_convert_range() {
+		use proptest::prelude::*;
+		use super::{convert_range, try_convert_range};
+
+		proptest!(|(text in "(\\PC|\n|\r\n|\u{200D}|\u{301}){0,24}", from in 0..32usize, to in 0..32usize)| {
+			let len = text.chars().count();
+			let range = convert_range(&text, from, to);
+			prop_assert!(text.get(range.clone()).is_some());
+			match try_convert_range(&text, from, to) {
+				Ok(exact) => {
+					prop_assert!(from <= to && to <= len);
+					prop_assert_eq!(exact.clone(), range);
+					prop_assert_eq!(text[exact].chars().count(), to - from);
+				},
+				Err(_) => prop_assert!(from > to || to > len),
+			}
+		});
 	}
-}
\ No newline at end of file
+
+	#[test]
+	fn test_pointer_operations() {
+		use proptest::prelude::*;
+		use super::{Pointer, PointerAmount};
+		use crate::prelude::ImeString;
+
+		let text = "(\\PC|\n|\r\n|\u{1F468}\u{200D}\u{1F469}){0,24}";
+		let ops = prop::collection::vec((0..14u8, -3..4isize, any::<bool>(), "(\\PC|\n){0,3}"), 0..24);
+		proptest!(|(mut text in text, ops in ops)| {
+			let mut pointer = Pointer::default();
+			for (op, amount, with_selection, insert) in ops {
+				match op {
+					0 => pointer.move_by(&text, PointerAmount::Char(amount), with_selection),
+					1 => pointer.move_by(&text, PointerAmount::Word(amount), with_selection),
+					2 => pointer.move_by(&text, PointerAmount::Line(amount), with_selection),
+					3 => pointer.move_to(&text, amount.unsigned_abs() * 5, with_selection),
+					4 => pointer.select_all(&text),
+					5 => pointer.select_word(&text),
+					6 => pointer.select_line(&text),
+					7 => pointer.delete(&mut text),
+					8 => pointer.delete_forward(&mut text),
+					9 => pointer.delete_word(&mut text),
+					10 => pointer.delete_word_forward(&mut text),
+					11 => { pointer.insert_text(&mut text, ImeString::ImeOff(insert), &None); },
+					12 => pointer.move_to_end(&text),
+					_ => pointer.move_to_start(),
+				}
+				let (start, end) = pointer.selection();
+				prop_assert!(start <= end && end <= text.chars().count());
+				prop_assert!(pointer.current_index(&text) <= text.len());
+				let _ = pointer.get_selected_text(&text);
+				let _ = pointer.get_selected_text_lines(&text);
+				let _ = pointer.get_selected_lines(&text);
+			}
+		});
+	}
+}
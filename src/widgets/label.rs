@@ -1,8 +1,8 @@
 //! A simple label widget for displaying text.
 
-use crate::{layout::{Layout, LayoutId}, prelude::{FillMode, FontId, InputState, Painter, Rect, Vec2}, App};
+use crate::{layout::{Layout, LayoutId}, prelude::{FillMode, FontId, FontRole, InputState, Painter, Rect, Vec2}, App};
 
-use super::{styles::{CONTENT_TEXT_SIZE, PRIMARY_TEXT_COLOR, SECONDARY_TEXT_COLOR, TITLE_TEXT_SIZE}, Signal, SignalGenerator, Widget};
+use super::{rich_text::{RichLayout, RichText}, styles::{CONTENT_TEXT_SIZE, PRIMARY_TEXT_COLOR, SECONDARY_TEXT_COLOR, TITLE_TEXT_SIZE}, Signal, SignalGenerator, Widget};
 
 /// A simple label widget for displaying text.
 pub struct Label<S: Signal, A: App<Signal = S>> {
@@ -10,7 +10,12 @@ pub struct Label<S: Signal, A: App<Signal = S>> {
 	pub inner: LabelInner,
 	/// The signals generated by the label.
 	pub signals: SignalGenerator<S, LabelInner, A>,
+	/// The signal sent when a link span of a rich label is clicked, given the link target.
+	#[allow(clippy::type_complexity)]
+	pub on_link: Option<Box<dyn Fn(&mut A, &mut LabelInner, &str) -> S>>,
 	inner_size: Vec2,
+	rich_layout: RichLayout,
+	pressed_link: Option<String>,
 }
 
 /// A struct determings the inner properties of the label.
@@ -22,6 +27,8 @@ pub struct LabelInner {
 	pub style: LabelStyle,
 	/// The font id of the text.
 	pub font: FontId,
+	/// The font role of the text, see [`FontRole`].
+	pub font_role: Option<FontRole>,
 	/// The size of the label.
 	/// 
 	/// Will automatically adjust to fit the text if not set.
@@ -32,6 +39,16 @@ pub struct LabelInner {
 	pub allow_break_in_word: bool,
 	/// Whether to automatically break the text to fit the size.
 	pub auto_break: bool,
+	/// Whether to wrap the words to the width of the label, see [`Label::wrap`].
+	/// 
+	/// Unlike [`Self::auto_break`], the text is kept as is.
+	pub wrap: bool,
+	/// How the lines too wide for the label are shown.
+	pub overflow: TextOverflow,
+	/// The rich text to display instead of [`Self::text`], see [`Label::rich`].
+	/// 
+	/// [`Self::text`] keeps the plain text of it.
+	pub rich: Option<RichText>,
 }
 
 impl Default for LabelInner {
@@ -40,9 +57,13 @@ impl Default for LabelInner {
 			text: String::new(),
 			style: LabelStyle::Content,
 			font: 0,
+			font_role: Some(FontRole::Body),
 			size: None,
 			allow_break_in_word: true,
 			auto_break: false,
+			wrap: false,
+			overflow: TextOverflow::Visible,
+			rich: None,
 		}
 	}
 }
@@ -61,12 +82,28 @@ pub enum LabelStyle {
 	},
 }
 
+/// How a line too wide for the label is shown.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum TextOverflow {
+	/// Draw the whole line, overflowing the label.
+	#[default] Visible,
+	/// Cut the line at the last character fitting in the label.
+	Clip,
+	/// Cut the line and end it with an ellipsis.
+	Ellipsis,
+	/// Keep the start and the end of the line with an ellipsis in between.
+	MiddleEllipsis,
+}
+
 impl<S: Signal, A: App<Signal = S>> Default for Label<S, A> {
 	fn default() -> Self {
 		Self {
 			inner: LabelInner::default(),
 			signals: SignalGenerator::default(),
+			on_link: None,
 			inner_size: Vec2::ZERO,
+			rich_layout: RichLayout::default(),
+			pressed_link: None,
 		}
 	}
 }
@@ -89,12 +126,37 @@ impl<S: Signal, A: App<Signal = S>> Label<S, A> {
 			inner: LabelInner {
 				text: text.into(),
 				style: LabelStyle::Title,
+				font_role: Some(FontRole::Heading),
 				..Default::default()
 			},
 			..Default::default()
 		}
 	}
 
+	/// Creates a new label showing the rich text.
+	/// 
+	/// Rich labels wrap their words to the width of the label when [`LabelInner::auto_break`] is set.
+	pub fn rich(rich: RichText) -> Self {
+		Self {
+			inner: LabelInner {
+				text: rich.plain_text(),
+				rich: Some(rich),
+				..Default::default()
+			},
+			..Default::default()
+		}
+	}
+
+	/// Creates a new label showing the markup, see [`RichText::parse`] for the syntax.
+	pub fn markup(markup: &str) -> Self {
+		Self::rich(RichText::parse(markup))
+	}
+
+	/// Sets the signal sent when a link span is clicked.
+	pub fn on_link(self, on_link: impl Fn(&mut A, &mut LabelInner, &str) -> S + 'static) -> Self {
+		Self { on_link: Some(Box::new(on_link)), ..self }
+	}
+
 	/// Sets whether to allow break in the middle of a word.
 	pub fn allow_break_in_word(self, allow_break_in_word: bool) -> Self {
 		Self { inner: LabelInner { allow_break_in_word, ..self.inner }, ..self }
@@ -105,9 +167,29 @@ impl<S: Signal, A: App<Signal = S>> Label<S, A> {
 		Self { inner: LabelInner { auto_break, ..self.inner }, ..self }
 	}
 
+	/// Sets whether to wrap the words to the width of the label.
+	/// 
+	/// Without a size set, the width is the space left in the parent after the label,
+	/// [`LabelInner::allow_break_in_word`] decides whether too long words are broken.
+	pub fn wrap(self, wrap: bool) -> Self {
+		Self { inner: LabelInner { wrap, ..self.inner }, ..self }
+	}
+
+	/// Sets how the lines too wide for the label are shown.
+	pub fn overflow(self, overflow: TextOverflow) -> Self {
+		Self { inner: LabelInner { overflow, ..self.inner }, ..self }
+	}
+
 	/// Sets the font of the label.
+	/// 
+	/// Clears the font role, so the font is used even if the theme registers one, see [`Self::font_role`].
 	pub fn font(self, font: FontId) -> Self {
-		Self { inner: LabelInner { font, ..self.inner }, ..self }
+		Self { inner: LabelInner { font, font_role: None, ..self.inner }, ..self }
+	}
+
+	/// Sets the font role of the text, `None` to always use the font id.
+	pub fn font_role(self, font_role: Option<FontRole>) -> Self {
+		Self { inner: LabelInner { font_role, ..self.inner }, ..self }
 	}
 
 	/// Sets the size of the label.
@@ -125,6 +207,89 @@ impl<S: Signal, A: App<Signal = S>> Label<S, A> {
 		Self { inner: LabelInner { text: text.into(), ..self.inner }, ..self }
 	}
 
+	fn font_size(&self) -> f32 {
+		match &self.inner.style {
+			LabelStyle::Title => TITLE_TEXT_SIZE,
+			LabelStyle::Content => CONTENT_TEXT_SIZE,
+			LabelStyle::Custom { font_size, .. } => *font_size,
+		}
+	}
+
+	fn fits_width(&self) -> bool {
+		self.inner.wrap || self.inner.overflow != TextOverflow::Visible
+	}
+
+	/// The width the text should fit in, from the size of the label or the area of its parent.
+	fn max_width(&self, id: LayoutId, painter: &Painter, layout: &Layout<S, A>) -> f32 {
+		if let Some(size) = self.inner.size {
+			return size.x;
+		}
+		let window = painter.window_size.x;
+		let Some(parent) = layout.get_parent_id(id) else { return window };
+		let Some(area) = layout.get_widget_area(parent) else { return window };
+		let padding = layout.get_widget_padding(parent).unwrap_or_default();
+		let left = layout.get_widget_pos(id).map_or(area.x + padding.x, |pos| pos.x);
+		(area.x + area.w - padding.x - left).clamp(0.0, window)
+	}
+
+	/// The text wrapped and truncated to fit the width.
+	fn fitted_text(&self, painter: &Painter, font: FontId, font_size: f32, max_width: f32) -> String {
+		let width = |text: &str| painter.text_size(font, font_size, text).unwrap_or_default().x;
+		let fits = |text: &str| width(text) <= max_width + 0.5;
+
+		let mut lines = vec!();
+		for line in self.inner.text.split('\n') {
+			if !self.inner.wrap {
+				lines.push(line.to_string());
+				continue;
+			}
+			let mut current = String::new();
+			for word in line.split_inclusive(char::is_whitespace) {
+				let pieces = if self.inner.allow_break_in_word && !fits(word.trim_end()) {
+					word.chars().map(|chr| chr.to_string()).collect::<Vec<_>>()
+				}else {
+					vec!(word.to_string())
+				};
+				for piece in pieces {
+					if !current.is_empty() && !fits(&format!("{}{}", current, piece.trim_end())) {
+						lines.push(std::mem::take(&mut current).trim_end().to_string());
+					}
+					current.push_str(&piece);
+				}
+			}
+			lines.push(current.trim_end().to_string());
+		}
+
+		lines.into_iter().map(|line| {
+			if self.inner.overflow == TextOverflow::Visible || fits(&line) {
+				return line;
+			}
+			let chars = line.chars().collect::<Vec<_>>();
+			let part = |range: std::ops::Range<usize>| chars[range].iter().collect::<String>();
+			match self.inner.overflow {
+				TextOverflow::Visible => line,
+				TextOverflow::Clip => {
+					let count = last_fitting(chars.len(), |count| fits(&part(0..count)));
+					part(0..count)
+				},
+				TextOverflow::Ellipsis => {
+					let count = last_fitting(chars.len(), |count| fits(&format!("{}…", part(0..count).trim_end())));
+					format!("{}…", part(0..count).trim_end())
+				},
+				TextOverflow::MiddleEllipsis => {
+					let joined = |head: usize, tail: usize| format!("{}…{}", part(0..head).trim_end(), part(chars.len() - tail..chars.len()).trim_start());
+					// keep the start and the end evenly, the start gets the extra char.
+					let kept = last_fitting(chars.len(), |kept| fits(&joined(kept.div_ceil(2), kept / 2)));
+					let (head, tail) = (kept.div_ceil(2), kept / 2);
+					// the next char of one end may be too wide while the other end still fits more.
+					let tail = tail + last_fitting(chars.len() - head - tail, |extra| fits(&joined(head, tail + extra)));
+					let head = head + last_fitting(chars.len() - head - tail, |extra| fits(&joined(head + extra, tail)));
+					joined(head, tail)
+				},
+			}
+		}).collect::<Vec<_>>().join("\n")
+	}
+
 	fn auto_break_func(&mut self, painter: &Painter) {
 		let size = if let Some(size) = self.inner.size {
 			size
@@ -156,7 +321,7 @@ impl<S: Signal, A: App<Signal = S>> Label<S, A> {
 			let mut new_text = String::new();
 			let mut current_width = 0.0;
 			for word in word {
-				let word_size = painter.text_size(self.inner.font, font_size, &word).unwrap_or_default();
+				let word_size = painter.text_size(painter.resolve_font(self.inner.font_role, self.inner.font), font_size, &word).unwrap_or_default();
 				if word_size.x + current_width <= size.x {
 					new_text.push_str(&word);
 					current_width += word_size.x;
@@ -174,13 +339,44 @@ impl<S: Signal, A: App<Signal = S>> Label<S, A> {
 	}
 }
 
+/// Find the largest count up to `len` that still fits, by a binary search as measuring the text is costly.
+/// 
+/// `fits` must hold for the smaller counts once it holds for a count, `0` is returned if nothing fits.
+fn last_fitting(len: usize, fits: impl Fn(usize) -> bool) -> usize {
+	let (mut low, mut high) = (0, len);
+	while low < high {
+		let middle = low + (high - low).div_ceil(2);
+		if fits(middle) {
+			low = middle;
+		}else {
+			high = middle - 1;
+		}
+	}
+	low
+}
+
 impl<S: Signal, A: App<Signal = S>> Widget for Label<S, A> {
 	type Signal = S;
 	type Application = A;
 
-	fn size(&self, _: LayoutId, painter: &Painter, _: &Layout<Self::Signal, A>) -> Vec2 {
+	fn size(&self, id: LayoutId, painter: &Painter, layout: &Layout<Self::Signal, A>) -> Vec2 {
 		if let Some(size) = self.inner.size {
 			size
+		}else if let Some(rich) = &self.inner.rich {
+			let font = painter.resolve_font(self.inner.font_role, self.inner.font);
+			let max_width = if self.inner.wrap {
+				self.max_width(id, painter, layout)
+			}else if self.inner.auto_break && self.inner_size != Vec2::ZERO { 
+				self.inner_size.x 
+			}else { 
+				f32::INFINITY 
+			};
+			rich.layout(painter, font, self.font_size(), max_width).size
+		}else if self.fits_width() {
+			let font = painter.resolve_font(self.inner.font_role, self.inner.font);
+			let max_width = self.max_width(id, painter, layout);
+			let size = painter.text_size(font, self.font_size(), self.fitted_text(painter, font, self.font_size(), max_width)).unwrap_or_default();
+			Vec2::new(size.x.min(max_width), size.y)
 		}else {
 			let font_size = match &self.inner.style {
 				LabelStyle::Title => TITLE_TEXT_SIZE,
@@ -189,7 +385,7 @@ impl<S: Signal, A: App<Signal = S>> Widget for Label<S, A> {
 			};
 
 			painter
-			.text_size(self.inner.font, font_size, &self.inner.text)
+			.text_size(painter.resolve_font(self.inner.font_role, self.inner.font), font_size, &self.inner.text)
 			.unwrap_or_default()
 			.min_both(if self.inner_size == Vec2::ZERO {
 				Vec2::INF
@@ -200,6 +396,20 @@ impl<S: Signal, A: App<Signal = S>> Widget for Label<S, A> {
 	}
 
 	fn draw(&mut self, painter: &mut Painter, size: Vec2) {
+		if let Some(rich) = &self.inner.rich {
+			let color = match &self.inner.style {
+				LabelStyle::Title => FillMode::from(PRIMARY_TEXT_COLOR),
+				LabelStyle::Content => FillMode::from(SECONDARY_TEXT_COLOR),
+				LabelStyle::Custom { color, .. } => color.clone(),
+			};
+			self.inner_size = size;
+			let font = painter.resolve_font(self.inner.font_role, self.inner.font);
+			let max_width = if self.inner.auto_break || self.inner.wrap { size.x } else { f32::INFINITY };
+			self.rich_layout = rich.layout(painter, font, self.font_size(), max_width);
+			rich.draw(painter, &self.rich_layout, &color);
+			return;
+		}
+
 		if self.inner.auto_break && self.inner_size != size {
 			self.inner_size = size;
 			self.auto_break_func(painter);
@@ -213,12 +423,33 @@ impl<S: Signal, A: App<Signal = S>> Widget for Label<S, A> {
 
 		painter.set_fill_mode(font_fill);
 
-		painter.draw_text(Vec2::ZERO, self.inner.font, font_size, &self.inner.text);
+		let font = painter.resolve_font(self.inner.font_role, self.inner.font);
+		if self.fits_width() {
+			painter.draw_text(Vec2::ZERO, font, font_size, self.fitted_text(painter, font, font_size, size.x));
+		}else {
+			painter.draw_text(Vec2::ZERO, font, font_size, &self.inner.text);
+		}
 	}
 
 	fn handle_event(&mut self, app: &mut A, input_state: &mut InputState<Self::Signal>, from: LayoutId, area: Rect, _: Vec2) -> bool {
 		// self.inner_size = area.size();
-		self.signals.generate_signals(app, &mut self.inner, input_state, from, area, false, false);
+		let link_at = |input_state: &InputState<S>, touch: u64| {
+			let pos = input_state.get_touch_pos(touch)? - area.lt();
+			let rich = self.inner.rich.as_ref()?;
+			rich.spans[self.rich_layout.span_at(pos)?].link.clone()
+		};
+		if let Some(touch) = input_state.get_touch_pressed_on(area).first() {
+			self.pressed_link = link_at(input_state, *touch);
+		}
+		let has_links = self.inner.rich.as_ref().is_some_and(|rich| rich.spans.iter().any(|span| span.link.is_some()));
+		let res = self.signals.generate_signals(app, &mut self.inner, input_state, from, area, has_links, false);
+
+		if res.is_clicked {
+			if let (Some(link), Some(on_link)) = (self.pressed_link.take(), &self.on_link) {
+				let signal = on_link(app, &mut self.inner, &link);
+				input_state.send_signal_from(from, signal);
+			}
+		}
 
 		false
 	}
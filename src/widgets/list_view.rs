@@ -0,0 +1,410 @@
+//! A virtualized list for very long lists, only the rows in view are in the layout.
+
+use std::{collections::{HashMap, HashSet, VecDeque}, ops::Range};
+
+use indexmap::IndexMap;
+
+use crate::{layout::{Layout, LayoutId}, prelude::{Animatedf32, FillMode, InputState, Painter, Rect, Vec2, Vec4}, App};
+
+use super::{card::{get_default_scroll_animaion, reveal_offset}, selection::{Selection, SelectionMode}, styles::{DEFAULT_ROUNDING, PRIMARY_COLOR, SELECTED_TEXT_COLOR}, EventHandleStrategy, ScrollAction, ScrollSource, Signal, SignalGenerator, Widget};
+
+/// A vertical list of rows with the same height, built on demand by a closure.
+///
+/// Rendering thousands of rows as individual widgets is costly since every widget lives in the layout.
+/// The list view only keeps the rows in its viewport, plus [`ListViewInner::overscan`] rows around it, as its children.
+/// Rows scrolled out of view are replaced in place by the rows scrolled into view, so their ids are reused.
+///
+/// The children of a list view are managed by itself, do not add children to it.
+/// Use [`Self::row_of`] to find the row of a widget sending a signal,
+/// and [`Self::refresh`] to build the rows in view again after the data behind them changed.
+///
+/// Rows are selected when pressed on, following [`ListViewInner::selection`], see [`Selection`] for the Ctrl and Shift semantics.
+///
+/// ```no_run
+/// # use nablo_ui::prelude::*;
+/// # struct MyApp { items: Vec<String> }
+/// # impl App for MyApp {
+/// #     type Signal = ();
+/// #     fn on_start(&mut self, _: &mut Context<(), Self>) {}
+/// #     fn on_signal(&mut self, _: &mut Context<(), Self>, _: SignalWrapper<()>) {}
+/// # }
+/// fn add_list(layout: &mut Layout<(), MyApp>, len: usize) -> Option<LayoutId> {
+///     layout.add_widget(ROOT_LAYOUT_ID, ListView::new(len, 32.0, |app: &mut MyApp, row| {
+///         Label::new(app.items[row].clone())
+///     }).size(Vec2::new(320.0, 480.0)))
+/// }
+/// ```
+pub struct ListView<S: Signal, A: App<Signal = S>> {
+	/// The inner properties of the list view.
+	pub inner: ListViewInner,
+	/// The signals generated by the list view.
+	pub signals: SignalGenerator<S, ListViewInner, A>,
+	/// The signal to be generated when the selection is changed by a press, see [`ListViewInner::selection`] for the selected rows.
+	#[allow(clippy::type_complexity)]
+	pub on_select: Option<Box<dyn Fn(&mut A, &mut ListViewInner) -> S>>,
+	#[allow(clippy::type_complexity)]
+	builder: Box<dyn Fn(&mut A, usize) -> Box<dyn Widget<Signal = S, Application = A>>>,
+	/// the row shown by each child, `None` for the children kept for recycling.
+	rows: HashMap<LayoutId, Option<usize>>,
+	/// the rows requested as new children, matched with the children in the order they are added.
+	pending: VecDeque<Option<usize>>,
+	/// the scroll position.
+	offset: Animatedf32,
+	/// the rows built in the last event handling.
+	synced: Range<usize>,
+	/// whether the rows in view should be built again.
+	stale: bool,
+	/// the presses already applied to the selection.
+	pressed: Vec<u64>,
+}
+
+/// The inner properties of a list view.
+pub struct ListViewInner {
+	/// The number of rows.
+	pub row_count: usize,
+	/// The height of every row.
+	pub row_height: f32,
+	/// The size of the list view.
+	pub size: Vec2,
+	/// The number of rows kept beyond each edge of the viewport, so fast scrolling shows no gaps.
+	pub overscan: usize,
+	/// The background color, `None` to draw no background.
+	pub background_color: Option<FillMode>,
+	/// The color of the scroll bar, `None` to hide it.
+	///
+	/// The rows get narrower by [`Self::scrollbar_width`] and a margin while the scroll bar is shown.
+	pub scrollbar_color: Option<FillMode>,
+	/// The width of the scroll bar.
+	pub scrollbar_width: f32,
+	/// The roundings of the background.
+	pub roundings: Vec4,
+	/// The multiplier applied to the mouse wheel delta.
+	pub wheel_sensitivity: f32,
+	/// The selected rows.
+	pub selection: Selection,
+	/// The color drawn behind the selected rows.
+	pub selected_color: FillMode,
+}
+
+impl Default for ListViewInner {
+	fn default() -> Self {
+		Self {
+			row_count: 0,
+			row_height: DEFAULT_ROUNDING * 4.0,
+			size: Vec2::new(320.0, 480.0),
+			overscan: 2,
+			background_color: None,
+			scrollbar_color: Some(FillMode::from(PRIMARY_COLOR)),
+			scrollbar_width: 4.0,
+			roundings: Vec4::ZERO,
+			wheel_sensitivity: 1.0,
+			selection: Selection::new(SelectionMode::None),
+			selected_color: FillMode::from(SELECTED_TEXT_COLOR),
+		}
+	}
+}
+
+impl<S: Signal, A: App<Signal = S>> ListView<S, A> {
+	/// Create a list view with `row_count` rows of `row_height`, the rows are built by `builder` from their index.
+	pub fn new<W: Widget<Signal = S, Application = A>>(row_count: usize, row_height: f32, builder: impl Fn(&mut A, usize) -> W + 'static) -> Self {
+		Self {
+			inner: ListViewInner {
+				row_count,
+				row_height,
+				..Default::default()
+			},
+			signals: SignalGenerator::default(),
+			on_select: None,
+			builder: Box::new(move |app, row| Box::new(builder(app, row))),
+			rows: HashMap::new(),
+			pending: VecDeque::new(),
+			offset: get_default_scroll_animaion(),
+			synced: 0..0,
+			stale: false,
+			pressed: vec!(),
+		}
+	}
+
+	/// Set the number of rows, the rows already built are kept.
+	pub fn row_count(self, row_count: usize) -> Self {
+		Self { inner: ListViewInner { row_count, ..self.inner }, ..self }
+	}
+
+	/// Set the height of every row.
+	pub fn row_height(self, row_height: f32) -> Self {
+		Self { inner: ListViewInner { row_height, ..self.inner }, ..self }
+	}
+
+	/// Set the size of the list view.
+	pub fn size(self, size: Vec2) -> Self {
+		Self { inner: ListViewInner { size, ..self.inner }, ..self }
+	}
+
+	/// Set the number of rows kept beyond each edge of the viewport.
+	pub fn overscan(self, overscan: usize) -> Self {
+		Self { inner: ListViewInner { overscan, ..self.inner }, ..self }
+	}
+
+	/// Set the background color.
+	pub fn background_color(self, background_color: impl Into<FillMode>) -> Self {
+		Self { inner: ListViewInner { background_color: Some(background_color.into()), ..self.inner }, ..self }
+	}
+
+	/// Set the color of the scroll bar, `None` to hide it.
+	pub fn scrollbar_color(self, scrollbar_color: Option<FillMode>) -> Self {
+		Self { inner: ListViewInner { scrollbar_color, ..self.inner }, ..self }
+	}
+
+	/// Set the width of the scroll bar.
+	pub fn scrollbar_width(self, scrollbar_width: f32) -> Self {
+		Self { inner: ListViewInner { scrollbar_width, ..self.inner }, ..self }
+	}
+
+	/// Set the roundings of the background.
+	pub fn roundings(self, roundings: impl Into<Vec4>) -> Self {
+		Self { inner: ListViewInner { roundings: roundings.into(), ..self.inner }, ..self }
+	}
+
+	/// Set the multiplier applied to the mouse wheel delta.
+	pub fn wheel_sensitivity(self, wheel_sensitivity: f32) -> Self {
+		Self { inner: ListViewInner { wheel_sensitivity, ..self.inner }, ..self }
+	}
+
+	/// Set how many rows can be selected, the selection is cleared.
+	pub fn selection_mode(self, mode: SelectionMode) -> Self {
+		Self { inner: ListViewInner { selection: Selection::new(mode), ..self.inner }, ..self }
+	}
+
+	/// Set the selected rows.
+	pub fn selection(self, selection: Selection) -> Self {
+		Self { inner: ListViewInner { selection, ..self.inner }, ..self }
+	}
+
+	/// Set the color drawn behind the selected rows.
+	pub fn selected_color(self, selected_color: impl Into<FillMode>) -> Self {
+		Self { inner: ListViewInner { selected_color: selected_color.into(), ..self.inner }, ..self }
+	}
+
+	/// Sets the signal to be generated when the selection is changed by a press.
+	pub fn on_select(self, on_select: impl Fn(&mut A, &mut ListViewInner) -> S + 'static) -> Self {
+		Self {
+			on_select: Some(Box::new(on_select)),
+			..self
+		}
+	}
+
+	/// Build the rows in view again, e.g. after the data behind them changed.
+	pub fn refresh(self) -> Self {
+		Self { stale: true, ..self }
+	}
+
+	/// Scroll so that the given row is at the top of the viewport, as far as the list allows.
+	pub fn scroll_to_row(mut self, row: usize) -> Self {
+		self.offset.set(row as f32 * self.inner.row_height);
+		self.offset.clamp(0.0, self.max_offset());
+		self
+	}
+
+	/// Get the scroll position the list view is heading to.
+	pub fn offset(&self) -> f32 {
+		self.offset.target()
+	}
+
+	/// Get the row shown by the given child, `None` if it is not a row in view.
+	pub fn row_of(&self, id: LayoutId) -> Option<usize> {
+		self.rows.get(&id).copied().flatten()
+	}
+
+	/// Get the child showing the given row, `None` if the row is not built.
+	pub fn row_id(&self, row: usize) -> Option<LayoutId> {
+		self.rows.iter().find(|(_, shown)| **shown == Some(row)).map(|(id, _)| *id)
+	}
+
+	/// Get the rows kept in the layout at the current scroll position, including the overscan.
+	pub fn visible_rows(&self) -> Range<usize> {
+		let height = self.inner.row_height.max(1.0);
+		let offset = self.offset.value();
+		let start = (offset / height).floor().max(0.0) as usize;
+		let end = ((offset + self.inner.size.y) / height).ceil().max(0.0) as usize;
+		start.saturating_sub(self.inner.overscan).min(self.inner.row_count)..(end + self.inner.overscan).min(self.inner.row_count)
+	}
+
+	fn max_offset(&self) -> f32 {
+		(self.inner.row_count as f32 * self.inner.row_height - self.inner.size.y).max(0.0)
+	}
+
+	fn shows_scrollbar(&self) -> bool {
+		self.inner.scrollbar_color.is_some() && self.max_offset() > 0.0
+	}
+
+	fn row_width(&self) -> f32 {
+		if self.shows_scrollbar() {
+			self.inner.size.x - self.inner.scrollbar_width * 2.0
+		}else {
+			self.inner.size.x
+		}
+	}
+
+	/// Release the rows out of view and build the rows scrolled into view, returns whether any row changed.
+	fn sync_rows(&mut self, app: &mut A, input_state: &mut InputState<S>, id: LayoutId) -> bool {
+		let range = self.visible_rows();
+		let stale = std::mem::take(&mut self.stale);
+		for shown in self.rows.values_mut().chain(self.pending.iter_mut()) {
+			if stale || shown.is_some_and(|row| !range.contains(&row)) {
+				*shown = None;
+			}
+		}
+		let built = self.rows.values().chain(self.pending.iter()).flatten().copied().collect::<HashSet<_>>();
+		let mut free = self.rows.iter().filter(|(_, shown)| shown.is_none()).map(|(id, _)| *id).collect::<Vec<_>>();
+		let mut changed = false;
+		for row in range.clone().filter(|row| !built.contains(row)) {
+			let widget = (self.builder)(app, row);
+			match free.pop() {
+				Some(child) => {
+					input_state.replace_boxed_child(id, child, widget);
+					self.rows.insert(child, Some(row));
+				},
+				None => {
+					input_state.add_boxed_child(id, widget);
+					self.pending.push_back(Some(row));
+				},
+			}
+			changed = true;
+		}
+		self.synced = range;
+		changed
+	}
+}
+
+impl<S: Signal, A: App<Signal = S>> Widget for ListView<S, A> {
+	type Signal = S;
+	type Application = A;
+
+	fn size(&self, _: LayoutId, _: &Painter, _: &Layout<Self::Signal, Self::Application>) -> Vec2 {
+		self.inner.size
+	}
+
+	fn draw(&mut self, painter: &mut Painter, size: Vec2) {
+		if let Some(background_color) = &self.inner.background_color {
+			painter.set_fill_mode(background_color.clone());
+			painter.draw_rect(Rect::from_size(size), self.inner.roundings);
+		}
+		let offset = self.offset.value();
+		painter.set_fill_mode(self.inner.selected_color.clone());
+		for row in self.inner.selection.selected().range(self.visible_rows()) {
+			painter.draw_rect(
+				Rect::from_lt_size(Vec2::new(0.0, *row as f32 * self.inner.row_height - offset), Vec2::new(self.row_width(), self.inner.row_height)), 
+				Vec4::ZERO
+			);
+		}
+		if let (true, Some(scrollbar_color)) = (self.shows_scrollbar(), &self.inner.scrollbar_color) {
+			let height = self.inner.size.y;
+			let content = self.inner.row_count as f32 * self.inner.row_height;
+			let width = self.inner.scrollbar_width;
+			let thumb = (height * height / content).clamp(width * 4.0, height);
+			let progress = (self.offset.value() / self.max_offset()).clamp(0.0, 1.0);
+			painter.set_fill_mode(scrollbar_color.clone());
+			painter.draw_rect(
+				Rect::from_lt_size(Vec2::new(self.inner.size.x - width * 1.5, (height - thumb) * progress), Vec2::new(width, thumb)),
+				Vec4::same(width / 2.0)
+			);
+		}
+	}
+
+	fn handle_event(&mut self, app: &mut A, input_state: &mut InputState<Self::Signal>, id: LayoutId, area: Rect, pos: Vec2) -> bool {
+		// taken before the signals and the rows, which mark the touches as used.
+		let pressed = input_state.get_touch_pressed_on(area);
+		self.signals.generate_signals(app, &mut self.inner, input_state, id, area, false, false);
+		// the row count or the size may have changed since the last scroll.
+		self.offset.clamp(0.0, self.max_offset());
+
+		// a press stays pressed for a while, apply it only once.
+		self.pressed.retain(|touch| pressed.contains(touch));
+		let mut selected = self.inner.selection.truncate(self.inner.row_count);
+		for touch in pressed {
+			if self.pressed.contains(&touch) {
+				continue;
+			}
+			self.pressed.push(touch);
+			let Some(touch_pos) = input_state.get_touch_pos(touch) else { continue };
+			let row = ((touch_pos.y - pos.y + self.offset.value()) / self.inner.row_height).floor();
+			if row >= 0.0 && (row as usize) < self.inner.row_count {
+				selected |= self.inner.selection.click(row as usize, input_state.modifiers());
+			}
+		}
+		if selected {
+			if let Some(on_select) = &self.on_select {
+				let signal = on_select(app, &mut self.inner);
+				input_state.send_signal_from(id, signal);
+			}
+		}
+
+		let changed = self.sync_rows(app, input_state, id);
+		changed || selected || self.offset.is_animating()
+	}
+
+	fn handle_child_layout(&mut self, childs: IndexMap<LayoutId, Vec2>, _: Rect, _: LayoutId) -> HashMap<LayoutId, Option<Rect>> {
+		// the requested children are appended in the order they were requested.
+		self.rows.retain(|child, _| childs.contains_key(child));
+		for child in childs.keys() {
+			if !self.rows.contains_key(child) {
+				if let Some(shown) = self.pending.pop_front() {
+					self.rows.insert(*child, shown);
+				}
+			}
+		}
+
+		let width = self.row_width();
+		let offset = self.offset.value();
+		self.rows.iter().filter_map(|(child, shown)| shown.map(|row| (*child, Some(Rect::from_lt_size(
+			Vec2::new(0.0, row as f32 * self.inner.row_height - offset),
+			Vec2::new(width, self.inner.row_height)
+		))))).collect()
+	}
+
+	fn scroll_by(&mut self, delta: Vec2, source: ScrollSource) -> Vec2 {
+		let factor = match source {
+			ScrollSource::Wheel => self.inner.wheel_sensitivity,
+			ScrollSource::Drag => 1.0,
+		};
+		if factor == 0.0 {
+			return delta;
+		}
+		let before = self.offset.target();
+		self.offset.set_by(- delta.y * factor);
+		self.offset.clamp(0.0, self.max_offset());
+		Vec2::new(delta.x, delta.y + (self.offset.target() - before) / factor)
+	}
+
+	fn scroll_action(&mut self, action: ScrollAction) -> bool {
+		let before = self.offset.target();
+		let target = match action {
+			ScrollAction::LineUp => before - self.inner.row_height,
+			ScrollAction::LineDown => before + self.inner.row_height,
+			ScrollAction::PageUp => before - self.inner.size.y,
+			ScrollAction::PageDown => before + self.inner.size.y,
+			ScrollAction::Home => 0.0,
+			ScrollAction::End => self.max_offset(),
+			ScrollAction::LineLeft | ScrollAction::LineRight => return false,
+		};
+		self.offset.set(target);
+		self.offset.clamp(0.0, self.max_offset());
+		self.offset.target() != before
+	}
+
+	fn scroll_to_reveal(&mut self, area: Rect) -> Vec2 {
+		let current = self.offset.value();
+		let before = self.offset.target();
+		self.offset.set(reveal_offset(area.y + current, area.h, before, self.inner.size.y));
+		self.offset.clamp(0.0, self.max_offset());
+		Vec2::y(self.offset.target() - current)
+	}
+
+	fn event_handle_strategy(&self) -> EventHandleStrategy {
+		if self.stale || self.offset.is_animating() || self.synced != self.visible_rows() {
+			EventHandleStrategy::AlwaysSecondary
+		}else {
+			EventHandleStrategy::OnHover
+		}
+	}
+}
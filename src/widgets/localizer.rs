@@ -0,0 +1,131 @@
+//! Localization hooks for the strings and numbers shown by the built-in widgets.
+
+use std::sync::Arc;
+
+/// A message shown by the built-in widgets.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BuiltinMessage<'a> {
+	/// The input text is longer than the limit.
+	TooLong {
+		/// The maximum length of the text in characters.
+		limit: usize,
+	},
+	/// Only digits are allowed in the input.
+	OnlyDigits,
+	/// The input is not a valid number.
+	InvalidNumber {
+		/// The text failed to parse.
+		input: &'a str,
+	},
+	/// The number is out of the allowed range.
+	OutOfRange {
+		/// The minimum allowed value.
+		min: Option<f32>,
+		/// The maximum allowed value.
+		max: Option<f32>,
+	},
+	/// The item of the text context menu cutting the selected text.
+	Cut,
+	/// The item of the text context menu copying the selected text.
+	Copy,
+	/// The item of the text context menu pasting the clipboard.
+	Paste,
+	/// The item of the text context menu selecting all the text.
+	SelectAll,
+}
+
+/// Supplies translated messages and locale-aware formatting for the built-in widgets.
+///
+/// Set it by [`crate::Context::set_localizer`].
+/// All methods have an English default, so you only need to override what differs in your locale.
+pub trait Localizer: Send + Sync {
+	/// Get the translated text of a built-in message.
+	fn message(&self, message: BuiltinMessage) -> String {
+		match message {
+			BuiltinMessage::TooLong { .. } => "Too long".to_string(),
+			BuiltinMessage::OnlyDigits => "Only digits allowed".to_string(),
+			BuiltinMessage::InvalidNumber { .. } => "Invalid number".to_string(),
+			BuiltinMessage::OutOfRange { min: Some(min), max: Some(max) } => format!("Must be between {} and {}", min, max),
+			BuiltinMessage::OutOfRange { min: Some(min), max: None } => format!("Must be at least {}", min),
+			BuiltinMessage::OutOfRange { min: None, max: Some(max) } => format!("Must be at most {}", max),
+			BuiltinMessage::OutOfRange { min: None, max: None } => "Out of range".to_string(),
+			BuiltinMessage::Cut => "Cut".to_string(),
+			BuiltinMessage::Copy => "Copy".to_string(),
+			BuiltinMessage::Paste => "Paste".to_string(),
+			BuiltinMessage::SelectAll => "Select all".to_string(),
+		}
+	}
+
+	/// Format a number with the given decimal places.
+	fn format_number(&self, value: f32, decimal_places: usize) -> String {
+		format!("{:.1$}", value, decimal_places)
+	}
+
+	/// Parse a number typed by the user, `None` if the text is not a valid number.
+	fn parse_number(&self, text: &str) -> Option<f32> {
+		text.parse().ok()
+	}
+
+	/// Format a date.
+	fn format_date(&self, date: time::Date) -> String {
+		date.to_string()
+	}
+}
+
+/// The default localizer, which uses English messages and formats numbers with `.` as the decimal separator.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultLocalizer;
+
+impl Localizer for DefaultLocalizer {}
+
+/// A localizer for locales using `,` as the decimal separator and `.` or space to group thousands.
+///
+/// Messages are still in English, wrap your own [`Localizer`] around it if you need translations.
+#[derive(Debug, Clone)]
+pub struct DecimalCommaLocalizer {
+	/// The separator between groups of thousands, `None` to not group.
+	pub group_separator: Option<char>,
+}
+
+impl Default for DecimalCommaLocalizer {
+	fn default() -> Self {
+		Self {
+			group_separator: Some('.'),
+		}
+	}
+}
+
+impl Localizer for DecimalCommaLocalizer {
+	fn format_number(&self, value: f32, decimal_places: usize) -> String {
+		let text = format!("{:.1$}", value, decimal_places);
+		let (sign, text) = match text.strip_prefix('-') {
+			Some(text) => ("-", text),
+			None => ("", text.as_str()),
+		};
+		let (integer, fraction) = text.split_once('.').unwrap_or((text, ""));
+		let mut out = sign.to_string();
+		for (i, digit) in integer.chars().enumerate() {
+			if let Some(separator) = self.group_separator {
+				if i != 0 && (integer.len() - i) % 3 == 0 {
+					out.push(separator);
+				}
+			}
+			out.push(digit);
+		}
+		if !fraction.is_empty() {
+			out.push(',');
+			out.push_str(fraction);
+		}
+		out
+	}
+
+	fn parse_number(&self, text: &str) -> Option<f32> {
+		let text = text.chars().filter(|c| Some(*c) != self.group_separator && !c.is_whitespace()).map(|c| if c == ',' { '.' } else { c }).collect::<String>();
+		text.parse().ok()
+	}
+}
+
+/// Get the localizer used when none is set.
+pub(crate) fn default_localizer() -> Arc<dyn Localizer> {
+	Arc::new(DefaultLocalizer)
+}
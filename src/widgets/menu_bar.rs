@@ -0,0 +1,300 @@
+//! A bar of titles opening dropdown menus, as on the top of desktop applications.
+
+use crate::{layout::{Layout, LayoutId}, prelude::{FillMode, FontId, FontRole, InputState, Painter, Rect, Vec2, Vec4}, App};
+
+use super::{context_menu::{ContextMenu, ContextMenuHandle}, styles::{CARD_COLOR, CONTENT_TEXT_SIZE, DEFAULT_PADDING, DISABLE_TEXT_COLOR, PRIMARY_TEXT_COLOR, SELECTED_TEXT_COLOR}, EventHandleStrategy, Signal, Widget};
+
+/// A bar of titles, each opening a [`ContextMenu`] below it when pressed.
+///
+/// While a menu is open, moving the pointer onto another title opens its menu instead,
+/// and pressing the title again closes it. The menus support separators, disabled items, shortcuts and submenus,
+/// see [`super::context_menu::ContextMenuItem`], and the signals of the items are sent from the bar.
+///
+/// ```no_run
+/// # use nablo_ui::prelude::*;
+/// enum Sig {
+///     Open,
+///     Save,
+///     Quit,
+/// }
+/// # impl Signal for Sig {}
+/// # struct MyApp;
+/// # impl App for MyApp {
+/// #     type Signal = Sig;
+/// #     fn on_start(&mut self, _: &mut Context<Sig, Self>) {}
+/// #     fn on_signal(&mut self, _: &mut Context<Sig, Self>, _: SignalWrapper<Sig>) {}
+/// # }
+/// fn add_menu_bar(layout: &mut Layout<Sig, MyApp>) {
+///     let file = ContextMenu::new()
+///         .item(ContextMenuItem::new("Open", |_| Sig::Open).primary_shortcut("O"))
+///         .item(ContextMenuItem::new("Save", |_| Sig::Save).primary_shortcut("S"))
+///         .separator()
+///         .item(ContextMenuItem::new("Quit", |_| Sig::Quit));
+///     layout.add_widget(ROOT_LAYOUT_ID, MenuBar::new().menu("File", file));
+/// }
+/// ```
+pub struct MenuBar<S: Signal, A: App<Signal = S>> {
+	/// The inner properties of the menu bar.
+	pub inner: MenuBarInner<S, A>,
+	/// the right edge of every title relative to the bar, as of the last draw.
+	edges: Vec<f32>,
+	hovered: Option<usize>,
+	/// the title whose menu is open, and the handle of the menu.
+	open: Option<(usize, ContextMenuHandle)>,
+	/// the touch pressing the bar.
+	pressing: Option<u64>,
+}
+
+/// A title of a [`MenuBar`] and the menu it opens.
+pub struct MenuBarEntry<S: Signal, A: App<Signal = S>> {
+	/// The title shown in the bar.
+	pub title: String,
+	/// The menu opened by the title.
+	pub menu: ContextMenu<S, A>,
+	/// Whether the menu can be opened, disabled titles are greyed out.
+	pub enabled: bool,
+}
+
+impl<S: Signal, A: App<Signal = S>> MenuBarEntry<S, A> {
+	/// Create a title opening the menu.
+	pub fn new(title: impl Into<String>, menu: ContextMenu<S, A>) -> Self {
+		Self {
+			title: title.into(),
+			menu,
+			enabled: true,
+		}
+	}
+
+	/// Set whether the menu can be opened.
+	pub fn enabled(self, enabled: bool) -> Self {
+		Self { enabled, ..self }
+	}
+}
+
+/// The inner properties of a menu bar.
+pub struct MenuBarInner<S: Signal, A: App<Signal = S>> {
+	/// The titles of the bar.
+	pub entries: Vec<MenuBarEntry<S, A>>,
+	/// The width of the bar, `None` to fit the titles.
+	pub width: Option<f32>,
+	/// The font of the titles.
+	pub font: FontId,
+	/// The font role of the titles, see [`FontRole`].
+	pub font_role: Option<FontRole>,
+	/// The font size of the titles.
+	pub font_size: f32,
+	/// The padding around the titles.
+	pub padding: Vec2,
+	/// The background color of the bar.
+	pub background_color: FillMode,
+	/// The background color of the hovered title and the title whose menu is open.
+	pub hover_color: FillMode,
+	/// The color of the titles.
+	pub text_color: FillMode,
+	/// The color of the disabled titles.
+	pub disabled_text_color: FillMode,
+}
+
+impl<S: Signal, A: App<Signal = S>> Default for MenuBar<S, A> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<S: Signal, A: App<Signal = S>> MenuBar<S, A> {
+	/// Create an empty menu bar.
+	pub fn new() -> Self {
+		Self {
+			inner: MenuBarInner {
+				entries: vec!(),
+				width: None,
+				font: 0,
+				font_role: Some(FontRole::Body),
+				font_size: CONTENT_TEXT_SIZE,
+				padding: Vec2::new(DEFAULT_PADDING * 1.5, DEFAULT_PADDING / 2.0),
+				background_color: FillMode::from(CARD_COLOR),
+				hover_color: FillMode::from(SELECTED_TEXT_COLOR),
+				text_color: FillMode::from(PRIMARY_TEXT_COLOR),
+				disabled_text_color: FillMode::from(DISABLE_TEXT_COLOR),
+			},
+			edges: vec!(),
+			hovered: None,
+			open: None,
+			pressing: None,
+		}
+	}
+
+	/// Add a title after the existing ones.
+	pub fn entry(mut self, entry: MenuBarEntry<S, A>) -> Self {
+		self.inner.entries.push(entry);
+		self
+	}
+
+	/// Add a title opening the menu after the existing ones.
+	pub fn menu(self, title: impl Into<String>, menu: ContextMenu<S, A>) -> Self {
+		self.entry(MenuBarEntry::new(title, menu))
+	}
+
+	/// Set the width of the bar, `None` to fit the titles.
+	pub fn width(self, width: Option<f32>) -> Self {
+		Self { inner: MenuBarInner { width, ..self.inner }, ..self }
+	}
+
+	/// Set the font of the titles.
+	/// 
+	/// Clears the font role, so the font is used even if the theme registers one, see [`Self::font_role`].
+	pub fn font(self, font: FontId) -> Self {
+		Self { inner: MenuBarInner { font, font_role: None, ..self.inner }, ..self }
+	}
+
+	/// Set the font role of the titles, see [`FontRole`].
+	pub fn font_role(self, font_role: Option<FontRole>) -> Self {
+		Self { inner: MenuBarInner { font_role, ..self.inner }, ..self }
+	}
+
+	/// Set the font size of the titles.
+	pub fn font_size(self, font_size: f32) -> Self {
+		Self { inner: MenuBarInner { font_size, ..self.inner }, ..self }
+	}
+
+	/// Set the padding around the titles.
+	pub fn padding(self, padding: impl Into<Vec2>) -> Self {
+		Self { inner: MenuBarInner { padding: padding.into(), ..self.inner }, ..self }
+	}
+
+	/// Set the background color of the bar.
+	pub fn background_color(self, background_color: impl Into<FillMode>) -> Self {
+		Self { inner: MenuBarInner { background_color: background_color.into(), ..self.inner }, ..self }
+	}
+
+	/// Set the background color of the hovered title and the title whose menu is open.
+	pub fn hover_color(self, hover_color: impl Into<FillMode>) -> Self {
+		Self { inner: MenuBarInner { hover_color: hover_color.into(), ..self.inner }, ..self }
+	}
+
+	/// Set the color of the titles.
+	pub fn text_color(self, text_color: impl Into<FillMode>) -> Self {
+		Self { inner: MenuBarInner { text_color: text_color.into(), ..self.inner }, ..self }
+	}
+
+	/// Set the color of the disabled titles.
+	pub fn disabled_text_color(self, disabled_text_color: impl Into<FillMode>) -> Self {
+		Self { inner: MenuBarInner { disabled_text_color: disabled_text_color.into(), ..self.inner }, ..self }
+	}
+
+	/// Check whether a menu of the bar is open.
+	pub fn is_open(&self) -> bool {
+		self.open.as_ref().is_some_and(|(_, handle)| handle.is_open())
+	}
+
+	/// Close the open menu, if any.
+	pub fn close(&mut self) {
+		if let Some((_, handle)) = self.open.take() {
+			handle.close();
+		}
+	}
+
+	fn height(&self) -> f32 {
+		self.inner.font_size + self.inner.padding.y * 2.0
+	}
+
+	/// Get the title at the x relative to the bar.
+	fn entry_at(&self, x: f32) -> Option<usize> {
+		if x < 0.0 {
+			return None;
+		}
+		self.edges.iter().position(|edge| x < *edge)
+	}
+
+	fn open_menu(&mut self, input_state: &mut InputState<S>, id: LayoutId, index: usize, pos: Vec2) {
+		self.close();
+		let Some(entry) = self.inner.entries.get(index).filter(|entry| entry.enabled) else { return };
+		let left = if index == 0 { 0.0 } else { self.edges[index - 1] };
+		let handle = entry.menu.opened_for(id).open(input_state, id, pos + Vec2::new(left, self.height()));
+		self.open = Some((index, handle));
+	}
+}
+
+impl<S: Signal, A: App<Signal = S>> Widget for MenuBar<S, A> {
+	type Signal = S;
+	type Application = A;
+
+	fn size(&self, _: LayoutId, painter: &Painter, _: &Layout<Self::Signal, Self::Application>) -> Vec2 {
+		let width = self.inner.width.unwrap_or_else(|| {
+			let font = painter.resolve_font(self.inner.font_role, self.inner.font);
+			self.inner.entries.iter().map(|entry| {
+				painter.text_size(font, self.inner.font_size, &entry.title).map_or(0.0, |size| size.x) + self.inner.padding.x * 2.0
+			}).sum()
+		});
+		Vec2::new(width, self.height())
+	}
+
+	fn draw(&mut self, painter: &mut Painter, size: Vec2) {
+		painter.set_fill_mode(self.inner.background_color.clone());
+		painter.draw_rect(Rect::from_size(size), Vec4::ZERO);
+
+		let font = painter.resolve_font(self.inner.font_role, self.inner.font);
+		let padding = self.inner.padding;
+		let open = self.open.as_ref().map(|(index, _)| *index);
+		self.edges.clear();
+		let mut left = 0.0;
+		for (index, entry) in self.inner.entries.iter().enumerate() {
+			let width = painter.text_size(font, self.inner.font_size, &entry.title).map_or(0.0, |size| size.x) + padding.x * 2.0;
+			if entry.enabled && (open == Some(index) || self.hovered == Some(index)) {
+				painter.set_fill_mode(self.inner.hover_color.clone());
+				painter.draw_rect(Rect::new(left, 0.0, width, size.y), Vec4::same(self.inner.font_size / 8.0));
+			}
+			painter.set_fill_mode(if entry.enabled { self.inner.text_color.clone() } else { self.inner.disabled_text_color.clone() });
+			painter.draw_text(Vec2::new(left + padding.x, padding.y), font, self.inner.font_size, &entry.title);
+			left += width;
+			self.edges.push(left);
+		}
+	}
+
+	fn handle_event(&mut self, _: &mut A, input_state: &mut InputState<Self::Signal>, id: LayoutId, area: Rect, pos: Vec2) -> bool {
+		let before = (self.hovered, self.open.as_ref().map(|(index, _)| *index));
+		// the menu closes itself on a press outside of it, including the one on its own title.
+		let was_open = before.1;
+		if !self.is_open() {
+			self.open = None;
+		}
+
+		self.hovered = input_state.hover_pos()
+			.filter(|hover| area.contains(*hover))
+			.and_then(|hover| self.entry_at(hover.x - pos.x));
+
+		for touch in input_state.get_touch_pressed_on(area) {
+			if self.pressing == Some(touch) || input_state.is_touch_released(touch) {
+				continue;
+			}
+			self.pressing = Some(touch);
+			let Some(index) = input_state.get_touch_pos(touch).and_then(|touch_pos| self.entry_at(touch_pos.x - pos.x)) else { continue };
+			if was_open == Some(index) {
+				self.close();
+			}else {
+				self.open_menu(input_state, id, index, pos);
+			}
+		}
+		if self.pressing.is_some_and(|touch| input_state.is_touch_released(touch)) {
+			self.pressing = None;
+		}
+
+		// moving onto another title while a menu is open switches the menu.
+		if let (Some(hovered), Some((open, _))) = (self.hovered, &self.open) {
+			if hovered != *open && self.inner.entries[hovered].enabled {
+				self.open_menu(input_state, id, hovered, pos);
+			}
+		}
+
+		before != (self.hovered, self.open.as_ref().map(|(index, _)| *index))
+	}
+
+	fn event_handle_strategy(&self) -> EventHandleStrategy {
+		if self.open.is_some() || self.pressing.is_some() {
+			// notices the menu closing itself.
+			EventHandleStrategy::AlwaysSecondary
+		}else {
+			EventHandleStrategy::OnHover
+		}
+	}
+}
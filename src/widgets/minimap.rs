@@ -0,0 +1,237 @@
+//! A scaled overview of a scrollable card, panning it when dragged.
+
+use std::{cell::{Cell, RefCell}, marker::PhantomData};
+
+use time::Duration;
+
+use crate::{layout::{Layout, LayoutId}, prelude::{FillMode, InputState, Painter, Rect, Vec2, Vec4}, App};
+
+use super::{card::Card, styles::{CARD_BORDER_COLOR, INPUT_BACKGROUND_COLOR, PRIMARY_COLOR}, EventHandleStrategy, Signal, Widget};
+
+/// How often the minimap looks at its card again while idle.
+/// 
+/// The minimap can only read the card when it is measured, so it asks for a layout pass this often.
+pub static MINIMAP_REFRESH_INTERVAL: Duration = Duration::milliseconds(200);
+
+/// A scaled overview of the children of a scrollable [`Card`], with the rect currently shown by the card.
+/// 
+/// Pressing or dragging on the minimap scrolls the card so that the view rect is centered on the pointer.
+/// 
+/// The card is read when the minimap is measured, after it scrolled or every [`MINIMAP_REFRESH_INTERVAL`].
+/// Only a plain [`Card`] is supported as the target, not one wrapped by another widget.
+pub struct Minimap<S: Signal, A: App<Signal = S>> {
+	/// The inner properties of the minimap.
+	pub inner: MinimapInner,
+	view: RefCell<MinimapView>,
+	stale: Cell<bool>,
+	dragging_by: Option<u64>,
+	refreshed_at: Duration,
+	_marker: PhantomData<(S, A)>,
+}
+
+/// The inner properties of the [`Minimap`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct MinimapInner {
+	/// The id of the card to show.
+	pub target: LayoutId,
+	/// The size of the minimap, the overview keeps the aspect ratio of the contents inside of it.
+	pub size: Vec2,
+	/// The background color of the minimap.
+	pub background_color: FillMode,
+	/// The color of the children in the overview.
+	pub child_color: FillMode,
+	/// The color of the outline of the view rect.
+	pub view_color: FillMode,
+	/// The width of the outline of the view rect.
+	pub view_width: f32,
+}
+
+/// What the minimap knows about its card, taken when measured.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct MinimapView {
+	/// the size of the contents, at least the viewport.
+	content: Vec2,
+	viewport: Vec2,
+	/// the scroll position shown now.
+	offset: Vec2,
+	/// the children in the coordinates of the contents.
+	children: Vec<Rect>,
+}
+
+impl MinimapView {
+	/// The factor from the coordinates of the contents to the minimap.
+	fn scale(&self, size: Vec2) -> f32 {
+		if self.content.x <= 0.0 || self.content.y <= 0.0 {
+			return 0.0;
+		}
+		(size.x / self.content.x).min(size.y / self.content.y)
+	}
+}
+
+impl<S: Signal, A: App<Signal = S>> Minimap<S, A> {
+	/// Creates a new minimap showing the card with the given id.
+	pub fn new(target: LayoutId) -> Self {
+		Self {
+			inner: MinimapInner {
+				target,
+				size: Vec2::new(120.0, 160.0),
+				background_color: INPUT_BACKGROUND_COLOR.into(),
+				child_color: CARD_BORDER_COLOR.into(),
+				view_color: PRIMARY_COLOR.into(),
+				view_width: 2.0,
+			},
+			view: RefCell::new(MinimapView::default()),
+			stale: Cell::new(false),
+			dragging_by: None,
+			refreshed_at: Duration::ZERO,
+			_marker: PhantomData,
+		}
+	}
+
+	/// Sets the card to show.
+	pub fn target(self, target: LayoutId) -> Self {
+		Self { inner: MinimapInner { target, ..self.inner }, ..self }
+	}
+
+	/// Sets the size of the minimap.
+	pub fn set_size(self, size: impl Into<Vec2>) -> Self {
+		Self { inner: MinimapInner { size: size.into(), ..self.inner }, ..self }
+	}
+
+	/// Sets the background color of the minimap.
+	pub fn background_color(self, background_color: impl Into<FillMode>) -> Self {
+		Self { inner: MinimapInner { background_color: background_color.into(), ..self.inner }, ..self }
+	}
+
+	/// Sets the color of the children in the overview.
+	pub fn child_color(self, child_color: impl Into<FillMode>) -> Self {
+		Self { inner: MinimapInner { child_color: child_color.into(), ..self.inner }, ..self }
+	}
+
+	/// Sets the color and the width of the outline of the view rect.
+	pub fn view_color(self, view_color: impl Into<FillMode>, view_width: f32) -> Self {
+		Self { inner: MinimapInner { view_color: view_color.into(), view_width, ..self.inner }, ..self }
+	}
+
+	/// Read the card from the layout, `None` if it is not a laid out [`Card`].
+	fn read_view(&self, layout: &Layout<S, A>) -> Option<MinimapView> {
+		let target = self.inner.target;
+		let card = layout.get_widget::<Card<S, A>>(target)?;
+		let card_pos = layout.get_widget_pos(target)?;
+		let offset = card.inner.scroll.current_offset();
+		let children = layout.get_children_ids(target).unwrap_or_default().iter().filter_map(|id| {
+			let pos = layout.get_widget_pos(*id)?;
+			let size = layout.get_widget_size(*id)?;
+			Some(Rect::from_lt_size(pos - card_pos + offset, size))
+		}).collect();
+		Some(MinimapView {
+			content: card.content_size().max(card.viewport_size()),
+			viewport: card.viewport_size(),
+			offset,
+			children,
+		})
+	}
+}
+
+impl<S: Signal, A: App<Signal = S>> Widget for Minimap<S, A> {
+	type Signal = S;
+	type Application = A;
+
+	fn handle_event(&mut self, _: &mut A, input_state: &mut InputState<Self::Signal>, _: LayoutId, area: Rect, pos: Vec2) -> bool {
+		if let Some(touch) = input_state.get_touch_pressed_on(area).first() {
+			self.dragging_by = Some(*touch);
+		}else if self.dragging_by.is_some_and(|touch| input_state.is_touch_released(touch)) {
+			self.dragging_by = None;
+		}
+
+		let view = self.view.borrow();
+		let scale = view.scale(self.inner.size);
+		let pointer = self.dragging_by.and_then(|touch| input_state.get_touch_pos(touch));
+		if let Some(pointer) = pointer.filter(|_| scale > 0.0) {
+			// centers the view rect on the pointer, the card clamps the position by itself.
+			let target = (pointer - pos) / scale - view.viewport / 2.0;
+			input_state.request_reveal(self.inner.target, Rect::from_lt_size(target - view.offset, view.viewport));
+		}
+		drop(view);
+
+		let now = input_state.program_running_time();
+		let refresh = now - self.refreshed_at >= MINIMAP_REFRESH_INTERVAL;
+		if refresh {
+			self.refreshed_at = now;
+		}
+		input_state.request_frame_after(MINIMAP_REFRESH_INTERVAL);
+		// marking the minimap dirty gets it measured, which reads the card again.
+		self.stale.take() || refresh || self.dragging_by.is_some()
+	}
+
+	fn draw(&mut self, painter: &mut Painter, size: Vec2) {
+		painter.set_fill_mode(self.inner.background_color.clone());
+		painter.draw_rect(Rect::from_size(size), Vec4::ZERO);
+
+		let view = self.view.borrow();
+		let scale = view.scale(size);
+		if scale <= 0.0 {
+			return;
+		}
+		let to_minimap = |rect: Rect| Rect::from_lt_size(rect.lt() * scale, rect.size() * scale);
+		painter.set_fill_mode(self.inner.child_color.clone());
+		for child in &view.children {
+			painter.draw_rect(to_minimap(*child), Vec4::ZERO);
+		}
+		painter.set_fill_mode(self.inner.view_color.clone());
+		painter.draw_stroked_rect(to_minimap(Rect::from_lt_size(view.offset, view.viewport)), Vec4::ZERO, self.inner.view_width);
+	}
+
+	fn size(&self, _: LayoutId, _: &Painter, layout: &Layout<Self::Signal, A>) -> Vec2 {
+		let view = self.read_view(layout).unwrap_or_default();
+		if *self.view.borrow() != view {
+			self.view.replace(view);
+			self.stale.set(true);
+		}
+		self.inner.size
+	}
+
+	fn event_handle_strategy(&self) -> EventHandleStrategy {
+		EventHandleStrategy::AlwaysSecondary
+	}
+}
+
+mod test {
+	#[test]
+	fn test_minimap_pans_card() {
+		use std::sync::{Arc, Mutex};
+		use super::Minimap;
+		use crate::{layout::{Layout, ROOT_LAYOUT_ID}, prelude::{Card, FontPool, Painter, Scroll, Vec2}, widgets::SignalWrapper, window::{event::{MouseButton, WindowEvent}, input_state::InputState}, App, Context};
+
+		struct TestApp;
+		impl App for TestApp {
+			type Signal = ();
+			fn on_start(&mut self, _: &mut Context<(), Self>) {}
+			fn on_signal(&mut self, _: &mut Context<(), Self>, _: SignalWrapper<()>) {}
+		}
+
+		let window = Vec2::new(400.0, 600.0);
+		let mut layout = Layout::<(), TestApp>::new();
+		layout.insert_root_widget(Card::new_vertical());
+		let card = Card::new_vertical().set_size(Vec2::new(200.0, 200.0)).scroll(Scroll::vertical());
+		let card_id = layout.add_widget(ROOT_LAYOUT_ID, card).unwrap();
+		for _ in 0..10 {
+			layout.add_widget(card_id, Card::new_vertical().set_size(Vec2::new(100.0, 100.0)));
+		}
+		let minimap_id = layout.add_widget(ROOT_LAYOUT_ID, Minimap::new(card_id).set_size(Vec2::new(100.0, 100.0))).unwrap();
+		let mut painter = Painter::headless(Arc::new(Mutex::new(FontPool::new())), window);
+		let mut state = InputState::new();
+		// the minimap reads the card once the card is laid out.
+		for _ in 0..2 {
+			layout.handle_draw(&mut painter, window);
+			state.update(vec!());
+			layout.handle_events(&mut state, &mut TestApp);
+		}
+
+		let pos = layout.get_widget_pos(minimap_id).unwrap();
+		state.update(vec!(WindowEvent::MouseMoved(pos + Vec2::new(10.0, 90.0)), WindowEvent::MousePressed(MouseButton::Left)));
+		layout.handle_events(&mut state, &mut TestApp);
+		let card = layout.get_widget::<Card<(), TestApp>>(card_id).unwrap();
+		assert!(card.offset().y > 0.0);
+	}
+}
@@ -2,20 +2,40 @@
 //! 
 //! For convenience, the `prelude` module is included, which re-exports all the types and functions from this module.
 
+pub mod animated_image;
 pub mod button;
 pub mod canvas;
 pub mod card;
 pub mod collapse;
+pub mod composite;
+pub mod context_menu;
 pub mod divider;
 pub mod draggable_value;
 pub mod inputbox;
 pub mod label;
+pub mod rich_text;
+pub mod list_view;
+pub mod localizer;
+pub mod menu_bar;
+pub mod minimap;
+pub mod modal;
+pub mod perf_overlay;
 pub mod progress_bar;
 pub mod radio;
+pub mod selection;
+pub mod skeleton;
 pub mod slider;
 pub mod styles;
+pub mod table;
+pub mod tabs;
+pub mod text_area;
+pub mod tooltip;
+pub mod viewport;
 pub mod floating_container;
-// pub mod color_picker;
+pub mod color_picker;
+pub mod combo_box;
+#[cfg(feature = "image-loader")]
+pub mod image;
 
 pub mod reactive;
 
@@ -26,10 +46,13 @@ use std::{any::Any, collections::HashMap};
 use indexmap::IndexMap;
 use time::Duration;
 
-use crate::{layout::{Layout, LayoutId}, math::{rect::Rect, vec2::Vec2}, render::painter::Painter, window::input_state::InputState, App};
+use crate::{layout::{HitRegion, Layout, LayoutId}, math::{rect::Rect, vec2::Vec2}, render::painter::Painter, window::{event::Key, input_state::{InputState, Modifiers, MOUSE_ID, MOUSE_UNPRESSED_ID}}, App};
 
 pub const DOUBLE_CLICK_THRESHOLD: Duration = Duration::milliseconds(250);
 
+/// The default time the pointer should stay on a widget before [`SignalGenerator::on_hover_start`] fires.
+pub const DEFAULT_HOVER_DELAY: Duration = Duration::milliseconds(500);
+
 /// Determing when we should call [`Widget::handle_event()`] on the widget.
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub enum EventHandleStrategy {
@@ -41,6 +64,56 @@ pub enum EventHandleStrategy {
 	#[default] OnHover = 2,
 }
 
+/// Where a scroll delta passed to [`Widget::scroll_by()`] comes from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScrollSource {
+	/// The mouse wheel or the trackpad.
+	Wheel,
+	/// A dragging touch or mouse.
+	Drag,
+}
+
+/// A keyboard scroll request passed to [`Widget::scroll_action()`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScrollAction {
+	/// Scroll up by a line, usually from the up arrow key.
+	LineUp,
+	/// Scroll down by a line, usually from the down arrow key.
+	LineDown,
+	/// Scroll left by a line, usually from the left arrow key.
+	LineLeft,
+	/// Scroll right by a line, usually from the right arrow key.
+	LineRight,
+	/// Scroll back by a viewport, usually from the page up key.
+	PageUp,
+	/// Scroll forward by a viewport, usually from the page down key.
+	PageDown,
+	/// Scroll to the start, usually from the home key.
+	Home,
+	/// Scroll to the end, usually from the end key.
+	End,
+}
+
+impl ScrollAction {
+	/// Get the action triggered by the given key, if any.
+	pub fn from_key(key: Key) -> Option<Self> {
+		match key {
+			Key::ArrawUp => Some(Self::LineUp),
+			Key::ArrawDown => Some(Self::LineDown),
+			Key::ArrawLeft => Some(Self::LineLeft),
+			Key::ArrawRight => Some(Self::LineRight),
+			Key::PageUp => Some(Self::PageUp),
+			Key::PageDown => Some(Self::PageDown),
+			Key::Home => Some(Self::Home),
+			Key::End => Some(Self::End),
+			_ => None,
+		}
+	}
+
+	/// All the keys triggering an action.
+	pub const KEYS: [Key; 8] = [Key::ArrawUp, Key::ArrawDown, Key::ArrawLeft, Key::ArrawRight, Key::PageUp, Key::PageDown, Key::Home, Key::End];
+}
+
 /// The main trait for all widgets.
 /// 
 /// You can implement this trait for your own widgets.
@@ -48,6 +121,8 @@ pub enum EventHandleStrategy {
 /// 
 /// The widget will not be dropped until the element is removed from the layout.
 /// Therefore you can safely store any data in the widget.
+// when adding a method, forward it in the wrappers as well, otherwise the wrapped widgets silently get the default:
+// `Reactive` in `widgets/reactive.rs` and `Tracked` in `layout/immediate.rs`.
 pub trait Widget: Any {
 	type Signal: Signal;
 	type Application: App<Signal = Self::Signal>;
@@ -121,11 +196,169 @@ pub trait Widget: Any {
 	fn inner_padding(&self) -> Vec2 {
 		Vec2::ZERO
 	}
+
+	/// Scroll the widget by the given drag delta.
+	/// 
+	/// Scrolling starts from the innermost widget under the wheel or the dragging touch,
+	/// the delta returned is the part not consumed and will be passed to the parent widget.
+	/// Return [`Vec2::ZERO`] to stop passing the delta.
+	/// 
+	/// By default, the widget can not scroll and the whole delta is returned.
+	fn scroll_by(&mut self, delta: Vec2, source: ScrollSource) -> Vec2 {
+		let _ = source;
+		delta
+	}
+
+	/// Scroll the widget in response to the keyboard, return whether it scrolled.
+	/// 
+	/// Keyboard scrolling starts from the widget last pressed on and is passed to the parent widget until one scrolls,
+	/// keys already used by a widget in [`Self::handle_event`] are ignored.
+	/// 
+	/// By default, the widget can not scroll.
+	fn scroll_action(&mut self, action: ScrollAction) -> bool {
+		let _ = action;
+		false
+	}
+
+	/// Scroll the widget with animation so that `area` lies inside of its viewport, see [`Layout::scroll_to_widget`].
+	/// 
+	/// The `area` is relative to the left top corner of the widget, where the children are currently placed.
+	/// Return how far the children will move once the animation ends, the positions of the children decrease by it.
+	/// 
+	/// By default, the widget can not scroll and [`Vec2::ZERO`] is returned.
+	fn scroll_to_reveal(&mut self, area: Rect) -> Vec2 {
+		let _ = area;
+		Vec2::ZERO
+	}
+
+	/// Whether the widget can take the keyboard focus, see [`InputState::is_focused`].
+	/// 
+	/// Focusable widgets are focused when pressed on, and are visited by Tab and Shift+Tab in the order of the layout tree.
+	/// A ring is drawn around the widget while it is focused by the keyboard.
+	/// 
+	/// By default, the widget is not focusable.
+	fn focusable(&self) -> bool {
+		false
+	}
+
+	/// Whether the layout tracks how much the widget is hovered and pressed, given by [`Painter::interaction`] while drawing.
+	/// 
+	/// The factors fade over [`crate::layout::INTERACTION_FADE`] and the widget is redrawn while they change,
+	/// call [`Painter::set_interaction_effect`] to let the gpu brighten and darken the shapes with them.
+	/// 
+	/// By default, the interaction is not tracked.
+	fn tracks_interaction(&self) -> bool {
+		false
+	}
+
+	/// Whether the widget blocks the UI below it while it is the root of an overlay, see [`Layout::push_overlay`].
+	/// 
+	/// Widgets in the main tree and in the overlays pushed before the topmost modal overlay receive no events,
+	/// and the keyboard focus is kept inside of the modal overlay and the overlays pushed after it.
+	/// See [`modal::Modal`] for a dialog built on it.
+	/// 
+	/// By default, the widget is not modal.
+	fn modal(&self) -> bool {
+		false
+	}
+
+	/// The type name of the widget, used to describe the widget in errors and logs, see [`Layout::debug_name`].
+	/// 
+	/// Wrappers around other widgets may return the name of the wrapped widget.
+	fn type_name(&self) -> &'static str {
+		std::any::type_name::<Self>()
+	}
+
+	/// Get the region receiving touches, see [`HitRegion`].
+	/// 
+	/// The area checks of [`InputState`] called with the `area` given to [`Self::handle_event`] will test this region instead.
+	/// 
+	/// By default, the region is the area the widget is drawn in.
+	fn hit_region(&self) -> HitRegion {
+		HitRegion::Area
+	}
+
+	/// Get the z-index of the widget, overridden by [`Layout::set_z_index`].
+	/// 
+	/// Widgets with a larger z-index are drawn above the others of the same tree and receive the touches first,
+	/// overlays are still above the whole layout. A z-index of `0` inherits the one of the parent.
+	/// 
+	/// By default, the z-index is `0`, so the widgets are drawn in the order of the layout tree.
+	fn z_index(&self) -> i32 {
+		0
+	}
+
+	/// Save the runtime state, such as animations and scroll offsets, so that it can be taken over
+	/// when the widget is replaced, see [`Layout::replace_widget`].
+	/// 
+	/// By default, nothing is saved.
+	fn save_state(&self) -> Option<Box<dyn Any>> {
+		None
+	}
+
+	/// Take over the state saved by [`Self::save_state`] of the widget this one replaces.
+	/// 
+	/// Only called with the state of a widget of the same type.
+	fn restore_state(&mut self, state: Box<dyn Any>) {
+		let _ = state;
+	}
+
+	/// Update the widget in fixed time steps, independent of the event and draw frames,
+	/// e.g. to step a physics simulation.
+	/// 
+	/// Called every [`Self::update_interval`] with the interval as `dt`, even when there is no input.
+	/// If event frames run late, it is called several times in a row to catch up, see [`crate::layout::MAX_UPDATE_STEPS`].
+	/// 
+	/// Return `true` if you need to redraw the UI.
+	fn update(&mut self, dt: Duration) -> bool {
+		let _ = dt;
+		false
+	}
+
+	/// Get the interval between the calls of [`Self::update`].
+	/// 
+	/// It is read every event frame, so the tick rate can change at runtime.
+	/// By default, the widget is never updated.
+	fn update_interval(&self) -> Option<Duration> {
+		None
+	}
 }
 
 /// The main trait for all signals.
+/// 
+/// With the `derive` feature, `#[derive(Signal)]` implements this trait and generates helpers for enums:
+/// 
+/// - `is_variant()`, `as_variant()` and `into_variant()` for each variant,
+///   the latter two are only generated for variants with payload.
+/// - A trait named `{Enum}Routing` implemented for [`SignalWrapper`], with `is_variant_of(alias)` and `as_variant_of(alias)`
+///   which also check the alias of the sender.
+/// 
+/// ```
+/// # use nablo_ui::prelude::*;
+/// #[derive(Signal)]
+/// enum Sig {
+///     Clicked,
+///     Volume(f32),
+///     Resized { width: f32, height: f32 },
+/// }
+/// 
+/// fn on_signal(signal: SignalWrapper<Sig>) {
+///     if signal.is_clicked_of("Submit") {
+///         println!("submitted");
+///     }
+///     if let Some(volume) = signal.as_volume_of("Volume") {
+///         println!("volume: {volume}");
+///     }
+///     if let Some((width, height)) = signal.signal.as_resized() {
+///         println!("resized: {width}x{height}");
+///     }
+/// }
+/// ```
 pub trait Signal: Send + Sync + 'static {}
 
+#[cfg(feature = "derive")]
+pub use nablo_ui_macros::Signal;
+
 impl Signal for () {}
 
 impl<T: Signal> Signal for Option<T> {}
@@ -147,11 +380,40 @@ impl<S: Signal, A: App<Signal = S>> dyn Widget<Signal = S, Application = A> {
 }
 
 /// A wrapper for signals.
+/// 
+/// Besides the sender, it carries the input at the time the signal is sent,
+/// so that shift-clicks or position dependent actions can be handled in [`App::on_signal`].
 pub struct SignalWrapper<S: Signal> {
 	/// The wrapped signal.
 	pub signal: S,
 	/// The sender of the signal.
 	pub from: LayoutId,
+	/// The alias of the sender, filled when the signal is delivered.
+	pub alias: Option<String>,
+	/// The running time of the program when the signal is sent, see [`InputState::program_running_time`].
+	pub time: Duration,
+	/// The position of [`Self::touch`] when the signal is sent, or the mouse pointer if there is no such touch.
+	pub pointer: Option<Vec2>,
+	/// The modifiers held when the signal is sent.
+	pub modifiers: Modifiers,
+	/// The touch triggering the signal, mouse buttons are touches too, see [`MOUSE_ID`].
+	/// 
+	/// `None` if the signal is not triggered by a touch, e.g. by hovering or by the keyboard.
+	pub touch: Option<u64>,
+}
+
+impl<S: Signal> SignalWrapper<S> {
+	/// Check if the signal is sent by the widget with given alias.
+	pub fn is_from(&self, alias: &str) -> bool {
+		self.alias.as_deref() == Some(alias)
+	}
+
+	/// Get the mouse button triggering the signal, 0 for the left button, 1 for the right button and 2 for the middle button.
+	/// 
+	/// Returns `None` if the signal is not triggered by a mouse button.
+	pub fn mouse_button(&self) -> Option<u64> {
+		self.touch.filter(|touch| (MOUSE_ID..MOUSE_UNPRESSED_ID).contains(touch)).map(|touch| touch - MOUSE_ID)
+	}
 }
 
 /// Callbacks that can lead to a signal.
@@ -169,10 +431,17 @@ pub struct SignalGenerator<S: Signal, T, A: App<Signal = S>> {
 	pub on_pressed: Option<Box<dyn Fn(&mut A, &mut T) -> S>>,
 	/// The signal to be generated when the widget is released.
 	pub on_released: Option<Box<dyn Fn(&mut A, &mut T) -> S>>,
-	/// The signal to be generated when the widget is hovered.
+	/// The signal to be generated when the pointer enters the widget.
 	pub on_hover: Option<Box<dyn Fn(&mut A, &mut T) -> S>>,
-	/// The signal to be generated when the widget is unhovered.
+	/// The signal to be generated when the pointer leaves the widget.
 	pub on_unhover: Option<Box<dyn Fn(&mut A, &mut T) -> S>>,
+	/// The signal to be generated once the pointer stayed on the widget for [`Self::hover_delay`],
+	/// useful for showing tooltips.
+	pub on_hover_start: Option<Box<dyn Fn(&mut A, &mut T) -> S>>,
+	/// The time the pointer should stay on the widget before [`Self::on_hover_start`] fires.
+	/// 
+	/// By default, it's [`DEFAULT_HOVER_DELAY`].
+	pub hover_delay: Duration,
 	/// The signal to be generated when the widget is dragged.
 	/// 
 	/// Also contains the scroll event,
@@ -185,6 +454,9 @@ pub struct SignalGenerator<S: Signal, T, A: App<Signal = S>> {
 	last_click_time: Option<Duration>,
 	dragging_by: Option<u64>,
 	is_hovering: bool,
+	hovering_since: Option<Duration>,
+	hover_started: bool,
+	hover_pos: Option<Vec2>,
 }
 
 impl<S: Signal, T, A: App<Signal = S>> SignalGenerator<S, T, A> {
@@ -192,6 +464,21 @@ impl<S: Signal, T, A: App<Signal = S>> SignalGenerator<S, T, A> {
 	pub fn is_dragging(&self) -> bool {
 		self.dragging_by.is_some()
 	}
+
+	/// check if the pointer is on the widget.
+	pub fn is_hovering(&self) -> bool {
+		self.is_hovering
+	}
+
+	/// check if the pointer has stayed on the widget for [`Self::hover_delay`].
+	pub fn is_hover_started(&self) -> bool {
+		self.hover_started
+	}
+
+	/// Get the absolute position of the pointer on the widget, `None` if the widget is not hovered.
+	pub fn hover_pos(&self) -> Option<Vec2> {
+		self.hover_pos
+	}
 }
 
 /// Result of the signal generation.
@@ -210,10 +497,15 @@ impl<S: Signal, T, A: App<Signal = S>> Default for SignalGenerator<S, T, A> {
 			on_released: None,
 			on_hover: None,
 			on_unhover: None,
+			on_hover_start: None,
+			hover_delay: DEFAULT_HOVER_DELAY,
 			on_drag: None,
 			on_double_click: None,
 			dragging_by: None,
 			is_hovering: false,
+			hovering_since: None,
+			hover_started: false,
+			hover_pos: None,
 			last_click_time: None,
 		}
 	}
@@ -268,7 +560,7 @@ impl<S: Signal, T, A: App<Signal = S>> SignalGenerator<S, T, A> {
 		}
 	}
 
-	/// Set the signal to be generated when the widget is hovered.
+	/// Set the signal to be generated when the pointer enters the widget.
 	pub fn on_hover(self, signal: impl Fn(&mut A, &mut T) -> S + 'static) -> Self {
 		Self {
 			on_hover: Some(Box::new(signal)),
@@ -284,7 +576,7 @@ impl<S: Signal, T, A: App<Signal = S>> SignalGenerator<S, T, A> {
 		}
 	}
 
-	/// Set the signal to be generated when the widget is unhovered.
+	/// Set the signal to be generated when the pointer leaves the widget.
 	pub fn on_unhover(self, signal: impl Fn(&mut A, &mut T) -> S + 'static) -> Self {
 		Self {
 			on_unhover: Some(Box::new(signal)),
@@ -300,6 +592,30 @@ impl<S: Signal, T, A: App<Signal = S>> SignalGenerator<S, T, A> {
 		}
 	}
 
+	/// Set the signal to be generated once the pointer stayed on the widget for [`Self::hover_delay`].
+	pub fn on_hover_start(self, signal: impl Fn(&mut A, &mut T) -> S + 'static) -> Self {
+		Self {
+			on_hover_start: Some(Box::new(signal)),
+			..self
+		}
+	}
+
+	/// Remove the signal to be generated once the pointer stayed on the widget.
+	pub fn remove_on_hover_start(self) -> Self {
+		Self {
+			on_hover_start: None,
+			..self
+		}
+	}
+
+	/// Set the time the pointer should stay on the widget before [`Self::on_hover_start`] fires.
+	pub fn hover_delay(self, hover_delay: Duration) -> Self {
+		Self {
+			hover_delay,
+			..self
+		}
+	}
+
 	/// Set the signal to be generated when the widget is dragged.
 	pub fn on_drag(self, signal: impl Fn(&mut A, &mut T, Vec2) -> S + 'static) -> Self {
 		Self {
@@ -345,15 +661,22 @@ impl<S: Signal, T, A: App<Signal = S>> SignalGenerator<S, T, A> {
 		force_draggable: bool,
 	) -> SignalGeneratorResult {
 		let touch_positions = input_state.touch_positions();
-		let contains_mouse = touch_positions.into_iter().any(|pos| area.contains(pos));
+		// prefer the mouse pointer, so the position does not jump to a released touch.
+		let hover_pos = input_state.hover_pos().filter(|pos| input_state.hit_test(area, *pos))
+			.or_else(|| touch_positions.into_iter().find(|pos| input_state.hit_test(area, *pos)));
+		let contains_mouse = hover_pos.is_some();
+		let current = input_state.program_running_time();
+		// the touches triggering the signals, taken before the click consumes the released one.
+		let pressed = input_state.get_touch_pressed_on(area).first().copied();
+		let released = input_state.get_touch_released_on(area).first().copied();
 		
 		force_clickable = force_clickable || force_draggable;
 
 		let mut out = false;
 		let mut out_drag_delta = None;
 
-		if input_state.any_touch_pressed_on(area) {
-			self.dragging_by = input_state.get_touch_pressed_on(area).first().cloned();
+		if pressed.is_some() {
+			self.dragging_by = pressed;
 		}else if let Some(touch_id) = self.dragging_by {
 			if input_state.is_touch_released(touch_id) {
 				self.dragging_by = None;
@@ -361,18 +684,36 @@ impl<S: Signal, T, A: App<Signal = S>> SignalGenerator<S, T, A> {
 		}
 
 		if !contains_mouse && self.is_hovering {
-			self.is_hovering = false;
+			self.hovering_since = None;
+			self.hover_started = false;
 			if let Some(signal) = &self.on_unhover {
-				input_state.send_signal_from(from, signal(app, style));
+				input_state.send_signal_triggered(from, None, signal(app, style));
+			}
+		}else if contains_mouse && !self.is_hovering {
+			self.hovering_since = Some(current);
+			if let Some(signal) = &self.on_hover {
+				input_state.send_signal_triggered(from, None, signal(app, style));
 			}
 		}
 
 		self.is_hovering = contains_mouse;
+		self.hover_pos = hover_pos;
+
+		if let Some(since) = self.hovering_since {
+			if !self.hover_started && current - since >= self.hover_delay {
+				self.hover_started = true;
+				if let Some(signal) = &self.on_hover_start {
+					input_state.send_signal_triggered(from, None, signal(app, style));
+				}
+			}else if !self.hover_started && self.on_hover_start.is_some() {
+				// keep the event frames running while the pointer rests on the widget.
+				input_state.request_redraw();
+			}
+		}
 
 		if let Some(signal) = &self.on_click {
 			if input_state.is_clicked(from, area) {
 				out = true;
-				let current = input_state.program_running_time();
 				if if let Some(last_click_time) = self.last_click_time {
 					// println!("{}", current - last_click_time);
 					current - last_click_time < DOUBLE_CLICK_THRESHOLD 
@@ -380,12 +721,12 @@ impl<S: Signal, T, A: App<Signal = S>> SignalGenerator<S, T, A> {
 					false
 				} {
 					if let Some(signal) = &self.on_double_click {
-						input_state.send_signal_from(from, signal(app, style));
+						input_state.send_signal_triggered(from, released, signal(app, style));
 					}else {
-						input_state.send_signal_from(from, signal(app, style));	
+						input_state.send_signal_triggered(from, released, signal(app, style));	
 					}
 				}else {
-					input_state.send_signal_from(from, signal(app, style));
+					input_state.send_signal_triggered(from, released, signal(app, style));
 				}
 				self.last_click_time = Some(current);
 			}
@@ -399,27 +740,21 @@ impl<S: Signal, T, A: App<Signal = S>> SignalGenerator<S, T, A> {
 		}
 
 		if let Some(signal) = &self.on_pressed {
-			if input_state.any_touch_pressed_on(area) {
-				input_state.send_signal_from(from, signal(app, style));
+			if pressed.is_some() {
+				input_state.send_signal_triggered(from, pressed, signal(app, style));
 			}
 		}
 
 		if let Some(signal) = &self.on_released {
-			if input_state.any_touch_released_on(area) {
-				input_state.send_signal_from(from, signal(app, style));
-			}
-		}
-
-		if let Some(signal) = &self.on_hover {
-			if input_state.is_any_touch_pressing() && contains_mouse {
-				input_state.send_signal_from(from, signal(app, style));
+			if released.is_some() {
+				input_state.send_signal_triggered(from, released, signal(app, style));
 			}
 		}
 
 		if let Some(signal) = &self.on_drag {
 			if let Some(id) = &self.dragging_by {
 				let drag_delta = input_state.drag_delta(*id);
-				input_state.send_signal_from(from, signal(app, style, drag_delta));
+				input_state.send_signal_triggered(from, Some(*id), signal(app, style, drag_delta));
 				out_drag_delta = Some(drag_delta + input_state.wheel_delta_consume());
 			}else if input_state.wheel_delta() != Vec2::ZERO {
 				out_drag_delta = Some(input_state.wheel_delta_consume());
@@ -440,8 +775,66 @@ impl<S: Signal, T, A: App<Signal = S>> SignalGenerator<S, T, A> {
 		}
 	}
 
+	/// Send the click signal as if the widget was clicked, e.g. when it is activated by the keyboard.
+	pub fn click(&mut self, app: &mut A, style: &mut T, input_state: &mut InputState<S>, from: LayoutId) {
+		self.last_click_time = None;
+		if let Some(signal) = &self.on_click {
+			input_state.send_signal_triggered(from, None, signal(app, style));
+		}
+	}
+
+	/// Send the drag signal with the delta as if the widget was dragged, e.g. when it is moved by the keyboard.
+	pub fn drag(&self, app: &mut A, style: &mut T, input_state: &mut InputState<S>, from: LayoutId, delta: Vec2) {
+		if let Some(signal) = &self.on_drag {
+			input_state.send_signal_triggered(from, None, signal(app, style, delta));
+		}
+	}
+
 	/// Get the touch id that is dragging the widget.
 	pub fn dragging_by(&self) -> Option<u64> {
 		self.dragging_by
 	}
-}
\ No newline at end of file
+}
+
+mod test {
+	#[test]
+	#[cfg(feature = "serde")]
+	fn test_config_round_trip() {
+		use crate::prelude::*;
+		use crate::widgets::{button::ButtonInner, card::CardInner, inputbox::InputBoxInner, radio::RadioInner};
+
+		let card = CardInner {
+			layout_strategy: LayoutStrategy { typesetting: Typesetting::Horizontal, alignment: [Alignment::Center; 2], ..Default::default() },
+			scroll: Scroll::vertical(),
+			overscroll: OverscrollPolicy::Contain,
+			border: Some((FillMode::Color(PRIMARY_COLOR), 2.0)),
+			..Default::default()
+		};
+		let text = ron::to_string(&card).unwrap();
+		assert_eq!(ron::from_str::<CardInner>(&text).unwrap(), card);
+
+		let button = ButtonInner {
+			label: "Save".to_string(),
+			style: ButtonStyle::Secondary,
+			state_style: Some(StateStyle::text()),
+			..Default::default()
+		};
+		let text = ron::to_string(&button).unwrap();
+		assert_eq!(ron::from_str::<ButtonInner>(&text).unwrap(), button);
+
+		let radio = RadioInner { text: "Remember me".to_string(), selected: true, ..Default::default() };
+		let text = ron::to_string(&radio).unwrap();
+		assert_eq!(ron::from_str::<RadioInner>(&text).unwrap(), radio);
+
+		let input = InputBoxInner { placeholder: "Name".to_string(), caret_style: CaretStyle::Block, ..Default::default() };
+		let text = ron::to_string(&input).unwrap();
+		let parsed = ron::from_str::<InputBoxInner>(&text).unwrap();
+		assert_eq!(parsed.placeholder, input.placeholder);
+		assert_eq!(parsed.caret_style, input.caret_style);
+		assert_eq!(parsed.border_color.target(), input.border_color.target());
+
+		// the missing fields are filled with the defaults.
+		let partial = ron::from_str::<ButtonInner>("(label: \"Ok\")").unwrap();
+		assert_eq!(partial, ButtonInner { label: "Ok".to_string(), ..Default::default() });
+	}
+}
@@ -0,0 +1,251 @@
+//! A modal dialog dimming and blocking the UI behind it.
+
+use std::collections::HashMap;
+
+use indexmap::IndexMap;
+
+use crate::{layout::{Layout, LayoutId, OverlayAnchor}, prelude::{FillMode, InputState, Key, Painter, Rect, Vec2, Vec4}, App};
+
+use super::{styles::{BACKDROP_COLOR, CARD_BORDER_COLOR, CARD_COLOR, DEFAULT_PADDING, DEFAULT_ROUNDING}, EventHandleStrategy, Signal, SignalGenerator, Widget};
+
+/// A dialog shown above everything else, see [`Modal::open`].
+///
+/// The modal covers the window with a backdrop dimming the UI behind it, and its children are stacked vertically
+/// in a dialog box at the center of the window. While it is open, the widgets behind it receive no events
+/// and the keyboard focus stays inside of it, see [`Widget::modal`].
+///
+/// Pressing the backdrop or the escape key dismisses the modal, which closes it and sends [`Modal::on_dismiss`].
+///
+/// ```no_run
+/// # use nablo_ui::prelude::*;
+/// enum Sig {
+///     Discard,
+///     Cancel,
+/// }
+/// # impl Signal for Sig {}
+/// # struct MyApp;
+/// # impl App for MyApp {
+/// #     type Signal = Sig;
+/// #     fn on_start(&mut self, _: &mut Context<Sig, Self>) {}
+/// #     fn on_signal(&mut self, _: &mut Context<Sig, Self>, _: SignalWrapper<Sig>) {}
+/// # }
+/// fn ask(layout: &mut Layout<Sig, MyApp>) {
+///     let modal = Modal::new().on_dismiss(|_, _| Sig::Cancel).open(layout);
+///     layout.add_widget(modal, Label::new("Discard the changes?"));
+///     layout.add_widget(modal, Button::new("Discard").on_click(|_, _| Sig::Discard));
+/// }
+/// ```
+pub struct Modal<S: Signal, A: App<Signal = S>> {
+	/// The inner properties of the modal.
+	pub inner: ModalInner,
+	/// The signals generated by the modal.
+	pub signals: SignalGenerator<S, ModalInner, A>,
+	/// The signal to be generated when the modal is dismissed by the backdrop or the escape key.
+	#[allow(clippy::type_complexity)]
+	pub on_dismiss: Option<Box<dyn Fn(&mut A, &mut ModalInner) -> S>>,
+	/// the dialog box relative to the modal, as of the last layout.
+	dialog: Rect,
+	/// the touch pressed on the backdrop.
+	backdrop_press: Option<u64>,
+	dismissed: bool,
+}
+
+/// The inner properties of a modal.
+pub struct ModalInner {
+	/// The color of the backdrop covering the window.
+	pub backdrop_color: FillMode,
+	/// The background color of the dialog box.
+	pub background_color: FillMode,
+	/// The border color of the dialog box.
+	pub border_color: FillMode,
+	/// The roundings of the dialog box.
+	pub roundings: Vec4,
+	/// The padding between the dialog box and the children.
+	pub padding: Vec2,
+	/// The space between the children.
+	pub spacing: f32,
+	/// Whether pressing the backdrop dismisses the modal.
+	pub dismiss_on_backdrop: bool,
+	/// Whether the escape key dismisses the modal.
+	pub dismiss_on_escape: bool,
+}
+
+impl Default for ModalInner {
+	fn default() -> Self {
+		Self {
+			backdrop_color: FillMode::from(BACKDROP_COLOR),
+			background_color: FillMode::from(CARD_COLOR),
+			border_color: FillMode::from(CARD_BORDER_COLOR),
+			roundings: Vec4::same(DEFAULT_ROUNDING),
+			padding: Vec2::same(DEFAULT_PADDING * 2.0),
+			spacing: DEFAULT_PADDING,
+			dismiss_on_backdrop: true,
+			dismiss_on_escape: true,
+		}
+	}
+}
+
+impl<S: Signal, A: App<Signal = S>> Default for Modal<S, A> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<S: Signal, A: App<Signal = S>> Modal<S, A> {
+	/// Create an empty modal.
+	pub fn new() -> Self {
+		Self {
+			inner: ModalInner::default(),
+			signals: SignalGenerator::default(),
+			on_dismiss: None,
+			dialog: Rect::ZERO,
+			backdrop_press: None,
+			dismissed: false,
+		}
+	}
+
+	/// Push the modal as an overlay covering the window, returns its id to add the children to.
+	///
+	/// Widgets can open a modal while handling events by pushing it with [`InputState::push_overlay`]
+	/// at [`OverlayAnchor::Screen`] of [`Vec2::ZERO`]. Remove the overlay to close the modal without dismissing it.
+	pub fn open(self, layout: &mut Layout<S, A>) -> LayoutId {
+		layout.push_overlay(OverlayAnchor::Screen(Vec2::ZERO), self)
+	}
+
+	/// Set the color of the backdrop covering the window.
+	pub fn backdrop_color(self, backdrop_color: impl Into<FillMode>) -> Self {
+		Self { inner: ModalInner { backdrop_color: backdrop_color.into(), ..self.inner }, ..self }
+	}
+
+	/// Set the background color of the dialog box.
+	pub fn background_color(self, background_color: impl Into<FillMode>) -> Self {
+		Self { inner: ModalInner { background_color: background_color.into(), ..self.inner }, ..self }
+	}
+
+	/// Set the border color of the dialog box.
+	pub fn border_color(self, border_color: impl Into<FillMode>) -> Self {
+		Self { inner: ModalInner { border_color: border_color.into(), ..self.inner }, ..self }
+	}
+
+	/// Set the roundings of the dialog box.
+	pub fn roundings(self, roundings: impl Into<Vec4>) -> Self {
+		Self { inner: ModalInner { roundings: roundings.into(), ..self.inner }, ..self }
+	}
+
+	/// Set the padding between the dialog box and the children.
+	pub fn padding(self, padding: impl Into<Vec2>) -> Self {
+		Self { inner: ModalInner { padding: padding.into(), ..self.inner }, ..self }
+	}
+
+	/// Set the space between the children.
+	pub fn spacing(self, spacing: f32) -> Self {
+		Self { inner: ModalInner { spacing, ..self.inner }, ..self }
+	}
+
+	/// Set whether pressing the backdrop dismisses the modal.
+	pub fn dismiss_on_backdrop(self, dismiss_on_backdrop: bool) -> Self {
+		Self { inner: ModalInner { dismiss_on_backdrop, ..self.inner }, ..self }
+	}
+
+	/// Set whether the escape key dismisses the modal.
+	pub fn dismiss_on_escape(self, dismiss_on_escape: bool) -> Self {
+		Self { inner: ModalInner { dismiss_on_escape, ..self.inner }, ..self }
+	}
+
+	/// Sets the signal to be generated when the modal is dismissed.
+	pub fn on_dismiss(self, on_dismiss: impl Fn(&mut A, &mut ModalInner) -> S + 'static) -> Self {
+		Self {
+			on_dismiss: Some(Box::new(on_dismiss)),
+			..self
+		}
+	}
+
+	fn dismiss(&mut self, app: &mut A, input_state: &mut InputState<S>, id: LayoutId) {
+		self.dismissed = true;
+		if let Some(on_dismiss) = &self.on_dismiss {
+			let signal = on_dismiss(app, &mut self.inner);
+			input_state.send_signal_from(id, signal);
+		}
+		input_state.remove_overlay(id);
+	}
+}
+
+impl<S: Signal, A: App<Signal = S>> Widget for Modal<S, A> {
+	type Signal = S;
+	type Application = A;
+
+	fn size(&self, _: LayoutId, painter: &Painter, _: &Layout<Self::Signal, Self::Application>) -> Vec2 {
+		painter.window_size
+	}
+
+	fn draw(&mut self, painter: &mut Painter, size: Vec2) {
+		painter.set_fill_mode(self.inner.backdrop_color.clone());
+		painter.draw_rect(Rect::from_size(size), Vec4::ZERO);
+
+		let stroke = 1.0;
+		painter.set_fill_mode(self.inner.background_color.clone());
+		painter.draw_rect(self.dialog, self.inner.roundings);
+		painter.set_fill_mode(self.inner.border_color.clone());
+		painter.draw_stroked_rect(self.dialog.shrink(Vec2::same(stroke / 2.0)), self.inner.roundings, stroke);
+	}
+
+	fn handle_event(&mut self, app: &mut A, input_state: &mut InputState<Self::Signal>, id: LayoutId, area: Rect, pos: Vec2) -> bool {
+		if self.dismissed {
+			return false;
+		}
+		// taken before the signals, which mark the touches as used.
+		let pressed = input_state.get_touch_pressed_on(area);
+		self.signals.generate_signals(app, &mut self.inner, input_state, id, area, false, false);
+
+		if self.inner.dismiss_on_escape && input_state.is_key_pressed(Key::Escape) {
+			self.dismiss(app, input_state, id);
+			return false;
+		}
+
+		if !self.inner.dismiss_on_backdrop {
+			self.backdrop_press = None;
+			return false;
+		}
+		let dialog = self.dialog.move_by(pos);
+		let outside = |input_state: &InputState<S>, touch: u64| input_state.get_touch_pos(touch).is_some_and(|touch_pos| !dialog.contains(touch_pos));
+		if self.backdrop_press.is_none() {
+			self.backdrop_press = pressed.into_iter().find(|touch| !input_state.is_touch_released(*touch) && outside(input_state, *touch));
+		}
+		if let Some(touch) = self.backdrop_press {
+			if input_state.is_touch_released(touch) {
+				self.backdrop_press = None;
+				// dragging from the backdrop into the dialog does not dismiss, e.g. when selecting text.
+				if outside(input_state, touch) {
+					self.dismiss(app, input_state, id);
+				}
+			}
+		}
+		false
+	}
+
+	fn handle_child_layout(&mut self, childs: IndexMap<LayoutId, Vec2>, area: Rect, _: LayoutId) -> HashMap<LayoutId, Option<Rect>> {
+		let spacing = self.inner.spacing;
+		let content = Vec2::new(
+			childs.values().map(|size| size.x).fold(0.0, f32::max),
+			childs.values().map(|size| size.y).sum::<f32>() + spacing * childs.len().saturating_sub(1) as f32,
+		);
+		let dialog_size = content + self.inner.padding * 2.0;
+		self.dialog = Rect::from_lt_size(((area.size() - dialog_size) / 2.0).max(Vec2::ZERO), dialog_size);
+
+		let mut top = self.dialog.lt().y + self.inner.padding.y;
+		childs.into_iter().map(|(child, size)| {
+			let lt = Vec2::new(self.dialog.lt().x + self.inner.padding.x + (content.x - size.x) / 2.0, top);
+			top += size.y + spacing;
+			(child, Some(Rect::from_lt_size(lt, size)))
+		}).collect()
+	}
+
+	fn event_handle_strategy(&self) -> EventHandleStrategy {
+		// sees the presses on the backdrop and the keys.
+		EventHandleStrategy::AlwaysPrimary
+	}
+
+	fn modal(&self) -> bool {
+		true
+	}
+}
@@ -0,0 +1,215 @@
+//! A widget showing the frame rate, frame times, widget count and texture memory.
+
+use std::marker::PhantomData;
+
+use crate::{layout::{Layout, LayoutId}, prelude::{Color, FillMode, FontId, FontRole, FrameStats, InputState, Painter, Rect, Vec2, Vec4}, App};
+
+use super::{styles::{CARD_COLOR, CONTENT_TEXT_SIZE, DEFAULT_PADDING, DEFAULT_ROUNDING, ERROR_COLOR, SECONDARY_TEXT_COLOR, SUCCESS_COLOR, WARNING_COLOR}, EventHandleStrategy, Signal, Widget};
+
+/// How many frames the graph shows at most.
+const FRAME_BARS: usize = 60;
+
+/// A widget showing the frame rate, a graph of recent frame times, frame time percentiles, widget count and texture memory.
+/// 
+/// The overlay updates itself from [`InputState::frame_stats()`] every event frame,
+/// and keeps requesting redraws while it is in the layout.
+pub struct PerfOverlay<S: Signal, A: App<Signal = S>> {
+	/// The inner properties of the overlay.
+	pub inner: PerfOverlayInner,
+	stats: FrameStats,
+	fps: f32,
+	_marker: PhantomData<(S, A)>,
+}
+
+/// The inner properties of the [`PerfOverlay`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct PerfOverlayInner {
+	/// The width of the overlay.
+	pub width: f32,
+	/// The height of the frame time graph.
+	pub graph_height: f32,
+	/// The frame rate considered smooth, frames slower than this are drawn in warning color, twice slower in error color.
+	pub target_fps: f32,
+	/// The font id of the text.
+	pub font: FontId,
+	/// The font role of the text, see [`FontRole`].
+	pub font_role: Option<FontRole>,
+	/// The font size of the text.
+	pub font_size: f32,
+	/// The background color of the overlay.
+	pub background_color: FillMode,
+	/// The color of the text.
+	pub text_color: FillMode,
+	/// The padding of the overlay.
+	pub padding: f32,
+}
+
+impl Default for PerfOverlayInner {
+	fn default() -> Self {
+		Self {
+			width: 240.0,
+			graph_height: 48.0,
+			target_fps: 60.0,
+			font: 0,
+			font_role: Some(FontRole::Monospace),
+			font_size: CONTENT_TEXT_SIZE * 0.75,
+			background_color: CARD_COLOR.into(),
+			text_color: SECONDARY_TEXT_COLOR.into(),
+			padding: DEFAULT_PADDING,
+		}
+	}
+}
+
+impl<S: Signal, A: App<Signal = S>> Default for PerfOverlay<S, A> {
+	fn default() -> Self {
+		Self {
+			inner: PerfOverlayInner::default(),
+			stats: FrameStats::default(),
+			fps: 0.0,
+			_marker: PhantomData,
+		}
+	}
+}
+
+impl<S: Signal, A: App<Signal = S>> PerfOverlay<S, A> {
+	/// Creates a new overlay with default values.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Sets the width of the overlay.
+	pub fn width(self, width: f32) -> Self {
+		Self { inner: PerfOverlayInner { width, ..self.inner }, ..self }
+	}
+
+	/// Sets the height of the frame time graph.
+	pub fn graph_height(self, graph_height: f32) -> Self {
+		Self { inner: PerfOverlayInner { graph_height, ..self.inner }, ..self }
+	}
+
+	/// Sets the frame rate considered smooth.
+	pub fn target_fps(self, target_fps: f32) -> Self {
+		Self { inner: PerfOverlayInner { target_fps, ..self.inner }, ..self }
+	}
+
+	/// Sets the font of the text.
+	/// 
+	/// Clears the font role, so the font is used even if the theme registers one, see [`Self::font_role`].
+	pub fn font(self, font: FontId) -> Self {
+		Self { inner: PerfOverlayInner { font, font_role: None, ..self.inner }, ..self }
+	}
+
+	/// Sets the font role of the text, `None` to always use [`PerfOverlayInner::font`].
+	pub fn font_role(self, font_role: Option<FontRole>) -> Self {
+		Self { inner: PerfOverlayInner { font_role, ..self.inner }, ..self }
+	}
+
+	/// Sets the font size of the text.
+	pub fn font_size(self, font_size: f32) -> Self {
+		Self { inner: PerfOverlayInner { font_size, ..self.inner }, ..self }
+	}
+
+	/// Sets the background color of the overlay.
+	pub fn background_color(self, background_color: impl Into<FillMode>) -> Self {
+		Self { inner: PerfOverlayInner { background_color: background_color.into(), ..self.inner }, ..self }
+	}
+
+	/// Sets the color of the text.
+	pub fn text_color(self, text_color: impl Into<FillMode>) -> Self {
+		Self { inner: PerfOverlayInner { text_color: text_color.into(), ..self.inner }, ..self }
+	}
+
+	/// Sets the padding of the overlay.
+	pub fn padding(self, padding: f32) -> Self {
+		Self { inner: PerfOverlayInner { padding, ..self.inner }, ..self }
+	}
+
+	fn text(&self) -> String {
+		format!(
+			"FPS {:.1}  p50 {:.1}ms  p99 {:.1}ms\nwidgets {}  textures {}",
+			self.fps,
+			self.stats.frame_time_percentile(0.5) * 1000.0,
+			self.stats.frame_time_percentile(0.99) * 1000.0,
+			self.stats.widgets,
+			format_bytes(self.stats.texture_memory),
+		)
+	}
+
+	fn text_height(&self, painter: &Painter) -> f32 {
+		let font = painter.resolve_font(self.inner.font_role, self.inner.font);
+		painter.line_height(font, self.inner.font_size).unwrap_or(self.inner.font_size) * 2.0
+	}
+}
+
+fn format_bytes(bytes: usize) -> String {
+	const KB: f32 = 1024.0;
+	let bytes = bytes as f32;
+	if bytes < KB {
+		format!("{} B", bytes)
+	}else if bytes < KB * KB {
+		format!("{:.1} KB", bytes / KB)
+	}else {
+		format!("{:.1} MB", bytes / KB / KB)
+	}
+}
+
+impl<S: Signal, A: App<Signal = S>> Widget for PerfOverlay<S, A> {
+	type Signal = S;
+	type Application = A;
+
+	fn handle_event(&mut self, _: &mut A, input_state: &mut InputState<Self::Signal>, _: LayoutId, _: Rect, _: Vec2) -> bool {
+		self.stats = input_state.frame_stats().clone();
+		self.fps = input_state.fps();
+		true
+	}
+
+	fn draw(&mut self, painter: &mut Painter, size: Vec2) {
+		let padding = self.inner.padding;
+		painter.set_fill_mode(self.inner.background_color.clone());
+		painter.draw_rect(Rect::from_size(size), Vec4::same(DEFAULT_ROUNDING));
+
+		painter.set_fill_mode(self.inner.text_color.clone());
+		let font = painter.resolve_font(self.inner.font_role, self.inner.font);
+		painter.draw_text(Vec2::same(padding), font, self.inner.font_size, self.text());
+
+		let graph = Rect::from_lt_size(
+			Vec2::new(padding, padding * 2.0 + self.text_height(painter)),
+			Vec2::new(size.x - padding * 2.0, self.inner.graph_height),
+		);
+		let target = 1.0 / self.inner.target_fps.max(1.0);
+		// the graph shows up to twice the target frame time, with the target marked in the middle.
+		let scale = graph.height() / (target * 2.0);
+		let bar_width = graph.width() / FRAME_BARS as f32;
+		let frame_times = self.stats.frame_times().collect::<Vec<_>>();
+		let skip = frame_times.len().saturating_sub(FRAME_BARS);
+		for (i, frame_time) in frame_times[skip..].iter().enumerate() {
+			let color: Color = if *frame_time <= target {
+				SUCCESS_COLOR
+			}else if *frame_time <= target * 2.0 {
+				WARNING_COLOR
+			}else {
+				ERROR_COLOR
+			};
+			let height = (frame_time * scale).min(graph.height());
+			painter.set_fill_mode(color);
+			painter.draw_rect(Rect::from_lt_size(
+				Vec2::new(graph.lt().x + i as f32 * bar_width, graph.rb().y - height),
+				Vec2::new((bar_width - 1.0).max(1.0), height),
+			), Vec4::ZERO);
+		}
+		painter.set_fill_mode(self.inner.text_color.clone());
+		let target_y = graph.rb().y - target * scale;
+		painter.draw_line(Vec2::new(graph.lt().x, target_y), Vec2::new(graph.rb().x, target_y), 1.0);
+	}
+
+	fn size(&self, _: LayoutId, painter: &Painter, _: &Layout<Self::Signal, A>) -> Vec2 {
+		Vec2::new(
+			self.inner.width,
+			self.text_height(painter) + self.inner.graph_height + self.inner.padding * 3.0,
+		)
+	}
+
+	fn event_handle_strategy(&self) -> EventHandleStrategy {
+		EventHandleStrategy::AlwaysPrimary
+	}
+}
@@ -4,15 +4,36 @@ pub use crate::widgets::card::*;
 pub use crate::widgets::*;
 pub use crate::widgets::styles::*;
 pub use crate::widgets::button::*;
+pub use crate::widgets::animated_image::*;
 pub use crate::widgets::label::*;
+pub use crate::widgets::rich_text::*;
+pub use crate::widgets::list_view::*;
+pub use crate::widgets::localizer::*;
+pub use crate::widgets::modal::*;
 pub use crate::widgets::canvas::*;
 pub use crate::widgets::collapse::*;
+pub use crate::widgets::composite::*;
+pub use crate::widgets::context_menu::*;
+pub use crate::widgets::menu_bar::*;
+pub use crate::widgets::minimap::*;
 pub use crate::widgets::divider::*;
 pub use crate::widgets::reactive::*;
 pub use crate::widgets::inputbox::*;
+pub use crate::widgets::text_area::*;
+pub use crate::widgets::tooltip::*;
+pub use crate::widgets::viewport::*;
 pub use crate::widgets::radio::*;
+pub use crate::widgets::selection::*;
+pub use crate::widgets::skeleton::*;
 pub use crate::widgets::slider::*;
+pub use crate::widgets::table::*;
+pub use crate::widgets::tabs::*;
+pub use crate::widgets::color_picker::*;
+pub use crate::widgets::combo_box::*;
+#[cfg(feature = "image-loader")]
+pub use crate::widgets::image::*;
 pub use crate::widgets::draggable_value::*;
+pub use crate::widgets::perf_overlay::*;
 pub use crate::widgets::progress_bar::*;
 pub use crate::widgets::floating_container::*;
 
@@ -56,7 +77,7 @@ macro_rules! deligate_signal_generator {
 					self
 				}
 
-				/// Add a hover signal to the widget.
+				/// Add a signal sent when the pointer enters the widget.
 				pub fn on_hover(mut self, signal: impl Fn(&mut A, &mut $style) -> S + 'static) -> Self {
 					self.signals = self.signals.on_hover(signal);
 					self
@@ -68,7 +89,7 @@ macro_rules! deligate_signal_generator {
 					self
 				}
 
-				/// Add an unhover signal to the widget.
+				/// Add a signal sent when the pointer leaves the widget.
 				pub fn on_unhover(mut self, signal: impl Fn(&mut A, &mut $style) -> S + 'static) -> Self {
 					self.signals = self.signals.on_unhover(signal);
 					self
@@ -80,6 +101,24 @@ macro_rules! deligate_signal_generator {
 					self
 				}
 
+				/// Add a signal sent once the pointer stayed on the widget for a while, see [`SignalGenerator::hover_delay`].
+				pub fn on_hover_start(mut self, signal: impl Fn(&mut A, &mut $style) -> S + 'static) -> Self {
+					self.signals = self.signals.on_hover_start(signal);
+					self
+				}
+
+				/// Remove the hover start signal from the widget.
+				pub fn remove_on_hover_start(mut self) -> Self {
+					self.signals = self.signals.remove_on_hover_start();
+					self
+				}
+
+				/// Set the time the pointer should stay on the widget before the hover start signal.
+				pub fn hover_delay(mut self, hover_delay: time::Duration) -> Self {
+					self.signals = self.signals.hover_delay(hover_delay);
+					self
+				}
+
 				/// Add a drag signal to the widget.
 				pub fn on_drag(mut self, signal: impl Fn(&mut A, &mut $style, Vec2) -> S + 'static) -> Self {
 					self.signals = self.signals.on_drag(signal);
@@ -110,15 +149,29 @@ macro_rules! deligate_signal_generator {
 
 deligate_signal_generator!{ 
 	Label<S, A>, LabelInner,
+	ListView<S, A>, ListViewInner,
+	Modal<S, A>, ModalInner,
+	ColorPicker<S, A>, ColorPickerInner,
+	ComboBox<S, A>, ComboBoxInner,
+	Tabs<S, A>, TabsInner,
 	Canvas<S, A>, CanvasInner,
 	Button<S, A>, ButtonInner,
 	Divider<S, A>, DividerInner,
 	Card<S, A>, CardInner,
 	Collapse<S, A>, CollapseInner,
 	InputBox<S, A>, InputBoxInner,
+	TextArea<S, A>, TextAreaInner,
 	Radio<S, A>, RadioInner,
 	Slider<S, A>, SliderInner,
+	Table<S, A>, TableInner,
 	DraggableValue<S, A>, DraggableValueInner,
 	ProgressBar<S, A>, ProgressBarInner,
 	FloatingContainer<S, A>, FloatingContainerInner,
-}
\ No newline at end of file
+	WgpuViewport<S, A>, WgpuViewportInner,
+	Skeleton<S, A>, SkeletonInner,
+	AnimatedImage<S, A>, AnimatedImageInner,
+}
+#[cfg(feature = "image-loader")]
+deligate_signal_generator!{ 
+	Image<S, A>, ImageInner,
+}
@@ -1,5 +1,7 @@
 //! A simple progress bar widget for Nablo.
 
+use std::any::Any;
+
 use crate::{layout::{Layout, LayoutId}, prelude::{Animatedf32, FillMode, InputState, Painter, Rect, Vec2, Vec4}, App};
 
 use super::{styles::{CONTENT_TEXT_SIZE, DEFAULT_ROUNDING, INPUT_BACKGROUND_COLOR, PRIMARY_COLOR}, Signal, SignalGenerator, Widget};
@@ -168,4 +170,14 @@ impl<S: Signal, A: App<Signal = S>> Widget for ProgressBar<S, A> {
 		painter.set_fill_mode(self.inner.foreground_color.clone());
 		painter.draw_rect(Rect::from_size(Vec2::new(size.x * progress, size.y)), self.inner.roundings);
 	}
+
+	fn save_state(&self) -> Option<Box<dyn Any>> {
+		Some(Box::new(self.inner.progress.clone()))
+	}
+
+	fn restore_state(&mut self, state: Box<dyn Any>) {
+		if let Ok(progress) = state.downcast::<Animatedf32>() {
+			self.inner.progress.continue_from(&progress);
+		}
+	}
 }
\ No newline at end of file
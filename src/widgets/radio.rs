@@ -1,6 +1,8 @@
 //! A radio button widget for Nablo.
 
-use crate::{layout::{Layout, LayoutId}, prelude::{Animatedf32, Color, FillMode, FontId, InputState, Painter, Rect, Vec2, Vec4}, App};
+use std::any::Any;
+
+use crate::{layout::{Layout, LayoutId}, prelude::{Animatedf32, Color, FillMode, FontId, FontRole, InputState, Painter, Rect, Vec2, Vec4}, window::event::Key, App};
 
 use super::{styles::{BRIGHT_FACTOR, CARD_BORDER_COLOR, CONTENT_TEXT_SIZE, DEFAULT_ROUNDING, PRIMARY_COLOR, PRIMARY_TEXT_COLOR, SECONDARY_TEXT_COLOR}, Signal, SignalGenerator, Widget};
 
@@ -31,6 +33,8 @@ impl<S: Signal, A: App<Signal = S>> Default for Radio<S, A> {
 
 /// The inner properties of a radio button.
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct RadioInner {
 	/// The text of the radio button.
 	pub text: String,
@@ -40,6 +44,8 @@ pub struct RadioInner {
 	pub selected: bool,
 	/// The font of the radio button.
 	pub font: FontId,
+	/// The font role of the radio button, see [`FontRole`].
+	pub font_role: Option<FontRole>,
 	/// The font size of the radio button.
 	pub font_size: f32,
 	/// The padding of the radio button.
@@ -55,6 +61,7 @@ impl Default for RadioInner {
 			style: RadioStyle::default(),
 			selected: false,
 			font: 0,
+			font_role: Some(FontRole::Body),
 			font_size: CONTENT_TEXT_SIZE,
 			padding: Vec2::same(DEFAULT_ROUNDING),
 			text_color: FillMode::Color(SECONDARY_TEXT_COLOR),
@@ -64,6 +71,7 @@ impl Default for RadioInner {
 
 /// The style of a radio button.
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum RadioStyle {
 	/// Classic style of radio button, with a circle and a check mark.
 	/// 
@@ -172,10 +180,24 @@ impl<S: Signal, A: App<Signal = S>> Radio<S, A> {
 	}
 
 	/// Set the font of the radio button.
+	/// 
+	/// Clears the font role, so the font is used even if the theme registers one, see [`Self::font_role`].
 	pub fn font(self, font: FontId) -> Self {
 		Self {
 			inner: RadioInner {
 				font,
+				font_role: None,
+				..self.inner
+			},
+			..self
+		}
+	}
+
+	/// Sets the font role of the radio button, `None` to always use the font id.
+	pub fn font_role(self, font_role: Option<FontRole>) -> Self {
+		Self {
+			inner: RadioInner {
+				font_role,
 				..self.inner
 			},
 			..self
@@ -302,7 +324,7 @@ impl<S: Signal, A: App<Signal = S>> Widget for Radio<S, A> {
 					1.5
 				);
 				let text_pos = Vec2::new(self.inner.font_size + self.inner.padding.x, 0.0);
-				painter.draw_text(text_pos, self.inner.font, self.inner.font_size, &self.inner.text);
+				painter.draw_text(text_pos, painter.resolve_font(self.inner.font_role, self.inner.font), self.inner.font_size, &self.inner.text);
 				let mut color = color.clone();
 				color.brighter(bright_factor);
 				color.mul_alpha(self.clicked_factor.value());
@@ -317,7 +339,7 @@ impl<S: Signal, A: App<Signal = S>> Widget for Radio<S, A> {
 					1.5
 				);
 				let text_pos = Vec2::new(self.inner.font_size + self.inner.padding.x, 0.0);
-				painter.draw_text(text_pos, self.inner.font, self.inner.font_size, &self.inner.text);
+				painter.draw_text(text_pos, painter.resolve_font(self.inner.font_role, self.inner.font), self.inner.font_size, &self.inner.text);
 				let mut color = color.clone();
 				color.brighter(bright_factor);
 				color.mul_alpha(self.clicked_factor.value());
@@ -348,7 +370,7 @@ impl<S: Signal, A: App<Signal = S>> Widget for Radio<S, A> {
 
 				painter.set_fill_mode(text_color);
 				let text_pos = Vec2::new(self.inner.font_size * 2.0 + self.inner.padding.x, 0.0);
-				painter.draw_text(text_pos, self.inner.font, self.inner.font_size, &self.inner.text);
+				painter.draw_text(text_pos, painter.resolve_font(self.inner.font_role, self.inner.font), self.inner.font_size, &self.inner.text);
 			},
 			RadioStyle::Button { selected_color, unselected_color, roundings } => {
 				let selected_color = selected_color.brighten(bright_factor);
@@ -367,7 +389,7 @@ impl<S: Signal, A: App<Signal = S>> Widget for Radio<S, A> {
 				painter.set_fill_mode(text_color);
 				painter.draw_text(
 					self.inner.padding,
-					self.inner.font,
+					painter.resolve_font(self.inner.font_role, self.inner.font),
 					self.inner.font_size,
 					&self.inner.text,
 				);
@@ -376,7 +398,7 @@ impl<S: Signal, A: App<Signal = S>> Widget for Radio<S, A> {
 	}
 
 	fn size(&self, _: LayoutId, painter: &Painter, _: &Layout<Self::Signal, A>) -> Vec2 {
-		let text_size = painter.text_size(self.inner.font, self.inner.font_size, &self.inner.text).unwrap_or_default();
+		let text_size = painter.text_size(painter.resolve_font(self.inner.font_role, self.inner.font), self.inner.font_size, &self.inner.text).unwrap_or_default();
 		match &self.inner.style {
 			RadioStyle::Radio(_) => {
 				Vec2::new(self.inner.font_size + self.inner.padding.x, 0.0) + text_size
@@ -422,10 +444,32 @@ impl<S: Signal, A: App<Signal = S>> Widget for Radio<S, A> {
 			self.clicked_factor.set(0.0);
 		}
 
-		if res.is_clicked {
+		let activated = input_state.is_focused(from) && [Key::Enter, Key::KeypadEnter, Key::Space].into_iter().any(|key| input_state.is_key_pressed(key));
+		if activated {
+			self.signals.click(app, &mut self.inner, input_state, from);
+		}
+
+		if res.is_clicked || activated {
 			self.inner.selected = !self.inner.selected;
 		}
 
 		self.clicked_factor.is_animating() || self.hover_factor.is_animating() || self.pressed_factor.is_animating()
 	}
+
+	fn focusable(&self) -> bool {
+		true
+	}
+
+	fn save_state(&self) -> Option<Box<dyn Any>> {
+		Some(Box::new([self.hover_factor.clone(), self.pressed_factor.clone(), self.clicked_factor.clone()]))
+	}
+
+	fn restore_state(&mut self, state: Box<dyn Any>) {
+		if let Ok(state) = state.downcast::<[Animatedf32; 3]>() {
+			let [hover_factor, pressed_factor, clicked_factor] = *state;
+			self.hover_factor = hover_factor;
+			self.pressed_factor = pressed_factor;
+			self.clicked_factor = clicked_factor;
+		}
+	}
 }
\ No newline at end of file
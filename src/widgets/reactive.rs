@@ -1,14 +1,19 @@
 //! A struct that can be used to convert a static widget into a reactive widget.
 
-use std::collections::HashMap;
+use std::{any::Any, collections::HashMap};
 
 use indexmap::IndexMap;
+use time::Duration;
 
-use crate::{layout::{Layout, LayoutId}, prelude::{InputState, Painter, Rect, Vec2}, App};
+use crate::{layout::{HitRegion, Layout, LayoutId}, prelude::{InputState, Painter, Rect, Vec2}, App};
 
-use super::{Signal, Widget};
+use super::{ScrollAction, ScrollSource, Signal, Widget};
 
 /// A struct that can be used to convert a static widget into a reactive widget.
+/// 
+/// The widget is rebuilt by [`Reactive::on_update`] every time events are handled, and redrawn every time by default.
+/// Give a key with [`Reactive::key`] to redraw only when the key of the rebuilt widget differs from the last one,
+/// or when the widget itself asks for it.
 pub struct Reactive<W, S: Signal, A: App<Signal = S>> 
 where 
 	W: Widget<Signal = S, Application = A>,
@@ -18,6 +23,9 @@ where
 	/// The function that used to update the display element of the widget.
 	#[allow(clippy::type_complexity)]
 	pub on_update: Box<dyn Fn(&mut A, W) -> W>,
+	/// Checks whether the rebuilt widget differs from the last one.
+	#[allow(clippy::type_complexity)]
+	changed: Option<Box<dyn FnMut(&W) -> bool>>,
 }
 
 impl <W, S, A> Reactive<W, S, A>
@@ -28,7 +36,29 @@ where
 {
 	/// Creates a new reactive widget.
 	pub fn new(widget: W, on_update: impl Fn(&mut A, W) -> W + 'static) -> Self {
-		Self { widget: Some(widget), on_update: Box::new(on_update) }
+		Self { widget: Some(widget), on_update: Box::new(on_update), changed: None }
+	}
+
+	/// Redraw only when the key of the rebuilt widget differs from the last one, e.g. the text of a label.
+	/// 
+	/// The key should cover everything [`Self::on_update`] changes about how the widget looks,
+	/// changes missed by the key are not shown until the widget is redrawn for other reasons.
+	pub fn key<K: PartialEq + 'static>(self, key: impl Fn(&W) -> K + 'static) -> Self {
+		let mut last = None;
+		Self {
+			changed: Some(Box::new(move |widget| {
+				let key = key(widget);
+				let changed = last.as_ref() != Some(&key);
+				last = Some(key);
+				changed
+			})),
+			..self
+		}
+	}
+
+	/// Redraw only when the rebuilt widget differs from the last one, same as [`Self::key`] with the widget itself as the key.
+	pub fn distinct(self) -> Self where W: PartialEq + Clone {
+		self.key(W::clone)
 	}
 
 	/// Returns a reference to the original static widget.
@@ -54,8 +84,12 @@ where
 	fn handle_event(&mut self, app: &mut A, input_state: &mut InputState<Self::Signal>, id: LayoutId, area: Rect, pos: Vec2) -> bool {
 		let widget = self.widget.take().unwrap();
 		self.widget = Some((*self.on_update)(app, widget));
-		self.get_widget_mut().handle_event(app, input_state, id, area, pos);
-		true
+		let changed = match &mut self.changed {
+			Some(changed) => changed(self.widget.as_ref().unwrap()),
+			None => true,
+		};
+		let redraw = self.get_widget_mut().handle_event(app, input_state, id, area, pos);
+		changed || redraw
 	}
 
 	fn draw(&mut self, painter: &mut Painter, size: Vec2) {
@@ -74,7 +108,91 @@ where
 		self.get_widget().inner_padding()
 	}
 
+	fn scroll_by(&mut self, delta: Vec2, source: ScrollSource) -> Vec2 {
+		self.get_widget_mut().scroll_by(delta, source)
+	}
+
+	fn scroll_action(&mut self, action: ScrollAction) -> bool {
+		self.get_widget_mut().scroll_action(action)
+	}
+
+	fn scroll_to_reveal(&mut self, area: Rect) -> Vec2 {
+		self.get_widget_mut().scroll_to_reveal(area)
+	}
+
 	fn event_handle_strategy(&self) -> super::EventHandleStrategy {
 		self.get_widget().event_handle_strategy()
 	}
-} 
\ No newline at end of file
+
+	fn focusable(&self) -> bool {
+		self.get_widget().focusable()
+	}
+
+	fn tracks_interaction(&self) -> bool {
+		self.get_widget().tracks_interaction()
+	}
+
+	fn modal(&self) -> bool {
+		self.get_widget().modal()
+	}
+
+	fn type_name(&self) -> &'static str {
+		self.get_widget().type_name()
+	}
+
+	fn hit_region(&self) -> HitRegion {
+		self.get_widget().hit_region()
+	}
+
+	fn z_index(&self) -> i32 {
+		self.get_widget().z_index()
+	}
+
+	fn save_state(&self) -> Option<Box<dyn Any>> {
+		self.get_widget().save_state()
+	}
+
+	fn restore_state(&mut self, state: Box<dyn Any>) {
+		self.get_widget_mut().restore_state(state)
+	}
+
+	fn update(&mut self, dt: Duration) -> bool {
+		self.get_widget_mut().update(dt)
+	}
+
+	fn update_interval(&self) -> Option<Duration> {
+		self.get_widget().update_interval()
+	}
+} 
+mod test {
+	#[test]
+	fn test_reactive_card_scrolls() {
+		use std::sync::{Arc, Mutex};
+		use super::Reactive;
+		use crate::{layout::{Layout, ROOT_LAYOUT_ID}, prelude::{Card, FontPool, Painter, Scroll, Vec2}, widgets::SignalWrapper, window::{event::WindowEvent, input_state::InputState}, App, Context};
+
+		struct TestApp;
+		impl App for TestApp {
+			type Signal = ();
+			fn on_start(&mut self, _: &mut Context<(), Self>) {}
+			fn on_signal(&mut self, _: &mut Context<(), Self>, _: SignalWrapper<()>) {}
+		}
+
+		let window = Vec2::new(400.0, 300.0);
+		let mut layout = Layout::<(), TestApp>::new();
+		layout.insert_root_widget(Card::new_vertical());
+		let scrolled = Card::new_vertical().set_size(Vec2::new(200.0, 200.0)).scroll(Scroll::vertical());
+		let id = layout.add_widget(ROOT_LAYOUT_ID, Reactive::new(scrolled, |_, card| card)).unwrap();
+		for _ in 0..10 {
+			layout.add_widget(id, Card::new_vertical().set_size(Vec2::new(100.0, 100.0)));
+		}
+		let mut painter = Painter::headless(Arc::new(Mutex::new(FontPool::new())), window);
+		layout.handle_draw(&mut painter, window);
+
+		let mut state = InputState::new();
+		state.update(vec!(WindowEvent::MouseMoved(Vec2::new(50.0, 50.0)), WindowEvent::MouseWheel(Vec2::new(0.0, -100.0))));
+		layout.handle_events(&mut state, &mut TestApp);
+		let card = layout.get_widget::<Reactive<Card<(), TestApp>, (), TestApp>>(id).unwrap();
+		assert!(card.get_widget().offset().y > 0.0);
+	}
+}
@@ -0,0 +1,418 @@
+//! Text made of spans with their own colors, fonts and decorations, drawn by [`super::label::Label::rich`].
+
+use crate::prelude::{FillMode, FontId, Painter, Rect, Vec2, Vec4};
+
+use super::{color_picker::parse_hex_color, styles::PRIMARY_COLOR};
+
+/// A piece of a [`RichText`] sharing one style.
+///
+/// The unset properties are taken from the label drawing the text.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct Span {
+	/// The text of the span, may contain line breaks.
+	pub text: String,
+	/// The color of the text.
+	pub color: Option<FillMode>,
+	/// The font of the text, used instead of the bold and italic fonts of the [`RichText`].
+	pub font: Option<FontId>,
+	/// The font size of the text.
+	pub font_size: Option<f32>,
+	/// Whether to draw the text with [`RichText::bold_font`].
+	pub bold: bool,
+	/// Whether to draw the text with [`RichText::italic_font`].
+	pub italic: bool,
+	/// Whether to draw a line under the text.
+	pub underline: bool,
+	/// Whether to draw a line through the text.
+	pub strikethrough: bool,
+	/// The target of the link, given to [`super::label::Label::on_link`] when the span is clicked.
+	pub link: Option<String>,
+}
+
+impl Span {
+	/// Create a span with the text.
+	pub fn new(text: impl Into<String>) -> Self {
+		Self { text: text.into(), ..Default::default() }
+	}
+
+	/// Set the color of the text.
+	pub fn color(self, color: impl Into<FillMode>) -> Self {
+		Self { color: Some(color.into()), ..self }
+	}
+
+	/// Set the font of the text.
+	pub fn font(self, font: FontId) -> Self {
+		Self { font: Some(font), ..self }
+	}
+
+	/// Set the font size of the text.
+	pub fn font_size(self, font_size: f32) -> Self {
+		Self { font_size: Some(font_size), ..self }
+	}
+
+	/// Draw the text with the bold font.
+	pub fn bold(self) -> Self {
+		Self { bold: true, ..self }
+	}
+
+	/// Draw the text with the italic font.
+	pub fn italic(self) -> Self {
+		Self { italic: true, ..self }
+	}
+
+	/// Draw a line under the text.
+	pub fn underline(self) -> Self {
+		Self { underline: true, ..self }
+	}
+
+	/// Draw a line through the text.
+	pub fn strikethrough(self) -> Self {
+		Self { strikethrough: true, ..self }
+	}
+
+	/// Make the span a link to the target, links are underlined and drawn with [`RichText::link_color`] by default.
+	pub fn link(self, target: impl Into<String>) -> Self {
+		Self { link: Some(target.into()), ..self }
+	}
+}
+
+impl From<&str> for Span {
+	fn from(value: &str) -> Self {
+		Self::new(value)
+	}
+}
+
+impl From<String> for Span {
+	fn from(value: String) -> Self {
+		Self::new(value)
+	}
+}
+
+/// A text made of [`Span`]s, built span by span or parsed from a markup, see [`Self::parse`].
+///
+/// Bold and italic spans are drawn with [`Self::bold_font`] and [`Self::italic_font`],
+/// as the fonts are not synthesized, spans stay regular until the fonts are set.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RichText {
+	/// The spans of the text, in order.
+	pub spans: Vec<Span>,
+	/// The font of the bold spans.
+	pub bold_font: Option<FontId>,
+	/// The font of the italic spans, bold italic spans use the bold font.
+	pub italic_font: Option<FontId>,
+	/// The color of the links without their own color.
+	pub link_color: FillMode,
+}
+
+impl Default for RichText {
+	fn default() -> Self {
+		Self {
+			spans: vec!(),
+			bold_font: None,
+			italic_font: None,
+			link_color: FillMode::Color(PRIMARY_COLOR),
+		}
+	}
+}
+
+impl RichText {
+	/// Create an empty rich text.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Add a span after the existing ones.
+	pub fn span(mut self, span: impl Into<Span>) -> Self {
+		self.spans.push(span.into());
+		self
+	}
+
+	/// Set the font of the bold spans.
+	pub fn bold_font(self, bold_font: FontId) -> Self {
+		Self { bold_font: Some(bold_font), ..self }
+	}
+
+	/// Set the font of the italic spans.
+	pub fn italic_font(self, italic_font: FontId) -> Self {
+		Self { italic_font: Some(italic_font), ..self }
+	}
+
+	/// Set the color of the links without their own color.
+	pub fn link_color(self, link_color: impl Into<FillMode>) -> Self {
+		Self { link_color: link_color.into(), ..self }
+	}
+
+	/// Parse a markup into spans.
+	///
+	/// `**bold**`, `*italic*`, `__underline__` and `~~strikethrough~~` toggle their styles,
+	/// `[text](target)` is a link and `{#ff8800 text}` colors the text with a hex color.
+	/// A backslash takes the next character as is, unclosed markups are kept as text.
+	pub fn parse(markup: &str) -> Self {
+		let mut out = Self::new();
+		let mut style = Span::default();
+		let mut text = String::new();
+		let flush = |out: &mut Self, text: &mut String, style: &Span| {
+			if !text.is_empty() {
+				out.spans.push(Span { text: std::mem::take(text), ..style.clone() });
+			}
+		};
+
+		let mut rest = markup;
+		while let Some(chr) = rest.chars().next() {
+			let marker = marker_at(rest);
+			if chr == '\\' {
+				let mut chars = rest[1..].chars();
+				if let Some(escaped) = chars.next() {
+					text.push(escaped);
+				}
+				rest = chars.as_str();
+			}else if let Some(mark) = marker.filter(|mark| *marker_style(&mut style, mark) || is_closed(&rest[mark.len()..], mark)) {
+				flush(&mut out, &mut text, &style);
+				let styled = marker_style(&mut style, mark);
+				*styled = !*styled;
+				rest = &rest[mark.len()..];
+			}else if let Some(mark) = marker {
+				// an unclosed marker, such as `2 * 3`, is kept as a whole.
+				text.push_str(mark);
+				rest = &rest[mark.len()..];
+			}else if let Some((label, target, after)) = chr.eq(&'[').then(|| parse_link(rest)).flatten() {
+				flush(&mut out, &mut text, &style);
+				out.spans.push(Span { text: label.to_string(), ..style.clone() }.link(target));
+				rest = after;
+			}else if let Some((color, colored, after)) = chr.eq(&'{').then(|| parse_color(rest)).flatten() {
+				flush(&mut out, &mut text, &style);
+				out.spans.push(Span { text: colored.to_string(), ..style.clone() }.color(color));
+				rest = after;
+			}else {
+				text.push(chr);
+				rest = &rest[chr.len_utf8()..];
+			}
+		}
+		flush(&mut out, &mut text, &style);
+		out
+	}
+
+	/// Get the text without any style.
+	pub fn plain_text(&self) -> String {
+		self.spans.iter().map(|span| span.text.as_str()).collect()
+	}
+
+	fn span_font(&self, span: &Span, font: FontId) -> FontId {
+		span.font
+			.or(if span.bold { self.bold_font } else { None })
+			.or(if span.italic { self.italic_font } else { None })
+			.unwrap_or(font)
+	}
+
+	/// Lay out the spans, wrapping the words at `max_width`.
+	///
+	/// `font` and `font_size` are used by the spans without their own.
+	pub(crate) fn layout(&self, painter: &Painter, font: FontId, font_size: f32, max_width: f32) -> RichLayout {
+		let mut runs: Vec<RichRun> = vec!();
+		// the runs of the current line, aligned to the same baseline when the line ends.
+		let mut line_start = 0;
+		let mut top = 0.0;
+		let mut x: f32 = 0.0;
+		let mut width: f32 = 0.0;
+		let mut line_height: f32 = painter.line_height(font, font_size).unwrap_or(font_size);
+
+		let end_line = |runs: &mut Vec<RichRun>, line_start: &mut usize, top: &mut f32, line_height: &mut f32| {
+			let ascender = runs[*line_start..].iter().map(|run| run.rect.h).fold(0.0, f32::max);
+			for run in &mut runs[*line_start..] {
+				run.rect.y = *top + ascender - run.rect.h;
+			}
+			*line_start = runs.len();
+			*top += *line_height;
+			*line_height = painter.line_height(font, font_size).unwrap_or(font_size);
+		};
+
+		for (index, span) in self.spans.iter().enumerate() {
+			let span_font = self.span_font(span, font);
+			let span_size = span.font_size.unwrap_or(font_size);
+			let span_line_height = painter.line_height(span_font, span_size).unwrap_or(span_size);
+			for (line_index, line) in span.text.split('\n').enumerate() {
+				if line_index > 0 {
+					end_line(&mut runs, &mut line_start, &mut top, &mut line_height);
+					x = 0.0;
+				}
+				line_height = line_height.max(span_line_height);
+				for word in split_words(line) {
+					let size = painter.text_size_pointer(span_font, span_size, word).unwrap_or_default();
+					if x > 0.0 && x + size.x > max_width && !word.trim().is_empty() {
+						end_line(&mut runs, &mut line_start, &mut top, &mut line_height);
+						line_height = line_height.max(span_line_height);
+						x = 0.0;
+					}
+					// the words of a span on the same line are drawn as one run.
+					let line_len = runs.len();
+					match runs.last_mut() {
+						Some(run) if line_len > line_start && run.span == index => {
+							run.text.push_str(word);
+							run.rect.w += size.x;
+						},
+						_ => runs.push(RichRun {
+							span: index,
+							text: word.to_string(),
+							font: span_font,
+							font_size: span_size,
+							rect: Rect::new(x, 0.0, size.x, size.y),
+						}),
+					}
+					x += size.x;
+					width = width.max(x);
+				}
+			}
+		}
+		end_line(&mut runs, &mut line_start, &mut top, &mut line_height);
+
+		RichLayout { runs, size: Vec2::new(width, top) }
+	}
+
+	/// Draw a layout of the text, `color` is used by the spans without their own.
+	pub(crate) fn draw(&self, painter: &mut Painter, layout: &RichLayout, color: &FillMode) {
+		for run in &layout.runs {
+			let span = &self.spans[run.span];
+			let fill = match (&span.color, &span.link) {
+				(Some(color), _) => color.clone(),
+				(None, Some(_)) => self.link_color.clone(),
+				(None, None) => color.clone(),
+			};
+			painter.set_fill_mode(fill);
+			painter.draw_text(run.rect.lt(), run.font, run.font_size, &run.text);
+			let stroke = (run.font_size / 14.0).max(1.0);
+			let width = run.text.trim_end().chars().count() as f32 / run.text.chars().count().max(1) as f32 * run.rect.w;
+			if span.underline || span.link.is_some() {
+				painter.draw_rect(Rect::new(run.rect.x, run.rect.y + run.rect.h + stroke, width, stroke), Vec4::ZERO);
+			}
+			if span.strikethrough {
+				painter.draw_rect(Rect::new(run.rect.x, run.rect.y + run.rect.h * 0.65 - stroke * 0.5, width, stroke), Vec4::ZERO);
+			}
+		}
+	}
+}
+
+/// A laid out [`RichText`].
+#[derive(Clone, Debug, PartialEq, Default)]
+pub(crate) struct RichLayout {
+	pub(crate) runs: Vec<RichRun>,
+	pub(crate) size: Vec2,
+}
+
+impl RichLayout {
+	/// Get the span at the position relative to the text.
+	pub(crate) fn span_at(&self, pos: Vec2) -> Option<usize> {
+		self.runs.iter().find(|run| run.rect.contains(pos)).map(|run| run.span)
+	}
+}
+
+/// A piece of a span on a line.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct RichRun {
+	pub(crate) span: usize,
+	pub(crate) text: String,
+	pub(crate) font: FontId,
+	pub(crate) font_size: f32,
+	/// the box of the text above the baseline, relative to the text.
+	pub(crate) rect: Rect,
+}
+
+/// Split the line into words, each keeping the whitespace after it.
+fn split_words(line: &str) -> impl Iterator<Item = &str> {
+	let mut rest = line;
+	std::iter::from_fn(move || {
+		if rest.is_empty() {
+			return None;
+		}
+		let word_end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+		let end = rest[word_end..].find(|c: char| !c.is_whitespace()).map_or(rest.len(), |space| word_end + space);
+		let (word, after) = rest.split_at(end);
+		rest = after;
+		Some(word)
+	})
+}
+
+/// Parse `[label](target)` at the start of the text, returns the label, the target and the text after it.
+fn parse_link(text: &str) -> Option<(&str, &str, &str)> {
+	let label_end = text.find("](")?;
+	let label = &text[1..label_end];
+	let after_label = &text[label_end + 2..];
+	let target_end = after_label.find(')')?;
+	(!label.contains('\n')).then_some((label, &after_label[..target_end], &after_label[target_end + 1..]))
+}
+
+/// The style markers of [`RichText::parse`], the longer ones first so `**` is not taken as two `*`.
+const MARKERS: [&str; 4] = ["**", "__", "~~", "*"];
+
+/// Get the style marker at the start of the text.
+fn marker_at(text: &str) -> Option<&'static str> {
+	MARKERS.into_iter().find(|mark| text.starts_with(mark))
+}
+
+/// Get the style toggled by the marker.
+fn marker_style<'a>(style: &'a mut Span, mark: &str) -> &'a mut bool {
+	match mark {
+		"**" => &mut style.bold,
+		"__" => &mut style.underline,
+		"~~" => &mut style.strikethrough,
+		_ => &mut style.italic,
+	}
+}
+
+/// Check if the marker appears again in the text, skipping the escaped characters.
+fn is_closed(text: &str, mark: &str) -> bool {
+	let mut rest = text;
+	while let Some(chr) = rest.chars().next() {
+		if chr == '\\' {
+			let mut chars = rest[1..].chars();
+			chars.next();
+			rest = chars.as_str();
+		}else if let Some(found) = marker_at(rest) {
+			if found == mark {
+				return true;
+			}
+			rest = &rest[found.len()..];
+		}else {
+			rest = &rest[chr.len_utf8()..];
+		}
+	}
+	false
+}
+
+/// Parse `{#rrggbb text}` at the start of the text, returns the color, the text and the text after it.
+fn parse_color(text: &str) -> Option<(crate::prelude::Color, &str, &str)> {
+	let end = text.find('}')?;
+	let (hex, colored) = text[1..end].split_once(' ')?;
+	Some((parse_hex_color(hex)?, colored, &text[end + 1..]))
+}
+
+mod test {
+	#[test]
+	fn test_parse() {
+		use super::{RichText, Span};
+		use crate::widgets::color_picker::parse_hex_color;
+
+		let spans = |markup: &str| RichText::parse(markup).spans;
+
+		assert_eq!(spans("**bold *both* bold**"), vec![
+			Span::new("bold ").bold(),
+			Span::new("both").bold().italic(),
+			Span::new(" bold").bold(),
+		]);
+		assert_eq!(spans("\\*not italic\\*"), vec![Span::new("*not italic*")]);
+
+		// unclosed markers are kept as text.
+		assert_eq!(spans("2 * 3 = 6"), vec![Span::new("2 * 3 = 6")]);
+		assert_eq!(spans("my__file.txt"), vec![Span::new("my__file.txt")]);
+		assert_eq!(spans("*a* **b"), vec![Span::new("a").italic(), Span::new(" **b")]);
+
+		assert_eq!(spans("see [docs](https://example.com)."), vec![
+			Span::new("see "),
+			Span::new("docs").link("https://example.com"),
+			Span::new("."),
+		]);
+		assert_eq!(spans("{#ff8800 orange} text"), vec![
+			Span::new("orange").color(parse_hex_color("#ff8800").unwrap()),
+			Span::new(" text"),
+		]);
+	}
+}
@@ -0,0 +1,262 @@
+//! A selection model for list like widgets, such as [`crate::widgets::list_view::ListView`].
+
+use std::collections::BTreeSet;
+
+use crate::window::input_state::Modifiers;
+
+/// How many items a [`Selection`] can hold.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SelectionMode {
+	/// Items can not be selected.
+	None,
+	/// At most one item is selected.
+	#[default]
+	Single,
+	/// Any number of items can be selected, with Ctrl (Cmd on macOS) toggling an item and Shift selecting a range.
+	Multiple,
+}
+
+/// The selected items of a list like widget, identified by their indices.
+///
+/// Clicks are applied by [`Self::click`] with the usual semantics:
+///
+/// - a plain click selects only the clicked item and makes it the anchor.
+/// - Ctrl, or Cmd on macOS, toggles the clicked item and makes it the anchor.
+/// - Shift selects the range from the anchor to the clicked item, replacing the selection,
+///   or adding to it when Ctrl is held as well. The anchor stays where it is.
+///
+/// Every method changing the selection returns whether it changed.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Selection {
+	/// How many items can be selected.
+	pub mode: SelectionMode,
+	selected: BTreeSet<usize>,
+	anchor: Option<usize>,
+}
+
+impl Selection {
+	/// Create an empty selection.
+	pub fn new(mode: SelectionMode) -> Self {
+		Self {
+			mode,
+			selected: BTreeSet::new(),
+			anchor: None,
+		}
+	}
+
+	/// Create an empty selection holding at most one item.
+	pub fn single() -> Self {
+		Self::new(SelectionMode::Single)
+	}
+
+	/// Create an empty selection holding any number of items.
+	pub fn multiple() -> Self {
+		Self::new(SelectionMode::Multiple)
+	}
+
+	/// Get the selected indices in ascending order.
+	pub fn selected(&self) -> &BTreeSet<usize> {
+		&self.selected
+	}
+
+	/// Get the lowest selected index.
+	pub fn first(&self) -> Option<usize> {
+		self.selected.first().copied()
+	}
+
+	/// Check whether the item is selected.
+	pub fn is_selected(&self, index: usize) -> bool {
+		self.selected.contains(&index)
+	}
+
+	/// Get the number of selected items.
+	pub fn len(&self) -> usize {
+		self.selected.len()
+	}
+
+	/// Check whether nothing is selected.
+	pub fn is_empty(&self) -> bool {
+		self.selected.is_empty()
+	}
+
+	/// Get the item Shift+click selects a range from, the last item clicked without Shift.
+	pub fn anchor(&self) -> Option<usize> {
+		self.anchor
+	}
+
+	/// Apply a click on the item with the given modifiers, see [`Selection`].
+	pub fn click(&mut self, index: usize, modifiers: Modifiers) -> bool {
+		match self.mode {
+			SelectionMode::None => false,
+			SelectionMode::Single if modifiers.primary() && self.is_selected(index) => self.clear(),
+			SelectionMode::Single => self.select_only(index),
+			SelectionMode::Multiple if modifiers.shift => {
+				let anchor = self.anchor.unwrap_or(index);
+				let before = self.selected.clone();
+				if !modifiers.primary() {
+					self.selected.clear();
+				}
+				self.selected.extend(anchor.min(index)..=anchor.max(index));
+				self.anchor = Some(anchor);
+				self.selected != before
+			},
+			SelectionMode::Multiple if modifiers.primary() => {
+				self.anchor = Some(index);
+				self.toggle(index)
+			},
+			SelectionMode::Multiple => self.select_only(index),
+		}
+	}
+
+	/// Select the item, replacing the selection in [`SelectionMode::Single`], and make it the anchor.
+	pub fn select(&mut self, index: usize) -> bool {
+		match self.mode {
+			SelectionMode::None => false,
+			SelectionMode::Single => self.select_only(index),
+			SelectionMode::Multiple => {
+				self.anchor = Some(index);
+				self.selected.insert(index)
+			},
+		}
+	}
+
+	/// Select only the item and make it the anchor.
+	pub fn select_only(&mut self, index: usize) -> bool {
+		if self.mode == SelectionMode::None {
+			return false;
+		}
+		self.anchor = Some(index);
+		if self.selected.len() == 1 && self.is_selected(index) {
+			return false;
+		}
+		self.selected.clear();
+		self.selected.insert(index);
+		true
+	}
+
+	/// Deselect the item.
+	pub fn deselect(&mut self, index: usize) -> bool {
+		self.selected.remove(&index)
+	}
+
+	/// Select the item if it is not selected, deselect it otherwise.
+	pub fn toggle(&mut self, index: usize) -> bool {
+		if self.is_selected(index) {
+			self.deselect(index)
+		}else {
+			self.select(index)
+		}
+	}
+
+	/// Select the items from `from` to `to` inclusively and anchor at `from`, replacing the selection.
+	///
+	/// Only `to` is selected in [`SelectionMode::Single`].
+	pub fn select_range(&mut self, from: usize, to: usize) -> bool {
+		match self.mode {
+			SelectionMode::None => false,
+			SelectionMode::Single => self.select_only(to),
+			SelectionMode::Multiple => {
+				let before = std::mem::take(&mut self.selected);
+				self.selected.extend(from.min(to)..=from.max(to));
+				self.anchor = Some(from);
+				self.selected != before
+			},
+		}
+	}
+
+	/// Select all the `count` items, only works in [`SelectionMode::Multiple`].
+	pub fn select_all(&mut self, count: usize) -> bool {
+		if self.mode != SelectionMode::Multiple {
+			return false;
+		}
+		// the stale items beyond `count` would make the length match without everything selected.
+		let truncated = self.truncate(count);
+		if self.selected.len() == count {
+			return truncated;
+		}
+		self.selected.extend(0..count);
+		true
+	}
+
+	/// Replace the selection by the given items, keeping only the first one in [`SelectionMode::Single`].
+	pub fn set_selected(&mut self, indices: impl IntoIterator<Item = usize>) -> bool {
+		let before = std::mem::take(&mut self.selected);
+		match self.mode {
+			SelectionMode::None => {},
+			SelectionMode::Single => self.selected.extend(indices.into_iter().take(1)),
+			SelectionMode::Multiple => self.selected.extend(indices),
+		}
+		self.anchor = self.selected.first().copied();
+		self.selected != before
+	}
+
+	/// Deselect everything.
+	pub fn clear(&mut self) -> bool {
+		self.anchor = None;
+		!std::mem::take(&mut self.selected).is_empty()
+	}
+
+	/// Deselect the items at or beyond `count`, e.g. after the items shrank.
+	pub fn truncate(&mut self, count: usize) -> bool {
+		if self.anchor.is_some_and(|anchor| anchor >= count) {
+			self.anchor = None;
+		}
+		!self.selected.split_off(&count).is_empty()
+	}
+}
+
+mod test {
+	#[test]
+	fn test_selection_clicks() {
+		use super::Selection;
+		use crate::window::input_state::Modifiers;
+
+		let plain = Modifiers::default();
+		// both keys, so the primary modifier is held on every platform.
+		let primary = Modifiers { ctrl: true, logo: true, ..Default::default() };
+		let shift = Modifiers { shift: true, ..Default::default() };
+		let primary_shift = Modifiers { shift: true, ..primary };
+		let selected = |selection: &Selection| selection.selected().iter().copied().collect::<Vec<_>>();
+
+		let mut selection = Selection::multiple();
+		assert!(selection.click(2, plain));
+		assert!(selection.click(4, plain));
+		assert_eq!(selected(&selection), [4]);
+		assert_eq!(selection.anchor(), Some(4));
+
+		assert!(selection.click(6, primary));
+		assert_eq!(selected(&selection), [4, 6]);
+		assert!(selection.click(4, primary));
+		assert_eq!(selected(&selection), [6]);
+		assert_eq!(selection.anchor(), Some(4));
+
+		assert!(selection.click(1, shift));
+		assert_eq!(selected(&selection), [1, 2, 3, 4]);
+		assert!(selection.click(6, shift));
+		assert_eq!(selected(&selection), [4, 5, 6]);
+		assert_eq!(selection.anchor(), Some(4));
+
+		assert!(selection.click(9, primary));
+		assert!(selection.click(11, primary_shift));
+		assert_eq!(selected(&selection), [4, 5, 6, 9, 10, 11]);
+		assert_eq!(selection.anchor(), Some(9));
+
+		assert!(selection.select_range(3, 1));
+		assert_eq!(selected(&selection), [1, 2, 3]);
+		assert_eq!(selection.anchor(), Some(3));
+
+		assert!(selection.truncate(2));
+		assert_eq!(selected(&selection), [1]);
+		assert_eq!(selection.anchor(), None);
+		// without an anchor, shift selects from the clicked item.
+		assert!(selection.click(0, shift));
+		assert_eq!(selected(&selection), [0]);
+
+		// stale items beyond the count are not counted as selecting everything.
+		let mut selection = Selection::multiple();
+		selection.set_selected([1, 5, 7]);
+		assert!(selection.select_all(3));
+		assert_eq!(selected(&selection), [0, 1, 2]);
+		assert!(!selection.select_all(3));
+	}
+}
@@ -0,0 +1,162 @@
+//! A placeholder widget with a shimmer, shown while the content is loading.
+//!
+//! Swap it for the real content with [`crate::layout::Layout::crossfade_widget`] once the data arrives.
+
+use time::Duration;
+
+use crate::{layout::{Layout, LayoutId}, prelude::{Color, FillMode, FillPreset, InputState, Painter, Rect, Vec2, Vec4}, App};
+
+use super::{styles::{CONTENT_TEXT_SIZE, DEFAULT_ROUNDING, INPUT_BACKGROUND_COLOR, INPUT_BORDER_COLOR}, EventHandleStrategy, Signal, SignalGenerator, Widget};
+
+/// How often the shimmer is redrawn.
+const FRAME_INTERVAL: Duration = Duration::milliseconds(16);
+/// The width of a text skeleton without a size set.
+pub static SKELETON_TEXT_WIDTH: f32 = CONTENT_TEXT_SIZE * 16.0;
+/// The diameter of an avatar skeleton without a size set.
+pub static SKELETON_AVATAR_SIZE: f32 = CONTENT_TEXT_SIZE * 2.5;
+/// The gap between the lines of a text skeleton, as a ratio of the line height.
+const LINE_GAP: f32 = 0.5;
+/// The width of the last line of a text skeleton, as a ratio of the width.
+const LAST_LINE_WIDTH: f32 = 0.6;
+
+/// The shape of a skeleton.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SkeletonKind {
+	/// A rounded rect.
+	Rect,
+	/// The given number of text lines, the last one shorter.
+	Text(usize),
+	/// A circle.
+	Avatar,
+}
+
+/// A placeholder widget with a highlight band sweeping over it, shown while the content is loading.
+pub struct Skeleton<S: Signal, A: App<Signal = S>> {
+	/// The inner properties of the skeleton.
+	pub inner: SkeletonInner,
+	/// The signals generated by the skeleton.
+	pub signals: SignalGenerator<S, SkeletonInner, A>,
+	progress: f32,
+}
+
+/// The inner properties of the skeleton.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SkeletonInner {
+	/// The shape of the skeleton.
+	pub kind: SkeletonKind,
+	/// The size of the skeleton.
+	pub size: Vec2,
+	/// The roundings of the skeleton.
+	pub roundings: Vec4,
+	/// The color of the skeleton.
+	pub base_color: Color,
+	/// The color of the sweeping highlight.
+	pub highlight_color: Color,
+	/// The time for the highlight to sweep across once, in seconds.
+	pub period: f32,
+}
+
+impl<S: Signal, A: App<Signal = S>> Skeleton<S, A> {
+	/// Create a rect skeleton of the size.
+	pub fn new(size: Vec2) -> Self {
+		Self::rect(size)
+	}
+
+	/// Create a rect skeleton of the size, e.g. in place of an image or a card.
+	pub fn rect(size: Vec2) -> Self {
+		Self::with_kind(SkeletonKind::Rect, size, Vec4::same(DEFAULT_ROUNDING))
+	}
+
+	/// Create a skeleton of text lines in the content text size, in place of a paragraph.
+	pub fn text(lines: usize) -> Self {
+		let lines = lines.max(1) as f32;
+		let height = CONTENT_TEXT_SIZE * (lines + (lines - 1.0) * LINE_GAP);
+		Self::with_kind(SkeletonKind::Text(lines as usize), Vec2::new(SKELETON_TEXT_WIDTH, height), Vec4::same(CONTENT_TEXT_SIZE / 4.0))
+	}
+
+	/// Create a circle skeleton, in place of an avatar.
+	pub fn avatar() -> Self {
+		Self::with_kind(SkeletonKind::Avatar, Vec2::same(SKELETON_AVATAR_SIZE), Vec4::ZERO)
+	}
+
+	fn with_kind(kind: SkeletonKind, size: Vec2, roundings: Vec4) -> Self {
+		Self {
+			inner: SkeletonInner {
+				kind,
+				size,
+				roundings,
+				base_color: INPUT_BACKGROUND_COLOR,
+				highlight_color: INPUT_BORDER_COLOR,
+				period: 1.5,
+			},
+			signals: SignalGenerator::default(),
+			progress: 0.0,
+		}
+	}
+
+	/// Set the size of the skeleton.
+	pub fn set_size(self, size: Vec2) -> Self {
+		Self { inner: SkeletonInner { size, ..self.inner }, ..self }
+	}
+
+	/// Set the roundings of the skeleton, of each line for a text skeleton.
+	/// 
+	/// Not used by an avatar skeleton, which is always a circle.
+	pub fn roundings(self, roundings: impl Into<Vec4>) -> Self {
+		Self { inner: SkeletonInner { roundings: roundings.into(), ..self.inner }, ..self }
+	}
+
+	/// Set the color of the skeleton.
+	pub fn base_color(self, base_color: impl Into<Color>) -> Self {
+		Self { inner: SkeletonInner { base_color: base_color.into(), ..self.inner }, ..self }
+	}
+
+	/// Set the color of the sweeping highlight.
+	pub fn highlight_color(self, highlight_color: impl Into<Color>) -> Self {
+		Self { inner: SkeletonInner { highlight_color: highlight_color.into(), ..self.inner }, ..self }
+	}
+
+	/// Set the time for the highlight to sweep across once, in seconds.
+	pub fn period(self, period: f32) -> Self {
+		Self { inner: SkeletonInner { period, ..self.inner }, ..self }
+	}
+}
+
+impl<S: Signal, A: App<Signal = S>> Widget for Skeleton<S, A> {
+	type Signal = S;
+	type Application = A;
+
+	fn size(&self, _: LayoutId, _: &Painter, _: &Layout<Self::Signal, Self::Application>) -> Vec2 {
+		self.inner.size
+	}
+
+	fn draw(&mut self, painter: &mut Painter, size: Vec2) {
+		let rect = Rect::from_lt_size(Vec2::ZERO, size);
+		painter.set_fill_mode(FillMode::preset(FillPreset::Shimmer(self.inner.base_color, self.inner.highlight_color, self.progress), rect));
+		match self.inner.kind {
+			SkeletonKind::Rect => painter.draw_rect(rect, self.inner.roundings),
+			SkeletonKind::Text(lines) => {
+				let lines = lines.max(1);
+				let line_height = size.y / (lines as f32 * (1.0 + LINE_GAP) - LINE_GAP);
+				for line in 0..lines {
+					let width = if line + 1 == lines && lines > 1 { size.x * LAST_LINE_WIDTH } else { size.x };
+					let lt = Vec2::new(0.0, line as f32 * line_height * (1.0 + LINE_GAP));
+					painter.draw_rect(Rect::from_lt_size(lt, Vec2::new(width, line_height)), self.inner.roundings);
+				}
+			},
+			SkeletonKind::Avatar => painter.draw_circle(size / 2.0, size.x.min(size.y) / 2.0),
+		}
+	}
+
+	fn handle_event(&mut self, app: &mut A, input_state: &mut InputState<Self::Signal>, id: LayoutId, area: Rect, _: Vec2) -> bool {
+		self.signals.generate_signals(app, &mut self.inner, input_state, id, area, false, false);
+		self.progress = input_state.program_running_time().as_seconds_f32() / self.inner.period.max(f32::EPSILON);
+		input_state.request_frame_after(FRAME_INTERVAL);
+		true
+	}
+
+	fn event_handle_strategy(&self) -> EventHandleStrategy {
+		// the shimmer moves every frame.
+		EventHandleStrategy::AlwaysSecondary
+	}
+}
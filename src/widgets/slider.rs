@@ -1,9 +1,14 @@
 //! A slider widget for the UI.
 
-use crate::{layout::{Layout, LayoutId}, prelude::{Animatedf32, FillMode, FontId, InputState, Painter, Rect, Vec2, Vec4}, App};
+use std::any::Any;
+
+use crate::{layout::{Layout, LayoutId}, prelude::{Animatedf32, FillMode, FontId, FontRole, InputState, Painter, Rect, Vec2, Vec4}, window::event::Key, App};
 
 use super::{styles::{BRIGHT_FACTOR, CONTENT_TEXT_SIZE, DEFAULT_PADDING, INPUT_BACKGROUND_COLOR, PRIMARY_COLOR, PRIMARY_TEXT_COLOR}, Signal, SignalGenerator, Widget};
 
+/// The fraction of the range moved by an arrow key when the slider is focused.
+pub const KEYBOARD_STEP: f32 = 0.01;
+
 /// A slider widget for the UI.
 pub struct Slider<S: Signal, A: App<Signal = S>> {
 	/// The inner properties of the slider.
@@ -17,6 +22,8 @@ pub struct Slider<S: Signal, A: App<Signal = S>> {
 
 /// The inner properties of the slider.
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct SliderInner {
 	/// The current value of the slider.
 	pub value: f32,
@@ -46,6 +53,8 @@ pub struct SliderInner {
 	pub font_color: FillMode,
 	/// The font of the slider.
 	pub font: FontId,
+	/// The font role of the slider, see [`FontRole`].
+	pub font_role: Option<FontRole>,
 	/// Whether to show the text on the left of the slider.
 	pub text_left: bool,
 	/// Whether to fill foreground color in the reverse direction.
@@ -73,6 +82,7 @@ impl Default for SliderInner {
 			font_size: CONTENT_TEXT_SIZE,
 			font_color: FillMode::Color(PRIMARY_TEXT_COLOR),
 			font: 0,
+			font_role: Some(FontRole::Body),
 			text_left: false,
 			reverse: false,
 			padding: DEFAULT_PADDING,
@@ -223,9 +233,19 @@ impl<S: Signal, A: App<Signal = S>> Slider<S, A> {
 	}
 
 	/// Sets the font of the slider.
+	/// 
+	/// Clears the font role, so the font is used even if the theme registers one, see [`Self::font_role`].
 	pub fn font(self, font: FontId) -> Self {
 		Self {
-			inner: SliderInner { font, ..self.inner },
+			inner: SliderInner { font, font_role: None, ..self.inner },
+			..self
+		}
+	}
+
+	/// Sets the font role of the slider, `None` to always use the font id.
+	pub fn font_role(self, font_role: Option<FontRole>) -> Self {
+		Self {
+			inner: SliderInner { font_role, ..self.inner },
 			..self
 		}
 	}
@@ -261,6 +281,21 @@ impl<S: Signal, A: App<Signal = S>> Slider<S, A> {
 			..self
 		}
 	}
+
+	/// Move the value by a fraction of the range, the range is in logarithmic scale for logarithmic sliders.
+	fn move_by(&mut self, fraction: f32) {
+		let step = if self.inner.is_logarithmic {
+			fraction * (self.inner.max.log10() - self.inner.min.log10())
+		}else {
+			fraction * (self.inner.max - self.inner.min)
+		};
+		self.inner.value = if self.inner.is_logarithmic {
+			10.0_f32.powf(self.inner.value.log10() + step)
+		}else {
+			self.inner.value + step
+		};
+		self.inner.value = self.inner.value.clamp(self.inner.min, self.inner.max);
+	}
 }
 
 impl<S: Signal, A: App<Signal = S>> Widget for Slider<S, A> {
@@ -268,13 +303,12 @@ impl<S: Signal, A: App<Signal = S>> Widget for Slider<S, A> {
 	type Application = A;
 
 	fn size(&self, _: LayoutId, painter: &Painter, _: &Layout<Self::Signal, A>) -> Vec2 {
-		let text_to_draw = format!("{}{:.3$}{}", 
+		let text_to_draw = format!("{}{}{}", 
 			self.inner.prefix, 
-			self.inner.value, 
+			painter.localizer().format_number(self.inner.value, self.inner.decimal_places), 
 			self.inner.suffix, 
-			self.inner.decimal_places
 		);
-		let text_size = painter.text_size(self.inner.font, self.inner.font_size, text_to_draw).unwrap_or_default();
+		let text_size = painter.text_size(painter.resolve_font(self.inner.font_role, self.inner.font), self.inner.font_size, text_to_draw).unwrap_or_default();
 
 		let padding = self.inner.padding;
 
@@ -284,14 +318,13 @@ impl<S: Signal, A: App<Signal = S>> Widget for Slider<S, A> {
 	fn draw(&mut self, painter: &mut Painter, size: Vec2) {
 		let bright_factor = BRIGHT_FACTOR * (self.hover_factor.value() - self.pressed_factor.value()).max(0.0); 
 
-		let text_to_draw = format!("{}{:.3$}{}", 
+		let text_to_draw = format!("{}{}{}", 
 			self.inner.prefix, 
-			self.inner.value, 
+			painter.localizer().format_number(self.inner.value, self.inner.decimal_places), 
 			self.inner.suffix, 
-			self.inner.decimal_places
 		);
 
-		let text_size = painter.text_size(self.inner.font, self.inner.font_size, &text_to_draw).unwrap_or_default();
+		let text_size = painter.text_size(painter.resolve_font(self.inner.font_role, self.inner.font), self.inner.font_size, &text_to_draw).unwrap_or_default();
 		let padding = self.inner.padding;
 		let length = self.inner.length;
 		let circle_radius = self.inner.font_size / 2.0;
@@ -350,7 +383,7 @@ impl<S: Signal, A: App<Signal = S>> Widget for Slider<S, A> {
 		painter.set_fill_mode(circle_color);
 		painter.draw_circle(circle_pos, circle_radius);
 
-		painter.draw_text(text_pos, self.inner.font, self.inner.font_size, text_to_draw);
+		painter.draw_text(text_pos, painter.resolve_font(self.inner.font_role, self.inner.font), self.inner.font_size, text_to_draw);
 	}
 
 	fn handle_event(&mut self, app: &mut A, input_state: &mut InputState<Self::Signal>, from: LayoutId, area: Rect, _: Vec2) -> bool {
@@ -370,27 +403,49 @@ impl<S: Signal, A: App<Signal = S>> Widget for Slider<S, A> {
 			self.pressed_factor.set(0.0);
 		}
 		
-		let changed = if let Some(delta) = res.drag_delta {
-			let step = delta.x / self.inner.length;
-			let step = if self.inner.is_logarithmic {
-				step * (self.inner.max.log10() - self.inner.min.log10())
-			}else {
-				step * (self.inner.max - self.inner.min)
-			};
-			self.inner.value = if self.inner.is_logarithmic {
-				10.0_f32.powf(self.inner.value.log10() + step)
-			}else {
-				self.inner.value + step
-			};
-			self.inner.value = self.inner.value.clamp(self.inner.min, self.inner.max);
+		let mut changed = if let Some(delta) = res.drag_delta {
+			self.move_by(delta.x / self.inner.length);
 			delta.x != 0.0
 		}else {
 			false
 		};
 
+		if input_state.is_focused(from) {
+			let fraction = if input_state.is_key_pressed(Key::ArrawLeft) || input_state.is_key_pressed(Key::ArrawDown) {
+				-KEYBOARD_STEP
+			}else if input_state.is_key_pressed(Key::ArrawRight) || input_state.is_key_pressed(Key::ArrawUp) {
+				KEYBOARD_STEP
+			}else if input_state.is_key_pressed(Key::Home) {
+				-1.0
+			}else if input_state.is_key_pressed(Key::End) {
+				1.0
+			}else {
+				0.0
+			};
+			if fraction != 0.0 {
+				self.move_by(fraction);
+				let delta = Vec2::x(fraction * self.inner.length);
+				self.signals.drag(app, &mut self.inner, input_state, from, delta);
+				changed = true;
+			}
+		}
+
 		self.pressed_factor.is_animating() || self.hover_factor.is_animating() || changed
 	}
 
+	fn focusable(&self) -> bool {
+		true
+	}
+
+	fn scroll_by(&mut self, delta: Vec2, _: super::ScrollSource) -> Vec2 {
+		// the drag is moving this widget, dont scroll the parents.
+		if self.signals.is_dragging() {
+			Vec2::ZERO
+		}else {
+			delta
+		}
+	}
+
 	fn event_handle_strategy(&self) -> super::EventHandleStrategy {
 		if self.signals.is_dragging() {
 			super::EventHandleStrategy::AlwaysSecondary
@@ -398,4 +453,16 @@ impl<S: Signal, A: App<Signal = S>> Widget for Slider<S, A> {
 			super::EventHandleStrategy::OnHover
 		}
 	}
+
+	fn save_state(&self) -> Option<Box<dyn Any>> {
+		Some(Box::new([self.hover_factor.clone(), self.pressed_factor.clone()]))
+	}
+
+	fn restore_state(&mut self, state: Box<dyn Any>) {
+		if let Ok(state) = state.downcast::<[Animatedf32; 2]>() {
+			let [hover_factor, pressed_factor] = *state;
+			self.hover_factor = hover_factor;
+			self.pressed_factor = pressed_factor;
+		}
+	}
 }
\ No newline at end of file
@@ -1,6 +1,9 @@
 //! The main color scheme for the application.
+//! 
+//! Besides the colors, the interactive widgets take a [`StateStyle`] giving their colors in each [`WidgetState`],
+//! the presets such as [`StateStyle::primary`] are the default look of the built-in widgets.
 
-use crate::prelude::{Color, EM};
+use crate::{math::animation::Lerp, prelude::{Color, EM}};
 
 /// The default background color.
 pub static BACKGROUND_COLOR: Color = Color::new(0x1E as f32 / 255.0, 0x1E as f32 / 255.0, 0x1E as f32 / 255.0, 1.0);
@@ -13,7 +16,7 @@ pub static CARD_BORDER_COLOR: Color = Color::new(0x3D as f32 / 255.0, 0x3D as f3
 pub static PRIMARY_COLOR: Color = Color::new(0x8A as f32 / 255.0, 0x6A as f32 / 255.0, 0xFF as f32 / 255.0, 1.0);
 /// The default background color of the button, selectable label, and other clickable elements when disabled.
 pub static DISABLE_COLOR: Color = Color::new(0x5A as f32 / 255.0, 0x4A as f32 / 255.0, 0x8F as f32 / 255.0, 1.0);
-/// The default bright factoe of the widget's background color when hovered.
+/// The default bright factoe of the widget's background color when hovered, see [`StateStyle::from_normal`].
 pub static BRIGHT_FACTOR: f32 = 0.075;
 
 /// The default colors for the error message.
@@ -43,7 +46,142 @@ pub static INPUT_BORDER_COLOR: Color = Color::new(0x44 as f32 / 255.0, 0x44 as f
 /// The color for selected text in input fields (e.g., text boxes).
 pub static SELECTED_TEXT_COLOR: Color = Color::new(0x8A as f32 / 255.0, 0x6A as f32 / 255.0, 0xFF as f32 / 255.0, 0.3);
 
+/// The color of the ring drawn around the widget focused by the keyboard.
+pub static FOCUS_RING_COLOR: Color = Color::new(0xB3 as f32 / 255.0, 0x9D as f32 / 255.0, 0xFF as f32 / 255.0, 1.0);
+/// The width of the ring drawn around the widget focused by the keyboard.
+pub static FOCUS_RING_WIDTH: f32 = 2.0;
+
+/// The color dimming the UI behind a modal dialog.
+pub static BACKDROP_COLOR: Color = Color::new(0.0, 0.0, 0.0, 0.5);
+
 /// The default padding for the application.
 pub static DEFAULT_PADDING: f32 = EM / 2.0;
 /// The default rounding for the application.
-pub static DEFAULT_ROUNDING: f32 = EM / 2.0;
\ No newline at end of file
+pub static DEFAULT_ROUNDING: f32 = EM / 2.0;
+
+/// The interaction state of a widget, each drawn with its own colors from a [`StateStyle`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum WidgetState {
+	/// Not hovered nor pressed.
+	#[default] Normal,
+	/// Under the pointer.
+	Hover,
+	/// Pressed on while under the pointer.
+	Active,
+	/// Not responding to the user.
+	Disabled,
+}
+
+/// The colors of a widget in one [`WidgetState`].
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StateColors {
+	/// The color filling the widget.
+	pub background: Color,
+	/// The color of the text and the icons.
+	pub foreground: Color,
+	/// The color of the outline.
+	pub border: Color,
+}
+
+impl StateColors {
+	/// Create the colors of a state.
+	pub const fn new(background: Color, foreground: Color, border: Color) -> Self {
+		Self { background, foreground, border }
+	}
+
+	/// Brighten every color by the factor, a negative factor darkens them. The alpha is kept.
+	pub fn brighten(self, factor: f32) -> Self {
+		let brighten = |color: Color| Color::new(color.r + factor, color.g + factor, color.b + factor, color.a);
+		Self {
+			background: brighten(self.background),
+			foreground: brighten(self.foreground),
+			border: brighten(self.border),
+		}
+	}
+}
+
+impl Lerp for StateColors {
+	fn lerp(self, other: Self, t: f32) -> Self {
+		Self {
+			background: self.background.lerp(other.background, t),
+			foreground: self.foreground.lerp(other.foreground, t),
+			border: self.border.lerp(other.border, t),
+		}
+	}
+}
+
+/// The colors of a widget in each [`WidgetState`], the widget animates between them as its state changes.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StateStyle {
+	/// The colors while not hovered nor pressed.
+	pub normal: StateColors,
+	/// The colors while under the pointer.
+	pub hover: StateColors,
+	/// The colors while pressed on.
+	pub active: StateColors,
+	/// The colors while disabled.
+	pub disabled: StateColors,
+}
+
+impl StateStyle {
+	/// Derive the hover colors by brightening the normal ones by [`BRIGHT_FACTOR`].
+	/// 
+	/// The pressed colors are the normal ones, so a press flashes back from the hover colors.
+	pub fn from_normal(normal: StateColors, disabled: StateColors) -> Self {
+		Self {
+			normal,
+			hover: normal.brighten(BRIGHT_FACTOR),
+			active: normal,
+			disabled,
+		}
+	}
+
+	/// Get the colors of the state.
+	pub fn get(&self, state: WidgetState) -> StateColors {
+		match state {
+			WidgetState::Normal => self.normal,
+			WidgetState::Hover => self.hover,
+			WidgetState::Active => self.active,
+			WidgetState::Disabled => self.disabled,
+		}
+	}
+
+	/// The filled look of the primary actions.
+	pub fn primary() -> Self {
+		Self::from_normal(
+			StateColors::new(PRIMARY_COLOR, PRIMARY_TEXT_COLOR, PRIMARY_COLOR), 
+			StateColors::new(DISABLE_COLOR, DISABLE_TEXT_COLOR, DISABLE_COLOR),
+		)
+	}
+
+	/// The outlined look of the secondary actions.
+	pub fn secondary() -> Self {
+		Self::from_normal(
+			StateColors::new(Color::TRANSPARENT, PRIMARY_COLOR, PRIMARY_COLOR), 
+			StateColors::new(Color::TRANSPARENT, DISABLE_TEXT_COLOR, DISABLE_COLOR),
+		)
+	}
+
+	/// The look of the actions shown as text only, turning into the primary color when hovered.
+	pub fn text() -> Self {
+		let normal = StateColors::new(Color::TRANSPARENT, PRIMARY_TEXT_COLOR, PRIMARY_COLOR);
+		let active = StateColors { foreground: PRIMARY_COLOR, ..normal };
+		Self {
+			normal,
+			hover: active.brighten(BRIGHT_FACTOR),
+			active,
+			disabled: StateColors { foreground: DISABLE_TEXT_COLOR, ..normal },
+		}
+	}
+
+	/// The look of the input fields, such as text boxes.
+	pub fn input() -> Self {
+		Self::from_normal(
+			StateColors::new(INPUT_BACKGROUND_COLOR, SECONDARY_TEXT_COLOR, INPUT_BORDER_COLOR), 
+			StateColors::new(INPUT_BACKGROUND_COLOR, DISABLE_TEXT_COLOR, INPUT_BORDER_COLOR),
+		)
+	}
+}
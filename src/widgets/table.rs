@@ -0,0 +1,557 @@
+//! A table widget with a header row, sortable columns and resizable column widths.
+
+use std::collections::HashMap;
+
+use indexmap::IndexMap;
+
+use crate::{layout::{Layout, LayoutId}, prelude::{FillMode, FontId, FontRole, InputState, Painter, Rect, Vec2, Vec4}, render::font::EM, App};
+
+use super::{card::{Alignment, Scroll}, styles::{CARD_BORDER_COLOR, CARD_COLOR, CONTENT_TEXT_SIZE, DEFAULT_PADDING, INPUT_BACKGROUND_COLOR, PRIMARY_COLOR, PRIMARY_TEXT_COLOR}, EventHandleStrategy, ScrollAction, ScrollSource, Signal, SignalGenerator, Widget};
+
+/// How far from a column separator a press starts resizing the column.
+pub const SEPARATOR_HIT_WIDTH: f32 = 4.0;
+
+/// A table laying out its children as cells, in row-major order, under a header row.
+///
+/// The number of columns is given by [`TableInner::columns`], so the first child goes to the first column of the first row,
+/// the child after the last column starts the next row. Every row is as tall as its tallest cell.
+///
+/// Pressing a sortable header sets [`TableInner::sort`] and sends [`Table::on_sort`],
+/// the table does not know the data behind the cells, so reorder the children in the signal handler.
+/// Dragging the separator right of a resizable header resizes the column.
+///
+/// The cells scroll with the same [`Scroll`] as [`crate::widgets::card::Card`], both axes by default,
+/// the header stays on top and only scrolls horizontally.
+///
+/// ```no_run
+/// # use nablo_ui::prelude::*;
+/// # struct MyApp;
+/// # impl App for MyApp {
+/// #     type Signal = ();
+/// #     fn on_start(&mut self, _: &mut Context<(), Self>) {}
+/// #     fn on_signal(&mut self, _: &mut Context<(), Self>, _: SignalWrapper<()>) {}
+/// # }
+/// fn add_files(layout: &mut Layout<(), MyApp>, files: &[(&str, u64)]) {
+///     let table = layout.add_widget(ROOT_LAYOUT_ID, Table::new([
+///         TableColumn::new("Name", 240.0),
+///         TableColumn::new("Size", 80.0).alignment(Alignment::Negative),
+///     ]).on_sort(|_, _| ())).unwrap();
+///     for (name, size) in files {
+///         layout.add_widget(table, Label::new(*name));
+///         layout.add_widget(table, Label::new(size.to_string()));
+///     }
+/// }
+/// ```
+pub struct Table<S: Signal, A: App<Signal = S>> {
+	/// The inner properties of the table.
+	pub inner: TableInner,
+	/// The signals generated by the table.
+	pub signals: SignalGenerator<S, TableInner, A>,
+	/// The signal to be generated when a header is pressed to sort, see [`TableInner::sort`] for the sorting.
+	#[allow(clippy::type_complexity)]
+	pub on_sort: Option<Box<dyn Fn(&mut A, &mut TableInner) -> S>>,
+	/// the size of all the cells as of the last layout.
+	content_size: Vec2,
+	/// the bottom of every row relative to the top of the first row, as of the last layout.
+	row_bottoms: Vec<f32>,
+	last_maximum: Vec2,
+	/// the touch dragging a separator, the column it resizes, the width and the touch position when pressed.
+	resizing: Option<(u64, usize, f32, f32)>,
+	/// the touch pressing a header and the column of the header.
+	sorting: Option<(u64, usize)>,
+}
+
+/// A column of a [`Table`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct TableColumn {
+	/// The title shown in the header.
+	pub title: String,
+	/// The width of the column.
+	pub width: f32,
+	/// The minimum width the column can be resized to.
+	pub min_width: f32,
+	/// The horizontal alignment of the title and the cells.
+	pub alignment: Alignment,
+	/// Whether pressing the header sorts by the column.
+	pub sortable: bool,
+	/// Whether dragging the separator right of the header resizes the column.
+	pub resizable: bool,
+}
+
+impl TableColumn {
+	/// Create a sortable and resizable column.
+	pub fn new(title: impl Into<String>, width: f32) -> Self {
+		Self {
+			title: title.into(),
+			width,
+			min_width: EM * 2.0,
+			alignment: Alignment::Positive,
+			sortable: true,
+			resizable: true,
+		}
+	}
+
+	/// Set the minimum width the column can be resized to.
+	pub fn min_width(self, min_width: f32) -> Self {
+		Self { min_width, ..self }
+	}
+
+	/// Set the horizontal alignment of the title and the cells.
+	pub fn alignment(self, alignment: Alignment) -> Self {
+		Self { alignment, ..self }
+	}
+
+	/// Set whether pressing the header sorts by the column.
+	pub fn sortable(self, sortable: bool) -> Self {
+		Self { sortable, ..self }
+	}
+
+	/// Set whether the column can be resized.
+	pub fn resizable(self, resizable: bool) -> Self {
+		Self { resizable, ..self }
+	}
+}
+
+/// The direction a [`Table`] is sorted in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum SortOrder {
+	/// From the smallest to the largest.
+	#[default]
+	Ascending,
+	/// From the largest to the smallest.
+	Descending,
+}
+
+impl SortOrder {
+	/// Get the other direction.
+	pub fn reversed(self) -> Self {
+		match self {
+			Self::Ascending => Self::Descending,
+			Self::Descending => Self::Ascending,
+		}
+	}
+}
+
+/// The inner properties of a table.
+pub struct TableInner {
+	/// The columns of the table.
+	pub columns: Vec<TableColumn>,
+	/// The size of the table.
+	pub size: Vec2,
+	/// The height of the header row.
+	pub header_height: f32,
+	/// The minimum height of a row.
+	pub min_row_height: f32,
+	/// The padding around the content of every cell.
+	pub cell_padding: Vec2,
+	/// The scroll state of the cells.
+	pub scroll: Scroll,
+	/// The column sorted by and the direction, set when a sortable header is pressed.
+	pub sort: Option<(usize, SortOrder)>,
+	/// The font size of the titles.
+	pub font_size: f32,
+	/// The font id of the titles.
+	pub font: FontId,
+	/// The font role of the titles, see [`FontRole`].
+	pub font_role: Option<FontRole>,
+	/// The color of the titles.
+	pub header_text_color: FillMode,
+	/// The background color of the header.
+	pub header_color: FillMode,
+	/// The background color of the table.
+	pub background_color: FillMode,
+	/// The color of the lines between the rows and the headers, and of the sorting arrow.
+	pub line_color: FillMode,
+	/// The color of the separator being dragged.
+	pub active_color: FillMode,
+	/// The roundings of the table.
+	pub roundings: Vec4,
+}
+
+impl Default for TableInner {
+	fn default() -> Self {
+		Self {
+			columns: vec!(),
+			size: Vec2::new(480.0, 320.0),
+			header_height: CONTENT_TEXT_SIZE + DEFAULT_PADDING * 2.0,
+			min_row_height: CONTENT_TEXT_SIZE + DEFAULT_PADDING,
+			cell_padding: Vec2::new(DEFAULT_PADDING, DEFAULT_PADDING / 2.0),
+			scroll: Scroll::both(),
+			sort: None,
+			font_size: CONTENT_TEXT_SIZE,
+			font: 0,
+			font_role: Some(FontRole::Body),
+			header_text_color: FillMode::from(PRIMARY_TEXT_COLOR),
+			header_color: FillMode::from(INPUT_BACKGROUND_COLOR),
+			background_color: FillMode::from(CARD_COLOR),
+			line_color: FillMode::from(CARD_BORDER_COLOR),
+			active_color: FillMode::from(PRIMARY_COLOR),
+			roundings: Vec4::ZERO,
+		}
+	}
+}
+
+impl<S: Signal, A: App<Signal = S>> Table<S, A> {
+	/// Create a table with the given columns.
+	pub fn new(columns: impl IntoIterator<Item = TableColumn>) -> Self {
+		Self {
+			inner: TableInner {
+				columns: columns.into_iter().collect(),
+				..Default::default()
+			},
+			signals: SignalGenerator::default(),
+			on_sort: None,
+			content_size: Vec2::ZERO,
+			row_bottoms: vec!(),
+			last_maximum: Vec2::ZERO,
+			resizing: None,
+			sorting: None,
+		}
+	}
+
+	/// Add a column after the existing ones.
+	pub fn column(mut self, column: TableColumn) -> Self {
+		self.inner.columns.push(column);
+		self
+	}
+
+	/// Set the size of the table.
+	pub fn size(self, size: Vec2) -> Self {
+		Self { inner: TableInner { size, ..self.inner }, ..self }
+	}
+
+	/// Set the height of the header row.
+	pub fn header_height(self, header_height: f32) -> Self {
+		Self { inner: TableInner { header_height, ..self.inner }, ..self }
+	}
+
+	/// Set the minimum height of a row.
+	pub fn min_row_height(self, min_row_height: f32) -> Self {
+		Self { inner: TableInner { min_row_height, ..self.inner }, ..self }
+	}
+
+	/// Set the padding around the content of every cell.
+	pub fn cell_padding(self, cell_padding: Vec2) -> Self {
+		Self { inner: TableInner { cell_padding, ..self.inner }, ..self }
+	}
+
+	/// Set the scroll state of the cells.
+	pub fn scroll(self, scroll: Scroll) -> Self {
+		Self { inner: TableInner { scroll, ..self.inner }, ..self }
+	}
+
+	/// Set the column sorted by and the direction, only changes the arrow shown in the header.
+	pub fn sort(self, sort: Option<(usize, SortOrder)>) -> Self {
+		Self { inner: TableInner { sort, ..self.inner }, ..self }
+	}
+
+	/// Set the font size of the titles.
+	pub fn font_size(self, font_size: f32) -> Self {
+		Self { inner: TableInner { font_size, ..self.inner }, ..self }
+	}
+
+	/// Set the font of the titles.
+	/// 
+	/// Clears the font role, so the font is used even if the theme registers one, see [`Self::font_role`].
+	pub fn font(self, font: FontId) -> Self {
+		Self { inner: TableInner { font, font_role: None, ..self.inner }, ..self }
+	}
+
+	/// Set the font role of the titles, `None` to always use the font id.
+	pub fn font_role(self, font_role: Option<FontRole>) -> Self {
+		Self { inner: TableInner { font_role, ..self.inner }, ..self }
+	}
+
+	/// Set the color of the titles.
+	pub fn header_text_color(self, header_text_color: impl Into<FillMode>) -> Self {
+		Self { inner: TableInner { header_text_color: header_text_color.into(), ..self.inner }, ..self }
+	}
+
+	/// Set the background color of the header.
+	pub fn header_color(self, header_color: impl Into<FillMode>) -> Self {
+		Self { inner: TableInner { header_color: header_color.into(), ..self.inner }, ..self }
+	}
+
+	/// Set the background color of the table.
+	pub fn background_color(self, background_color: impl Into<FillMode>) -> Self {
+		Self { inner: TableInner { background_color: background_color.into(), ..self.inner }, ..self }
+	}
+
+	/// Set the color of the lines between the rows and the headers.
+	pub fn line_color(self, line_color: impl Into<FillMode>) -> Self {
+		Self { inner: TableInner { line_color: line_color.into(), ..self.inner }, ..self }
+	}
+
+	/// Set the color of the separator being dragged.
+	pub fn active_color(self, active_color: impl Into<FillMode>) -> Self {
+		Self { inner: TableInner { active_color: active_color.into(), ..self.inner }, ..self }
+	}
+
+	/// Set the roundings of the table.
+	pub fn roundings(self, roundings: impl Into<Vec4>) -> Self {
+		Self { inner: TableInner { roundings: roundings.into(), ..self.inner }, ..self }
+	}
+
+	/// Sets the signal to be generated when a header is pressed to sort.
+	pub fn on_sort(self, on_sort: impl Fn(&mut A, &mut TableInner) -> S + 'static) -> Self {
+		Self {
+			on_sort: Some(Box::new(on_sort)),
+			..self
+		}
+	}
+
+	/// Get the scroll position the table is heading to.
+	pub fn offset(&self) -> Vec2 {
+		self.inner.scroll.offset()
+	}
+
+	/// Get the number of rows, as of the last layout.
+	pub fn rows(&self) -> usize {
+		self.row_bottoms.len()
+	}
+
+	/// Get the size of the area the cells are shown in.
+	fn viewport(&self) -> Vec2 {
+		Vec2::new(self.inner.size.x, (self.inner.size.y - self.inner.header_height).max(0.0))
+	}
+
+	fn max_offset(&self) -> Vec2 {
+		self.inner.scroll.maximum(self.content_size - self.viewport())
+	}
+
+	/// Get the left edge of every column and the total width.
+	fn column_lefts(&self) -> (Vec<f32>, f32) {
+		let mut left = 0.0;
+		let lefts = self.inner.columns.iter().map(|column| {
+			let current = left;
+			left += column.width;
+			current
+		}).collect();
+		(lefts, left)
+	}
+
+	/// Get the column whose separator is at the given x relative to the contents.
+	fn separator_at(&self, x: f32) -> Option<usize> {
+		let (lefts, _) = self.column_lefts();
+		self.inner.columns.iter().zip(lefts).enumerate()
+			.filter(|(_, (column, _))| column.resizable)
+			.find(|(_, (column, left))| (left + column.width - x).abs() <= SEPARATOR_HIT_WIDTH)
+			.map(|(index, _)| index)
+	}
+
+	/// Get the column at the given x relative to the contents.
+	fn column_at(&self, x: f32) -> Option<usize> {
+		let (lefts, _) = self.column_lefts();
+		self.inner.columns.iter().zip(lefts).position(|(column, left)| x >= left && x < left + column.width)
+	}
+}
+
+/// Get the position of content of the given length inside of the given length.
+fn align(alignment: Alignment, space: f32, length: f32) -> f32 {
+	match alignment {
+		Alignment::Positive => 0.0,
+		Alignment::Center => (space - length) / 2.0,
+		Alignment::Negative => space - length,
+	}
+}
+
+impl<S: Signal, A: App<Signal = S>> Widget for Table<S, A> {
+	type Signal = S;
+	type Application = A;
+
+	fn size(&self, _: LayoutId, _: &Painter, _: &Layout<Self::Signal, Self::Application>) -> Vec2 {
+		self.inner.size
+	}
+
+	fn draw(&mut self, painter: &mut Painter, size: Vec2) {
+		let inner = &self.inner;
+		let offset = inner.scroll.current_offset();
+		let (lefts, total_width) = self.column_lefts();
+		let right = (total_width - offset.x).min(size.x);
+
+		painter.set_fill_mode(inner.background_color.clone());
+		painter.draw_rect(Rect::from_size(size), inner.roundings);
+
+		painter.set_fill_mode(inner.line_color.clone());
+		for bottom in &self.row_bottoms {
+			let y = inner.header_height + bottom - offset.y;
+			if y > inner.header_height && y < size.y {
+				painter.draw_line(Vec2::new(0.0, y), Vec2::new(right, y), 1.0);
+			}
+		}
+
+		painter.set_fill_mode(inner.header_color.clone());
+		painter.draw_rect(Rect::from_size(Vec2::new(size.x, inner.header_height)), Vec4::ZERO);
+
+		let font = painter.resolve_font(inner.font_role, inner.font);
+		let padding = inner.cell_padding.x;
+		for (index, (column, left)) in inner.columns.iter().zip(lefts).enumerate() {
+			let left = left - offset.x;
+			if left > size.x || left + column.width < 0.0 {
+				continue;
+			}
+			let sorted = inner.sort.filter(|(sorted, _)| *sorted == index).map(|(_, order)| order);
+			let arrow = if sorted.is_some() { inner.font_size / 2.0 + padding } else { 0.0 };
+			let text_size = painter.text_size(font, inner.font_size, &column.title).unwrap_or_default();
+			let text_x = left + padding + align(column.alignment, column.width - padding * 2.0 - arrow, text_size.x);
+			painter.set_fill_mode(inner.header_text_color.clone());
+			painter.draw_text(Vec2::new(text_x, (inner.header_height - text_size.y) / 2.0), font, inner.font_size, &column.title);
+
+			let edge = left + column.width;
+			if let Some(order) = sorted {
+				let half = inner.font_size / 4.0;
+				let center = Vec2::new(edge - padding - half, inner.header_height / 2.0);
+				let (tip, base) = match order {
+					SortOrder::Ascending => (- half, half),
+					SortOrder::Descending => (half, - half),
+				};
+				painter.set_fill_mode(inner.line_color.clone());
+				painter.draw_triangle(center + Vec2::y(tip), center + Vec2::new(- half, base), center + Vec2::new(half, base));
+			}
+
+			let active = self.resizing.is_some_and(|(_, resizing, _, _)| resizing == index);
+			painter.set_fill_mode(if active { inner.active_color.clone() } else { inner.line_color.clone() });
+			painter.draw_line(Vec2::new(edge, 0.0), Vec2::new(edge, if active { size.y } else { inner.header_height }), 1.0);
+		}
+	}
+
+	fn handle_event(&mut self, app: &mut A, input_state: &mut InputState<Self::Signal>, id: LayoutId, area: Rect, pos: Vec2) -> bool {
+		// taken before the signals, which mark the touches as used.
+		let header = Rect::from_lt_size(pos, Vec2::new(self.inner.size.x, self.inner.header_height)) & area;
+		let pressed = input_state.get_touch_pressed_on(header);
+		self.signals.generate_signals(app, &mut self.inner, input_state, id, area, false, false);
+		let mut redraw = false;
+
+		let scroll_x = self.inner.scroll.current_offset().x;
+		for touch in pressed {
+			if self.resizing.is_some_and(|(resizing, ..)| resizing == touch) || self.sorting.is_some_and(|(sorting, _)| sorting == touch) {
+				continue;
+			}
+			let Some(touch_pos) = input_state.get_touch_pos(touch) else { continue };
+			let x = touch_pos.x - pos.x + scroll_x;
+			if let Some(column) = self.separator_at(x) {
+				self.resizing = Some((touch, column, self.inner.columns[column].width, touch_pos.x));
+				redraw = true;
+			}else if let Some(column) = self.column_at(x).filter(|column| self.inner.columns[*column].sortable) {
+				self.sorting = Some((touch, column));
+			}
+		}
+
+		if let Some((touch, column, width, start)) = self.resizing {
+			match input_state.get_touch_pos(touch) {
+				Some(touch_pos) if !input_state.is_touch_released(touch) => {
+					if let Some(resized) = self.inner.columns.get_mut(column) {
+						let new_width = (width + touch_pos.x - start).max(resized.min_width);
+						redraw |= new_width != resized.width;
+						resized.width = new_width;
+					}
+				},
+				_ => {
+					self.resizing = None;
+					redraw = true;
+				},
+			}
+		}
+
+		if let Some((touch, column)) = self.sorting {
+			if input_state.is_touch_released(touch) {
+				self.sorting = None;
+				let released_on = input_state.get_touch_pos(touch)
+					.filter(|touch_pos| header.contains(*touch_pos))
+					.and_then(|touch_pos| self.column_at(touch_pos.x - pos.x + scroll_x));
+				if released_on == Some(column) && !input_state.is_touch_beyond_slop(touch) {
+					self.inner.sort = Some(match self.inner.sort {
+						Some((sorted, order)) if sorted == column => (column, order.reversed()),
+						_ => (column, SortOrder::Ascending),
+					});
+					if let Some(on_sort) = &self.on_sort {
+						let signal = on_sort(app, &mut self.inner);
+						input_state.send_signal_from(id, signal);
+					}
+					redraw = true;
+				}
+			}
+		}
+
+		// the size of the contents is unknown before the first layout.
+		if self.content_size != Vec2::ZERO {
+			let maximum = self.max_offset();
+			let last_maximum = std::mem::replace(&mut self.last_maximum, maximum);
+			self.inner.scroll.keep_inside(maximum, last_maximum, input_state.is_drag_scrolling_on(area));
+		}
+
+		redraw || self.inner.scroll.is_animating()
+	}
+
+	fn handle_child_layout(&mut self, childs: IndexMap<LayoutId, Vec2>, _: Rect, id: LayoutId) -> HashMap<LayoutId, Option<Rect>> {
+		let columns = self.inner.columns.len();
+		if columns == 0 {
+			return HashMap::new();
+		}
+		let padding = self.inner.cell_padding;
+		let (lefts, total_width) = self.column_lefts();
+		let sizes = childs.into_iter().collect::<Vec<_>>();
+
+		self.row_bottoms.clear();
+		let mut bottom = 0.0;
+		for row in sizes.chunks(columns) {
+			let height = row.iter().map(|(_, size)| size.y).fold(0.0, f32::max) + padding.y * 2.0;
+			bottom += height.max(self.inner.min_row_height);
+			self.row_bottoms.push(bottom);
+		}
+		self.content_size = Vec2::new(total_width, bottom);
+
+		let offset = self.inner.scroll.current_offset();
+		let mut out = HashMap::new();
+		// clip the cells below the header.
+		out.insert(id, Some(Rect::from_lt_size(Vec2::y(self.inner.header_height), self.viewport())));
+		for (index, (child, size)) in sizes.into_iter().enumerate() {
+			let (row, column) = (index / columns, index % columns);
+			let top = if row == 0 { 0.0 } else { self.row_bottoms[row - 1] };
+			let height = self.row_bottoms[row] - top;
+			let width = &self.inner.columns[column].width;
+			let size = Vec2::new(size.x.min(width - padding.x * 2.0).max(0.0), size.y.min(height));
+			let lt = Vec2::new(
+				lefts[column] + padding.x + align(self.inner.columns[column].alignment, width - padding.x * 2.0, size.x),
+				self.inner.header_height + top + (height - size.y) / 2.0,
+			);
+			out.insert(child, Some(Rect::from_lt_size(lt - offset, size)));
+		}
+		out
+	}
+
+	fn scroll_by(&mut self, delta: Vec2, source: ScrollSource) -> Vec2 {
+		// dragging a separator resizes instead of scrolling.
+		if self.resizing.is_some() && source == ScrollSource::Drag {
+			return Vec2::ZERO;
+		}
+		- self.inner.scroll.apply(- delta, source, self.content_size - self.viewport())
+	}
+
+	fn scroll_action(&mut self, action: ScrollAction) -> bool {
+		let line = self.inner.min_row_height;
+		let page = self.viewport();
+		let before = self.offset();
+		let maximum = self.max_offset();
+		let target = match action {
+			ScrollAction::LineUp => before - Vec2::y(line),
+			ScrollAction::LineDown => before + Vec2::y(line),
+			ScrollAction::LineLeft => before - Vec2::x(line),
+			ScrollAction::LineRight => before + Vec2::x(line),
+			ScrollAction::PageUp => before - Vec2::y(page.y),
+			ScrollAction::PageDown => before + Vec2::y(page.y),
+			ScrollAction::Home => Vec2::new(before.x, 0.0),
+			ScrollAction::End => Vec2::new(before.x, maximum.y),
+		};
+		self.inner.scroll.set_offset(target.max(Vec2::ZERO).min(maximum));
+		self.offset() != before
+	}
+
+	fn event_handle_strategy(&self) -> EventHandleStrategy {
+		if self.resizing.is_some() || self.sorting.is_some() || self.inner.scroll.is_animating() {
+			EventHandleStrategy::AlwaysSecondary
+		}else {
+			EventHandleStrategy::OnHover
+		}
+	}
+}
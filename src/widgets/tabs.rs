@@ -0,0 +1,564 @@
+//! A container showing one of its children at a time, picked by a strip of tabs.
+
+use std::collections::HashMap;
+
+use indexmap::IndexMap;
+
+use crate::{layout::{Layout, LayoutId}, prelude::{FillMode, FontId, FontRole, InputState, Painter, Rect, Vec2, Vec4}, App};
+
+use super::{card::Scroll, styles::{BACKGROUND_COLOR, CARD_BORDER_COLOR, CARD_COLOR, CONTENT_TEXT_SIZE, DEFAULT_PADDING, PRIMARY_COLOR, PRIMARY_TEXT_COLOR, SECONDARY_TEXT_COLOR, SELECTED_TEXT_COLOR}, EventHandleStrategy, ScrollSource, Signal, SignalGenerator, Widget};
+
+/// A container with a strip of tabs on the top, showing the child of the selected tab below it.
+///
+/// The n-th child of the widget is the content of the n-th tab, the children of the other tabs are not laid out nor drawn.
+/// The strip scrolls horizontally when the tabs do not fit, by the wheel or by dragging while the pointer is on it.
+///
+/// Pressing the close button of a closable tab sends [`Tabs::on_close`], then removes the tab along with its child.
+/// Select a tab from the app by [`Layout::widget_mut_by_alias`] with [`Tabs::selected`].
+///
+/// ```no_run
+/// # use nablo_ui::prelude::*;
+/// enum Sig {
+///     LogClosed,
+/// }
+/// # impl Signal for Sig {}
+/// # struct MyApp;
+/// # impl App for MyApp {
+/// #     type Signal = Sig;
+/// #     fn on_start(&mut self, _: &mut Context<Sig, Self>) {}
+/// #     fn on_signal(&mut self, _: &mut Context<Sig, Self>, _: SignalWrapper<Sig>) {}
+/// # }
+/// fn add_tabs(layout: &mut Layout<Sig, MyApp>) {
+///     let tabs = Tabs::new()
+///         .tab("General")
+///         .tab(Tab::new("Log").closable(true))
+///         .on_close(|_, _, _| Sig::LogClosed);
+///     let tabs = layout.add_widget(ROOT_LAYOUT_ID, tabs).unwrap();
+///     layout.alias_widget(tabs, "tabs");
+///     layout.add_widget(tabs, Label::new("General settings"));
+///     layout.add_widget(tabs, Label::new("Nothing logged yet"));
+/// }
+///
+/// fn show_log(layout: &mut Layout<Sig, MyApp>) {
+///     layout.widget_mut_by_alias("tabs", |tabs: Tabs<Sig, MyApp>| tabs.selected(1));
+/// }
+/// ```
+pub struct Tabs<S: Signal, A: App<Signal = S>> {
+	/// The inner properties of the tabs.
+	pub inner: TabsInner,
+	/// The signals generated by the tabs.
+	pub signals: SignalGenerator<S, TabsInner, A>,
+	/// The signal to be generated when a tab is selected by the user.
+	#[allow(clippy::type_complexity)]
+	pub on_select: Option<Box<dyn Fn(&mut A, &mut TabsInner) -> S>>,
+	/// The signal to be generated when a tab is closed by the user, with the index of the closed tab.
+	///
+	/// The tab is still in [`TabsInner::tabs`] when the signal is generated.
+	#[allow(clippy::type_complexity)]
+	pub on_close: Option<Box<dyn Fn(&mut A, &mut TabsInner, usize) -> S>>,
+	/// the children of the widget as of the last layout.
+	children: Vec<LayoutId>,
+	/// the size of the child of the selected tab.
+	content_size: Vec2,
+	/// the size of the widget as of the last draw.
+	drawn_size: Vec2,
+	/// the right edge of every tab relative to the strip, as of the last draw.
+	tab_edges: Vec<f32>,
+	/// whether the pointer is on the strip, which scrolls the strip instead of the parent.
+	strip_hovered: bool,
+	hovered: Option<usize>,
+	close_hovered: bool,
+	/// the touch pressing a close button, and the tab of the button.
+	closing: Option<(u64, usize)>,
+	/// the touch last pressed on the strip.
+	pressing: Option<u64>,
+	/// the selected tab last scrolled into view.
+	revealed: Option<usize>,
+	last_maximum: Vec2,
+}
+
+/// A tab of [`Tabs`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Tab {
+	/// The label of the tab.
+	pub label: String,
+	/// Whether the tab has a close button.
+	pub closable: bool,
+}
+
+impl Tab {
+	/// Create a tab with the given label.
+	pub fn new(label: impl Into<String>) -> Self {
+		Self {
+			label: label.into(),
+			closable: false,
+		}
+	}
+
+	/// Set whether the tab has a close button.
+	pub fn closable(self, closable: bool) -> Self {
+		Self { closable, ..self }
+	}
+}
+
+impl From<&str> for Tab {
+	fn from(label: &str) -> Self {
+		Self::new(label)
+	}
+}
+
+impl From<String> for Tab {
+	fn from(label: String) -> Self {
+		Self::new(label)
+	}
+}
+
+/// The inner properties of tabs.
+pub struct TabsInner {
+	/// The tabs of the strip.
+	pub tabs: Vec<Tab>,
+	/// The index of the selected tab.
+	pub selected: usize,
+	/// The size of the widget, `None` to fit the strip and the child of the selected tab.
+	pub size: Option<Vec2>,
+	/// The scroll of the strip, only the horizontal axis is used.
+	pub strip_scroll: Scroll,
+	/// The font of the labels.
+	pub font: FontId,
+	/// The font role of the labels, see [`FontRole`].
+	pub font_role: Option<FontRole>,
+	/// The font size of the labels.
+	pub font_size: f32,
+	/// The padding around the labels.
+	pub padding: Vec2,
+	/// The padding around the child of the selected tab.
+	pub content_padding: Vec2,
+	/// The background color of the strip.
+	pub strip_color: FillMode,
+	/// The background color of the selected tab and the content.
+	pub background_color: FillMode,
+	/// The background color of the hovered tab.
+	pub hover_color: FillMode,
+	/// The color of the line under the selected tab.
+	pub indicator_color: FillMode,
+	/// The color of the line between the strip and the content.
+	pub border_color: FillMode,
+	/// The color of the labels.
+	pub text_color: FillMode,
+	/// The color of the label of the selected tab.
+	pub selected_text_color: FillMode,
+	/// The roundings of the tabs.
+	pub roundings: Vec4,
+}
+
+impl Default for TabsInner {
+	fn default() -> Self {
+		Self {
+			tabs: vec!(),
+			selected: 0,
+			size: None,
+			strip_scroll: Scroll::horizontal(),
+			font: 0,
+			font_role: Some(FontRole::Body),
+			font_size: CONTENT_TEXT_SIZE,
+			padding: Vec2::new(DEFAULT_PADDING * 1.5, DEFAULT_PADDING),
+			content_padding: Vec2::same(DEFAULT_PADDING),
+			strip_color: FillMode::from(BACKGROUND_COLOR),
+			background_color: FillMode::from(CARD_COLOR),
+			hover_color: FillMode::from(SELECTED_TEXT_COLOR),
+			indicator_color: FillMode::from(PRIMARY_COLOR),
+			border_color: FillMode::from(CARD_BORDER_COLOR),
+			text_color: FillMode::from(SECONDARY_TEXT_COLOR),
+			selected_text_color: FillMode::from(PRIMARY_TEXT_COLOR),
+			roundings: Vec4::new(DEFAULT_PADDING / 2.0, DEFAULT_PADDING / 2.0, 0.0, 0.0),
+		}
+	}
+}
+
+impl<S: Signal, A: App<Signal = S>> Default for Tabs<S, A> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<S: Signal, A: App<Signal = S>> Tabs<S, A> {
+	/// Create tabs without any tab.
+	pub fn new() -> Self {
+		Self {
+			inner: TabsInner::default(),
+			signals: SignalGenerator::default(),
+			on_select: None,
+			on_close: None,
+			children: vec!(),
+			content_size: Vec2::ZERO,
+			drawn_size: Vec2::ZERO,
+			tab_edges: vec!(),
+			strip_hovered: false,
+			hovered: None,
+			close_hovered: false,
+			closing: None,
+			pressing: None,
+			revealed: None,
+			last_maximum: Vec2::ZERO,
+		}
+	}
+
+	/// Add a tab after the existing ones, its content is the child added at the same index.
+	pub fn tab(mut self, tab: impl Into<Tab>) -> Self {
+		self.inner.tabs.push(tab.into());
+		self
+	}
+
+	/// Add tabs after the existing ones.
+	pub fn tabs(mut self, tabs: impl IntoIterator<Item = impl Into<Tab>>) -> Self {
+		self.inner.tabs.extend(tabs.into_iter().map(Into::into));
+		self
+	}
+
+	/// Set the selected tab.
+	pub fn selected(self, selected: usize) -> Self {
+		Self { inner: TabsInner { selected, ..self.inner }, ..self }
+	}
+
+	/// Set the size of the widget, `None` to fit the strip and the child of the selected tab.
+	pub fn size(self, size: Option<Vec2>) -> Self {
+		Self { inner: TabsInner { size, ..self.inner }, ..self }
+	}
+
+	/// Set the font of the labels.
+	/// 
+	/// Clears the font role, so the font is used even if the theme registers one, see [`Self::font_role`].
+	pub fn font(self, font: FontId) -> Self {
+		Self { inner: TabsInner { font, font_role: None, ..self.inner }, ..self }
+	}
+
+	/// Set the font role of the labels, see [`FontRole`].
+	pub fn font_role(self, font_role: Option<FontRole>) -> Self {
+		Self { inner: TabsInner { font_role, ..self.inner }, ..self }
+	}
+
+	/// Set the font size of the labels.
+	pub fn font_size(self, font_size: f32) -> Self {
+		Self { inner: TabsInner { font_size, ..self.inner }, ..self }
+	}
+
+	/// Set the padding around the labels.
+	pub fn padding(self, padding: impl Into<Vec2>) -> Self {
+		Self { inner: TabsInner { padding: padding.into(), ..self.inner }, ..self }
+	}
+
+	/// Set the padding around the child of the selected tab.
+	pub fn content_padding(self, content_padding: impl Into<Vec2>) -> Self {
+		Self { inner: TabsInner { content_padding: content_padding.into(), ..self.inner }, ..self }
+	}
+
+	/// Set the background color of the strip.
+	pub fn strip_color(self, strip_color: impl Into<FillMode>) -> Self {
+		Self { inner: TabsInner { strip_color: strip_color.into(), ..self.inner }, ..self }
+	}
+
+	/// Set the background color of the selected tab and the content.
+	pub fn background_color(self, background_color: impl Into<FillMode>) -> Self {
+		Self { inner: TabsInner { background_color: background_color.into(), ..self.inner }, ..self }
+	}
+
+	/// Set the background color of the hovered tab.
+	pub fn hover_color(self, hover_color: impl Into<FillMode>) -> Self {
+		Self { inner: TabsInner { hover_color: hover_color.into(), ..self.inner }, ..self }
+	}
+
+	/// Set the color of the line under the selected tab.
+	pub fn indicator_color(self, indicator_color: impl Into<FillMode>) -> Self {
+		Self { inner: TabsInner { indicator_color: indicator_color.into(), ..self.inner }, ..self }
+	}
+
+	/// Set the color of the line between the strip and the content.
+	pub fn border_color(self, border_color: impl Into<FillMode>) -> Self {
+		Self { inner: TabsInner { border_color: border_color.into(), ..self.inner }, ..self }
+	}
+
+	/// Set the color of the labels.
+	pub fn text_color(self, text_color: impl Into<FillMode>) -> Self {
+		Self { inner: TabsInner { text_color: text_color.into(), ..self.inner }, ..self }
+	}
+
+	/// Set the color of the label of the selected tab.
+	pub fn selected_text_color(self, selected_text_color: impl Into<FillMode>) -> Self {
+		Self { inner: TabsInner { selected_text_color: selected_text_color.into(), ..self.inner }, ..self }
+	}
+
+	/// Set the roundings of the tabs.
+	pub fn roundings(self, roundings: impl Into<Vec4>) -> Self {
+		Self { inner: TabsInner { roundings: roundings.into(), ..self.inner }, ..self }
+	}
+
+	/// Sets the signal to be generated when a tab is selected by the user.
+	pub fn on_select(self, on_select: impl Fn(&mut A, &mut TabsInner) -> S + 'static) -> Self {
+		Self {
+			on_select: Some(Box::new(on_select)),
+			..self
+		}
+	}
+
+	/// Sets the signal to be generated when a tab is closed by the user.
+	pub fn on_close(self, on_close: impl Fn(&mut A, &mut TabsInner, usize) -> S + 'static) -> Self {
+		Self {
+			on_close: Some(Box::new(on_close)),
+			..self
+		}
+	}
+
+	/// Select the tab at the index.
+	pub fn select(&mut self, index: usize) {
+		self.inner.selected = index;
+	}
+
+	fn strip_height(&self) -> f32 {
+		self.inner.font_size + self.inner.padding.y * 2.0
+	}
+
+	fn tab_widths(&self, painter: &Painter) -> Vec<f32> {
+		let font = painter.resolve_font(self.inner.font_role, self.inner.font);
+		self.inner.tabs.iter().map(|tab| {
+			let label = painter.text_size(font, self.inner.font_size, &tab.label).map_or(0.0, |size| size.x);
+			let close = if tab.closable { self.inner.font_size + self.inner.padding.x / 2.0 } else { 0.0 };
+			label + close + self.inner.padding.x * 2.0
+		}).collect()
+	}
+
+	fn tab_left(&self, index: usize) -> f32 {
+		if index == 0 { 0.0 } else { self.tab_edges[index - 1] }
+	}
+
+	/// Get the close button of the tab relative to the strip, without the scroll.
+	fn close_button(&self, index: usize) -> Rect {
+		let size = self.inner.font_size;
+		Rect::from_lt_size(Vec2::new(self.tab_edges[index] - self.inner.padding.x - size, self.inner.padding.y), Vec2::same(size))
+	}
+
+	/// Get the tab and whether the close button is hit at the x relative to the strip, without the scroll.
+	fn tab_at(&self, pos: Vec2) -> Option<(usize, bool)> {
+		if pos.x < 0.0 {
+			return None;
+		}
+		let index = self.tab_edges.iter().position(|edge| pos.x < *edge)?;
+		let on_close = self.inner.tabs.get(index)?.closable && self.close_button(index).contains(pos);
+		Some((index, on_close))
+	}
+
+	fn overflow(&self) -> Vec2 {
+		Vec2::x(self.tab_edges.last().copied().unwrap_or(0.0) - self.drawn_size.x)
+	}
+
+	fn close_tab(&mut self, app: &mut A, input_state: &mut InputState<S>, id: LayoutId, index: usize) {
+		if index >= self.inner.tabs.len() {
+			return;
+		}
+		if let Some(on_close) = &self.on_close {
+			let signal = on_close(app, &mut self.inner, index);
+			input_state.send_signal_from(id, signal);
+		}
+		self.inner.tabs.remove(index);
+		if index < self.tab_edges.len() {
+			self.tab_edges.remove(index);
+		}
+		if index < self.children.len() {
+			input_state.remove_child(id, self.children.remove(index));
+		}
+		if self.inner.selected > index || (self.inner.selected == index && index == self.inner.tabs.len()) {
+			self.inner.selected = self.inner.selected.saturating_sub(1);
+		}
+	}
+
+	/// Scroll the strip so the selected tab is fully visible.
+	fn reveal_selected(&mut self) {
+		let selected = self.inner.selected;
+		if self.revealed == Some(selected) || selected >= self.tab_edges.len() || self.drawn_size == Vec2::ZERO {
+			return;
+		}
+		self.revealed = Some(selected);
+		let (left, right) = (self.tab_left(selected), self.tab_edges[selected]);
+		let offset = self.inner.strip_scroll.offset().x;
+		if left < offset {
+			self.inner.strip_scroll.set_offset(Vec2::x(left));
+		}else if right > offset + self.drawn_size.x {
+			self.inner.strip_scroll.set_offset(Vec2::x(right - self.drawn_size.x));
+		}
+	}
+}
+
+impl<S: Signal, A: App<Signal = S>> Widget for Tabs<S, A> {
+	type Signal = S;
+	type Application = A;
+
+	fn size(&self, _: LayoutId, painter: &Painter, _: &Layout<Self::Signal, Self::Application>) -> Vec2 {
+		self.inner.size.unwrap_or_else(|| {
+			let strip_width = self.tab_widths(painter).iter().sum::<f32>();
+			let content = self.content_size + self.inner.content_padding * 2.0;
+			Vec2::new(strip_width.max(content.x), self.strip_height() + content.y)
+		})
+	}
+
+	fn draw(&mut self, painter: &mut Painter, size: Vec2) {
+		self.drawn_size = size;
+		let strip_height = self.strip_height();
+		painter.set_fill_mode(self.inner.background_color.clone());
+		painter.draw_rect(Rect::from_size(size), Vec4::ZERO);
+		painter.set_fill_mode(self.inner.strip_color.clone());
+		painter.draw_rect(Rect::from_size(Vec2::new(size.x, strip_height)), Vec4::ZERO);
+		painter.set_fill_mode(self.inner.border_color.clone());
+		painter.draw_rect(Rect::new(0.0, strip_height - 1.0, size.x, 1.0), Vec4::ZERO);
+
+		self.tab_edges.clear();
+		let mut left = 0.0;
+		for width in self.tab_widths(painter) {
+			left += width;
+			self.tab_edges.push(left);
+		}
+
+		let clip_rect = painter.clip_rect();
+		painter.set_clip_rect(clip_rect & Rect::from_lt_size(painter.releative_to(), Vec2::new(size.x, strip_height)));
+		let font = painter.resolve_font(self.inner.font_role, self.inner.font);
+		let scroll = self.inner.strip_scroll.current_offset().x;
+		let padding = self.inner.padding;
+		for (index, tab) in self.inner.tabs.iter().enumerate() {
+			let left = self.tab_left(index) - scroll;
+			let width = self.tab_edges[index] - self.tab_left(index);
+			if left + width < 0.0 || left > size.x {
+				continue;
+			}
+			let selected = index == self.inner.selected;
+			if selected {
+				painter.set_fill_mode(self.inner.background_color.clone());
+				painter.draw_rect(Rect::new(left, 0.0, width, strip_height), self.inner.roundings);
+				painter.set_fill_mode(self.inner.indicator_color.clone());
+				painter.draw_rect(Rect::new(left, strip_height - 2.0, width, 2.0), Vec4::ZERO);
+			}else if self.hovered == Some(index) {
+				painter.set_fill_mode(self.inner.hover_color.clone());
+				painter.draw_rect(Rect::new(left, 0.0, width, strip_height), self.inner.roundings);
+			}
+
+			let text_color = if selected { self.inner.selected_text_color.clone() } else { self.inner.text_color.clone() };
+			painter.set_fill_mode(text_color.clone());
+			painter.draw_text(Vec2::new(left + padding.x, padding.y), font, self.inner.font_size, &tab.label);
+
+			if tab.closable {
+				let button = self.close_button(index).move_by(Vec2::x(- scroll));
+				if self.hovered == Some(index) && self.close_hovered {
+					painter.set_fill_mode(self.inner.hover_color.clone());
+					painter.draw_rect(button, Vec4::same(button.w / 4.0));
+				}
+				painter.set_fill_mode(text_color);
+				let cross = button.shrink(Vec2::same(button.w / 4.0));
+				let stroke = (self.inner.font_size / 12.0).max(1.0);
+				painter.draw_line(cross.lt(), cross.rb(), stroke);
+				painter.draw_line(Vec2::new(cross.rb().x, cross.lt().y), Vec2::new(cross.lt().x, cross.rb().y), stroke);
+			}
+		}
+		painter.set_clip_rect(clip_rect);
+	}
+
+	fn handle_event(&mut self, app: &mut A, input_state: &mut InputState<Self::Signal>, id: LayoutId, area: Rect, pos: Vec2) -> bool {
+		// taken before the signals, which mark the touches as used.
+		let strip = Rect::from_lt_size(pos, Vec2::new(self.drawn_size.x, self.strip_height())) & area;
+		let pressed = input_state.get_touch_pressed_on(strip);
+		self.signals.generate_signals(app, &mut self.inner, input_state, id, area, false, false);
+		let before = (self.hovered, self.close_hovered, self.inner.selected, self.inner.tabs.len());
+
+		let scroll = self.inner.strip_scroll.current_offset().x;
+		let to_strip = |touch_pos: Vec2| touch_pos - pos + Vec2::x(scroll);
+		let hover = input_state.hover_pos().filter(|hover| strip.contains(*hover));
+		self.strip_hovered = hover.is_some();
+		let hovered = hover.and_then(|hover| self.tab_at(to_strip(hover)));
+		self.hovered = hovered.map(|(index, _)| index);
+		self.close_hovered = hovered.is_some_and(|(_, on_close)| on_close);
+
+		for touch in pressed {
+			if self.pressing == Some(touch) || input_state.is_touch_released(touch) {
+				continue;
+			}
+			self.pressing = Some(touch);
+			let Some((index, on_close)) = input_state.get_touch_pos(touch).and_then(|touch_pos| self.tab_at(to_strip(touch_pos))) else { continue };
+			if on_close {
+				self.closing = Some((touch, index));
+			}else if index != self.inner.selected {
+				self.inner.selected = index;
+				if let Some(on_select) = &self.on_select {
+					let signal = on_select(app, &mut self.inner);
+					input_state.send_signal_from(id, signal);
+				}
+			}
+		}
+		if self.pressing.is_some_and(|touch| input_state.is_touch_released(touch)) {
+			self.pressing = None;
+		}
+
+		if let Some((touch, index)) = self.closing {
+			if input_state.is_touch_released(touch) {
+				self.closing = None;
+				let released_on = input_state.get_touch_pos(touch)
+					.filter(|touch_pos| strip.contains(*touch_pos))
+					.and_then(|touch_pos| self.tab_at(to_strip(touch_pos)));
+				if released_on == Some((index, true)) {
+					self.close_tab(app, input_state, id, index);
+				}
+			}
+		}
+
+		self.reveal_selected();
+		if self.drawn_size != Vec2::ZERO {
+			let maximum = self.inner.strip_scroll.maximum(self.overflow());
+			let last_maximum = std::mem::replace(&mut self.last_maximum, maximum);
+			self.inner.strip_scroll.keep_inside(maximum, last_maximum, false);
+		}
+
+		before != (self.hovered, self.close_hovered, self.inner.selected, self.inner.tabs.len()) || self.inner.strip_scroll.is_animating()
+	}
+
+	fn handle_child_layout(&mut self, childs: IndexMap<LayoutId, Vec2>, _: Rect, id: LayoutId) -> HashMap<LayoutId, Option<Rect>> {
+		self.children = childs.keys().copied().collect();
+		let Some((child, size)) = childs.get_index(self.inner.selected) else {
+			self.content_size = Vec2::ZERO;
+			return HashMap::new();
+		};
+		self.content_size = *size;
+
+		let strip_height = self.strip_height();
+		let padding = self.inner.content_padding;
+		let mut out = HashMap::new();
+		let size = match self.inner.size {
+			Some(widget_size) => {
+				let viewport = (widget_size - Vec2::y(strip_height)).max(Vec2::ZERO);
+				// clip the content below the strip.
+				out.insert(id, Some(Rect::from_lt_size(Vec2::y(strip_height), viewport)));
+				size.min((viewport - padding * 2.0).max(Vec2::ZERO))
+			},
+			None => *size,
+		};
+		out.insert(*child, Some(Rect::from_lt_size(Vec2::y(strip_height) + padding, size)));
+		out
+	}
+
+	fn scroll_by(&mut self, delta: Vec2, source: ScrollSource) -> Vec2 {
+		let overflow = self.overflow();
+		if !self.strip_hovered || overflow.x <= 0.0 {
+			return delta;
+		}
+		// the wheel scrolls vertically, which scrolls the strip as well.
+		let along = if delta.x != 0.0 { delta.x } else { delta.y };
+		let rest = - self.inner.strip_scroll.apply(Vec2::x(- along), source, overflow).x;
+		if rest == along {
+			delta
+		}else {
+			Vec2::ZERO
+		}
+	}
+
+	fn event_handle_strategy(&self) -> EventHandleStrategy {
+		if self.pressing.is_some() || self.closing.is_some() || self.inner.strip_scroll.is_animating() {
+			EventHandleStrategy::AlwaysSecondary
+		}else {
+			EventHandleStrategy::OnHover
+		}
+	}
+}
@@ -0,0 +1,779 @@
+//! A multi-line text editor widget.
+
+use std::any::Any;
+
+use time::Duration;
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::{layout::{Layout, LayoutId}, prelude::{AnimatedColor, Animatedf32, Color, FillMode, FontId, FontRole, ImeString, InputState, Key, Painter, Rect, Vec2, Vec4}, App};
+
+use super::{inputbox::{char_slice, grapheme_boundaries, step_graphemes, Pointer, PointerAmount}, styles::{BRIGHT_FACTOR, CONTENT_TEXT_SIZE, DEFAULT_PADDING, DEFAULT_ROUNDING, DISABLE_TEXT_COLOR, INPUT_BACKGROUND_COLOR, INPUT_BORDER_COLOR, PRIMARY_COLOR, SECONDARY_TEXT_COLOR, SELECTED_TEXT_COLOR}, EventHandleStrategy, ScrollAction, ScrollSource, Signal, SignalGenerator, Widget, DOUBLE_CLICK_THRESHOLD};
+
+/// A multi-line text editor with soft line wrapping and vertical scrolling.
+///
+/// Editing works like [`super::inputbox::InputBox`] and shares its [`Pointer`],
+/// `Enter` inserts a line break, `Escape` ends editing.
+pub struct TextArea<S: Signal, A: App<Signal = S>> {
+	/// The inner properties of the text area.
+	pub inner: TextAreaInner,
+	/// The signal to send when the text changes.
+	///
+	/// The signal will be constructed with the current text in the text area.
+	#[allow(clippy::type_complexity)]
+	pub on_change: Option<Box<dyn Fn(&mut TextAreaInner) -> S>>,
+	/// The signal to send when editing ends, by `Escape` or clicking outside.
+	///
+	/// The signal will be constructed with the current text in the text area.
+	#[allow(clippy::type_complexity)]
+	pub on_submit: Option<Box<dyn Fn(&mut TextAreaInner) -> S>>,
+	/// The general signal to send when the text area is interacted with.
+	pub signals: SignalGenerator<S, TextAreaInner, A>,
+	is_typing: bool,
+	hover_factor: Animatedf32,
+	last_click_time: Option<Duration>,
+	click_count: usize,
+	selecting_by: Option<u64>,
+	/// the actions needing the wrapped lines, resolved in [`Widget::draw`].
+	pending: Vec<PendingAction>,
+	/// the horizontal position kept while moving the pointer up and down.
+	preferred_x: Option<f32>,
+	follow_pointer: bool,
+	text_offset: Vec2,
+	view_size: Vec2,
+	content_size: Vec2,
+	line_height: f32,
+}
+
+/// An action on the text, resolved in [`Widget::draw`] where the wrapped lines are available.
+enum PendingAction {
+	/// Place the pointer at the position, extending the selection if `true`.
+	Place(Vec2, bool),
+	/// Select the word at the position.
+	Word(Vec2),
+	/// Select the line at the position.
+	Line(Vec2),
+	/// Move the pointer by the given amount of wrapped lines.
+	Vertical(isize, bool),
+	/// Move the pointer by the given amount of pages.
+	Page(isize, bool),
+	/// Move the pointer to the start of the wrapped line.
+	LineStart(bool),
+	/// Move the pointer to the end of the wrapped line.
+	LineEnd(bool),
+}
+
+/// The inner properties of the text area.
+pub struct TextAreaInner {
+	/// The placeholder text to display when the text area is empty.
+	pub placeholder: String,
+	/// The current text in the text area.
+	pub text: String,
+	/// The size of the visible text, without the padding.
+	pub size: Vec2,
+	/// The font id of the text area.
+	pub font: FontId,
+	/// The font role of the text area, see [`FontRole`].
+	pub font_role: Option<FontRole>,
+	/// The font size of the text area.
+	pub font_size: f32,
+	/// The current pointer position in the text area.
+	pub pointer: Pointer,
+	/// Whether to wrap the lines longer than the width, otherwise the text scrolls horizontally.
+	pub wrap: bool,
+	/// Whether to show the line numbers at the left side.
+	///
+	/// Only the first wrapped line of a line is numbered.
+	pub line_numbers: bool,
+	/// The current scroll position, the offset of the text from the top left of the content area.
+	///
+	/// While typing, it follows the pointer to keep it visible.
+	/// You can also set it manually, it will be clamped to the text size when drawing.
+	pub scroll_position: Vec2,
+	/// The background color of the text area.
+	pub background_color: FillMode,
+	/// The text color of the text area.
+	pub text_color: FillMode,
+	/// The color of the line numbers.
+	pub line_number_color: FillMode,
+	/// The border color of the text area.
+	pub border_color: AnimatedColor,
+	/// The padding of the text area.
+	pub padding: Vec2,
+	/// The roundings of the text area.
+	pub roundings: Vec4,
+	/// The color of the placeholder text.
+	pub placeholder_color: FillMode,
+	/// The color of the selected text.
+	pub selected_color: FillMode,
+}
+
+impl Default for TextAreaInner {
+	fn default() -> Self {
+		Self {
+			placeholder: "".to_string(),
+			text: "".to_string(),
+			size: Vec2::new(300.0, CONTENT_TEXT_SIZE * 8.0),
+			font: 0,
+			font_role: Some(FontRole::Body),
+			font_size: CONTENT_TEXT_SIZE,
+			pointer: Pointer::default(),
+			wrap: true,
+			line_numbers: false,
+			scroll_position: Vec2::ZERO,
+			background_color: FillMode::Color(INPUT_BACKGROUND_COLOR),
+			text_color: FillMode::Color(SECONDARY_TEXT_COLOR),
+			line_number_color: FillMode::Color(DISABLE_TEXT_COLOR),
+			border_color: AnimatedColor::default_with_value(INPUT_BORDER_COLOR),
+			padding: Vec2::same(DEFAULT_PADDING),
+			roundings: Vec4::same(DEFAULT_ROUNDING),
+			placeholder_color: FillMode::Color(DISABLE_TEXT_COLOR),
+			selected_color: FillMode::Color(SELECTED_TEXT_COLOR),
+		}
+	}
+}
+
+/// A line as drawn after wrapping, the indices are counted in chars.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct VisualLine {
+	start: usize,
+	/// the end of the line, without the line break.
+	end: usize,
+	/// the index of the line in the text if it is the first wrapped line of it.
+	number: Option<usize>,
+}
+
+impl<S: Signal, A: App<Signal = S>> Default for TextArea<S, A> {
+	fn default() -> Self {
+		Self {
+			inner: TextAreaInner::default(),
+			on_change: None,
+			on_submit: None,
+			signals: SignalGenerator::default(),
+			is_typing: false,
+			hover_factor: Animatedf32::default(),
+			last_click_time: None,
+			click_count: 0,
+			selecting_by: None,
+			pending: vec!(),
+			preferred_x: None,
+			follow_pointer: false,
+			text_offset: Vec2::ZERO,
+			view_size: Vec2::ZERO,
+			content_size: Vec2::ZERO,
+			line_height: 0.0,
+		}
+	}
+}
+
+impl<S: Signal, A: App<Signal = S>> TextArea<S, A> {
+	/// Create a new text area.
+	///
+	/// `font` is used when the [`FontRole::Body`] font is not registered, see [`Self::font_role`].
+	pub fn new(font: FontId, font_size: f32) -> Self {
+		Self {
+			inner: TextAreaInner {
+				font,
+				font_size,
+				..Default::default()
+			},
+			..Default::default()
+		}
+	}
+
+	/// Set the padding of the text area.
+	pub fn padding(self, padding: Vec2) -> Self {
+		Self { inner: TextAreaInner { padding, ..self.inner }, ..self }
+	}
+
+	/// Set the background color of the text area.
+	pub fn background_color(self, color: impl Into<FillMode>) -> Self {
+		Self { inner: TextAreaInner { background_color: color.into(), ..self.inner }, ..self }
+	}
+
+	/// Set the text color of the text area.
+	pub fn text_color(self, color: impl Into<FillMode>) -> Self {
+		Self { inner: TextAreaInner { text_color: color.into(), ..self.inner }, ..self }
+	}
+
+	/// Set the color of the line numbers.
+	pub fn line_number_color(self, color: impl Into<FillMode>) -> Self {
+		Self { inner: TextAreaInner { line_number_color: color.into(), ..self.inner }, ..self }
+	}
+
+	/// Set the placeholder text to display when the text area is empty.
+	pub fn placeholder(self, placeholder: impl Into<String>) -> Self {
+		Self { inner: TextAreaInner { placeholder: placeholder.into(), ..self.inner }, ..self }
+	}
+
+	/// Set the current text in the text area.
+	pub fn text(self, text: impl Into<String>) -> Self {
+		Self { inner: TextAreaInner { text: text.into(), ..self.inner }, ..self }
+	}
+
+	/// Set the size of the visible text, without the padding.
+	pub fn size(self, size: Vec2) -> Self {
+		Self { inner: TextAreaInner { size, ..self.inner }, ..self }
+	}
+
+	/// Set the font of the text area.
+	/// 
+	/// Clears the font role, so the font is used even if the theme registers one, see [`Self::font_role`].
+	pub fn font(self, font: FontId) -> Self {
+		Self { inner: TextAreaInner { font, font_role: None, ..self.inner }, ..self }
+	}
+
+	/// Set the font role of the text area, `None` to always use the font id.
+	pub fn font_role(self, font_role: Option<FontRole>) -> Self {
+		Self { inner: TextAreaInner { font_role, ..self.inner }, ..self }
+	}
+
+	/// Set the text area up as a code editor, in the [`FontRole::Monospace`] font with line numbers and without wrapping.
+	pub fn code(self) -> Self {
+		Self { inner: TextAreaInner { font_role: Some(FontRole::Monospace), line_numbers: true, wrap: false, ..self.inner }, ..self }
+	}
+
+	/// Set whether to wrap the lines longer than the width.
+	pub fn wrap(self, wrap: bool) -> Self {
+		Self { inner: TextAreaInner { wrap, ..self.inner }, ..self }
+	}
+
+	/// Set whether to show the line numbers.
+	pub fn line_numbers(self, line_numbers: bool) -> Self {
+		Self { inner: TextAreaInner { line_numbers, ..self.inner }, ..self }
+	}
+
+	/// Set the current pointer position in the text area.
+	pub fn pointer(self, pointer: Pointer) -> Self {
+		Self { inner: TextAreaInner { pointer, ..self.inner }, ..self }
+	}
+
+	/// Set the signal to send when the text changes.
+	pub fn on_change(self, on_change: impl Fn(&mut TextAreaInner) -> S + 'static) -> Self {
+		Self {
+			on_change: Some(Box::new(on_change)),
+			..self
+		}
+	}
+
+	/// Set the signal to send when editing ends.
+	pub fn on_submit(self, on_submit: impl Fn(&mut TextAreaInner) -> S + 'static) -> Self {
+		Self {
+			on_submit: Some(Box::new(on_submit)),
+			..self
+		}
+	}
+
+	fn resolved_font(&self, painter: &Painter) -> FontId {
+		painter.resolve_font(self.inner.font_role, self.inner.font)
+	}
+
+	fn text_width(&self, painter: &Painter, text: &str) -> f32 {
+		if text.is_empty() {
+			return 0.0;
+		}
+		painter.text_size_pointer(self.resolved_font(painter), self.inner.font_size, text).unwrap_or_default().x
+	}
+
+	/// Split the text into the lines as drawn, wrapping at word boundaries when the width is given.
+	fn wrap_lines(&self, text: &str, painter: &Painter, width: Option<f32>) -> Vec<VisualLine> {
+		let mut out = vec!();
+		let mut base = 0;
+		for (number, line) in text.split('\n').enumerate() {
+			let len = line.chars().count();
+			let mut start = base;
+			let mut index = base;
+			let mut line_width = 0.0;
+			let mut number = Some(number);
+			let mut push = |start: usize, end: usize| out.push(VisualLine { start, end, number: number.take() });
+			if let Some(width) = width.filter(|width| *width > 0.0) {
+				for segment in line.split_word_bounds() {
+					let segment_width = self.text_width(painter, segment);
+					let is_space = segment.trim().is_empty();
+					// spaces may hang over the end of a line.
+					if line_width + segment_width > width && index > start && !is_space {
+						push(start, index);
+						start = index;
+						line_width = 0.0;
+					}
+					if segment_width > width && !is_space {
+						// a single word longer than the line is broken between graphemes.
+						for grapheme in segment.graphemes(true) {
+							let grapheme_width = self.text_width(painter, grapheme);
+							if line_width + grapheme_width > width && index > start {
+								push(start, index);
+								start = index;
+								line_width = 0.0;
+							}
+							line_width += grapheme_width;
+							index += grapheme.chars().count();
+						}
+						continue;
+					}
+					line_width += segment_width;
+					index += segment.chars().count();
+				}
+			}
+			push(start, base + len);
+			base += len + 1;
+		}
+		out
+	}
+
+	/// Get the wrapped line the index is drawn on, an index at a wrapping point belongs to the later line.
+	fn line_of(lines: &[VisualLine], index: usize) -> usize {
+		lines.iter().rposition(|line| line.start <= index).unwrap_or(0)
+	}
+
+	/// Get the last index the pointer can be placed at on the wrapped line.
+	fn line_limit(text: &str, lines: &[VisualLine], line: usize) -> usize {
+		let current = lines[line];
+		match lines.get(line + 1) {
+			// the end of a wrapped line is drawn at the start of the next one.
+			Some(next) if next.number.is_none() && current.end > current.start => step_graphemes(text, current.end, -1),
+			_ => current.end,
+		}
+	}
+
+	/// Get the position of the index relative to the top left of the text.
+	fn pos_of(&self, text: &str, lines: &[VisualLine], index: usize, painter: &Painter) -> Vec2 {
+		let line = Self::line_of(lines, index);
+		let x = self.text_width(painter, char_slice(text, lines[line].start, index));
+		Vec2::new(x, line as f32 * self.line_height)
+	}
+
+	/// Get the index closest to the position relative to the top left of the text.
+	fn index_at(&self, text: &str, lines: &[VisualLine], pos: Vec2, painter: &Painter) -> usize {
+		let line = if self.line_height > 0.0 {
+			((pos.y / self.line_height).max(0.0) as usize).min(lines.len() - 1)
+		}else {
+			0
+		};
+		self.index_at_x(text, lines, line, pos.x, painter)
+	}
+
+	fn index_at_x(&self, text: &str, lines: &[VisualLine], line: usize, x: f32, painter: &Painter) -> usize {
+		let start = lines[line].start;
+		let limit = Self::line_limit(text, lines, line);
+		let line_text = char_slice(text, start, limit);
+		let mut previous = (0, 0.0);
+		for boundary in grapheme_boundaries(line_text).into_iter().skip(1) {
+			let width = self.text_width(painter, char_slice(line_text, 0, boundary));
+			if width >= x {
+				// pick the nearer side of the grapheme.
+				return start + if x - previous.1 < width - x { previous.0 } else { boundary };
+			}
+			previous = (boundary, width);
+		}
+		start + previous.0
+	}
+
+	fn resolve_pending(&mut self, text: &str, lines: &[VisualLine], painter: &Painter) {
+		for action in std::mem::take(&mut self.pending) {
+			let current = self.inner.pointer.current_index_utf8();
+			let vertical = |this: &mut Self, amount: isize, with_selection: bool| {
+				let pos = this.pos_of(text, lines, current, painter);
+				let x = *this.preferred_x.get_or_insert(pos.x);
+				let line = (Self::line_of(lines, current) as isize + amount).clamp(0, lines.len() as isize - 1) as usize;
+				let index = this.index_at_x(text, lines, line, x, painter);
+				this.inner.pointer.move_to(text, index, with_selection);
+			};
+			match action {
+				PendingAction::Place(pos, with_selection) => {
+					let index = self.index_at(text, lines, pos, painter);
+					self.inner.pointer.move_to(text, index, with_selection);
+				},
+				PendingAction::Word(pos) => {
+					let index = self.index_at(text, lines, pos, painter);
+					self.inner.pointer.move_to(text, index, false);
+					self.inner.pointer.select_word(text);
+				},
+				PendingAction::Line(pos) => {
+					let index = self.index_at(text, lines, pos, painter);
+					self.inner.pointer.move_to(text, index, false);
+					self.inner.pointer.select_line(text);
+				},
+				PendingAction::Vertical(amount, with_selection) => vertical(self, amount, with_selection),
+				PendingAction::Page(amount, with_selection) => {
+					let page = if self.line_height > 0.0 { (self.view_size.y / self.line_height).floor().max(1.0) as isize } else { 1 };
+					vertical(self, amount * page, with_selection);
+				},
+				PendingAction::LineStart(with_selection) => {
+					let line = Self::line_of(lines, current);
+					self.inner.pointer.move_to(text, lines[line].start, with_selection);
+				},
+				PendingAction::LineEnd(with_selection) => {
+					let line = Self::line_of(lines, current);
+					self.inner.pointer.move_to(text, Self::line_limit(text, lines, line), with_selection);
+				},
+			}
+			if !matches!(action, PendingAction::Vertical(..) | PendingAction::Page(..)) {
+				self.preferred_x = None;
+			}
+		}
+	}
+
+	/// Get the rects of the selected text on every wrapped line, relative to the top left of the text.
+	fn selection_rects(&self, text: &str, lines: &[VisualLine], painter: &Painter) -> Vec<Rect> {
+		let (start, end) = self.inner.pointer.selection();
+		let newline_width = self.inner.font_size / 3.0;
+		lines.iter().enumerate().filter_map(|(index, line)| {
+			let from = start.max(line.start);
+			let to = end.min(line.end);
+			// the line break is selected if the selection goes on to the next line.
+			let has_break = end > line.end && start <= line.end && lines.get(index + 1).is_some_and(|next| next.number.is_some());
+			if from > to || (from == to && !has_break) {
+				return None;
+			}
+			let x = self.text_width(painter, char_slice(text, line.start, from));
+			let width = self.text_width(painter, char_slice(text, from, to)) + if has_break { newline_width } else { 0.0 };
+			Some(Rect::new(x, index as f32 * self.line_height, width, self.line_height))
+		}).collect()
+	}
+
+	/// Scroll the text so the caret stays inside the view.
+	fn scroll_to_caret(&mut self, caret: Rect) {
+		let view = self.view_size;
+		let margin = Vec2::new(self.inner.font_size.min(view.x / 4.0), 0.0);
+		let least = caret.rb() + margin - view;
+		let most = caret.lt() - margin;
+		self.inner.scroll_position = self.inner.scroll_position.max(least).min(most.max(least));
+	}
+
+	fn max_scroll(&self) -> Vec2 {
+		(self.content_size - self.view_size).max(Vec2::ZERO)
+	}
+
+	fn update_border_color(&mut self) {
+		self.inner.border_color.set(if self.is_typing {
+			PRIMARY_COLOR + BRIGHT_FACTOR * Color::WHITE
+		}else {
+			INPUT_BORDER_COLOR
+		});
+	}
+
+	fn submit(&mut self, input_state: &mut InputState<S>, id: LayoutId) {
+		self.is_typing = false;
+		self.update_border_color();
+		if let Some(on_submit) = &self.on_submit {
+			let signal = on_submit(&mut self.inner);
+			input_state.send_signal_from(id, signal);
+		}
+	}
+
+	fn handle_keys(&mut self, input_state: &mut InputState<S>) {
+		let modifiers = input_state.modifiers();
+		let shift = modifiers.shift;
+		let word = modifiers.ctrl || modifiers.alt;
+		let amount = |amount: isize| if word { PointerAmount::Word(amount) } else { PointerAmount::Char(amount) };
+		let mut moved = false;
+
+		if input_state.is_key_pressed(Key::ArrawLeft) {
+			self.inner.pointer.move_by(&self.inner.text, amount(-1), shift);
+			moved = true;
+		}
+		if input_state.is_key_pressed(Key::ArrawRight) {
+			self.inner.pointer.move_by(&self.inner.text, amount(1), shift);
+			moved = true;
+		}
+		if moved {
+			self.preferred_x = None;
+		}
+		if input_state.is_key_pressed(Key::ArrawUp) {
+			self.pending.push(PendingAction::Vertical(-1, shift));
+		}
+		if input_state.is_key_pressed(Key::ArrawDown) {
+			self.pending.push(PendingAction::Vertical(1, shift));
+		}
+		if input_state.is_key_pressed(Key::PageUp) {
+			self.pending.push(PendingAction::Page(-1, shift));
+		}
+		if input_state.is_key_pressed(Key::PageDown) {
+			self.pending.push(PendingAction::Page(1, shift));
+		}
+		if input_state.is_key_pressed(Key::Home) {
+			if modifiers.primary() {
+				self.inner.pointer.move_to(&self.inner.text, 0, shift);
+			}else {
+				self.pending.push(PendingAction::LineStart(shift));
+			}
+		}
+		if input_state.is_key_pressed(Key::End) {
+			if modifiers.primary() {
+				self.inner.pointer.move_to(&self.inner.text, self.inner.text.chars().count(), shift);
+			}else {
+				self.pending.push(PendingAction::LineEnd(shift));
+			}
+		}
+		if input_state.is_key_pressed(Key::KeyA) && modifiers.primary() {
+			self.inner.pointer.select_all(&self.inner.text);
+		}
+
+		let mut cut = false;
+		if input_state.is_key_pressed(Key::Backspace) {
+			if word {
+				self.inner.pointer.delete_word(&mut self.inner.text);
+			}else {
+				self.inner.pointer.delete(&mut self.inner.text);
+			}
+		}
+		if input_state.is_key_pressed(Key::Delete) {
+			if shift && self.inner.pointer.has_selected_text() {
+				cut = true;
+			}else if word {
+				self.inner.pointer.delete_word_forward(&mut self.inner.text);
+			}else {
+				self.inner.pointer.delete_forward(&mut self.inner.text);
+			}
+		}
+
+		let insert_pressed = input_state.is_key_pressed(Key::Insert);
+		if (modifiers.primary() && input_state.is_key_pressed(Key::KeyC)) || (modifiers.ctrl && insert_pressed) {
+			let text = self.inner.pointer.get_selected_text(&self.inner.text);
+			input_state.copy_text(text);
+		}
+		if cut || (modifiers.primary() && input_state.is_key_pressed(Key::KeyX)) {
+			let text = self.inner.pointer.get_selected_text(&self.inner.text);
+			input_state.copy_text(text);
+			self.inner.pointer.delete_selected_text(&mut self.inner.text);
+		}
+		if (modifiers.primary() && input_state.is_key_pressed(Key::KeyV)) || (shift && insert_pressed) {
+			input_state.request_paste_text();
+		}
+	}
+}
+
+impl<S: Signal, A: App<Signal = S>> Widget for TextArea<S, A> {
+	type Signal = S;
+	type Application = A;
+
+	fn size(&self, _: LayoutId, _: &Painter, _: &Layout<Self::Signal, A>) -> Vec2 {
+		self.inner.size + self.inner.padding * 2.0
+	}
+
+	fn draw(&mut self, painter: &mut Painter, size: Vec2) {
+		let font = self.resolved_font(painter);
+		let font_size = self.inner.font_size;
+		self.line_height = painter.line_height(font, font_size).unwrap_or(font_size);
+		let is_placeholder = self.inner.text.is_empty() && !self.is_typing;
+		let text = if is_placeholder { self.inner.placeholder.clone() } else { self.inner.text.clone() };
+
+		let stroke = 2.0;
+		let mut bg_color = self.inner.background_color.clone();
+		bg_color.brighter(self.hover_factor.value() * BRIGHT_FACTOR);
+		painter.set_fill_mode(bg_color);
+		painter.draw_rect(Rect::from_size(size), self.inner.roundings);
+		painter.set_fill_mode(self.inner.border_color.value() + self.hover_factor.value() * BRIGHT_FACTOR * Color::WHITE);
+		painter.draw_stroked_rect(Rect::from_size(size).shrink(Vec2::same(stroke / 2.0)), self.inner.roundings, stroke);
+
+		let line_count = self.inner.text.split('\n').count();
+		let gutter = if self.inner.line_numbers {
+			self.text_width(painter, &"0".repeat(line_count.to_string().len())) + self.inner.padding.x
+		}else {
+			0.0
+		};
+		let caret_size = Vec2::new(2.0, font_size);
+		self.view_size = (size - self.inner.padding * 2.0 - Vec2::x(gutter)).max(Vec2::ZERO);
+		let lines = self.wrap_lines(&text, painter, if self.inner.wrap { Some(self.view_size.x - caret_size.x) } else { None });
+
+		if !is_placeholder {
+			self.resolve_pending(&text, &lines, painter);
+		}
+		self.pending.clear();
+
+		let content_width = if self.inner.wrap {
+			self.view_size.x
+		}else {
+			lines.iter().map(|line| self.text_width(painter, char_slice(&text, line.start, line.end))).fold(0.0, f32::max) + caret_size.x
+		};
+		self.content_size = Vec2::new(content_width, lines.len() as f32 * self.line_height);
+
+		let caret = self.pos_of(&text, &lines, self.inner.pointer.current_index_utf8(), painter);
+		if self.follow_pointer && self.is_typing {
+			self.scroll_to_caret(Rect::from_lt_size(caret, Vec2::new(caret_size.x, self.line_height)));
+		}
+		self.follow_pointer = false;
+		self.inner.scroll_position = self.inner.scroll_position.min(self.max_scroll()).max(Vec2::ZERO);
+
+		let content_lt = self.inner.padding + Vec2::x(gutter);
+		let text_pos = content_lt - self.inner.scroll_position;
+		self.text_offset = text_pos;
+		let clip_rect = painter.clip_rect();
+		let first = (self.inner.scroll_position.y / self.line_height.max(1.0)).floor() as usize;
+		let visible = (self.view_size.y / self.line_height.max(1.0)).ceil() as usize + 1;
+
+		if self.inner.line_numbers {
+			painter.set_clip_rect(clip_rect & Rect::from_lt_size(painter.releative_to() + Vec2::y(self.inner.padding.y), Vec2::new(content_lt.x, self.view_size.y)));
+			painter.set_fill_mode(self.inner.line_number_color.clone());
+			for (index, line) in lines.iter().enumerate().skip(first).take(visible) {
+				let Some(number) = line.number else { continue };
+				let label = (number + 1).to_string();
+				let x = content_lt.x - self.inner.padding.x / 2.0 - self.text_width(painter, &label);
+				painter.draw_text(Vec2::new(x, text_pos.y + index as f32 * self.line_height), font, font_size, label);
+			}
+		}
+
+		painter.set_clip_rect(clip_rect & Rect::from_lt_size(painter.releative_to() + content_lt, self.view_size));
+		if self.is_typing && self.inner.pointer.has_selected_text() {
+			painter.set_fill_mode(self.inner.selected_color.clone());
+			for rect in self.selection_rects(&text, &lines, painter) {
+				painter.draw_rect(rect.move_by(text_pos), Vec4::same(font_size / 8.0));
+			}
+		}
+		let mut text_color = if is_placeholder { self.inner.placeholder_color.clone() } else { self.inner.text_color.clone() };
+		if !self.is_typing {
+			text_color.brighter(self.hover_factor.value() * BRIGHT_FACTOR);
+		}
+		painter.set_fill_mode(text_color);
+		for (index, line) in lines.iter().enumerate().skip(first).take(visible) {
+			let line_text = char_slice(&text, line.start, line.end);
+			if !line_text.is_empty() {
+				painter.draw_text(text_pos + Vec2::y(index as f32 * self.line_height), font, font_size, line_text);
+			}
+		}
+		if self.is_typing {
+			painter.draw_rect(Rect::from_lt_size(caret + text_pos, Vec2::new(caret_size.x, self.line_height)), Vec4::ZERO);
+		}
+		painter.set_clip_rect(clip_rect);
+	}
+
+	fn handle_event(&mut self, app: &mut A, input_state: &mut InputState<Self::Signal>, id: LayoutId, area: Rect, pos: Vec2) -> bool {
+		let was_typing = self.is_typing;
+		self.signals.generate_signals(app, &mut self.inner, input_state, id, area, true, false);
+
+		if input_state.is_touch_in(area) {
+			self.hover_factor.set(1.0);
+		}else {
+			self.hover_factor.set(0.0);
+		}
+
+		// a drag selection released outside the area should not end editing.
+		let selection_released = self.selecting_by.is_some_and(|touch| input_state.is_touch_released(touch));
+		if input_state.is_any_touch_released() && !input_state.is_touch_in(area) && self.is_typing && !selection_released {
+			self.submit(input_state, id);
+		}
+
+		match self.signals.dragging_by() {
+			Some(touch) if self.selecting_by != Some(touch) => {
+				self.selecting_by = Some(touch);
+				let current = input_state.program_running_time();
+				self.click_count = if self.last_click_time.is_some_and(|last| current - last < DOUBLE_CLICK_THRESHOLD) {
+					self.click_count % 3 + 1
+				}else {
+					1
+				};
+				self.last_click_time = Some(current);
+				if let Some(touch_pos) = input_state.get_touch_pos(touch) {
+					let touch_pos = touch_pos - pos - self.text_offset;
+					self.pending.push(match self.click_count {
+						1 => PendingAction::Place(touch_pos, self.is_typing && input_state.modifiers().shift),
+						2 => PendingAction::Word(touch_pos),
+						_ => PendingAction::Line(touch_pos),
+					});
+				}
+				self.is_typing = true;
+				self.follow_pointer = true;
+				self.update_border_color();
+			},
+			Some(touch) => {
+				if self.click_count == 1 && input_state.drag_delta(touch) != Vec2::ZERO {
+					if let Some(touch_pos) = input_state.get_touch_pos(touch) {
+						self.pending.push(PendingAction::Place(touch_pos - pos - self.text_offset, true));
+						self.follow_pointer = true;
+					}
+				}
+			},
+			None => self.selecting_by = None,
+		}
+
+		if self.is_typing {
+			let previous_text = self.inner.text.clone();
+			let previous_pointer = self.inner.pointer.selection();
+			let pending = self.pending.len();
+
+			let input = match input_state.get_input_string() {
+				ImeString::ImeOff(input) => ImeString::ImeOff(input.replace('\r', "")),
+				input => input,
+			};
+			self.inner.pointer.insert_text(&mut self.inner.text, input, &None);
+			self.handle_keys(input_state);
+
+			if self.inner.text != previous_text || self.inner.pointer.selection() != previous_pointer || self.pending.len() != pending {
+				self.follow_pointer = true;
+			}
+			if self.inner.text != previous_text {
+				self.preferred_x = None;
+				if let Some(on_change) = &self.on_change {
+					let signal = on_change(&mut self.inner);
+					input_state.send_signal_from(id, signal);
+				}
+			}
+
+			if input_state.is_key_pressed(Key::Escape) {
+				self.submit(input_state, id);
+			}
+		}
+
+		if self.is_typing != was_typing {
+			if self.is_typing {
+				input_state.show_keyboard(id);
+				// only scroll away from a keyboard actually covering the window.
+				if input_state.keyboard_inset() > 0.0 {
+					input_state.request_visible(id, area);
+				}
+			}else {
+				input_state.hide_keyboard(id);
+			}
+		}
+
+		self.is_typing || !self.pending.is_empty() || self.inner.border_color.is_animating() || self.hover_factor.is_animating()
+	}
+
+	fn scroll_by(&mut self, delta: Vec2, _: ScrollSource) -> Vec2 {
+		// the drag is selecting text, dont scroll the parents.
+		if self.selecting_by.is_some() {
+			return Vec2::ZERO;
+		}
+		let before = self.inner.scroll_position;
+		self.inner.scroll_position = (before - delta).min(self.max_scroll()).max(Vec2::ZERO);
+		delta - (before - self.inner.scroll_position)
+	}
+
+	fn scroll_action(&mut self, action: ScrollAction) -> bool {
+		let before = self.inner.scroll_position;
+		let line = self.line_height;
+		let page = self.view_size.y;
+		let target = match action {
+			ScrollAction::LineUp => before - Vec2::y(line),
+			ScrollAction::LineDown => before + Vec2::y(line),
+			ScrollAction::LineLeft => before - Vec2::x(line),
+			ScrollAction::LineRight => before + Vec2::x(line),
+			ScrollAction::PageUp => before - Vec2::y(page),
+			ScrollAction::PageDown => before + Vec2::y(page),
+			ScrollAction::Home => Vec2::new(before.x, 0.0),
+			ScrollAction::End => Vec2::new(before.x, self.max_scroll().y),
+		};
+		self.inner.scroll_position = target.min(self.max_scroll()).max(Vec2::ZERO);
+		self.inner.scroll_position != before
+	}
+
+	fn event_handle_strategy(&self) -> EventHandleStrategy {
+		if self.is_typing {
+			EventHandleStrategy::AlwaysSecondary
+		}else {
+			EventHandleStrategy::OnHover
+		}
+	}
+
+	fn save_state(&self) -> Option<Box<dyn Any>> {
+		Some(Box::new((self.hover_factor.clone(), self.inner.border_color.clone(), self.inner.scroll_position)))
+	}
+
+	fn restore_state(&mut self, state: Box<dyn Any>) {
+		if let Ok(state) = state.downcast::<(Animatedf32, AnimatedColor, Vec2)>() {
+			let (hover_factor, border_color, scroll_position) = *state;
+			self.hover_factor = hover_factor;
+			self.inner.border_color.continue_from(&border_color);
+			self.inner.scroll_position = scroll_position;
+		}
+	}
+}
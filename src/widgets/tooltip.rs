@@ -0,0 +1,174 @@
+//! A small label shown near the pointer while it rests on a widget.
+//!
+//! Attach a tooltip to any widget with [`crate::layout::Layout::set_tooltip`].
+
+use std::marker::PhantomData;
+
+use time::Duration;
+
+use crate::{layout::{Layout, LayoutId}, prelude::{FillMode, FontId, FontRole, InputState, Painter, Rect, Vec2, Vec4}, App};
+
+use super::{styles::{CARD_BORDER_COLOR, DEFAULT_PADDING, DEFAULT_ROUNDING, INPUT_BACKGROUND_COLOR, PRIMARY_TEXT_COLOR}, Signal, Widget, DEFAULT_HOVER_DELAY};
+
+/// The default font size of tooltips, a bit smaller than the content.
+pub const TOOLTIP_TEXT_SIZE: f32 = crate::render::font::EM * 0.875;
+/// Where a tooltip is placed relative to the pointer, below the usual cursor icons.
+pub const TOOLTIP_OFFSET: Vec2 = Vec2::new(12.0, 20.0);
+
+/// A tooltip, shown as an overlay near the pointer once it rested on the widget for [`TooltipInner::delay`].
+///
+/// The tooltip hides when the pointer leaves the widget or presses, and shows again after the pointer leaves and comes back.
+/// Since it is an overlay, it is not clipped by the containers of the widget.
+///
+/// ```no_run
+/// # use nablo_ui::prelude::*;
+/// # struct MyApp;
+/// # impl App for MyApp {
+/// #     type Signal = ();
+/// #     fn on_start(&mut self, _: &mut Context<(), Self>) {}
+/// #     fn on_signal(&mut self, _: &mut Context<(), Self>, _: SignalWrapper<()>) {}
+/// # }
+/// fn add_save_button(layout: &mut Layout<(), MyApp>) {
+///     let button = layout.add_widget(ROOT_LAYOUT_ID, Button::new("Save")).unwrap();
+///     layout.set_tooltip(button, Tooltip::new("Save the document (Ctrl+S)"));
+/// }
+/// ```
+pub struct Tooltip<S: Signal, A: App<Signal = S>> {
+	/// The inner properties of the tooltip.
+	pub inner: TooltipInner,
+	_marker: PhantomData<(S, A)>,
+}
+
+/// The inner properties of a tooltip.
+#[derive(Clone, Debug)]
+pub struct TooltipInner {
+	/// The text of the tooltip, may contain line breaks.
+	pub text: String,
+	/// How long the pointer rests on the widget before the tooltip shows.
+	pub delay: Duration,
+	/// The font of the text.
+	pub font: FontId,
+	/// The font role of the text, see [`FontRole`].
+	pub font_role: Option<FontRole>,
+	/// The font size of the text.
+	pub font_size: f32,
+	/// The color of the text.
+	pub text_color: FillMode,
+	/// The background color of the tooltip.
+	pub background_color: FillMode,
+	/// The border color of the tooltip.
+	pub border_color: FillMode,
+	/// The padding around the text.
+	pub padding: Vec2,
+	/// The roundings of the tooltip.
+	pub roundings: Vec4,
+}
+
+impl<S: Signal, A: App<Signal = S>> Clone for Tooltip<S, A> {
+	fn clone(&self) -> Self {
+		Self {
+			inner: self.inner.clone(),
+			_marker: PhantomData,
+		}
+	}
+}
+
+impl<S: Signal, A: App<Signal = S>> Tooltip<S, A> {
+	/// Create a tooltip with the given text.
+	pub fn new(text: impl Into<String>) -> Self {
+		Self {
+			inner: TooltipInner {
+				text: text.into(),
+				delay: DEFAULT_HOVER_DELAY,
+				font: 0,
+				font_role: Some(FontRole::Body),
+				font_size: TOOLTIP_TEXT_SIZE,
+				text_color: FillMode::from(PRIMARY_TEXT_COLOR),
+				background_color: FillMode::from(INPUT_BACKGROUND_COLOR),
+				border_color: FillMode::from(CARD_BORDER_COLOR),
+				padding: Vec2::new(DEFAULT_PADDING, DEFAULT_PADDING / 2.0),
+				roundings: Vec4::same(DEFAULT_ROUNDING / 2.0),
+			},
+			_marker: PhantomData,
+		}
+	}
+
+	/// Set the text of the tooltip.
+	pub fn text(self, text: impl Into<String>) -> Self {
+		Self { inner: TooltipInner { text: text.into(), ..self.inner }, ..self }
+	}
+
+	/// Set how long the pointer rests on the widget before the tooltip shows.
+	pub fn delay(self, delay: Duration) -> Self {
+		Self { inner: TooltipInner { delay, ..self.inner }, ..self }
+	}
+
+	/// Set the font of the text.
+	/// 
+	/// Clears the font role, so the font is used even if the theme registers one, see [`Self::font_role`].
+	pub fn font(self, font: FontId) -> Self {
+		Self { inner: TooltipInner { font, font_role: None, ..self.inner }, ..self }
+	}
+
+	/// Set the font role of the text, see [`FontRole`].
+	pub fn font_role(self, font_role: Option<FontRole>) -> Self {
+		Self { inner: TooltipInner { font_role, ..self.inner }, ..self }
+	}
+
+	/// Set the font size of the text.
+	pub fn font_size(self, font_size: f32) -> Self {
+		Self { inner: TooltipInner { font_size, ..self.inner }, ..self }
+	}
+
+	/// Set the color of the text.
+	pub fn text_color(self, text_color: impl Into<FillMode>) -> Self {
+		Self { inner: TooltipInner { text_color: text_color.into(), ..self.inner }, ..self }
+	}
+
+	/// Set the background color of the tooltip.
+	pub fn background_color(self, background_color: impl Into<FillMode>) -> Self {
+		Self { inner: TooltipInner { background_color: background_color.into(), ..self.inner }, ..self }
+	}
+
+	/// Set the border color of the tooltip.
+	pub fn border_color(self, border_color: impl Into<FillMode>) -> Self {
+		Self { inner: TooltipInner { border_color: border_color.into(), ..self.inner }, ..self }
+	}
+
+	/// Set the padding around the text.
+	pub fn padding(self, padding: impl Into<Vec2>) -> Self {
+		Self { inner: TooltipInner { padding: padding.into(), ..self.inner }, ..self }
+	}
+
+	/// Set the roundings of the tooltip.
+	pub fn roundings(self, roundings: impl Into<Vec4>) -> Self {
+		Self { inner: TooltipInner { roundings: roundings.into(), ..self.inner }, ..self }
+	}
+}
+
+impl<S: Signal, A: App<Signal = S>> Widget for Tooltip<S, A> {
+	type Signal = S;
+	type Application = A;
+
+	fn size(&self, _: LayoutId, painter: &Painter, _: &Layout<Self::Signal, Self::Application>) -> Vec2 {
+		let font = painter.resolve_font(self.inner.font_role, self.inner.font);
+		painter.text_size(font, self.inner.font_size, &self.inner.text).unwrap_or_default() + self.inner.padding * 2.0
+	}
+
+	fn draw(&mut self, painter: &mut Painter, size: Vec2) {
+		let stroke = 1.0;
+		painter.set_fill_mode(self.inner.background_color.clone());
+		painter.draw_rect(Rect::from_size(size), self.inner.roundings);
+		painter.set_fill_mode(self.inner.border_color.clone());
+		painter.draw_stroked_rect(Rect::from_size(size).shrink(Vec2::same(stroke / 2.0)), self.inner.roundings, stroke);
+
+		let font = painter.resolve_font(self.inner.font_role, self.inner.font);
+		painter.set_fill_mode(self.inner.text_color.clone());
+		painter.draw_text(self.inner.padding, font, self.inner.font_size, &self.inner.text);
+	}
+
+	fn handle_event(&mut self, _: &mut A, _: &mut InputState<Self::Signal>, _: LayoutId, _: Rect, _: Vec2) -> bool {
+		// shown and hidden by the layout.
+		false
+	}
+}
@@ -0,0 +1,158 @@
+//! A widget showing content rendered with wgpu, see [`crate::render::viewport`].
+
+use crate::{layout::{Layout, LayoutId}, prelude::{InputState, Painter, Rect, Vec2, Vec4}, render::viewport::{ViewportId, ViewportInput}, window::event::WindowEvent, App};
+
+use super::{EventHandleStrategy, ScrollSource, Signal, SignalGenerator, Widget};
+
+/// A widget showing the content of a viewport added by [`crate::Context::add_viewport`].
+///
+/// The input on the widget is forwarded to the renderer of the viewport, see [`ViewportInput`].
+pub struct WgpuViewport<S: Signal, A: App<Signal = S>> {
+	/// The inner properties of the viewport.
+	pub inner: WgpuViewportInner,
+	/// The signals generated by the viewport.
+	pub signals: SignalGenerator<S, WgpuViewportInner, A>,
+	hover_pos: Option<Vec2>,
+	/// the touches pressed on the viewport and not released yet.
+	pressing: Vec<u64>,
+	focused: bool,
+	/// the wheel delta captured by [`Widget::scroll_by`], forwarded in the next event handling.
+	wheel: Vec2,
+}
+
+/// The inner properties of a viewport widget.
+pub struct WgpuViewportInner {
+	/// The viewport to show.
+	pub viewport: ViewportId,
+	/// The size of the viewport.
+	pub size: Vec2,
+	/// The roundings of the viewport.
+	pub roundings: Vec4,
+	/// Whether to render the viewport every frame, for animated content.
+	///
+	/// Otherwise the viewport is only rendered when the widget is redrawn, e.g. after it forwarded an input.
+	pub continuous: bool,
+}
+
+impl<S: Signal, A: App<Signal = S>> WgpuViewport<S, A> {
+	/// Create a new viewport widget rendering every frame.
+	pub fn new(viewport: ViewportId, size: Vec2) -> Self {
+		Self {
+			inner: WgpuViewportInner {
+				viewport,
+				size,
+				roundings: Vec4::ZERO,
+				continuous: true,
+			},
+			signals: SignalGenerator::default(),
+			hover_pos: None,
+			pressing: vec!(),
+			focused: false,
+			wheel: Vec2::ZERO,
+		}
+	}
+
+	/// Set the size of the viewport.
+	pub fn size(self, size: Vec2) -> Self {
+		Self { inner: WgpuViewportInner { size, ..self.inner }, ..self }
+	}
+
+	/// Set the roundings of the viewport.
+	pub fn roundings(self, roundings: impl Into<Vec4>) -> Self {
+		Self { inner: WgpuViewportInner { roundings: roundings.into(), ..self.inner }, ..self }
+	}
+
+	/// Set whether to render the viewport every frame.
+	pub fn continuous(self, continuous: bool) -> Self {
+		Self { inner: WgpuViewportInner { continuous, ..self.inner }, ..self }
+	}
+
+	fn forward(&self, input_state: &mut InputState<S>, input: ViewportInput) {
+		input_state.viewport_inputs.push((self.inner.viewport, input));
+	}
+}
+
+impl<S: Signal, A: App<Signal = S>> Widget for WgpuViewport<S, A> {
+	type Signal = S;
+	type Application = A;
+
+	fn size(&self, _: LayoutId, _: &Painter, _: &Layout<Self::Signal, A>) -> Vec2 {
+		self.inner.size
+	}
+
+	fn draw(&mut self, painter: &mut Painter, size: Vec2) {
+		painter.draw_viewport(Rect::from_size(size), self.inner.viewport, self.inner.roundings);
+	}
+
+	fn handle_event(&mut self, app: &mut A, input_state: &mut InputState<Self::Signal>, id: LayoutId, area: Rect, pos: Vec2) -> bool {
+		let inputs = input_state.viewport_inputs.len();
+		// taken before the signals, which mark the touches as used.
+		let pressed = input_state.get_touch_pressed_on(area);
+		self.signals.generate_signals(app, &mut self.inner, input_state, id, area, false, false);
+
+		let hover_pos = input_state.hover_pos().filter(|hover| area.contains(*hover)).map(|hover| hover - pos);
+		if hover_pos != self.hover_pos {
+			match hover_pos {
+				Some(hover) => self.forward(input_state, ViewportInput::PointerMoved(hover)),
+				None => self.forward(input_state, ViewportInput::PointerLeft),
+			}
+			self.hover_pos = hover_pos;
+		}
+
+		if input_state.is_any_touch_pressed() {
+			self.focused = !pressed.is_empty();
+		}
+		for touch in pressed {
+			if let Some(touch_pos) = input_state.get_touch_pos(touch) {
+				self.forward(input_state, ViewportInput::Pressed(touch, touch_pos - pos));
+				self.pressing.push(touch);
+			}
+		}
+		for touch in std::mem::take(&mut self.pressing) {
+			let touch_pos = input_state.get_touch_pos(touch).map(|touch_pos| touch_pos - pos);
+			if input_state.is_touch_released(touch) {
+				self.forward(input_state, ViewportInput::Released(touch, touch_pos.unwrap_or(Vec2::ZERO)));
+				continue;
+			}
+			let delta = input_state.drag_delta(touch);
+			if let (Some(touch_pos), true) = (touch_pos, delta != Vec2::ZERO) {
+				self.forward(input_state, ViewportInput::Dragged(touch, touch_pos, delta));
+			}
+			self.pressing.push(touch);
+		}
+
+		let wheel = std::mem::take(&mut self.wheel);
+		if wheel != Vec2::ZERO {
+			self.forward(input_state, ViewportInput::Wheel(wheel));
+		}
+
+		if self.focused {
+			let keys = input_state.raw_events().iter().filter_map(|event| match event {
+				WindowEvent::KeyPressed(key) => Some(ViewportInput::KeyPressed(*key)),
+				WindowEvent::KeyReleased(key) => Some(ViewportInput::KeyReleased(*key)),
+				_ => None,
+			}).collect::<Vec<_>>();
+			for key in keys {
+				self.forward(input_state, key);
+			}
+		}
+
+		self.inner.continuous || input_state.viewport_inputs.len() > inputs
+	}
+
+	fn scroll_by(&mut self, delta: Vec2, source: ScrollSource) -> Vec2 {
+		// the viewport handles the wheel and the drags itself, dont scroll the parents.
+		if source == ScrollSource::Wheel {
+			self.wheel += delta;
+		}
+		Vec2::ZERO
+	}
+
+	fn event_handle_strategy(&self) -> EventHandleStrategy {
+		if self.inner.continuous || self.focused || !self.pressing.is_empty() || self.hover_pos.is_some() {
+			EventHandleStrategy::AlwaysSecondary
+		}else {
+			EventHandleStrategy::OnHover
+		}
+	}
+}
@@ -0,0 +1,215 @@
+//! Embedding Nablo UI into an existing wgpu application.
+//!
+//! Use [`EmbeddedUi`] instead of [`super::manager::Manager`] if your application owns the window, the event loop and the gpu device,
+//! e.g. to draw the ui as an overlay of your own renderer:
+//!
+//! - feed the winit events of the window to [`EmbeddedUi::handle_event`].
+//! - record the ui into your command encoder with [`EmbeddedUi::render`] every frame, after your own passes.
+//! - handle the requests of the ui, such as setting the cursor icon, returned by [`EmbeddedUi::take_output_events`].
+//!
+//! The ui is drawn over the content of the given view with alpha blending,
+//! set the clear color to a transparent color with [`Context::set_clear_color`] to see your content behind it.
+//!
+//! ```no_run
+//! use nablo_ui::prelude::*;
+//! use nablo_ui::window::embed::EmbeddedUi;
+//!
+//! struct MyApp;
+//!
+//! impl App for MyApp {
+//!     type Signal = ();
+//!
+//!     fn on_start(&mut self, ctx: &mut Context<(), Self>) {
+//!         ctx.set_clear_color_without_animation(Color::TRANSPARENT);
+//!     }
+//!
+//!     fn on_signal(&mut self, _: &mut Context<(), Self>, _: SignalWrapper<()>) {}
+//! }
+//!
+//! fn frame(ui: &mut EmbeddedUi<MyApp, ()>, device: &wgpu::Device, view: &wgpu::TextureView, dt: time::Duration) -> wgpu::CommandBuffer {
+//!     let mut encoder = device.create_command_encoder(&Default::default());
+//!     // your own passes go here.
+//!     ui.render(&mut encoder, view, dt);
+//!     encoder.finish()
+//! }
+//! ```
+
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+use arboard::Clipboard;
+use time::Duration;
+
+use crate::{math::vec2::Vec2, render::backend::{crate_embedded_state, WgpuState}, widgets::Signal, App, Context};
+
+#[cfg(any(target_os = "android", target_os = "ios"))]
+use super::mobile::Clipboard;
+use super::event::{OutputEvent, WindowEvent};
+
+/// Nablo UI drawn into the views of a host wgpu application, see [`crate::window::embed`].
+pub struct EmbeddedUi<A, S: Signal>
+where A: App<Signal = S>,
+{
+	/// The app to run.
+	pub app: A,
+	ctx: Context<S, A>,
+	state: WgpuState<'static>,
+	quality_factor: f32,
+	last_event_time: Duration,
+	clipboard: Option<Clipboard>,
+	/// the output events for the host to handle.
+	output_events: Vec<OutputEvent>,
+}
+
+impl<A, S: Signal + 'static> EmbeddedUi<A, S>
+where A: App<Signal = S>,
+{
+	/// Create the ui drawing with the given device and queue, calls [`App::on_start`].
+	///
+	/// `format` is the format of the views given to [`Self::render`],
+	/// `size` is the size of the window in physical pixels.
+	/// Load the fonts into `ctx` before, e.g. by creating it with [`Context::new`].
+	pub fn new(mut app: A, mut ctx: Context<S, A>, device: &wgpu::Device, queue: &wgpu::Queue, format: wgpu::TextureFormat, size: Vec2, scale_factor: f64) -> Self {
+		ctx.input_state.scale_factor = scale_factor;
+		ctx.input_state.window_size = size / ctx.input_state.ui_scale();
+		ctx.input_state.window_focused = true;
+		let state = crate_embedded_state(device.clone(), queue.clone(), format, size);
+		#[cfg(feature = "render-graph")]
+		ctx.render_graph.invalidate();
+		app.on_start(&mut ctx);
+		Self {
+			app,
+			ctx,
+			state,
+			quality_factor: 1.0,
+			last_event_time: Duration::ZERO,
+			clipboard: match Clipboard::new() {
+				Ok(clipboard) => Some(clipboard),
+				Err(e) => {
+					log::error!("Failed to create clipboard: {}", e);
+					None
+				}
+			},
+			output_events: vec!(),
+		}
+	}
+
+	/// Sets the quality factor of the rendering, see [`super::manager::Manager::quality_factor`].
+	pub fn quality_factor(self, quality_factor: f32) -> Self {
+		Self {
+			quality_factor,
+			..self
+		}
+	}
+
+	/// Get the context of the ui.
+	pub fn ctx(&mut self) -> &mut Context<S, A> {
+		&mut self.ctx
+	}
+
+	/// Handle a winit event of the window the ui is drawn into.
+	///
+	/// Returns whether the ui needs to be redrawn, the ui is only redrawn when [`Self::render`] is called.
+	pub fn handle_event(&mut self, event: &winit::event::WindowEvent) -> bool {
+		if let winit::event::WindowEvent::Resized(size) = event {
+			self.ctx.input_state.window_size = Vec2::new(size.width as f32, size.height as f32);
+			self.state.resized(self.ctx.input_state.window_size, self.quality_factor);
+			self.ctx.input_state.quality_factor = self.quality_factor;
+			self.ctx.layout.make_all_dirty();
+		}
+		self.ctx.input_state.update(WindowEvent::from_winit(event.clone()));
+		if self.ctx.input_state.should_close {
+			self.ctx.input_state.should_close = false;
+			if self.app.on_request_exit(&mut self.ctx) {
+				self.ctx.exit = true;
+			}
+		}
+		self.event_frame();
+		self.ctx.needs_redraw()
+	}
+
+	/// Record the ui into the encoder, drawing over `view`.
+	///
+	/// `delta` is the time elapsed since the last call, given to [`App::on_draw_frame`].
+	/// Only the changed parts of the ui are redrawn into the intermediate texture, which is drawn onto `view` every call.
+	pub fn render(&mut self, encoder: &mut wgpu::CommandEncoder, view: &wgpu::TextureView, delta: Duration) {
+		self.event_frame();
+		let frame = if self.ctx.needs_redraw() {
+			self.ctx.draw_frame(&mut self.app, delta, false)
+		}else {
+			None
+		};
+		let (frame, uniform) = match frame {
+			Some((painter, refresh_area)) => {
+				let (commands, uniform) = self.ctx.build_frame(painter, &self.state.font_render.char_texture_map, &refresh_area);
+				self.state.render_viewports(&mut self.ctx.viewports, &uniform);
+				self.state.set_clear_color(self.ctx.clear_color());
+				(self.state.prepare(refresh_area, commands, uniform), uniform)
+			},
+			None => (None, self.ctx.frame_uniform()),
+		};
+		self.state.encode(
+			encoder,
+			view,
+			frame,
+			&uniform,
+			#[cfg(feature = "render-graph")]
+			&mut self.ctx.render_graph,
+		);
+		self.state.cleanup();
+	}
+
+	/// Take the output events the host should handle, such as [`OutputEvent::SetCursorIcon`].
+	///
+	/// The gpu resources and the clipboard are handled by the ui, custom events of widget packs are returned
+	/// since their handlers are only run by [`super::manager::Manager`].
+	pub fn take_output_events(&mut self) -> Vec<OutputEvent> {
+		std::mem::take(&mut self.output_events)
+	}
+
+	/// Check whether a close request of the window was accepted by [`App::on_request_exit`].
+	pub fn should_exit(&self) -> bool {
+		self.ctx.exit
+	}
+
+	fn event_frame(&mut self) {
+		let current = self.ctx.input_state.program_running_time();
+		self.ctx.input_state.delta_time = current - self.last_event_time;
+		self.last_event_time = current;
+		self.ctx.handle_event_frame(&mut self.app);
+
+		let output_events = self.ctx.input_state.output_events.drain(..).collect::<Vec<_>>();
+		for event in output_events {
+			let Some(event) = self.state.handle_output_event(event) else {
+				continue;
+			};
+			match event {
+				OutputEvent::CopyToClipboard(text) => {
+					if let Some(cb) = &mut self.clipboard {
+						if let Err(e) = cb.set_text(text) {
+							log::warn!("Failed to set clipboard: {}", e);
+						}
+					}else {
+						log::warn!("Clipboard is not available, copied text is dropped");
+					}
+				},
+				OutputEvent::RequestClipboard => {
+					if let Some(cb) = &mut self.clipboard {
+						match cb.get_text() {
+							Ok(text) => {
+								self.ctx.input_state.paste_text(text);
+							},
+							Err(e) => {
+								log::warn!("Failed to get clipboard: {}", e);
+							}
+						}
+					}else {
+						log::warn!("Clipboard is not available, paste request is ignored");
+					}
+				},
+				event => self.output_events.push(event),
+			}
+		}
+
+		self.app.on_event_frame(&mut self.ctx);
+		self.ctx.layout.notify_dirty();
+	}
+}
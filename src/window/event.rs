@@ -17,14 +17,22 @@ pub enum OutputEvent {
 	Move(Vec2),
 	/// Set the cursor icon of the window.
 	SetCursorIcon(CursorIcon),
+	/// Set the cursor of the window to an image.
+	/// 
+	/// Contains the pixels in RGBA8 format row by row, the size of the image in pixels, 
+	/// the hotspot which is the pixel of the image at the pointer position,
+	/// and the named cursor used where custom cursors are not supported, e.g. on mobile platforms, or when the image is invalid.
+	/// 
+	/// The host caches the created cursors, setting the same image again is cheap.
+	SetCustomCursor(Vec<u8>, Vec2, Vec2, CursorIcon),
 	/// Set the cursor position of the window.
 	SetCursorPosition(Vec2),
 	/// Set the cursor visibility of the window.
 	SetCursorVisible(bool),
-	/// request host to register a new texture.
+	/// request host to register a new texture with the given id.
 	/// 
 	/// Do NOT send this manually, use [`crate::Context::register_texture()`] instead.
-	RegisterTexture(Vec2, Vec<u8>),
+	RegisterTexture(TextureId, Vec2, Vec<u8>),
 	/// request host to change the texture.
 	/// 
 	/// Do NOT send this manually, use [`crate::Context::update_texture()`] instead.
@@ -53,6 +61,78 @@ pub enum OutputEvent {
 	CopyToClipboard(String),
 	/// Request host to get the content of the clipboard.
 	RequestClipboard,
+	/// Request host to show the virtual keyboard, only takes effect on mobile platforms.
+	/// 
+	/// Sent by the manager when [`crate::window::input_state::InputState::show_keyboard()`] is called.
+	ShowKeyboard,
+	/// Request host to hide the virtual keyboard, only takes effect on mobile platforms.
+	/// 
+	/// Sent by the manager when [`crate::window::input_state::InputState::hide_keyboard()`] is called.
+	HideKeyboard,
+	/// Request the user's attention, e.g. flashing the taskbar button on Windows or bouncing the dock icon on macOS.
+	/// 
+	/// `None` cancels the previous request. Usually ignored if the window is already focused.
+	RequestAttention(Option<Attention>),
+	/// Set the progress shown on the taskbar button, only supported on Windows.
+	SetTaskbarProgress(TaskbarProgress),
+	/// Set the badge count shown on the dock icon, only supported on macOS. `None` removes the badge.
+	SetBadgeCount(Option<u32>),
+	/// Announce the text to the assistive technologies such as screen readers, see [`crate::Context::announce`].
+	Announce(String, AnnouncePriority),
+	/// A custom event handled by the handler registered by a widget pack, see [`crate::pack`].
+	/// 
+	/// Contains the name of the event and the payload passed to the handler.
+	Custom(String, crate::pack::OutputPayload),
+}
+
+/// How urgent an attention request is.
+/// 
+/// Mainly warping the user attention type from the `winit` crate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Attention {
+	/// Keep requesting until the window is focused, e.g. bouncing the dock icon until the app is activated.
+	Critical,
+	/// Request once, e.g. bouncing the dock icon a single time.
+	Informational,
+}
+
+impl From<Attention> for winit::window::UserAttentionType {
+	fn from(value: Attention) -> Self {
+		match value {
+			Attention::Critical => winit::window::UserAttentionType::Critical,
+			Attention::Informational => winit::window::UserAttentionType::Informational,
+		}
+	}
+}
+
+/// How urgent an announcement to the assistive technologies is, see [`crate::Context::announce`].
+/// 
+/// Same as the politeness of the live regions on the web.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum AnnouncePriority {
+	/// Announced after the screen reader finished what it is saying, e.g. "upload complete".
+	#[default]
+	Polite,
+	/// Interrupts the screen reader, for important and time sensitive changes such as errors.
+	Assertive,
+}
+
+/// The progress shown on the taskbar button.
+/// 
+/// The values are ranged from 0.0 to 1.0.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum TaskbarProgress {
+	/// No progress is shown.
+	#[default]
+	None,
+	/// The progress is shown as a busy indicator.
+	Indeterminate,
+	/// The operation is running.
+	Normal(f32),
+	/// The operation is paused, usually shown in yellow.
+	Paused(f32),
+	/// The operation failed, usually shown in red.
+	Error(f32),
 }
 
 /// The cursor icon of the window.
@@ -152,8 +232,16 @@ pub enum WindowEvent {
 	HoveredFileCancelled,
 	/// Contains the new state of the window.
 	Focused(bool),
+	/// Whether the window is fully hidden, e.g. minimized or covered by other windows.
+	Occluded(bool),
 	KeyPressed(Key),
 	KeyReleased(Key),
+	/// The text typed by the last [`Self::KeyPressed`], as composed by the keyboard layout of the platform,
+	/// e.g. `é` after typing `´` and `e` with dead keys.
+	/// 
+	/// Empty when the key typed nothing, e.g. a dead key waiting for the next one.
+	/// When sent, it replaces the character guessed from the physical key.
+	TextInput(String),
 	StringInput(String),
 	ImeEnabled,
 	Ime(ImeEvent),
@@ -452,6 +540,21 @@ impl Key {
 	}
 }
 
+impl WindowEvent {
+	/// Convert a winit event, along with the text typed by key presses, see [`Self::TextInput`].
+	pub fn from_winit(event: WinitEvent) -> Vec<Self> {
+		let text = match &event {
+			WinitEvent::KeyboardInput { event, .. } if event.state == winit::event::ElementState::Pressed => {
+				Some(event.text.as_ref().map(|text| text.to_string()).unwrap_or_default())
+			},
+			_ => None,
+		};
+		let mut out = vec!(Self::from(event));
+		out.extend(text.map(WindowEvent::TextInput));
+		out
+	}
+}
+
 impl From<WinitEvent> for WindowEvent {
 	fn from(event: WinitEvent) -> Self {
 		match event {
@@ -462,6 +565,7 @@ impl From<WinitEvent> for WindowEvent {
 			WinitEvent::HoveredFile(path) => WindowEvent::HoveredFile(path),
 			WinitEvent::HoveredFileCancelled => WindowEvent::HoveredFileCancelled,
 			WinitEvent::Focused(focused) => WindowEvent::Focused(focused),
+			WinitEvent::Occluded(occluded) => WindowEvent::Occluded(occluded),
 			WinitEvent::KeyboardInput { event, .. } => {
 				let key = Key::from(event.physical_key);
 				if event.state == winit::event::ElementState::Pressed {
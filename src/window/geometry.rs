@@ -0,0 +1,96 @@
+//! Saving and restoring the geometry of the window across runs.
+
+#[cfg(feature = "serde")]
+use std::path::Path;
+
+use winit::{dpi::{PhysicalPosition, PhysicalSize}, event_loop::ActiveEventLoop, monitor::MonitorHandle, window::Window};
+
+use crate::math::{rect::Rect, vec2::Vec2};
+
+/// The geometry of a window, in physical pixels.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WindowGeometry {
+	/// The inner size of the window when it is not maximized.
+	pub size: Vec2,
+	/// The outer position of the window when it is not maximized.
+	///
+	/// `None` if the platform does not support getting the window position.
+	pub position: Option<Vec2>,
+	/// Whether the window is maximized.
+	pub maximized: bool,
+	/// The name of the monitor the window is on.
+	pub monitor: Option<String>,
+}
+
+/// An error that occurs when saving or loading [`WindowGeometry`].
+#[cfg(feature = "serde")]
+#[derive(Debug, thiserror::Error)]
+pub enum GeometryError {
+	/// Failed to read or write the file.
+	#[error("failed to access the geometry file: {0}")]
+	Io(#[from] std::io::Error),
+	/// Failed to serialize the geometry.
+	#[error("failed to serialize the geometry: {0}")]
+	Serialize(#[from] ron::Error),
+	/// The file does not contain a valid geometry.
+	#[error("failed to parse the geometry file: {0}")]
+	Deserialize(#[from] ron::error::SpannedError),
+}
+
+impl WindowGeometry {
+	/// Get the current geometry of the window.
+	pub fn from_window(window: &Window) -> Self {
+		let size = window.inner_size();
+		Self {
+			size: Vec2::new(size.width as f32, size.height as f32),
+			position: window.outer_position().ok().map(|pos| Vec2::new(pos.x as f32, pos.y as f32)),
+			maximized: window.is_maximized(),
+			monitor: window.current_monitor().and_then(|monitor| monitor.name()),
+		}
+	}
+
+	/// Load the geometry from the given file.
+	#[cfg(feature = "serde")]
+	pub fn load(path: impl AsRef<Path>) -> Result<Self, GeometryError> {
+		let data = std::fs::read_to_string(path)?;
+		Ok(ron::from_str(&data)?)
+	}
+
+	/// Save the geometry to the given file, the parent directories will be created if needed.
+	#[cfg(feature = "serde")]
+	pub fn save(&self, path: impl AsRef<Path>) -> Result<(), GeometryError> {
+		let path = path.as_ref();
+		if let Some(parent) = path.parent() {
+			std::fs::create_dir_all(parent)?;
+		}
+		let data = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())?;
+		std::fs::write(path, data)?;
+		Ok(())
+	}
+
+	/// Returns true if the window would be visible on one of the given monitors.
+	///
+	/// Used to avoid restoring a window to a monitor which is no longer connected.
+	pub fn is_visible_on(&self, monitors: impl IntoIterator<Item = Rect>) -> bool {
+		let Some(position) = self.position else {
+			return true;
+		};
+		let window = Rect::from_lt_size(position, self.size);
+		monitors.into_iter().any(|monitor| !(monitor & window).is_empty())
+	}
+}
+
+/// Find the monitor with the given name, or the primary monitor if `name` is `None` or not found.
+pub(crate) fn find_monitor(event_loop: &ActiveEventLoop, name: Option<&str>) -> Option<MonitorHandle> {
+	name.and_then(|name| event_loop.available_monitors().find(|monitor| monitor.name().as_deref() == Some(name)))
+		.or_else(|| event_loop.primary_monitor())
+		.or_else(|| event_loop.available_monitors().next())
+}
+
+/// Get the area of the monitor in physical pixels.
+pub(crate) fn monitor_rect(monitor: &MonitorHandle) -> Rect {
+	let PhysicalPosition { x, y } = monitor.position();
+	let PhysicalSize { width, height } = monitor.size();
+	Rect::new(x as f32, y as f32, width as f32, height as f32)
+}
@@ -1,12 +1,30 @@
 //! Here we define the InputState-related struct which holds the state of the input events.
 
-use std::{collections::HashMap, path::PathBuf};
+use std::{any::Any, collections::{HashMap, VecDeque}, path::PathBuf, sync::Arc};
 
 use time::{Duration, OffsetDateTime};
 
-use crate::{layout::{LayoutId, ROOT_LAYOUT_ID}, math::{rect::Rect, vec2::Vec2}, widgets::{Signal, SignalWrapper}, window::event::TouchPhase};
+use crate::{layout::{HitRegion, LayoutId, OverlayAnchor, ROOT_LAYOUT_ID}, math::{rect::Rect, vec2::Vec2}, render::{damage::Damage, viewport::{ViewportId, ViewportInput}}, widgets::{localizer::{default_localizer, Localizer}, Signal, SignalWrapper, Widget}, window::event::TouchPhase, App};
 
-use super::event::{ImeEvent, Key, MouseButton, OutputEvent, Theme, WindowEvent};
+use super::{mobile::MOBILE, event::{AnnouncePriority, Attention, ImeEvent, Key, MouseButton, OutputEvent, TaskbarProgress, Theme, WindowEvent}};
+
+/// A change of the overlays requested by a widget, see [`InputState::push_overlay`].
+pub(crate) enum OverlayRequest {
+	/// Push the widget, a boxed `Box<dyn Widget>` of the application, as an overlay.
+	Push(OverlayAnchor, Box<dyn Any>),
+	/// Remove the overlay.
+	Remove(LayoutId),
+}
+
+/// A change of the children of a widget requested by itself, see [`InputState::add_child`].
+pub(crate) enum ChildRequest {
+	/// Add the widget, a boxed `Box<dyn Widget>` of the application, as the last child of the parent.
+	Add(LayoutId, Box<dyn Any>),
+	/// Replace the child of the parent in place, keeping its id.
+	Replace(LayoutId, LayoutId, Box<dyn Any>),
+	/// Remove the child of the parent.
+	Remove(LayoutId, LayoutId),
+}
 
 /// We will handle mouse events as special touch events with id MOUSE_ID.
 /// 
@@ -18,6 +36,9 @@ use super::event::{ImeEvent, Key, MouseButton, OutputEvent, Theme, WindowEvent};
 /// - Forward button: MOUSE_ID + 4
 /// - Other buttons: MOUSE_ID + button_id
 pub const MOUSE_ID: u64 = 1000;
+
+/// The touch id of the right mouse button, which opens context menus.
+pub const MOUSE_RIGHT_ID: u64 = MOUSE_ID + 1;
 /// The time threshold between pressed.
 /// 
 /// if press time is less than this threshold, it will be considered as a tap.
@@ -26,6 +47,63 @@ pub const DEFAULT_EPSILON_TIME: Duration = Duration::milliseconds(100);
 /// The id of the touch event when the mouse is not pressed.
 pub const MOUSE_UNPRESSED_ID: u64 = 2000;
 
+/// The default distance in logical pixels a mouse can move between press and release and still be considered as a click.
+pub const DEFAULT_CLICK_SLOP: f32 = 6.0;
+/// The default distance in logical pixels a finger can move between press and release and still be considered as a tap.
+/// 
+/// Fingers are less precise than mouse, so the slop is larger.
+pub const DEFAULT_TOUCH_CLICK_SLOP: f32 = 16.0;
+/// The default time a finger holds still to open the context menu, see [`InputState::long_press_time`].
+pub const DEFAULT_LONG_PRESS_TIME: Duration = Duration::milliseconds(500);
+
+/// How many draw frames are kept in [`FrameStats`].
+pub const FRAME_STATS_CAPACITY: usize = 120;
+
+/// The minimum zoom level of the ui, see [`InputState::set_zoom`].
+pub const MIN_ZOOM: f32 = 0.5;
+/// The maximum zoom level of the ui, see [`InputState::set_zoom`].
+pub const MAX_ZOOM: f32 = 3.0;
+/// The factor the zoom shortcuts multiply or divide the zoom level by, see [`InputState::set_zoom_shortcuts`].
+pub const ZOOM_STEP: f32 = 1.1;
+
+/// Statistics of recent frames, maintained by the manager.
+#[derive(Debug, Clone, Default)]
+pub struct FrameStats {
+	frame_times: VecDeque<f32>,
+	/// The number of widgets in the layout.
+	pub widgets: usize,
+	/// The memory used by the textures in bytes, estimated as 4 bytes per pixel.
+	pub texture_memory: usize,
+}
+
+impl FrameStats {
+	/// Get the durations of recent draw frames in seconds, from oldest to newest.
+	pub fn frame_times(&self) -> impl Iterator<Item = f32> + '_ {
+		self.frame_times.iter().copied()
+	}
+
+	/// Get the frame time in seconds below which the given portion of recent draw frames fall.
+	/// 
+	/// `percentile` is between 0.0 and 1.0, e.g. `0.99` for the 99th percentile.
+	/// Returns 0.0 if no frame is drawn yet.
+	pub fn frame_time_percentile(&self, percentile: f32) -> f32 {
+		if self.frame_times.is_empty() {
+			return 0.0;
+		}
+		let mut sorted = self.frame_times.iter().copied().collect::<Vec<_>>();
+		sorted.sort_by(f32::total_cmp);
+		let index = ((sorted.len() - 1) as f32 * percentile.clamp(0.0, 1.0)).round() as usize;
+		sorted[index]
+	}
+
+	pub(crate) fn push_frame_time(&mut self, frame_time: f32) {
+		if self.frame_times.len() >= FRAME_STATS_CAPACITY {
+			self.frame_times.pop_front();
+		}
+		self.frame_times.push_back(frame_time);
+	}
+}
+
 /// The input state of the window.
 /// 
 /// This struct holds the state of the input events.
@@ -37,6 +115,10 @@ pub struct InputState<S: Signal> {
 	pub window_size: Vec2,
 	/// The scaling factor of the window.
 	pub scale_factor: f64,
+	/// the zoom level of the ui on top of the scale factor, see [`InputState::set_zoom`].
+	pub(crate) zoom: f32,
+	/// whether Ctrl+= and Ctrl+- change the zoom level.
+	pub(crate) zoom_shortcuts: bool,
 	/// The list of dropped files.
 	pub dropped_files: Vec<PathBuf>,
 	/// The file being hovered by the mouse.
@@ -45,16 +127,62 @@ pub struct InputState<S: Signal> {
 	// pub modifiers: Modifiers,
 	/// The current theme of the window.
 	pub theme: Theme,
+	/// How far in logical pixels a mouse can move between press and release and still be considered as a click.
+	/// 
+	/// Once moved further, the press will be treated as a drag and will not trigger click.
+	pub click_slop: f32,
+	/// Same as [`Self::click_slop`], but for touches.
+	pub touch_click_slop: f32,
+	/// How long a finger holds still within [`Self::touch_click_slop`] to open the context menu under it,
+	/// the same as a right click with a mouse. `None` disables the long press.
+	/// 
+	/// Only the context menus attached by [`crate::layout::Layout::set_context_menu`] are opened.
+	pub long_press_time: Option<Duration>,
 	pub(crate) input_string: String,
 	pub(crate) ime_string: (String, Option<(usize, usize)>, bool),
 	pub(crate) redraw_requested: bool,
+	/// the earliest running time to run another event frame at, see [`InputState::request_frame_after`].
+	pub(crate) wake_at: Option<Duration>,
 	pub(crate) signals_to_send: Vec<SignalWrapper<S>>,
 	pub(crate) handling_id: LayoutId,
 	pub(crate) should_close: bool,
 	pub(crate) window_focused: bool,
+	pub(crate) window_occluded: bool,
 	pub(crate) program_start_time: OffsetDateTime,
+	pub(crate) delta_time: Duration,
+	pub(crate) draw_delta_time: Duration,
+	pub(crate) smoothed_frame_time: f32,
+	pub(crate) quality_factor: f32,
+	pub(crate) frame_stats: FrameStats,
+	pub(crate) safe_area: Rect,
+	/// the widget the virtual keyboard is shown for.
+	pub(crate) keyboard_owner: Option<LayoutId>,
+	pub(crate) keyboard_inset: Option<f32>,
+	pub(crate) keyboard_height_fraction: f32,
+	pub(crate) visible_request: Option<(LayoutId, Rect)>,
+	/// the areas to scroll the containers to after the event frame, see [`InputState::request_reveal`].
+	pub(crate) reveal_requests: Vec<(LayoutId, Rect)>,
+	/// the overlays to push or remove after the event frame, see [`InputState::push_overlay`].
+	pub(crate) overlay_requests: Vec<OverlayRequest>,
+	/// the children to add, replace or remove after the event frame, see [`InputState::add_child`].
+	pub(crate) child_requests: Vec<ChildRequest>,
+	/// the inputs on the viewport widgets, forwarded to their renderers after the event frame.
+	pub(crate) viewport_inputs: Vec<(ViewportId, ViewportInput)>,
+	/// whether a widget opened a context menu in the current event frame, see [`crate::widgets::context_menu::ContextMenu::open`].
+	pub(crate) context_menu_opened: bool,
+	/// the widget with the keyboard focus, mirrored from the layout, see [`crate::layout::Layout::focused`].
+	pub(crate) focused: Option<LayoutId>,
+	/// whether the focus was moved by the keyboard.
+	pub(crate) focus_visible: bool,
+	/// the focus change requested by widgets, applied after the event frame.
+	pub(crate) focus_request: Option<Option<LayoutId>>,
+	/// the area to redraw requested by widgets, see [`InputState::request_redraw_area`].
+	pub(crate) damage: Damage,
+	/// the area, position and hit region of the widget handling events.
+	pub(crate) hit_region: Option<(Rect, Vec2, HitRegion)>,
 	pub(crate) output_events: Vec<OutputEvent>,
 	pub(crate) all_dirty: bool,
+	pub(crate) localizer: Arc<dyn Localizer>,
 	// last_mouse_position: Option<Vec2>,
 	wheel: Vec2,
 	pressing_touches: HashMap<u64, TouchState>,
@@ -66,6 +194,8 @@ pub struct InputState<S: Signal> {
 	is_ime_enabled: bool,
 	pasted_text: String,
 	cached_input: String,
+	/// where the character guessed from the last pressed key starts in `cached_input`.
+	key_char: Option<usize>,
 }
 
 /// The input string contains the ime condition.
@@ -102,8 +232,51 @@ pub struct Modifiers {
 	pub shift: bool,
 	/// The control key.
 	pub ctrl: bool,
-	/// The alt key.
+	/// The alt key, the option key on macOS.
 	pub alt: bool,
+	/// The logo key, the command key on macOS and the windows key on windows.
+	pub logo: bool,
+}
+
+/// Whether the primary modifier is the command key, see [`Modifiers::primary`].
+pub const PRIMARY_IS_COMMAND: bool = cfg!(any(target_os = "macos", target_os = "ios"));
+
+impl Modifiers {
+	/// Whether the primary modifier of shortcuts is pressing, the command key on macOS and the control key elsewhere.
+	/// 
+	/// Use it instead of [`Self::ctrl`] for shortcuts like copy and paste, so they feel native on every platform.
+	pub fn primary(&self) -> bool {
+		if PRIMARY_IS_COMMAND {
+			self.logo
+		}else {
+			self.ctrl
+		}
+	}
+
+	/// The name of the primary modifier, `Cmd` on macOS and `Ctrl` elsewhere.
+	pub fn primary_name() -> &'static str {
+		if PRIMARY_IS_COMMAND {
+			"Cmd"
+		}else {
+			"Ctrl"
+		}
+	}
+
+	/// Format a shortcut using the primary modifier for display, e.g. `Cmd+S` on macOS and `Ctrl+S` elsewhere.
+	pub fn primary_shortcut(key: &str) -> String {
+		format!("{}+{}", Self::primary_name(), key)
+	}
+
+	/// Whether the modifiers turn key presses into shortcuts instead of typed text.
+	/// 
+	/// The option key types characters on macOS, so only the command and control keys count there.
+	fn blocks_text(&self) -> bool {
+		if PRIMARY_IS_COMMAND {
+			self.logo || self.ctrl
+		}else {
+			self.ctrl || self.alt || self.logo
+		}
+	}
 }
 
 struct TouchState {
@@ -111,6 +284,9 @@ struct TouchState {
 	time: Duration,
 	pos: Vec2,
 	last_pos: Vec2,
+	start_pos: Vec2,
+	// whether the touch has moved further than the slop since pressed, which makes it a drag instead of a click.
+	beyond_slop: bool,
 	// (widget_id, accepted_pressed)
 	using_by: Option<(LayoutId, bool)>,
 	last_used: bool,
@@ -128,6 +304,8 @@ impl<S: Signal> InputState<S> {
 			// mouse_pos: None,
 			window_size: Vec2::INF,
 			scale_factor: 1.0,
+			zoom: 1.0,
+			zoom_shortcuts: true,
 			signals_to_send: Vec::new(),
 			handling_id: ROOT_LAYOUT_ID,
 			wheel: Vec2::ZERO,
@@ -135,6 +313,26 @@ impl<S: Signal> InputState<S> {
 			input_string: String::new(),
 			ime_string: (String::new(), None, false),
 			program_start_time: OffsetDateTime::now_utc(),
+			delta_time: Duration::ZERO,
+			draw_delta_time: Duration::ZERO,
+			smoothed_frame_time: 0.0,
+			quality_factor: 1.0,
+			frame_stats: FrameStats::default(),
+			safe_area: Rect::WINDOW,
+			keyboard_owner: None,
+			keyboard_inset: None,
+			keyboard_height_fraction: 0.4,
+			visible_request: None,
+			reveal_requests: vec!(),
+			overlay_requests: vec!(),
+			child_requests: vec!(),
+			viewport_inputs: vec!(),
+			context_menu_opened: false,
+			focused: None,
+			focus_visible: false,
+			focus_request: None,
+			damage: Damage::default(),
+			hit_region: None,
 			pressing_touches: HashMap::new(),
 			released_touches: HashMap::new(),
 			pressing_keys: HashMap::new(),
@@ -143,19 +341,31 @@ impl<S: Signal> InputState<S> {
 			has_new_events: false,
 			should_close: false,
 			window_focused: true,
+			window_occluded: false,
 			is_ime_enabled: false,
 			redraw_requested: true,
+			wake_at: None,
 			dropped_files: vec!(),
 			hovering_file: None,
 			theme: Theme::Dark,
+			click_slop: DEFAULT_CLICK_SLOP,
+			touch_click_slop: DEFAULT_TOUCH_CLICK_SLOP,
+			long_press_time: Some(DEFAULT_LONG_PRESS_TIME),
 			output_events: vec!(),
 			pasted_text: String::new(),
 			cached_input: String::new(),
+			key_char: None,
 			all_dirty: false,
+			localizer: default_localizer(),
 			// last_mouse_position: None,
 		}
 	}
 
+	/// Get the localizer used to translate the messages of the built-in widgets.
+	pub fn localizer(&self) -> &dyn Localizer {
+		self.localizer.as_ref()
+	}
+
 	/// Get the raw events of current frame.
 	pub fn raw_events(&self) -> &[WindowEvent] {
 		&self.raw_events
@@ -166,6 +376,222 @@ impl<S: Signal> InputState<S> {
 		OffsetDateTime::now_utc() - self.program_start_time
 	}
 
+	/// Get the time elapsed since the last event frame.
+	pub fn delta_time(&self) -> Duration {
+		self.delta_time
+	}
+
+	/// Get the time elapsed since the last draw frame.
+	/// 
+	/// Draw frames are skipped when nothing changes, so this can be long after idling.
+	pub fn draw_delta_time(&self) -> Duration {
+		self.draw_delta_time
+	}
+
+	/// Get the draw frames per second, smoothed over recent frames.
+	pub fn fps(&self) -> f32 {
+		if self.smoothed_frame_time > 0.0 {
+			1.0 / self.smoothed_frame_time
+		}else {
+			0.0
+		}
+	}
+
+	/// Get the area of the window not covered by notches, rounded corners or system bars.
+	/// 
+	/// Same as the whole window on desktop platforms.
+	pub fn safe_area(&self) -> Rect {
+		let safe_area = self.safe_area;
+		Rect::new(safe_area.x / self.zoom, safe_area.y / self.zoom, safe_area.w / self.zoom, safe_area.h / self.zoom)
+	}
+
+	/// Request the virtual keyboard to show for the given widget, only takes effect on mobile platforms.
+	/// 
+	/// Usually called by widgets accepting text input when they get focused.
+	/// The keyboard belongs to the last widget requesting it, and is hidden once that widget is removed.
+	pub fn show_keyboard(&mut self, id: LayoutId) {
+		self.keyboard_owner = Some(id);
+	}
+
+	/// Request the virtual keyboard shown for the given widget to hide, only takes effect on mobile platforms.
+	/// 
+	/// Ignored if the keyboard was requested by another widget since.
+	pub fn hide_keyboard(&mut self, id: LayoutId) {
+		if self.keyboard_owner == Some(id) {
+			self.keyboard_owner = None;
+		}
+	}
+
+	/// Check if the virtual keyboard is requested to show.
+	pub fn is_keyboard_visible(&self) -> bool {
+		self.keyboard_owner.is_some()
+	}
+
+	/// Get the widget the virtual keyboard is requested for, see [`Self::show_keyboard`].
+	pub fn keyboard_owner(&self) -> Option<LayoutId> {
+		self.keyboard_owner
+	}
+
+	/// Get the height of the bottom part of the window covered by the virtual keyboard, zero if the keyboard is hidden.
+	/// 
+	/// The platforms do not report the keyboard size through `winit`, so this is estimated by
+	/// [`crate::window::manager::WindowSettings::keyboard_height`] on mobile platforms unless set by [`Self::set_keyboard_inset`].
+	/// Always zero on desktop platforms unless set, as there is no virtual keyboard to estimate.
+	pub fn keyboard_inset(&self) -> f32 {
+		if self.keyboard_owner.is_some() && (MOBILE || self.keyboard_inset.is_some()) {
+			self.keyboard_inset.unwrap_or(self.window_size.y * self.keyboard_height_fraction)
+		}else {
+			0.0
+		}
+	}
+
+	/// Set the height covered by the virtual keyboard, `None` to use the estimated height.
+	/// 
+	/// Useful if the app gets the keyboard size from platform specific apis.
+	pub fn set_keyboard_inset(&mut self, inset: Option<f32>) {
+		self.keyboard_inset = inset;
+	}
+
+	/// Get the area of the window where the content is visible to the user,
+	/// which is the [`Self::safe_area`] minus the part covered by the virtual keyboard.
+	pub fn visible_area(&self) -> Rect {
+		let safe_area = self.safe_area();
+		let bottom = (self.window_size.y - self.keyboard_inset()).min(safe_area.y + safe_area.h);
+		Rect::new(safe_area.x, safe_area.y, safe_area.w, (bottom - safe_area.y).max(0.0))
+	}
+
+	/// Request the scroll containers to scroll the given absolute area of a widget into [`Self::visible_area`].
+	/// 
+	/// The containers are scrolled after the current event frame, only the last request in a frame is kept.
+	pub fn request_visible(&mut self, id: LayoutId, area: Rect) {
+		self.visible_request = Some((id, area));
+	}
+
+	/// Request the scroll container to scroll with animation so that `area` is shown, see [`crate::widgets::Widget::scroll_to_reveal`].
+	/// 
+	/// The `area` is relative to the left top corner of the container, where its children are currently placed.
+	/// The container is scrolled after the current event frame.
+	pub fn request_reveal(&mut self, id: LayoutId, area: Rect) {
+		self.reveal_requests.push((id, area));
+	}
+
+	/// Get the widget with the keyboard focus, see [`crate::layout::Layout::focused`].
+	pub fn focused(&self) -> Option<LayoutId> {
+		self.focused
+	}
+
+	/// Check whether the widget has the keyboard focus.
+	/// 
+	/// Focused widgets should respond to the keys, such as activating on enter or moving by the arrow keys.
+	pub fn is_focused(&self, id: LayoutId) -> bool {
+		self.focused == Some(id)
+	}
+
+	/// Check whether the focus was moved by the keyboard, the focus ring is only shown then.
+	pub fn is_focus_visible(&self) -> bool {
+		self.focus_visible
+	}
+
+	/// Request to move the keyboard focus to the widget, e.g. when it starts taking text input.
+	/// 
+	/// The focus is moved after the current event frame, but [`Self::is_focused`] reflects it right away.
+	pub fn request_focus(&mut self, id: LayoutId) {
+		self.focused = Some(id);
+		self.focus_request = Some(Some(id));
+	}
+
+	/// Request to remove the keyboard focus, see [`Self::request_focus`].
+	pub fn clear_focus(&mut self) {
+		self.focused = None;
+		self.focus_request = Some(None);
+	}
+
+	/// Request to push a widget as an overlay, see [`crate::layout::Layout::push_overlay`].
+	/// 
+	/// Widgets can not reach the layout while handling events, use this to open popups such as suggestion lists.
+	/// The overlay is pushed after the current event frame, the requests are applied in order.
+	pub fn push_overlay<W: Widget<Signal = S>>(&mut self, anchor: OverlayAnchor, widget: W) {
+		let widget: Box<dyn Widget<Signal = S, Application = W::Application>> = Box::new(widget);
+		self.overlay_requests.push(OverlayRequest::Push(anchor, Box::new(widget)));
+	}
+
+	/// Request to remove an overlay and its children after the current event frame, e.g. a popup closing itself.
+	/// 
+	/// Ids that are not the root of an overlay are ignored.
+	pub fn remove_overlay(&mut self, id: LayoutId) {
+		self.overlay_requests.push(OverlayRequest::Remove(id));
+	}
+
+	/// Request to add a widget as the last child of `parent` after the current event frame.
+	/// 
+	/// Widgets can not reach the layout while handling events, use this to build their own children lazily, 
+	/// such as the rows of [`crate::widgets::list_view::ListView`]. The requests are applied in order.
+	pub fn add_child<W: Widget<Signal = S>>(&mut self, parent: LayoutId, widget: W) {
+		self.add_boxed_child::<W::Application>(parent, Box::new(widget));
+	}
+
+	/// Request to replace the child `id` of `parent` in place after the current event frame, the child keeps its id.
+	/// 
+	/// Ids that are not children of `parent` are ignored.
+	pub fn replace_child<W: Widget<Signal = S>>(&mut self, parent: LayoutId, id: LayoutId, widget: W) {
+		self.replace_boxed_child::<W::Application>(parent, id, Box::new(widget));
+	}
+
+	/// Request to remove the child `id` of `parent` and its children after the current event frame.
+	/// 
+	/// Ids that are not children of `parent` are ignored.
+	pub fn remove_child(&mut self, parent: LayoutId, id: LayoutId) {
+		self.child_requests.push(ChildRequest::Remove(parent, id));
+	}
+
+	pub(crate) fn add_boxed_child<A: App<Signal = S>>(&mut self, parent: LayoutId, widget: Box<dyn Widget<Signal = S, Application = A>>) {
+		self.child_requests.push(ChildRequest::Add(parent, Box::new(widget)));
+	}
+
+	pub(crate) fn replace_boxed_child<A: App<Signal = S>>(&mut self, parent: LayoutId, id: LayoutId, widget: Box<dyn Widget<Signal = S, Application = A>>) {
+		self.child_requests.push(ChildRequest::Replace(parent, id, Box::new(widget)));
+	}
+
+	/// Get the quality factor the window is currently rendered with.
+	/// 
+	/// Differs from [`crate::window::manager::WindowSettings::quality_factor`] while live resizing 
+	/// or when lowered by [`crate::window::manager::WindowSettings::adaptive_quality`].
+	pub fn quality_factor(&self) -> f32 {
+		self.quality_factor
+	}
+
+	/// Get the statistics of recent frames.
+	pub fn frame_stats(&self) -> &FrameStats {
+		&self.frame_stats
+	}
+
+	pub(crate) fn update_draw_delta_time(&mut self, delta: Duration) {
+		const SMOOTHING: f32 = 0.1;
+		self.draw_delta_time = delta;
+		let frame_time = delta.as_seconds_f32();
+		self.frame_stats.push_frame_time(frame_time);
+		self.smoothed_frame_time = if self.smoothed_frame_time > 0.0 {
+			self.smoothed_frame_time + (frame_time - self.smoothed_frame_time) * SMOOTHING
+		}else {
+			frame_time
+		};
+	}
+
+	/// Check if the absolute point is in the given area.
+	/// 
+	/// If the area is the one given to the widget handling events, the [`HitRegion`] of the widget is tested instead.
+	/// All the area checks of the input state use this method.
+	pub fn hit_test(&self, area: Rect, point: Vec2) -> bool {
+		Self::hit_test_with(&self.hit_region, area, point)
+	}
+
+	fn hit_test_with(hit_region: &Option<(Rect, Vec2, HitRegion)>, area: Rect, point: Vec2) -> bool {
+		match hit_region {
+			Some((widget_area, pos, region)) if *widget_area == area => region.contains(area, *pos, point),
+			_ => area.contains(point),
+		}
+	}
+
 	/// Check if current area is clicked or not.
 	pub fn is_clicked(&mut self, click_by: LayoutId, hitbox: Rect) -> bool {
 		if self.pressing_touches.values().any(|touch| {
@@ -185,8 +611,9 @@ impl<S: Signal> InputState<S> {
 		}) {
 			let mut out = false;
 			self.released_touches.retain(|_, touch| {
-				if touch.using_by == Some((click_by, true)) && hitbox.contains(touch.pos) {
-					out = true;
+				if touch.using_by == Some((click_by, true)) && Self::hit_test_with(&self.hit_region, hitbox, touch.pos) {
+					// a press that turned into a drag should not be a click, but it is still consumed.
+					out = !touch.beyond_slop;
 					false
 				}else {
 					true
@@ -199,10 +626,10 @@ impl<S: Signal> InputState<S> {
 
 		for touch in self.pressing_touches.values_mut() {
 			if let Some((using_by, _)) = &touch.using_by {
-				if *using_by == click_by && hitbox.contains(touch.pos) {
+				if *using_by == click_by && Self::hit_test_with(&self.hit_region, hitbox, touch.pos) {
 					touch.last_used = true;
 				}
-			}else if touch.using_by.is_none() && hitbox.contains(touch.pos) {
+			}else if touch.using_by.is_none() && Self::hit_test_with(&self.hit_region, hitbox, touch.pos) {
 				touch.using_by = Some((click_by, current - touch.time < DEFAULT_EPSILON_TIME));
 				touch.last_used = true;
 			}
@@ -211,6 +638,16 @@ impl<S: Signal> InputState<S> {
 		false
 	}
 
+	/// Check whether [`Self::is_clicked`] would report a click of the widget, without consuming the click.
+	/// 
+	/// Only touches already claimed by [`Self::is_clicked`] of the widget are counted.
+	pub fn peek_clicked(&self, click_by: LayoutId, hitbox: Rect) -> bool {
+		let claimed = |touch: &TouchState| matches!(touch.using_by, Some((using_by, true)) if using_by == click_by);
+		!self.pressing_touches.values().any(claimed) && self.released_touches.values().any(|touch| {
+			claimed(touch) && !touch.beyond_slop && Self::hit_test_with(&self.hit_region, hitbox, touch.pos)
+		})
+	}
+
 	/// Check if there is any touch pressed.
 	pub fn is_any_touch_pressed(&self) -> bool {
 		let current = OffsetDateTime::now_utc() - self.program_start_time;
@@ -237,7 +674,7 @@ impl<S: Signal> InputState<S> {
 		let current = OffsetDateTime::now_utc() - self.program_start_time;
 		let mut result = vec!();
 		for (id, state) in self.pressing_touches.iter() {
-			if area.contains(state.pos) && current - state.time < DEFAULT_EPSILON_TIME && state.using_by.is_none() {
+			if self.hit_test(area, state.pos) && current - state.time < DEFAULT_EPSILON_TIME && state.using_by.is_none() {
 				result.push(*id);
 			}
 		}
@@ -252,7 +689,12 @@ impl<S: Signal> InputState<S> {
 	/// Check if there is any touch pressing on the given area.
 	pub fn any_touch_pressing_on(&self, area: impl Into<Rect>) -> bool {
 		let area = area.into();
-		self.pressing_touches.values().any(|touch| area.contains(touch.pos))
+		self.pressing_touches.values().any(|touch| self.hit_test(area, touch.pos))
+	}
+
+	/// Get the positions of the touches and mouse buttons holding down, without the hovering mouse.
+	pub fn pressing_positions(&self) -> Vec<Vec2> {
+		self.pressing_touches.values().filter(|touch| touch.id != MOUSE_UNPRESSED_ID).map(|touch| touch.pos).collect()
 	}
 
 	/// Check if there is any touch pressing.
@@ -274,9 +716,40 @@ impl<S: Signal> InputState<S> {
 		self.released_touches.contains_key(&id) || !self.pressing_touches.contains_key(&id)
 	}
 
+	/// Check if the given touch has moved further than the click slop since pressed.
+	/// 
+	/// Such touch is considered as a drag and will not trigger click when released.
+	pub fn is_touch_beyond_slop(&self, id: u64) -> bool {
+		self.pressing_touches.get(&id).or_else(|| self.released_touches.get(&id)).map(|touch| touch.beyond_slop).unwrap_or(false)
+	}
+
+	/// Get the finger holding still the longest, with its position and how long it has been held.
+	/// 
+	/// Mouse buttons are never long pressed.
+	pub(crate) fn long_press_candidate(&self) -> Option<(u64, Vec2, Duration)> {
+		let now = self.program_running_time();
+		self.pressing_touches.values()
+			.filter(|touch| touch.id < MOUSE_ID && !touch.beyond_slop)
+			.min_by_key(|touch| touch.time)
+			.map(|touch| (touch.id, touch.start_pos, now - touch.time))
+	}
+
+	/// Keep the touch from clicking when released, e.g. after its long press opened a context menu.
+	pub(crate) fn cancel_touch_click(&mut self, id: u64) {
+		if let Some(touch) = self.pressing_touches.get_mut(&id) {
+			touch.beyond_slop = true;
+		}
+	}
+
+	fn update_slop(touch: &mut TouchState, slop: f32) {
+		if !touch.beyond_slop && (touch.pos - touch.start_pos).length() > slop {
+			touch.beyond_slop = true;
+		}
+	}
+
 	/// Check if the given touch is in the given area.
 	pub fn is_touch_in(&self, rect: Rect) -> bool {
-		self.pressing_touches.values().any(|touch| rect.contains(touch.pos))
+		self.pressing_touches.values().any(|touch| self.hit_test(rect, touch.pos))
 	}
 
 	/// Get all the touches released on the given area, repesented by their ids.
@@ -284,7 +757,7 @@ impl<S: Signal> InputState<S> {
 		let area = area.into();
 		let mut result = vec!();
 		for (id, touch) in self.released_touches.iter() {
-			if id != &MOUSE_UNPRESSED_ID && area.contains(touch.pos) {
+			if id != &MOUSE_UNPRESSED_ID && self.hit_test(area, touch.pos) {
 				result.push(*id);
 			}
 		}
@@ -346,6 +819,7 @@ impl<S: Signal> InputState<S> {
 			shift: self.is_key_pressing(Key::ShiftLeft) || self.is_key_pressing(Key::ShiftRight),
 			ctrl: self.is_key_pressing(Key::ControlLeft) || self.is_key_pressing(Key::ControlRight),
 			alt: self.is_key_pressing(Key::AltLeft) || self.is_key_pressing(Key::AltRight),
+			logo: [Key::SuperLeft, Key::SuperRight, Key::MetaLeft, Key::MetaRight].into_iter().any(|key| self.is_key_pressing(key)),
 		}
 	}
 
@@ -354,17 +828,21 @@ impl<S: Signal> InputState<S> {
 			return;
 		}
 		for event in events {
+			let scale = self.ui_scale();
 			match &event {
-				WindowEvent::Resized(size) => self.window_size = *size / self.scale_factor as f32,
+				WindowEvent::Resized(size) => self.window_size = *size / scale,
 				WindowEvent::CloseRequested => self.should_close = true,
 				WindowEvent::DroppedFile(path) => self.dropped_files.push(path.clone()),
 				WindowEvent::HoveredFile(path) => self.hovering_file = Some(path.clone()),
 				WindowEvent::HoveredFileCancelled => self.hovering_file = None,
 				WindowEvent::Focused(window_focused) => self.window_focused = *window_focused,
+				WindowEvent::Occluded(window_occluded) => self.window_occluded = *window_occluded,
 				WindowEvent::KeyPressed(key) => {
 					let current = OffsetDateTime::now_utc() - self.program_start_time;
-					if !self.modifiers().ctrl && !self.modifiers().alt && !self.is_ime_enabled {
+					self.key_char = None;
+					if !self.modifiers().blocks_text() && !self.is_ime_enabled {
 						if let Some(key) = key.get_char(self.modifiers().shift) {
+							self.key_char = Some(self.cached_input.len());
 							self.cached_input.push(key);
 						}
 					}
@@ -376,6 +854,19 @@ impl<S: Signal> InputState<S> {
 					self.released_keys.insert(*key, OffsetDateTime::now_utc() - self.program_start_time);
 					self.pressing_keys.remove(key);
 				}
+				WindowEvent::TextInput(text) => {
+					let key_char = self.key_char.take();
+					if !self.modifiers().blocks_text() && !self.is_ime_enabled {
+						let typed = text.chars().filter(|chr| !chr.is_control()).collect::<String>();
+						// keys like enter type control characters, keep the guessed character for them.
+						if !typed.is_empty() || text.is_empty() {
+							if let Some(start) = key_char {
+								self.cached_input.truncate(start);
+							}
+							self.cached_input.push_str(&typed);
+						}
+					}
+				},
 				WindowEvent::StringInput(inner) => self.input_string.push_str(inner),
 				WindowEvent::ImeEnabled => {
 					// println!("ime enabled, input string: {}", self.input_string);
@@ -402,7 +893,7 @@ impl<S: Signal> InputState<S> {
 				WindowEvent::MouseMoved(pos) => {
 					let touch = if let Some(touch) = self.pressing_touches.remove(&MOUSE_UNPRESSED_ID)  {
 						TouchState {
-							pos: *pos / self.scale_factor as f32,
+							pos: *pos / scale,
 							..touch
 						}
 					}else {
@@ -410,8 +901,10 @@ impl<S: Signal> InputState<S> {
 							id: MOUSE_UNPRESSED_ID,
 							// to avoid the unwanted click event
 							time: Duration::ZERO,
-							pos: *pos / self.scale_factor as f32,
-							last_pos: *pos / self.scale_factor as f32,
+							pos: *pos / scale,
+							last_pos: *pos / scale,
+							start_pos: *pos / scale,
+							beyond_slop: false,
 							using_by: None,
 							last_used: false,
 						}
@@ -420,7 +913,8 @@ impl<S: Signal> InputState<S> {
 					for i in 0..5 {
 						let id = i + MOUSE_ID;
 						if let Some(touch) = self.pressing_touches.get_mut(&id) {
-							touch.pos = *pos / self.scale_factor as f32;
+							touch.pos = *pos / scale;
+							Self::update_slop(touch, self.click_slop);
 						}
 					}
 				},
@@ -452,6 +946,8 @@ impl<S: Signal> InputState<S> {
 						time: OffsetDateTime::now_utc() - self.program_start_time,
 						pos: mouse_pos,
 						last_pos: mouse_pos,
+						start_pos: mouse_pos,
+						beyond_slop: false,
 						using_by: None,
 						last_used: false,
 					});
@@ -481,14 +977,17 @@ impl<S: Signal> InputState<S> {
 						}
 					}else if let Some(inner) = self.pressing_touches.get_mut(&id) {
 						self.released_touches.retain(|_, touch| touch.id != id);
-						inner.pos = touch.pos / self.scale_factor as f32;
+						inner.pos = touch.pos / scale;
+						Self::update_slop(inner, self.touch_click_slop);
 					}else {
 						self.released_touches.retain(|_, touch| touch.id != id);
 						self.pressing_touches.insert(id, TouchState {
 							id,
 							time: OffsetDateTime::now_utc() - self.program_start_time,
-							pos: touch.pos  / self.scale_factor as f32,
-							last_pos: touch.pos / self.scale_factor as f32,
+							pos: touch.pos  / scale,
+							last_pos: touch.pos / scale,
+							start_pos: touch.pos / scale,
+							beyond_slop: false,
 							using_by: None,
 							last_used: false,
 						});
@@ -506,6 +1005,14 @@ impl<S: Signal> InputState<S> {
 		
 	}
 	
+	/// Check whether the window is fully hidden, e.g. minimized or covered by other windows.
+	/// 
+	/// Nothing is drawn while the window is occluded, the event frames, timers and signals keep running.
+	/// Not every platform reports occlusion, see [`winit::event::WindowEvent::Occluded`].
+	pub fn is_window_occluded(&self) -> bool {
+		self.window_occluded
+	}
+
 	/// Get the window size.
 	pub fn window_size(&self) -> Vec2 {
 		self.window_size
@@ -516,6 +1023,60 @@ impl<S: Signal> InputState<S> {
 		self.scale_factor
 	}
 
+	/// Get the zoom level of the ui, see [`Self::set_zoom`].
+	pub fn zoom(&self) -> f32 {
+		self.zoom
+	}
+
+	/// Get the number of physical pixels per logical pixel, the scale factor multiplied by the zoom level.
+	pub fn ui_scale(&self) -> f32 {
+		self.scale_factor as f32 * self.zoom
+	}
+
+	/// Set the zoom level of the ui, clamped between [`MIN_ZOOM`] and [`MAX_ZOOM`].
+	/// 
+	/// Everything is scaled by the zoom level on top of the scale factor of the window, including the texts, paddings and
+	/// the sizes of the widgets. The window is smaller in logical pixels when zoomed in, so the layout reflows to fit it.
+	pub fn set_zoom(&mut self, zoom: f32) {
+		let zoom = zoom.clamp(MIN_ZOOM, MAX_ZOOM);
+		if zoom == self.zoom {
+			return;
+		}
+		let ratio = self.zoom / zoom;
+		self.window_size *= ratio;
+		for touch in self.pressing_touches.values_mut().chain(self.released_touches.values_mut()) {
+			touch.pos *= ratio;
+			touch.last_pos *= ratio;
+			touch.start_pos *= ratio;
+		}
+		self.zoom = zoom;
+		self.all_dirty = true;
+		self.redraw_requested = true;
+	}
+
+	/// Set whether Ctrl+= and Ctrl+- zoom in and out by [`ZOOM_STEP`], and Ctrl+0 resets the zoom level.
+	/// 
+	/// Cmd is used instead of Ctrl on macOS, see [`Modifiers::primary`].
+	/// 
+	/// The shortcuts are only handled if no widget used the keys. By default, the shortcuts are enabled.
+	pub fn set_zoom_shortcuts(&mut self, enabled: bool) {
+		self.zoom_shortcuts = enabled;
+	}
+
+	/// Handle the zoom shortcuts, called after the widgets handled the events.
+	pub(crate) fn handle_zoom_shortcuts(&mut self) {
+		if !self.zoom_shortcuts || !self.modifiers().primary() {
+			return;
+		}
+		if self.is_key_pressed(Key::Equal) || self.is_key_pressed(Key::KeypadAdd) {
+			self.set_zoom(self.zoom * ZOOM_STEP);
+		}else if self.is_key_pressed(Key::Minus) || self.is_key_pressed(Key::KeypadSubtract) {
+			self.set_zoom(self.zoom / ZOOM_STEP);
+		}else if self.is_key_pressed(Key::Key0) || self.is_key_pressed(Key::Num0) {
+			self.set_zoom(1.0);
+		}
+	}
+
 	/// Get the wheel delta.
 	pub fn wheel_delta(&self) -> Vec2 {
 		self.wheel
@@ -543,6 +1104,34 @@ impl<S: Signal> InputState<S> {
 		self.drag_deltas().get(&id).cloned().unwrap_or_default()
 	}
 
+	/// Get the drags which should scroll containers, as the position where the drag started and the drag delta.
+	/// 
+	/// Only touches moved further than the click slop count as drags.
+	pub(crate) fn scroll_drags(&self) -> Vec<(Vec2, Vec2)> {
+		self.pressing_touches.values().filter_map(|touch| {
+			let delta = touch.pos - touch.last_pos;
+			if touch.id != MOUSE_UNPRESSED_ID && touch.beyond_slop && delta != Vec2::ZERO {
+				Some((touch.start_pos, delta))
+			}else {
+				None
+			}
+		}).collect()
+	}
+
+	/// Get the positions of the touches pressed in this frame.
+	pub(crate) fn pressed_positions(&self) -> Vec<Vec2> {
+		let current = OffsetDateTime::now_utc() - self.program_start_time;
+		self.pressing_touches.values()
+			.filter(|touch| touch.id != MOUSE_UNPRESSED_ID && current - touch.time < DEFAULT_EPSILON_TIME)
+			.map(|touch| touch.pos)
+			.collect()
+	}
+
+	/// Check whether any drag started in the given area is still going on.
+	pub(crate) fn is_drag_scrolling_on(&self, area: Rect) -> bool {
+		self.pressing_touches.values().any(|touch| touch.id != MOUSE_UNPRESSED_ID && touch.beyond_slop && area.contains(touch.start_pos))
+	}
+
 	/// Consume the touch with the given id, let it cant be used by other widgets.
 	pub fn consume_touch(&mut self, id: u64) {
 		if let Some(touch) = self.pressing_touches.get_mut(&id) {
@@ -569,21 +1158,45 @@ impl<S: Signal> InputState<S> {
 	/// 
 	/// If you call maually (outside of event handling loop), the sender will be root.
 	/// If you want to send a signal with a specific sender, use the `send_signal_from` method.
+	/// 
+	/// The touch triggering the signal is guessed as in [`Self::send_signal_from`].
 	pub fn send_signal(&mut self, signal: S) {
-		self.signals_to_send.push(SignalWrapper {
-			signal,
-			from: self.handling_id,
-		});
+		self.send_signal_from(self.handling_id, signal);
 	}
 
 	/// Send a signal to the app, with a specific sender.
+	/// 
+	/// The touch triggering the signal is guessed to be the one released or pressed in the current event frame, 
+	/// use [`Self::send_signal_triggered`] to give it.
 	pub fn send_signal_from(&mut self, from: LayoutId, signal: S) {
+		self.send_signal_triggered(from, self.recent_touch(), signal);
+	}
+
+	/// Send a signal to the app with a specific sender, triggered by the given touch, see [`SignalWrapper::touch`].
+	pub fn send_signal_triggered(&mut self, from: LayoutId, touch: Option<u64>, signal: S) {
+		let pointer = touch.and_then(|touch| self.get_touch_pos(touch)).or_else(|| self.hover_pos());
 		self.signals_to_send.push(SignalWrapper {
 			signal,
 			from,
+			alias: None,
+			time: self.program_running_time(),
+			pointer,
+			modifiers: self.modifiers(),
+			touch,
 		});
 	}
 
+	/// the touch released in the current event frame, or else the latest touch just pressed.
+	fn recent_touch(&self) -> Option<u64> {
+		let current = self.program_running_time();
+		self.released_touches.keys().copied().find(|id| *id != MOUSE_UNPRESSED_ID).or_else(|| {
+			self.pressing_touches.values()
+				.filter(|touch| touch.id != MOUSE_UNPRESSED_ID && current - touch.time < DEFAULT_EPSILON_TIME)
+				.max_by_key(|touch| touch.time)
+				.map(|touch| touch.id)
+		})
+	}
+
 	/// Set the window title.
 	pub fn set_title(&mut self, title: impl Into<String>) {
 		self.output_events.push(OutputEvent::SetWindowTitle(title.into()));
@@ -594,6 +1207,14 @@ impl<S: Signal> InputState<S> {
 		self.output_events.push(OutputEvent::SetCursorIcon(icon));
 	}
 
+	/// Set the cursor to an image, see [`OutputEvent::SetCustomCursor`].
+	/// 
+	/// `rgba` contains the pixels of the image row by row, `hotspot` is the pixel at the pointer position,
+	/// `fallback` is used where custom cursors are not supported.
+	pub fn set_custom_cursor(&mut self, rgba: Vec<u8>, size: impl Into<Vec2>, hotspot: impl Into<Vec2>, fallback: super::event::CursorIcon) {
+		self.output_events.push(OutputEvent::SetCustomCursor(rgba, size.into(), hotspot.into(), fallback));
+	}
+
 	/// Set the cursor position.
 	pub fn set_cursor_position(&mut self, pos: impl Into<Vec2>) {
 		self.output_events.push(OutputEvent::SetCursorPosition(pos.into()));
@@ -614,6 +1235,48 @@ impl<S: Signal> InputState<S> {
 		self.output_events.push(OutputEvent::Move(pos.into()));
 	}
 
+	/// Request the user's attention, `None` to cancel the previous request, see [`Attention`].
+	pub fn request_attention(&mut self, attention: Option<Attention>) {
+		self.output_events.push(OutputEvent::RequestAttention(attention));
+	}
+
+	/// Set the progress shown on the taskbar button, only supported on Windows.
+	pub fn set_taskbar_progress(&mut self, progress: TaskbarProgress) {
+		self.output_events.push(OutputEvent::SetTaskbarProgress(progress));
+	}
+
+	/// Send a custom output event, handled by the handler registered by a widget pack, see [`crate::pack`].
+	/// 
+	/// Events without a handler are dropped with a warning.
+	pub fn send_custom_event(&mut self, name: impl Into<String>, payload: impl std::any::Any + Send + Sync) {
+		self.output_events.push(OutputEvent::Custom(name.into(), std::sync::Arc::new(payload)));
+	}
+
+	/// Set the badge count shown on the dock icon, `None` to remove it, only supported on macOS.
+	pub fn set_badge_count(&mut self, count: Option<u32>) {
+		self.output_events.push(OutputEvent::SetBadgeCount(count));
+	}
+
+	/// Queue an announcement to the assistive technologies, see [`crate::Context::announce`].
+	/// 
+	/// An assertive announcement drops the polite ones queued in the same event frame, since it would interrupt them anyway.
+	/// Repeating the last queued announcement is ignored.
+	pub fn announce(&mut self, text: impl Into<String>, priority: AnnouncePriority) {
+		let text = text.into();
+		if text.is_empty() {
+			return;
+		}
+		if priority == AnnouncePriority::Assertive {
+			self.output_events.retain(|event| !matches!(event, OutputEvent::Announce(_, AnnouncePriority::Polite)));
+		}
+		let repeated = self.output_events.iter().rev()
+			.find_map(|event| if let OutputEvent::Announce(last, last_priority) = event { Some((last, *last_priority)) } else { None })
+			.is_some_and(|(last, last_priority)| *last == text && last_priority == priority);
+		if !repeated {
+			self.output_events.push(OutputEvent::Announce(text, priority));
+		}
+	}
+
 	/// Returns the time since the program started.
 	pub fn run_time(&self) -> Duration {
 		OffsetDateTime::now_utc() - self.program_start_time
@@ -656,8 +1319,9 @@ impl<S: Signal> InputState<S> {
 
 	/// Paste the given text to the input string.
 	pub fn paste_text(&mut self, text: impl Into<String>) {
-		self.pasted_text.push_str(&text.into());
-		println!("pasted: {}", self.pasted_text);
+		let text = text.into();
+		log::trace!("Pasted {} characters", text.chars().count());
+		self.pasted_text.push_str(&text);
 	}
 
 	pub(crate) fn prepare_for_next_frame(&mut self) {
@@ -691,6 +1355,37 @@ impl<S: Signal> InputState<S> {
 		self.pressing_touches.get(&MOUSE_UNPRESSED_ID).map(|touch| touch.pos)
 	}
 
+	/// Get the position of the mouse pointer, `None` if the pointer is outside of the window or there is no mouse.
+	/// 
+	/// Unlike [`Self::touch_positions`], this is available whether any button is pressed or not.
+	pub fn hover_pos(&self) -> Option<Vec2> {
+		self.mouse_pos()
+	}
+
+	/// Request a redraw, which also runs another event frame.
+	/// 
+	/// Useful for widgets waiting for a time without any input, e.g. the hover delay.
+	pub fn request_redraw(&mut self) {
+		self.redraw_requested = true;
+	}
+
+	/// Request to redraw only the given absolute area in the next draw frame, without marking the widget dirty.
+	/// 
+	/// Useful for small changes in a large widget, e.g. the blinking caret of an input box.
+	/// The widgets are drawn as usual, but only the area is refreshed, so the whole change must be inside it.
+	pub fn request_redraw_area(&mut self, area: Rect) {
+		self.damage.add(area);
+		self.redraw_requested = true;
+	}
+
+	/// Run another event frame once the given time passed, without running frames in between.
+	/// 
+	/// Useful for widgets waiting for a time without any input, e.g. debouncing. If requested several times, the earliest wins.
+	pub fn request_frame_after(&mut self, delay: Duration) {
+		let at = self.program_running_time() + delay;
+		self.wake_at = Some(self.wake_at.map_or(at, |wake_at| wake_at.min(at)));
+	}
+
 	/// Mark all widgets dirty, will trigger a redraw.
 	pub fn mark_all_dirty(&mut self) {
 		self.redraw_requested = true;
@@ -722,4 +1417,43 @@ impl<S: Signal> InputState<S> {
 
 	// 	!(has_unprocessed_touch || has_unprocessed_key || has_hovering_file)
 	// }
-}
\ No newline at end of file
+}
+mod test {
+	#[test]
+	fn test_dead_keys() {
+		use super::{ImeString, InputState};
+		use crate::{prelude::Signal, window::event::{Key, WindowEvent}};
+
+		struct Sig;
+		impl Signal for Sig {}
+
+		fn typed(events: Vec<WindowEvent>) -> String {
+			let mut state = InputState::<Sig>::new();
+			state.update(events);
+			state.prepare_for_next_frame();
+			match state.get_input_string() {
+				ImeString::ImeOff(text) => text,
+				_ => String::new(),
+			}
+		}
+
+		fn press(key: Key, text: &str) -> Vec<WindowEvent> {
+			vec!(WindowEvent::KeyPressed(key), WindowEvent::TextInput(text.to_string()), WindowEvent::KeyReleased(key))
+		}
+
+		// german, the acute dead key is right of `ß`.
+		assert_eq!(typed([press(Key::Equal, ""), press(Key::KeyE, "é")].into_iter().flatten().collect()), "é");
+		// french azerty, the circumflex dead key is right of `p` and `a` is where qwerty has `q`.
+		assert_eq!(typed([press(Key::BracketLeft, ""), press(Key::KeyQ, "â")].into_iter().flatten().collect()), "â");
+		// spanish, a dead key followed by space types the accent itself.
+		assert_eq!(typed([press(Key::Quote, ""), press(Key::Space, "´")].into_iter().flatten().collect()), "´");
+		// german, a dead key followed by a key it does not compose with types both.
+		assert_eq!(typed([press(Key::Backquote, ""), press(Key::KeyX, "^x")].into_iter().flatten().collect()), "^x");
+		// shifted keys follow the layout instead of the us symbols.
+		assert_eq!(typed([vec!(WindowEvent::KeyPressed(Key::ShiftLeft)), press(Key::Key2, "\"")].into_iter().flatten().collect()), "\"");
+		// keys typing control characters keep the guessed character.
+		assert_eq!(typed(press(Key::Enter, "\r")), "\n");
+		// hosts without text events still type the guessed characters.
+		assert_eq!(typed(vec!(WindowEvent::KeyPressed(Key::KeyA), WindowEvent::KeyPressed(Key::KeyB))), "ab");
+	}
+}
@@ -1,21 +1,26 @@
 //! A simple window manager for Nablo, based on winit.
 
-use std::sync::Arc;
+use std::{collections::HashMap, hash::{DefaultHasher, Hash, Hasher}, path::PathBuf, sync::Arc};
 
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
 use arboard::Clipboard;
 use time::{Duration, OffsetDateTime};
-use winit::{application::ApplicationHandler, dpi::{PhysicalPosition, PhysicalSize, Position, Size}, event_loop::ActiveEventLoop, window::{self, Icon, Window}};
+use winit::{application::ApplicationHandler, dpi::{PhysicalPosition, PhysicalSize, Position, Size}, event_loop::{ActiveEventLoop, ControlFlow}, window::{self, CustomCursor, Icon, Window}};
 
-use crate::{math::{rect::Rect, vec2::Vec2}, render::{backend::{crate_wgpu_state, Uniform, WgpuState}, painter::Painter}, widgets::Signal, App, Context};
+use crate::{math::{color::Color, vec2::Vec2}, render::backend::{crate_wgpu_state, WgpuState}, widgets::{styles::BACKGROUND_COLOR, Signal}, App, Context};
 
 // use crate::layout::ROOT_LAYOUT_ID;
 
-use super::event::{OutputEvent, Theme};
+#[cfg(any(target_os = "android", target_os = "ios"))]
+use super::mobile::Clipboard;
+use super::{event::{OutputEvent, Theme, WindowEvent}, geometry::{find_monitor, monitor_rect, WindowGeometry}, mobile::{safe_area, MOBILE}, shell::Shell};
 
-const STACK_SIZE: u32 = 64;
 /// Controls the maximum number of characters that can be uploaded per frame.
 pub static MAXIUM_CHAR_UPLOAD_PER_FRAME: usize = 128;
 
+/// Controls the maximum number of custom cursors kept by the manager, the cache is cleared when it is full.
+pub static MAX_CACHED_CURSORS: usize = 64;
+
 /// Settings for the window.
 /// 
 /// All the position and size values are in physical pixels ranther than logical pixels.
@@ -24,6 +29,8 @@ pub struct WindowSettings {
 	/// The title of the window.
 	pub title: String,
 	/// Allows the window to be resized.
+	/// 
+	/// By default, the window is resizable except on mobile platforms.
 	pub resizable: bool,
 	/// The icon of the window.
 	/// 
@@ -47,6 +54,26 @@ pub struct WindowSettings {
 	/// 
 	/// If the position is `None`, the window will be centered on the screen.
 	pub position: Option<Vec2>,
+	/// The name of the monitor to place the window on.
+	/// 
+	/// If the monitor is `None` or not found, the primary monitor will be used.
+	/// Only affects [`Self::centered`] and [`Self::screen_percent`].
+	pub monitor: Option<String>,
+	/// Whether to center the window on the monitor, overrides [`Self::position`].
+	pub centered: bool,
+	/// The size of the window as a fraction of the monitor size, overrides [`Self::default_size`].
+	pub screen_percent: Option<f32>,
+	/// Whether the window is maximized when created.
+	/// 
+	/// By default, the window is maximized on mobile platforms.
+	pub maximized: bool,
+	/// The file to remember the window geometry in.
+	/// 
+	/// If set, the geometry saved in the last run will override the size, position and maximized state,
+	/// and the geometry will be saved when the app exits.
+	/// 
+	/// Needs the `serde` feature, the geometry is not remembered without it.
+	pub geometry_path: Option<PathBuf>,
 	/// The control flow of the event loop.
 	pub control_flow: winit::event_loop::ControlFlow,
 	/// The event frame per second of the window.
@@ -61,6 +88,10 @@ pub struct WindowSettings {
 	/// 
 	/// By default, the frame rate is set to 0.0.
 	pub draw_frame_rate: f32,
+	/// The color the window is cleared with, can be changed later by [`Context::set_clear_color`].
+	/// 
+	/// By default, the clear color is [`crate::widgets::styles::BACKGROUND_COLOR`].
+	pub clear_color: Color,
 	/// The quality factor of the window.
 	/// 
 	/// The quality factor is used to control the quality of the rendering.
@@ -69,27 +100,187 @@ pub struct WindowSettings {
 	/// 
 	/// The lower the value, the lower the quality and the faster the rendering.
 	pub quality_factor: f32,
+	/// The estimated height of the virtual keyboard as a fraction of the window height,
+	/// used to keep the focused widget above the keyboard on mobile platforms, see [`crate::window::input_state::InputState::keyboard_inset`].
+	/// 
+	/// By default, the keyboard height is 0.4 of the window height.
+	pub keyboard_height: f32,
+	/// How to render while the window is being resized.
+	/// 
+	/// If `None`, the UI is relayouted and rendered in full quality on every resize event.
+	/// 
+	/// By default, the live resize mode is off.
+	pub live_resize: Option<LiveResize>,
+	/// Whether to lower the quality factor when frames take too long, and raise it back when they are fast again.
+	/// 
+	/// [`Self::quality_factor`] is the highest factor used, the current one is given by 
+	/// [`crate::window::input_state::InputState::quality_factor`].
+	/// 
+	/// By default, the adaptive quality is off.
+	pub adaptive_quality: Option<AdaptiveQuality>,
+	/// Whether to speak the announcements with the text to speech engine of the system where there is no announcement api for
+	/// the screen readers, see [`Context::announce`]. Supported on Windows and Linux, the latter requires `spd-say` of speech dispatcher.
+	/// 
+	/// The announcements are spoken even without a screen reader running, so only enable it for apps used without one.
+	/// 
+	/// By default, the speech fallback is off.
+	pub speech_fallback: bool,
+}
+
+/// The live resize mode, used while the window is being resized interactively, see [`WindowSettings::live_resize`].
+/// 
+/// Relayouts are throttled and the UI is rendered in reduced quality until no resize event comes for [`Self::settle_time`],
+/// then a full quality frame is drawn.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LiveResize {
+	/// How many times per second the UI is relayouted while resizing.
+	pub relayout_rate: f32,
+	/// The quality factor used while resizing, see [`WindowSettings::quality_factor`].
+	pub quality_factor: f32,
+	/// How long after the last resize event the resizing is considered ended.
+	pub settle_time: Duration,
+}
+
+/// The adaptive quality mode, see [`WindowSettings::adaptive_quality`].
+/// 
+/// The time taken to draw and present every frame is smoothed, 
+/// once it stays over [`Self::frame_budget`] for [`Self::hold_time`], the quality factor is lowered by [`Self::step`].
+/// It is raised back by a step once the frame time stays under the budget scaled by [`Self::headroom`] as long,
+/// the gap between the two keeps the factor from flipping back and forth.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AdaptiveQuality {
+	/// How long a frame may take, including waiting for the display.
+	pub frame_budget: Duration,
+	/// The fraction of the budget the frame time has to fall under before the quality is raised.
+	pub headroom: f32,
+	/// The lowest quality factor used.
+	pub min_factor: f32,
+	/// How much the quality factor changes at once.
+	pub step: f32,
+	/// How long the frame time has to stay over or under the thresholds before the quality changes.
+	pub hold_time: Duration,
+}
+
+impl Default for AdaptiveQuality {
+	fn default() -> Self {
+		Self {
+			frame_budget: Duration::milliseconds(20),
+			headroom: 0.9,
+			min_factor: 0.5,
+			step: 0.1,
+			hold_time: Duration::milliseconds(500),
+		}
+	}
+}
+
+impl Default for LiveResize {
+	fn default() -> Self {
+		Self {
+			relayout_rate: 30.0,
+			quality_factor: 0.5,
+			settle_time: Duration::milliseconds(150),
+		}
+	}
 }
 
 impl Default for WindowSettings {
 	fn default() -> Self {
 		Self {
 			title: "Nablo UI".to_string(),
-			resizable: true,
+			resizable: !MOBILE,
 			icon: None,
 			min_size: None,
 			max_size: None,
 			default_size: None,
 			position: None,
+			monitor: None,
+			centered: false,
+			screen_percent: None,
+			maximized: MOBILE,
+			geometry_path: None,
 			control_flow: winit::event_loop::ControlFlow::Poll,
 			event_frame_rate: 0.0,
 			draw_frame_rate: 0.0,
 			theme: Theme::Dark,
+			clear_color: BACKGROUND_COLOR,
 			quality_factor: 1.0,
+			keyboard_height: 0.4,
+			live_resize: None,
+			adaptive_quality: None,
+			speech_fallback: false,
 		}
 	}
 }
 
+impl WindowSettings {
+	/// Center the window on the primary monitor.
+	pub fn centered_on_primary(self) -> Self {
+		Self {
+			monitor: None,
+			centered: true,
+			..self
+		}
+	}
+
+	/// Center the window on the monitor with the given name.
+	pub fn centered_on(self, monitor: impl Into<String>) -> Self {
+		Self {
+			monitor: Some(monitor.into()),
+			centered: true,
+			..self
+		}
+	}
+
+	/// Size the window to the given fraction of the monitor size, clamped to [0.1, 1.0].
+	pub fn percent_of_screen(self, percent: f32) -> Self {
+		Self {
+			screen_percent: Some(percent.clamp(0.1, 1.0)),
+			..self
+		}
+	}
+
+	/// Remember the window geometry in the given file across runs, needs the `serde` feature.
+	pub fn remember_geometry(self, path: impl Into<PathBuf>) -> Self {
+		Self {
+			geometry_path: Some(path.into()),
+			..self
+		}
+	}
+
+	/// Resolve the size and position of the window from the settings and the saved geometry.
+	fn resolve_geometry(&self, event_loop: &ActiveEventLoop) -> (Option<Vec2>, Option<Vec2>, bool) {
+		#[cfg(feature = "serde")]
+		let saved = self.geometry_path.as_ref().and_then(|path| match WindowGeometry::load(path) {
+			Ok(geometry) => Some(geometry),
+			Err(e) => {
+				log::debug!("No window geometry restored from {}: {}", path.display(), e);
+				None
+			}
+		}).filter(|geometry| geometry.is_visible_on(event_loop.available_monitors().map(|monitor| monitor_rect(&monitor))));
+		#[cfg(not(feature = "serde"))]
+		let saved: Option<WindowGeometry> = None;
+
+		if let Some(saved) = saved {
+			return (Some(saved.size), saved.position, saved.maximized);
+		}
+
+		let monitor = find_monitor(event_loop, self.monitor.as_deref()).map(|monitor| monitor_rect(&monitor));
+		let size = match (self.screen_percent, monitor) {
+			(Some(percent), Some(monitor)) => Some(monitor.size() * percent),
+			_ => self.default_size,
+		};
+		let position = match (self.centered, monitor) {
+			(true, Some(monitor)) => {
+				// winit creates 800x600 windows by default.
+				let size = size.unwrap_or(Vec2::new(800.0, 600.0));
+				Some(monitor.lt() + (monitor.size() - size) / 2.0)
+			},
+			_ => self.position,
+		};
+		(size, position, self.maximized)
+	}
+}
+
 /// A Simple window manager for Nablo UI.
 // #[allow(dead_code)]
 pub struct Manager<'w, A, S: Signal> 
@@ -104,6 +295,29 @@ where A: App<Signal = S>,
 	last_event_time: Duration,
 	last_draw_time: Duration,
 	clipboard: Option<Clipboard>,
+	/// the last geometry of the window when it was not maximized.
+	geometry: Option<WindowGeometry>,
+	/// the theme when the clear color was last synced.
+	theme: Option<Theme>,
+	/// the time of the last resize event, if the window is being resized in live resize mode.
+	last_resize_time: Option<Duration>,
+	/// the time of the last relayout caused by resizing.
+	last_relayout_time: Duration,
+	/// whether a resize is waiting for the next throttled relayout.
+	relayout_pending: bool,
+	/// when the app was suspended, `None` if the app is running or not started yet.
+	suspended_at: Option<OffsetDateTime>,
+	/// whether the virtual keyboard is shown.
+	keyboard_shown: bool,
+	shell: Shell,
+	/// the created custom cursors, keyed by the hash of the image and the hotspot.
+	custom_cursors: HashMap<u64, CustomCursor>,
+	/// the quality factor picked by the adaptive quality, `None` to use the one in the settings.
+	adaptive_factor: Option<f32>,
+	/// the smoothed time taken by a frame in seconds.
+	frame_cost: f32,
+	/// whether the frame time is over the budget (`true`) or under the headroom, and since when.
+	adaptive_since: Option<(bool, Duration)>,
 	// font_texture_to_upload: Vec<(Vec<u8>, char, FontId)>,
 }
 
@@ -125,26 +339,50 @@ where
 		if let Some(max_size) = self.window_settings.max_size {
 			attributes.max_inner_size = Some(Size::Physical(PhysicalSize::from([max_size.x as u32, max_size.y as u32])));
 		}
-		if let Some(default_size) = self.window_settings.default_size {
+		let (size, position, maximized) = self.window_settings.resolve_geometry(event_loop);
+		if let Some(default_size) = size {
 			attributes.inner_size = Some(Size::Physical(PhysicalSize::from([default_size.x as u32, default_size.y as u32])));
 		}
-		if let Some(position) = self.window_settings.position {
+		if let Some(position) = position {
 			attributes.position = Some(Position::Physical(PhysicalPosition::from([position.x as i32, position.y as i32])));
 		}
+		attributes.maximized = maximized;
 		attributes.preferred_theme = Some(match &self.window_settings.theme {
 			Theme::Dark => winit::window::Theme::Dark,
 			Theme::Light => winit::window::Theme::Light,
 		});
 		let window = event_loop.create_window(attributes).expect("Failed to create window");
-		window.set_ime_allowed(true);
+		// enabling ime brings up the soft keyboard on mobile platforms.
+		window.set_ime_allowed(!MOBILE);
+		self.ctx.input_state.safe_area = safe_area(&window);
+		self.ctx.input_state.keyboard_height_fraction = self.window_settings.keyboard_height;
 		self.ctx.input_state.scale_factor = window.scale_factor();
 		self.ctx.input_state.window_size = Vec2::new(window.inner_size().width as f32, window.inner_size().height as f32);
-		self.app.on_start(&mut self.ctx);
+		let suspended_at = self.suspended_at.take();
+		if let Some(suspended_at) = suspended_at {
+			// the running time stops while suspended, so animations and timers continue where they were.
+			self.ctx.input_state.program_start_time += OffsetDateTime::now_utc() - suspended_at;
+		}else {
+			self.ctx.set_clear_color_without_animation(self.window_settings.clear_color);
+			self.app.on_start(&mut self.ctx);
+		}
 		self.ctx.input_state.window_focused = true;
+		self.ctx.input_state.window_occluded = false;
 		let size = self.ctx.input_state.window_size;
 		let window = Arc::new(window);
-		let state = crate_wgpu_state(window.clone(), size);
+		let mut state = crate_wgpu_state(window.clone(), size);
+		#[cfg(feature = "render-graph")]
+		self.ctx.render_graph.invalidate();
+		self.ctx.viewports.invalidate();
+		if suspended_at.is_some() {
+			self.restore_gpu_resources(&mut state);
+		}
 		self.window = Some((window, state));
+		self.record_geometry();
+		if suspended_at.is_some() {
+			self.ctx.layout.make_all_dirty();
+			self.app.on_resume(&mut self.ctx);
+		}
 	}
 
 	fn window_event(
@@ -157,15 +395,46 @@ where
 			return;
 		}
 
+		log::trace!("Window event: {:?}", event);
+
+		if matches!(event, winit::event::WindowEvent::Resized(_) | winit::event::WindowEvent::Moved(_)) {
+			self.record_geometry();
+		}
+
+		if let winit::event::WindowEvent::Occluded(occluded) = &event {
+			if let Some((window, state)) = &mut self.window {
+				if !*occluded && self.ctx.input_state.window_occluded {
+					// the compositor may have dropped the contents, draw the whole window again.
+					state.is_first_frame = true;
+					self.ctx.layout.make_all_dirty();
+					window.request_redraw();
+				}
+			}
+		}
+
 		if let winit::event::WindowEvent::Resized(size) = &event {
 			self.ctx.input_state.window_size = Vec2::new(size.width as f32, size.height as f32);
+			let quality_factor = if let Some(live_resize) = &self.window_settings.live_resize {
+				self.last_resize_time = Some(self.ctx.input_state.program_running_time());
+				live_resize.quality_factor
+			}else {
+				self.current_quality_factor()
+			};
+			self.ctx.input_state.quality_factor = quality_factor;
 			if let Some((window, state)) = &mut self.window {
-				state.resized(self.ctx.input_state.window_size, self.window_settings.quality_factor);
+				state.resized(self.ctx.input_state.window_size, quality_factor);
 				self.ctx.input_state.scale_factor = window.scale_factor();
+				self.ctx.input_state.safe_area = safe_area(window);
+			}
+			if self.window_settings.live_resize.is_some() {
+				self.relayout_pending = true;
+			}else {
+				self.ctx.layout.make_all_dirty();
 			}
-			self.ctx.layout.make_all_dirty();
 		}
 
+		self.update_live_resize();
+
 		// if let winit::event::WindowEvent::Focused(focused) = &event {
 		// 	if let Some((window, state)) = &mut self.window {
 		// 		if *focused {
@@ -177,7 +446,7 @@ where
 		// 	}
 		// }
 
-		self.ctx.input_state.update(vec!(event.into()));
+		self.ctx.input_state.update(WindowEvent::from_winit(event));
 		#[allow(clippy::collapsible_if)]
 		if self.ctx.input_state.should_close {
 			if self.app.on_request_exit(&mut self.ctx) {
@@ -193,28 +462,29 @@ where
 			event_delta_time - self.last_event_time >= Duration::seconds_f32(1.0 / self.window_settings.event_frame_rate)
 		};
 
-		if should_handle_events {
-			self.last_event_time = event_delta_time;
-			// self.ctx.layout.handle_continous_events(&mut self.ctx.input_state);
-			self.ctx.layout.handle_events(&mut self.ctx.input_state, &mut self.app);
-			let signals = self.ctx.input_state.signals_to_send.drain(..).collect::<Vec<_>>();
-			for signal in signals {
-				self.app.on_signal(&mut self.ctx, signal);
+		if self.theme != Some(self.ctx.input_state.theme) {
+			let theme = self.ctx.input_state.theme;
+			if let Some(color) = self.ctx.theme_clear_colors.get(&theme).copied() {
+				if self.theme.is_some() {
+					self.ctx.set_clear_color(color);
+				}else {
+					self.ctx.set_clear_color_without_animation(color);
+				}
 			}
+			self.theme = Some(theme);
+		}
 
-			let events = if let Ok(mut events) = self.ctx.fonts.lock() {
-				events.generate_textures()
-			}else {
-				panic!("Failed to lock font pool")
-			};
-
-			self.ctx.input_state.output_events.extend(events);
-
-			self.ctx.input_state.prepare_for_next_frame();
-
-			if self.ctx.input_state.all_dirty {
-				self.ctx.input_state.all_dirty = false;
-				self.ctx.layout.make_all_dirty();
+		if should_handle_events {
+			self.ctx.input_state.delta_time = event_delta_time - self.last_event_time;
+			self.last_event_time = event_delta_time;
+			self.ctx.handle_event_frame(&mut self.app);
+			if self.ctx.input_state.is_keyboard_visible() != self.keyboard_shown {
+				self.keyboard_shown = self.ctx.input_state.is_keyboard_visible();
+				self.ctx.input_state.output_events.push(if self.keyboard_shown {
+					OutputEvent::ShowKeyboard
+				}else {
+					OutputEvent::HideKeyboard
+				});
 			}
 
 			if let Some((window, state)) = &mut self.window {
@@ -230,6 +500,9 @@ where
 				// }
 
 				for event in output_events {
+					let Some(event) = state.handle_output_event(event) else {
+						continue;
+					};
 					match event {
 						OutputEvent::SetWindowTitle(title) => {
 							window.set_title(&title);
@@ -244,6 +517,33 @@ where
 						OutputEvent::SetCursorIcon(icon) => {
 							window.set_cursor(icon);
 						},
+						OutputEvent::SetCustomCursor(rgba, size, hotspot, fallback) => {
+							if MOBILE {
+								window.set_cursor(fallback);
+								continue;
+							}
+							let mut hasher = DefaultHasher::new();
+							(&rgba, size.x.to_bits(), size.y.to_bits(), hotspot.x.to_bits(), hotspot.y.to_bits()).hash(&mut hasher);
+							let key = hasher.finish();
+							if let Some(cursor) = self.custom_cursors.get(&key) {
+								window.set_cursor(cursor.clone());
+								continue;
+							}
+							match CustomCursor::from_rgba(rgba, size.x as u16, size.y as u16, hotspot.x as u16, hotspot.y as u16) {
+								Ok(source) => {
+									if self.custom_cursors.len() >= MAX_CACHED_CURSORS {
+										self.custom_cursors.clear();
+									}
+									let cursor = event_loop.create_custom_cursor(source);
+									window.set_cursor(cursor.clone());
+									self.custom_cursors.insert(key, cursor);
+								},
+								Err(e) => {
+									log::warn!("Failed to create custom cursor, using the fallback: {}", e);
+									window.set_cursor(fallback);
+								},
+							}
+						},
 						OutputEvent::SetCursorPosition(position) => {
 							window.set_cursor_position(Position::Physical(PhysicalPosition::from([position.x as i32, position.y as i32])))
 								.expect("Failed to set cursor position");
@@ -251,32 +551,50 @@ where
 						OutputEvent::SetCursorVisible(visible) => {
 							window.set_cursor_visible(visible);
 						},
-						OutputEvent::RegisterTexture(size, data) => {
-							state.insert_texture(&data, size.x as u32, size.y as u32).expect("Failed to create texture");
+						OutputEvent::CopyToClipboard(text) => {
+							if let Some(cb) = &mut self.clipboard {
+								if let Err(e) = cb.set_text(text) {
+									log::warn!("Failed to set clipboard: {}", e);
+								}
+							}else {
+								log::warn!("Clipboard is not available, copied text is dropped");
+							}
 						},
-						OutputEvent::UpdateTexture(texture_id, size, data) => {
-							state.update_texture(texture_id, &data,size.x as u32, size.y as u32).expect("Failed to update texture");
+						OutputEvent::ShowKeyboard => {
+							if MOBILE {
+								window.set_ime_allowed(true);
+								#[cfg(target_os = "android")]
+								{
+									use winit::platform::android::ActiveEventLoopExtAndroid;
+									event_loop.android_app().show_soft_input(true);
+								}
+							}
 						},
-						OutputEvent::RemoveTexture(texture_id) => {
-							state.remove_texture(texture_id);
+						OutputEvent::HideKeyboard => {
+							if MOBILE {
+								window.set_ime_allowed(false);
+								#[cfg(target_os = "android")]
+								{
+									use winit::platform::android::ActiveEventLoopExtAndroid;
+									event_loop.android_app().hide_soft_input(false);
+								}
+							}
 						},
-						OutputEvent::ClearTexture => {
-							state.clear_texture();
+						OutputEvent::RequestAttention(attention) => {
+							window.request_user_attention(attention.map(Into::into));
 						},
-						OutputEvent::AddChar(data, chr, font_id) => {
-							// self.font_texture_to_upload.push((data, chr, font_id));
-							state.add_char(font_id, chr, data);
+						OutputEvent::SetTaskbarProgress(progress) => {
+							self.shell.set_progress(window, progress);
 						},
-						OutputEvent::RemoveFont(font_id) => {
-							state.remove_font(font_id);
+						OutputEvent::SetBadgeCount(count) => {
+							self.shell.set_badge(count);
 						},
-						OutputEvent::CopyToClipboard(text) => {
-							if let Some(cb) = &mut self.clipboard {
-								if let Err(e) = cb.set_text(text) {
-									println!("Failed to set clipboard: {}", e);
-								}
-							}else {
-								println!("WARN: Failed to create clipboard")
+						OutputEvent::Announce(text, priority) => {
+							self.shell.announce(&text, priority, self.window_settings.speech_fallback);
+						},
+						OutputEvent::Custom(name, payload) => {
+							if !self.ctx.extensions.handle_output(window, &name, payload.as_ref()) {
+								log::warn!("No handler for output event `{}`, event is dropped", name);
 							}
 						},
 						OutputEvent::RequestClipboard => {
@@ -286,17 +604,25 @@ where
 										self.ctx.input_state.paste_text(text);
 									},
 									Err(e) => {
-										println!("Failed to get clipboard: {}", e);
+										log::warn!("Failed to get clipboard: {}", e);
 									}
 								}
 							}else {
-								println!("WARN: Failed to create clipboard")
+								log::warn!("Clipboard is not available, paste request is ignored");
 							}
 						},
+						// already handled by the gpu state.
+						OutputEvent::RegisterTexture(..) | 
+						OutputEvent::UpdateTexture(..) | 
+						OutputEvent::RemoveTexture(_) | 
+						OutputEvent::ClearTexture | 
+						OutputEvent::AddChar(..) | 
+						OutputEvent::RemoveFont(_) => {},
 					}
 				}
 			
 				self.app.on_event_frame(&mut self.ctx);
+				self.ctx.layout.notify_dirty();
 			}
 		}
 
@@ -306,69 +632,57 @@ where
 			true
 		}else {
 			(draw_delta_time - self.last_draw_time) >= Duration::seconds_f32(1.0 / self.window_settings.draw_frame_rate)
-		} && (self.ctx.input_state.redraw_requested || self.ctx.layout.any_widget_dirty() || self.ctx.force_redraw_per_frame);
+		} && self.ctx.needs_redraw() && !self.ctx.input_state.window_occluded;
 
 		if should_draw {
-			self.ctx.input_state.redraw_requested = false;
-			let mut painter = Painter::new(self.ctx.fonts.clone(), self.ctx.input_state.window_size);
-			painter.set_scale_factor(self.ctx.input_state.scale_factor as f32);
-			
-			if self.ctx.force_redraw_per_frame {
-				self.ctx.layout.make_all_dirty();
-			}
-			
-			self.app.on_draw_frame(&mut self.ctx);
-			let refresh_area = self.ctx.layout.handle_draw(&mut painter, self.ctx.input_state.window_size);
-			let refresh_area = if self.ctx.force_redraw_per_frame {
-				Rect::WINDOW
-			}else if let Some(area) = refresh_area {
-				area
-			}else {
+			#[cfg(feature = "capture")]
+			let capturing = self.ctx.capture.is_some();
+			#[cfg(not(feature = "capture"))]
+			let capturing = false;
+			let delta = draw_delta_time - self.last_draw_time;
+			let frame_start = std::time::Instant::now();
+			let Some((painter, refresh_area)) = self.ctx.draw_frame(&mut self.app, delta, capturing) else {
 				return;
 			};
 			if let Some((window, state)) =  &mut self.window {
-				// painter.shapes.reverse();
-				let (commands, stack_len) = painter.parse(
-					&state.font_render,
-					refresh_area
-				);
-
-				if stack_len >= STACK_SIZE {
-					panic!("Gpu Stack overflows, max size is {} but current size is {}", STACK_SIZE, stack_len);
-				}
-				// println!("commands: {:#?}", commands);
-				// panic!();
-				let window_size = self.ctx.input_state.window_size();
-				let mouse_pos = self.ctx.input_state.mouse_pos().unwrap_or(Vec2::INF);
-				let time = (OffsetDateTime::now_utc() - self.ctx.input_state.program_start_time).as_seconds_f32();
-
-				let uniform = Uniform {
-					window_size: [
-						window_size.x, 
-						window_size.y
-					],
-					mouse: [
-						mouse_pos.x, 
-						mouse_pos.y
-					],
-					time,
-					scale_factor: self.ctx.input_state.scale_factor as f32,
-					command_len: commands.len() as u32,
-					stack_len,
-				};
+				let (commands, uniform) = self.ctx.build_frame(painter, &state.font_render.char_texture_map, &refresh_area);
+				state.render_viewports(&mut self.ctx.viewports, &uniform);
+				state.set_clear_color(self.ctx.clear_color());
 				state.draw(
 					refresh_area, 
 					commands,
 					// stack_len as u64,
 					uniform, 
+					#[cfg(feature = "render-graph")]
+					&mut self.ctx.render_graph,
 				);
-				if self.ctx.force_redraw_per_frame {
+				if self.ctx.force_redraw_per_frame || capturing || self.ctx.clear_color.is_animating() {
 					window.request_redraw();
 				}
+				#[cfg(feature = "capture")]
+				if let Some(capture) = &mut self.ctx.capture {
+					let now = OffsetDateTime::now_utc() - self.ctx.input_state.program_start_time;
+					if capture.wants_frame(now) {
+						if let Some((width, height, rgba)) = state.read_frame() {
+							capture.push_frame(now, width, height, rgba);
+						}else {
+							log::warn!("Failed to read back the frame, capture is aborted");
+							self.ctx.capture = None;
+						}
+					}
+					if self.ctx.capture.as_ref().is_some_and(|capture| capture.is_finished()) {
+						let capture = self.ctx.capture.take().unwrap();
+						std::thread::spawn(move || match capture.save() {
+							Ok(path) => log::info!("Capture saved to {}", path.display()),
+							Err(e) => log::error!("Failed to save capture: {}", e),
+						});
+					}
+				}
 				state.cleanup();
 			}
 			self.ctx.input_state.redraw_requested = false;
 			self.last_draw_time = draw_delta_time;
+			self.update_adaptive_quality(frame_start.elapsed().as_secs_f32());
 			// render::backend::render(painter.parse());
 		}
 
@@ -377,12 +691,41 @@ where
 		}
 	}
 
+	fn about_to_wait(&mut self, event_loop: &ActiveEventLoop) {
+		let Some(wake_at) = self.ctx.input_state.wake_at else { return };
+		let remaining = wake_at - self.ctx.input_state.program_running_time();
+		if remaining <= Duration::ZERO {
+			self.ctx.input_state.wake_at = None;
+			event_loop.set_control_flow(self.window_settings.control_flow);
+			if let Some((window, _)) = &self.window {
+				window.request_redraw();
+			}
+			return;
+		}
+
+		// wake up on time if the event loop would wait for events otherwise.
+		let remaining = std::time::Duration::try_from(remaining).unwrap_or_default();
+		let control_flow = match self.window_settings.control_flow {
+			ControlFlow::Poll => ControlFlow::Poll,
+			ControlFlow::WaitUntil(instant) => match ControlFlow::wait_duration(remaining) {
+				ControlFlow::WaitUntil(wake) => ControlFlow::WaitUntil(instant.min(wake)),
+				_ => ControlFlow::WaitUntil(instant),
+			},
+			ControlFlow::Wait => ControlFlow::wait_duration(remaining),
+		};
+		event_loop.set_control_flow(control_flow);
+	}
+
 	fn suspended(&mut self, _: &ActiveEventLoop) {
+		self.save_geometry();
+		self.app.on_suspend(&mut self.ctx);
 		self.window = None;
+		self.suspended_at = Some(OffsetDateTime::now_utc());
 	}
 
 	fn exiting(&mut self, _: &ActiveEventLoop) {
 		self.app.on_exit(&mut self.ctx);
+		self.save_geometry();
 	}
 }
 
@@ -391,9 +734,26 @@ where A: App<Signal = S>,
 {
 	/// Creates a new manager with the given app.
 	pub fn new(app: A, font_data: Vec<u8>, font_index: u32) -> Self {
+		let mut manager = Self::new_without_fonts(app);
+		manager.ctx.insert_font(font_data, font_index);
+		manager
+	}
+
+	/// Creates a new manager with the given app, using the embedded default font.
+	/// 
+	/// See [`crate::render::font::DEFAULT_FONT`] for details.
+	#[cfg(feature = "default-font")]
+	pub fn new_with_default_font(app: A) -> Self {
+		Self::new(app, crate::render::font::DEFAULT_FONT.to_vec(), 0)
+	}
+
+	/// Creates a new manager with the given app but without any font.
+	/// 
+	/// Load fonts later by [`Context::insert_font`], texts will not be drawn until then.
+	pub fn new_without_fonts(app: A) -> Self {
 		Self {
 			app,
-			ctx: Context::new(font_data, font_index),
+			ctx: Context::without_fonts(),
 			window: None,
 			last_event_time: Duration::ZERO,
 			last_draw_time: Duration::ZERO,
@@ -401,10 +761,22 @@ where A: App<Signal = S>,
 			clipboard: match Clipboard::new() {
 				Ok(clipboard) => Some(clipboard),
 				Err(e) => {
-					eprintln!("Failed to create clipboard: {}", e);
+					log::error!("Failed to create clipboard: {}", e);
 					None
 				}
 			},
+			geometry: None,
+			theme: None,
+			last_resize_time: None,
+			last_relayout_time: Duration::ZERO,
+			relayout_pending: false,
+			suspended_at: None,
+			keyboard_shown: false,
+			shell: Shell::default(),
+			custom_cursors: HashMap::new(),
+			adaptive_factor: None,
+			frame_cost: 0.0,
+			adaptive_since: None,
 			// font_texture_to_upload: vec!(),
 		}
 	}
@@ -442,6 +814,17 @@ where A: App<Signal = S>,
 		}
 	}
 
+	/// Sets the color the window is cleared with.
+	pub fn clear_color(self, clear_color: impl Into<Color>) -> Self {
+		Self {
+			window_settings: WindowSettings {
+				clear_color: clear_color.into(),
+				..self.window_settings
+			},
+			..self
+		}
+	}
+
 	/// Sets the theme of the window.
 	pub fn theme(self, theme: Theme) -> Self {
 		Self {
@@ -497,6 +880,205 @@ where A: App<Signal = S>,
 		}
 	}
 
+	/// Centers the window on the primary monitor.
+	pub fn centered_on_primary(self) -> Self {
+		Self {
+			window_settings: self.window_settings.centered_on_primary(),
+			..self
+		}
+	}
+
+	/// Sizes the window to the given fraction of the monitor size.
+	pub fn percent_of_screen(self, percent: f32) -> Self {
+		Self {
+			window_settings: self.window_settings.percent_of_screen(percent),
+			..self
+		}
+	}
+
+	/// Sets whether the window is maximized when created.
+	pub fn maximized(self, maximized: bool) -> Self {
+		Self {
+			window_settings: WindowSettings {
+				maximized,
+				..self.window_settings
+			},
+			..self
+		}
+	}
+
+	/// Remembers the window geometry in the given file across runs.
+	pub fn remember_geometry(self, path: impl Into<PathBuf>) -> Self {
+		Self {
+			window_settings: self.window_settings.remember_geometry(path),
+			..self
+		}
+	}
+
+	/// Get the current geometry of the window, `None` if the window is not created yet.
+	/// 
+	/// The size and position are the ones before the window was maximized.
+	pub fn geometry(&self) -> Option<WindowGeometry> {
+		let (window, _) = self.window.as_ref()?;
+		let current = WindowGeometry::from_window(window);
+		Some(match &self.geometry {
+			Some(normal) if current.maximized => WindowGeometry {
+				size: normal.size,
+				position: normal.position,
+				..current
+			},
+			_ => current,
+		})
+	}
+
+	/// Upload the retained textures and the loaded glyphs to the recreated gpu state.
+	fn restore_gpu_resources(&mut self, state: &mut WgpuState<'_>) {
+		// the retained textures already reflect the pending texture changes, the others are only kept by the pending events.
+		let events = std::mem::take(&mut self.ctx.input_state.output_events);
+		let last_clear = events.iter().rposition(|event| matches!(event, OutputEvent::ClearTexture));
+		let retained = &self.ctx.texture_data;
+		self.ctx.input_state.output_events = events.into_iter().enumerate().filter(|(i, event)| match event {
+			OutputEvent::RegisterTexture(id, ..) | 
+			OutputEvent::UpdateTexture(id, ..) | 
+			OutputEvent::RemoveTexture(id) => !retained.contains_key(id) && last_clear.is_none_or(|clear| *i > clear),
+			OutputEvent::ClearTexture => false,
+			_ => true,
+		}).map(|(_, event)| event).collect();
+		let lost = self.ctx.textures.keys().filter(|id| !self.ctx.texture_data.contains_key(id)).count();
+		if lost > 0 {
+			log::warn!("{} textures are not retained and need to be registered again, see `Context::retain_textures`", lost);
+		}
+		let textures = self.ctx.texture_data.iter().filter_map(|(id, rgba)| {
+			self.ctx.textures.get(id).map(|texture| (*id, rgba.as_slice(), texture.width, texture.height))
+		});
+		if let Err(e) = state.restore_textures(textures, self.ctx.available_texture_ids.clone()) {
+			log::error!("Failed to restore textures: {}", e);
+		}
+		if let Ok(mut fonts) = self.ctx.fonts.lock() {
+			fonts.reupload_glyphs();
+		}
+	}
+
+	/// Throttle the relayouts while resizing, and restore the quality when the resizing ends.
+	/// Get the quality factor used outside of live resizing.
+	fn current_quality_factor(&self) -> f32 {
+		self.adaptive_factor.unwrap_or(self.window_settings.quality_factor)
+	}
+
+	/// Feed the time taken by the last frame to the adaptive quality, changing the quality factor if needed.
+	fn update_adaptive_quality(&mut self, frame_cost: f32) {
+		const SMOOTHING: f32 = 0.1;
+		let Some(adaptive) = self.window_settings.adaptive_quality else {
+			return;
+		};
+		self.frame_cost = if self.frame_cost > 0.0 {
+			self.frame_cost + (frame_cost - self.frame_cost) * SMOOTHING
+		}else {
+			frame_cost
+		};
+		// live resizing picks its own quality.
+		if self.last_resize_time.is_some() {
+			self.adaptive_since = None;
+			return;
+		}
+
+		let max = self.window_settings.quality_factor;
+		let min = adaptive.min_factor.min(max);
+		let current = self.current_quality_factor().clamp(min, max);
+		let budget = adaptive.frame_budget.as_seconds_f32();
+		let over = if self.frame_cost > budget && current > min {
+			true
+		}else if self.frame_cost < budget * adaptive.headroom && current < max {
+			false
+		}else {
+			self.adaptive_since = None;
+			return;
+		};
+
+		let now = self.ctx.input_state.program_running_time();
+		match self.adaptive_since {
+			Some((since_over, since)) if since_over == over => {
+				if now - since < adaptive.hold_time {
+					return;
+				}
+			},
+			_ => {
+				self.adaptive_since = Some((over, now));
+				return;
+			},
+		}
+
+		let factor = if over { current - adaptive.step } else { current + adaptive.step }.clamp(min, max);
+		log::debug!("Adaptive quality changes the quality factor from {} to {}, frame time {:.2}ms", current, factor, self.frame_cost * 1000.0);
+		self.adaptive_factor = Some(factor);
+		self.adaptive_since = None;
+		// the frames at the new quality are measured from scratch.
+		self.frame_cost = 0.0;
+		self.ctx.input_state.quality_factor = factor;
+		if let Some((window, state)) = &mut self.window {
+			state.resized(self.ctx.input_state.window_size, factor);
+			window.request_redraw();
+		}
+		self.ctx.layout.make_all_dirty();
+	}
+
+	fn update_live_resize(&mut self) {
+		let (Some(live_resize), Some(last_resize_time)) = (self.window_settings.live_resize, self.last_resize_time) else {
+			return;
+		};
+		let now = self.ctx.input_state.program_running_time();
+		if now - last_resize_time >= live_resize.settle_time {
+			self.last_resize_time = None;
+			self.relayout_pending = false;
+			let quality_factor = self.current_quality_factor();
+			self.ctx.input_state.quality_factor = quality_factor;
+			if let Some((_, state)) = &mut self.window {
+				state.resized(self.ctx.input_state.window_size, quality_factor);
+			}
+			self.ctx.layout.make_all_dirty();
+			return;
+		}
+
+		let interval = if live_resize.relayout_rate > 0.0 {
+			Duration::seconds_f32(1.0 / live_resize.relayout_rate)
+		}else {
+			Duration::ZERO
+		};
+		if self.relayout_pending && now - self.last_relayout_time >= interval {
+			self.relayout_pending = false;
+			self.last_relayout_time = now;
+			self.ctx.layout.make_all_dirty();
+		}
+		// keep the frames coming until the resizing ends.
+		if let Some((window, _)) = &self.window {
+			window.request_redraw();
+		}
+	}
+
+	fn record_geometry(&mut self) {
+		if let Some((window, _)) = &self.window {
+			let geometry = WindowGeometry::from_window(window);
+			if !geometry.maximized {
+				self.geometry = Some(geometry);
+			}
+		}
+	}
+
+	#[cfg(feature = "serde")]
+	fn save_geometry(&self) {
+		let Some(path) = &self.window_settings.geometry_path else {
+			return;
+		};
+		if let Some(geometry) = self.geometry() {
+			if let Err(e) = geometry.save(path) {
+				log::warn!("Failed to save window geometry to {}: {}", path.display(), e);
+			}
+		}
+	}
+
+	#[cfg(not(feature = "serde"))]
+	fn save_geometry(&self) {}
+
 	/// Sets the control flow of the event loop.
 	pub fn control_flow(self, control_flow: winit::event_loop::ControlFlow) -> Self {
 		Self {
@@ -541,6 +1123,39 @@ where A: App<Signal = S>,
 		}
 	}
 
+	/// Sets the adaptive quality mode, see [`WindowSettings::adaptive_quality`].
+	pub fn adaptive_quality(self, adaptive_quality: Option<AdaptiveQuality>) -> Self {
+		Self {
+			window_settings: WindowSettings {
+				adaptive_quality,
+				..self.window_settings
+			},
+			..self
+		}
+	}
+
+	/// Sets the live resize mode, see [`WindowSettings::live_resize`].
+	pub fn live_resize(self, live_resize: Option<LiveResize>) -> Self {
+		Self {
+			window_settings: WindowSettings {
+				live_resize,
+				..self.window_settings
+			},
+			..self
+		}
+	}
+
+	/// Sets whether to speak the announcements, see [`WindowSettings::speech_fallback`].
+	pub fn speech_fallback(self, speech_fallback: bool) -> Self {
+		Self {
+			window_settings: WindowSettings {
+				speech_fallback,
+				..self.window_settings
+			},
+			..self
+		}
+	}
+
 	/// Runs the manager.
 	/// 
 	/// # Panics
@@ -548,6 +1163,22 @@ where A: App<Signal = S>,
 	/// Panics if the window creation fails.
 	pub fn run(&mut self) {
 		let event_loop = winit::event_loop::EventLoop::new().expect("Failed to create event loop");
+		self.run_with(event_loop);
+	}
+
+	/// Runs the manager on Android, with the app passed to `android_main`, see [`super::mobile`].
+	/// 
+	/// # Panics
+	/// 
+	/// Panics if the window creation fails.
+	#[cfg(target_os = "android")]
+	pub fn run_android(&mut self, app: super::mobile::AndroidApp) {
+		use winit::platform::android::EventLoopBuilderExtAndroid;
+		let event_loop = winit::event_loop::EventLoop::builder().with_android_app(app).build().expect("Failed to create event loop");
+		self.run_with(event_loop);
+	}
+
+	fn run_with(&mut self, event_loop: winit::event_loop::EventLoop<()>) {
 		event_loop.set_control_flow(self.window_settings.control_flow);
 
 		let last_draw_time = OffsetDateTime::now_utc() - self.ctx.input_state.program_start_time;
@@ -0,0 +1,79 @@
+//! Support for Android and iOS.
+//! 
+//! On iOS, run the [`Manager`](super::manager::Manager) as usual.
+//! 
+//! On Android, enable the `android-native-activity` or `android-game-activity` feature,
+//! build the crate as a `cdylib` and start the manager from `android_main`:
+//! 
+//! ```ignore
+//! #[no_mangle]
+//! fn android_main(app: AndroidApp) {
+//!     Manager::new_with_default_font(MyApp::default()).run_android(app);
+//! }
+//! ```
+//! 
+//! The window always covers the whole screen on mobile platforms,
+//! use [`InputState::safe_area()`](super::input_state::InputState::safe_area) to keep the content away from notches and system bars.
+//! The clipboard is not supported yet.
+
+use winit::window::Window;
+
+use crate::math::rect::Rect;
+
+#[cfg(target_os = "android")]
+pub use winit::platform::android::activity::AndroidApp;
+
+/// Whether the target is a mobile platform, used to pick touch-first defaults.
+pub const MOBILE: bool = cfg!(any(target_os = "android", target_os = "ios"));
+
+/// Get the area of the window not covered by notches, rounded corners or system bars, in logical pixels.
+pub(crate) fn safe_area(window: &Window) -> Rect {
+	let scale_factor = window.scale_factor() as f32;
+	let physical = physical_safe_area(window);
+	Rect::new(physical.x / scale_factor, physical.y / scale_factor, physical.w / scale_factor, physical.h / scale_factor)
+}
+
+fn physical_safe_area(window: &Window) -> Rect {
+	#[cfg(target_os = "android")]
+	{
+		use winit::platform::android::WindowExtAndroid;
+		let rect = window.content_rect();
+		Rect::new(rect.left as f32, rect.top as f32, (rect.right - rect.left) as f32, (rect.bottom - rect.top) as f32)
+	}
+	#[cfg(target_os = "ios")]
+	{
+		// the inner area of the window is the safe area on iOS.
+		let size = window.inner_size();
+		let offset = match (window.inner_position(), window.outer_position()) {
+			(Ok(inner), Ok(outer)) => ((inner.x - outer.x) as f32, (inner.y - outer.y) as f32),
+			_ => (0.0, 0.0),
+		};
+		Rect::new(offset.0, offset.1, size.width as f32, size.height as f32)
+	}
+	#[cfg(not(any(target_os = "android", target_os = "ios")))]
+	{
+		let size = window.inner_size();
+		Rect::new(0.0, 0.0, size.width as f32, size.height as f32)
+	}
+}
+
+/// The clipboard is not supported on mobile platforms yet.
+#[cfg(any(target_os = "android", target_os = "ios"))]
+pub(crate) struct Clipboard;
+
+#[cfg(any(target_os = "android", target_os = "ios"))]
+impl Clipboard {
+	const UNSUPPORTED: &'static str = "clipboard is not supported on this platform";
+
+	pub(crate) fn new() -> Result<Self, &'static str> {
+		Err(Self::UNSUPPORTED)
+	}
+
+	pub(crate) fn set_text(&mut self, _: String) -> Result<(), &'static str> {
+		Err(Self::UNSUPPORTED)
+	}
+
+	pub(crate) fn get_text(&mut self) -> Result<String, &'static str> {
+		Err(Self::UNSUPPORTED)
+	}
+}
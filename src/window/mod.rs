@@ -1,6 +1,10 @@
 //! Here defines window event related things.
 
+pub mod embed;
 pub mod event;
+pub mod geometry;
 pub mod input_state;
 pub mod manager;
-pub mod prelude;
\ No newline at end of file
+pub mod mobile;
+pub mod prelude;
+pub mod shell;
\ No newline at end of file
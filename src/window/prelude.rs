@@ -1,5 +1,7 @@
 //! re-exported window related items here for convenience
 
 pub use crate::window::manager::*;
+pub use crate::window::embed::*;
 pub use crate::window::input_state::*;
-pub use crate::window::event::*;
\ No newline at end of file
+pub use crate::window::event::*;
+pub use crate::window::geometry::*;
\ No newline at end of file
@@ -0,0 +1,141 @@
+//! Integration with the shell of the operating system, such as the taskbar on Windows and the dock on macOS.
+//!
+//! Use [`crate::window::input_state::InputState::request_attention()`], [`crate::window::input_state::InputState::set_taskbar_progress()`],
+//! [`crate::window::input_state::InputState::set_badge_count()`] and [`crate::window::input_state::InputState::announce()`] to send requests,
+//! platforms without the corresponding feature ignore them.
+
+use winit::window::Window;
+
+use super::event::{AnnouncePriority, TaskbarProgress};
+
+/// Holds the platform handles needed to talk to the shell.
+#[derive(Default)]
+pub(crate) struct Shell {
+	#[cfg(target_os = "windows")]
+	taskbar: Option<windows::Win32::UI::Shell::ITaskbarList3>,
+	#[cfg(target_os = "windows")]
+	voice: Option<windows::Win32::Media::Speech::ISpVoice>,
+	/// the `spd-say` processes still speaking, reaped once they exit.
+	#[cfg(all(unix, not(any(target_os = "macos", target_os = "ios", target_os = "android"))))]
+	speaking: Vec<std::process::Child>,
+}
+
+impl Shell {
+	/// Set the progress shown on the taskbar button, only supported on Windows.
+	#[allow(unused_variables)]
+	pub(crate) fn set_progress(&mut self, window: &Window, progress: TaskbarProgress) {
+		#[cfg(target_os = "windows")]
+		{
+			use windows::Win32::{Foundation::HWND, System::Com::{CoCreateInstance, CLSCTX_INPROC_SERVER}, UI::Shell::*};
+			use winit::raw_window_handle::{HasWindowHandle, RawWindowHandle};
+
+			let Ok(handle) = window.window_handle() else { return };
+			let RawWindowHandle::Win32(handle) = handle.as_raw() else { return };
+			let hwnd = HWND(handle.hwnd.get() as *mut _);
+
+			if self.taskbar.is_none() {
+				// winit has initialized com on the event loop thread.
+				self.taskbar = unsafe { CoCreateInstance::<_, ITaskbarList3>(&TaskbarList, None, CLSCTX_INPROC_SERVER) }
+					.and_then(|taskbar| unsafe { taskbar.HrInit() }.map(|_| taskbar))
+					.inspect_err(|e| log::warn!("Failed to create taskbar list: {}", e))
+					.ok();
+			}
+			let Some(taskbar) = &self.taskbar else { return };
+
+			const TOTAL: u64 = 10000;
+			let (state, value) = match progress {
+				TaskbarProgress::None => (TBPF_NOPROGRESS, None),
+				TaskbarProgress::Indeterminate => (TBPF_INDETERMINATE, None),
+				TaskbarProgress::Normal(value) => (TBPF_NORMAL, Some(value)),
+				TaskbarProgress::Paused(value) => (TBPF_PAUSED, Some(value)),
+				TaskbarProgress::Error(value) => (TBPF_ERROR, Some(value)),
+			};
+			let result = unsafe { taskbar.SetProgressState(hwnd, state) }.and_then(|_| match value {
+				Some(value) => unsafe { taskbar.SetProgressValue(hwnd, (value.clamp(0.0, 1.0) * TOTAL as f32) as u64, TOTAL) },
+				None => Ok(()),
+			});
+			if let Err(e) = result {
+				log::warn!("Failed to set taskbar progress: {}", e);
+			}
+		}
+		#[cfg(not(target_os = "windows"))]
+		log::debug!("Taskbar progress is not supported on this platform");
+	}
+
+	/// Set the badge count shown on the dock icon, only supported on macOS.
+	pub(crate) fn set_badge(&mut self, count: Option<u32>) {
+		#[cfg(target_os = "macos")]
+		{
+			use objc2_app_kit::NSApplication;
+			use objc2_foundation::{MainThreadMarker, NSString};
+
+			// output events are handled on the event loop thread, which is the main thread on macOS.
+			let Some(mtm) = MainThreadMarker::new() else { return };
+			let label = count.map(|count| NSString::from_str(&count.to_string()));
+			unsafe { NSApplication::sharedApplication(mtm).dockTile().setBadgeLabel(label.as_deref()) };
+		}
+		#[cfg(not(target_os = "macos"))]
+		log::debug!("Badge count {:?} is not supported on this platform", count);
+	}
+
+	/// Announce the text to the screen reader, or speak it if `speech_fallback` is set and there is no announcement api.
+	#[allow(unused_variables)]
+	pub(crate) fn announce(&mut self, text: &str, priority: AnnouncePriority, speech_fallback: bool) {
+		#[cfg(target_os = "macos")]
+		{
+			use objc2::{rc::Retained, runtime::AnyObject};
+			use objc2_app_kit::{NSAccessibilityAnnouncementKey, NSAccessibilityAnnouncementRequestedNotification, NSAccessibilityPostNotificationWithUserInfo, NSAccessibilityPriorityKey, NSAccessibilityPriorityLevel, NSApplication};
+			use objc2_foundation::{MainThreadMarker, NSDictionary, NSNumber, NSString};
+
+			let Some(mtm) = MainThreadMarker::new() else { return };
+			let level = match priority {
+				AnnouncePriority::Polite => NSAccessibilityPriorityLevel::NSAccessibilityPriorityMedium,
+				AnnouncePriority::Assertive => NSAccessibilityPriorityLevel::NSAccessibilityPriorityHigh,
+			};
+			// SAFETY: every object is an `AnyObject`, and the keys are constants provided by AppKit.
+			unsafe {
+				let values = vec![
+					Retained::cast::<AnyObject>(NSString::from_str(text)),
+					Retained::cast::<AnyObject>(NSNumber::numberWithInteger(level.0)),
+				];
+				let user_info = NSDictionary::from_vec(&[NSAccessibilityAnnouncementKey, NSAccessibilityPriorityKey], values);
+				NSAccessibilityPostNotificationWithUserInfo(&NSApplication::sharedApplication(mtm), NSAccessibilityAnnouncementRequestedNotification, Some(&user_info));
+			}
+		}
+		#[cfg(target_os = "windows")]
+		if speech_fallback {
+			use windows::{core::HSTRING, Win32::{Media::Speech::*, System::Com::{CoCreateInstance, CLSCTX_ALL}}};
+
+			if self.voice.is_none() {
+				// winit has initialized com on the event loop thread.
+				self.voice = unsafe { CoCreateInstance::<_, ISpVoice>(&SpVoice, None, CLSCTX_ALL) }
+					.inspect_err(|e| log::warn!("Failed to create speech voice: {}", e))
+					.ok();
+			}
+			let Some(voice) = &self.voice else { return };
+			let mut flags = SPF_ASYNC.0 | SPF_IS_NOT_XML.0;
+			if priority == AnnouncePriority::Assertive {
+				flags |= SPF_PURGEBEFORESPEAK.0;
+			}
+			if let Err(e) = unsafe { voice.Speak(&HSTRING::from(text), flags as u32, None) } {
+				log::warn!("Failed to speak announcement: {}", e);
+			}
+		}
+		#[cfg(all(unix, not(any(target_os = "macos", target_os = "ios", target_os = "android"))))]
+		if speech_fallback {
+			self.speaking.retain_mut(|child| child.try_wait().is_ok_and(|status| status.is_none()));
+			let priority = match priority {
+				AnnouncePriority::Polite => "text",
+				AnnouncePriority::Assertive => "important",
+			};
+			match std::process::Command::new("spd-say").args(["--priority", priority, "--", text]).spawn() {
+				Ok(child) => self.speaking.push(child),
+				Err(e) => log::warn!("Failed to speak announcement with spd-say: {}", e),
+			}
+		}
+		#[cfg(not(target_os = "macos"))]
+		if !speech_fallback {
+			log::debug!("Announcement \"{}\" is dropped, screen reader announcements are not supported on this platform", text);
+		}
+	}
+}